@@ -65,6 +65,8 @@ mod ts_types {
         pub point: u8,
         /// Whether we're in the come-out phase
         pub is_come_out: bool,
+        /// Table phase counter, incremented by every roll applied to the table
+        pub phase_id: u32,
         /// The round ID when this epoch started
         pub epoch_start_round: u64,
         /// The SOL balance available as house bankroll
@@ -120,6 +122,12 @@ mod ts_types {
         pub total_won: u64,
         pub total_lost: u64,
         pub last_updated_round: u64,
+        /// The CrapsGame phase_id of the next roll this position has yet to settle
+        pub phase_id: u32,
+        /// Bitmask of active bet categories (BET_MASK_*)
+        pub active_bets: u32,
+        /// House reservations currently held by this position's bets
+        pub reserved_payouts: u64,
     }
 
     /// Payout ratio
@@ -41,6 +41,14 @@ pub const ONE_WEEK_SLOTS: u64 = 7 * ONE_DAY_SLOTS;
 /// The number of slots for breather between rounds.
 pub const INTERMISSION_SLOTS: u64 = 35;
 
+/// Default lamports paid to whoever closes an expired round, taken from its unclaimed
+/// SOL before the rest is vaulted. Tunable via `PARAM_ROUND_CLOSE_BOUNTY`.
+pub const ROUND_CLOSE_BOUNTY: u64 = 100_000;
+
+/// Default lamports paid to whoever resets a round, taken from undistributed treasury
+/// revenue. Tunable via `PARAM_ROUND_RESET_BOUNTY`.
+pub const ROUND_RESET_BOUNTY: u64 = 0;
+
 /// The maximum token supply (5 million).
 pub const MAX_SUPPLY: u64 = ONE_ORE * 5_000_000;
 
@@ -56,6 +64,15 @@ pub const CONFIG: &[u8] = b"config";
 /// The seed of the miner account PDA.
 pub const MINER: &[u8] = b"miner";
 
+/// The seed of the mining pool account PDA.
+pub const POOL: &[u8] = b"pool";
+
+/// The seed of the mining pool member account PDA.
+pub const POOL_MEMBER: &[u8] = b"pool_member";
+
+/// The seed of the session account PDA.
+pub const SESSION: &[u8] = b"session";
+
 /// The seed of the seeker account PDA.
 pub const SEEKER: &[u8] = b"seeker";
 
@@ -65,12 +82,46 @@ pub const SQUARE: &[u8] = b"square";
 /// The seed of the stake account PDA.
 pub const STAKE: &[u8] = b"stake";
 
+/// The seed of the stake receipt token mint PDA.
+pub const STAKE_RECEIPT_MINT: &[u8] = b"stake_receipt_mint";
+
 /// The seed of the round account PDA.
 pub const ROUND: &[u8] = b"round";
 
+/// The seed of the round archive account PDA.
+pub const ROUND_ARCHIVE: &[u8] = b"round_archive";
+
+/// The seed of the admin multisig account PDA.
+pub const ADMIN_MULTISIG: &[u8] = b"admin_multisig";
+
+/// The seed of the admin audit log PDA.
+pub const AUDIT_LOG: &[u8] = b"audit_log";
+
+/// The seed of the insurance fund PDA.
+pub const INSURANCE_FUND: &[u8] = b"insurance_fund";
+
+/// The seed of the roles account PDA.
+pub const ROLES: &[u8] = b"roles";
+
+/// The seed of the param account PDA.
+pub const PARAM: &[u8] = b"param";
+
+/// The seed of the vesting account PDA.
+pub const VESTING: &[u8] = b"vesting";
+
+/// The seed of the roll proof account PDA.
+pub const ROLL_PROOF: &[u8] = b"roll_proof";
+
+/// The number of roll proof accounts, about a week of rounds. Each round's proof is
+/// written to slot `round_id % ROLL_PROOF_SLOTS`, over the proof from that many rounds ago.
+pub const ROLL_PROOF_SLOTS: u64 = ONE_WEEK_SLOTS / (ONE_MINUTE_SLOTS + INTERMISSION_SLOTS);
+
 /// The seed of the treasury account PDA.
 pub const TREASURY: &[u8] = b"treasury";
 
+/// The seed of the protocol metrics account PDA.
+pub const METRICS: &[u8] = b"metrics";
+
 /// Program id for const pda derivations
 const PROGRAM_ID: [u8; 32] = unsafe { *(&crate::id() as *const Pubkey as *const [u8; 32]) };
 
@@ -110,6 +161,32 @@ pub const CRAP_MINT_ADDRESS: Pubkey = pubkey!("7frAenkamJSASBH9YukkzBsSMz9paQdYu
 #[cfg(not(feature = "devnet"))]
 pub const CRAP_MINT_ADDRESS: Pubkey = pubkey!("CRAPqnVVhpuFfWBJJbiZ3BtG1MrXF3cvD3mLSXpnPump");
 
+// Game tokens for the other tables share the CRAP mint until their own mints are created.
+
+/// The Baccarat token mint address.
+pub const CARAT_MINT_ADDRESS: Pubkey = CRAP_MINT_ADDRESS;
+
+/// The Blackjack token mint address.
+pub const BJ_MINT_ADDRESS: Pubkey = CRAP_MINT_ADDRESS;
+
+/// The Roulette token mint address.
+pub const ROUL_MINT_ADDRESS: Pubkey = CRAP_MINT_ADDRESS;
+
+/// The Casino War token mint address.
+pub const WAR_MINT_ADDRESS: Pubkey = CRAP_MINT_ADDRESS;
+
+/// The Sic Bo token mint address.
+pub const SICO_MINT_ADDRESS: Pubkey = CRAP_MINT_ADDRESS;
+
+/// The Three Card Poker token mint address.
+pub const TCP_MINT_ADDRESS: Pubkey = CRAP_MINT_ADDRESS;
+
+/// The Video Poker token mint address.
+pub const VPK_MINT_ADDRESS: Pubkey = CRAP_MINT_ADDRESS;
+
+/// The Ultimate Texas Hold'em token mint address.
+pub const UTH_MINT_ADDRESS: Pubkey = CRAP_MINT_ADDRESS;
+
 /// The decimal precision of the RNG token.
 pub const RNG_TOKEN_DECIMALS: u8 = 9;
 
@@ -125,6 +202,9 @@ pub const ONE_CRAP: u64 = 10u64.pow(CRAP_TOKEN_DECIMALS as u32);
 /// The maximum CRAP token supply (100 million for devnet testing).
 pub const MAX_CRAP_SUPPLY: u64 = ONE_CRAP * 100_000_000;
 
+/// The maximum supply of each other game token minted against RNG (same cap as CRAP).
+pub const MAX_GAME_TOKEN_SUPPLY: u64 = ONE_RNG * 100_000_000;
+
 /// The address to indicate ORE rewards are split between all miners.
 pub const SPLIT_ADDRESS: Pubkey = pubkey!("SpLiT11111111111111111111111111111111111112");
 
@@ -132,6 +212,10 @@ pub const SPLIT_ADDRESS: Pubkey = pubkey!("SpLiT11111111111111111111111111111111
 pub const TREASURY_ADDRESS: Pubkey =
     Pubkey::new_from_array(ed25519::derive_program_address(&[TREASURY], &PROGRAM_ID).0);
 
+/// The address of the protocol metrics account.
+pub const METRICS_ADDRESS: Pubkey =
+    Pubkey::new_from_array(ed25519::derive_program_address(&[METRICS], &PROGRAM_ID).0);
+
 /// The address of the treasury account.
 pub const TREASURY_BUMP: u8 = ed25519::derive_program_address(&[TREASURY], &PROGRAM_ID).1;
 
@@ -144,6 +228,16 @@ pub const BOOST_RESERVE_TOKEN: Pubkey = pubkey!("Gce36ZUsBDJsoLrfCBxUB5Sfq2DsGun
 /// The fee paid to bots if they checkpoint a user.
 pub const CHECKPOINT_FEE: u64 = 10_000; // 0.00001 SOL
 
+/// The default tip paid to bots for each SOL claim they push out.
+pub const CLAIM_CRANK_TIP_SOL: u64 = 10_000; // 0.00001 SOL
+
+/// The default tip paid to bots for each vested ORE release they push out.
+pub const CLAIM_CRANK_TIP_ORE: u64 = ONE_ORE / 100_000;
+
+/// Claim crank kinds.
+pub const CLAIM_CRANK_SOL: u8 = 0;
+pub const CLAIM_CRANK_VESTED: u8 = 1;
+
 /// The number of squares on the board (6x6 grid for dice combinations).
 pub const BOARD_SIZE: usize = 36;
 
@@ -157,9 +251,37 @@ pub const CRAPS_GAME: &[u8] = b"craps_game";
 /// The seed of the craps position account PDA.
 pub const CRAPS_POSITION: &[u8] = b"craps_position";
 
+/// The seed of the craps side bets account PDA.
+pub const CRAPS_SIDE_BETS: &[u8] = b"craps_side_bets";
+
 /// The seed of the craps vault token account PDA (holds CRAP tokens for the house).
 pub const CRAPS_VAULT: &[u8] = b"craps_vault";
 
+/// Default number of craps sub-rolls per round. Each round advances the craps table this
+/// many times, with sub-roll 0 being the winning square. Tunable via `PARAM_ROLLS_PER_ROUND`.
+pub const ROLLS_PER_ROUND: u8 = 1;
+
+/// The most craps sub-rolls a round can have (fast table mode).
+pub const MAX_ROLLS_PER_ROUND: u8 = 4;
+
+/// The seed of the test roll oracle account PDA (localnet/devnet only).
+pub const TEST_ROLL_ORACLE: &[u8] = b"test_roll_oracle";
+
+/// The seed of the faucet PDA, which holds the mint authority of the devnet RNG and CRAP mints.
+pub const FAUCET: &[u8] = b"faucet";
+
+/// The seed of a tester's faucet claim account PDA (devnet only).
+pub const FAUCET_CLAIM: &[u8] = b"faucet_claim";
+
+/// RNG minted by each faucet drip (devnet only).
+pub const FAUCET_RNG_AMOUNT: u64 = ONE_RNG * 1_000;
+
+/// CRAP minted by each faucet drip (devnet only).
+pub const FAUCET_CRAP_AMOUNT: u64 = ONE_CRAP * 1_000;
+
+/// Slots a tester waits between drips of the same token (~24 hours at 400ms/slot).
+pub const FAUCET_COOLDOWN_SLOTS: u64 = 216_000;
+
 /// Pass Line / Don't Pass payout ratio (1:1).
 pub const PASS_LINE_PAYOUT_NUM: u64 = 1;
 pub const PASS_LINE_PAYOUT_DEN: u64 = 1;
@@ -223,6 +345,12 @@ pub const HARD_6_8_PAYOUT_DEN: u64 = 1;
 /// Maximum single bet amount (100 SOL).
 pub const MAX_BET_AMOUNT: u64 = 100 * solana_program::native_token::LAMPORTS_PER_SOL;
 
+/// Maximum number of legs in a hedged bet bundle.
+pub const MAX_BUNDLE_LEGS: usize = 4;
+
+/// Maximum share of net house wins that can be routed to stakers (50%).
+pub const MAX_HOUSE_PROFIT_STAKER_SHARE_BPS: u64 = 5000;
+
 // ============================================================================
 // BONUS CRAPS SIDE BETS (Small, Tall, All)
 // ============================================================================
@@ -570,3 +698,97 @@ pub const NO_12_PAYOUT_DEN: u64 = 6;
 // ============================================================================
 // Next bet constants are the same as HOP_* constants above.
 // They pay at true odds for single-roll bets on specific dice sums.
+
+// ============================================================================
+// EXCHANGE CONSTANTS
+// ============================================================================
+
+/// The seed of the exchange pool account PDA.
+pub const EXCHANGE_POOL: &[u8] = b"exchange_pool";
+
+/// The seed of the exchange LP token mint PDA.
+pub const EXCHANGE_LP_MINT: &[u8] = b"exchange_lp_mint";
+
+/// The seed of the exchange SOL vault PDA.
+pub const EXCHANGE_SOL_VAULT: &[u8] = b"exchange_sol_vault";
+
+/// The seed of the exchange RNG vault PDA.
+pub const EXCHANGE_RNG_VAULT: &[u8] = b"exchange_rng_vault";
+
+/// The decimal precision of the exchange LP token.
+pub const LP_TOKEN_DECIMALS: u8 = 9;
+
+/// Default swap fee numerator (100 / 10000 = 1%).
+pub const EXCHANGE_DEFAULT_FEE_NUMERATOR: u64 = 100;
+
+/// Swap fee denominator.
+pub const EXCHANGE_DEFAULT_FEE_DENOMINATOR: u64 = 10_000;
+
+/// Default maximum swap size, as basis points of the input reserve (10%).
+pub const EXCHANGE_MAX_SWAP_BPS: u64 = 1_000;
+
+/// LP tokens locked forever when a pool is created, so it can never be fully drained.
+pub const EXCHANGE_MINIMUM_LIQUIDITY: u64 = 1_000;
+
+/// Minimum SOL (lamports) the admin must seed a new pool with (0.1 SOL).
+pub const EXCHANGE_MIN_INITIAL_SOL: u64 = solana_program::native_token::LAMPORTS_PER_SOL / 10;
+
+/// Minimum RNG the admin must seed a new pool with.
+pub const EXCHANGE_MIN_INITIAL_RNG: u64 = ONE_RNG;
+
+/// Pool status: swaps and deposits allowed.
+pub const EXCHANGE_STATUS_ACTIVE: u8 = 0;
+
+/// Pool status: swaps and deposits halted; liquidity can still be removed.
+pub const EXCHANGE_STATUS_PAUSED: u8 = 1;
+
+/// Pool status: permanently wound down; liquidity can still be removed.
+pub const EXCHANGE_STATUS_DEPRECATED: u8 = 2;
+
+// ============================================================================
+// GAME TOKEN POOL CONSTANTS
+// ============================================================================
+
+/// The seed of a game token pool account PDA (one per game token mint).
+pub const GAME_TOKEN_POOL: &[u8] = b"game_token_pool";
+
+/// The seed of a game token pool's RNG vault token account PDA.
+pub const GAME_TOKEN_POOL_RNG_VAULT: &[u8] = b"game_token_pool_rng_vault";
+
+/// The seed of a game token pool's game token vault token account PDA.
+pub const GAME_TOKEN_POOL_GAME_VAULT: &[u8] = b"game_token_pool_game_vault";
+
+// ============================================================================
+// LP STAKING CONSTANTS
+// ============================================================================
+
+/// The seed of an LP stake account PDA.
+pub const LP_STAKE: &[u8] = b"lp_stake";
+
+// ============================================================================
+// REFERRAL CONSTANTS
+// ============================================================================
+
+/// The seed of a swap referrer account PDA.
+pub const REFERRER: &[u8] = b"referrer";
+
+// ============================================================================
+// LIMIT ORDER CONSTANTS
+// ============================================================================
+
+/// The seed of a limit order account PDA.
+pub const LIMIT_ORDER: &[u8] = b"limit_order";
+
+// ============================================================================
+// DCA ORDER CONSTANTS
+// ============================================================================
+
+/// The seed of a DCA order account PDA.
+pub const DCA_ORDER: &[u8] = b"dca_order";
+
+// ============================================================================
+// LP LOCK CONSTANTS
+// ============================================================================
+
+/// The seed of an LP lock account PDA.
+pub const LP_LOCK: &[u8] = b"lp_lock";
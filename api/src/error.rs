@@ -1,10 +1,13 @@
 use steel::*;
 
-/// ORE program error codes
+/// ORE program error codes, returned as `ProgramError::Custom(code)`.
 /// Range 1000-1999: Game errors
 /// Range 2000-2999: Validation errors
 /// Range 3000-3999: System errors
-#[derive(Debug, Error, Clone, Copy, PartialEq, Eq, IntoPrimitive)]
+///
+/// Clients can map a code back to its variant with `OreError::try_from(code)`, and
+/// `OreError::ALL` lists every code with its message for generated docs.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 #[repr(u32)]
 pub enum OreError {
     // Game Errors (1000-1999)
@@ -29,6 +32,30 @@ pub enum OreError {
     #[error("No active bets to settle")]
     NoBetsToSettle = 1007,
 
+    #[error("House bankroll does not cover reserved payouts")]
+    ReservesUncovered = 1008,
+
+    #[error("Craps vault holds less than the house bankroll and escrow")]
+    VaultShortfall = 1009,
+
+    #[error("Round has not expired yet")]
+    RoundNotExpired = 1010,
+
+    #[error("Round has no recorded dice result")]
+    RollNotRecorded = 1011,
+
+    #[error("Winning square does not match the round's dice result")]
+    WinningSquareMismatch = 1012,
+
+    #[error("Position is from a different epoch")]
+    EpochMismatch = 1013,
+
+    #[error("No pending winnings to claim")]
+    NoPendingWinnings = 1014,
+
+    #[error("Position has rolls left to settle")]
+    RollsUnsettled = 1015,
+
     // Validation Errors (2000-2999)
     #[error("Invalid bet type specified")]
     InvalidBetType = 2001,
@@ -45,6 +72,30 @@ pub enum OreError {
     #[error("Invalid account provided")]
     InvalidAccount = 2005,
 
+    #[error("Swap output below minimum or input above maximum")]
+    SlippageExceeded = 2006,
+
+    #[error("Withdrawal is still in its unstake cooldown")]
+    WithdrawalLocked = 2007,
+
+    #[error("Pool has a deployment whose winnings are not yet harvested")]
+    PoolHarvestPending = 2008,
+
+    #[error("Merkle proof does not match the round archive")]
+    InvalidProof = 2009,
+
+    #[error("Claim is below the minimum claim amount")]
+    ClaimBelowMinimum = 2010,
+
+    #[error("This module is paused")]
+    ModulePaused = 2011,
+
+    #[error("Timelock has not expired or nothing is scheduled")]
+    TimelockNotExpired = 2012,
+
+    #[error("Faucet is cooling down for this account")]
+    FaucetCooldown = 2013,
+
     // System Errors (3000-3999)
     #[error("Arithmetic operation overflowed")]
     ArithmeticOverflow = 3001,
@@ -56,4 +107,64 @@ pub enum OreError {
     DeserializationFailed = 3003,
 }
 
+impl OreError {
+    /// Every error, in code order.
+    pub const ALL: &'static [OreError] = &[
+        OreError::AlreadySettled,
+        OreError::RoundNotActive,
+        OreError::RoundExpired,
+        OreError::InsufficientBankroll,
+        OreError::BetTooSmall,
+        OreError::BetTooLarge,
+        OreError::NoBetsToSettle,
+        OreError::ReservesUncovered,
+        OreError::VaultShortfall,
+        OreError::RoundNotExpired,
+        OreError::RollNotRecorded,
+        OreError::WinningSquareMismatch,
+        OreError::EpochMismatch,
+        OreError::NoPendingWinnings,
+        OreError::RollsUnsettled,
+        OreError::InvalidBetType,
+        OreError::InvalidBetAmount,
+        OreError::InvalidPoint,
+        OreError::InvalidAuthority,
+        OreError::InvalidAccount,
+        OreError::SlippageExceeded,
+        OreError::WithdrawalLocked,
+        OreError::PoolHarvestPending,
+        OreError::InvalidProof,
+        OreError::ClaimBelowMinimum,
+        OreError::ModulePaused,
+        OreError::TimelockNotExpired,
+        OreError::FaucetCooldown,
+        OreError::ArithmeticOverflow,
+        OreError::AccountNotFound,
+        OreError::DeserializationFailed,
+    ];
+
+    /// The custom error code returned on chain.
+    pub fn code(self) -> u32 {
+        self.into()
+    }
+}
+
 error!(OreError);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes_roundtrip() {
+        let mut last = 0;
+        for error in OreError::ALL {
+            // Listed in code order, so codes are unique.
+            assert!(error.code() > last);
+            last = error.code();
+            assert_eq!(OreError::try_from(error.code()), Ok(*error));
+            assert!(!error.to_string().is_empty());
+        }
+        assert!(OreError::try_from(0).is_err());
+    }
+}
@@ -1,10 +1,21 @@
 use serde::{Deserialize, Serialize};
 use steel::*;
 
+/// Event discriminators. Every event is a Pod struct whose first field is its
+/// discriminator, logged with `sol_log_data` (or `program_log`) so indexers can decode
+/// it from the `disc` alone.
 pub enum OreEvent {
     Reset = 0,
     Bury = 1,
     Deploy = 2,
+    BetPlaced = 3,
+    BetSettled = 4,
+    PointEstablished = 5,
+    SevenOut = 6,
+    DebtRecorded = 7,
+    AdminFeeSet = 8,
+    SwapExecuted = 9,
+    YieldClaimed = 10,
 }
 
 #[repr(C)]
@@ -58,8 +69,14 @@ pub struct ResetEvent {
     /// The sum of both dice (2-12).
     pub dice_sum: u8,
 
+    /// Whether the ORE reward was split pro-rata among all winners (1) or not (0).
+    pub split_reward: u8,
+
+    /// Percent of the ORE reward paid to the top miner if the reward was not split.
+    pub top_miner_share_pct: u8,
+
     /// Padding for alignment.
-    pub _event_padding: [u8; 5],
+    pub _event_padding: [u8; 3],
 }
 
 #[repr(C)]
@@ -93,10 +110,10 @@ pub struct DeployEvent {
     /// The authority of the deployer.
     pub authority: Pubkey,
 
-    /// The amount of SOL deployed per square.
+    /// The total amount of RNG deployed by this instruction.
     pub amount: u64,
 
-    /// The mask of the squares deployed to.
+    /// The mask of the squares the miner holds this round after the deploy.
     pub mask: u64,
 
     /// The round id.
@@ -106,6 +123,193 @@ pub struct DeployEvent {
     pub ts: i64,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct BetPlacedEvent {
+    /// The event discriminator.
+    pub disc: u64,
+
+    /// The authority of the craps position.
+    pub authority: Pubkey,
+
+    /// The amount wagered.
+    pub amount: u64,
+
+    /// The craps epoch the bet was placed in.
+    pub epoch_id: u64,
+
+    /// The timestamp of the event.
+    pub ts: i64,
+
+    /// The bet type (see CrapsBetType).
+    pub bet_type: u8,
+
+    /// The point or sum the bet targets (0 if not applicable).
+    pub point: u8,
+
+    /// Padding for alignment.
+    pub _event_padding: [u8; 6],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct BetSettledEvent {
+    /// The event discriminator.
+    pub disc: u64,
+
+    /// The authority of the craps position.
+    pub authority: Pubkey,
+
+    /// The round the roll belongs to.
+    pub round_id: u64,
+
+    /// The square (0-35) the position was settled against.
+    pub winning_square: u64,
+
+    /// The total returned to the position (stake plus winnings).
+    pub winnings: u64,
+
+    /// The total stake lost.
+    pub lost: u64,
+
+    /// The position's pending winnings after settlement.
+    pub pending_winnings: u64,
+
+    /// The timestamp of the event.
+    pub ts: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct PointEstablishedEvent {
+    /// The event discriminator.
+    pub disc: u64,
+
+    /// The round the point was rolled in.
+    pub round_id: u64,
+
+    /// The craps epoch (shooter) the point belongs to.
+    pub epoch_id: u64,
+
+    /// The timestamp of the event.
+    pub ts: i64,
+
+    /// The point (4, 5, 6, 8, 9, or 10).
+    pub point: u8,
+
+    /// Padding for alignment.
+    pub _event_padding: [u8; 7],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct SevenOutEvent {
+    /// The event discriminator.
+    pub disc: u64,
+
+    /// The round the seven was rolled in.
+    pub round_id: u64,
+
+    /// The epoch that ended.
+    pub epoch_id: u64,
+
+    /// The timestamp of the event.
+    pub ts: i64,
+
+    /// The point that was showing when the seven rolled.
+    pub point: u8,
+
+    /// Padding for alignment.
+    pub _event_padding: [u8; 7],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct DebtRecordedEvent {
+    /// The event discriminator.
+    pub disc: u64,
+
+    /// The authority of the craps position.
+    pub authority: Pubkey,
+
+    /// The amount the house could not pay out.
+    pub amount: u64,
+
+    /// The position's total unpaid debt after this settlement.
+    pub unpaid_debt: u64,
+
+    /// The timestamp of the event.
+    pub ts: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct AdminFeeSetEvent {
+    /// The event discriminator.
+    pub disc: u64,
+
+    /// The admin fee (bps) before the change.
+    pub old_admin_fee: u64,
+
+    /// The admin fee (bps) after the change.
+    pub new_admin_fee: u64,
+
+    /// The timestamp of the event.
+    pub ts: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct SwapExecutedEvent {
+    /// The event discriminator.
+    pub disc: u64,
+
+    /// The mint the treasury swapped from.
+    pub input_mint: Pubkey,
+
+    /// The mint the treasury swapped into.
+    pub output_mint: Pubkey,
+
+    /// The amount of the input token spent.
+    pub amount_in: u64,
+
+    /// The amount of the output token received.
+    pub amount_out: u64,
+
+    /// The timestamp of the event.
+    pub ts: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+pub struct YieldClaimedEvent {
+    /// The event discriminator.
+    pub disc: u64,
+
+    /// The authority of the stake account.
+    pub authority: Pubkey,
+
+    /// The amount of ORE claimed.
+    pub amount: u64,
+
+    /// The timestamp of the event.
+    pub ts: i64,
+
+    /// Whether the yield was restaked (1) or paid out (0).
+    pub restaked: u8,
+
+    /// Padding for alignment.
+    pub _event_padding: [u8; 7],
+}
+
 event!(ResetEvent);
 event!(BuryEvent);
 event!(DeployEvent);
+event!(BetPlacedEvent);
+event!(BetSettledEvent);
+event!(PointEstablishedEvent);
+event!(SevenOutEvent);
+event!(DebtRecordedEvent);
+event!(AdminFeeSetEvent);
+event!(SwapExecutedEvent);
+event!(YieldClaimedEvent);
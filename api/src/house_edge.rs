@@ -0,0 +1,144 @@
+//! Theoretical house edge of each craps bet, derived from the payout constants and the
+//! rules `SettleCraps` applies. Used for audits, docs, and odds panels in frontends.
+
+use crate::{consts::*, payout::payout_odds, state::point_to_index};
+
+const POINTS: [u8; 6] = [4, 5, 6, 8, 9, 10];
+
+/// Ways to roll `sum` with two dice, out of 36.
+fn ways(sum: u8) -> f64 {
+    match sum {
+        2..=12 => (6 - (sum as i8 - 7).abs()) as f64,
+        _ => 0.0,
+    }
+}
+
+/// Chance `sum` rolls before a 7.
+fn before_seven(sum: u8) -> f64 {
+    ways(sum) / (ways(sum) + ways(7))
+}
+
+/// Chance a 7 rolls before `sum`.
+fn seven_before(sum: u8) -> f64 {
+    1.0 - before_seven(sum)
+}
+
+/// Chance a Pass Line bet wins from the come-out roll.
+fn pass_line_win() -> f64 {
+    let naturals = (ways(7) + ways(11)) / 36.0;
+    let points_made: f64 = POINTS.iter().map(|&p| ways(p) / 36.0 * before_seven(p)).sum();
+    naturals + points_made
+}
+
+/// Chance a Don't Pass bet wins from the come-out roll. 12 is barred and pushes.
+fn dont_pass_win() -> f64 {
+    let craps = (ways(2) + ways(3)) / 36.0;
+    let seven_outs: f64 = POINTS.iter().map(|&p| ways(p) / 36.0 * seven_before(p)).sum();
+    craps + seven_outs
+}
+
+/// The house edge of a bet on `point`: the fraction of the stake the house expects to keep
+/// each time the bet resolves, with pushes counted as resolutions. Negative edges favor
+/// the player.
+///
+/// Returns `None` for bets that can't be placed on `point`, and for side bets, whose
+/// payouts depend on the whole sequence of a shooter's rolls.
+pub fn house_edge(bet_type: u8, point: u8) -> Option<f64> {
+    let (num, den) = payout_odds(bet_type, point)?;
+    // A win returns the stake plus the payout.
+    let pays = 1.0 + num as f64 / den as f64;
+    let (win, push) = match bet_type {
+        0 => (pass_line_win(), 0.0),
+        1 => (dont_pass_win(), ways(12) / 36.0),
+        // Odds, Place, and Yes bets win when their number rolls before a 7.
+        2 | 6 | 8 | 26 => (before_seven(point), 0.0),
+        3 | 7 | 27 => (seven_before(point), 0.0),
+        // Come bets are placed straight onto their point and settle there.
+        4 | 5 if point_to_index(point).is_none() => return None,
+        4 => (before_seven(point), 0.0),
+        5 => (seven_before(point), 0.0),
+        // Only one of the ways to roll a hardway is hard.
+        9 => (1.0 / (ways(point) + ways(7)), 0.0),
+        10 => {
+            let normal = 1.0 + FIELD_PAYOUT_NORMAL_NUM as f64 / FIELD_PAYOUT_NORMAL_DEN as f64;
+            let returned: f64 = [3, 4, 9, 10, 11].iter().map(|&s| ways(s) * normal).sum();
+            let returned = returned + (ways(2) + ways(12)) * pays;
+            return Some(1.0 - returned / 36.0);
+        }
+        11 => (ways(7) / 36.0, 0.0),
+        12 => ((ways(2) + ways(3) + ways(12)) / 36.0, 0.0),
+        13 => (ways(11) / 36.0, 0.0),
+        14 => (ways(2) / 36.0, 0.0),
+        15 => (ways(12) / 36.0, 0.0),
+        28 => (ways(point) / 36.0, 0.0),
+        _ => return None,
+    };
+    Some(1.0 - win * pays - push)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_house_edge_within_declared_ranges() {
+        // Edges in percent. Odds, Yes, No, and Hop bets pay true odds and carry no edge.
+        let declared: &[(u8, &[u8], f64, f64)] = &[
+            (0, &[0], 1.41, 1.42),
+            (1, &[0], 1.36, 1.37),
+            (2, &POINTS, 0.0, 0.0),
+            (3, &POINTS, 0.0, 0.0),
+            (6, &POINTS, 0.0, 0.0),
+            (7, &POINTS, 0.0, 0.0),
+            (8, &[4, 10], 6.66, 6.67),
+            (8, &[5, 9], 4.0, 4.0),
+            (8, &[6, 8], 1.51, 1.52),
+            (9, &[4, 10], 11.11, 11.12),
+            (9, &[6, 8], 9.09, 9.10),
+            (10, &[0], 5.55, 5.56),
+            (11, &[0], 16.66, 16.67),
+            (12, &[0], 11.11, 11.12),
+            (13, &[0], 11.11, 11.12),
+            (14, &[0], 13.88, 13.89),
+            (15, &[0], 13.88, 13.89),
+            (26, &[2, 3, 4, 5, 6, 8, 9, 10, 11, 12], 0.0, 0.0),
+            (27, &[2, 3, 4, 5, 6, 8, 9, 10, 11, 12], 0.0, 0.0),
+            (28, &[2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12], 0.0, 0.0),
+            // Come and Don't Come go straight onto a point at even money, so they price as
+            // put and lay bets. Laid this way, Don't Come favors the player.
+            (4, &[4, 10], 33.33, 33.34),
+            (4, &[5, 9], 20.0, 20.0),
+            (4, &[6, 8], 9.09, 9.10),
+            (5, &[4, 10], -33.34, -33.33),
+            (5, &[5, 9], -20.0, -20.0),
+            (5, &[6, 8], -9.10, -9.09),
+        ];
+
+        for &(bet_type, points, min, max) in declared {
+            for &point in points {
+                let edge = house_edge(bet_type, point).unwrap() * 100.0;
+                assert!(
+                    edge >= min - 1e-9 && edge <= max + 1e-9,
+                    "bet type {} on {} has a {:.4}% edge, declared {}%..{}%",
+                    bet_type,
+                    point,
+                    edge,
+                    min,
+                    max
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_house_edge_unpriced_bets() {
+        // Side bets depend on a shooter's whole sequence of rolls.
+        for bet_type in 16..=25 {
+            assert_eq!(house_edge(bet_type, 0), None);
+        }
+        assert_eq!(house_edge(4, 7), None);
+        assert_eq!(house_edge(8, 0), None);
+        assert_eq!(house_edge(26, 7), None);
+        assert_eq!(house_edge(29, 0), None);
+    }
+}
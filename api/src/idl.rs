@@ -0,0 +1,1234 @@
+//! Shank annotations for IDL generation.
+//!
+//! `OreInstruction` is a plain discriminator enum, so the accounts and args of each
+//! instruction are described here instead. Shank numbers instructions by position, so
+//! variants are kept in discriminator order with unused discriminators reserved.
+//! Regenerate `idl/ore.json` with `scripts/generate-idl.sh` after changing this file.
+
+use shank::ShankInstruction;
+
+use crate::instruction::*;
+
+#[derive(Clone, Copy, Debug, ShankInstruction)]
+pub enum OreInstructionIdl {
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "automation", desc = "The automation account")]
+    #[account(2, writable, name = "executor", desc = "The automation executor")]
+    #[account(3, writable, name = "miner", desc = "The miner account")]
+    #[account(4, name = "system_program", desc = "The system program")]
+    Automate(Automate),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "board", desc = "The board account")]
+    #[account(2, writable, name = "config", desc = "The config account")]
+    #[account(3, writable, name = "treasury", desc = "The treasury account")]
+    #[account(4, writable, name = "round", desc = "The round account")]
+    #[account(5, name = "system_program", desc = "The system program")]
+    #[account(6, writable, name = "audit_log", desc = "The admin audit log")]
+    Initialize(Initialize),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "board", desc = "The board account")]
+    #[account(2, writable, name = "miner", desc = "The miner account")]
+    #[account(3, writable, name = "round", desc = "The round account")]
+    #[account(4, writable, name = "treasury", desc = "The treasury account")]
+    #[account(5, name = "system_program", desc = "The system program")]
+    #[account(6, name = "config", desc = "The config account")]
+    #[account(7, optional, name = "stake", desc = "The stake account")]
+    #[account(8, writable, optional, name = "next_round", desc = "The next round account, to redeploy into")]
+    Checkpoint(Checkpoint),
+
+    /// Session delegates claiming on behalf of a miner pass the authority and its session.
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "miner", desc = "The miner account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    #[account(3, name = "config", desc = "The config account")]
+    #[account(4, writable, optional, name = "authority", desc = "The miner authority")]
+    #[account(5, optional, name = "session", desc = "The authority's session account")]
+    ClaimSOL(ClaimSOL),
+
+    /// Session delegates claiming on behalf of a miner pass the authority's session last.
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "miner", desc = "The miner account")]
+    #[account(2, writable, name = "mint", desc = "The ORE mint")]
+    #[account(3, writable, name = "recipient", desc = "The recipient token account")]
+    #[account(4, writable, name = "treasury", desc = "The treasury account")]
+    #[account(5, writable, name = "treasury_tokens", desc = "The treasury ORE token account")]
+    #[account(6, name = "system_program", desc = "The system program")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    #[account(8, name = "associated_token_program", desc = "The associated token program")]
+    #[account(9, name = "config", desc = "The config account")]
+    #[account(10, writable, name = "vesting", desc = "The vesting account")]
+    #[account(11, optional, name = "stake", desc = "The stake account")]
+    #[account(12, optional, name = "session", desc = "The authority's session account")]
+    ClaimORE(ClaimORE),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "board", desc = "The board account")]
+    #[account(2, writable, name = "rent_payer", desc = "The account that paid the rent")]
+    #[account(3, writable, name = "round", desc = "The round account")]
+    #[account(4, writable, name = "treasury", desc = "The treasury account")]
+    #[account(5, name = "system_program", desc = "The system program")]
+    #[account(6, name = "param", desc = "The param account")]
+    Close(Close),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "authority", desc = "The miner authority")]
+    #[account(2, writable, name = "automation", desc = "The automation account")]
+    #[account(3, writable, name = "board", desc = "The board account")]
+    #[account(4, writable, name = "miner", desc = "The miner account")]
+    #[account(5, writable, name = "round", desc = "The round account")]
+    #[account(6, name = "system_program", desc = "The system program")]
+    #[account(7, writable, name = "signer_rng_tokens", desc = "The signer RNG token account")]
+    #[account(8, writable, name = "round_rng_tokens", desc = "The round RNG token account")]
+    #[account(9, name = "rng_mint", desc = "The RNG mint")]
+    #[account(10, name = "token_program", desc = "The SPL token program")]
+    #[account(11, writable, name = "var", desc = "The entropy var account")]
+    #[account(12, name = "entropy_program", desc = "The entropy program")]
+    #[account(13, name = "config", desc = "The config account")]
+    #[account(14, optional, name = "previous_round", desc = "The previous round account")]
+    #[account(15, writable, optional, name = "session", desc = "The authority's session account, for a Delegated automation")]
+    Deploy(Deploy),
+
+    /// Unused discriminator.
+    Reserved7,
+
+    #[account(0, signer, name = "board", desc = "The board account")]
+    Log(Log),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "board", desc = "The board account")]
+    #[account(2, writable, name = "config", desc = "The config account")]
+    #[account(3, writable, name = "fee_collector", desc = "The fee collector")]
+    #[account(4, writable, name = "mint", desc = "The ORE mint")]
+    #[account(5, writable, name = "round", desc = "The round account")]
+    #[account(6, writable, name = "round_next", desc = "The next round account")]
+    #[account(7, writable, name = "top_miner", desc = "The top miner of the round")]
+    #[account(8, writable, name = "treasury", desc = "The treasury account")]
+    #[account(9, writable, name = "treasury_tokens", desc = "The treasury ORE token account")]
+    #[account(10, name = "system_program", desc = "The system program")]
+    #[account(11, name = "token_program", desc = "The SPL token program")]
+    #[account(12, name = "ore_program", desc = "The ORE program")]
+    #[account(13, name = "slot_hashes_sysvar", desc = "The slot hashes sysvar")]
+    #[account(14, writable, name = "roll_proof", desc = "The roll proof slot of the round")]
+    #[account(15, name = "roles", desc = "The roles account")]
+    #[account(16, name = "param", desc = "The param account")]
+    #[account(17, name = "rolls_param", desc = "The rolls per round param account")]
+    #[account(18, writable, name = "craps_game", desc = "The craps game account")]
+    #[account(19, writable, name = "var", desc = "The entropy var account")]
+    #[account(20, name = "entropy_program", desc = "The entropy program")]
+    #[account(21, optional, name = "test_roll_oracle", desc = "The test roll oracle")]
+    Reset(Reset),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, signer, name = "payer", desc = "The rent payer")]
+    #[account(2, writable, name = "mint", desc = "The ORE mint")]
+    #[account(3, writable, name = "sender", desc = "The sender token account")]
+    #[account(4, writable, name = "stake", desc = "The stake account")]
+    #[account(5, writable, name = "stake_tokens", desc = "The stake ORE token account")]
+    #[account(6, writable, name = "treasury", desc = "The treasury account")]
+    #[account(7, name = "system_program", desc = "The system program")]
+    #[account(8, name = "token_program", desc = "The SPL token program")]
+    #[account(9, name = "associated_token_program", desc = "The associated token program")]
+    #[account(10, name = "config", desc = "The config account")]
+    Deposit(Deposit),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "mint", desc = "The ORE mint")]
+    #[account(2, writable, name = "recipient", desc = "The recipient token account")]
+    #[account(3, writable, name = "stake", desc = "The stake account")]
+    #[account(4, writable, name = "stake_tokens", desc = "The stake ORE token account")]
+    #[account(5, writable, name = "treasury", desc = "The treasury account")]
+    #[account(6, name = "config", desc = "The config account")]
+    #[account(7, name = "system_program", desc = "The system program")]
+    #[account(8, name = "token_program", desc = "The SPL token program")]
+    #[account(9, name = "associated_token_program", desc = "The associated token program")]
+    Withdraw(Withdraw),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "mint", desc = "The ORE mint")]
+    #[account(2, writable, name = "recipient", desc = "The recipient token account")]
+    #[account(3, writable, name = "stake", desc = "The stake account")]
+    #[account(4, writable, name = "treasury", desc = "The treasury account")]
+    #[account(5, writable, name = "treasury_tokens", desc = "The treasury ORE token account")]
+    #[account(6, name = "system_program", desc = "The system program")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    #[account(8, name = "associated_token_program", desc = "The associated token program")]
+    #[account(9, name = "config", desc = "The config account")]
+    #[account(10, optional, name = "session", desc = "The authority's session account")]
+    ClaimYield(ClaimYield),
+
+    /// The swap program's accounts follow the listed accounts.
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "board", desc = "The board account")]
+    #[account(2, name = "config", desc = "The config account")]
+    #[account(3, writable, name = "mint", desc = "The ORE mint")]
+    #[account(4, writable, name = "treasury", desc = "The treasury account")]
+    #[account(5, writable, name = "treasury_ore", desc = "The treasury ORE token account")]
+    #[account(6, writable, name = "treasury_sol", desc = "The treasury wrapped SOL token account")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    #[account(8, name = "ore_program", desc = "The ORE program")]
+    Bury(Bury),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "config", desc = "The config account")]
+    #[account(2, writable, name = "treasury", desc = "The treasury account")]
+    #[account(3, writable, name = "treasury_sol", desc = "The treasury wrapped SOL token account")]
+    #[account(4, name = "system_program", desc = "The system program")]
+    Wrap(Wrap),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    #[account(3, writable, name = "audit_log", desc = "The admin audit log")]
+    SetAdmin(SetAdmin),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    #[account(3, writable, name = "audit_log", desc = "The admin audit log")]
+    SetFeeCollector(SetFeeCollector),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, name = "new_swap_program", desc = "The new swap program")]
+    #[account(3, writable, name = "audit_log", desc = "The admin audit log")]
+    SetSwapProgram(SetSwapProgram),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "board", desc = "The board account")]
+    #[account(2, writable, name = "config", desc = "The config account")]
+    #[account(3, writable, name = "new_var", desc = "The new entropy var account")]
+    #[account(4, writable, name = "audit_log", desc = "The admin audit log")]
+    SetVarAddress(SetVarAddress),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "board", desc = "The board account")]
+    #[account(2, writable, name = "config", desc = "The config account")]
+    #[account(3, writable, name = "provider", desc = "The entropy provider")]
+    #[account(4, writable, name = "var", desc = "The entropy var account")]
+    #[account(5, name = "system_program", desc = "The system program")]
+    #[account(6, name = "entropy_program", desc = "The entropy program")]
+    #[account(7, name = "roles", desc = "The roles account")]
+    NewVar(NewVar),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    #[account(3, writable, name = "audit_log", desc = "The admin audit log")]
+    SetAdminFee(SetAdminFee),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "automation", desc = "The automation account")]
+    #[account(2, writable, name = "miner", desc = "The miner account")]
+    #[account(3, name = "system_program", desc = "The system program")]
+    RecycleSOL(RecycleSOL),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "board", desc = "The board account")]
+    #[account(2, name = "config", desc = "The config account")]
+    #[account(3, writable, name = "round", desc = "The round account")]
+    #[account(4, name = "roles", desc = "The roles account")]
+    StartRound(StartRound),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "craps_game", desc = "The craps game account")]
+    #[account(2, writable, name = "craps_position", desc = "The craps position account")]
+    #[account(3, name = "craps_vault", desc = "The craps vault authority")]
+    #[account(4, writable, name = "signer_crap_tokens", desc = "The signer CRAP token account")]
+    #[account(5, writable, name = "vault_crap_tokens", desc = "The vault CRAP token account")]
+    #[account(6, name = "crap_mint", desc = "The CRAP mint")]
+    #[account(7, name = "board", desc = "The board account")]
+    #[account(8, name = "system_program", desc = "The system program")]
+    #[account(9, name = "token_program", desc = "The SPL token program")]
+    #[account(10, name = "associated_token_program", desc = "The associated token program")]
+    #[account(11, name = "config", desc = "The config account")]
+    #[account(12, writable, optional, name = "craps_side_bets", desc = "The craps side bets account")]
+    #[account(13, writable, optional, name = "metrics", desc = "The protocol metrics account")]
+    PlaceCrapsBet(PlaceCrapsBet),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "craps_game", desc = "The craps game account")]
+    #[account(2, writable, name = "craps_position", desc = "The craps position account")]
+    #[account(3, name = "round", desc = "The round account")]
+    #[account(4, writable, optional, name = "craps_side_bets", desc = "The craps side bets account")]
+    #[account(5, writable, optional, name = "metrics", desc = "The protocol metrics account")]
+    SettleCraps(SettleCraps),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "craps_game", desc = "The craps game account")]
+    #[account(2, writable, name = "craps_position", desc = "The craps position account")]
+    #[account(3, name = "craps_vault", desc = "The craps vault authority")]
+    #[account(4, writable, name = "vault_crap_tokens", desc = "The vault CRAP token account")]
+    #[account(5, writable, name = "signer_crap_tokens", desc = "The signer CRAP token account")]
+    #[account(6, name = "crap_mint", desc = "The CRAP mint")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    ClaimCrapsWinnings(ClaimCrapsWinnings),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "craps_game", desc = "The craps game account")]
+    #[account(2, name = "craps_vault", desc = "The craps vault authority")]
+    #[account(3, writable, name = "signer_crap_tokens", desc = "The signer CRAP token account")]
+    #[account(4, writable, name = "vault_crap_tokens", desc = "The vault CRAP token account")]
+    #[account(5, name = "crap_mint", desc = "The CRAP mint")]
+    #[account(6, name = "system_program", desc = "The system program")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    #[account(8, name = "associated_token_program", desc = "The associated token program")]
+    FundCrapsHouse(FundCrapsHouse),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "config", desc = "The config account")]
+    #[account(2, writable, name = "round", desc = "The round account")]
+    #[account(3, name = "system_program", desc = "The system program")]
+    MigrateRound(MigrateRound),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "miner", desc = "The miner account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    MigrateMiner(MigrateMiner),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "craps_game", desc = "The craps game account")]
+    #[account(2, writable, name = "craps_position", desc = "The craps position account")]
+    #[account(3, name = "round", desc = "The round account")]
+    #[account(4, name = "config", desc = "The config account")]
+    #[account(5, writable, optional, name = "craps_side_bets", desc = "The craps side bets account")]
+    #[account(6, writable, optional, name = "metrics", desc = "The protocol metrics account")]
+    ForceSettleCraps(ForceSettleCraps),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "craps_game", desc = "The craps game account")]
+    #[account(2, writable, name = "craps_position", desc = "The craps position account")]
+    #[account(3, name = "craps_vault", desc = "The craps vault authority")]
+    #[account(4, writable, name = "signer_crap_tokens", desc = "The signer CRAP token account")]
+    #[account(5, writable, name = "vault_crap_tokens", desc = "The vault CRAP token account")]
+    #[account(6, name = "token_program", desc = "The SPL token program")]
+    ClaimCrapsDebt(ClaimCrapsDebt),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "config", desc = "The config account")]
+    #[account(2, writable, name = "test_roll_oracle", desc = "The test roll oracle")]
+    #[account(3, name = "system_program", desc = "The system program")]
+    SetTestRoll(SetTestRoll),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "craps_game", desc = "The craps game account")]
+    #[account(2, writable, name = "craps_position", desc = "The craps position account")]
+    #[account(3, name = "round", desc = "The round account")]
+    #[account(4, writable, optional, name = "metrics", desc = "The protocol metrics account")]
+    SettleCrapsSingleRoll(SettleCrapsSingleRoll),
+
+    #[account(0, name = "craps_game", desc = "The craps game account")]
+    #[account(1, name = "craps_position", desc = "The craps position account")]
+    #[account(2, optional, name = "craps_side_bets", desc = "The craps side bets account")]
+    SimulateSettle(SimulateSettle),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "craps_position", desc = "The craps position account")]
+    #[account(2, writable, name = "craps_side_bets", desc = "The craps side bets account")]
+    #[account(3, name = "system_program", desc = "The system program")]
+    MigrateCrapsPosition(MigrateCrapsPosition),
+
+    /// The swap program's accounts follow the listed accounts.
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "config", desc = "The config account")]
+    #[account(2, writable, name = "treasury", desc = "The treasury account")]
+    #[account(3, writable, name = "treasury_input", desc = "The treasury input token account")]
+    #[account(4, writable, name = "treasury_output", desc = "The treasury output token account")]
+    #[account(5, name = "input_mint", desc = "The input mint")]
+    #[account(6, name = "output_mint", desc = "The output mint")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    #[account(8, name = "swap_program", desc = "The swap program")]
+    RouteSwap(RouteSwap),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "mint", desc = "The ORE mint")]
+    #[account(2, writable, name = "stake", desc = "The stake account")]
+    #[account(3, writable, name = "stake_tokens", desc = "The stake ORE token account")]
+    #[account(4, writable, name = "treasury", desc = "The treasury account")]
+    #[account(5, writable, name = "treasury_tokens", desc = "The treasury ORE token account")]
+    #[account(6, name = "token_program", desc = "The SPL token program")]
+    #[account(7, name = "config", desc = "The config account")]
+    #[account(8, optional, name = "session", desc = "The authority's session account")]
+    ClaimYieldAndRestake(ClaimYieldAndRestake),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    #[account(3, writable, name = "audit_log", desc = "The admin audit log")]
+    SetUnstakeCooldown(SetUnstakeCooldown),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "mint", desc = "The ORE mint")]
+    #[account(2, writable, name = "recipient", desc = "The recipient token account")]
+    #[account(3, writable, name = "stake", desc = "The stake account")]
+    #[account(4, writable, name = "stake_tokens", desc = "The stake ORE token account")]
+    #[account(5, name = "system_program", desc = "The system program")]
+    #[account(6, name = "token_program", desc = "The SPL token program")]
+    #[account(7, name = "associated_token_program", desc = "The associated token program")]
+    #[account(8, name = "config", desc = "The config account")]
+    ClaimWithdrawal(ClaimWithdrawal),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "stake", desc = "The stake account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    MigrateStake(MigrateStake),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "config", desc = "The config account")]
+    #[account(2, writable, name = "craps_game", desc = "The craps game account")]
+    #[account(3, name = "system_program", desc = "The system program")]
+    #[account(4, writable, name = "audit_log", desc = "The admin audit log")]
+    SetHouseProfitShare(SetHouseProfitShare),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "stake", desc = "The stake account")]
+    SetStakeOperator(SetStakeOperator),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "stake", desc = "The stake account")]
+    #[account(2, writable, name = "receipt_mint", desc = "The stake receipt mint")]
+    #[account(3, writable, name = "receipt_tokens", desc = "The stake receipt token account")]
+    #[account(4, name = "treasury", desc = "The treasury account")]
+    #[account(5, name = "system_program", desc = "The system program")]
+    #[account(6, name = "token_program", desc = "The SPL token program")]
+    #[account(7, name = "associated_token_program", desc = "The associated token program")]
+    #[account(8, name = "config", desc = "The config account")]
+    MintStakeReceipt(MintStakeReceipt),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "mint", desc = "The ORE mint")]
+    #[account(2, writable, name = "receipt_mint", desc = "The stake receipt mint")]
+    #[account(3, writable, name = "receipt_tokens", desc = "The stake receipt token account")]
+    #[account(4, writable, name = "source_stake", desc = "The stake the receipt was minted from")]
+    #[account(5, writable, name = "source_stake_tokens", desc = "The source stake ORE token account")]
+    #[account(6, writable, name = "stake", desc = "The stake account")]
+    #[account(7, writable, name = "stake_tokens", desc = "The stake ORE token account")]
+    #[account(8, writable, name = "treasury", desc = "The treasury account")]
+    #[account(9, name = "token_program", desc = "The SPL token program")]
+    #[account(10, name = "config", desc = "The config account")]
+    RedeemStakeReceipt(RedeemStakeReceipt),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "rng_mint", desc = "The RNG mint")]
+    #[account(2, writable, name = "sender", desc = "The sender token account")]
+    #[account(3, writable, name = "stake", desc = "The stake account")]
+    #[account(4, writable, name = "stake_rng_tokens", desc = "The stake RNG token account")]
+    #[account(5, writable, name = "treasury", desc = "The treasury account")]
+    #[account(6, name = "system_program", desc = "The system program")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    #[account(8, name = "associated_token_program", desc = "The associated token program")]
+    #[account(9, name = "config", desc = "The config account")]
+    DepositRng(DepositRng),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "rng_mint", desc = "The RNG mint")]
+    #[account(2, writable, name = "recipient", desc = "The recipient token account")]
+    #[account(3, writable, name = "stake", desc = "The stake account")]
+    #[account(4, writable, name = "stake_rng_tokens", desc = "The stake RNG token account")]
+    #[account(5, writable, name = "treasury", desc = "The treasury account")]
+    #[account(6, name = "system_program", desc = "The system program")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    #[account(8, name = "associated_token_program", desc = "The associated token program")]
+    #[account(9, name = "config", desc = "The config account")]
+    WithdrawRng(WithdrawRng),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "mint", desc = "The ORE mint")]
+    #[account(2, writable, name = "stake", desc = "The stake account")]
+    #[account(3, writable, name = "stake_tokens", desc = "The stake ORE token account")]
+    #[account(4, name = "treasury", desc = "The treasury account")]
+    #[account(5, name = "system_program", desc = "The system program")]
+    #[account(6, name = "token_program", desc = "The SPL token program")]
+    CloseStake(CloseStake),
+
+    /// The args are followed by up to 36 `DeployManyEntry` values.
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "board", desc = "The board account")]
+    #[account(2, writable, name = "miner", desc = "The miner account")]
+    #[account(3, writable, name = "round", desc = "The round account")]
+    #[account(4, name = "system_program", desc = "The system program")]
+    #[account(5, writable, name = "signer_rng_tokens", desc = "The signer RNG token account")]
+    #[account(6, writable, name = "round_rng_tokens", desc = "The round RNG token account")]
+    #[account(7, name = "rng_mint", desc = "The RNG mint")]
+    #[account(8, name = "token_program", desc = "The SPL token program")]
+    #[account(9, writable, name = "var", desc = "The entropy var account")]
+    #[account(10, name = "entropy_program", desc = "The entropy program")]
+    #[account(11, name = "config", desc = "The config account")]
+    DeployMany(DeployMany),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "board", desc = "The board account")]
+    #[account(2, writable, name = "miner", desc = "The miner account")]
+    #[account(3, writable, name = "round", desc = "The round account")]
+    #[account(4, name = "system_program", desc = "The system program")]
+    #[account(5, writable, name = "signer_rng_tokens", desc = "The signer RNG token account")]
+    #[account(6, writable, name = "round_rng_tokens", desc = "The round RNG token account")]
+    #[account(7, name = "rng_mint", desc = "The RNG mint")]
+    #[account(8, name = "token_program", desc = "The SPL token program")]
+    #[account(9, writable, name = "var", desc = "The entropy var account")]
+    #[account(10, name = "entropy_program", desc = "The entropy program")]
+    #[account(11, name = "config", desc = "The config account")]
+    DeploySpread(DeploySpread),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "miner", desc = "The miner account")]
+    SetAutoRedeploy(SetAutoRedeploy),
+
+    /// (miner, stake) account pairs follow the listed accounts.
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "board", desc = "The board account")]
+    #[account(2, writable, name = "round", desc = "The round account")]
+    #[account(3, writable, name = "treasury", desc = "The treasury account")]
+    #[account(4, name = "system_program", desc = "The system program")]
+    CheckpointBatch(CheckpointBatch),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "pool", desc = "The mining pool")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    OpenPool(OpenPool),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "pool", desc = "The mining pool")]
+    #[account(2, writable, name = "pool_member", desc = "The pool member account")]
+    #[account(3, writable, name = "signer_rng_tokens", desc = "The signer RNG token account")]
+    #[account(4, writable, name = "pool_rng_tokens", desc = "The pool RNG token account")]
+    #[account(5, name = "rng_mint", desc = "The RNG mint")]
+    #[account(6, name = "system_program", desc = "The system program")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    #[account(8, name = "associated_token_program", desc = "The associated token program")]
+    JoinPool(JoinPool),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "pool", desc = "The mining pool")]
+    #[account(2, writable, name = "pool_member", desc = "The pool member account")]
+    #[account(3, writable, name = "signer_rng_tokens", desc = "The signer RNG token account")]
+    #[account(4, writable, name = "pool_rng_tokens", desc = "The pool RNG token account")]
+    #[account(5, name = "rng_mint", desc = "The RNG mint")]
+    #[account(6, name = "system_program", desc = "The system program")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    #[account(8, name = "associated_token_program", desc = "The associated token program")]
+    LeavePool(LeavePool),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "pool", desc = "The mining pool")]
+    #[account(2, writable, name = "pool_member", desc = "The pool member account")]
+    #[account(3, name = "mint", desc = "The ORE mint")]
+    #[account(4, writable, name = "recipient", desc = "The recipient token account")]
+    #[account(5, writable, name = "pool_tokens", desc = "The pool ORE token account")]
+    #[account(6, name = "system_program", desc = "The system program")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    #[account(8, name = "associated_token_program", desc = "The associated token program")]
+    ClaimPoolRewards(ClaimPoolRewards),
+
+    /// The args are followed by up to 36 `DeployManyEntry` values.
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "pool", desc = "The mining pool")]
+    #[account(2, writable, name = "board", desc = "The board account")]
+    #[account(3, writable, name = "miner", desc = "The miner account")]
+    #[account(4, writable, name = "round", desc = "The round account")]
+    #[account(5, name = "system_program", desc = "The system program")]
+    #[account(6, writable, name = "pool_rng_tokens", desc = "The pool RNG token account")]
+    #[account(7, writable, name = "round_rng_tokens", desc = "The round RNG token account")]
+    #[account(8, name = "rng_mint", desc = "The RNG mint")]
+    #[account(9, name = "token_program", desc = "The SPL token program")]
+    #[account(10, writable, name = "var", desc = "The entropy var account")]
+    #[account(11, name = "entropy_program", desc = "The entropy program")]
+    #[account(12, name = "config", desc = "The config account")]
+    PoolDeploy(PoolDeploy),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "pool", desc = "The mining pool")]
+    #[account(2, writable, name = "miner", desc = "The miner account")]
+    #[account(3, name = "mint", desc = "The ORE mint")]
+    #[account(4, writable, name = "pool_tokens", desc = "The pool ORE token account")]
+    #[account(5, writable, name = "treasury", desc = "The treasury account")]
+    #[account(6, writable, name = "treasury_tokens", desc = "The treasury ORE token account")]
+    #[account(7, name = "system_program", desc = "The system program")]
+    #[account(8, name = "token_program", desc = "The SPL token program")]
+    #[account(9, name = "associated_token_program", desc = "The associated token program")]
+    HarvestPool(HarvestPool),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    #[account(3, writable, name = "audit_log", desc = "The admin audit log")]
+    SetMotherlodeParams(SetMotherlodeParams),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    #[account(3, writable, name = "audit_log", desc = "The admin audit log")]
+    SetRewardSplitPolicy(SetRewardSplitPolicy),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "board", desc = "The board account")]
+    #[account(2, writable, name = "round", desc = "The round account")]
+    #[account(3, writable, name = "round_archive", desc = "The round archive account")]
+    #[account(4, writable, name = "rent_payer", desc = "The account that paid the rent")]
+    #[account(5, name = "system_program", desc = "The system program")]
+    ArchiveRound(ArchiveRound),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "board", desc = "The board account")]
+    #[account(2, writable, name = "miner", desc = "The miner account")]
+    #[account(3, name = "round", desc = "The round account")]
+    #[account(4, writable, name = "round_archive", desc = "The round archive account")]
+    #[account(5, writable, name = "treasury", desc = "The treasury account")]
+    #[account(6, name = "system_program", desc = "The system program")]
+    #[account(7, optional, name = "stake", desc = "The stake account")]
+    CheckpointArchived(CheckpointArchived),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    #[account(3, writable, name = "audit_log", desc = "The admin audit log")]
+    SetVestingPolicy(SetVestingPolicy),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "vesting", desc = "The vesting account")]
+    #[account(2, writable, name = "mint", desc = "The ORE mint")]
+    #[account(3, writable, name = "recipient", desc = "The recipient token account")]
+    #[account(4, name = "treasury", desc = "The treasury account")]
+    #[account(5, writable, name = "treasury_tokens", desc = "The treasury ORE token account")]
+    #[account(6, name = "system_program", desc = "The system program")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    #[account(8, name = "associated_token_program", desc = "The associated token program")]
+    #[account(9, name = "config", desc = "The config account")]
+    ClaimVested(ClaimVested),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    #[account(3, writable, name = "audit_log", desc = "The admin audit log")]
+    SetClaimMinimums(SetClaimMinimums),
+
+    /// Inactive miner accounts to sweep follow the listed accounts.
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "board", desc = "The board account")]
+    #[account(2, name = "config", desc = "The config account")]
+    #[account(3, writable, name = "treasury", desc = "The treasury account")]
+    SweepDust(SweepDust),
+
+    /// Per-claim accounts follow the listed accounts; see `crank_sol_claims` and `crank_vested_claims`.
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "config", desc = "The config account")]
+    #[account(2, name = "param", desc = "The param account")]
+    CrankClaims(CrankClaims),
+
+    /// Approver signers follow the listed accounts.
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "config", desc = "The config account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    #[account(3, writable, name = "audit_log", desc = "The admin audit log")]
+    #[account(4, writable, name = "admin_multisig", desc = "The admin multisig account")]
+    SetAdminMultisig(SetAdminMultisig),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, writable, name = "audit_log", desc = "The admin audit log")]
+    AcceptAdmin(AcceptAdmin),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    #[account(3, name = "roles", desc = "The roles account")]
+    #[account(4, writable, name = "audit_log", desc = "The admin audit log")]
+    Pause(Pause),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, writable, name = "audit_log", desc = "The admin audit log")]
+    ScheduleUnpause(ScheduleUnpause),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    ApplyUnpause(ApplyUnpause),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    #[account(3, writable, name = "audit_log", desc = "The admin audit log")]
+    SetGuardian(SetGuardian),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    ApplyGuardian(ApplyGuardian),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, writable, name = "treasury", desc = "The treasury account")]
+    #[account(3, name = "system_program", desc = "The system program")]
+    #[account(4, writable, name = "audit_log", desc = "The admin audit log")]
+    MigrateConfig(MigrateConfig),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "config", desc = "The config account")]
+    #[account(2, writable, name = "param", desc = "The param account")]
+    #[account(3, name = "system_program", desc = "The system program")]
+    #[account(4, writable, name = "audit_log", desc = "The admin audit log")]
+    SetParam(SetParam),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, name = "craps_game", desc = "The craps game account")]
+    #[account(3, writable, name = "audit_log", desc = "The admin audit log")]
+    ScheduleHouseWithdrawal(ScheduleHouseWithdrawal),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, writable, name = "craps_game", desc = "The craps game account")]
+    #[account(3, name = "craps_vault", desc = "The craps vault authority")]
+    #[account(4, writable, name = "vault_crap_tokens", desc = "The vault CRAP token account")]
+    #[account(5, writable, name = "recipient_crap_tokens", desc = "The recipient CRAP token account")]
+    #[account(6, name = "token_program", desc = "The SPL token program")]
+    #[account(7, writable, name = "audit_log", desc = "The admin audit log")]
+    WithdrawHouseBankroll(WithdrawHouseBankroll),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    #[account(3, writable, name = "audit_log", desc = "The admin audit log")]
+    SetRevenuePolicy(SetRevenuePolicy),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "config", desc = "The config account")]
+    #[account(2, writable, name = "treasury", desc = "The treasury account")]
+    #[account(3, writable, name = "insurance_fund", desc = "The insurance fund")]
+    #[account(4, name = "system_program", desc = "The system program")]
+    DistributeRevenue(DistributeRevenue),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "config", desc = "The config account")]
+    #[account(2, writable, name = "roles", desc = "The roles account")]
+    #[account(3, name = "system_program", desc = "The system program")]
+    #[account(4, writable, name = "audit_log", desc = "The admin audit log")]
+    SetRole(SetRole),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "config", desc = "The config account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    #[account(3, writable, name = "audit_log", desc = "The admin audit log")]
+    SetRoundPermission(SetRoundPermission),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "account", desc = "The account to migrate")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    MigrateAccount(MigrateAccount),
+
+    #[account(0, name = "craps_game", desc = "The craps game account")]
+    #[account(1, name = "vault_crap_tokens", desc = "The vault CRAP token account")]
+    AssertSolvency(AssertSolvency),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "board", desc = "The board account")]
+    #[account(2, writable, name = "config", desc = "The config account")]
+    #[account(3, writable, name = "treasury", desc = "The treasury account")]
+    #[account(4, writable, name = "round", desc = "The round account")]
+    #[account(5, name = "system_program", desc = "The system program")]
+    #[account(6, writable, name = "audit_log", desc = "The admin audit log")]
+    #[account(7, writable, name = "craps_game", desc = "The craps game account")]
+    #[account(8, name = "craps_vault", desc = "The craps vault authority")]
+    #[account(9, writable, name = "vault_crap_tokens", desc = "The vault CRAP token account")]
+    #[account(10, writable, name = "crap_mint", desc = "The CRAP mint")]
+    #[account(11, writable, name = "treasury_tokens", desc = "The treasury ORE token account")]
+    #[account(12, name = "mint", desc = "The ORE mint")]
+    #[account(13, name = "token_program", desc = "The SPL token program")]
+    #[account(14, name = "associated_token_program", desc = "The associated token program")]
+    Bootstrap(Bootstrap),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "faucet_claim", desc = "The signer's faucet claim account")]
+    #[account(2, name = "faucet", desc = "The faucet mint authority")]
+    #[account(3, writable, name = "rng_mint", desc = "The RNG mint")]
+    #[account(4, writable, name = "signer_rng_tokens", desc = "The signer RNG token account")]
+    #[account(5, name = "system_program", desc = "The system program")]
+    #[account(6, name = "token_program", desc = "The SPL token program")]
+    #[account(7, name = "associated_token_program", desc = "The associated token program")]
+    FaucetRng(FaucetRng),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "faucet_claim", desc = "The signer's faucet claim account")]
+    #[account(2, name = "faucet", desc = "The faucet mint authority")]
+    #[account(3, writable, name = "crap_mint", desc = "The CRAP mint")]
+    #[account(4, writable, name = "signer_crap_tokens", desc = "The signer CRAP token account")]
+    #[account(5, name = "system_program", desc = "The system program")]
+    #[account(6, name = "token_program", desc = "The SPL token program")]
+    #[account(7, name = "associated_token_program", desc = "The associated token program")]
+    FaucetCrap(FaucetCrap),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "metrics", desc = "The protocol metrics account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    OpenMetrics(OpenMetrics),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "craps_game", desc = "The craps game account")]
+    #[account(2, writable, name = "craps_position", desc = "The craps position account")]
+    #[account(3, name = "craps_vault", desc = "The craps vault authority")]
+    #[account(4, writable, name = "signer_crap_tokens", desc = "The signer CRAP token account")]
+    #[account(5, writable, name = "vault_crap_tokens", desc = "The vault CRAP token account")]
+    #[account(6, name = "crap_mint", desc = "The CRAP mint")]
+    #[account(7, name = "board", desc = "The board account")]
+    #[account(8, name = "system_program", desc = "The system program")]
+    #[account(9, name = "token_program", desc = "The SPL token program")]
+    #[account(10, name = "associated_token_program", desc = "The associated token program")]
+    #[account(11, name = "config", desc = "The config account")]
+    #[account(12, writable, optional, name = "craps_side_bets", desc = "The craps side bets account")]
+    #[account(13, writable, optional, name = "metrics", desc = "The protocol metrics account")]
+    PlaceCrapsBundle(PlaceCrapsBundle),
+
+    /// The swap program's accounts follow the listed accounts.
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "board", desc = "The board account")]
+    #[account(2, writable, name = "miner", desc = "The miner account")]
+    #[account(3, writable, name = "round", desc = "The round account")]
+    #[account(4, name = "system_program", desc = "The system program")]
+    #[account(5, writable, name = "signer_rng_tokens", desc = "The signer RNG token account")]
+    #[account(6, writable, name = "round_rng_tokens", desc = "The round RNG token account")]
+    #[account(7, name = "rng_mint", desc = "The RNG mint")]
+    #[account(8, name = "token_program", desc = "The SPL token program")]
+    #[account(9, writable, name = "var", desc = "The entropy var account")]
+    #[account(10, name = "entropy_program", desc = "The entropy program")]
+    #[account(11, name = "config", desc = "The config account")]
+    #[account(12, name = "swap_program", desc = "The swap program")]
+    DeployWithSol(DeployWithSol),
+
+    /// The swap program's accounts follow the listed accounts.
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "mint", desc = "The ORE mint")]
+    #[account(2, writable, name = "recipient", desc = "The signer ORE token account")]
+    #[account(3, writable, name = "stake", desc = "The stake account")]
+    #[account(4, writable, name = "treasury", desc = "The treasury account")]
+    #[account(5, writable, name = "treasury_tokens", desc = "The treasury ORE token account")]
+    #[account(6, name = "token_program", desc = "The SPL token program")]
+    #[account(7, name = "config", desc = "The config account")]
+    #[account(8, name = "swap_program", desc = "The swap program")]
+    ClaimYieldAsSol(ClaimYieldAsSol),
+
+    /// The swap program's accounts follow the listed accounts.
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, name = "config", desc = "The config account")]
+    #[account(2, writable, name = "craps_game", desc = "The craps game account")]
+    #[account(3, name = "craps_vault", desc = "The craps vault authority")]
+    #[account(4, writable, name = "vault_crap_tokens", desc = "The vault CRAP token account")]
+    #[account(5, writable, name = "treasury", desc = "The treasury account")]
+    #[account(6, writable, name = "treasury_crap_tokens", desc = "The treasury CRAP token account")]
+    #[account(7, writable, name = "treasury_tokens", desc = "The treasury ORE token account")]
+    #[account(8, name = "crap_mint", desc = "The CRAP mint")]
+    #[account(9, name = "mint", desc = "The ORE mint")]
+    #[account(10, name = "token_program", desc = "The SPL token program")]
+    #[account(11, name = "swap_program", desc = "The swap program")]
+    DistributeHouseProfit(DistributeHouseProfit),
+
+    #[account(0, signer, name = "authority", desc = "The session authority")]
+    #[account(1, writable, signer, name = "payer", desc = "The rent payer")]
+    #[account(2, writable, name = "session", desc = "The session account")]
+    #[account(3, name = "system_program", desc = "The system program")]
+    CreateSession(CreateSession),
+
+    #[account(0, signer, name = "authority", desc = "The session authority")]
+    #[account(1, writable, name = "session", desc = "The session account")]
+    #[account(2, writable, name = "payer", desc = "The rent refund recipient")]
+    RevokeSession(RevokeSession),
+
+    #[account(0, writable, signer, name = "admin", desc = "The pool admin")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "lp_mint", desc = "The pool LP mint")]
+    #[account(3, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(4, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(5, writable, name = "admin_rng_tokens", desc = "The admin RNG token account")]
+    #[account(6, writable, name = "admin_lp_tokens", desc = "The admin LP token account")]
+    #[account(7, name = "rng_mint", desc = "The RNG mint")]
+    #[account(8, name = "sol_mint", desc = "The wrapped SOL mint")]
+    #[account(9, name = "system_program", desc = "The system program")]
+    #[account(10, name = "token_program", desc = "The SPL token program")]
+    #[account(11, name = "associated_token_program", desc = "The SPL associated token program")]
+    #[account(12, name = "rent", desc = "The rent sysvar")]
+    InitializeExchangePool(InitializeExchangePool),
+
+    #[account(0, writable, signer, name = "provider", desc = "The liquidity provider")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "lp_mint", desc = "The pool LP mint")]
+    #[account(3, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(4, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(5, writable, name = "provider_sol_tokens", desc = "The provider wSOL token account")]
+    #[account(6, writable, name = "provider_rng_tokens", desc = "The provider RNG token account")]
+    #[account(7, writable, name = "provider_lp_tokens", desc = "The provider LP token account")]
+    #[account(8, name = "rng_mint", desc = "The RNG mint")]
+    #[account(9, name = "sol_mint", desc = "The wrapped SOL mint")]
+    #[account(10, name = "system_program", desc = "The system program")]
+    #[account(11, name = "token_program", desc = "The SPL token program")]
+    #[account(12, name = "associated_token_program", desc = "The SPL associated token program")]
+    AddLiquidity(AddLiquidity),
+
+    #[account(0, writable, signer, name = "provider", desc = "The liquidity provider")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "lp_mint", desc = "The pool LP mint")]
+    #[account(3, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(4, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(5, writable, name = "provider_sol_tokens", desc = "The provider wSOL token account")]
+    #[account(6, writable, name = "provider_rng_tokens", desc = "The provider RNG token account")]
+    #[account(7, writable, name = "provider_lp_tokens", desc = "The provider LP token account")]
+    #[account(8, name = "rng_mint", desc = "The RNG mint")]
+    #[account(9, name = "sol_mint", desc = "The wrapped SOL mint")]
+    #[account(10, name = "system_program", desc = "The system program")]
+    #[account(11, name = "token_program", desc = "The SPL token program")]
+    RemoveLiquidity(RemoveLiquidity),
+
+    #[account(0, writable, signer, name = "user", desc = "The swapper")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(3, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(4, writable, name = "user_rng_tokens", desc = "The user RNG token account")]
+    #[account(5, name = "rng_mint", desc = "The RNG mint")]
+    #[account(6, name = "sol_mint", desc = "The wrapped SOL mint")]
+    #[account(7, name = "system_program", desc = "The system program")]
+    #[account(8, name = "token_program", desc = "The SPL token program")]
+    #[account(9, writable, optional, name = "referrer", desc = "The registered referrer account")]
+    SwapSolToRng(SwapSolToRng),
+
+    #[account(0, writable, signer, name = "user", desc = "The swapper")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(3, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(4, writable, name = "user_sol_tokens", desc = "The user wSOL token account")]
+    #[account(5, writable, name = "user_rng_tokens", desc = "The user RNG token account")]
+    #[account(6, name = "rng_mint", desc = "The RNG mint")]
+    #[account(7, name = "sol_mint", desc = "The wrapped SOL mint")]
+    #[account(8, name = "system_program", desc = "The system program")]
+    #[account(9, name = "token_program", desc = "The SPL token program")]
+    #[account(10, writable, optional, name = "referrer", desc = "The registered referrer account")]
+    SwapRngToSol(SwapRngToSol),
+
+    #[account(0, writable, signer, name = "user", desc = "The swapper")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(3, writable, name = "user_rng_tokens", desc = "The user RNG token account")]
+    #[account(4, writable, name = "user_game_tokens", desc = "The user game token account")]
+    #[account(5, writable, name = "game_mint", desc = "The game token mint")]
+    #[account(6, name = "rng_mint", desc = "The RNG mint")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    SwapRngToGameToken(SwapRngToGameToken),
+
+    #[account(0, writable, signer, name = "user", desc = "The swapper")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(3, writable, name = "user_rng_tokens", desc = "The user RNG token account")]
+    #[account(4, writable, name = "user_game_tokens", desc = "The user game token account")]
+    #[account(5, writable, name = "game_mint", desc = "The game token mint")]
+    #[account(6, name = "rng_mint", desc = "The RNG mint")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    SwapGameTokenToRng(SwapGameTokenToRng),
+
+    #[account(0, writable, signer, name = "admin", desc = "The pool admin")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(3, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(4, writable, name = "admin_sol_tokens", desc = "The admin wSOL token account")]
+    #[account(5, writable, name = "admin_rng_tokens", desc = "The admin RNG token account")]
+    #[account(6, name = "rng_mint", desc = "The RNG mint")]
+    #[account(7, name = "sol_mint", desc = "The wrapped SOL mint")]
+    #[account(8, name = "token_program", desc = "The SPL token program")]
+    ClaimProtocolFees(ClaimProtocolFees),
+
+    /// Returns a SwapQuote as return data.
+    #[account(0, name = "exchange_pool", desc = "The exchange pool account")]
+    QuoteSwap(QuoteSwap),
+
+    #[account(0, writable, signer, name = "user", desc = "The swapper")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(3, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(4, writable, name = "user_game_tokens", desc = "The user game token account")]
+    #[account(5, writable, name = "game_mint", desc = "The game token mint")]
+    #[account(6, name = "rng_mint", desc = "The RNG mint")]
+    #[account(7, name = "sol_mint", desc = "The wrapped SOL mint")]
+    #[account(8, name = "system_program", desc = "The system program")]
+    #[account(9, name = "token_program", desc = "The SPL token program")]
+    SwapSolToGameToken(SwapSolToGameToken),
+
+    #[account(0, writable, signer, name = "user", desc = "The swapper")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(3, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(4, writable, name = "user_rng_tokens", desc = "The user RNG token account")]
+    #[account(5, name = "rng_mint", desc = "The RNG mint")]
+    #[account(6, name = "sol_mint", desc = "The wrapped SOL mint")]
+    #[account(7, name = "system_program", desc = "The system program")]
+    #[account(8, name = "token_program", desc = "The SPL token program")]
+    #[account(9, writable, optional, name = "referrer", desc = "The registered referrer account")]
+    SwapSolToRngExactOut(SwapSolToRngExactOut),
+
+    #[account(0, writable, signer, name = "user", desc = "The swapper")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(3, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(4, writable, name = "user_sol_tokens", desc = "The user wSOL token account")]
+    #[account(5, writable, name = "user_rng_tokens", desc = "The user RNG token account")]
+    #[account(6, name = "rng_mint", desc = "The RNG mint")]
+    #[account(7, name = "sol_mint", desc = "The wrapped SOL mint")]
+    #[account(8, name = "system_program", desc = "The system program")]
+    #[account(9, name = "token_program", desc = "The SPL token program")]
+    #[account(10, writable, optional, name = "referrer", desc = "The registered referrer account")]
+    SwapRngToSolExactOut(SwapRngToSolExactOut),
+
+    #[account(0, writable, signer, name = "signer", desc = "The pool admin, or the guardian to pause")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    SetPoolStatus(SetPoolStatus),
+
+    #[account(0, writable, signer, name = "admin", desc = "The pool admin")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    SetPoolGuardian(SetPoolGuardian),
+
+    #[account(0, writable, signer, name = "admin", desc = "The pool admin")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    SetPoolFees(SetPoolFees),
+
+    #[account(0, writable, signer, name = "signer", desc = "The transaction signer")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    ApplyPoolFees(ApplyPoolFees),
+
+    #[account(0, writable, signer, name = "admin", desc = "The exchange pool admin")]
+    #[account(1, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "game_token_pool", desc = "The game token pool account")]
+    #[account(3, writable, name = "rng_vault", desc = "The game token pool RNG vault")]
+    #[account(4, writable, name = "game_vault", desc = "The game token pool game token vault")]
+    #[account(5, writable, name = "admin_rng_tokens", desc = "The admin RNG token account")]
+    #[account(6, writable, name = "admin_game_tokens", desc = "The admin game token account")]
+    #[account(7, name = "game_mint", desc = "The game token mint")]
+    #[account(8, name = "rng_mint", desc = "The RNG mint")]
+    #[account(9, name = "system_program", desc = "The system program")]
+    #[account(10, name = "token_program", desc = "The SPL token program")]
+    #[account(11, name = "rent", desc = "The rent sysvar")]
+    InitializeGameTokenPool(InitializeGameTokenPool),
+
+    #[account(0, writable, signer, name = "user", desc = "The swapper")]
+    #[account(1, writable, name = "game_token_pool", desc = "The game token pool account")]
+    #[account(2, writable, name = "rng_vault", desc = "The game token pool RNG vault")]
+    #[account(3, writable, name = "game_vault", desc = "The game token pool game token vault")]
+    #[account(4, writable, name = "user_rng_tokens", desc = "The user RNG token account")]
+    #[account(5, writable, name = "user_game_tokens", desc = "The user game token account")]
+    #[account(6, name = "game_mint", desc = "The game token mint")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    SwapGameTokenPool(SwapGameTokenPool),
+
+    #[account(0, writable, signer, name = "signer", desc = "The LP staker")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "lp_stake", desc = "The LP stake account")]
+    #[account(3, writable, name = "lp_stake_tokens", desc = "The LP stake's LP token account")]
+    #[account(4, writable, name = "user_lp_tokens", desc = "The signer LP token account")]
+    #[account(5, name = "lp_mint", desc = "The pool LP mint")]
+    #[account(6, name = "system_program", desc = "The system program")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    #[account(8, name = "associated_token_program", desc = "The SPL associated token program")]
+    StakeLp(StakeLp),
+
+    #[account(0, writable, signer, name = "signer", desc = "The LP staker")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "lp_stake", desc = "The LP stake account")]
+    #[account(3, writable, name = "lp_stake_tokens", desc = "The LP stake's LP token account")]
+    #[account(4, writable, name = "user_lp_tokens", desc = "The signer LP token account")]
+    #[account(5, name = "lp_mint", desc = "The pool LP mint")]
+    #[account(6, name = "token_program", desc = "The SPL token program")]
+    UnstakeLp(UnstakeLp),
+
+    #[account(0, writable, signer, name = "signer", desc = "The LP staker")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "lp_stake", desc = "The LP stake account")]
+    #[account(3, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(4, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(5, writable, name = "user_sol_tokens", desc = "The signer wSOL token account")]
+    #[account(6, writable, name = "user_rng_tokens", desc = "The signer RNG token account")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    ClaimLpRewards(ClaimLpRewards),
+
+    #[account(0, writable, signer, name = "provider", desc = "The liquidity provider")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "lp_mint", desc = "The pool LP mint")]
+    #[account(3, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(4, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(5, writable, name = "provider_rng_tokens", desc = "The provider RNG token account")]
+    #[account(6, writable, name = "provider_lp_tokens", desc = "The provider LP token account")]
+    #[account(7, name = "rng_mint", desc = "The RNG mint")]
+    #[account(8, name = "sol_mint", desc = "The wrapped SOL mint")]
+    #[account(9, name = "system_program", desc = "The system program")]
+    #[account(10, name = "token_program", desc = "The SPL token program")]
+    #[account(11, name = "associated_token_program", desc = "The SPL associated token program")]
+    ZapLiquidity(ZapLiquidity),
+
+    #[account(0, writable, signer, name = "provider", desc = "The liquidity provider")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "lp_mint", desc = "The pool LP mint")]
+    #[account(3, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(4, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(5, writable, name = "provider_out_tokens", desc = "The provider wSOL or RNG token account")]
+    #[account(6, writable, name = "provider_lp_tokens", desc = "The provider LP token account")]
+    #[account(7, name = "rng_mint", desc = "The RNG mint")]
+    #[account(8, name = "sol_mint", desc = "The wrapped SOL mint")]
+    #[account(9, name = "token_program", desc = "The SPL token program")]
+    RemoveLiquiditySingle(RemoveLiquiditySingle),
+
+    #[account(0, writable, signer, name = "caller", desc = "The crank caller")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(3, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(4, name = "treasury", desc = "The treasury account")]
+    #[account(5, writable, name = "treasury_sol_tokens", desc = "The treasury wSOL token account")]
+    #[account(6, writable, name = "treasury_rng_tokens", desc = "The treasury RNG token account")]
+    #[account(7, name = "sol_mint", desc = "The wrapped SOL mint")]
+    #[account(8, name = "rng_mint", desc = "The RNG mint")]
+    #[account(9, name = "system_program", desc = "The system program")]
+    #[account(10, name = "token_program", desc = "The SPL token program")]
+    #[account(11, name = "associated_token_program", desc = "The SPL associated token program")]
+    SkimProtocolFees(SkimProtocolFees),
+
+    #[account(0, writable, signer, name = "signer", desc = "The referrer's authority")]
+    #[account(1, writable, name = "referrer", desc = "The referrer account")]
+    #[account(2, name = "system_program", desc = "The system program")]
+    RegisterReferrer(RegisterReferrer),
+
+    #[account(0, writable, signer, name = "signer", desc = "The referrer's authority")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "referrer", desc = "The referrer account")]
+    #[account(3, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(4, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(5, writable, name = "signer_sol_tokens", desc = "The signer wSOL token account")]
+    #[account(6, writable, name = "signer_rng_tokens", desc = "The signer RNG token account")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    ClaimReferralFees(ClaimReferralFees),
+
+    #[account(0, writable, signer, name = "admin", desc = "The pool admin")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    SetReferralFee(SetReferralFee),
+
+    #[account(0, writable, signer, name = "admin", desc = "The pool admin")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    SetCircuitBreaker(SetCircuitBreaker),
+
+    #[account(0, writable, signer, name = "admin", desc = "The pool admin")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    SetMaxSwap(SetMaxSwap),
+
+    #[account(0, writable, signer, name = "provider", desc = "The liquidity provider")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "lp_mint", desc = "The pool LP mint")]
+    #[account(3, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(4, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(5, writable, name = "provider_sol_tokens", desc = "The provider wSOL token account")]
+    #[account(6, writable, name = "provider_rng_tokens", desc = "The provider RNG token account")]
+    #[account(7, writable, name = "provider_lp_tokens", desc = "The provider LP token account")]
+    #[account(8, name = "rng_mint", desc = "The RNG mint")]
+    #[account(9, name = "sol_mint", desc = "The wrapped SOL mint")]
+    #[account(10, name = "system_program", desc = "The system program")]
+    #[account(11, name = "token_program", desc = "The SPL token program")]
+    EmergencyRemoveLiquidity(EmergencyRemoveLiquidity),
+
+    #[account(0, writable, signer, name = "signer", desc = "The order owner")]
+    #[account(1, writable, name = "limit_order", desc = "The limit order account")]
+    #[account(2, writable, name = "escrow", desc = "The limit order's input token account")]
+    #[account(3, writable, name = "user_input_tokens", desc = "The signer input token account")]
+    #[account(4, writable, name = "user_output_tokens", desc = "The signer output token account")]
+    #[account(5, name = "input_mint", desc = "The input mint")]
+    #[account(6, name = "output_mint", desc = "The output mint")]
+    #[account(7, name = "system_program", desc = "The system program")]
+    #[account(8, name = "token_program", desc = "The SPL token program")]
+    #[account(9, name = "associated_token_program", desc = "The SPL associated token program")]
+    PlaceLimitOrder(PlaceLimitOrder),
+
+    #[account(0, writable, signer, name = "filler", desc = "The order filler")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(3, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(4, writable, name = "limit_order", desc = "The limit order account")]
+    #[account(5, writable, name = "escrow", desc = "The limit order's input token account")]
+    #[account(6, writable, name = "owner", desc = "The order owner")]
+    #[account(7, writable, name = "owner_output_tokens", desc = "The owner output token account")]
+    #[account(8, writable, name = "filler_input_tokens", desc = "The filler input token account")]
+    #[account(9, name = "token_program", desc = "The SPL token program")]
+    FillLimitOrder(FillLimitOrder),
+
+    #[account(0, writable, signer, name = "signer", desc = "The order owner, or anyone once the order expires")]
+    #[account(1, writable, name = "limit_order", desc = "The limit order account")]
+    #[account(2, writable, name = "escrow", desc = "The limit order's input token account")]
+    #[account(3, writable, name = "owner", desc = "The order owner")]
+    #[account(4, writable, name = "owner_input_tokens", desc = "The owner input token account")]
+    #[account(5, name = "token_program", desc = "The SPL token program")]
+    CancelLimitOrder(CancelLimitOrder),
+
+    #[account(0, writable, signer, name = "signer", desc = "The order owner")]
+    #[account(1, writable, name = "dca_order", desc = "The DCA order account")]
+    #[account(2, writable, name = "escrow", desc = "The DCA order's input token account")]
+    #[account(3, writable, name = "user_input_tokens", desc = "The signer input token account")]
+    #[account(4, writable, name = "user_output_tokens", desc = "The signer output token account")]
+    #[account(5, name = "input_mint", desc = "The input mint")]
+    #[account(6, name = "output_mint", desc = "The output mint")]
+    #[account(7, name = "system_program", desc = "The system program")]
+    #[account(8, name = "token_program", desc = "The SPL token program")]
+    #[account(9, name = "associated_token_program", desc = "The SPL associated token program")]
+    OpenDcaOrder(OpenDcaOrder),
+
+    #[account(0, writable, signer, name = "cranker", desc = "The cranker")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(3, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(4, writable, name = "dca_order", desc = "The DCA order account")]
+    #[account(5, writable, name = "escrow", desc = "The DCA order's input token account")]
+    #[account(6, writable, name = "owner", desc = "The order owner")]
+    #[account(7, writable, name = "owner_output_tokens", desc = "The owner output token account")]
+    #[account(8, writable, name = "cranker_input_tokens", desc = "The cranker input token account")]
+    #[account(9, name = "token_program", desc = "The SPL token program")]
+    CrankDcaOrder(CrankDcaOrder),
+
+    #[account(0, writable, signer, name = "signer", desc = "The order owner")]
+    #[account(1, writable, name = "dca_order", desc = "The DCA order account")]
+    #[account(2, writable, name = "escrow", desc = "The DCA order's input token account")]
+    #[account(3, writable, name = "owner_input_tokens", desc = "The owner input token account")]
+    #[account(4, name = "token_program", desc = "The SPL token program")]
+    CancelDcaOrder(CancelDcaOrder),
+
+    #[account(0, writable, signer, name = "signer", desc = "The lock owner")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "lp_lock", desc = "The LP lock account")]
+    #[account(3, writable, name = "lp_lock_tokens", desc = "The LP lock's LP token account")]
+    #[account(4, writable, name = "user_lp_tokens", desc = "The signer LP token account")]
+    #[account(5, name = "lp_mint", desc = "The pool LP mint")]
+    #[account(6, name = "system_program", desc = "The system program")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    #[account(8, name = "associated_token_program", desc = "The SPL associated token program")]
+    LockLp(LockLp),
+
+    #[account(0, writable, signer, name = "signer", desc = "The lock owner")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "lp_lock", desc = "The LP lock account")]
+    #[account(3, writable, name = "lp_lock_tokens", desc = "The LP lock's LP token account")]
+    #[account(4, writable, name = "user_lp_tokens", desc = "The signer LP token account")]
+    #[account(5, writable, name = "lp_mint", desc = "The pool LP mint")]
+    #[account(6, writable, name = "sol_vault", desc = "The pool wSOL vault")]
+    #[account(7, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(8, writable, name = "user_sol_tokens", desc = "The signer wSOL token account")]
+    #[account(9, writable, name = "user_rng_tokens", desc = "The signer RNG token account")]
+    #[account(10, name = "token_program", desc = "The SPL token program")]
+    UnlockLp(UnlockLp),
+
+    #[account(0, name = "exchange_pool", desc = "The exchange pool account")]
+    QuoteGameTokenBacking(QuoteGameTokenBacking),
+
+    #[account(0, writable, signer, name = "admin", desc = "The pool admin")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    SetBuybackConfig(SetBuybackConfig),
+
+    #[account(0, writable, signer, name = "signer", desc = "The crank caller")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    #[account(2, writable, name = "rng_vault", desc = "The pool RNG vault")]
+    #[account(3, writable, name = "game_pool", desc = "The CRAP game token pool")]
+    #[account(4, writable, name = "game_pool_rng_vault", desc = "The CRAP pool RNG vault")]
+    #[account(5, writable, name = "game_pool_game_vault", desc = "The CRAP pool CRAP vault")]
+    #[account(6, writable, name = "crap_mint", desc = "The CRAP mint")]
+    #[account(7, name = "token_program", desc = "The SPL token program")]
+    BuybackCrap(BuybackCrap),
+
+    #[account(0, writable, signer, name = "admin", desc = "The pool admin")]
+    #[account(1, writable, name = "exchange_pool", desc = "The exchange pool account")]
+    SetSlotNotionalCap(SetSlotNotionalCap),
+}
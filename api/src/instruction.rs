@@ -1,5 +1,8 @@
+use shank::ShankType;
 use steel::*;
 
+use crate::consts::{MAX_BUNDLE_LEGS, MAX_ROLLS_PER_ROUND};
+
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive)]
 pub enum OreInstruction {
@@ -9,16 +12,43 @@ pub enum OreInstruction {
     Checkpoint = 2,
     ClaimSOL = 3,
     ClaimORE = 4,
+    ClaimVested = 62,
+    SweepDust = 64,
+    CrankClaims = 65,
     Close = 5,
     Deploy = 6,
     Log = 8,
     Reset = 9,
     RecycleSOL = 21,
+    DeployMany = 47,
+    DeploySpread = 48,
+    DeployWithSol = 88,
+    SetAutoRedeploy = 49,
+    CheckpointBatch = 50,
+    ArchiveRound = 59,
+    CheckpointArchived = 60,
 
     // Staker
     Deposit = 10,
     Withdraw = 11,
     ClaimYield = 12,
+    ClaimYieldAndRestake = 36,
+    ClaimYieldAsSol = 89,
+    ClaimWithdrawal = 38,
+    SetStakeOperator = 41,
+    MintStakeReceipt = 42,
+    RedeemStakeReceipt = 43,
+    DepositRng = 44,
+    WithdrawRng = 45,
+    CloseStake = 46,
+
+    // Mining pools
+    OpenPool = 51,
+    JoinPool = 52,
+    LeavePool = 53,
+    ClaimPoolRewards = 54,
+    PoolDeploy = 55,
+    HarvestPool = 56,
 
     // Admin
     Bury = 13,
@@ -30,12 +60,36 @@ pub enum OreInstruction {
     NewVar = 19,
     SetAdminFee = 20,
     StartRound = 22,
+    RouteSwap = 35,
+    SetUnstakeCooldown = 37,
+    SetMotherlodeParams = 57,
+    SetRewardSplitPolicy = 58,
+    SetVestingPolicy = 61,
+    SetClaimMinimums = 63,
+    SetAdminMultisig = 66,
+    AcceptAdmin = 67,
+    Pause = 68,
+    ScheduleUnpause = 69,
+    ApplyUnpause = 70,
+    SetGuardian = 71,
+    ApplyGuardian = 72,
+    SetParam = 74,
+    SetRevenuePolicy = 77,
+    DistributeRevenue = 78,
+    SetRole = 79,
+    SetRoundPermission = 80,
 
     // Craps
     PlaceCrapsBet = 23,
     SettleCraps = 24,
     ClaimCrapsWinnings = 25,
     FundCrapsHouse = 26,
+    ScheduleHouseWithdrawal = 75,
+    WithdrawHouseBankroll = 76,
+    AssertSolvency = 82,
+    SetHouseProfitShare = 40,
+    DistributeHouseProfit = 90,
+    PlaceCrapsBundle = 87,
 
     // SECURITY FIX 2.1: Force settle allows anyone to release reserved payouts
     // for positions that have not been settled within the expiry window.
@@ -44,35 +98,128 @@ pub enum OreInstruction {
     // SECURITY FIX 2.2: Claim unpaid debt when house is funded again
     ClaimCrapsDebt = 30,
 
+    // Test roll oracle (localnet/devnet builds only)
+    SetTestRoll = 31,
+
+    // One-shot table setup (localnet builds only)
+    Bootstrap = 83,
+
+    // Rate-limited test token faucet (devnet builds only)
+    FaucetRng = 84,
+    FaucetCrap = 85,
+
+    // Protocol KPI counters
+    OpenMetrics = 86,
+
+    // Session keys
+    CreateSession = 91,
+    RevokeSession = 92,
+
+    // Exchange
+    InitializeExchangePool = 93,
+    AddLiquidity = 94,
+    RemoveLiquidity = 95,
+    SwapSolToRng = 96,
+    SwapRngToSol = 97,
+    SwapRngToGameToken = 98,
+    SwapGameTokenToRng = 99,
+    ClaimProtocolFees = 100,
+    QuoteSwap = 101,
+    SwapSolToGameToken = 102,
+    SwapSolToRngExactOut = 103,
+    SwapRngToSolExactOut = 104,
+    SetPoolStatus = 105,
+    SetPoolGuardian = 106,
+    SetPoolFees = 107,
+    ApplyPoolFees = 108,
+    InitializeGameTokenPool = 109,
+    SwapGameTokenPool = 110,
+    StakeLp = 111,
+    UnstakeLp = 112,
+    ClaimLpRewards = 113,
+    ZapLiquidity = 114,
+    RemoveLiquiditySingle = 115,
+    SkimProtocolFees = 116,
+    RegisterReferrer = 117,
+    ClaimReferralFees = 118,
+    SetReferralFee = 119,
+    SetCircuitBreaker = 120,
+    SetMaxSwap = 121,
+    EmergencyRemoveLiquidity = 122,
+    PlaceLimitOrder = 123,
+    FillLimitOrder = 124,
+    CancelLimitOrder = 125,
+    OpenDcaOrder = 126,
+    CrankDcaOrder = 127,
+    CancelDcaOrder = 128,
+    LockLp = 129,
+    UnlockLp = 130,
+    QuoteGameTokenBacking = 131,
+    SetBuybackConfig = 132,
+    BuybackCrap = 133,
+    SetSlotNotionalCap = 134,
+
+    // Settle only single-roll bets (fast table mode)
+    SettleCrapsSingleRoll = 32,
+
+    // Read-only settlement preview (returns a SettlePreview via return data)
+    SimulateSettle = 33,
+
     // Migration
     MigrateRound = 27,
     MigrateMiner = 28,
+    MigrateCrapsPosition = 34,
+    MigrateStake = 39,
+    MigrateConfig = 73,
+    MigrateAccount = 81,
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct Initialize {}
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct Automate {
     pub amount: [u8; 8],
     pub deposit: [u8; 8],
     pub fee: [u8; 8],
     pub mask: [u8; 8],
     pub strategy: u8,
+    /// Max spend (deploys plus fee) per round. Zero means no limit.
+    pub round_limit: [u8; 8],
+    /// Total spend budget. u64::MAX means no budget.
+    pub budget: [u8; 8],
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct ClaimSOL {}
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct ClaimVested {}
+
+/// Sweeps sub-minimum balances from inactive miners, passed as trailing miner accounts.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SweepDust {}
+
+/// Pushes matured claims to their owners, tipping the signer per account paid.
+/// `kind` is `CLAIM_CRANK_SOL` or `CLAIM_CRANK_VESTED`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct CrankClaims {
+    pub kind: u8,
+    pub _padding: [u8; 7],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct ClaimORE {}
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct Deploy {
     pub amount: [u8; 8],
     /// 64-bit bitmask for 36 dice combinations (6x6 grid).
@@ -84,12 +231,125 @@ pub struct Deploy {
     pub _padding: [u8; 7],
 }
 
+/// Deploys a different amount to each of several squares in one instruction.
+/// The args are followed by a list of `DeployManyEntry` values.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct DeployMany {
+    /// Dice prediction for betting (2-12, or 0 for safe mode).
+    pub dice_prediction: u8,
+    /// Padding for alignment.
+    pub _padding: [u8; 7],
+}
+
+/// Spreads a total amount evenly across a named set of squares.
+/// Fails without deploying if the miner already holds any square in the set.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct DeploySpread {
+    /// Total amount to spread across the preset's squares.
+    pub amount: [u8; 8],
+    /// The `DeployPreset` set of squares.
+    pub preset: u8,
+    /// Dice prediction for betting (2-12, or 0 for safe mode).
+    pub dice_prediction: u8,
+    /// Padding for alignment.
+    pub _padding: [u8; 6],
+}
+
+/// Swaps SOL for RNG through the configured swap program and spreads the RNG received
+/// across a set of squares. The args are followed by the swap program's instruction data.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct DeployWithSol {
+    /// Most lamports the swap may take from the signer.
+    pub sol_amount: [u8; 8],
+    /// Least RNG the swap must deliver.
+    pub min_rng_out: [u8; 8],
+    /// Bitmask of squares to deploy to.
+    pub squares: [u8; 8],
+    /// Dice prediction for betting (2-12, or 0 for safe mode).
+    pub dice_prediction: u8,
+    /// Padding for alignment.
+    pub _padding: [u8; 7],
+}
+
+/// Enables (1) or disables (0) rolling checkpointed winnings into the current round.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetAutoRedeploy {
+    pub enabled: u8,
+}
+
+/// Checkpoints many miners from the same round, passed as trailing
+/// (miner, stake) account pairs.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct CheckpointBatch {}
+
+/// Replaces an ended round with a compact archive and returns the round's rent.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct ArchiveRound {}
+
+/// Checkpoints a miner against a round archive, proving the winning square's
+/// totals with a Merkle proof.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct CheckpointArchived {
+    pub deployed: [u8; 8],
+    pub count: [u8; 8],
+    pub proof: [[u8; 32]; 6],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct OpenPool {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct JoinPool {
+    pub amount: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct LeavePool {
+    pub shares: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct ClaimPoolRewards {}
+
+/// Deploys pooled RNG. The args are followed by a list of `DeployManyEntry` values.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct PoolDeploy {
+    /// Dice prediction for betting (2-12, or 0 for safe mode).
+    pub dice_prediction: u8,
+    /// Padding for alignment.
+    pub _padding: [u8; 7],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct HarvestPool {}
+
+/// A single (square, amount) pair in a `DeployMany` instruction.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct DeployManyEntry {
+    pub square: u8,
+    pub amount: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct Log {}
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct Reset {}
 
 #[repr(C)]
@@ -114,13 +374,13 @@ pub struct Uncommit {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct SetAdmin {
     pub admin: [u8; 32],
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct SetFeeCollector {
     pub fee_collector: [u8; 32],
 }
@@ -132,45 +392,99 @@ pub struct SetFeeRate {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct Wrap {}
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct Bury {}
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct RecycleSOL {}
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct Deposit {
     pub amount: [u8; 8],
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct Withdraw {
     pub amount: [u8; 8],
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct ClaimYield {
     pub amount: [u8; 8],
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct ClaimYieldAndRestake {
+    pub amount: [u8; 8],
+}
+
+/// Claims staking yield and swaps it for SOL through the configured swap program.
+/// The args are followed by the swap program's instruction data.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct ClaimYieldAsSol {
+    pub amount: [u8; 8],
+    /// Least SOL the swap must deliver to the signer.
+    pub min_sol_out: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct ClaimWithdrawal {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetStakeOperator {
+    pub operator: [u8; 32],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct MintStakeReceipt {
+    pub amount: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct RedeemStakeReceipt {
+    pub amount: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct DepositRng {
+    pub amount: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct WithdrawRng {
+    pub amount: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct CloseStake {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct Checkpoint {}
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct Close {}
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct NewVar {
     pub id: [u8; 8],
     pub commit: [u8; 32],
@@ -178,21 +492,162 @@ pub struct NewVar {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct SetAdminFee {
     pub admin_fee: [u8; 8],
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetUnstakeCooldown {
+    pub cooldown_slots: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetRewardSplitPolicy {
+    /// Probability (bps) that a round's ORE reward is split.
+    pub split_bps: [u8; 8],
+    /// Percent of an unsplit reward paid to the top miner.
+    pub top_miner_share_pct: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetVestingPolicy {
+    /// ORE claims above this amount vest. Zero disables vesting.
+    pub threshold: [u8; 8],
+    /// Number of slots a vesting claim is released over.
+    pub duration_slots: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct AcceptAdmin {}
+
+/// Pauses modules at once. Approved by the guardian or the admin.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct Pause {
+    /// Bitmask of `PAUSE_*` modules.
+    pub modules: [u8; 8],
+}
+
+/// Schedules paused modules to unpause after the timelock. Approved by the admin.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct ScheduleUnpause {
+    /// Bitmask of `PAUSE_*` modules.
+    pub modules: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct ApplyUnpause {}
+
+/// Schedules a new guardian, applied after the timelock. Approved by the admin.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetGuardian {
+    pub guardian: [u8; 32],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct ApplyGuardian {}
+
+/// Sets a tunable parameter. Approved by the admin.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetParam {
+    /// The parameter key (`PARAM_*`).
+    pub key: [u8; 8],
+    /// The encoded value. Numeric kinds use the first 8 bytes, little-endian.
+    pub value: [u8; 32],
+}
+
+/// Sets how protocol revenue is split between buyback-burn, staking yield and the
+/// insurance fund. Approved by the admin.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetRevenuePolicy {
+    /// Share (bps) bought back and burned.
+    pub burn_bps: [u8; 8],
+    /// Share (bps) bought back and shared with stakers.
+    pub stake_bps: [u8; 8],
+    /// Share (bps) paid into the insurance fund.
+    pub insurance_bps: [u8; 8],
+}
+
+/// Routes the treasury's undistributed revenue according to the revenue policy.
+/// Permissionless.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct DistributeRevenue {}
+
+/// Grants or revokes roles for a key. Approved by the admin.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetRole {
+    /// The key to update.
+    pub authority: [u8; 32],
+    /// The role bits (`ROLE_*`) the key should hold. Zero revokes all roles.
+    pub roles: [u8; 8],
+}
+
+/// Sets who may reset rounds. Approved by the admin.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetRoundPermission {
+    /// The permission mode (`ROUND_PERMISSION_*`).
+    pub mode: [u8; 8],
+}
+
+/// Creates or updates the admin multisig. Approved by the current admin.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetAdminMultisig {
+    pub signers: [[u8; 32]; 10],
+    pub num_signers: u8,
+    pub threshold: u8,
+    pub _padding: [u8; 6],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetClaimMinimums {
+    /// Smallest SOL claim allowed. Zero means no minimum.
+    pub min_sol: [u8; 8],
+    /// Smallest ORE claim allowed. Zero means no minimum.
+    pub min_ore: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetMotherlodeParams {
+    /// Motherlode odds, 1 in N rounds.
+    pub odds: [u8; 8],
+    /// ORE minted into the motherlode pool each round.
+    pub funding: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct SetSwapProgram {}
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct RouteSwap {
+    pub amount_in: [u8; 8],
+    pub min_amount_out: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct SetVarAddress {}
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct StartRound {
     pub duration: [u8; 8],
 }
@@ -206,6 +661,19 @@ instruction!(OreInstruction, ClaimSOL);
 instruction!(OreInstruction, ClaimORE);
 instruction!(OreInstruction, RecycleSOL);
 instruction!(OreInstruction, Deploy);
+instruction!(OreInstruction, DeployMany);
+instruction!(OreInstruction, DeploySpread);
+instruction!(OreInstruction, DeployWithSol);
+instruction!(OreInstruction, SetAutoRedeploy);
+instruction!(OreInstruction, CheckpointBatch);
+instruction!(OreInstruction, ArchiveRound);
+instruction!(OreInstruction, CheckpointArchived);
+instruction!(OreInstruction, OpenPool);
+instruction!(OreInstruction, JoinPool);
+instruction!(OreInstruction, LeavePool);
+instruction!(OreInstruction, ClaimPoolRewards);
+instruction!(OreInstruction, PoolDeploy);
+instruction!(OreInstruction, HarvestPool);
 instruction!(OreInstruction, Log);
 instruction!(OreInstruction, Wrap);
 instruction!(OreInstruction, Bury);
@@ -215,10 +683,40 @@ instruction!(OreInstruction, SetFeeCollector);
 instruction!(OreInstruction, Deposit);
 instruction!(OreInstruction, Withdraw);
 instruction!(OreInstruction, ClaimYield);
+instruction!(OreInstruction, ClaimYieldAndRestake);
+instruction!(OreInstruction, ClaimYieldAsSol);
+instruction!(OreInstruction, ClaimWithdrawal);
+instruction!(OreInstruction, SetStakeOperator);
+instruction!(OreInstruction, MintStakeReceipt);
+instruction!(OreInstruction, RedeemStakeReceipt);
+instruction!(OreInstruction, DepositRng);
+instruction!(OreInstruction, WithdrawRng);
+instruction!(OreInstruction, CloseStake);
 instruction!(OreInstruction, NewVar);
 instruction!(OreInstruction, SetAdminFee);
+instruction!(OreInstruction, SetUnstakeCooldown);
+instruction!(OreInstruction, SetMotherlodeParams);
+instruction!(OreInstruction, SetRewardSplitPolicy);
+instruction!(OreInstruction, SetVestingPolicy);
+instruction!(OreInstruction, ClaimVested);
+instruction!(OreInstruction, SetClaimMinimums);
+instruction!(OreInstruction, SetAdminMultisig);
+instruction!(OreInstruction, AcceptAdmin);
+instruction!(OreInstruction, Pause);
+instruction!(OreInstruction, ScheduleUnpause);
+instruction!(OreInstruction, ApplyUnpause);
+instruction!(OreInstruction, SetGuardian);
+instruction!(OreInstruction, ApplyGuardian);
+instruction!(OreInstruction, SetParam);
+instruction!(OreInstruction, SetRevenuePolicy);
+instruction!(OreInstruction, DistributeRevenue);
+instruction!(OreInstruction, SetRole);
+instruction!(OreInstruction, SetRoundPermission);
+instruction!(OreInstruction, SweepDust);
+instruction!(OreInstruction, CrankClaims);
 instruction!(OreInstruction, SetSwapProgram);
 instruction!(OreInstruction, SetVarAddress);
+instruction!(OreInstruction, RouteSwap);
 
 // ============================================================================
 // CRAPS INSTRUCTIONS
@@ -276,7 +774,7 @@ pub enum CrapsBetType {
 
 /// Place a craps bet.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct PlaceCrapsBet {
     /// The bet type (CrapsBetType as u8).
     pub bet_type: u8,
@@ -288,31 +786,90 @@ pub struct PlaceCrapsBet {
     pub amount: [u8; 8],
 }
 
+/// Place up to `MAX_BUNDLE_LEGS` craps bets as one hedged bundle. The house reserves
+/// the most the bundle can pay on any roll rather than the sum of each leg's maximum.
+/// Legs with a zero amount are unused.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct PlaceCrapsBundle {
+    /// The bet type of each leg (CrapsBetType as u8). Side bets can't be bundled.
+    pub bet_types: [u8; MAX_BUNDLE_LEGS],
+    /// The point of each leg, as in PlaceCrapsBet.
+    pub points: [u8; MAX_BUNDLE_LEGS],
+    /// The amount of each leg.
+    pub amounts: [[u8; 8]; MAX_BUNDLE_LEGS],
+}
+
 /// Settle craps bets after a round is complete.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct SettleCraps {
     /// The winning square from the round.
     pub winning_square: [u8; 8],
 }
 
+/// Settle a position holding only single-roll bets against the round's first roll.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SettleCrapsSingleRoll {}
+
 /// Claim craps winnings.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct ClaimCrapsWinnings {}
 
 /// Fund the craps house bankroll (admin only).
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct FundCrapsHouse {
     /// Amount to deposit into house bankroll.
     pub amount: [u8; 8],
 }
 
+/// Set the share of net house wins routed to stakers each epoch (admin only).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetHouseProfitShare {
+    /// Staker share of net house wins (bps).
+    pub share_bps: [u8; 8],
+}
+
+/// Swap the stakers' share of net house wins to ORE and credit it as staking yield.
+/// The args are followed by the swap program's instruction data.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct DistributeHouseProfit {
+    /// Least ORE the swap must deliver.
+    pub min_ore_out: [u8; 8],
+}
+
+/// Schedule a withdrawal of excess house bankroll (admin only).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct ScheduleHouseWithdrawal {
+    /// Amount of CRAP to withdraw. Zero cancels a pending withdrawal.
+    pub amount: [u8; 8],
+}
+
+/// Execute a scheduled house bankroll withdrawal once its timelock passes (admin only).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct WithdrawHouseBankroll {}
+
+/// Fails unless the craps house is solvent: the bankroll covers reserved payouts and
+/// the vault covers the bankroll plus escrow. Read-only, for monitoring and tests.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct AssertSolvency {
+    /// CRAP held in the vault for players outside the bankroll.
+    pub escrow: [u8; 8],
+}
+
 /// SECURITY FIX 2.1: Force settle a craps position that hasn't been settled in time.
 /// This allows anyone to trigger settlement for expired positions, releasing reserved payouts.
+/// While craps is paused, the guardian can settle early, refunding the bets.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct ForceSettleCraps {
     /// The winning square from the round (must match round's actual result).
     pub winning_square: [u8; 8],
@@ -321,20 +878,128 @@ pub struct ForceSettleCraps {
 /// SECURITY FIX 2.2: Claim unpaid debt from previous insolvency.
 /// Called by position owner when house has been re-funded.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct ClaimCrapsDebt {}
 
+/// Inject dice rolls for a round into the test roll oracle (admin only, localnet/devnet builds).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetTestRoll {
+    /// The round the roll applies to.
+    pub round_id: [u8; 8],
+    /// The winning square (0-35) to use for the round.
+    pub winning_square: [u8; 8],
+    /// The squares of the later sub-rolls, as square + 1. Zero leaves a sub-roll to the
+    /// slot hash. Index 0 is ignored, since sub-roll 0 is the winning square.
+    pub sub_rolls: [u8; MAX_ROLLS_PER_ROUND as usize],
+}
+
+/// Initialize the program and open a funded craps table in one transaction (localnet builds).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct Bootstrap {
+    /// CRAP minted into the craps vault as the house bankroll. The signer must be the
+    /// CRAP mint authority.
+    pub house_bankroll: [u8; 8],
+}
+
+/// Mint test RNG to the signer, once per faucet cooldown (devnet builds).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct FaucetRng {}
+
+/// Mint test CRAP to the signer, once per faucet cooldown (devnet builds).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct FaucetCrap {}
+
+/// Open the protocol metrics account. Anyone may pay for it; it can only be opened once.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct OpenMetrics {}
+
+/// Create or renew the signer's session, letting `delegate` sign the allowed operations
+/// on their behalf until it expires. Withdrawals are never allowed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct CreateSession {
+    /// The delegate key.
+    pub delegate: [u8; 32],
+    /// Seconds until the session expires, at most 24 hours.
+    pub duration: [u8; 8],
+    /// Bitmask of `SessionOperation`s, or 0 for all of them.
+    pub allowed_operations: [u8; 8],
+    /// Total the delegate may deploy through a Delegated automation.
+    pub deploy_budget: [u8; 8],
+}
+
+/// Close the signer's session before it expires.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct RevokeSession {}
+
+/// Preview what a position would win or lose on a given roll without mutating state.
+/// The result is written to the transaction return data as a `SettlePreview`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SimulateSettle {
+    /// The square (0-35) to preview settlement against.
+    pub winning_square: [u8; 8],
+}
+
+/// Return data of a SimulateSettle instruction.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct SettlePreview {
+    /// Total returned to the position (stake plus winnings) by bets that win.
+    pub winnings: u64,
+    /// Total stake lost by bets that lose.
+    pub lost: u64,
+    /// Stake still riding on unresolved bets after the roll.
+    pub remaining_bets: u64,
+    /// Pending winnings the position would hold after the roll.
+    pub pending_winnings: u64,
+    /// 1 if the roll ends the shooter's turn (seven-out), 0 otherwise.
+    pub seven_out: u64,
+}
+
+impl SettlePreview {
+    pub fn to_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+
+    /// Parses the return data of a simulated SimulateSettle transaction.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        bytemuck::try_pod_read_unaligned(data).ok()
+    }
+}
+
 instruction!(OreInstruction, PlaceCrapsBet);
+instruction!(OreInstruction, PlaceCrapsBundle);
 instruction!(OreInstruction, SettleCraps);
+instruction!(OreInstruction, SettleCrapsSingleRoll);
+instruction!(OreInstruction, SimulateSettle);
 instruction!(OreInstruction, ClaimCrapsWinnings);
 instruction!(OreInstruction, FundCrapsHouse);
+instruction!(OreInstruction, ScheduleHouseWithdrawal);
+instruction!(OreInstruction, WithdrawHouseBankroll);
+instruction!(OreInstruction, AssertSolvency);
+instruction!(OreInstruction, SetHouseProfitShare);
+instruction!(OreInstruction, DistributeHouseProfit);
 instruction!(OreInstruction, ForceSettleCraps);
 instruction!(OreInstruction, ClaimCrapsDebt);
+instruction!(OreInstruction, SetTestRoll);
+instruction!(OreInstruction, Bootstrap);
+instruction!(OreInstruction, FaucetRng);
+instruction!(OreInstruction, FaucetCrap);
+instruction!(OreInstruction, OpenMetrics);
+instruction!(OreInstruction, CreateSession);
+instruction!(OreInstruction, RevokeSession);
 instruction!(OreInstruction, MigrateRound);
 
 /// Migrate a Round account to the new struct size (admin only).
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct MigrateRound {
     /// The round ID to migrate.
     pub round_id: [u8; 8],
@@ -344,5 +1009,501 @@ instruction!(OreInstruction, MigrateMiner);
 
 /// Migrate a Miner account to the new struct size.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
 pub struct MigrateMiner {}
+
+instruction!(OreInstruction, MigrateCrapsPosition);
+
+/// Migrate a CrapsPosition account to the compact layout.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct MigrateCrapsPosition {}
+
+instruction!(OreInstruction, MigrateStake);
+
+/// Migrate a Stake account to the new struct size.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct MigrateStake {}
+
+instruction!(OreInstruction, MigrateConfig);
+
+/// Migrate the Config and Treasury accounts to the current layout versions (admin only).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct MigrateConfig {}
+
+instruction!(OreInstruction, MigrateAccount);
+
+/// Migrate any program account to its current size and layout version.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct MigrateAccount {}
+
+instruction!(OreInstruction, InitializeExchangePool);
+
+/// Create the SOL/RNG exchange pool, seeded by the admin (admin only).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct InitializeExchangePool {
+    /// Lamports deposited into the SOL reserve.
+    pub sol_amount: [u8; 8],
+    /// RNG deposited into the RNG reserve.
+    pub rng_amount: [u8; 8],
+}
+
+instruction!(OreInstruction, AddLiquidity);
+
+/// Deposit SOL and RNG at the pool ratio in exchange for LP tokens.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct AddLiquidity {
+    /// Most lamports to deposit.
+    pub sol_amount: [u8; 8],
+    /// Most RNG to deposit.
+    pub rng_amount: [u8; 8],
+    /// Fewest LP tokens to accept.
+    pub min_lp_tokens: [u8; 8],
+}
+
+instruction!(OreInstruction, RemoveLiquidity);
+
+/// Burn LP tokens for a proportional share of both reserves.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct RemoveLiquidity {
+    /// LP tokens to burn.
+    pub lp_amount: [u8; 8],
+    /// Fewest lamports to accept.
+    pub min_sol: [u8; 8],
+    /// Fewest RNG to accept.
+    pub min_rng: [u8; 8],
+}
+
+instruction!(OreInstruction, SwapSolToRng);
+
+/// Swap SOL for RNG through the pool.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SwapSolToRng {
+    /// Lamports to swap.
+    pub sol_amount: [u8; 8],
+    /// Fewest RNG to accept.
+    pub min_rng_out: [u8; 8],
+}
+
+instruction!(OreInstruction, SwapRngToSol);
+
+/// Swap RNG for SOL through the pool.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SwapRngToSol {
+    /// RNG to swap.
+    pub rng_amount: [u8; 8],
+    /// Fewest lamports to accept.
+    pub min_sol_out: [u8; 8],
+}
+
+instruction!(OreInstruction, SwapRngToGameToken);
+
+/// Swap RNG for a game token at 1:1, less the swap fee.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SwapRngToGameToken {
+    /// RNG to swap.
+    pub rng_amount: [u8; 8],
+    /// The game token to mint (see `GameTokenType` in the program).
+    pub game_token_type: u8,
+}
+
+instruction!(OreInstruction, SwapGameTokenToRng);
+
+/// Swap a game token back to RNG at 1:1, less the swap fee.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SwapGameTokenToRng {
+    /// Game tokens to burn.
+    pub game_token_amount: [u8; 8],
+    /// The game token to burn (see `GameTokenType` in the program).
+    pub game_token_type: u8,
+}
+
+instruction!(OreInstruction, ClaimProtocolFees);
+
+/// Withdraw the protocol's accrued swap fees to the admin (admin only).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct ClaimProtocolFees {}
+
+instruction!(OreInstruction, QuoteSwap);
+
+/// Quote a swap against the pool without executing it. Returns a `SwapQuote` as return data.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct QuoteSwap {
+    /// Input amount.
+    pub amount_in: [u8; 8],
+    /// 1 to quote SOL in for RNG out, 0 for RNG in for SOL out.
+    pub sol_to_rng: u8,
+}
+
+instruction!(OreInstruction, SwapSolToGameToken);
+
+/// Swap SOL for a game token in one step: SOL to RNG through the pool, then RNG to the
+/// game token at 1:1.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SwapSolToGameToken {
+    /// Lamports to swap.
+    pub sol_amount: [u8; 8],
+    /// Fewest game tokens to accept.
+    pub min_game_out: [u8; 8],
+    /// The game token to mint (see `GameTokenType` in the program).
+    pub game_token_type: u8,
+}
+
+instruction!(OreInstruction, SwapSolToRngExactOut);
+
+/// Swap SOL for an exact amount of RNG through the pool.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SwapSolToRngExactOut {
+    /// RNG to receive.
+    pub rng_out: [u8; 8],
+    /// Most lamports to spend.
+    pub max_sol_in: [u8; 8],
+}
+
+instruction!(OreInstruction, SwapRngToSolExactOut);
+
+/// Swap RNG for an exact amount of SOL through the pool.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SwapRngToSolExactOut {
+    /// Lamports to receive.
+    pub sol_out: [u8; 8],
+    /// Most RNG to spend.
+    pub max_rng_in: [u8; 8],
+}
+
+instruction!(OreInstruction, SetPoolStatus);
+
+/// Set the exchange pool status. The guardian may only pause; other changes are admin only.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetPoolStatus {
+    /// EXCHANGE_STATUS_ACTIVE, EXCHANGE_STATUS_PAUSED or EXCHANGE_STATUS_DEPRECATED.
+    pub status: u8,
+}
+
+instruction!(OreInstruction, SetPoolGuardian);
+
+/// Set the key allowed to pause the exchange pool (admin only).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetPoolGuardian {
+    /// The new guardian, or the default pubkey to remove it.
+    pub guardian: [u8; 32],
+}
+
+instruction!(OreInstruction, SetPoolFees);
+
+/// Schedule a change to the swap fee and its protocol share (admin only). ApplyPoolFees
+/// makes it take effect once EXCHANGE_FEE_TIMELOCK_SECONDS have passed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetPoolFees {
+    /// Swap fee over EXCHANGE_DEFAULT_FEE_DENOMINATOR, at most EXCHANGE_MAX_FEE_NUMERATOR.
+    pub fee_numerator: [u8; 8],
+    /// Share of the swap fee kept by the protocol, in basis points.
+    pub protocol_fee_share_bps: [u8; 8],
+}
+
+instruction!(OreInstruction, ApplyPoolFees);
+
+/// Apply a scheduled fee change whose timelock has passed. Anyone can call it.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct ApplyPoolFees {}
+
+instruction!(OreInstruction, InitializeGameTokenPool);
+
+/// Create an RNG/game token pool seeded from the admin's tokens (exchange admin only).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct InitializeGameTokenPool {
+    /// RNG deposited into the RNG reserve.
+    pub rng_amount: [u8; 8],
+    /// Game tokens deposited into the game token reserve.
+    pub game_amount: [u8; 8],
+    /// The pool's game token (see `GameTokenType` in the program).
+    pub game_token_type: u8,
+}
+
+instruction!(OreInstruction, SwapGameTokenPool);
+
+/// Swap between RNG and a game token through the game token's pool.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SwapGameTokenPool {
+    /// Input amount.
+    pub amount_in: [u8; 8],
+    /// Fewest output tokens to accept.
+    pub min_amount_out: [u8; 8],
+    /// 1 to swap RNG for game tokens, 0 for game tokens to RNG.
+    pub rng_to_game: u8,
+}
+
+instruction!(OreInstruction, StakeLp);
+
+/// Stake LP tokens to earn the LP stakers' share of protocol swap fees.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct StakeLp {
+    /// LP tokens to stake.
+    pub amount: [u8; 8],
+}
+
+instruction!(OreInstruction, UnstakeLp);
+
+/// Unstake LP tokens. Accrued rewards stay claimable.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct UnstakeLp {
+    /// LP tokens to unstake.
+    pub amount: [u8; 8],
+}
+
+instruction!(OreInstruction, ClaimLpRewards);
+
+/// Claim the SOL and RNG fee rewards accrued to an LP stake.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct ClaimLpRewards {}
+
+instruction!(OreInstruction, ZapLiquidity);
+
+/// Add liquidity from a single token. Part of the deposit is swapped so both sides match
+/// the reserve ratio.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct ZapLiquidity {
+    /// Lamports or RNG to deposit.
+    pub amount: [u8; 8],
+    /// Fewest LP tokens to accept.
+    pub min_lp_tokens: [u8; 8],
+    /// 1 to deposit SOL, 0 to deposit RNG.
+    pub sol_in: u8,
+}
+
+instruction!(OreInstruction, RemoveLiquiditySingle);
+
+/// Burn LP tokens for a single token. The unwanted leg is swapped back through the pool.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct RemoveLiquiditySingle {
+    /// LP tokens to burn.
+    pub lp_amount: [u8; 8],
+    /// Least combined output to accept.
+    pub min_amount_out: [u8; 8],
+    /// 1 to receive wSOL, 0 to receive RNG.
+    pub sol_out: u8,
+}
+
+instruction!(OreInstruction, SkimProtocolFees);
+
+/// Move protocol fees past their skim thresholds from the pool vaults to the treasury.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SkimProtocolFees {}
+
+instruction!(OreInstruction, RegisterReferrer);
+
+/// Register the signer as a swap referrer.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct RegisterReferrer {}
+
+instruction!(OreInstruction, ClaimReferralFees);
+
+/// Claim the SOL and RNG referral fees accrued to the signer's referrer account.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct ClaimReferralFees {}
+
+instruction!(OreInstruction, SetReferralFee);
+
+/// Set the share of the protocol fee paid to swap referrers (admin only).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetReferralFee {
+    /// Referral share of the protocol fee, in basis points.
+    pub referral_fee_share_bps: [u8; 8],
+}
+
+instruction!(OreInstruction, SetCircuitBreaker);
+
+/// Configure the pool's price circuit breaker (admin only).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetCircuitBreaker {
+    /// Largest SOL price move allowed within a window, in basis points. 0 disables the breaker.
+    pub max_price_move_bps: [u8; 8],
+    /// Length of the price window, in seconds.
+    pub window_seconds: [u8; 8],
+}
+
+instruction!(OreInstruction, SetMaxSwap);
+
+/// Set the largest single swap, as basis points of the input reserve (admin only).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetMaxSwap {
+    /// Largest swap input, in basis points of the input reserve.
+    pub max_swap_bps: [u8; 8],
+}
+
+instruction!(OreInstruction, EmergencyRemoveLiquidity);
+
+/// Burn LP tokens for a share of the reserves while the pool is halted. The final
+/// withdrawer empties the pool.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct EmergencyRemoveLiquidity {
+    /// LP tokens to burn.
+    pub lp_amount: [u8; 8],
+    /// Fewest lamports to accept.
+    pub min_sol: [u8; 8],
+    /// Least RNG to accept.
+    pub min_rng: [u8; 8],
+}
+
+instruction!(OreInstruction, PlaceLimitOrder);
+
+/// Place a limit order against the exchange pool, escrowing the input.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct PlaceLimitOrder {
+    /// Owner-chosen order id.
+    pub id: [u8; 8],
+    /// Input tokens to escrow, including the filler bounty.
+    pub amount_in: [u8; 8],
+    /// Minimum output per input token, Q64.64.
+    pub limit_price: [u8; 16],
+    /// Timestamp after which the order can no longer be filled.
+    pub expires_at: [u8; 8],
+    /// LIMIT_ORDER_SIDE_SOL_TO_RNG or LIMIT_ORDER_SIDE_RNG_TO_SOL.
+    pub side: u8,
+}
+
+instruction!(OreInstruction, FillLimitOrder);
+
+/// Fill a limit order against the pool once its limit is reached. Permissionless.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct FillLimitOrder {}
+
+instruction!(OreInstruction, CancelLimitOrder);
+
+/// Cancel a limit order and refund its escrow. Anyone can clean up an expired order.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct CancelLimitOrder {}
+
+instruction!(OreInstruction, OpenDcaOrder);
+
+/// Open a DCA order that swaps its budget through the pool in fixed slices.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct OpenDcaOrder {
+    /// Owner-chosen order id.
+    pub id: [u8; 8],
+    /// Input swapped per slice, including the crank bounty.
+    pub amount_per_interval: [u8; 8],
+    /// Slots between slices.
+    pub interval_slots: [u8; 8],
+    /// Total input to escrow.
+    pub budget: [u8; 8],
+    /// Minimum output per input token for each slice, Q64.64.
+    pub min_price: [u8; 16],
+    /// LIMIT_ORDER_SIDE_SOL_TO_RNG or LIMIT_ORDER_SIDE_RNG_TO_SOL.
+    pub side: u8,
+}
+
+instruction!(OreInstruction, CrankDcaOrder);
+
+/// Execute the next due slice of a DCA order. Permissionless.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct CrankDcaOrder {}
+
+instruction!(OreInstruction, CancelDcaOrder);
+
+/// Cancel a DCA order and refund its remaining budget.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct CancelDcaOrder {}
+
+instruction!(OreInstruction, LockLp);
+
+/// Lock LP tokens for a fixed term to earn a boosted share of LP staker fees.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct LockLp {
+    /// Owner-chosen lock id.
+    pub id: [u8; 8],
+    /// LP tokens to lock.
+    pub amount: [u8; 8],
+    /// Lock term, in seconds.
+    pub duration: [u8; 8],
+}
+
+instruction!(OreInstruction, UnlockLp);
+
+/// Release an LP lock and pay its rewards. Unlocking early burns a penalty.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct UnlockLp {}
+
+instruction!(OreInstruction, QuoteGameTokenBacking);
+
+/// Report a game token's RNG backing and outstanding supply. Returns a `GameTokenBacking`
+/// as return data.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct QuoteGameTokenBacking {
+    /// The game token to report on (see `GameTokenType` in the program).
+    pub game_token_type: u8,
+}
+
+instruction!(OreInstruction, SetBuybackConfig);
+
+/// Configure the CRAP buyback-and-burn (admin only).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetBuybackConfig {
+    /// Share of accrued protocol SOL fees spent per buyback, in basis points. 0 disables.
+    pub share_bps: [u8; 8],
+    /// Most lamports a single buyback can spend.
+    pub max_sol: [u8; 8],
+    /// Minimum seconds between buybacks.
+    pub interval_seconds: [u8; 8],
+}
+
+instruction!(OreInstruction, BuybackCrap);
+
+/// Spend protocol SOL fees buying CRAP through the pools and burn it. Permissionless.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct BuybackCrap {}
+
+instruction!(OreInstruction, SetSlotNotionalCap);
+
+/// Set the cap on SOL notional swapped within one slot, as basis points of the SOL
+/// reserve (admin only).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, ShankType)]
+pub struct SetSlotNotionalCap {
+    /// Per-slot notional cap, in basis points of the SOL reserve. 0 disables the cap.
+    pub cap_bps: [u8; 8],
+}
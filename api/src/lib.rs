@@ -1,18 +1,28 @@
 pub mod consts;
 pub mod error;
 pub mod event;
+pub mod house_edge;
+pub mod idl;
 pub mod instruction;
+pub mod payout;
+#[cfg(feature = "sdk")]
 pub mod sdk;
+pub mod simulator;
 pub mod state;
 
 #[cfg(feature = "ts-bindings")]
 pub mod bindings;
 
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
 pub mod prelude {
     pub use crate::consts::*;
     pub use crate::error::*;
     pub use crate::event::*;
     pub use crate::instruction::*;
+    pub use crate::payout::*;
+    #[cfg(feature = "sdk")]
     pub use crate::sdk::*;
     pub use crate::state::*;
 }
@@ -0,0 +1,326 @@
+//! Craps payout formulas, shared by the program and off-chain clients so payout
+//! previews use exactly the on-chain math.
+
+use crate::{
+    consts::*,
+    error::OreError,
+    state::{index_to_point, CrapsPosition, NUM_POINTS},
+};
+
+/// Winnings on a bet at `payout_num:payout_den` odds, not including the stake.
+pub fn calculate_payout(bet_amount: u64, payout_num: u64, payout_den: u64) -> u64 {
+    // payout = bet_amount * payout_num / payout_den
+    // Use u128 to avoid overflow
+    ((bet_amount as u128 * payout_num as u128) / payout_den as u128) as u64
+}
+
+/// The maximum a bet can return, stake included, given its type and point.
+/// The house reserves this much bankroll when the bet is placed.
+pub fn max_payout(bet_type: u8, point: u8, amount: u64) -> Result<u64, OreError> {
+    // Invalid bets reserve only the stake; they are rejected later.
+    let Some((num, den)) = payout_odds(bet_type, point) else {
+        return Ok(amount);
+    };
+    // amount * (numerator / denominator) + amount
+    let payout = amount
+        .checked_mul(num)
+        .ok_or(OreError::ArithmeticOverflow)?
+        .checked_div(den)
+        .ok_or(OreError::ArithmeticOverflow)?;
+    amount
+        .checked_add(payout)
+        .ok_or(OreError::ArithmeticOverflow)
+}
+
+/// The most a position's open bets can return, stake included, with line odds priced
+/// at `table_point`. This is what placing each bet on its own reserves, and what
+/// settlement releases as the bets resolve.
+pub fn position_max_payout(position: &CrapsPosition, table_point: u8) -> Result<u64, OreError> {
+    let mut total: u64 = 0;
+    let mut add = |bet_type: u8, point: u8, amount: u64| -> Result<(), OreError> {
+        if amount > 0 {
+            total = total
+                .checked_add(max_payout(bet_type, point, amount)?)
+                .ok_or(OreError::ArithmeticOverflow)?;
+        }
+        Ok(())
+    };
+
+    add(0, 0, position.pass_line)?;
+    add(1, 0, position.dont_pass)?;
+    add(2, table_point, position.pass_odds)?;
+    add(3, table_point, position.dont_pass_odds)?;
+    add(10, 0, position.field_bet)?;
+    add(11, 0, position.any_seven)?;
+    add(12, 0, position.any_craps)?;
+    add(13, 0, position.yo_eleven)?;
+    add(14, 0, position.aces)?;
+    add(15, 0, position.twelve)?;
+    for i in 0..NUM_POINTS {
+        let point = index_to_point(i).unwrap_or(0);
+        add(4, point, position.come_bets[i])?;
+        add(5, point, position.dont_come_bets[i])?;
+        add(6, point, position.come_odds[i])?;
+        add(7, point, position.dont_come_odds[i])?;
+        add(8, point, position.place_bets[i])?;
+    }
+    for (bet, hardway) in position.hardways.iter().zip([4, 6, 8, 10]) {
+        add(9, hardway, *bet)?;
+    }
+    for i in 0..11 {
+        let sum = i as u8 + 2;
+        add(26, sum, position.yes_bets[i])?;
+        add(27, sum, position.no_bets[i])?;
+        add(28, sum, position.next_bets[i])?;
+    }
+    Ok(total)
+}
+
+/// The best odds a bet can pay as `(numerator, denominator)`, given its type and point.
+/// Bets whose payout depends on the point return `None` for points they can't be placed on.
+pub fn payout_odds(bet_type: u8, point: u8) -> Option<(u64, u64)> {
+    let odds = match bet_type {
+        // Pass Line (1:1)
+        0 => (PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN),
+        // Don't Pass (1:1)
+        1 => (PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN),
+        // Pass Odds - depends on point (2:1, 3:2, or 6:5)
+        2 => match point {
+            4 | 10 => (TRUE_ODDS_4_10_NUM, TRUE_ODDS_4_10_DEN),
+            5 | 9 => (TRUE_ODDS_5_9_NUM, TRUE_ODDS_5_9_DEN),
+            6 | 8 => (TRUE_ODDS_6_8_NUM, TRUE_ODDS_6_8_DEN),
+            _ => return None, // Shouldn't happen, but safe fallback
+        },
+        // Don't Pass Odds - lay odds (1:2, 2:3, or 5:6)
+        3 => match point {
+            4 | 10 => (TRUE_ODDS_4_10_DEN, TRUE_ODDS_4_10_NUM),
+            5 | 9 => (TRUE_ODDS_5_9_DEN, TRUE_ODDS_5_9_NUM),
+            6 | 8 => (TRUE_ODDS_6_8_DEN, TRUE_ODDS_6_8_NUM),
+            _ => return None,
+        },
+        // Come (1:1)
+        4 => (PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN),
+        // Don't Come (1:1)
+        5 => (PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN),
+        // Come Odds
+        6 => match point {
+            4 | 10 => (TRUE_ODDS_4_10_NUM, TRUE_ODDS_4_10_DEN),
+            5 | 9 => (TRUE_ODDS_5_9_NUM, TRUE_ODDS_5_9_DEN),
+            6 | 8 => (TRUE_ODDS_6_8_NUM, TRUE_ODDS_6_8_DEN),
+            _ => return None,
+        },
+        // Don't Come Odds - lay odds
+        7 => match point {
+            4 | 10 => (TRUE_ODDS_4_10_DEN, TRUE_ODDS_4_10_NUM),
+            5 | 9 => (TRUE_ODDS_5_9_DEN, TRUE_ODDS_5_9_NUM),
+            6 | 8 => (TRUE_ODDS_6_8_DEN, TRUE_ODDS_6_8_NUM),
+            _ => return None,
+        },
+        // Place bet
+        8 => match point {
+            4 | 10 => (PLACE_4_10_PAYOUT_NUM, PLACE_4_10_PAYOUT_DEN),
+            5 | 9 => (PLACE_5_9_PAYOUT_NUM, PLACE_5_9_PAYOUT_DEN),
+            6 | 8 => (PLACE_6_8_PAYOUT_NUM, PLACE_6_8_PAYOUT_DEN),
+            _ => return None,
+        },
+        // Hardway
+        9 => match point {
+            4 | 10 => (HARD_4_10_PAYOUT_NUM, HARD_4_10_PAYOUT_DEN),
+            6 | 8 => (HARD_6_8_PAYOUT_NUM, HARD_6_8_PAYOUT_DEN),
+            _ => return None,
+        },
+        // Field - worst case is 2:1
+        10 => (FIELD_PAYOUT_2_12_NUM, FIELD_PAYOUT_2_12_DEN),
+        // Any Seven (4:1)
+        11 => (ANY_SEVEN_PAYOUT_NUM, ANY_SEVEN_PAYOUT_DEN),
+        // Any Craps (7:1)
+        12 => (ANY_CRAPS_PAYOUT_NUM, ANY_CRAPS_PAYOUT_DEN),
+        // Yo Eleven (15:1)
+        13 => (YO_ELEVEN_PAYOUT_NUM, YO_ELEVEN_PAYOUT_DEN),
+        // Aces (30:1)
+        14 => (ACES_PAYOUT_NUM, ACES_PAYOUT_DEN),
+        // Twelve (30:1)
+        15 => (TWELVE_PAYOUT_NUM, TWELVE_PAYOUT_DEN),
+        // Bonus Small (30:1)
+        16 => (BONUS_SMALL_PAYOUT_NUM, BONUS_SMALL_PAYOUT_DEN),
+        // Bonus Tall (30:1)
+        17 => (BONUS_TALL_PAYOUT_NUM, BONUS_TALL_PAYOUT_DEN),
+        // Bonus All (150:1)
+        18 => (BONUS_ALL_PAYOUT_NUM, BONUS_ALL_PAYOUT_DEN),
+        // Fire Bet - worst case is all 6 points
+        19 => (FIRE_6_POINTS_PAYOUT_NUM, FIRE_6_POINTS_PAYOUT_DEN),
+        // Different Doubles - worst case is all 6 doubles
+        20 => (DIFF_DOUBLES_6_PAYOUT_NUM, DIFF_DOUBLES_6_PAYOUT_DEN),
+        // Ride the Line - worst case is 11+ wins
+        21 => (RIDE_11_WINS_PAYOUT_NUM, RIDE_11_WINS_PAYOUT_DEN),
+        // Mugsy's Corner - worst case is a point-phase 7
+        22 => (MUGSY_POINT_7_PAYOUT_NUM, MUGSY_POINT_7_PAYOUT_DEN),
+        // Hot Hand - worst case is all 10 totals
+        23 => (HOT_HAND_10_PAYOUT_NUM, HOT_HAND_10_PAYOUT_DEN),
+        // Replay - worst case is a 4 or 10 made 4+ times
+        24 => (REPLAY_4_10_4X_PAYOUT_NUM, REPLAY_4_10_4X_PAYOUT_DEN),
+        // Fielder's Choice - point selects the sub-bet
+        25 => match point {
+            0 => (FIELDERS_1_PAYOUT_NUM, FIELDERS_1_PAYOUT_DEN),
+            1 => (FIELDERS_2_PAYOUT_NUM, FIELDERS_2_PAYOUT_DEN),
+            2 => (FIELDERS_3_PAYOUT_NUM, FIELDERS_3_PAYOUT_DEN),
+            _ => return None,
+        },
+        // Yes bet (true odds) - sum before 7
+        26 => match point {
+            2 => (YES_2_PAYOUT_NUM, YES_2_PAYOUT_DEN),
+            3 => (YES_3_PAYOUT_NUM, YES_3_PAYOUT_DEN),
+            4 => (YES_4_PAYOUT_NUM, YES_4_PAYOUT_DEN),
+            5 => (YES_5_PAYOUT_NUM, YES_5_PAYOUT_DEN),
+            6 => (YES_6_PAYOUT_NUM, YES_6_PAYOUT_DEN),
+            8 => (YES_8_PAYOUT_NUM, YES_8_PAYOUT_DEN),
+            9 => (YES_9_PAYOUT_NUM, YES_9_PAYOUT_DEN),
+            10 => (YES_10_PAYOUT_NUM, YES_10_PAYOUT_DEN),
+            11 => (YES_11_PAYOUT_NUM, YES_11_PAYOUT_DEN),
+            12 => (YES_12_PAYOUT_NUM, YES_12_PAYOUT_DEN),
+            _ => return None, // 7 is invalid
+        },
+        // No bet (inverse true odds) - 7 before sum
+        27 => match point {
+            2 => (NO_2_PAYOUT_NUM, NO_2_PAYOUT_DEN),
+            3 => (NO_3_PAYOUT_NUM, NO_3_PAYOUT_DEN),
+            4 => (NO_4_PAYOUT_NUM, NO_4_PAYOUT_DEN),
+            5 => (NO_5_PAYOUT_NUM, NO_5_PAYOUT_DEN),
+            6 => (NO_6_PAYOUT_NUM, NO_6_PAYOUT_DEN),
+            8 => (NO_8_PAYOUT_NUM, NO_8_PAYOUT_DEN),
+            9 => (NO_9_PAYOUT_NUM, NO_9_PAYOUT_DEN),
+            10 => (NO_10_PAYOUT_NUM, NO_10_PAYOUT_DEN),
+            11 => (NO_11_PAYOUT_NUM, NO_11_PAYOUT_DEN),
+            12 => (NO_12_PAYOUT_NUM, NO_12_PAYOUT_DEN),
+            _ => return None, // 7 is invalid
+        },
+        // Next bet (single-roll true odds)
+        28 => match point {
+            2 => (HOP_2_PAYOUT_NUM, HOP_2_PAYOUT_DEN),
+            3 => (HOP_3_PAYOUT_NUM, HOP_3_PAYOUT_DEN),
+            4 => (HOP_4_PAYOUT_NUM, HOP_4_PAYOUT_DEN),
+            5 => (HOP_5_PAYOUT_NUM, HOP_5_PAYOUT_DEN),
+            6 => (HOP_6_PAYOUT_NUM, HOP_6_PAYOUT_DEN),
+            7 => (HOP_7_PAYOUT_NUM, HOP_7_PAYOUT_DEN),
+            8 => (HOP_8_PAYOUT_NUM, HOP_8_PAYOUT_DEN),
+            9 => (HOP_9_PAYOUT_NUM, HOP_9_PAYOUT_DEN),
+            10 => (HOP_10_PAYOUT_NUM, HOP_10_PAYOUT_DEN),
+            11 => (HOP_11_PAYOUT_NUM, HOP_11_PAYOUT_DEN),
+            12 => (HOP_12_PAYOUT_NUM, HOP_12_PAYOUT_DEN),
+            _ => return None,
+        },
+        _ => return None, // Invalid bet type, will be caught later
+    };
+    Some(odds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_payout() {
+        // Pass line pays even money.
+        assert_eq!(max_payout(0, 0, 100), Ok(200));
+        // Lay odds on 4 pay 1:2.
+        assert_eq!(max_payout(3, 4, 100), Ok(150));
+        // Unknown bet types reserve only the stake.
+        assert_eq!(max_payout(200, 0, 100), Ok(100));
+        assert_eq!(max_payout(18, 0, u64::MAX), Err(OreError::ArithmeticOverflow));
+    }
+
+    /// The odds every bet pays, written out by hand. A change to a payout constant
+    /// changes the house edge, so it has to change this table too.
+    #[test]
+    fn test_payout_odds_golden() {
+        let points = [4, 5, 6, 8, 9, 10];
+        let sums = [2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        // Bets listed without points pay the same whatever point they are given.
+        let golden: &[(u8, &[u8], &[(u64, u64)])] = &[
+            // Pass Line, Don't Pass
+            (0, &[], &[(1, 1)]),
+            (1, &[], &[(1, 1)]),
+            // Pass Odds take true odds; Don't Pass Odds lay them.
+            (2, &points, &[(2, 1), (3, 2), (6, 5), (6, 5), (3, 2), (2, 1)]),
+            (3, &points, &[(1, 2), (2, 3), (5, 6), (5, 6), (2, 3), (1, 2)]),
+            // Come, Don't Come, and their odds
+            (4, &[], &[(1, 1)]),
+            (5, &[], &[(1, 1)]),
+            (6, &points, &[(2, 1), (3, 2), (6, 5), (6, 5), (3, 2), (2, 1)]),
+            (7, &points, &[(1, 2), (2, 3), (5, 6), (5, 6), (2, 3), (1, 2)]),
+            // Place
+            (8, &points, &[(9, 5), (7, 5), (7, 6), (7, 6), (7, 5), (9, 5)]),
+            // Hardways
+            (9, &[4, 6, 8, 10], &[(7, 1), (9, 1), (9, 1), (7, 1)]),
+            // Field (2 and 12), Any Seven, Any Craps, Yo, Aces, Twelve
+            (10, &[], &[(2, 1)]),
+            (11, &[], &[(4, 1)]),
+            (12, &[], &[(7, 1)]),
+            (13, &[], &[(15, 1)]),
+            (14, &[], &[(30, 1)]),
+            (15, &[], &[(30, 1)]),
+            // Side bets at their best payouts. Fielder's Choice picks its sub-bet by point.
+            (16, &[], &[(30, 1)]),
+            (17, &[], &[(30, 1)]),
+            (18, &[], &[(150, 1)]),
+            (19, &[], &[(999, 1)]),
+            (20, &[], &[(100, 1)]),
+            (21, &[], &[(150, 1)]),
+            (22, &[], &[(3, 1)]),
+            (23, &[], &[(80, 1)]),
+            (24, &[], &[(1000, 1)]),
+            (25, &[0, 1, 2], &[(4, 1), (2, 1), (4, 1)]),
+            // Yes and No on every sum but 7
+            (
+                26,
+                &[2, 3, 4, 5, 6, 8, 9, 10, 11, 12],
+                &[(6, 1), (3, 1), (2, 1), (3, 2), (6, 5), (6, 5), (3, 2), (2, 1), (3, 1), (6, 1)],
+            ),
+            (
+                27,
+                &[2, 3, 4, 5, 6, 8, 9, 10, 11, 12],
+                &[(1, 6), (1, 3), (1, 2), (2, 3), (5, 6), (5, 6), (2, 3), (1, 2), (1, 3), (1, 6)],
+            ),
+            // Hop on every sum
+            (
+                28,
+                &sums,
+                &[
+                    (35, 1),
+                    (17, 1),
+                    (11, 1),
+                    (8, 1),
+                    (31, 5),
+                    (5, 1),
+                    (31, 5),
+                    (8, 1),
+                    (11, 1),
+                    (17, 1),
+                    (35, 1),
+                ],
+            ),
+        ];
+
+        for &(bet_type, bet_points, odds) in golden {
+            for point in 0..=u8::MAX {
+                let expected = match bet_points.iter().position(|&p| p == point) {
+                    _ if bet_points.is_empty() => Some(odds[0]),
+                    Some(i) => Some(odds[i]),
+                    None => None,
+                };
+                assert_eq!(
+                    payout_odds(bet_type, point),
+                    expected,
+                    "bet type {} point {}",
+                    bet_type,
+                    point
+                );
+            }
+        }
+
+        // Nothing past Hop pays.
+        for bet_type in 29..=u8::MAX {
+            assert_eq!(payout_odds(bet_type, 0), None);
+        }
+    }
+}
@@ -3,7 +3,10 @@ use spl_associated_token_account::get_associated_token_address;
 use steel::*;
 
 use crate::{
-    consts::{BOARD, BOARD_SIZE, MINT_ADDRESS, SOL_MINT, TREASURY_ADDRESS},
+    consts::{
+        BOARD, BOARD_SIZE, CLAIM_CRANK_SOL, CLAIM_CRANK_VESTED, MINT_ADDRESS, RNG_MINT_ADDRESS,
+        SOL_MINT, TREASURY_ADDRESS,
+    },
     instruction::*,
     state::*,
 };
@@ -24,6 +27,7 @@ pub fn initialize(signer: Pubkey) -> Instruction {
             AccountMeta::new(treasury_address, false),
             AccountMeta::new(round_address, false),
             AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
         ],
         data: Initialize {}.to_bytes(),
     }
@@ -53,6 +57,8 @@ pub fn automate(
     fee: u64,
     mask: u64,
     strategy: u8,
+    round_limit: u64,
+    budget: u64,
 ) -> Instruction {
     let automation_address = automation_pda(signer).0;
     let miner_address = miner_pda(signer).0;
@@ -71,6 +77,8 @@ pub fn automate(
             fee: fee.to_le_bytes(),
             mask: mask.to_le_bytes(),
             strategy: strategy as u8,
+            round_limit: round_limit.to_le_bytes(),
+            budget: budget.to_le_bytes(),
         }
         .to_bytes(),
     }
@@ -84,6 +92,7 @@ pub fn claim_sol(signer: Pubkey) -> Instruction {
             AccountMeta::new(signer, true),
             AccountMeta::new(miner_address, false),
             AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
         ],
         data: ClaimSOL {}.to_bytes(),
     }
@@ -108,11 +117,175 @@ pub fn claim_ore(signer: Pubkey) -> Instruction {
             AccountMeta::new_readonly(system_program::ID, false),
             AccountMeta::new_readonly(spl_token::ID, false),
             AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new(vesting_pda(signer).0, false),
+            AccountMeta::new_readonly(stake_pda(signer).0, false),
         ],
         data: ClaimORE {}.to_bytes(),
     }
 }
 
+/// Builds a ClaimSOL signed by a session delegate of the authority holding the Claims
+/// permission. The SOL is paid to the authority.
+pub fn claim_sol_with_session(delegate: Pubkey, authority: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(delegate, true),
+            AccountMeta::new(miner_pda(authority).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new(authority, false),
+            AccountMeta::new_readonly(session_pda(authority).0, false),
+        ],
+        data: ClaimSOL {}.to_bytes(),
+    }
+}
+
+/// Builds a ClaimORE signed by a session delegate of the authority holding the Claims
+/// permission. The ORE is paid to the authority's existing token account.
+pub fn claim_ore_with_session(delegate: Pubkey, authority: Pubkey) -> Instruction {
+    let treasury_address = treasury_pda().0;
+    let treasury_tokens_address = get_associated_token_address(&treasury_address, &MINT_ADDRESS);
+    let recipient_address = get_associated_token_address(&authority, &MINT_ADDRESS);
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(delegate, true),
+            AccountMeta::new(miner_pda(authority).0, false),
+            AccountMeta::new(MINT_ADDRESS, false),
+            AccountMeta::new(recipient_address, false),
+            AccountMeta::new(treasury_address, false),
+            AccountMeta::new(treasury_tokens_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new(vesting_pda(authority).0, false),
+            AccountMeta::new_readonly(stake_pda(authority).0, false),
+            AccountMeta::new_readonly(session_pda(authority).0, false),
+        ],
+        data: ClaimORE {}.to_bytes(),
+    }
+}
+
+/// Pushes SOL rewards out to the given miners' authorities, tipping the signer.
+pub fn crank_sol_claims(signer: Pubkey, authorities: &[Pubkey]) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(config_pda().0, false),
+        AccountMeta::new_readonly(param_pda(PARAM_CLAIM_CRANK_TIP_SOL).0, false),
+    ];
+    for authority in authorities {
+        accounts.push(AccountMeta::new(miner_pda(*authority).0, false));
+        accounts.push(AccountMeta::new(*authority, false));
+    }
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: CrankClaims {
+            kind: CLAIM_CRANK_SOL,
+            _padding: [0; 7],
+        }
+        .to_bytes(),
+    }
+}
+
+/// Releases vested ORE to the given authorities' token accounts, tipping the signer.
+pub fn crank_vested_claims(signer: Pubkey, authorities: &[Pubkey]) -> Instruction {
+    let treasury_address = treasury_pda().0;
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(config_pda().0, false),
+        AccountMeta::new_readonly(param_pda(PARAM_CLAIM_CRANK_TIP_ORE).0, false),
+        AccountMeta::new(get_associated_token_address(&signer, &MINT_ADDRESS), false),
+        AccountMeta::new_readonly(MINT_ADDRESS, false),
+        AccountMeta::new_readonly(treasury_address, false),
+        AccountMeta::new(get_associated_token_address(&treasury_address, &MINT_ADDRESS), false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+    ];
+    for authority in authorities {
+        accounts.push(AccountMeta::new(vesting_pda(*authority).0, false));
+        accounts.push(AccountMeta::new(
+            get_associated_token_address(authority, &MINT_ADDRESS),
+            false,
+        ));
+    }
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: CrankClaims {
+            kind: CLAIM_CRANK_VESTED,
+            _padding: [0; 7],
+        }
+        .to_bytes(),
+    }
+}
+
+/// Create or renew the authority's session for `delegate`. `allowed_operations` is a
+/// bitmask of `SessionOperation`s, or 0 for all of them. `deploy_budget` caps what the
+/// delegate may deploy through a Delegated automation.
+pub fn create_session(
+    authority: Pubkey,
+    payer: Pubkey,
+    delegate: Pubkey,
+    duration: i64,
+    allowed_operations: u64,
+    deploy_budget: u64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new(session_pda(authority).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: CreateSession {
+            delegate: delegate.to_bytes(),
+            duration: duration.to_le_bytes(),
+            allowed_operations: allowed_operations.to_le_bytes(),
+            deploy_budget: deploy_budget.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Close the authority's session, refunding its rent to `payer`.
+pub fn revoke_session(authority: Pubkey, payer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(session_pda(authority).0, false),
+            AccountMeta::new(payer, false),
+        ],
+        data: RevokeSession {}.to_bytes(),
+    }
+}
+
+pub fn claim_vested(signer: Pubkey) -> Instruction {
+    let treasury_address = treasury_pda().0;
+    let treasury_tokens_address = get_associated_token_address(&treasury_address, &MINT_ADDRESS);
+    let recipient_address = get_associated_token_address(&signer, &MINT_ADDRESS);
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(vesting_pda(signer).0, false),
+            AccountMeta::new(MINT_ADDRESS, false),
+            AccountMeta::new(recipient_address, false),
+            AccountMeta::new_readonly(treasury_address, false),
+            AccountMeta::new(treasury_tokens_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+        ],
+        data: ClaimVested {}.to_bytes(),
+    }
+}
+
 // let [signer_info, authority_info, automation_info, board_info, miner_info, round_info, system_program] =
 // [signer_rng_ata, round_rng_ata, rng_mint, token_program] [var_info, entropy_program]
 
@@ -187,6 +360,9 @@ pub fn deploy(
             // Entropy accounts (2)
             AccountMeta::new(entropy_var_address, false),
             AccountMeta::new_readonly(entropy_api::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+            // Previous round, for the follow-last-winner automation strategy
+            AccountMeta::new_readonly(round_pda(round_id.saturating_sub(1)).0, false),
         ],
         data: Deploy {
             amount: amount.to_le_bytes(),
@@ -198,6 +374,174 @@ pub fn deploy(
     }
 }
 
+/// Builds a Deploy signed by the executor of a Delegated automation, spending from
+/// the deploy budget of the authority's session with the Mining permission.
+pub fn deploy_with_session(
+    executor: Pubkey,
+    authority: Pubkey,
+    amount: u64,
+    round_id: u64,
+    squares: [bool; BOARD_SIZE],
+) -> Instruction {
+    let mut ix = deploy(executor, authority, amount, round_id, squares, 0);
+    ix.accounts.push(AccountMeta::new(session_pda(authority).0, false));
+    ix
+}
+
+/// Deploys a specific amount to each listed square in a single instruction.
+/// Each entry is a `(square, amount)` pair.
+pub fn deploy_many(
+    signer: Pubkey,
+    round_id: u64,
+    entries: &[(u8, u64)],
+    dice_prediction: u8,
+) -> Instruction {
+    use crate::consts::RNG_MINT_ADDRESS;
+
+    let board_address = board_pda().0;
+    let miner_address = miner_pda(signer).0;
+    let round_address = round_pda(round_id).0;
+    let entropy_var_address = entropy_api::state::var_pda(board_address, 0).0;
+    let signer_rng_ata = get_associated_token_address(&signer, &RNG_MINT_ADDRESS);
+    let round_rng_ata = get_associated_token_address(&round_address, &RNG_MINT_ADDRESS);
+
+    let mut data = DeployMany {
+        dice_prediction,
+        _padding: [0; 7],
+    }
+    .to_bytes();
+    for (square, amount) in entries {
+        data.push(*square);
+        data.extend_from_slice(&amount.to_le_bytes());
+    }
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(board_address, false),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new(round_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(signer_rng_ata, false),
+            AccountMeta::new(round_rng_ata, false),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new(entropy_var_address, false),
+            AccountMeta::new_readonly(entropy_api::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+        ],
+        data,
+    }
+}
+
+/// Spreads `amount` evenly across the squares of a `DeployPreset`.
+pub fn deploy_spread(
+    signer: Pubkey,
+    round_id: u64,
+    amount: u64,
+    preset: DeployPreset,
+    dice_prediction: u8,
+) -> Instruction {
+    use crate::consts::RNG_MINT_ADDRESS;
+
+    let board_address = board_pda().0;
+    let miner_address = miner_pda(signer).0;
+    let round_address = round_pda(round_id).0;
+    let entropy_var_address = entropy_api::state::var_pda(board_address, 0).0;
+    let signer_rng_ata = get_associated_token_address(&signer, &RNG_MINT_ADDRESS);
+    let round_rng_ata = get_associated_token_address(&round_address, &RNG_MINT_ADDRESS);
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(board_address, false),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new(round_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(signer_rng_ata, false),
+            AccountMeta::new(round_rng_ata, false),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new(entropy_var_address, false),
+            AccountMeta::new_readonly(entropy_api::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+        ],
+        data: DeploySpread {
+            amount: amount.to_le_bytes(),
+            preset: preset as u8,
+            dice_prediction,
+            _padding: [0; 6],
+        }
+        .to_bytes(),
+    }
+}
+
+/// Swaps up to `sol_amount` lamports for RNG through `swap_program` and spreads the RNG
+/// received across `squares`. The swap accounts and data come from the router's quote.
+pub fn deploy_with_sol(
+    signer: Pubkey,
+    round_id: u64,
+    swap_program: Pubkey,
+    sol_amount: u64,
+    min_rng_out: u64,
+    squares: [bool; BOARD_SIZE],
+    dice_prediction: u8,
+    swap_accounts: &[AccountMeta],
+    swap_data: &[u8],
+) -> Instruction {
+    use crate::consts::RNG_MINT_ADDRESS;
+
+    let board_address = board_pda().0;
+    let miner_address = miner_pda(signer).0;
+    let round_address = round_pda(round_id).0;
+    let entropy_var_address = entropy_api::state::var_pda(board_address, 0).0;
+    let signer_rng_ata = get_associated_token_address(&signer, &RNG_MINT_ADDRESS);
+    let round_rng_ata = get_associated_token_address(&round_address, &RNG_MINT_ADDRESS);
+
+    let mut mask: u64 = 0;
+    for (i, &square) in squares.iter().enumerate() {
+        if square {
+            mask |= 1 << i;
+        }
+    }
+
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(board_address, false),
+        AccountMeta::new(miner_address, false),
+        AccountMeta::new(round_address, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new(signer_rng_ata, false),
+        AccountMeta::new(round_rng_ata, false),
+        AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new(entropy_var_address, false),
+        AccountMeta::new_readonly(entropy_api::ID, false),
+        AccountMeta::new_readonly(config_pda().0, false),
+        AccountMeta::new_readonly(swap_program, false),
+    ];
+    for account in swap_accounts.iter() {
+        let mut acc_clone = account.clone();
+        acc_clone.is_signer = false;
+        accounts.push(acc_clone);
+    }
+    let mut data = DeployWithSol {
+        sol_amount: sol_amount.to_le_bytes(),
+        min_rng_out: min_rng_out.to_le_bytes(),
+        squares: mask.to_le_bytes(),
+        dice_prediction,
+        _padding: [0; 7],
+    }
+    .to_bytes();
+    data.extend_from_slice(swap_data);
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data,
+    }
+}
+
 // let [pool, user_source_token, user_destination_token, a_vault, b_vault, a_token_vault, b_token_vault, a_vault_lp_mint, b_vault_lp_mint, a_vault_lp, b_vault_lp, protocol_token_fee, user_key, vault_program, token_program] =
 
 pub fn bury(signer: Pubkey, swap_accounts: &[AccountMeta], swap_data: &[u8]) -> Instruction {
@@ -206,7 +550,7 @@ pub fn bury(signer: Pubkey, swap_accounts: &[AccountMeta], swap_data: &[u8]) ->
     let mint_address = MINT_ADDRESS;
     let treasury_address = TREASURY_ADDRESS;
     let treasury_ore_address = get_associated_token_address(&treasury_address, &MINT_ADDRESS);
-    let treasury_sol_address = get_associated_token_address(&treasury_address, &SOL_MINT);
+    let treasury_sol_address = treasury_sol_address();
     let mut accounts = vec![
         AccountMeta::new(signer, true),
         AccountMeta::new(board_address, false),
@@ -232,10 +576,67 @@ pub fn bury(signer: Pubkey, swap_accounts: &[AccountMeta], swap_data: &[u8]) ->
     }
 }
 
+pub fn route_swap(
+    signer: Pubkey,
+    input_mint: Pubkey,
+    output_mint: Pubkey,
+    swap_program: Pubkey,
+    amount_in: u64,
+    min_amount_out: u64,
+    swap_accounts: &[AccountMeta],
+    swap_data: &[u8],
+) -> Instruction {
+    let config_address = config_pda().0;
+    let treasury_address = TREASURY_ADDRESS;
+    let treasury_input_address = get_associated_token_address(&treasury_address, &input_mint);
+    let treasury_output_address = get_associated_token_address(&treasury_address, &output_mint);
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(config_address, false),
+        AccountMeta::new(treasury_address, false),
+        AccountMeta::new(treasury_input_address, false),
+        AccountMeta::new(treasury_output_address, false),
+        AccountMeta::new_readonly(input_mint, false),
+        AccountMeta::new_readonly(output_mint, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new_readonly(swap_program, false),
+    ];
+    for account in swap_accounts.iter() {
+        let mut acc_clone = account.clone();
+        acc_clone.is_signer = false;
+        accounts.push(acc_clone);
+    }
+    let mut data = RouteSwap {
+        amount_in: amount_in.to_le_bytes(),
+        min_amount_out: min_amount_out.to_le_bytes(),
+    }
+    .to_bytes();
+    data.extend_from_slice(swap_data);
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data,
+    }
+}
+
+pub fn distribute_revenue(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new(TREASURY_ADDRESS, false),
+            AccountMeta::new(insurance_fund_pda().0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: DistributeRevenue {}.to_bytes(),
+    }
+}
+
 pub fn wrap(signer: Pubkey) -> Instruction {
     let config_address = config_pda().0;
     let treasury_address = TREASURY_ADDRESS;
-    let treasury_sol_address = get_associated_token_address(&treasury_address, &SOL_MINT);
+    let treasury_sol_address = treasury_sol_address();
     Instruction {
         accounts: vec![
             AccountMeta::new(signer, true),
@@ -249,7 +650,7 @@ pub fn wrap(signer: Pubkey) -> Instruction {
     }
 }
 
-// let [signer_info, board_info, config_info, fee_collector_info, mint_info, round_info, round_next_info, top_miner_info, treasury_info, treasury_tokens_info, system_program, token_program, ore_program, slot_hashes_sysvar] =
+// let [signer_info, board_info, config_info, fee_collector_info, mint_info, round_info, round_next_info, top_miner_info, treasury_info, treasury_tokens_info, system_program, token_program, ore_program, slot_hashes_sysvar, roll_proof_info, roles_info, param_info, craps_game_info] =
 
 pub fn reset(
     signer: Pubkey,
@@ -283,6 +684,11 @@ pub fn reset(
             AccountMeta::new_readonly(spl_token::ID, false),
             AccountMeta::new_readonly(crate::ID, false),
             AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
+            AccountMeta::new(roll_proof_pda(round_id).0, false),
+            AccountMeta::new_readonly(roles_pda().0, false),
+            AccountMeta::new_readonly(param_pda(PARAM_ROUND_RESET_BOUNTY).0, false),
+            AccountMeta::new_readonly(param_pda(PARAM_ROLLS_PER_ROUND).0, false),
+            AccountMeta::new(craps_game_pda().0, false),
             // Entropy accounts.
             AccountMeta::new(entropy_var_address, false),
             AccountMeta::new_readonly(entropy_api::ID, false),
@@ -291,307 +697,2602 @@ pub fn reset(
     }
 }
 
-// let [signer_info, board_info, rent_payer_info, round_info, treasury_info, system_program] =
+/// Reset with the test roll oracle appended (localnet/devnet builds only).
+pub fn reset_with_test_roll(
+    signer: Pubkey,
+    fee_collector: Pubkey,
+    round_id: u64,
+    top_miner: Pubkey,
+) -> Instruction {
+    let mut ix = reset(signer, fee_collector, round_id, top_miner);
+    ix.accounts
+        .push(AccountMeta::new_readonly(test_roll_oracle_pda().0, false));
+    ix
+}
 
-pub fn close(signer: Pubkey, round_id: u64, rent_payer: Pubkey) -> Instruction {
-    let board_address = board_pda().0;
-    let treasury_address = TREASURY_ADDRESS;
-    let round_address = round_pda(round_id).0;
-    Instruction {
+/// Inject a dice roll for a round into the test roll oracle (admin only, localnet/devnet builds).
+pub fn set_test_roll(signer: Pubkey, round_id: u64, winning_square: u64) -> Instruction {
+    set_test_rolls(signer, round_id, &[winning_square])
+}
+
+/// Inject every sub-roll of a round into the test roll oracle, starting with the winning
+/// square (admin only, localnet/devnet builds). Sub-rolls not given are left to the slot hash.
+pub fn set_test_rolls(signer: Pubkey, round_id: u64, squares: &[u64]) -> Instruction {
+    use crate::consts::MAX_ROLLS_PER_ROUND;
+
+    let mut sub_rolls = [0; MAX_ROLLS_PER_ROUND as usize];
+    for (index, square) in squares.iter().enumerate().skip(1) {
+        if let Some(sub_roll) = sub_rolls.get_mut(index) {
+            *sub_roll = (square + 1).min(u8::MAX as u64) as u8;
+        }
+    }
+    let config_address = config_pda().0;
+    let test_roll_oracle_address = test_roll_oracle_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(config_address, false),
+            AccountMeta::new(test_roll_oracle_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: SetTestRoll {
+            round_id: round_id.to_le_bytes(),
+            winning_square: squares.first().copied().unwrap_or(0).to_le_bytes(),
+            sub_rolls,
+        }
+        .to_bytes(),
+    }
+}
+
+/// Initialize the program and open a craps table with `house_bankroll` CRAP minted into
+/// the vault (localnet builds only). The signer becomes admin and must hold the CRAP
+/// mint authority.
+pub fn bootstrap(signer: Pubkey, house_bankroll: u64) -> Instruction {
+    use crate::consts::CRAP_MINT_ADDRESS;
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(board_pda().0, false),
+            AccountMeta::new(config_pda().0, false),
+            AccountMeta::new(treasury_pda().0, false),
+            AccountMeta::new(round_pda(0).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+            AccountMeta::new(craps_game_pda().0, false),
+            AccountMeta::new_readonly(craps_vault_pda().0, false),
+            AccountMeta::new(craps_vault_tokens_address(), false),
+            AccountMeta::new(CRAP_MINT_ADDRESS, false),
+            AccountMeta::new(treasury_tokens_address(), false),
+            AccountMeta::new_readonly(MINT_ADDRESS, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+        ],
+        data: Bootstrap {
+            house_bankroll: house_bankroll.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Mint test RNG to the signer from the faucet (devnet builds only).
+pub fn faucet_rng(signer: Pubkey) -> Instruction {
+    use crate::consts::RNG_MINT_ADDRESS;
+
+    faucet(signer, RNG_MINT_ADDRESS, FaucetRng {}.to_bytes())
+}
+
+/// Mint test CRAP to the signer from the faucet (devnet builds only).
+pub fn faucet_crap(signer: Pubkey) -> Instruction {
+    use crate::consts::CRAP_MINT_ADDRESS;
+
+    faucet(signer, CRAP_MINT_ADDRESS, FaucetCrap {}.to_bytes())
+}
+
+fn faucet(signer: Pubkey, mint: Pubkey, data: Vec<u8>) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(faucet_claim_pda(signer).0, false),
+            AccountMeta::new_readonly(faucet_pda().0, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new(get_associated_token_address(&signer, &mint), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+        ],
+        data,
+    }
+}
+
+/// Open the protocol metrics account.
+pub fn open_metrics(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(metrics_pda().0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: OpenMetrics {}.to_bytes(),
+    }
+}
+
+/// Fund the craps house bankroll with CRAP from the signer's token account.
+pub fn fund_craps_house(signer: Pubkey, amount: u64) -> Instruction {
+    use crate::consts::CRAP_MINT_ADDRESS;
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(craps_game_pda().0, false),
+            AccountMeta::new_readonly(craps_vault_pda().0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &CRAP_MINT_ADDRESS), false),
+            AccountMeta::new(craps_vault_tokens_address(), false),
+            AccountMeta::new_readonly(CRAP_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+        ],
+        data: FundCrapsHouse {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Place a craps bet of `amount` CRAP. `point` selects the number for come, place,
+/// hardway and true-odds bets, and is ignored otherwise.
+pub fn place_craps_bet(signer: Pubkey, bet_type: CrapsBetType, point: u8, amount: u64) -> Instruction {
+    use crate::consts::CRAP_MINT_ADDRESS;
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(craps_game_pda().0, false),
+            AccountMeta::new(craps_position_pda(signer).0, false),
+            AccountMeta::new_readonly(craps_vault_pda().0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &CRAP_MINT_ADDRESS), false),
+            AccountMeta::new(craps_vault_tokens_address(), false),
+            AccountMeta::new_readonly(CRAP_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(board_pda().0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new(craps_side_bets_pda(signer).0, false),
+            AccountMeta::new(metrics_pda().0, false),
+        ],
+        data: PlaceCrapsBet {
+            bet_type: bet_type as u8,
+            point,
+            _padding: [0; 6],
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Place craps bets as one hedged bundle of `(bet_type, point, amount)` legs. The house
+/// reserves the most the bundle can pay on any roll instead of each leg's maximum.
+///
+/// Panics if given more than `MAX_BUNDLE_LEGS` legs.
+pub fn place_craps_bundle(signer: Pubkey, legs: &[(CrapsBetType, u8, u64)]) -> Instruction {
+    use crate::consts::MAX_BUNDLE_LEGS;
+
+    assert!(legs.len() <= MAX_BUNDLE_LEGS, "a bundle holds at most {} legs", MAX_BUNDLE_LEGS);
+    let mut args = PlaceCrapsBundle::zeroed();
+    for (i, &(bet_type, point, amount)) in legs.iter().enumerate() {
+        args.bet_types[i] = bet_type as u8;
+        args.points[i] = point;
+        args.amounts[i] = amount.to_le_bytes();
+    }
+
+    // Bundles take the same accounts as a single bet.
+    Instruction {
+        data: args.to_bytes(),
+        ..place_craps_bet(signer, CrapsBetType::PassLine, 0, 0)
+    }
+}
+
+/// Settle the signer's craps position against a finished round.
+/// `winning_square` must match the roll recorded on the round.
+pub fn settle_craps(signer: Pubkey, round_id: u64, winning_square: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(craps_game_pda().0, false),
+            AccountMeta::new(craps_position_pda(signer).0, false),
+            AccountMeta::new_readonly(round_pda(round_id).0, false),
+            AccountMeta::new(craps_side_bets_pda(signer).0, false),
+            AccountMeta::new(metrics_pda().0, false),
+        ],
+        data: SettleCraps {
+            winning_square: winning_square.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Claim the signer's pending craps winnings from the craps vault.
+pub fn claim_craps_winnings(signer: Pubkey) -> Instruction {
+    use crate::consts::CRAP_MINT_ADDRESS;
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(craps_game_pda().0, false),
+            AccountMeta::new(craps_position_pda(signer).0, false),
+            AccountMeta::new_readonly(craps_vault_pda().0, false),
+            AccountMeta::new(craps_vault_tokens_address(), false),
+            AccountMeta::new(get_associated_token_address(&signer, &CRAP_MINT_ADDRESS), false),
+            AccountMeta::new_readonly(CRAP_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: ClaimCrapsWinnings {}.to_bytes(),
+    }
+}
+
+/// Check the craps house is solvent. Read-only: the instruction fails if the bankroll
+/// doesn't cover reserved payouts or the vault holds less than the bankroll plus `escrow`.
+pub fn assert_solvency(escrow: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(craps_game_pda().0, false),
+            AccountMeta::new_readonly(craps_vault_tokens_address(), false),
+        ],
+        data: AssertSolvency {
+            escrow: escrow.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Preview settling a craps position against a given square. Read-only: run it through
+/// simulateTransaction and parse the return data with `SettlePreview::from_bytes`.
+pub fn simulate_settle(authority: Pubkey, winning_square: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(craps_game_pda().0, false),
+            AccountMeta::new_readonly(craps_position_pda(authority).0, false),
+            AccountMeta::new_readonly(craps_side_bets_pda(authority).0, false),
+        ],
+        data: SimulateSettle {
+            winning_square: winning_square.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Schedule a withdrawal of excess house bankroll (admin only).
+pub fn schedule_house_withdrawal(signer: Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_pda().0, false),
+            AccountMeta::new_readonly(craps_game_pda().0, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: ScheduleHouseWithdrawal {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Execute a scheduled house bankroll withdrawal to the fee collector's CRAP
+/// token account (admin only).
+pub fn withdraw_house_bankroll(signer: Pubkey, fee_collector: Pubkey) -> Instruction {
+    use crate::consts::CRAP_MINT_ADDRESS;
+
+    let craps_vault_address = craps_vault_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_pda().0, false),
+            AccountMeta::new(craps_game_pda().0, false),
+            AccountMeta::new_readonly(craps_vault_address, false),
+            AccountMeta::new(craps_vault_tokens_address(), false),
+            AccountMeta::new(
+                get_associated_token_address(&fee_collector, &CRAP_MINT_ADDRESS),
+                false,
+            ),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: WithdrawHouseBankroll {}.to_bytes(),
+    }
+}
+
+// let [signer_info, board_info, rent_payer_info, round_info, treasury_info, system_program] =
+
+/// Set the share of net house wins routed to stakers each epoch (admin only).
+pub fn set_house_profit_share(signer: Pubkey, share_bps: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new(craps_game_pda().0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetHouseProfitShare {
+            share_bps: share_bps.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Swap the stakers' share of net house wins to ORE through `swap_program` and credit it
+/// as staking yield (bury authority only). The swap accounts and data come from the
+/// router's quote.
+pub fn distribute_house_profit(
+    signer: Pubkey,
+    swap_program: Pubkey,
+    min_ore_out: u64,
+    swap_accounts: &[AccountMeta],
+    swap_data: &[u8],
+) -> Instruction {
+    use crate::consts::CRAP_MINT_ADDRESS;
+
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(config_pda().0, false),
+        AccountMeta::new(craps_game_pda().0, false),
+        AccountMeta::new_readonly(craps_vault_pda().0, false),
+        AccountMeta::new(craps_vault_tokens_address(), false),
+        AccountMeta::new(TREASURY_ADDRESS, false),
+        AccountMeta::new(get_associated_token_address(&TREASURY_ADDRESS, &CRAP_MINT_ADDRESS), false),
+        AccountMeta::new(treasury_tokens_address(), false),
+        AccountMeta::new_readonly(CRAP_MINT_ADDRESS, false),
+        AccountMeta::new_readonly(MINT_ADDRESS, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new_readonly(swap_program, false),
+    ];
+    for account in swap_accounts.iter() {
+        let mut acc_clone = account.clone();
+        acc_clone.is_signer = false;
+        accounts.push(acc_clone);
+    }
+    let mut data = DistributeHouseProfit {
+        min_ore_out: min_ore_out.to_le_bytes(),
+    }
+    .to_bytes();
+    data.extend_from_slice(swap_data);
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data,
+    }
+}
+
+pub fn close(signer: Pubkey, round_id: u64, rent_payer: Pubkey) -> Instruction {
+    let board_address = board_pda().0;
+    let treasury_address = TREASURY_ADDRESS;
+    let round_address = round_pda(round_id).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(board_address, false),
+            AccountMeta::new(rent_payer, false),
+            AccountMeta::new(round_address, false),
+            AccountMeta::new(treasury_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(param_pda(PARAM_ROUND_CLOSE_BOUNTY).0, false),
+        ],
+        data: Close {}.to_bytes(),
+    }
+}
+
+pub fn archive_round(signer: Pubkey, round_id: u64, rent_payer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(board_pda().0, false),
+            AccountMeta::new(round_pda(round_id).0, false),
+            AccountMeta::new(round_archive_pda(round_id).0, false),
+            AccountMeta::new(rent_payer, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: ArchiveRound {}.to_bytes(),
+    }
+}
+
+/// Builds a checkpoint against a round archive. `deployed`, `count` and `proof` are
+/// the winning square's totals and Merkle proof, from `round_square_proof`.
+pub fn checkpoint_archived(
+    signer: Pubkey,
+    authority: Pubkey,
+    round_id: u64,
+    deployed: u64,
+    count: u64,
+    proof: [[u8; 32]; ROUND_ARCHIVE_TREE_DEPTH],
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(board_pda().0, false),
+            AccountMeta::new(miner_pda(authority).0, false),
+            AccountMeta::new_readonly(round_pda(round_id).0, false),
+            AccountMeta::new(round_archive_pda(round_id).0, false),
+            AccountMeta::new(TREASURY_ADDRESS, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(stake_pda(authority).0, false),
+        ],
+        data: CheckpointArchived {
+            deployed: deployed.to_le_bytes(),
+            count: count.to_le_bytes(),
+            proof,
+        }
+        .to_bytes(),
+    }
+}
+
+// let [signer_info, automation_info, board_info, miner_info, round_info, treasury_info, system_program] =
+
+pub fn checkpoint(signer: Pubkey, authority: Pubkey, round_id: u64) -> Instruction {
+    let miner_address = miner_pda(authority).0;
+    let board_address = board_pda().0;
+    let round_address = round_pda(round_id).0;
+    let treasury_address = TREASURY_ADDRESS;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(board_address, false),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new(round_address, false),
+            AccountMeta::new(treasury_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new_readonly(stake_pda(authority).0, false),
+        ],
+        data: Checkpoint {}.to_bytes(),
+    }
+}
+
+pub fn open_pool(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(pool_pda(signer).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: OpenPool {}.to_bytes(),
+    }
+}
+
+fn pool_membership_accounts(signer: Pubkey, pool: Pubkey) -> Vec<AccountMeta> {
+    use crate::consts::RNG_MINT_ADDRESS;
+    vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(pool, false),
+        AccountMeta::new(pool_member_pda(pool, signer).0, false),
+        AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+        AccountMeta::new(get_associated_token_address(&pool, &RNG_MINT_ADDRESS), false),
+        AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+    ]
+}
+
+pub fn join_pool(signer: Pubkey, pool: Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: pool_membership_accounts(signer, pool),
+        data: JoinPool {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn leave_pool(signer: Pubkey, pool: Pubkey, shares: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: pool_membership_accounts(signer, pool),
+        data: LeavePool {
+            shares: shares.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn claim_pool_rewards(signer: Pubkey, pool: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(pool_member_pda(pool, signer).0, false),
+            AccountMeta::new_readonly(MINT_ADDRESS, false),
+            AccountMeta::new(get_associated_token_address(&signer, &MINT_ADDRESS), false),
+            AccountMeta::new(get_associated_token_address(&pool, &MINT_ADDRESS), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+        ],
+        data: ClaimPoolRewards {}.to_bytes(),
+    }
+}
+
+/// Deploys the signer's pool RNG, as `(square, amount)` pairs.
+pub fn pool_deploy(
+    signer: Pubkey,
+    round_id: u64,
+    entries: &[(u8, u64)],
+    dice_prediction: u8,
+) -> Instruction {
+    use crate::consts::RNG_MINT_ADDRESS;
+
+    let pool_address = pool_pda(signer).0;
+    let board_address = board_pda().0;
+    let round_address = round_pda(round_id).0;
+    let entropy_var_address = entropy_api::state::var_pda(board_address, 0).0;
+
+    let mut data = PoolDeploy {
+        dice_prediction,
+        _padding: [0; 7],
+    }
+    .to_bytes();
+    for (square, amount) in entries {
+        data.push(*square);
+        data.extend_from_slice(&amount.to_le_bytes());
+    }
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(pool_address, false),
+            AccountMeta::new(board_address, false),
+            AccountMeta::new(miner_pda(pool_address).0, false),
+            AccountMeta::new(round_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(get_associated_token_address(&pool_address, &RNG_MINT_ADDRESS), false),
+            AccountMeta::new(get_associated_token_address(&round_address, &RNG_MINT_ADDRESS), false),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new(entropy_var_address, false),
+            AccountMeta::new_readonly(entropy_api::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+        ],
+        data,
+    }
+}
+
+pub fn harvest_pool(signer: Pubkey, pool: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(miner_pda(pool).0, false),
+            AccountMeta::new_readonly(MINT_ADDRESS, false),
+            AccountMeta::new(get_associated_token_address(&pool, &MINT_ADDRESS), false),
+            AccountMeta::new(TREASURY_ADDRESS, false),
+            AccountMeta::new(treasury_tokens_address(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+        ],
+        data: HarvestPool {}.to_bytes(),
+    }
+}
+
+/// Checkpoints every listed miner authority's miner for `round_id` in one instruction.
+pub fn checkpoint_batch(signer: Pubkey, round_id: u64, authorities: &[Pubkey]) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(board_pda().0, false),
+        AccountMeta::new(round_pda(round_id).0, false),
+        AccountMeta::new(TREASURY_ADDRESS, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    for authority in authorities {
+        accounts.push(AccountMeta::new(miner_pda(*authority).0, false));
+        accounts.push(AccountMeta::new_readonly(stake_pda(*authority).0, false));
+    }
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: CheckpointBatch {}.to_bytes(),
+    }
+}
+
+/// Checkpoints a miner and, if auto-redeploy is enabled, rolls its winnings into
+/// the current round `next_round_id`.
+pub fn checkpoint_with_redeploy(
+    signer: Pubkey,
+    authority: Pubkey,
+    round_id: u64,
+    next_round_id: u64,
+) -> Instruction {
+    let mut ix = checkpoint(signer, authority, round_id);
+    ix.accounts
+        .push(AccountMeta::new(round_pda(next_round_id).0, false));
+    ix
+}
+
+pub fn set_auto_redeploy(signer: Pubkey, enabled: bool) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(miner_pda(signer).0, false),
+        ],
+        data: SetAutoRedeploy {
+            enabled: enabled as u8,
+        }
+        .to_bytes(),
+    }
+}
+
+/// Proposes `admin` as the new admin. Takes effect once the proposed admin accepts.
+pub fn set_admin(signer: Pubkey, admin: Pubkey) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetAdmin {
+            admin: admin.to_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn accept_admin(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_pda().0, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: AcceptAdmin {}.to_bytes(),
+    }
+}
+
+/// Pauses `modules` (`PAUSE_*` bits). Signed by the guardian or the admin.
+pub fn pause(signer: Pubkey, modules: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_pda().0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(roles_pda().0, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: Pause {
+            modules: modules.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn set_role(signer: Pubkey, authority: Pubkey, roles: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new(roles_pda().0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetRole {
+            authority: authority.to_bytes(),
+            roles: roles.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn schedule_unpause(signer: Pubkey, modules: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_pda().0, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: ScheduleUnpause {
+            modules: modules.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn apply_unpause(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_pda().0, false),
+        ],
+        data: ApplyUnpause {}.to_bytes(),
+    }
+}
+
+/// Schedules `guardian` to take over once the timelock passes. See `apply_guardian`.
+pub fn set_guardian(signer: Pubkey, guardian: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_pda().0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetGuardian {
+            guardian: guardian.to_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn apply_guardian(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_pda().0, false),
+        ],
+        data: ApplyGuardian {}.to_bytes(),
+    }
+}
+
+/// Sets a numeric parameter. Use `set_param_pubkey` for pubkey parameters.
+pub fn set_param(signer: Pubkey, key: u64, value: u64) -> Instruction {
+    let mut bytes = [0; 32];
+    bytes[..8].copy_from_slice(&value.to_le_bytes());
+    set_param_bytes(signer, key, bytes)
+}
+
+pub fn set_param_pubkey(signer: Pubkey, key: u64, value: Pubkey) -> Instruction {
+    set_param_bytes(signer, key, value.to_bytes())
+}
+
+fn set_param_bytes(signer: Pubkey, key: u64, value: [u8; 32]) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new(param_pda(key).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetParam {
+            key: key.to_le_bytes(),
+            value,
+        }
+        .to_bytes(),
+    }
+}
+
+/// Creates or updates the admin multisig. `approvers` are the other members signing
+/// when the multisig is already the admin.
+pub fn set_admin_multisig(
+    signer: Pubkey,
+    members: &[Pubkey],
+    threshold: u8,
+    approvers: &[Pubkey],
+) -> Instruction {
+    let mut signers = [[0; 32]; MAX_ADMIN_SIGNERS];
+    for (slot, member) in signers.iter_mut().zip(members) {
+        *slot = member.to_bytes();
+    }
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(config_pda().0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new(audit_log_pda().0, false),
+        AccountMeta::new(admin_multisig_pda().0, false),
+    ];
+    for approver in approvers {
+        accounts.push(AccountMeta::new_readonly(*approver, true));
+    }
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: SetAdminMultisig {
+            signers,
+            num_signers: members.len().min(MAX_ADMIN_SIGNERS) as u8,
+            threshold,
+            _padding: [0; 6],
+        }
+        .to_bytes(),
+    }
+}
+
+/// Appends the admin multisig and member approvals to an admin-gated instruction,
+/// for use once the config admin is the multisig.
+pub fn with_admin_approvals(mut ix: Instruction, approvers: &[Pubkey]) -> Instruction {
+    ix.accounts
+        .push(AccountMeta::new_readonly(admin_multisig_pda().0, false));
+    for approver in approvers {
+        ix.accounts.push(AccountMeta::new_readonly(*approver, true));
+    }
+    ix
+}
+
+pub fn set_admin_fee(signer: Pubkey, admin_fee: u64) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetAdminFee {
+            admin_fee: admin_fee.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn set_motherlode_params(signer: Pubkey, odds: u64, funding: u64) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetMotherlodeParams {
+            odds: odds.to_le_bytes(),
+            funding: funding.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn set_reward_split_policy(signer: Pubkey, split_bps: u64, top_miner_share_pct: u64) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetRewardSplitPolicy {
+            split_bps: split_bps.to_le_bytes(),
+            top_miner_share_pct: top_miner_share_pct.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn set_revenue_policy(signer: Pubkey, burn_bps: u64, stake_bps: u64, insurance_bps: u64) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetRevenuePolicy {
+            burn_bps: burn_bps.to_le_bytes(),
+            stake_bps: stake_bps.to_le_bytes(),
+            insurance_bps: insurance_bps.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn set_round_permission(signer: Pubkey, mode: u64) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetRoundPermission {
+            mode: mode.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn set_vesting_policy(signer: Pubkey, threshold: u64, duration_slots: u64) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetVestingPolicy {
+            threshold: threshold.to_le_bytes(),
+            duration_slots: duration_slots.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn set_claim_minimums(signer: Pubkey, min_sol: u64, min_ore: u64) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetClaimMinimums {
+            min_sol: min_sol.to_le_bytes(),
+            min_ore: min_ore.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn sweep_dust(signer: Pubkey, authorities: &[Pubkey]) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(board_pda().0, false),
+        AccountMeta::new_readonly(config_pda().0, false),
+        AccountMeta::new(TREASURY_ADDRESS, false),
+    ];
+    for authority in authorities {
+        accounts.push(AccountMeta::new(miner_pda(*authority).0, false));
+    }
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: SweepDust {}.to_bytes(),
+    }
+}
+
+pub fn set_unstake_cooldown(signer: Pubkey, cooldown_slots: u64) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetUnstakeCooldown {
+            cooldown_slots: cooldown_slots.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn set_fee_collector(signer: Pubkey, fee_collector: Pubkey) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetFeeCollector {
+            fee_collector: fee_collector.to_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+// let [signer_info, mint_info, sender_info, stake_info, stake_tokens_info, treasury_info, system_program, token_program, associated_token_program] =
+
+pub fn deposit(signer: Pubkey, payer: Pubkey, amount: u64) -> Instruction {
+    let mint_address = MINT_ADDRESS;
+    let stake_address = stake_pda(signer).0;
+    let stake_tokens_address = stake_tokens_address(signer);
+    let sender_address = get_associated_token_address(&signer, &MINT_ADDRESS);
+    let treasury_address = TREASURY_ADDRESS;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new(mint_address, false),
+            AccountMeta::new(sender_address, false),
+            AccountMeta::new(stake_address, false),
+            AccountMeta::new(stake_tokens_address, false),
+            AccountMeta::new(treasury_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+        ],
+        data: Deposit {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+// let [signer_info, mint_info, recipient_info, stake_info, stake_tokens_info, treasury_info, system_program, token_program, associated_token_program] =
+
+pub fn withdraw(signer: Pubkey, amount: u64) -> Instruction {
+    let stake_address = stake_pda(signer).0;
+    let stake_tokens_address = stake_tokens_address(signer);
+    let mint_address = MINT_ADDRESS;
+    let recipient_address = get_associated_token_address(&signer, &MINT_ADDRESS);
+    let treasury_address = TREASURY_ADDRESS;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(mint_address, false),
+            AccountMeta::new(recipient_address, false),
+            AccountMeta::new(stake_address, false),
+            AccountMeta::new(stake_tokens_address, false),
+            AccountMeta::new(treasury_address, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+        ],
+        data: Withdraw {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+// let [signer_info, mint_info, recipient_info, stake_info, stake_tokens_info, system_program, token_program, associated_token_program, config_info] =
+
+pub fn claim_withdrawal(signer: Pubkey) -> Instruction {
+    let stake_address = stake_pda(signer).0;
+    let stake_tokens_address = stake_tokens_address(signer);
+    let mint_address = MINT_ADDRESS;
+    let recipient_address = get_associated_token_address(&signer, &MINT_ADDRESS);
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(mint_address, false),
+            AccountMeta::new(recipient_address, false),
+            AccountMeta::new(stake_address, false),
+            AccountMeta::new(stake_tokens_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+        ],
+        data: ClaimWithdrawal {}.to_bytes(),
+    }
+}
+
+// let [signer_info, automation_info, miner_info, system_program] = accounts else {
+
+pub fn recycle_sol(signer: Pubkey, authority: Pubkey) -> Instruction {
+    let automation_address = automation_pda(authority).0;
+    let miner_address = miner_pda(authority).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(automation_address, false),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: RecycleSOL {}.to_bytes(),
+    }
+}
+
+// let [signer_info, mint_info, recipient_info, stake_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program, config_info, optional_accounts @ ..] =
+
+pub fn claim_yield(signer: Pubkey, authority: Pubkey, amount: u64) -> Instruction {
+    let stake_address = stake_pda(authority).0;
+    let mint_address = MINT_ADDRESS;
+    let recipient_address = get_associated_token_address(&authority, &MINT_ADDRESS);
+    let treasury_address = TREASURY_ADDRESS;
+    let treasury_tokens_address = treasury_tokens_address();
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(mint_address, false),
+            AccountMeta::new(recipient_address, false),
+            AccountMeta::new(stake_address, false),
+            AccountMeta::new(treasury_address, false),
+            AccountMeta::new(treasury_tokens_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+        ],
+        data: ClaimYield {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Builds a ClaimYield signed by a session delegate of the authority holding the
+/// StakingYield permission. The ORE is paid to the authority's existing token account.
+pub fn claim_yield_with_session(delegate: Pubkey, authority: Pubkey, amount: u64) -> Instruction {
+    let mut ix = claim_yield(delegate, authority, amount);
+    ix.accounts
+        .push(AccountMeta::new_readonly(session_pda(authority).0, false));
+    ix
+}
+
+/// Claims `amount` of staking yield and swaps it for at least `min_sol_out` lamports
+/// through `swap_program`. The swap accounts and data come from the router's quote.
+pub fn claim_yield_as_sol(
+    signer: Pubkey,
+    amount: u64,
+    min_sol_out: u64,
+    swap_program: Pubkey,
+    swap_accounts: &[AccountMeta],
+    swap_data: &[u8],
+) -> Instruction {
+    let stake_address = stake_pda(signer).0;
+    let recipient_address = get_associated_token_address(&signer, &MINT_ADDRESS);
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(MINT_ADDRESS, false),
+        AccountMeta::new(recipient_address, false),
+        AccountMeta::new(stake_address, false),
+        AccountMeta::new(TREASURY_ADDRESS, false),
+        AccountMeta::new(treasury_tokens_address(), false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new_readonly(config_pda().0, false),
+        AccountMeta::new_readonly(swap_program, false),
+    ];
+    for account in swap_accounts.iter() {
+        let mut acc_clone = account.clone();
+        acc_clone.is_signer = false;
+        accounts.push(acc_clone);
+    }
+    let mut data = ClaimYieldAsSol {
+        amount: amount.to_le_bytes(),
+        min_sol_out: min_sol_out.to_le_bytes(),
+    }
+    .to_bytes();
+    data.extend_from_slice(swap_data);
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data,
+    }
+}
+
+pub fn close_stake(signer: Pubkey) -> Instruction {
+    let stake_address = stake_pda(signer).0;
+    let stake_tokens_address = stake_tokens_address(signer);
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(MINT_ADDRESS, false),
+            AccountMeta::new(stake_address, false),
+            AccountMeta::new(stake_tokens_address, false),
+            AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: CloseStake {}.to_bytes(),
+    }
+}
+
+pub fn deposit_rng(signer: Pubkey, amount: u64) -> Instruction {
+    let stake_address = stake_pda(signer).0;
+    let stake_rng_tokens_address = stake_rng_tokens_address(signer);
+    let sender_address = get_associated_token_address(&signer, &RNG_MINT_ADDRESS);
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new(sender_address, false),
+            AccountMeta::new(stake_address, false),
+            AccountMeta::new(stake_rng_tokens_address, false),
+            AccountMeta::new(TREASURY_ADDRESS, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+        ],
+        data: DepositRng {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn withdraw_rng(signer: Pubkey, amount: u64) -> Instruction {
+    let stake_address = stake_pda(signer).0;
+    let stake_rng_tokens_address = stake_rng_tokens_address(signer);
+    let recipient_address = get_associated_token_address(&signer, &RNG_MINT_ADDRESS);
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new(recipient_address, false),
+            AccountMeta::new(stake_address, false),
+            AccountMeta::new(stake_rng_tokens_address, false),
+            AccountMeta::new(TREASURY_ADDRESS, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+        ],
+        data: WithdrawRng {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+// let [signer_info, mint_info, stake_info, stake_tokens_info, treasury_info, treasury_tokens_info, token_program, config_info, optional_accounts @ ..] =
+
+pub fn claim_yield_and_restake(signer: Pubkey, authority: Pubkey, amount: u64) -> Instruction {
+    let stake_address = stake_pda(authority).0;
+    let stake_tokens_address = stake_tokens_address(authority);
+    let mint_address = MINT_ADDRESS;
+    let treasury_address = TREASURY_ADDRESS;
+    let treasury_tokens_address = treasury_tokens_address();
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(mint_address, false),
+            AccountMeta::new(stake_address, false),
+            AccountMeta::new(stake_tokens_address, false),
+            AccountMeta::new(treasury_address, false),
+            AccountMeta::new(treasury_tokens_address, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+        ],
+        data: ClaimYieldAndRestake {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Builds a ClaimYieldAndRestake signed by a session delegate of the authority holding
+/// the StakingYield permission.
+pub fn claim_yield_and_restake_with_session(
+    delegate: Pubkey,
+    authority: Pubkey,
+    amount: u64,
+) -> Instruction {
+    let mut ix = claim_yield_and_restake(delegate, authority, amount);
+    ix.accounts
+        .push(AccountMeta::new_readonly(session_pda(authority).0, false));
+    ix
+}
+
+pub fn mint_stake_receipt(signer: Pubkey, amount: u64) -> Instruction {
+    let stake_address = stake_pda(signer).0;
+    let receipt_mint_address = stake_receipt_mint_pda().0;
+    let receipt_tokens_address = get_associated_token_address(&signer, &receipt_mint_address);
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(stake_address, false),
+            AccountMeta::new(receipt_mint_address, false),
+            AccountMeta::new(receipt_tokens_address, false),
+            AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+        ],
+        data: MintStakeReceipt {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+// let [signer_info, mint_info, receipt_mint_info, receipt_tokens_info, source_stake_info, source_stake_tokens_info, stake_info, stake_tokens_info, treasury_info, token_program, config_info] =
+
+pub fn redeem_stake_receipt(signer: Pubkey, source_authority: Pubkey, amount: u64) -> Instruction {
+    let receipt_mint_address = stake_receipt_mint_pda().0;
+    let receipt_tokens_address = get_associated_token_address(&signer, &receipt_mint_address);
+    let source_stake_address = stake_pda(source_authority).0;
+    let source_stake_tokens_address = stake_tokens_address(source_authority);
+    let stake_address = stake_pda(signer).0;
+    let stake_tokens_address = stake_tokens_address(signer);
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(MINT_ADDRESS, false),
+            AccountMeta::new(receipt_mint_address, false),
+            AccountMeta::new(receipt_tokens_address, false),
+            AccountMeta::new(source_stake_address, false),
+            AccountMeta::new(source_stake_tokens_address, false),
+            AccountMeta::new(stake_address, false),
+            AccountMeta::new(stake_tokens_address, false),
+            AccountMeta::new(TREASURY_ADDRESS, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(config_pda().0, false),
+        ],
+        data: RedeemStakeReceipt {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn set_stake_operator(signer: Pubkey, operator: Pubkey) -> Instruction {
+    let stake_address = stake_pda(signer).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(stake_address, false),
+        ],
+        data: SetStakeOperator {
+            operator: operator.to_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn new_var(
+    signer: Pubkey,
+    provider: Pubkey,
+    id: u64,
+    commit: [u8; 32],
+    samples: u64,
+) -> Instruction {
+    let board_address = board_pda().0;
+    let config_address = config_pda().0;
+    let var_address = entropy_api::state::var_pda(board_address, id).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(board_address, false),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new(provider, false),
+            AccountMeta::new(var_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(entropy_api::ID, false),
+            AccountMeta::new_readonly(roles_pda().0, false),
+        ],
+        data: NewVar {
+            id: id.to_le_bytes(),
+            commit: commit,
+            samples: samples.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+pub fn set_swap_program(signer: Pubkey, new_program: Pubkey) -> Instruction {
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new_readonly(new_program, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetSwapProgram {}.to_bytes(),
+    }
+}
+
+pub fn set_var_address(signer: Pubkey, new_var_address: Pubkey) -> Instruction {
+    let board_address = board_pda().0;
+    let config_address = config_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(board_address, false),
+            AccountMeta::new(config_address, false),
+            AccountMeta::new(new_var_address, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: SetVarAddress {}.to_bytes(),
+    }
+}
+
+/// Start a round manually (admin only).
+/// Duration is in slots (~400ms per slot).
+pub fn start_round(signer: Pubkey, round_id: u64, duration: u64) -> Instruction {
+    let board_address = board_pda().0;
+    let config_address = config_pda().0;
+    let round_address = round_pda(round_id).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(board_address, false),
+            AccountMeta::new_readonly(config_address, false),
+            AccountMeta::new(round_address, false),
+            AccountMeta::new_readonly(roles_pda().0, false),
+        ],
+        data: StartRound {
+            duration: duration.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Migrate a Round account to the new struct size (admin only).
+/// This reallocates the account and initializes new fields.
+pub fn migrate_round(signer: Pubkey, round_id: u64) -> Instruction {
+    let config_address = config_pda().0;
+    let round_address = round_pda(round_id).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(config_address, false),
+            AccountMeta::new(round_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: MigrateRound {
+            round_id: round_id.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Migrate a Miner account to the new struct size.
+/// This reallocates the account and initializes new fields.
+pub fn migrate_miner(signer: Pubkey, authority: Pubkey) -> Instruction {
+    let miner_address = miner_pda(authority).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: MigrateMiner {}.to_bytes(),
+    }
+}
+
+/// Migrate a Stake account to the new struct size.
+/// This reallocates the account to add the pending withdrawal, operator, receipt, and
+/// dual staking fields.
+pub fn migrate_stake(signer: Pubkey) -> Instruction {
+    let stake_address = stake_pda(signer).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(stake_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: MigrateStake {}.to_bytes(),
+    }
+}
+
+/// Migrate any program account to its current size and layout version.
+pub fn migrate_account(signer: Pubkey, address: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: MigrateAccount {}.to_bytes(),
+    }
+}
+
+/// Migrate the Config and Treasury accounts to the current layout versions.
+pub fn migrate_config(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_pda().0, false),
+            AccountMeta::new(treasury_pda().0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(audit_log_pda().0, false),
+        ],
+        data: MigrateConfig {}.to_bytes(),
+    }
+}
+
+/// Migrate a CrapsPosition account to the compact layout.
+/// Open side bets move to the signer's CrapsSideBets account and excess rent is refunded.
+pub fn migrate_craps_position(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(craps_position_pda(signer).0, false),
+            AccountMeta::new(craps_side_bets_pda(signer).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: MigrateCrapsPosition {}.to_bytes(),
+    }
+}
+
+/// Create the SOL/RNG exchange pool, seeding it from the admin's SOL and RNG (admin only).
+pub fn initialize_exchange_pool(signer: Pubkey, sol_amount: u64, rng_amount: u64) -> Instruction {
+    let lp_mint_address = exchange_lp_mint_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(lp_mint_address, false),
+            AccountMeta::new(exchange_sol_vault_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+            AccountMeta::new(get_associated_token_address(&signer, &lp_mint_address), false),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(SOL_MINT, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ],
+        data: InitializeExchangePool {
+            sol_amount: sol_amount.to_le_bytes(),
+            rng_amount: rng_amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Deposit SOL and RNG into the exchange pool for LP tokens.
+pub fn add_liquidity(
+    signer: Pubkey,
+    sol_amount: u64,
+    rng_amount: u64,
+    min_lp_tokens: u64,
+) -> Instruction {
+    let lp_mint_address = exchange_lp_mint_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(lp_mint_address, false),
+            AccountMeta::new(exchange_sol_vault_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &SOL_MINT), false),
+            AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+            AccountMeta::new(get_associated_token_address(&signer, &lp_mint_address), false),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(SOL_MINT, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+        ],
+        data: AddLiquidity {
+            sol_amount: sol_amount.to_le_bytes(),
+            rng_amount: rng_amount.to_le_bytes(),
+            min_lp_tokens: min_lp_tokens.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Burn LP tokens for the signer's share of the exchange pool's SOL (as wSOL) and RNG.
+pub fn remove_liquidity(
+    signer: Pubkey,
+    lp_amount: u64,
+    min_sol: u64,
+    min_rng: u64,
+) -> Instruction {
+    let lp_mint_address = exchange_lp_mint_pda().0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(lp_mint_address, false),
+            AccountMeta::new(exchange_sol_vault_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &SOL_MINT), false),
+            AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+            AccountMeta::new(get_associated_token_address(&signer, &lp_mint_address), false),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(SOL_MINT, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: RemoveLiquidity {
+            lp_amount: lp_amount.to_le_bytes(),
+            min_sol: min_sol.to_le_bytes(),
+            min_rng: min_rng.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Swap SOL for RNG through the exchange pool.
+/// Pass a registered `referrer` to credit it with a share of the protocol fee.
+pub fn swap_sol_to_rng(
+    signer: Pubkey,
+    sol_amount: u64,
+    min_rng_out: u64,
+    referrer: Option<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(exchange_pool_pda().0, false),
+        AccountMeta::new(exchange_sol_vault_pda().0, false),
+        AccountMeta::new(exchange_rng_vault_pda().0, false),
+        AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+        AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+        AccountMeta::new_readonly(SOL_MINT, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+    ];
+    if let Some(referrer) = referrer {
+        accounts.push(AccountMeta::new(referrer_pda(referrer).0, false));
+    }
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: SwapSolToRng {
+            sol_amount: sol_amount.to_le_bytes(),
+            min_rng_out: min_rng_out.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Swap RNG for SOL (paid as wSOL) through the exchange pool.
+/// Pass a registered `referrer` to credit it with a share of the protocol fee.
+pub fn swap_rng_to_sol(
+    signer: Pubkey,
+    rng_amount: u64,
+    min_sol_out: u64,
+    referrer: Option<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(exchange_pool_pda().0, false),
+        AccountMeta::new(exchange_sol_vault_pda().0, false),
+        AccountMeta::new(exchange_rng_vault_pda().0, false),
+        AccountMeta::new(get_associated_token_address(&signer, &SOL_MINT), false),
+        AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+        AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+        AccountMeta::new_readonly(SOL_MINT, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+    ];
+    if let Some(referrer) = referrer {
+        accounts.push(AccountMeta::new(referrer_pda(referrer).0, false));
+    }
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: SwapRngToSol {
+            rng_amount: rng_amount.to_le_bytes(),
+            min_sol_out: min_sol_out.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Swap RNG for a game token at 1:1, less the swap fee.
+pub fn swap_rng_to_game_token(
+    signer: Pubkey,
+    game_mint: Pubkey,
+    game_token_type: u8,
+    rng_amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+            AccountMeta::new(get_associated_token_address(&signer, &game_mint), false),
+            AccountMeta::new(game_mint, false),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: SwapRngToGameToken {
+            rng_amount: rng_amount.to_le_bytes(),
+            game_token_type,
+        }
+        .to_bytes(),
+    }
+}
+
+/// Swap a game token back to RNG at 1:1, less the swap fee.
+pub fn swap_game_token_to_rng(
+    signer: Pubkey,
+    game_mint: Pubkey,
+    game_token_type: u8,
+    game_token_amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+            AccountMeta::new(get_associated_token_address(&signer, &game_mint), false),
+            AccountMeta::new(game_mint, false),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: SwapGameTokenToRng {
+            game_token_amount: game_token_amount.to_le_bytes(),
+            game_token_type,
+        }
+        .to_bytes(),
+    }
+}
+
+/// Withdraw the exchange pool's accrued protocol fees to the admin (admin only).
+pub fn claim_protocol_fees(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(exchange_sol_vault_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &SOL_MINT), false),
+            AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(SOL_MINT, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: ClaimProtocolFees {}.to_bytes(),
+    }
+}
+
+/// Quote a swap through the exchange pool. Simulate it and parse the return data with
+/// `SwapQuote::from_bytes`.
+pub fn quote_swap(amount_in: u64, sol_to_rng: bool) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![AccountMeta::new_readonly(exchange_pool_pda().0, false)],
+        data: QuoteSwap {
+            amount_in: amount_in.to_le_bytes(),
+            sol_to_rng: sol_to_rng as u8,
+        }
+        .to_bytes(),
+    }
+}
+
+/// Swap SOL for a game token in one step, routing through the SOL/RNG pool.
+pub fn swap_sol_to_game_token(
+    signer: Pubkey,
+    game_mint: Pubkey,
+    game_token_type: u8,
+    sol_amount: u64,
+    min_game_out: u64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(exchange_sol_vault_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &game_mint), false),
+            AccountMeta::new(game_mint, false),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(SOL_MINT, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: SwapSolToGameToken {
+            sol_amount: sol_amount.to_le_bytes(),
+            min_game_out: min_game_out.to_le_bytes(),
+            game_token_type,
+        }
+        .to_bytes(),
+    }
+}
+
+/// Swap SOL for exactly `rng_out` RNG through the exchange pool, spending at most
+/// `max_sol_in` lamports.
+/// Pass a registered `referrer` to credit it with a share of the protocol fee.
+pub fn swap_sol_to_rng_exact_out(
+    signer: Pubkey,
+    rng_out: u64,
+    max_sol_in: u64,
+    referrer: Option<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(exchange_pool_pda().0, false),
+        AccountMeta::new(exchange_sol_vault_pda().0, false),
+        AccountMeta::new(exchange_rng_vault_pda().0, false),
+        AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+        AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+        AccountMeta::new_readonly(SOL_MINT, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+    ];
+    if let Some(referrer) = referrer {
+        accounts.push(AccountMeta::new(referrer_pda(referrer).0, false));
+    }
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: SwapSolToRngExactOut {
+            rng_out: rng_out.to_le_bytes(),
+            max_sol_in: max_sol_in.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Swap RNG for exactly `sol_out` lamports (paid as wSOL) through the exchange pool,
+/// spending at most `max_rng_in` RNG.
+/// Pass a registered `referrer` to credit it with a share of the protocol fee.
+pub fn swap_rng_to_sol_exact_out(
+    signer: Pubkey,
+    sol_out: u64,
+    max_rng_in: u64,
+    referrer: Option<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(exchange_pool_pda().0, false),
+        AccountMeta::new(exchange_sol_vault_pda().0, false),
+        AccountMeta::new(exchange_rng_vault_pda().0, false),
+        AccountMeta::new(get_associated_token_address(&signer, &SOL_MINT), false),
+        AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+        AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+        AccountMeta::new_readonly(SOL_MINT, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+    ];
+    if let Some(referrer) = referrer {
+        accounts.push(AccountMeta::new(referrer_pda(referrer).0, false));
+    }
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: SwapRngToSolExactOut {
+            sol_out: sol_out.to_le_bytes(),
+            max_rng_in: max_rng_in.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Set the exchange pool status. Signed by the pool admin, or the guardian to pause.
+pub fn set_pool_status(signer: Pubkey, status: u8) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+        ],
+        data: SetPoolStatus { status }.to_bytes(),
+    }
+}
+
+/// Set the exchange pool guardian (admin only).
+pub fn set_pool_guardian(signer: Pubkey, guardian: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+        ],
+        data: SetPoolGuardian {
+            guardian: guardian.to_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Schedule a change to the exchange pool's swap fee and protocol share (admin only).
+pub fn set_pool_fees(
+    signer: Pubkey,
+    fee_numerator: u64,
+    protocol_fee_share_bps: u64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+        ],
+        data: SetPoolFees {
+            fee_numerator: fee_numerator.to_le_bytes(),
+            protocol_fee_share_bps: protocol_fee_share_bps.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Apply the exchange pool's scheduled fee change once its timelock has passed.
+pub fn apply_pool_fees(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+        ],
+        data: ApplyPoolFees {}.to_bytes(),
+    }
+}
+
+/// Create the RNG/game token pool for `game_mint`, seeded from the admin's tokens
+/// (exchange admin only).
+pub fn initialize_game_token_pool(
+    signer: Pubkey,
+    game_mint: Pubkey,
+    game_token_type: u8,
+    rng_amount: u64,
+    game_amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(exchange_pool_pda().0, false),
+            AccountMeta::new(game_token_pool_pda(&game_mint).0, false),
+            AccountMeta::new(game_token_pool_rng_vault_pda(&game_mint).0, false),
+            AccountMeta::new(game_token_pool_game_vault_pda(&game_mint).0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+            AccountMeta::new(get_associated_token_address(&signer, &game_mint), false),
+            AccountMeta::new_readonly(game_mint, false),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ],
+        data: InitializeGameTokenPool {
+            rng_amount: rng_amount.to_le_bytes(),
+            game_amount: game_amount.to_le_bytes(),
+            game_token_type,
+        }
+        .to_bytes(),
+    }
+}
+
+/// Swap between RNG and a game token through the game token's pool.
+pub fn swap_game_token_pool(
+    signer: Pubkey,
+    game_mint: Pubkey,
+    amount_in: u64,
+    min_amount_out: u64,
+    rng_to_game: bool,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(game_token_pool_pda(&game_mint).0, false),
+            AccountMeta::new(game_token_pool_rng_vault_pda(&game_mint).0, false),
+            AccountMeta::new(game_token_pool_game_vault_pda(&game_mint).0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+            AccountMeta::new(get_associated_token_address(&signer, &game_mint), false),
+            AccountMeta::new_readonly(game_mint, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: SwapGameTokenPool {
+            amount_in: amount_in.to_le_bytes(),
+            min_amount_out: min_amount_out.to_le_bytes(),
+            rng_to_game: rng_to_game as u8,
+        }
+        .to_bytes(),
+    }
+}
+
+/// Stake LP tokens for a share of the exchange's protocol fees.
+pub fn stake_lp(signer: Pubkey, amount: u64) -> Instruction {
+    let lp_mint_address = exchange_lp_mint_pda().0;
+    let lp_stake_address = lp_stake_pda(signer).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(lp_stake_address, false),
+            AccountMeta::new(
+                get_associated_token_address(&lp_stake_address, &lp_mint_address),
+                false,
+            ),
+            AccountMeta::new(get_associated_token_address(&signer, &lp_mint_address), false),
+            AccountMeta::new_readonly(lp_mint_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+        ],
+        data: StakeLp {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Unstake LP tokens back to the signer.
+pub fn unstake_lp(signer: Pubkey, amount: u64) -> Instruction {
+    let lp_mint_address = exchange_lp_mint_pda().0;
+    let lp_stake_address = lp_stake_pda(signer).0;
+    Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(board_address, false),
-            AccountMeta::new(rent_payer, false),
-            AccountMeta::new(round_address, false),
-            AccountMeta::new(treasury_address, false),
-            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(lp_stake_address, false),
+            AccountMeta::new(
+                get_associated_token_address(&lp_stake_address, &lp_mint_address),
+                false,
+            ),
+            AccountMeta::new(get_associated_token_address(&signer, &lp_mint_address), false),
+            AccountMeta::new_readonly(lp_mint_address, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
         ],
-        data: Close {}.to_bytes(),
+        data: UnstakeLp {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
     }
 }
 
-// let [signer_info, automation_info, board_info, miner_info, round_info, treasury_info, system_program] =
+/// Claim the fee rewards accrued to the signer's LP stake, paid as wSOL and RNG.
+pub fn claim_lp_rewards(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(lp_stake_pda(signer).0, false),
+            AccountMeta::new(exchange_sol_vault_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &SOL_MINT), false),
+            AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: ClaimLpRewards {}.to_bytes(),
+    }
+}
 
-pub fn checkpoint(signer: Pubkey, authority: Pubkey, round_id: u64) -> Instruction {
-    let miner_address = miner_pda(authority).0;
-    let board_address = board_pda().0;
-    let round_address = round_pda(round_id).0;
-    let treasury_address = TREASURY_ADDRESS;
+/// Add liquidity to the exchange pool from SOL alone (`sol_in`) or RNG alone.
+pub fn zap_liquidity(signer: Pubkey, amount: u64, min_lp_tokens: u64, sol_in: bool) -> Instruction {
+    let lp_mint_address = exchange_lp_mint_pda().0;
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(board_address, false),
-            AccountMeta::new(miner_address, false),
-            AccountMeta::new(round_address, false),
-            AccountMeta::new(treasury_address, false),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(lp_mint_address, false),
+            AccountMeta::new(exchange_sol_vault_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+            AccountMeta::new(get_associated_token_address(&signer, &lp_mint_address), false),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(SOL_MINT, false),
             AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
         ],
-        data: Checkpoint {}.to_bytes(),
+        data: ZapLiquidity {
+            amount: amount.to_le_bytes(),
+            min_lp_tokens: min_lp_tokens.to_le_bytes(),
+            sol_in: sol_in as u8,
+        }
+        .to_bytes(),
     }
 }
 
-pub fn set_admin(signer: Pubkey, admin: Pubkey) -> Instruction {
-    let config_address = config_pda().0;
+/// Burn LP tokens for wSOL alone (`sol_out`) or RNG alone.
+pub fn remove_liquidity_single(
+    signer: Pubkey,
+    lp_amount: u64,
+    min_amount_out: u64,
+    sol_out: bool,
+) -> Instruction {
+    let lp_mint_address = exchange_lp_mint_pda().0;
+    let out_mint = if sol_out { SOL_MINT } else { RNG_MINT_ADDRESS };
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(config_address, false),
-            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(lp_mint_address, false),
+            AccountMeta::new(exchange_sol_vault_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &out_mint), false),
+            AccountMeta::new(get_associated_token_address(&signer, &lp_mint_address), false),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(SOL_MINT, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
         ],
-        data: SetAdmin {
-            admin: admin.to_bytes(),
+        data: RemoveLiquiditySingle {
+            lp_amount: lp_amount.to_le_bytes(),
+            min_amount_out: min_amount_out.to_le_bytes(),
+            sol_out: sol_out as u8,
         }
         .to_bytes(),
     }
 }
 
-pub fn set_admin_fee(signer: Pubkey, admin_fee: u64) -> Instruction {
-    let config_address = config_pda().0;
+/// Skim the exchange pool's protocol fees to the treasury. Permissionless.
+pub fn skim_protocol_fees(signer: Pubkey) -> Instruction {
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(config_address, false),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(exchange_sol_vault_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+            AccountMeta::new(get_associated_token_address(&TREASURY_ADDRESS, &SOL_MINT), false),
+            AccountMeta::new(
+                get_associated_token_address(&TREASURY_ADDRESS, &RNG_MINT_ADDRESS),
+                false,
+            ),
+            AccountMeta::new_readonly(SOL_MINT, false),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
             AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
         ],
-        data: SetAdminFee {
-            admin_fee: admin_fee.to_le_bytes(),
+        data: SkimProtocolFees {}.to_bytes(),
+    }
+}
+
+/// Register the signer as a swap referrer.
+pub fn register_referrer(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(referrer_pda(signer).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: RegisterReferrer {}.to_bytes(),
+    }
+}
+
+/// Claim the signer's referral fees, paid as wSOL and RNG.
+pub fn claim_referral_fees(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(referrer_pda(signer).0, false),
+            AccountMeta::new(exchange_sol_vault_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &SOL_MINT), false),
+            AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: ClaimReferralFees {}.to_bytes(),
+    }
+}
+
+/// Set the share of the protocol fee paid to swap referrers (admin only).
+pub fn set_referral_fee(signer: Pubkey, referral_fee_share_bps: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+        ],
+        data: SetReferralFee {
+            referral_fee_share_bps: referral_fee_share_bps.to_le_bytes(),
         }
         .to_bytes(),
     }
 }
 
-pub fn set_fee_collector(signer: Pubkey, fee_collector: Pubkey) -> Instruction {
-    let config_address = config_pda().0;
+/// Configure the exchange pool's price circuit breaker (admin only).
+pub fn set_circuit_breaker(
+    signer: Pubkey,
+    max_price_move_bps: u64,
+    window_seconds: i64,
+) -> Instruction {
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(config_address, false),
-            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(exchange_pool_pda().0, false),
         ],
-        data: SetFeeCollector {
-            fee_collector: fee_collector.to_bytes(),
+        data: SetCircuitBreaker {
+            max_price_move_bps: max_price_move_bps.to_le_bytes(),
+            window_seconds: window_seconds.to_le_bytes(),
         }
         .to_bytes(),
     }
 }
 
-// let [signer_info, mint_info, sender_info, stake_info, stake_tokens_info, treasury_info, system_program, token_program, associated_token_program] =
+/// Set the exchange pool's max swap size in basis points of the input reserve (admin only).
+pub fn set_max_swap(signer: Pubkey, max_swap_bps: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+        ],
+        data: SetMaxSwap {
+            max_swap_bps: max_swap_bps.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
 
-pub fn deposit(signer: Pubkey, payer: Pubkey, amount: u64) -> Instruction {
-    let mint_address = MINT_ADDRESS;
-    let stake_address = stake_pda(signer).0;
-    let stake_tokens_address = get_associated_token_address(&stake_address, &MINT_ADDRESS);
-    let sender_address = get_associated_token_address(&signer, &MINT_ADDRESS);
-    let treasury_address = TREASURY_ADDRESS;
+/// Burn LP tokens for a share of a paused or deprecated pool's reserves (SOL as wSOL).
+pub fn emergency_remove_liquidity(
+    signer: Pubkey,
+    lp_amount: u64,
+    min_sol: u64,
+    min_rng: u64,
+) -> Instruction {
+    let lp_mint_address = exchange_lp_mint_pda().0;
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(payer, true),
-            AccountMeta::new(mint_address, false),
-            AccountMeta::new(sender_address, false),
-            AccountMeta::new(stake_address, false),
-            AccountMeta::new(stake_tokens_address, false),
-            AccountMeta::new(treasury_address, false),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(lp_mint_address, false),
+            AccountMeta::new(exchange_sol_vault_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &SOL_MINT), false),
+            AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+            AccountMeta::new(get_associated_token_address(&signer, &lp_mint_address), false),
+            AccountMeta::new_readonly(RNG_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(SOL_MINT, false),
             AccountMeta::new_readonly(system_program::ID, false),
             AccountMeta::new_readonly(spl_token::ID, false),
-            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
         ],
-        data: Deposit {
-            amount: amount.to_le_bytes(),
+        data: EmergencyRemoveLiquidity {
+            lp_amount: lp_amount.to_le_bytes(),
+            min_sol: min_sol.to_le_bytes(),
+            min_rng: min_rng.to_le_bytes(),
         }
         .to_bytes(),
     }
 }
 
-// let [signer_info, mint_info, recipient_info, stake_info, stake_tokens_info, treasury_info, system_program, token_program, associated_token_program] =
+/// Mints (input, output) for a limit or DCA order side.
+fn order_mints(side: u8) -> (Pubkey, Pubkey) {
+    if side == LIMIT_ORDER_SIDE_SOL_TO_RNG {
+        (SOL_MINT, RNG_MINT_ADDRESS)
+    } else {
+        (RNG_MINT_ADDRESS, SOL_MINT)
+    }
+}
 
-pub fn withdraw(signer: Pubkey, amount: u64) -> Instruction {
-    let stake_address = stake_pda(signer).0;
-    let stake_tokens_address = get_associated_token_address(&stake_address, &MINT_ADDRESS);
-    let mint_address = MINT_ADDRESS;
-    let recipient_address = get_associated_token_address(&signer, &MINT_ADDRESS);
-    let treasury_address = TREASURY_ADDRESS;
+/// Place a limit order against the exchange pool. `limit_price` is the minimum output
+/// per input token, Q64.64.
+pub fn place_limit_order(
+    signer: Pubkey,
+    id: u64,
+    side: u8,
+    amount_in: u64,
+    limit_price: u128,
+    expires_at: i64,
+) -> Instruction {
+    let limit_order_address = limit_order_pda(signer, id).0;
+    let (input_mint, output_mint) = order_mints(side);
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(mint_address, false),
-            AccountMeta::new(recipient_address, false),
-            AccountMeta::new(stake_address, false),
-            AccountMeta::new(stake_tokens_address, false),
-            AccountMeta::new(treasury_address, false),
+            AccountMeta::new(limit_order_address, false),
+            AccountMeta::new(
+                get_associated_token_address(&limit_order_address, &input_mint),
+                false,
+            ),
+            AccountMeta::new(get_associated_token_address(&signer, &input_mint), false),
+            AccountMeta::new(get_associated_token_address(&signer, &output_mint), false),
+            AccountMeta::new_readonly(input_mint, false),
+            AccountMeta::new_readonly(output_mint, false),
             AccountMeta::new_readonly(system_program::ID, false),
             AccountMeta::new_readonly(spl_token::ID, false),
             AccountMeta::new_readonly(spl_associated_token_account::ID, false),
         ],
-        data: Withdraw {
-            amount: amount.to_le_bytes(),
+        data: PlaceLimitOrder {
+            id: id.to_le_bytes(),
+            amount_in: amount_in.to_le_bytes(),
+            limit_price: limit_price.to_le_bytes(),
+            expires_at: expires_at.to_le_bytes(),
+            side,
         }
         .to_bytes(),
     }
 }
 
-// let [signer_info, automation_info, miner_info, system_program] = accounts else {
-
-pub fn recycle_sol(signer: Pubkey, authority: Pubkey) -> Instruction {
-    let automation_address = automation_pda(authority).0;
-    let miner_address = miner_pda(authority).0;
+/// Fill `authority`'s limit order, earning the filler bounty in the input token.
+pub fn fill_limit_order(signer: Pubkey, authority: Pubkey, id: u64, side: u8) -> Instruction {
+    let limit_order_address = limit_order_pda(authority, id).0;
+    let (input_mint, output_mint) = order_mints(side);
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(automation_address, false),
-            AccountMeta::new(miner_address, false),
-            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(exchange_sol_vault_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(limit_order_address, false),
+            AccountMeta::new(
+                get_associated_token_address(&limit_order_address, &input_mint),
+                false,
+            ),
+            AccountMeta::new(authority, false),
+            AccountMeta::new(get_associated_token_address(&authority, &output_mint), false),
+            AccountMeta::new(get_associated_token_address(&signer, &input_mint), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
         ],
-        data: RecycleSOL {}.to_bytes(),
+        data: FillLimitOrder {}.to_bytes(),
     }
 }
 
-// let [signer_info, mint_info, recipient_info, stake_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program] =
-
-pub fn claim_yield(signer: Pubkey, amount: u64) -> Instruction {
-    let stake_address = stake_pda(signer).0;
-    let mint_address = MINT_ADDRESS;
-    let recipient_address = get_associated_token_address(&signer, &MINT_ADDRESS);
-    let treasury_address = TREASURY_ADDRESS;
-    let treasury_tokens_address = treasury_tokens_address();
+/// Cancel `authority`'s limit order and refund its escrow.
+pub fn cancel_limit_order(signer: Pubkey, authority: Pubkey, id: u64, side: u8) -> Instruction {
+    let limit_order_address = limit_order_pda(authority, id).0;
+    let (input_mint, _) = order_mints(side);
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(mint_address, false),
-            AccountMeta::new(recipient_address, false),
-            AccountMeta::new(stake_address, false),
-            AccountMeta::new(treasury_address, false),
-            AccountMeta::new(treasury_tokens_address, false),
-            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(limit_order_address, false),
+            AccountMeta::new(
+                get_associated_token_address(&limit_order_address, &input_mint),
+                false,
+            ),
+            AccountMeta::new(authority, false),
+            AccountMeta::new(get_associated_token_address(&authority, &input_mint), false),
             AccountMeta::new_readonly(spl_token::ID, false),
-            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
         ],
-        data: ClaimYield {
-            amount: amount.to_le_bytes(),
-        }
-        .to_bytes(),
+        data: CancelLimitOrder {}.to_bytes(),
     }
 }
 
-pub fn new_var(
+/// Open a DCA order that swaps `budget` through the exchange pool in slices of
+/// `amount_per_interval`, one every `interval_slots`. `min_price` is the minimum output
+/// per input token for each slice, Q64.64.
+pub fn open_dca_order(
     signer: Pubkey,
-    provider: Pubkey,
     id: u64,
-    commit: [u8; 32],
-    samples: u64,
+    side: u8,
+    amount_per_interval: u64,
+    interval_slots: u64,
+    budget: u64,
+    min_price: u128,
 ) -> Instruction {
-    let board_address = board_pda().0;
-    let config_address = config_pda().0;
-    let var_address = entropy_api::state::var_pda(board_address, id).0;
+    let dca_order_address = dca_order_pda(signer, id).0;
+    let (input_mint, output_mint) = order_mints(side);
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(board_address, false),
-            AccountMeta::new(config_address, false),
-            AccountMeta::new(provider, false),
-            AccountMeta::new(var_address, false),
+            AccountMeta::new(dca_order_address, false),
+            AccountMeta::new(
+                get_associated_token_address(&dca_order_address, &input_mint),
+                false,
+            ),
+            AccountMeta::new(get_associated_token_address(&signer, &input_mint), false),
+            AccountMeta::new(get_associated_token_address(&signer, &output_mint), false),
+            AccountMeta::new_readonly(input_mint, false),
+            AccountMeta::new_readonly(output_mint, false),
             AccountMeta::new_readonly(system_program::ID, false),
-            AccountMeta::new_readonly(entropy_api::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
         ],
-        data: NewVar {
+        data: OpenDcaOrder {
             id: id.to_le_bytes(),
-            commit: commit,
-            samples: samples.to_le_bytes(),
+            amount_per_interval: amount_per_interval.to_le_bytes(),
+            interval_slots: interval_slots.to_le_bytes(),
+            budget: budget.to_le_bytes(),
+            min_price: min_price.to_le_bytes(),
+            side,
         }
         .to_bytes(),
     }
 }
 
-pub fn set_swap_program(signer: Pubkey, new_program: Pubkey) -> Instruction {
-    let config_address = config_pda().0;
+/// Execute the next due slice of `authority`'s DCA order, earning the crank bounty in
+/// the input token.
+pub fn crank_dca_order(signer: Pubkey, authority: Pubkey, id: u64, side: u8) -> Instruction {
+    let dca_order_address = dca_order_pda(authority, id).0;
+    let (input_mint, output_mint) = order_mints(side);
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(config_address, false),
-            AccountMeta::new_readonly(new_program, false),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(exchange_sol_vault_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(dca_order_address, false),
+            AccountMeta::new(
+                get_associated_token_address(&dca_order_address, &input_mint),
+                false,
+            ),
+            AccountMeta::new(authority, false),
+            AccountMeta::new(get_associated_token_address(&authority, &output_mint), false),
+            AccountMeta::new(get_associated_token_address(&signer, &input_mint), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
         ],
-        data: SetSwapProgram {}.to_bytes(),
+        data: CrankDcaOrder {}.to_bytes(),
     }
 }
 
-pub fn set_var_address(signer: Pubkey, new_var_address: Pubkey) -> Instruction {
-    let board_address = board_pda().0;
-    let config_address = config_pda().0;
+/// Cancel the signer's DCA order and refund its remaining budget.
+pub fn cancel_dca_order(signer: Pubkey, id: u64, side: u8) -> Instruction {
+    let dca_order_address = dca_order_pda(signer, id).0;
+    let (input_mint, _) = order_mints(side);
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(board_address, false),
-            AccountMeta::new(config_address, false),
-            AccountMeta::new(new_var_address, false),
+            AccountMeta::new(dca_order_address, false),
+            AccountMeta::new(
+                get_associated_token_address(&dca_order_address, &input_mint),
+                false,
+            ),
+            AccountMeta::new(get_associated_token_address(&signer, &input_mint), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
         ],
-        data: SetVarAddress {}.to_bytes(),
+        data: CancelDcaOrder {}.to_bytes(),
     }
 }
 
-/// Start a round manually (admin only).
-/// Duration is in slots (~400ms per slot).
-pub fn start_round(signer: Pubkey, round_id: u64, duration: u64) -> Instruction {
-    let board_address = board_pda().0;
-    let config_address = config_pda().0;
-    let round_address = round_pda(round_id).0;
+/// Lock LP tokens for `duration` seconds for a boosted share of LP staker fees.
+pub fn lock_lp(signer: Pubkey, id: u64, amount: u64, duration: i64) -> Instruction {
+    let lp_mint_address = exchange_lp_mint_pda().0;
+    let lp_lock_address = lp_lock_pda(signer, id).0;
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(board_address, false),
-            AccountMeta::new_readonly(config_address, false),
-            AccountMeta::new(round_address, false),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(lp_lock_address, false),
+            AccountMeta::new(
+                get_associated_token_address(&lp_lock_address, &lp_mint_address),
+                false,
+            ),
+            AccountMeta::new(get_associated_token_address(&signer, &lp_mint_address), false),
+            AccountMeta::new_readonly(lp_mint_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
         ],
-        data: StartRound {
+        data: LockLp {
+            id: id.to_le_bytes(),
+            amount: amount.to_le_bytes(),
             duration: duration.to_le_bytes(),
         }
         .to_bytes(),
     }
 }
 
-/// Migrate a Round account to the new struct size (admin only).
-/// This reallocates the account and initializes new fields.
-pub fn migrate_round(signer: Pubkey, round_id: u64) -> Instruction {
-    let config_address = config_pda().0;
-    let round_address = round_pda(round_id).0;
+/// Release the signer's LP lock, paying its rewards as wSOL and RNG.
+pub fn unlock_lp(signer: Pubkey, id: u64) -> Instruction {
+    let lp_mint_address = exchange_lp_mint_pda().0;
+    let lp_lock_address = lp_lock_pda(signer, id).0;
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new_readonly(config_address, false),
-            AccountMeta::new(round_address, false),
-            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(lp_lock_address, false),
+            AccountMeta::new(
+                get_associated_token_address(&lp_lock_address, &lp_mint_address),
+                false,
+            ),
+            AccountMeta::new(get_associated_token_address(&signer, &lp_mint_address), false),
+            AccountMeta::new(lp_mint_address, false),
+            AccountMeta::new(exchange_sol_vault_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(get_associated_token_address(&signer, &SOL_MINT), false),
+            AccountMeta::new(get_associated_token_address(&signer, &RNG_MINT_ADDRESS), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
         ],
-        data: MigrateRound {
-            round_id: round_id.to_le_bytes(),
+        data: UnlockLp {}.to_bytes(),
+    }
+}
+
+/// Report a game token's backing. Simulate it and parse the return data with
+/// `GameTokenBacking::from_bytes`.
+pub fn quote_game_token_backing(game_token_type: u8) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![AccountMeta::new_readonly(exchange_pool_pda().0, false)],
+        data: QuoteGameTokenBacking { game_token_type }.to_bytes(),
+    }
+}
+
+/// Configure the CRAP buyback-and-burn (admin only).
+pub fn set_buyback_config(
+    signer: Pubkey,
+    share_bps: u64,
+    max_sol: u64,
+    interval_seconds: i64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+        ],
+        data: SetBuybackConfig {
+            share_bps: share_bps.to_le_bytes(),
+            max_sol: max_sol.to_le_bytes(),
+            interval_seconds: interval_seconds.to_le_bytes(),
         }
         .to_bytes(),
     }
 }
 
-/// Migrate a Miner account to the new struct size.
-/// This reallocates the account and initializes new fields.
-pub fn migrate_miner(signer: Pubkey, authority: Pubkey) -> Instruction {
-    let miner_address = miner_pda(authority).0;
+/// Buy CRAP with protocol SOL fees and burn it. Permissionless.
+pub fn buyback_crap(signer: Pubkey) -> Instruction {
+    use crate::consts::CRAP_MINT_ADDRESS;
+
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(miner_address, false),
-            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(exchange_pool_pda().0, false),
+            AccountMeta::new(exchange_rng_vault_pda().0, false),
+            AccountMeta::new(game_token_pool_pda(&CRAP_MINT_ADDRESS).0, false),
+            AccountMeta::new(game_token_pool_rng_vault_pda(&CRAP_MINT_ADDRESS).0, false),
+            AccountMeta::new(game_token_pool_game_vault_pda(&CRAP_MINT_ADDRESS).0, false),
+            AccountMeta::new(CRAP_MINT_ADDRESS, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
         ],
-        data: MigrateMiner {}.to_bytes(),
+        data: BuybackCrap {}.to_bytes(),
+    }
+}
+
+/// Set the exchange pool's per-slot SOL notional cap in basis points of the SOL reserve
+/// (admin only).
+pub fn set_slot_notional_cap(signer: Pubkey, cap_bps: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(exchange_pool_pda().0, false),
+        ],
+        data: SetSlotNotionalCap {
+            cap_bps: cap_bps.to_le_bytes(),
+        }
+        .to_bytes(),
     }
 }
@@ -0,0 +1,439 @@
+//! Off-chain craps settlement.
+//!
+//! `settle` resolves a position against a single roll by the rules `SettleCraps` applies
+//! on-chain, so frontends and bots can predict settlements and tests can check the
+//! program against an independent implementation. Side bets live on `CrapsSideBets`
+//! and are not simulated.
+//!
+//! `bundle_reservation` runs it over every roll to price hedged bet bundles, on-chain
+//! as well as off.
+
+use crate::{
+    consts::*,
+    error::OreError,
+    instruction::CrapsBetType,
+    payout::{calculate_payout, payout_odds, position_max_payout},
+    state::{index_to_point, CrapsGame, CrapsPosition, BET_MASK_TRACKED, NUM_POINTS},
+};
+
+/// A roll of two dice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Roll {
+    die1: u8,
+    die2: u8,
+}
+
+impl Roll {
+    /// Returns `None` unless both dice show 1 through 6.
+    pub fn new(die1: u8, die2: u8) -> Option<Self> {
+        ((1..=6).contains(&die1) && (1..=6).contains(&die2)).then_some(Self { die1, die2 })
+    }
+
+    /// The roll for a board square, where square = (die1 - 1) * 6 + (die2 - 1).
+    pub fn from_square(square: usize) -> Option<Self> {
+        (square < BOARD_SIZE).then(|| Self {
+            die1: (square / 6) as u8 + 1,
+            die2: (square % 6) as u8 + 1,
+        })
+    }
+
+    /// The board square this roll lands on.
+    pub fn square(&self) -> usize {
+        (self.die1 as usize - 1) * 6 + (self.die2 as usize - 1)
+    }
+
+    pub fn dice(&self) -> (u8, u8) {
+        (self.die1, self.die2)
+    }
+
+    pub fn sum(&self) -> u8 {
+        self.die1 + self.die2
+    }
+
+    /// Whether the dice are doubles, making the sum the hard way.
+    pub fn is_hard(&self) -> bool {
+        self.die1 == self.die2
+    }
+}
+
+/// The result of settling a position against a roll.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Outcome {
+    /// Stakes returned plus winnings, before the house bankroll is checked.
+    pub winnings: u64,
+
+    /// Stakes lost to the house.
+    pub lost: u64,
+
+    /// The position after the roll, with winnings not yet credited to it.
+    pub position: CrapsPosition,
+
+    /// The table after the roll. Only the phase is advanced; reservations and the
+    /// bankroll are left as they were, and a new epoch keeps the old start round.
+    pub game: CrapsGame,
+
+    /// Whether the roll sevened out and ended the epoch.
+    pub seven_out: bool,
+
+    /// Whether the position was from a finished epoch and refunded instead of settled.
+    pub refunded: bool,
+}
+
+/// Settles copies of a position and the table against a single roll.
+pub fn settle(position: &CrapsPosition, game: &CrapsGame, roll: Roll) -> Result<Outcome, OreError> {
+    let mut outcome = Outcome {
+        winnings: 0,
+        lost: 0,
+        position: *position,
+        game: *game,
+        seven_out: false,
+        refunded: false,
+    };
+
+    // Positions from a finished epoch get their stakes back.
+    if position.epoch_id != game.epoch_id {
+        outcome.winnings = position.total_active_bets();
+        outcome.position.clear_all_bets();
+        outcome.position.epoch_id = game.epoch_id;
+        outcome.refunded = true;
+        return Ok(outcome);
+    }
+
+    if !outcome.position.is_tracking_active_bets() {
+        outcome.position.refresh_active_bets();
+    }
+
+    // A position without bets leaves the table alone.
+    if !outcome.position.has_active_bets(!BET_MASK_TRACKED) {
+        return Ok(outcome);
+    }
+
+    let tally = resolve(&mut outcome.position, &mut outcome.game, roll)?;
+    outcome.winnings = tally.winnings;
+    outcome.lost = tally.lost;
+    outcome.seven_out = outcome.game.epoch_id != game.epoch_id;
+    Ok(outcome)
+}
+
+/// The bankroll a bundle of bets needs reserved: the most the house can owe on any next
+/// roll, counting what the roll pays plus the most the bets left standing can return.
+/// Legs that hedge each other can't all win, so this is usually less than reserving each
+/// leg at its maximum, and never more. `legs` holds only the bundle's bets.
+///
+/// Whatever the roll, the bets left standing need no more than this on their own, so
+/// settlement can go back to reserving each of them at its maximum.
+pub fn bundle_reservation(legs: &CrapsPosition, game: &CrapsGame) -> Result<u64, OreError> {
+    let gross = position_max_payout(legs, game.point)?;
+    let mut worst: u64 = 0;
+    // Dice order never changes a settlement.
+    for die1 in 1..=6 {
+        for die2 in die1..=6 {
+            let outcome = settle(legs, game, Roll { die1, die2 })?;
+            let standing = position_max_payout(&outcome.position, outcome.game.point)?;
+            worst = worst.max(outcome.winnings.saturating_add(standing));
+        }
+    }
+    Ok(worst.min(gross))
+}
+
+/// How a bet fares on a roll.
+#[derive(Clone, Copy)]
+enum Verdict {
+    Win,
+    Lose,
+    Push,
+    Stands,
+}
+
+impl Verdict {
+    fn of(won: bool, lost: bool) -> Self {
+        if won {
+            Verdict::Win
+        } else if lost {
+            Verdict::Lose
+        } else {
+            Verdict::Stands
+        }
+    }
+}
+
+/// Running totals for a roll.
+#[derive(Default)]
+struct Tally {
+    winnings: u64,
+    lost: u64,
+}
+
+impl Tally {
+    /// Settles a bet by its verdict, taking it down unless it stands.
+    fn apply(
+        &mut self,
+        bet: &mut u64,
+        verdict: Verdict,
+        (num, den): (u64, u64),
+    ) -> Result<(), OreError> {
+        if *bet == 0 {
+            return Ok(());
+        }
+        match verdict {
+            Verdict::Win => {
+                let win_amount = bet
+                    .checked_add(calculate_payout(*bet, num, den))
+                    .ok_or(OreError::ArithmeticOverflow)?;
+                self.winnings = self
+                    .winnings
+                    .checked_add(win_amount)
+                    .ok_or(OreError::ArithmeticOverflow)?;
+            }
+            Verdict::Push => {
+                self.winnings = self
+                    .winnings
+                    .checked_add(*bet)
+                    .ok_or(OreError::ArithmeticOverflow)?;
+            }
+            Verdict::Lose => {
+                self.lost = self.lost.checked_add(*bet).ok_or(OreError::ArithmeticOverflow)?;
+            }
+            Verdict::Stands => return Ok(()),
+        }
+        *bet = 0;
+        Ok(())
+    }
+}
+
+/// The odds a bet pays, or nothing for a point it can't be on.
+fn odds(bet_type: CrapsBetType, point: u8) -> (u64, u64) {
+    payout_odds(bet_type as u8, point).unwrap_or((0, 1))
+}
+
+fn resolve(
+    position: &mut CrapsPosition,
+    game: &mut CrapsGame,
+    roll: Roll,
+) -> Result<Tally, OreError> {
+    let sum = roll.sum();
+    let mut tally = Tally::default();
+
+    // Single-roll bets.
+    let field_odds = match sum {
+        2 | 12 => (FIELD_PAYOUT_2_12_NUM, FIELD_PAYOUT_2_12_DEN),
+        _ => (FIELD_PAYOUT_NORMAL_NUM, FIELD_PAYOUT_NORMAL_DEN),
+    };
+    let field_won = matches!(sum, 2 | 3 | 4 | 9 | 10 | 11 | 12);
+    tally.apply(&mut position.field_bet, Verdict::of(field_won, true), field_odds)?;
+    let single_rolls = [
+        (&mut position.any_seven, sum == 7, CrapsBetType::AnySeven),
+        (&mut position.any_craps, matches!(sum, 2 | 3 | 12), CrapsBetType::AnyCraps),
+        (&mut position.yo_eleven, sum == 11, CrapsBetType::YoEleven),
+        (&mut position.aces, sum == 2, CrapsBetType::Aces),
+        (&mut position.twelve, sum == 12, CrapsBetType::Twelve),
+    ];
+    for (bet, won, bet_type) in single_rolls {
+        tally.apply(bet, Verdict::of(won, true), odds(bet_type, 0))?;
+    }
+    for (i, bet) in position.next_bets.iter_mut().enumerate() {
+        let target = i as u8 + 2;
+        tally.apply(bet, Verdict::of(sum == target, true), odds(CrapsBetType::Hop, target))?;
+    }
+
+    // Hardways win the hard way and lose on 7 or the easy way.
+    for (bet, hardway) in position.hardways.iter_mut().zip([4u8, 6, 8, 10]) {
+        let verdict = Verdict::of(sum == hardway && roll.is_hard(), sum == 7 || sum == hardway);
+        tally.apply(bet, verdict, odds(CrapsBetType::Hardway, hardway))?;
+    }
+
+    // Place bets win on their number and lose on 7, while working.
+    if position.are_place_bets_working() {
+        for i in 0..NUM_POINTS {
+            let point = index_to_point(i).unwrap_or(0);
+            let verdict = Verdict::of(sum == point, sum == 7);
+            tally.apply(&mut position.place_bets[i], verdict, odds(CrapsBetType::Place, point))?;
+        }
+    }
+
+    // Yes bets win on their sum before 7; No bets win on 7 before their sum.
+    for i in 0..11 {
+        let target = i as u8 + 2;
+        if target == 7 {
+            continue;
+        }
+        let yes = Verdict::of(sum == target, sum == 7);
+        tally.apply(&mut position.yes_bets[i], yes, odds(CrapsBetType::Buy, target))?;
+        let no = Verdict::of(sum == 7, sum == target);
+        tally.apply(&mut position.no_bets[i], no, odds(CrapsBetType::Lay, target))?;
+    }
+
+    // Come bets travel with their odds, which only settle alongside them.
+    for i in 0..NUM_POINTS {
+        let point = index_to_point(i).unwrap_or(0);
+        if position.come_bets[i] > 0 {
+            let verdict = Verdict::of(sum == point, sum == 7);
+            let come_odds = odds(CrapsBetType::ComeOdds, point);
+            tally.apply(&mut position.come_odds[i], verdict, come_odds)?;
+            tally.apply(&mut position.come_bets[i], verdict, odds(CrapsBetType::Come, point))?;
+        }
+        if position.dont_come_bets[i] > 0 {
+            let verdict = Verdict::of(sum == 7, sum == point);
+            let lay_odds = odds(CrapsBetType::DontComeOdds, point);
+            tally.apply(&mut position.dont_come_odds[i], verdict, lay_odds)?;
+            let flat_odds = odds(CrapsBetType::DontCome, point);
+            tally.apply(&mut position.dont_come_bets[i], verdict, flat_odds)?;
+        }
+    }
+
+    // Line bets follow the table phase.
+    let pass = odds(CrapsBetType::PassLine, 0);
+    if game.is_coming_out() {
+        if matches!(sum, 7 | 11) {
+            tally.apply(&mut position.pass_line, Verdict::Win, pass)?;
+            tally.apply(&mut position.dont_pass, Verdict::Lose, pass)?;
+        } else if matches!(sum, 2 | 3 | 12) {
+            // Don't Pass is barred on 12.
+            let dont_pass = if sum == 12 { Verdict::Push } else { Verdict::Win };
+            tally.apply(&mut position.pass_line, Verdict::Lose, pass)?;
+            tally.apply(&mut position.dont_pass, dont_pass, pass)?;
+        } else {
+            game.set_point(sum);
+        }
+    } else {
+        let point = game.get_point().unwrap_or(0);
+        if sum == point || sum == 7 {
+            let pass_verdict = Verdict::of(sum == point, true);
+            if position.pass_line > 0 {
+                let pass_odds = odds(CrapsBetType::PassOdds, point);
+                tally.apply(&mut position.pass_odds, pass_verdict, pass_odds)?;
+                tally.apply(&mut position.pass_line, pass_verdict, pass)?;
+            }
+            let dont_verdict = Verdict::of(sum == 7, true);
+            if position.dont_pass > 0 {
+                let lay_odds = odds(CrapsBetType::DontPassOdds, point);
+                tally.apply(&mut position.dont_pass_odds, dont_verdict, lay_odds)?;
+                tally.apply(&mut position.dont_pass, dont_verdict, pass)?;
+            }
+        }
+        if sum == point {
+            game.clear_point();
+        } else if sum == 7 {
+            // The round is unknown off-chain, so the new epoch keeps the old start round.
+            game.start_new_epoch(game.epoch_start_round);
+            position.reset_for_epoch(game.epoch_id);
+        }
+    }
+
+    position.refresh_active_bets();
+    Ok(tally)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::point_to_index;
+    use steel::Zeroable;
+
+    fn roll(die1: u8, die2: u8) -> Roll {
+        Roll::new(die1, die2).unwrap()
+    }
+
+    fn setup(point: u8) -> (CrapsGame, CrapsPosition) {
+        let mut game = CrapsGame::zeroed();
+        game.epoch_id = 1;
+        match point {
+            0 => game.clear_point(),
+            _ => game.set_point(point),
+        }
+        let mut position = CrapsPosition::zeroed();
+        position.epoch_id = 1;
+        (game, position)
+    }
+
+    #[test]
+    fn test_roll() {
+        assert_eq!(Roll::from_square(15), Some(roll(3, 4)));
+        assert_eq!(roll(3, 4).square(), 15);
+        assert_eq!(Roll::from_square(BOARD_SIZE), None);
+        assert_eq!(Roll::new(0, 6), None);
+        assert!(roll(5, 5).is_hard());
+    }
+
+    #[test]
+    fn test_settle_point_made() {
+        let (game, mut position) = setup(6);
+        position.pass_line = 100;
+        position.pass_odds = 50;
+        position.field_bet = 50;
+        position.place_bets[point_to_index(8).unwrap()] = 60;
+        position.set_place_working(true);
+
+        let outcome = settle(&position, &game, roll(2, 4)).unwrap();
+
+        // Pass line pays 1:1 and odds on 6 pay 6:5; field loses and place 8 stays up.
+        assert_eq!(outcome.winnings, 200 + 110);
+        assert_eq!(outcome.lost, 50);
+        assert_eq!(outcome.position.total_active_bets(), 60);
+        assert!(outcome.game.is_coming_out());
+        assert!(!outcome.seven_out);
+    }
+
+    #[test]
+    fn test_settle_seven_out() {
+        let (game, mut position) = setup(4);
+        position.pass_line = 100;
+        position.dont_pass = 100;
+        position.dont_pass_odds = 100;
+        position.hardways[0] = 10;
+
+        let outcome = settle(&position, &game, roll(3, 4)).unwrap();
+
+        // Laying 100 against the 4 wins 50.
+        assert_eq!(outcome.winnings, 200 + 150);
+        assert_eq!(outcome.lost, 110);
+        assert_eq!(outcome.position.total_active_bets(), 0);
+        assert_eq!(outcome.position.epoch_id, 2);
+        assert_eq!(outcome.game.epoch_id, 2);
+        assert!(outcome.seven_out);
+    }
+
+    #[test]
+    fn test_settle_come_out() {
+        let (game, mut position) = setup(0);
+        position.dont_pass = 100;
+
+        // Don't Pass pushes on 12 and wins on 3.
+        assert_eq!(settle(&position, &game, roll(6, 6)).unwrap().winnings, 100);
+        assert_eq!(settle(&position, &game, roll(1, 2)).unwrap().winnings, 200);
+
+        let outcome = settle(&position, &game, roll(4, 4)).unwrap();
+        assert_eq!(outcome.game.point, 8);
+        assert_eq!(outcome.position.dont_pass, 100);
+    }
+
+    #[test]
+    fn test_bundle_reservation() {
+        let (game, mut legs) = setup(0);
+        legs.pass_line = 10;
+        legs.any_craps = 10;
+
+        // Any Craps pays 80 on craps, when Pass loses; a point leaves Pass standing at 20.
+        assert_eq!(position_max_payout(&legs, game.point), Ok(100));
+        assert_eq!(bundle_reservation(&legs, &game), Ok(80));
+
+        // A lone bet has nothing to net against.
+        legs.any_craps = 0;
+        assert_eq!(bundle_reservation(&legs, &game), Ok(20));
+    }
+
+    #[test]
+    fn test_settle_old_epoch_refund() {
+        let (mut game, mut position) = setup(6);
+        position.pass_line = 100;
+        position.field_bet = 50;
+        game.start_new_epoch(3);
+
+        let outcome = settle(&position, &game, roll(2, 4)).unwrap();
+
+        assert!(outcome.refunded);
+        assert_eq!(outcome.winnings, 150);
+        assert_eq!(outcome.position.total_active_bets(), 0);
+        assert_eq!(outcome.position.epoch_id, game.epoch_id);
+    }
+}
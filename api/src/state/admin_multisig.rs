@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use steel::*;
+
+use crate::state::admin_multisig_pda;
+
+use super::OreAccount;
+
+/// Maximum number of members in the admin multisig.
+pub const MAX_ADMIN_SIGNERS: usize = 10;
+
+/// A threshold multisig that can hold the config admin role. When the config admin
+/// is this account's address, admin-gated instructions need signatures from at
+/// least `threshold` distinct members.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct AdminMultisig {
+    /// The members of the multisig. Only the first `num_signers` are used.
+    pub signers: [Pubkey; MAX_ADMIN_SIGNERS],
+
+    /// The number of members.
+    pub num_signers: u8,
+
+    /// The number of member signatures required.
+    pub threshold: u8,
+
+    /// Padding for alignment.
+    pub _padding: [u8; 6],
+}
+
+impl AdminMultisig {
+    pub fn pda() -> (Pubkey, u8) {
+        admin_multisig_pda()
+    }
+
+    /// The active members.
+    pub fn members(&self) -> &[Pubkey] {
+        &self.signers[..(self.num_signers as usize).min(MAX_ADMIN_SIGNERS)]
+    }
+
+    /// Counts the distinct members among `signers`.
+    pub fn approvals(&self, signers: &[Pubkey]) -> usize {
+        self.members()
+            .iter()
+            .filter(|member| signers.contains(member))
+            .count()
+    }
+
+    /// Whether `signers` meet the threshold.
+    pub fn is_approved(&self, signers: &[Pubkey]) -> bool {
+        self.threshold > 0 && self.approvals(signers) >= self.threshold as usize
+    }
+}
+
+account!(OreAccount, AdminMultisig);
+account_size!(AdminMultisig, 336);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_counts_distinct_members() {
+        let members: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let mut multisig = AdminMultisig::zeroed();
+        multisig.signers[..3].copy_from_slice(&members);
+        multisig.num_signers = 3;
+        multisig.threshold = 2;
+
+        assert!(!multisig.is_approved(&[members[0]]));
+        assert!(!multisig.is_approved(&[members[0], members[0]]));
+        assert!(!multisig.is_approved(&[members[0], Pubkey::new_unique()]));
+        assert!(multisig.is_approved(&[members[2], members[0]]));
+    }
+}
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use shank::{ShankAccount, ShankType};
+use steel::*;
+
+use crate::state::audit_log_pda;
+
+use super::OreAccount;
+
+/// Number of admin actions the audit log keeps before overwriting the oldest.
+pub const AUDIT_LOG_CAPACITY: usize = 32;
+
+/// One recorded admin action.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankType)]
+pub struct AuditEntry {
+    /// The signer that submitted the action.
+    pub actor: Pubkey,
+
+    /// The slot the action landed in.
+    pub slot: u64,
+
+    /// The `OreInstruction` discriminator.
+    pub instruction: u8,
+
+    /// Padding for alignment.
+    pub _padding: [u8; 7],
+
+    /// The value before the action. Numeric values are packed as little-endian u64s.
+    pub old_value: [u8; 32],
+
+    /// The value after the action, encoded like `old_value`.
+    pub new_value: [u8; 32],
+}
+
+/// A ring buffer of the most recent admin actions, so parameter history can be
+/// verified on-chain without an indexer.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct AuditLog {
+    /// The total number of actions ever recorded. The next entry is written at
+    /// `head % AUDIT_LOG_CAPACITY`.
+    pub head: u64,
+
+    /// The recorded actions.
+    pub entries: [AuditEntry; AUDIT_LOG_CAPACITY],
+}
+
+impl AuditLog {
+    pub fn pda() -> (Pubkey, u8) {
+        audit_log_pda()
+    }
+
+    /// Records an action, overwriting the oldest once the log is full.
+    pub fn push(&mut self, entry: AuditEntry) {
+        self.entries[(self.head % AUDIT_LOG_CAPACITY as u64) as usize] = entry;
+        self.head = self.head.saturating_add(1);
+    }
+
+    /// The retained actions, oldest first.
+    pub fn entries(&self) -> impl Iterator<Item = &AuditEntry> {
+        let len = (self.head as usize).min(AUDIT_LOG_CAPACITY);
+        let start = if len < AUDIT_LOG_CAPACITY {
+            0
+        } else {
+            (self.head % AUDIT_LOG_CAPACITY as u64) as usize
+        };
+        (0..len).map(move |i| &self.entries[(start + i) % AUDIT_LOG_CAPACITY])
+    }
+}
+
+/// Packs up to four u64s into an audit value.
+pub fn audit_value(values: &[u64]) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    for (chunk, value) in bytes.chunks_exact_mut(8).zip(values) {
+        chunk.copy_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+account!(OreAccount, AuditLog);
+account_size!(AuditLog, 3600);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_log_wraps() {
+        let mut log = AuditLog::zeroed();
+        for slot in 0..(AUDIT_LOG_CAPACITY as u64 + 3) {
+            log.push(AuditEntry {
+                slot,
+                new_value: audit_value(&[slot]),
+                ..AuditEntry::zeroed()
+            });
+        }
+        let slots: Vec<u64> = log.entries().map(|e| e.slot).collect();
+        assert_eq!(slots.len(), AUDIT_LOG_CAPACITY);
+        assert_eq!(slots[0], 3);
+        assert_eq!(*slots.last().unwrap(), AUDIT_LOG_CAPACITY as u64 + 2);
+        assert_eq!(log.head, AUDIT_LOG_CAPACITY as u64 + 3);
+    }
+}
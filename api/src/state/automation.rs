@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
 use steel::*;
 
 use crate::state::miner_pda;
@@ -6,7 +7,7 @@ use crate::state::miner_pda;
 use super::OreAccount;
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
 pub struct Automation {
     /// The amount of SOL to deploy on each territory per round.
     pub amount: u64,
@@ -30,13 +31,40 @@ pub struct Automation {
     /// Bits 0-35 represent the 36 dice combinations (6x6 grid).
     /// If strategy is Random, lower 8 bits determine how many squares to deploy to.
     pub mask: u64,
+
+    /// The most this automation may spend (deploys plus fee) in a single round.
+    /// Zero means no per-round limit.
+    pub round_limit: u64,
+
+    /// The total this automation may still spend (deploys plus fees) over its lifetime.
+    /// Decremented by each automated deploy. u64::MAX means no budget.
+    pub budget: u64,
+
+    /// The round in which `spent_this_round` was spent.
+    pub spent_round_id: u64,
+
+    /// The amount spent in round `spent_round_id`.
+    pub spent_this_round: u64,
 }
 
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, IntoPrimitive, TryFromPrimitive)]
 pub enum AutomationStrategy {
+    /// Deploy `amount` to a random set of squares; the low 8 bits of the mask are the count.
     Random = 0,
+    /// Deploy `amount` to every square in the mask.
     Preferred = 1,
+    /// Deploy `amount` to a single square; the low 8 bits of the mask are the square index.
+    FixedSquare = 2,
+    /// Deploy `amount` to one square of the mask per round, rotating through them.
+    Rotate = 3,
+    /// Deploy `amount` to the previous round's winning square, falling back to the mask.
+    FollowLastWinner = 4,
+    /// Split `amount` evenly across every square in the mask.
+    Spread = 5,
+    /// The executor acts as a session key: each deploy uses the squares and amount it
+    /// signs for, capped at `amount` per square and paid from the automation balance.
+    Delegated = 6,
 }
 
 impl AutomationStrategy {
@@ -49,6 +77,57 @@ impl Automation {
     pub fn pda(&self) -> (Pubkey, u8) {
         miner_pda(self.authority)
     }
+
+    /// Returns how much the automation may still spend in the given round, the
+    /// lesser of its balance, remaining budget, and remaining per-round limit.
+    pub fn spend_allowance(&self, round_id: u64) -> u64 {
+        let mut allowance = self.balance.min(self.budget);
+        if self.round_limit > 0 {
+            let spent = if self.spent_round_id == round_id {
+                self.spent_this_round
+            } else {
+                0
+            };
+            allowance = allowance.min(self.round_limit.saturating_sub(spent));
+        }
+        allowance
+    }
+
+    /// Records an automated spend against the budget and the round limit.
+    pub fn record_spend(&mut self, round_id: u64, amount: u64) {
+        if self.spent_round_id != round_id {
+            self.spent_round_id = round_id;
+            self.spent_this_round = 0;
+        }
+        self.spent_this_round += amount;
+        self.budget = self.budget.saturating_sub(amount);
+    }
 }
 
 account!(OreAccount, Automation);
+account_size!(Automation, 144);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spend_allowance_respects_budget_and_round_limit() {
+        let mut automation = Automation::zeroed();
+        automation.balance = 1_000;
+        automation.budget = u64::MAX;
+        assert_eq!(automation.spend_allowance(1), 1_000);
+
+        automation.round_limit = 300;
+        automation.record_spend(1, 200);
+        assert_eq!(automation.spend_allowance(1), 100);
+
+        // The round limit resets in a new round.
+        assert_eq!(automation.spend_allowance(2), 300);
+
+        automation.budget = 250;
+        automation.record_spend(2, 200);
+        assert_eq!(automation.budget, 50);
+        assert_eq!(automation.spend_allowance(3), 50);
+    }
+}
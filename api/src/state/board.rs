@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
 use steel::*;
 
 use crate::state::board_pda;
@@ -6,7 +7,7 @@ use crate::state::board_pda;
 use super::OreAccount;
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
 pub struct Board {
     /// The current round number.
     pub round_id: u64,
@@ -25,3 +26,4 @@ impl Board {
 }
 
 account!(OreAccount, Board);
+account_size!(Board, 32);
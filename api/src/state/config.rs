@@ -1,12 +1,66 @@
 use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
 use steel::*;
 
+use crate::consts::{ONE_DAY_SLOTS, ONE_ORE};
 use crate::state::config_pda;
 
 use super::OreAccount;
 
+/// Current Config layout version, set by `Initialize` and `MigrateConfig`.
+pub const CONFIG_VERSION: u8 = 1;
+
+/// Hard ceiling on the admin fee (10%).
+pub const MAX_ADMIN_FEE_BPS: u64 = 1000;
+
+/// Motherlode odds (1 in N rounds) used while the config field is unset.
+pub const DEFAULT_MOTHERLODE_ODDS: u64 = 625;
+
+/// Bounds on the admin-configurable motherlode odds.
+pub const MIN_MOTHERLODE_ODDS: u64 = 100;
+pub const MAX_MOTHERLODE_ODDS: u64 = 10_000;
+
+/// ORE minted into the motherlode pool each round while the config field is unset (0.2 ORE).
+pub const DEFAULT_MOTHERLODE_FUNDING: u64 = ONE_ORE / 5;
+
+/// Maximum ORE minted into the motherlode pool each round (1 ORE).
+pub const MAX_MOTHERLODE_FUNDING: u64 = ONE_ORE;
+
+/// Probability (bps) that a round's ORE reward is split, used while the config field is unset.
+pub const DEFAULT_SPLIT_REWARD_BPS: u64 = 5000;
+
+/// Rounds a miner must sit out before its dust balances can be swept (~30 days).
+pub const DUST_SWEEP_INACTIVE_ROUNDS: u64 = 40_000;
+
+/// Longest schedule large claims may vest over (~30 days).
+pub const MAX_VESTING_DURATION_SLOTS: u64 = 30 * ONE_DAY_SLOTS;
+
+/// Pause bits for the modules the guardian or admin can halt.
+pub const PAUSE_MINING: u64 = 1 << 0;
+pub const PAUSE_CRAPS: u64 = 1 << 1;
+pub const PAUSE_STAKING: u64 = 1 << 2;
+pub const PAUSE_EXCHANGE: u64 = 1 << 3;
+pub const PAUSE_CLAIMING: u64 = 1 << 4;
+pub const PAUSE_ALL: u64 = PAUSE_MINING | PAUSE_CRAPS | PAUSE_STAKING | PAUSE_EXCHANGE | PAUSE_CLAIMING;
+
+/// Delay between the admin scheduling an unpause and it taking effect (48 hours).
+pub const UNPAUSE_TIMELOCK_SECONDS: i64 = 48 * 60 * 60;
+
+/// Delay between the admin scheduling a new guardian and it taking over (48 hours).
+pub const GUARDIAN_TIMELOCK_SECONDS: i64 = 48 * 60 * 60;
+
+/// Who may reset rounds. Open lets anyone crank, paid by `PARAM_ROUND_RESET_BOUNTY`;
+/// operators restricts it to keys holding `ROLE_OPERATOR` or `ROLE_CRANKER`, and the admin.
+pub const ROUND_PERMISSION_OPEN: u64 = 0;
+pub const ROUND_PERMISSION_OPERATORS: u64 = 1;
+
+/// Revenue split (bps) used while the policy is unset: the buyback ORE is 90% burned
+/// and 10% shared with stakers, and nothing goes to the insurance fund.
+pub const DEFAULT_REVENUE_BURN_BPS: u64 = 9000;
+pub const DEFAULT_REVENUE_STAKE_BPS: u64 = 1000;
+
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
 pub struct Config {
     /// The address that can update the config.
     pub admin: Pubkey,
@@ -25,12 +79,277 @@ pub struct Config {
 
     /// Amount to pay to fee collector (bps)
     pub admin_fee: u64,
+
+    /// Number of slots a stake withdrawal waits before it can be claimed.
+    pub unstake_cooldown_slots: u64,
+
+    /// Motherlode odds, 1 in N rounds. Zero means the default.
+    pub motherlode_odds: u64,
+
+    /// ORE minted into the motherlode pool each round. Zero means the default.
+    pub motherlode_funding: u64,
+
+    /// Probability (bps) that a round's ORE reward is split pro-rata among all winners.
+    /// Zero means the default.
+    pub split_reward_bps: u64,
+
+    /// Percent of an unsplit round's ORE reward paid to the top miner, with the rest
+    /// shared pro-rata among winners.
+    pub top_miner_share_pct: u64,
+
+    /// ORE claims above this amount vest instead of paying out at once.
+    /// Zero disables vesting.
+    pub vesting_threshold: u64,
+
+    /// Number of slots a vesting claim is released over.
+    pub vesting_duration_slots: u64,
+
+    /// Smallest SOL claim allowed. Smaller balances can be swept. Zero means no minimum.
+    pub min_claim_sol: u64,
+
+    /// Smallest ORE claim allowed. Smaller balances can be swept. Zero means no minimum.
+    pub min_claim_ore: u64,
+
+    /// The admin proposed by `SetAdmin`, which takes over once it calls `AcceptAdmin`.
+    /// The default pubkey means no transfer is pending.
+    pub pending_admin: Pubkey,
+
+    /// The address that can pause modules and force settle craps without the admin.
+    /// It cannot move funds or change fees.
+    pub guardian: Pubkey,
+
+    /// Bitmask of paused modules (`PAUSE_*`).
+    pub paused: u64,
+
+    /// Modules the admin has scheduled to unpause.
+    pub pending_unpause: u64,
+
+    /// Unix timestamp when the scheduled unpause can be applied. Zero means none.
+    pub unpause_effective_at: i64,
+
+    /// The guardian scheduled by the admin. The default pubkey removes the guardian.
+    pub pending_guardian: Pubkey,
+
+    /// Unix timestamp when the scheduled guardian can be applied. Zero means none.
+    pub guardian_effective_at: i64,
+
+    /// Layout version of this account. Zero for accounts that predate versioning.
+    pub version: u8,
+
+    pub _padding: [u8; 7],
+
+    /// CRAP the admin has scheduled to withdraw from the house bankroll.
+    pub house_withdrawal_amount: u64,
+
+    /// Unix timestamp when the scheduled house withdrawal can execute. Zero means none.
+    pub house_withdrawal_effective_at: i64,
+
+    /// Share (bps) of protocol revenue bought back as ORE and burned.
+    pub revenue_burn_bps: u64,
+
+    /// Share (bps) of protocol revenue bought back as ORE and shared with stakers.
+    pub revenue_stake_bps: u64,
+
+    /// Share (bps) of protocol revenue paid into the insurance fund.
+    /// The three shares sum to 10000, or are all zero for the default split.
+    pub revenue_insurance_bps: u64,
+
+    /// Who may reset rounds (`ROUND_PERMISSION_*`).
+    pub round_permission: u64,
+
+    /// Space for future parameters, so they can be added without a realloc.
+    /// `MigrateConfig` backfills any that need a nonzero default.
+    pub _reserved: [u64; 10],
 }
 
 impl Config {
     pub fn pda() -> (Pubkey, u8) {
         config_pda()
     }
+
+    /// Whether any of the `modules` pause bits are set.
+    pub fn is_paused(&self, modules: u64) -> bool {
+        self.paused & modules != 0
+    }
+
+    /// Pauses `modules` at once, dropping any scheduled unpause of them.
+    pub fn pause(&mut self, modules: u64) {
+        self.paused |= modules & PAUSE_ALL;
+        self.pending_unpause &= !modules;
+        if self.pending_unpause == 0 {
+            self.unpause_effective_at = 0;
+        }
+    }
+
+    /// Schedule paused `modules` to unpause after the timelock.
+    pub fn schedule_unpause(&mut self, modules: u64, now: i64) -> Option<i64> {
+        let modules = modules & self.paused;
+        if modules == 0 {
+            return None;
+        }
+        let effective_at = now.checked_add(UNPAUSE_TIMELOCK_SECONDS)?;
+        self.pending_unpause = modules;
+        self.unpause_effective_at = effective_at;
+        Some(effective_at)
+    }
+
+    /// Apply the scheduled unpause if its timelock has expired.
+    /// Returns true if any modules were unpaused.
+    pub fn apply_pending_unpause(&mut self, now: i64) -> bool {
+        if self.unpause_effective_at == 0 || now < self.unpause_effective_at {
+            return false;
+        }
+        self.paused &= !self.pending_unpause;
+        self.pending_unpause = 0;
+        self.unpause_effective_at = 0;
+        true
+    }
+
+    /// Schedule a guardian change that can be applied after the timelock.
+    pub fn schedule_guardian(&mut self, guardian: Pubkey, now: i64) -> Option<i64> {
+        let effective_at = now.checked_add(GUARDIAN_TIMELOCK_SECONDS)?;
+        self.pending_guardian = guardian;
+        self.guardian_effective_at = effective_at;
+        Some(effective_at)
+    }
+
+    /// Apply the scheduled guardian if its timelock has expired.
+    /// Returns true if the guardian was updated.
+    pub fn apply_pending_guardian(&mut self, now: i64) -> bool {
+        if self.guardian_effective_at == 0 || now < self.guardian_effective_at {
+            return false;
+        }
+        self.guardian = self.pending_guardian;
+        self.pending_guardian = Pubkey::default();
+        self.guardian_effective_at = 0;
+        true
+    }
+
+    /// The effective motherlode odds, 1 in N rounds.
+    pub fn motherlode_odds(&self) -> u64 {
+        if self.motherlode_odds == 0 {
+            DEFAULT_MOTHERLODE_ODDS
+        } else {
+            self.motherlode_odds
+        }
+    }
+
+    /// The effective split reward probability (bps).
+    pub fn split_reward_bps(&self) -> u64 {
+        if self.split_reward_bps == 0 {
+            DEFAULT_SPLIT_REWARD_BPS
+        } else {
+            self.split_reward_bps
+        }
+    }
+
+    /// Whether an ORE claim of `amount` should vest rather than pay out at once.
+    pub fn should_vest(&self, amount: u64) -> bool {
+        self.vesting_threshold > 0 && self.vesting_duration_slots > 0 && amount > self.vesting_threshold
+    }
+
+    /// The effective ORE minted into the motherlode pool each round.
+    pub fn motherlode_funding(&self) -> u64 {
+        if self.motherlode_funding == 0 {
+            DEFAULT_MOTHERLODE_FUNDING
+        } else {
+            self.motherlode_funding
+        }
+    }
+
+    /// The effective revenue split (bps) as `(burn, stake, insurance)`.
+    pub fn revenue_split(&self) -> (u64, u64, u64) {
+        if self.revenue_burn_bps == 0 && self.revenue_stake_bps == 0 && self.revenue_insurance_bps == 0 {
+            (DEFAULT_REVENUE_BURN_BPS, DEFAULT_REVENUE_STAKE_BPS, 0)
+        } else {
+            (self.revenue_burn_bps, self.revenue_stake_bps, self.revenue_insurance_bps)
+        }
+    }
+
+    /// Splits `amount` SOL of revenue into `(buyback, insurance)` shares.
+    pub fn split_revenue(&self, amount: u64) -> (u64, u64) {
+        let (_, _, insurance_bps) = self.revenue_split();
+        let insurance = (amount as u128 * insurance_bps as u128 / 10_000) as u64;
+        (amount - insurance, insurance)
+    }
+
+    /// The share of `amount` bought-back ORE to distribute to stakers. The rest is burned.
+    pub fn buyback_stake_share(&self, amount: u64) -> u64 {
+        let (burn_bps, stake_bps, _) = self.revenue_split();
+        let buyback_bps = burn_bps + stake_bps;
+        if buyback_bps == 0 {
+            return 0;
+        }
+        (amount as u128 * stake_bps as u128 / buyback_bps as u128) as u64
+    }
 }
 
 account!(OreAccount, Config);
+account_size!(Config, 512);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpause_respects_timelock() {
+        let mut config = Config::zeroed();
+        config.pause(PAUSE_CRAPS | PAUSE_CLAIMING);
+        assert!(config.is_paused(PAUSE_CRAPS));
+        assert!(!config.is_paused(PAUSE_MINING));
+
+        // Only paused modules can be scheduled.
+        assert!(config.schedule_unpause(PAUSE_MINING, 1_000).is_none());
+        let effective_at = config.schedule_unpause(PAUSE_CRAPS, 1_000).unwrap();
+        assert_eq!(effective_at, 1_000 + UNPAUSE_TIMELOCK_SECONDS);
+        assert!(!config.apply_pending_unpause(effective_at - 1));
+        assert!(config.apply_pending_unpause(effective_at));
+        assert!(!config.is_paused(PAUSE_CRAPS));
+        assert!(config.is_paused(PAUSE_CLAIMING));
+    }
+
+    #[test]
+    fn test_pause_cancels_scheduled_unpause() {
+        let mut config = Config::zeroed();
+        config.pause(PAUSE_STAKING);
+        config.schedule_unpause(PAUSE_STAKING, 0).unwrap();
+        config.pause(PAUSE_STAKING);
+        assert!(!config.apply_pending_unpause(UNPAUSE_TIMELOCK_SECONDS));
+        assert!(config.is_paused(PAUSE_STAKING));
+    }
+
+    #[test]
+    fn test_guardian_change_respects_timelock() {
+        let mut config = Config::zeroed();
+        let guardian = Pubkey::new_unique();
+        let effective_at = config.schedule_guardian(guardian, 1_000).unwrap();
+        assert_eq!(effective_at, 1_000 + GUARDIAN_TIMELOCK_SECONDS);
+        assert!(!config.apply_pending_guardian(effective_at - 1));
+        assert_eq!(config.guardian, Pubkey::default());
+        assert!(config.apply_pending_guardian(effective_at));
+        assert_eq!(config.guardian, guardian);
+        assert!(!config.apply_pending_guardian(effective_at + 1));
+    }
+
+    #[test]
+    fn test_revenue_split() {
+        // The default split matches the legacy 10% staker share of buybacks.
+        let mut config = Config::zeroed();
+        assert_eq!(config.split_revenue(1_000), (1_000, 0));
+        assert_eq!(config.buyback_stake_share(1_000), 100);
+
+        // Stake share is relative to the buyback, since insurance is taken out first.
+        config.revenue_burn_bps = 6000;
+        config.revenue_stake_bps = 2000;
+        config.revenue_insurance_bps = 2000;
+        assert_eq!(config.split_revenue(1_000), (800, 200));
+        assert_eq!(config.buyback_stake_share(800), 200);
+
+        // Nothing is shared when all revenue goes to insurance.
+        config.revenue_burn_bps = 0;
+        config.revenue_stake_bps = 0;
+        config.revenue_insurance_bps = 10_000;
+        assert_eq!(config.split_revenue(1_000), (0, 1_000));
+        assert_eq!(config.buyback_stake_share(0), 0);
+    }
+}
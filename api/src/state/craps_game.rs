@@ -1,14 +1,74 @@
 use serde::{Deserialize, Serialize};
+use serde_big_array::BigArray;
+use shank::{ShankAccount, ShankType};
 use steel::*;
 
+use crate::error::OreError;
 use crate::state::craps_game_pda;
 
 use super::OreAccount;
 
+/// Share of reserved payouts (bps) kept in the bankroll on top of the reservations
+/// themselves when the house withdraws.
+pub const HOUSE_SAFETY_BUFFER_BPS: u64 = 5000;
+
+/// Delay between scheduling a house bankroll withdrawal and executing it (48 hours).
+pub const HOUSE_WITHDRAWAL_TIMELOCK_SECONDS: i64 = 48 * 60 * 60;
+
+/// Number of rolls whose table phase the craps game keeps (16 rounds of sub-rolls).
+/// Positions further behind than this can no longer settle and are refunded.
+pub const PHASE_HISTORY_LEN: usize = 64;
+
+/// The table phase a roll was thrown in, as recorded when the roll was applied.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankType)]
+pub struct TablePhase {
+    /// The round the roll belongs to.
+    pub round_id: u64,
+
+    /// The epoch the roll was thrown in.
+    pub epoch_id: u64,
+
+    /// The table phase counter before the roll.
+    pub phase_id: u32,
+
+    /// The point before the roll (0 = come-out).
+    pub point: u8,
+
+    /// Whether the roll was a come-out roll.
+    pub is_come_out: u8, // 0 = false, 1 = true
+
+    /// The roll's sub-roll index within its round.
+    pub roll_index: u8,
+
+    /// Whether the roll has been recorded. Entries of legacy accounts read as 0.
+    pub is_recorded: u8,
+}
+
+impl TablePhase {
+    /// Check if the roll was a come-out roll.
+    pub fn is_coming_out(&self) -> bool {
+        self.is_come_out == 1
+    }
+
+    /// Get the point if one was established.
+    pub fn get_point(&self) -> Option<u8> {
+        if self.point == 0 {
+            None
+        } else {
+            Some(self.point)
+        }
+    }
+}
+
 /// CrapsGame is a singleton account that tracks the global craps game state.
 /// It maintains epoch information and the current point for line bets.
+///
+/// The table moves once per roll, when reset applies a round's sub-rolls, and records
+/// the phase each roll was thrown in. Positions settle against those records in order,
+/// so every position sees the same point and epoch for a roll.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
 pub struct CrapsGame {
     /// The current epoch number. An epoch is a sequence of rounds that ends when a 7 is rolled.
     pub epoch_id: u64,
@@ -20,7 +80,11 @@ pub struct CrapsGame {
     pub is_come_out: u8, // 0 = false, 1 = true
 
     /// Padding for alignment.
-    pub _padding: [u8; 6],
+    pub _padding: [u8; 2],
+
+    /// Table phase counter, incremented by every roll applied to the table.
+    /// Positions record the phase of the next roll they have yet to settle.
+    pub phase_id: u32,
 
     /// The round ID when this epoch started.
     pub epoch_start_round: u64,
@@ -36,6 +100,23 @@ pub struct CrapsGame {
 
     /// Total potential payouts reserved for pending bets
     pub reserved_payouts: u64,
+
+    /// Share of net house wins routed to stakers each epoch (bps).
+    pub staker_share_bps: u64,
+
+    /// The epoch in which house profit was last distributed to stakers.
+    pub last_distribution_epoch: u64,
+
+    /// High-water mark of net house wins already shared with stakers.
+    pub distributed_net_wins: u64,
+
+    /// Total CRAP tokens routed from the house bankroll to stakers.
+    pub total_distributed_to_stakers: u64,
+
+    /// The phases of the most recent rolls. The roll with phase counter `p` is stored at
+    /// `p % PHASE_HISTORY_LEN`.
+    #[serde(with = "BigArray")]
+    pub phase_history: [TablePhase; PHASE_HISTORY_LEN],
 }
 
 impl CrapsGame {
@@ -79,6 +160,115 @@ impl CrapsGame {
         self.is_come_out = 1;
     }
 
+    /// The phase the table is in now, which the next roll will be thrown in.
+    pub fn phase(&self) -> TablePhase {
+        TablePhase {
+            epoch_id: self.epoch_id,
+            phase_id: self.phase_id,
+            point: self.point,
+            is_come_out: self.is_come_out,
+            ..TablePhase::default()
+        }
+    }
+
+    /// Applies a roll with `dice_sum` to the table, recording the phase it was thrown in.
+    /// A come-out point number sets the point, hitting the point returns to come-out,
+    /// and a seven in the point phase ends the epoch. Returns the recorded phase.
+    pub fn apply_roll(&mut self, round_id: u64, roll_index: u8, dice_sum: u8) -> TablePhase {
+        let phase = TablePhase {
+            round_id,
+            roll_index,
+            is_recorded: 1,
+            ..self.phase()
+        };
+        self.phase_history[self.phase_id as usize % PHASE_HISTORY_LEN] = phase;
+
+        if self.is_coming_out() {
+            if matches!(dice_sum, 4 | 5 | 6 | 8 | 9 | 10) {
+                self.set_point(dice_sum);
+            }
+        } else if dice_sum == self.point {
+            self.clear_point();
+        } else if dice_sum == 7 {
+            self.start_new_epoch(round_id);
+        }
+        self.phase_id = self.phase_id.wrapping_add(1);
+        phase
+    }
+
+    /// The recorded phase of the roll with counter `phase_id`, or None if that roll has
+    /// not been thrown yet or has left the history.
+    pub fn recorded_phase(&self, phase_id: u32) -> Option<&TablePhase> {
+        let age = self.phase_id.wrapping_sub(phase_id) as usize;
+        if age == 0 || age > PHASE_HISTORY_LEN {
+            return None;
+        }
+        let phase = &self.phase_history[phase_id as usize % PHASE_HISTORY_LEN];
+        (phase.is_recorded == 1 && phase.phase_id == phase_id).then_some(phase)
+    }
+
+    /// Net house wins (collected minus paid out) over the game's lifetime.
+    pub fn net_wins(&self) -> u64 {
+        self.total_collected.saturating_sub(self.total_payouts)
+    }
+
+    /// Bankroll the house may withdraw: everything above reserved payouts plus a
+    /// `HOUSE_SAFETY_BUFFER_BPS` buffer on them.
+    pub fn withdrawable_bankroll(&self) -> u64 {
+        let buffer = (self.reserved_payouts as u128 * HOUSE_SAFETY_BUFFER_BPS as u128 / 10000) as u64;
+        self.house_bankroll
+            .saturating_sub(self.reserved_payouts)
+            .saturating_sub(buffer)
+    }
+
+    /// Checks the solvency invariants: the bankroll covers every reserved payout, and
+    /// the vault's `vault_balance` covers the bankroll plus `escrow` held for players.
+    pub fn check_solvency(&self, vault_balance: u64, escrow: u64) -> Result<(), OreError> {
+        if self.house_bankroll < self.reserved_payouts {
+            return Err(OreError::ReservesUncovered);
+        }
+        let required = self
+            .house_bankroll
+            .checked_add(escrow)
+            .ok_or(OreError::ArithmeticOverflow)?;
+        if vault_balance < required {
+            return Err(OreError::VaultShortfall);
+        }
+        Ok(())
+    }
+
+    /// CRAP owed to stakers from net wins above the last distribution's high-water mark,
+    /// limited to bankroll not reserved for pending bets. Returns None if nothing is owed
+    /// or a distribution already ran this epoch.
+    pub fn staker_distribution(&self) -> Option<u64> {
+        if self.staker_share_bps == 0 || self.epoch_id <= self.last_distribution_epoch {
+            return None;
+        }
+        let new_wins = self.net_wins().checked_sub(self.distributed_net_wins)?;
+        let share = (new_wins as u128)
+            .checked_mul(self.staker_share_bps as u128)?
+            .checked_div(10000)? as u64;
+        let available = self.house_bankroll.saturating_sub(self.reserved_payouts);
+        let amount = share.min(available);
+        (amount > 0).then_some(amount)
+    }
+
+    /// Record a staker distribution. The high-water mark only advances by the wins the
+    /// amount actually covers, so a share capped by reserves carries into later epochs.
+    pub fn record_staker_distribution(&mut self, amount: u64) -> Option<()> {
+        let covered_wins = (amount as u128)
+            .checked_mul(10000)?
+            .checked_div(self.staker_share_bps as u128)? as u64;
+        self.house_bankroll = self.house_bankroll.checked_sub(amount)?;
+        self.total_distributed_to_stakers = self.total_distributed_to_stakers.checked_add(amount)?;
+        self.distributed_net_wins = self
+            .distributed_net_wins
+            .saturating_add(covered_wins)
+            .min(self.net_wins());
+        self.last_distribution_epoch = self.epoch_id;
+        Some(())
+    }
+
     /// Start a new epoch.
     pub fn start_new_epoch(&mut self, round_id: u64) {
         self.epoch_id += 1;
@@ -88,3 +278,130 @@ impl CrapsGame {
 }
 
 account!(OreAccount, CrapsGame);
+account_size!(CrapsGame, 1632);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_roll_records_phases() {
+        let mut game = CrapsGame::zeroed();
+        game.epoch_id = 1;
+        game.clear_point();
+
+        // Come-out 6 sets the point, 8 leaves it, and a seven ends the epoch.
+        game.apply_roll(10, 0, 6);
+        game.apply_roll(10, 1, 8);
+        game.apply_roll(10, 2, 7);
+        assert!(game.is_coming_out());
+        assert_eq!(game.epoch_id, 2);
+        assert_eq!(game.epoch_start_round, 10);
+        assert_eq!(game.phase_id, 3);
+
+        // Each roll keeps the phase it was thrown in.
+        let first = game.recorded_phase(0).unwrap();
+        assert!(first.is_coming_out());
+        assert_eq!((first.round_id, first.roll_index, first.epoch_id), (10, 0, 1));
+        let last = game.recorded_phase(2).unwrap();
+        assert_eq!(last.get_point(), Some(6));
+        assert_eq!(last.epoch_id, 1);
+
+        // Making the point returns to come-out in the same epoch.
+        game.apply_roll(11, 0, 4);
+        game.apply_roll(11, 1, 4);
+        assert!(game.is_coming_out());
+        assert_eq!(game.epoch_id, 2);
+
+        // Rolls not yet thrown have no phase.
+        assert_eq!(game.recorded_phase(game.phase_id), None);
+    }
+
+    #[test]
+    fn test_phase_history_forgets_old_rolls() {
+        let mut game = CrapsGame::zeroed();
+        game.clear_point();
+        for i in 0..(PHASE_HISTORY_LEN as u64 + 2) {
+            game.apply_roll(i, 0, 11);
+        }
+        assert_eq!(game.recorded_phase(0), None);
+        assert_eq!(game.recorded_phase(1), None);
+        assert_eq!(game.recorded_phase(2).unwrap().round_id, 2);
+
+        // Legacy accounts have an empty history.
+        let mut legacy = CrapsGame::zeroed();
+        legacy.phase_id = 5;
+        assert_eq!(legacy.recorded_phase(4), None);
+    }
+
+    #[test]
+    fn test_staker_distribution_shares_new_net_wins_once_per_epoch() {
+        let mut game = CrapsGame::zeroed();
+        game.epoch_id = 1;
+        game.staker_share_bps = 1000;
+        game.house_bankroll = 10_000;
+        game.total_collected = 5_000;
+        game.total_payouts = 3_000;
+
+        // 10% of 2,000 net wins.
+        assert_eq!(game.staker_distribution(), Some(200));
+        game.record_staker_distribution(200).unwrap();
+        assert_eq!(game.house_bankroll, 9_800);
+        assert_eq!(game.distributed_net_wins, 2_000);
+
+        // Only once per epoch.
+        game.total_collected = 6_000;
+        assert_eq!(game.staker_distribution(), None);
+
+        // Next epoch shares only wins above the high-water mark.
+        game.start_new_epoch(10);
+        assert_eq!(game.staker_distribution(), Some(100));
+
+        // A losing stretch below the high-water mark owes nothing.
+        game.total_payouts = 5_000;
+        assert_eq!(game.staker_distribution(), None);
+    }
+
+    #[test]
+    fn test_staker_distribution_leaves_reserved_payouts() {
+        let mut game = CrapsGame::zeroed();
+        game.epoch_id = 1;
+        game.staker_share_bps = 5000;
+        game.house_bankroll = 1_000;
+        game.reserved_payouts = 900;
+        game.total_collected = 4_000;
+
+        assert_eq!(game.staker_distribution(), Some(100));
+
+        // The uncovered 1,800 of wins carries over once reserves free up.
+        game.record_staker_distribution(100).unwrap();
+        assert_eq!(game.distributed_net_wins, 200);
+        game.start_new_epoch(10);
+        game.house_bankroll = 10_000;
+        game.reserved_payouts = 0;
+        assert_eq!(game.staker_distribution(), Some(1_900));
+    }
+
+    #[test]
+    fn test_withdrawable_bankroll_keeps_buffer() {
+        let mut game = CrapsGame::zeroed();
+        game.house_bankroll = 10_000;
+        game.reserved_payouts = 2_000;
+        assert_eq!(game.withdrawable_bankroll(), 7_000);
+
+        game.reserved_payouts = 7_000;
+        assert_eq!(game.withdrawable_bankroll(), 0);
+    }
+
+    #[test]
+    fn test_check_solvency() {
+        let mut game = CrapsGame::zeroed();
+        game.house_bankroll = 10_000;
+        game.reserved_payouts = 4_000;
+        assert_eq!(game.check_solvency(10_500, 500), Ok(()));
+        assert_eq!(game.check_solvency(10_499, 500), Err(OreError::VaultShortfall));
+
+        game.reserved_payouts = 10_001;
+        assert_eq!(game.check_solvency(u64::MAX, 0), Err(OreError::ReservesUncovered));
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
 use steel::*;
 
+use crate::error::OreError;
 use crate::state::craps_position_pda;
 
 use super::OreAccount;
@@ -11,9 +13,27 @@ pub const NUM_POINTS: usize = 6;
 /// Number of hardway bets (hard 4, 6, 8, 10).
 pub const NUM_HARDWAYS: usize = 4;
 
+// Active-bet categories tracked in `CrapsPosition::active_bets`.
+// Settlement skips every section whose bit is clear.
+pub const BET_MASK_LINE: u32 = 1 << 0;
+pub const BET_MASK_COME: u32 = 1 << 1;
+pub const BET_MASK_PLACE: u32 = 1 << 2;
+pub const BET_MASK_HARDWAY: u32 = 1 << 3;
+pub const BET_MASK_SINGLE_ROLL: u32 = 1 << 4;
+pub const BET_MASK_NEXT: u32 = 1 << 5;
+pub const BET_MASK_YES: u32 = 1 << 6;
+pub const BET_MASK_NO: u32 = 1 << 7;
+/// Bets held on the companion CrapsSideBets account (bonus, shooter, and Fielder's Choice).
+pub const BET_MASK_SIDE: u32 = 1 << 8;
+
+/// Set once the bitmap is maintained for a position. Positions written before the
+/// bitmap existed have it clear and are rebuilt from their bet fields on first use.
+pub const BET_MASK_TRACKED: u32 = 1 << 31;
+
 /// CrapsPosition tracks a user's craps bets for the current epoch.
+/// Rarely-used exotic bets live on a lazily-created CrapsSideBets account.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
 pub struct CrapsPosition {
     /// The authority (owner) of this craps position.
     pub authority: Pubkey,
@@ -61,8 +81,9 @@ pub struct CrapsPosition {
     /// Whether place bets are working (on).
     pub place_working: u8, // 0 = off, 1 = on
 
-    /// Padding for alignment.
-    pub _padding1: [u8; 7],
+    /// Reservation a hedged bundle saved, as a 7-byte little-endian integer. It sits
+    /// in what was alignment padding, so older accounts read as zero.
+    pub bundle_credit: [u8; 7],
 
     // ==================== YES BETS (TRUE ODDS) ====================
     // "Yes" bets - chosen sum hits before 7, pays at true odds (0% house edge).
@@ -112,93 +133,14 @@ pub struct CrapsPosition {
     /// Twelve bet (wins on 12).
     pub twelve: u64,
 
-    // ==================== BONUS CRAPS SIDE BETS ====================
-    // These bets win if all required totals are hit before a 7.
-
-    /// Small bet amount - wins if 2,3,4,5,6 all hit before 7.
-    pub bonus_small: u64,
-
-    /// Tall bet amount - wins if 8,9,10,11,12 all hit before 7.
-    pub bonus_tall: u64,
-
-    /// All bet amount - wins if all 2-6 and 8-12 hit before 7.
-    pub bonus_all: u64,
-
-    /// Bitmask tracking which Small totals have been hit.
-    /// Bit 0 = 2, Bit 1 = 3, Bit 2 = 4, Bit 3 = 5, Bit 4 = 6.
-    pub small_hits: u8,
-
-    /// Bitmask tracking which Tall totals have been hit.
-    /// Bit 0 = 8, Bit 1 = 9, Bit 2 = 10, Bit 3 = 11, Bit 4 = 12.
-    pub tall_hits: u8,
-
-    /// Padding for alignment.
-    pub _padding2: [u8; 6],
-
-    // ==================== COME-OUT ONLY SIDE BETS ====================
-    // These bets can only be placed on come-out roll and persist until seven-out.
-
-    /// Fire Bet - wins based on unique points made (4+ required).
-    pub fire_bet: u64,
-
-    /// Fire Bet tracking: bitmask of unique points made.
-    /// Bit 0=4, 1=5, 2=6, 3=8, 4=9, 5=10.
-    pub fire_points_made: u8,
-
-    /// Padding for u64 alignment after fire_points_made.
-    pub _pad_fire: [u8; 7],
-
-    /// Different Doubles bet.
-    pub diff_doubles_bet: u64,
-
-    /// Different Doubles tracking: bitmask of unique doubles rolled.
-    /// Bit 0=1-1, 1=2-2, 2=3-3, 3=4-4, 4=5-5, 5=6-6.
-    pub diff_doubles_hits: u8,
-
-    /// Padding for u64 alignment after diff_doubles_hits.
-    pub _pad_diff: [u8; 7],
-
-    /// Ride the Line bet - wins based on pass line wins before seven-out.
-    pub ride_the_line_bet: u64,
-
-    /// Ride the Line tracking: count of pass line wins this shooter.
-    pub ride_wins_count: u8,
-
-    /// Padding for u64 alignment after ride_wins_count.
-    pub _pad_ride: [u8; 7],
-
-    /// Mugsy's Corner bet - wins on 7 (different payouts based on phase).
-    pub mugsy_bet: u64,
-
-    /// Mugsy's Corner tracking: 0=come-out, 1=point phase, 2=resolved.
-    pub mugsy_state: u8,
-
-    /// Padding for u64 alignment after mugsy_state.
-    pub _pad_mugsy: [u8; 7],
-
-    /// Hot Hand bet - must hit all 10 totals (2-6, 8-12) before 7.
-    pub hot_hand_bet: u64,
-
-    /// Hot Hand tracking: bitmask of totals hit (same as small_hits | tall_hits).
-    /// Bit 0-4 = 2,3,4,5,6 | Bit 5-9 = 8,9,10,11,12.
-    pub hot_hand_hits: u16,
-
-    /// Padding for u64 alignment after hot_hand_hits.
-    pub _pad_hot: [u8; 6],
-
-    /// Replay bet - wins when same point is made multiple times.
-    pub replay_bet: u64,
-
-    /// Replay tracking: count of times each point was made.
-    /// Index: 0=4, 1=5, 2=6, 3=8, 4=9, 5=10.
-    pub replay_counts: [u8; NUM_POINTS],
+    // ==================== TABLE SYNC ====================
 
-    /// Padding for u64 alignment after replay_counts.
-    pub _pad_replay: [u8; 2],
+    /// The CrapsGame phase_id of the next roll this position has yet to settle.
+    /// Phase (come-out vs point) lives only on CrapsGame, which records it for every roll.
+    pub phase_id: u32,
 
-    /// Fielder's Choice bets (3 single-roll bets).
-    /// [0] = 2,3,4 | [1] = 4,9,10 | [2] = 10,11,12
-    pub fielders_choice: [u64; 3],
+    /// Bitmask of active bet categories (BET_MASK_*), set at placement.
+    pub active_bets: u32,
 
     // ==================== TRACKING ====================
 
@@ -222,6 +164,10 @@ pub struct CrapsPosition {
     /// This allows settlement to complete even when house can't pay, avoiding stuck state.
     /// User can claim this debt later when house is funded.
     pub unpaid_debt: u64,
+
+    /// House reservations (max payouts) currently held by this position's bets.
+    /// This is the exact ledger released when the bets settle, are refunded, or are forfeited.
+    pub reserved_payouts: u64,
 }
 
 impl CrapsPosition {
@@ -239,7 +185,25 @@ impl CrapsPosition {
         self.place_working = if working { 1 } else { 0 };
     }
 
-    /// Get total active bets.
+    /// The reservation hedged bundles saved this position: the sum of their legs'
+    /// maxima less what they reserved. Settlement reserves it again before the next roll.
+    pub fn get_bundle_credit(&self) -> u64 {
+        let mut bytes = [0; 8];
+        bytes[..7].copy_from_slice(&self.bundle_credit);
+        u64::from_le_bytes(bytes)
+    }
+
+    /// Set the bundle credit. Fails if it doesn't fit in 7 bytes.
+    pub fn set_bundle_credit(&mut self, credit: u64) -> Result<(), OreError> {
+        let bytes = credit.to_le_bytes();
+        if bytes[7] != 0 {
+            return Err(OreError::ArithmeticOverflow);
+        }
+        self.bundle_credit.copy_from_slice(&bytes[..7]);
+        Ok(())
+    }
+
+    /// Get total active bets held on this account (side bets live on CrapsSideBets).
     pub fn total_active_bets(&self) -> u64 {
         let mut total = self.pass_line
             + self.dont_pass
@@ -250,19 +214,7 @@ impl CrapsPosition {
             + self.any_craps
             + self.yo_eleven
             + self.aces
-            + self.twelve
-            + self.bonus_small
-            + self.bonus_tall
-            + self.bonus_all
-            + self.fire_bet
-            + self.diff_doubles_bet
-            + self.ride_the_line_bet
-            + self.mugsy_bet
-            + self.hot_hand_bet
-            + self.replay_bet
-            + self.fielders_choice[0]
-            + self.fielders_choice[1]
-            + self.fielders_choice[2];
+            + self.twelve;
 
         for i in 0..NUM_POINTS {
             total += self.come_bets[i]
@@ -284,6 +236,93 @@ impl CrapsPosition {
         total
     }
 
+    /// The field a bet of this type is held in, or `None` for side bets and points the
+    /// bet can't be placed on. Line odds always sit on the table point.
+    pub fn bet_mut(&mut self, bet_type: u8, point: u8) -> Option<&mut u64> {
+        let bet = match bet_type {
+            0 => &mut self.pass_line,
+            1 => &mut self.dont_pass,
+            2 => &mut self.pass_odds,
+            3 => &mut self.dont_pass_odds,
+            4 => &mut self.come_bets[point_to_index(point)?],
+            5 => &mut self.dont_come_bets[point_to_index(point)?],
+            6 => &mut self.come_odds[point_to_index(point)?],
+            7 => &mut self.dont_come_odds[point_to_index(point)?],
+            8 => &mut self.place_bets[point_to_index(point)?],
+            9 => &mut self.hardways[hardway_to_index(point)?],
+            10 => &mut self.field_bet,
+            11 => &mut self.any_seven,
+            12 => &mut self.any_craps,
+            13 => &mut self.yo_eleven,
+            14 => &mut self.aces,
+            15 => &mut self.twelve,
+            26 if is_valid_yes_no_sum(point) => &mut self.yes_bets[sum_to_index(point)?],
+            27 if is_valid_yes_no_sum(point) => &mut self.no_bets[sum_to_index(point)?],
+            28 => &mut self.next_bets[sum_to_index(point)?],
+            _ => return None,
+        };
+        Some(bet)
+    }
+
+    /// Check whether any bet in the given categories may be active.
+    /// Bits are set at placement and cleared lazily, so a set bit can be stale but a
+    /// clear bit always means the category is empty.
+    pub fn has_active_bets(&self, mask: u32) -> bool {
+        self.active_bets & mask != 0
+    }
+
+    /// Check whether the bitmap is maintained for this position.
+    pub fn is_tracking_active_bets(&self) -> bool {
+        self.active_bets & BET_MASK_TRACKED != 0
+    }
+
+    /// Mark the categories of a newly placed bet as active.
+    pub fn mark_active_bets(&mut self, mask: u32) {
+        if !self.is_tracking_active_bets() {
+            self.refresh_active_bets();
+        }
+        self.active_bets |= mask;
+    }
+
+    /// Record whether the companion CrapsSideBets account holds bets.
+    pub fn set_side_bets_active(&mut self, active: bool) {
+        if active {
+            self.mark_active_bets(BET_MASK_SIDE);
+        } else {
+            self.active_bets &= !BET_MASK_SIDE;
+        }
+    }
+
+    /// Rebuild the bitmap from the bet fields.
+    /// The side-bet bit is kept as is, since those bets live on CrapsSideBets.
+    pub fn refresh_active_bets(&mut self) {
+        let line = self.pass_line | self.dont_pass | self.pass_odds | self.dont_pass_odds;
+        let come = (0..NUM_POINTS).fold(0, |acc, i| {
+            acc | self.come_bets[i] | self.come_odds[i] | self.dont_come_bets[i] | self.dont_come_odds[i]
+        });
+        let single_roll = self.field_bet
+            | self.any_seven
+            | self.any_craps
+            | self.yo_eleven
+            | self.aces
+            | self.twelve;
+        let categories = [
+            (line, BET_MASK_LINE),
+            (come, BET_MASK_COME),
+            (self.place_bets.iter().fold(0, |acc, &x| acc | x), BET_MASK_PLACE),
+            (self.hardways.iter().fold(0, |acc, &x| acc | x), BET_MASK_HARDWAY),
+            (single_roll, BET_MASK_SINGLE_ROLL),
+            (self.next_bets.iter().fold(0, |acc, &x| acc | x), BET_MASK_NEXT),
+            (self.yes_bets.iter().fold(0, |acc, &x| acc | x), BET_MASK_YES),
+            (self.no_bets.iter().fold(0, |acc, &x| acc | x), BET_MASK_NO),
+        ];
+        let side = self.active_bets & BET_MASK_SIDE;
+        self.active_bets = categories
+            .iter()
+            .filter(|(amount, _)| *amount != 0)
+            .fold(BET_MASK_TRACKED | side, |acc, (_, mask)| acc | mask);
+    }
+
     /// Clear single-roll bets.
     pub fn clear_single_roll_bets(&mut self) {
         self.field_bet = 0;
@@ -292,11 +331,11 @@ impl CrapsPosition {
         self.yo_eleven = 0;
         self.aces = 0;
         self.twelve = 0;
-        self.fielders_choice = [0; 3];
         self.next_bets = [0; 11];
     }
 
     /// Clear all bets (for new epoch).
+    /// Also clears the side-bet bit; callers clear the CrapsSideBets account alongside.
     pub fn clear_all_bets(&mut self) {
         self.pass_line = 0;
         self.dont_pass = 0;
@@ -311,186 +350,7 @@ impl CrapsPosition {
         self.no_bets = [0; 11];
         self.hardways = [0; NUM_HARDWAYS];
         self.clear_single_roll_bets();
-        self.clear_bonus_bets();
-        self.clear_shooter_bets();
-    }
-
-    /// Clear bonus craps bets and reset hit tracking.
-    pub fn clear_bonus_bets(&mut self) {
-        self.bonus_small = 0;
-        self.bonus_tall = 0;
-        self.bonus_all = 0;
-        self.small_hits = 0;
-        self.tall_hits = 0;
-    }
-
-    /// Record a dice total for bonus craps tracking.
-    /// Returns (small_complete, tall_complete) indicating if either bet just won.
-    pub fn record_bonus_hit(&mut self, total: u8) -> (bool, bool) {
-        let mut small_just_completed = false;
-        let mut tall_just_completed = false;
-
-        // Small tracks totals 2-6 (bits 0-4 map to totals 2-6)
-        if total >= 2 && total <= 6 {
-            let bit = total - 2; // 2->0, 3->1, 4->2, 5->3, 6->4
-            let was_complete = self.small_hits == 0b11111;
-            self.small_hits |= 1 << bit;
-            small_just_completed = !was_complete && self.small_hits == 0b11111;
-        }
-
-        // Tall tracks totals 8-12 (bits 0-4 map to totals 8-12)
-        if total >= 8 && total <= 12 {
-            let bit = total - 8; // 8->0, 9->1, 10->2, 11->3, 12->4
-            let was_complete = self.tall_hits == 0b11111;
-            self.tall_hits |= 1 << bit;
-            tall_just_completed = !was_complete && self.tall_hits == 0b11111;
-        }
-
-        (small_just_completed, tall_just_completed)
-    }
-
-    /// Check if Small bet is complete (all 2,3,4,5,6 hit).
-    pub fn is_small_complete(&self) -> bool {
-        self.small_hits == 0b11111
-    }
-
-    /// Check if Tall bet is complete (all 8,9,10,11,12 hit).
-    pub fn is_tall_complete(&self) -> bool {
-        self.tall_hits == 0b11111
-    }
-
-    /// Check if All bet is complete (both Small and Tall complete).
-    pub fn is_all_complete(&self) -> bool {
-        self.is_small_complete() && self.is_tall_complete()
-    }
-
-    /// Check if player has any active bonus bets.
-    pub fn has_bonus_bets(&self) -> bool {
-        self.bonus_small > 0 || self.bonus_tall > 0 || self.bonus_all > 0
-    }
-
-    /// Clear come-out only side bets and their tracking (called on seven-out).
-    pub fn clear_shooter_bets(&mut self) {
-        self.fire_bet = 0;
-        self.fire_points_made = 0;
-        self.diff_doubles_bet = 0;
-        self.diff_doubles_hits = 0;
-        self.ride_the_line_bet = 0;
-        self.ride_wins_count = 0;
-        self.mugsy_bet = 0;
-        self.mugsy_state = 0;
-        self.hot_hand_bet = 0;
-        self.hot_hand_hits = 0;
-        self.replay_bet = 0;
-        self.replay_counts = [0; NUM_POINTS];
-    }
-
-    /// Check if player has any active shooter bets.
-    pub fn has_shooter_bets(&self) -> bool {
-        self.fire_bet > 0
-            || self.diff_doubles_bet > 0
-            || self.ride_the_line_bet > 0
-            || self.mugsy_bet > 0
-            || self.hot_hand_bet > 0
-            || self.replay_bet > 0
-    }
-
-    /// Record a point being made for Fire Bet tracking.
-    /// Returns the number of unique points made (for payout calculation).
-    pub fn record_fire_point(&mut self, point: u8) -> u8 {
-        if let Some(idx) = point_to_index(point) {
-            self.fire_points_made |= 1 << idx;
-        }
-        self.fire_points_made.count_ones() as u8
-    }
-
-    /// Get number of unique points made for Fire Bet.
-    pub fn fire_points_count(&self) -> u8 {
-        self.fire_points_made.count_ones() as u8
-    }
-
-    /// Record a double roll for Different Doubles tracking.
-    /// Returns the number of unique doubles hit (for payout calculation).
-    pub fn record_double(&mut self, die_value: u8) -> u8 {
-        if die_value >= 1 && die_value <= 6 {
-            let bit = die_value - 1; // 1->0, 2->1, etc.
-            self.diff_doubles_hits |= 1 << bit;
-        }
-        self.diff_doubles_hits.count_ones() as u8
-    }
-
-    /// Get number of unique doubles hit.
-    pub fn diff_doubles_count(&self) -> u8 {
-        self.diff_doubles_hits.count_ones() as u8
-    }
-
-    /// Record a pass line win for Ride the Line tracking.
-    pub fn record_ride_win(&mut self) {
-        if self.ride_wins_count < 255 {
-            self.ride_wins_count += 1;
-        }
-    }
-
-    /// Record a dice total for Hot Hand tracking.
-    /// Returns true if all 10 totals have now been hit.
-    pub fn record_hot_hand_hit(&mut self, total: u8) -> bool {
-        // Small totals 2-6 go in bits 0-4
-        if total >= 2 && total <= 6 {
-            let bit = total - 2; // 2->0, 3->1, 4->2, 5->3, 6->4
-            self.hot_hand_hits |= 1 << bit;
-        }
-        // Tall totals 8-12 go in bits 5-9
-        if total >= 8 && total <= 12 {
-            let bit = (total - 8) + 5; // 8->5, 9->6, 10->7, 11->8, 12->9
-            self.hot_hand_hits |= 1 << bit;
-        }
-        // All 10 totals hit means bits 0-9 are all set (0x3FF = 1023)
-        self.hot_hand_hits == 0x3FF
-    }
-
-    /// Get number of unique totals hit for Hot Hand.
-    pub fn hot_hand_count(&self) -> u8 {
-        self.hot_hand_hits.count_ones() as u8
-    }
-
-    /// Check if Hot Hand bet is complete (all 10 totals hit).
-    pub fn is_hot_hand_complete(&self) -> bool {
-        self.hot_hand_hits == 0x3FF
-    }
-
-    /// Record a point being made for Replay Bet tracking.
-    /// Returns the count for that point after incrementing.
-    pub fn record_replay_point(&mut self, point: u8) -> u8 {
-        if let Some(idx) = point_to_index(point) {
-            if self.replay_counts[idx] < 255 {
-                self.replay_counts[idx] += 1;
-            }
-            self.replay_counts[idx]
-        } else {
-            0
-        }
-    }
-
-    /// Get max replay count for any point.
-    pub fn max_replay_count(&self) -> u8 {
-        *self.replay_counts.iter().max().unwrap_or(&0)
-    }
-
-    /// Set Mugsy state to point phase.
-    pub fn set_mugsy_point_phase(&mut self) {
-        if self.mugsy_state == 0 {
-            self.mugsy_state = 1;
-        }
-    }
-
-    /// Check if Mugsy bet is in come-out phase.
-    pub fn is_mugsy_comeout(&self) -> bool {
-        self.mugsy_state == 0
-    }
-
-    /// Check if Mugsy bet is in point phase.
-    pub fn is_mugsy_point_phase(&self) -> bool {
-        self.mugsy_state == 1
+        self.active_bets = BET_MASK_TRACKED;
     }
 
     /// Reset for new epoch.
@@ -503,6 +363,22 @@ impl CrapsPosition {
     }
 }
 
+/// Helper: Map a bet type (CrapsBetType as u8) to its active-bet category.
+pub fn bet_type_to_mask(bet_type: u8) -> u32 {
+    match bet_type {
+        0..=3 => BET_MASK_LINE,
+        4..=7 => BET_MASK_COME,
+        8 => BET_MASK_PLACE,
+        9 => BET_MASK_HARDWAY,
+        10..=15 => BET_MASK_SINGLE_ROLL,
+        16..=25 => BET_MASK_SIDE,
+        26 => BET_MASK_YES,
+        27 => BET_MASK_NO,
+        28 => BET_MASK_NEXT,
+        _ => 0,
+    }
+}
+
 /// Helper: Convert point number (4,5,6,8,9,10) to array index (0-5).
 pub fn point_to_index(point: u8) -> Option<usize> {
     match point {
@@ -564,3 +440,4 @@ pub fn is_valid_yes_no_sum(sum: u8) -> bool {
 }
 
 account!(OreAccount, CrapsPosition);
+account_size!(CrapsPosition, 736);
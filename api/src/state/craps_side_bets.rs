@@ -0,0 +1,339 @@
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use steel::*;
+
+use crate::state::craps_side_bets_pda;
+
+use super::{point_to_index, OreAccount, NUM_POINTS};
+
+/// CrapsSideBets holds a user's exotic craps bets (Bonus Craps, shooter bets, and
+/// Fielder's Choice). It is created the first time one of these bets is placed, so
+/// positions that never use them only pay rent for the core CrapsPosition.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct CrapsSideBets {
+    /// The authority (owner) of these side bets.
+    pub authority: Pubkey,
+
+    /// The epoch ID these bets are for.
+    pub epoch_id: u64,
+
+    // ==================== BONUS CRAPS SIDE BETS ====================
+    // These bets win if all required totals are hit before a 7.
+
+    /// Small bet amount - wins if 2,3,4,5,6 all hit before 7.
+    pub bonus_small: u64,
+
+    /// Tall bet amount - wins if 8,9,10,11,12 all hit before 7.
+    pub bonus_tall: u64,
+
+    /// All bet amount - wins if all 2-6 and 8-12 hit before 7.
+    pub bonus_all: u64,
+
+    /// Bitmask tracking which Small totals have been hit.
+    /// Bit 0 = 2, Bit 1 = 3, Bit 2 = 4, Bit 3 = 5, Bit 4 = 6.
+    pub small_hits: u8,
+
+    /// Bitmask tracking which Tall totals have been hit.
+    /// Bit 0 = 8, Bit 1 = 9, Bit 2 = 10, Bit 3 = 11, Bit 4 = 12.
+    pub tall_hits: u8,
+
+    /// Padding for alignment.
+    pub _padding2: [u8; 6],
+
+    // ==================== COME-OUT ONLY SIDE BETS ====================
+    // These bets can only be placed on come-out roll and persist until seven-out.
+
+    /// Fire Bet - wins based on unique points made (4+ required).
+    pub fire_bet: u64,
+
+    /// Fire Bet tracking: bitmask of unique points made.
+    /// Bit 0=4, 1=5, 2=6, 3=8, 4=9, 5=10.
+    pub fire_points_made: u8,
+
+    /// Padding for u64 alignment after fire_points_made.
+    pub _pad_fire: [u8; 7],
+
+    /// Different Doubles bet.
+    pub diff_doubles_bet: u64,
+
+    /// Different Doubles tracking: bitmask of unique doubles rolled.
+    /// Bit 0=1-1, 1=2-2, 2=3-3, 3=4-4, 4=5-5, 5=6-6.
+    pub diff_doubles_hits: u8,
+
+    /// Padding for u64 alignment after diff_doubles_hits.
+    pub _pad_diff: [u8; 7],
+
+    /// Ride the Line bet - wins based on pass line wins before seven-out.
+    pub ride_the_line_bet: u64,
+
+    /// Ride the Line tracking: count of pass line wins this shooter.
+    pub ride_wins_count: u8,
+
+    /// Padding for u64 alignment after ride_wins_count.
+    pub _pad_ride: [u8; 7],
+
+    /// Mugsy's Corner bet - wins on 7 (different payouts based on phase).
+    pub mugsy_bet: u64,
+
+    /// Hot Hand bet - must hit all 10 totals (2-6, 8-12) before 7.
+    pub hot_hand_bet: u64,
+
+    /// Hot Hand tracking: bitmask of totals hit (same as small_hits | tall_hits).
+    /// Bit 0-4 = 2,3,4,5,6 | Bit 5-9 = 8,9,10,11,12.
+    pub hot_hand_hits: u16,
+
+    /// Padding for u64 alignment after hot_hand_hits.
+    pub _pad_hot: [u8; 6],
+
+    /// Replay bet - wins when same point is made multiple times.
+    pub replay_bet: u64,
+
+    /// Replay tracking: count of times each point was made.
+    /// Index: 0=4, 1=5, 2=6, 3=8, 4=9, 5=10.
+    pub replay_counts: [u8; NUM_POINTS],
+
+    /// Padding for u64 alignment after replay_counts.
+    pub _pad_replay: [u8; 2],
+
+    // ==================== FIELDER'S CHOICE ====================
+
+    /// Fielder's Choice bets (3 single-roll bets).
+    /// [0] = 2,3,4 | [1] = 4,9,10 | [2] = 10,11,12
+    pub fielders_choice: [u64; 3],
+}
+
+impl CrapsSideBets {
+    pub fn pda(authority: Pubkey) -> (Pubkey, u8) {
+        craps_side_bets_pda(authority)
+    }
+
+    /// Check if any side bet is active.
+    pub fn has_any_bets(&self) -> bool {
+        self.total_bets() > 0
+    }
+
+    /// Get total active side bets.
+    pub fn total_bets(&self) -> u64 {
+        self.bonus_small
+            + self.bonus_tall
+            + self.bonus_all
+            + self.fire_bet
+            + self.diff_doubles_bet
+            + self.ride_the_line_bet
+            + self.mugsy_bet
+            + self.hot_hand_bet
+            + self.replay_bet
+            + self.fielders_choice.iter().sum::<u64>()
+    }
+
+    /// Clear all side bets and their tracking.
+    pub fn clear_all_bets(&mut self) {
+        self.clear_bonus_bets();
+        self.clear_shooter_bets();
+        self.fielders_choice = [0; 3];
+    }
+
+    /// Clear bonus craps bets and reset hit tracking.
+    pub fn clear_bonus_bets(&mut self) {
+        self.bonus_small = 0;
+        self.bonus_tall = 0;
+        self.bonus_all = 0;
+        self.small_hits = 0;
+        self.tall_hits = 0;
+    }
+
+    /// Record a dice total for bonus craps tracking.
+    /// Returns (small_complete, tall_complete) indicating if either bet just won.
+    pub fn record_bonus_hit(&mut self, total: u8) -> (bool, bool) {
+        let mut small_just_completed = false;
+        let mut tall_just_completed = false;
+
+        // Small tracks totals 2-6 (bits 0-4 map to totals 2-6)
+        if total >= 2 && total <= 6 {
+            let bit = total - 2; // 2->0, 3->1, 4->2, 5->3, 6->4
+            let was_complete = self.small_hits == 0b11111;
+            self.small_hits |= 1 << bit;
+            small_just_completed = !was_complete && self.small_hits == 0b11111;
+        }
+
+        // Tall tracks totals 8-12 (bits 0-4 map to totals 8-12)
+        if total >= 8 && total <= 12 {
+            let bit = total - 8; // 8->0, 9->1, 10->2, 11->3, 12->4
+            let was_complete = self.tall_hits == 0b11111;
+            self.tall_hits |= 1 << bit;
+            tall_just_completed = !was_complete && self.tall_hits == 0b11111;
+        }
+
+        (small_just_completed, tall_just_completed)
+    }
+
+    /// Check if Small bet is complete (all 2,3,4,5,6 hit).
+    pub fn is_small_complete(&self) -> bool {
+        self.small_hits == 0b11111
+    }
+
+    /// Check if Tall bet is complete (all 8,9,10,11,12 hit).
+    pub fn is_tall_complete(&self) -> bool {
+        self.tall_hits == 0b11111
+    }
+
+    /// Check if All bet is complete (both Small and Tall complete).
+    pub fn is_all_complete(&self) -> bool {
+        self.is_small_complete() && self.is_tall_complete()
+    }
+
+    /// Check if player has any active bonus bets.
+    pub fn has_bonus_bets(&self) -> bool {
+        self.bonus_small > 0 || self.bonus_tall > 0 || self.bonus_all > 0
+    }
+
+    /// Clear come-out only side bets and their tracking (called on seven-out).
+    pub fn clear_shooter_bets(&mut self) {
+        self.fire_bet = 0;
+        self.fire_points_made = 0;
+        self.diff_doubles_bet = 0;
+        self.diff_doubles_hits = 0;
+        self.ride_the_line_bet = 0;
+        self.ride_wins_count = 0;
+        self.mugsy_bet = 0;
+        self.hot_hand_bet = 0;
+        self.hot_hand_hits = 0;
+        self.replay_bet = 0;
+        self.replay_counts = [0; NUM_POINTS];
+    }
+
+    /// Check if player has any active shooter bets.
+    pub fn has_shooter_bets(&self) -> bool {
+        self.fire_bet > 0
+            || self.diff_doubles_bet > 0
+            || self.ride_the_line_bet > 0
+            || self.mugsy_bet > 0
+            || self.hot_hand_bet > 0
+            || self.replay_bet > 0
+    }
+
+    /// Record a point being made for Fire Bet tracking.
+    /// Returns the number of unique points made (for payout calculation).
+    pub fn record_fire_point(&mut self, point: u8) -> u8 {
+        if let Some(idx) = point_to_index(point) {
+            self.fire_points_made |= 1 << idx;
+        }
+        self.fire_points_made.count_ones() as u8
+    }
+
+    /// Get number of unique points made for Fire Bet.
+    pub fn fire_points_count(&self) -> u8 {
+        self.fire_points_made.count_ones() as u8
+    }
+
+    /// Record a double roll for Different Doubles tracking.
+    /// Returns the number of unique doubles hit (for payout calculation).
+    pub fn record_double(&mut self, die_value: u8) -> u8 {
+        if die_value >= 1 && die_value <= 6 {
+            let bit = die_value - 1; // 1->0, 2->1, etc.
+            self.diff_doubles_hits |= 1 << bit;
+        }
+        self.diff_doubles_hits.count_ones() as u8
+    }
+
+    /// Get number of unique doubles hit.
+    pub fn diff_doubles_count(&self) -> u8 {
+        self.diff_doubles_hits.count_ones() as u8
+    }
+
+    /// Record a pass line win for Ride the Line tracking.
+    pub fn record_ride_win(&mut self) {
+        if self.ride_wins_count < 255 {
+            self.ride_wins_count += 1;
+        }
+    }
+
+    /// Record a dice total for Hot Hand tracking.
+    /// Returns true if all 10 totals have now been hit.
+    pub fn record_hot_hand_hit(&mut self, total: u8) -> bool {
+        // Small totals 2-6 go in bits 0-4
+        if total >= 2 && total <= 6 {
+            let bit = total - 2; // 2->0, 3->1, 4->2, 5->3, 6->4
+            self.hot_hand_hits |= 1 << bit;
+        }
+        // Tall totals 8-12 go in bits 5-9
+        if total >= 8 && total <= 12 {
+            let bit = (total - 8) + 5; // 8->5, 9->6, 10->7, 11->8, 12->9
+            self.hot_hand_hits |= 1 << bit;
+        }
+        // All 10 totals hit means bits 0-9 are all set (0x3FF = 1023)
+        self.hot_hand_hits == 0x3FF
+    }
+
+    /// Get number of unique totals hit for Hot Hand.
+    pub fn hot_hand_count(&self) -> u8 {
+        self.hot_hand_hits.count_ones() as u8
+    }
+
+    /// Check if Hot Hand bet is complete (all 10 totals hit).
+    pub fn is_hot_hand_complete(&self) -> bool {
+        self.hot_hand_hits == 0x3FF
+    }
+
+    /// Record a point being made for Replay Bet tracking.
+    /// Returns the count for that point after incrementing.
+    pub fn record_replay_point(&mut self, point: u8) -> u8 {
+        if let Some(idx) = point_to_index(point) {
+            if self.replay_counts[idx] < 255 {
+                self.replay_counts[idx] += 1;
+            }
+            self.replay_counts[idx]
+        } else {
+            0
+        }
+    }
+
+    /// Get max replay count for any point.
+    pub fn max_replay_count(&self) -> u8 {
+        *self.replay_counts.iter().max().unwrap_or(&0)
+    }
+
+    /// Reset for new epoch.
+    pub fn reset_for_epoch(&mut self, epoch_id: u64) {
+        self.epoch_id = epoch_id;
+        self.clear_all_bets();
+    }
+}
+
+account!(OreAccount, CrapsSideBets);
+account_size!(CrapsSideBets, 192);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_bets() {
+        let mut side_bets = CrapsSideBets::zeroed();
+        assert!(!side_bets.has_any_bets());
+
+        side_bets.bonus_small = 10;
+        side_bets.mugsy_bet = 20;
+        side_bets.fielders_choice[2] = 5;
+        assert_eq!(side_bets.total_bets(), 35);
+    }
+
+    #[test]
+    fn test_reset_clears_bets_and_tracking() {
+        let mut side_bets = CrapsSideBets::zeroed();
+        side_bets.epoch_id = 1;
+        side_bets.fire_bet = 10;
+        side_bets.record_fire_point(6);
+        side_bets.bonus_all = 10;
+        side_bets.record_bonus_hit(2);
+        side_bets.fielders_choice[0] = 10;
+
+        side_bets.reset_for_epoch(2);
+
+        assert_eq!(side_bets.epoch_id, 2);
+        assert!(!side_bets.has_any_bets());
+        assert_eq!(side_bets.fire_points_count(), 0);
+        assert_eq!(side_bets.small_hits, 0);
+    }
+}
@@ -0,0 +1,161 @@
+use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use solana_program::pubkey::Pubkey;
+use steel::*;
+
+use crate::consts::DCA_ORDER;
+
+use super::limit_order::LIMIT_ORDER_SIDE_SOL_TO_RNG;
+use super::OreAccount;
+
+/// Share of each DCA slice paid to the cranker, in basis points (0.1%).
+pub const EXCHANGE_DCA_CRANK_BOUNTY_BPS: u64 = 10;
+
+/// A recurring order that swaps a fixed slice of its budget through the pool
+/// every `interval_slots`, for converting fees or bankroll gradually.
+///
+/// The budget is escrowed in the order's token account. Anyone can crank a due
+/// slice, earning a bounty taken from the slice. Sides match LimitOrder.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct DcaOrder {
+    /// The owner of this order.
+    pub authority: Pubkey,
+
+    /// Owner-chosen id, so one owner can run several schedules.
+    pub id: u64,
+
+    /// LIMIT_ORDER_SIDE_SOL_TO_RNG or LIMIT_ORDER_SIDE_RNG_TO_SOL.
+    pub side: u8,
+
+    pub _padding: [u8; 7],
+
+    /// Input swapped per slice, including the crank bounty.
+    pub amount_per_interval: u64,
+
+    /// Slots between slices.
+    pub interval_slots: u64,
+
+    /// Input still escrowed for future slices.
+    pub remaining_budget: u64,
+
+    /// Earliest slot the next slice can execute.
+    pub next_execution_slot: u64,
+
+    /// Minimum output per input token for each slice, Q64.64 (low/high words).
+    /// Zero disables the check.
+    pub min_price_low: u64,
+    pub min_price_high: u64,
+
+    /// Slices executed so far.
+    pub executions: u64,
+
+    /// Total input swapped (excluding bounties).
+    pub total_in: u64,
+
+    /// Total output received.
+    pub total_out: u64,
+
+    /// Timestamp the order was placed.
+    pub created_at: i64,
+}
+
+impl DcaOrder {
+    pub fn pda(&self) -> (Pubkey, u8) {
+        dca_order_pda(self.authority, self.id)
+    }
+
+    pub fn min_price(&self) -> u128 {
+        ((self.min_price_high as u128) << 64) | (self.min_price_low as u128)
+    }
+
+    pub fn set_min_price(&mut self, price: u128) {
+        self.min_price_low = price as u64;
+        self.min_price_high = (price >> 64) as u64;
+    }
+
+    pub fn is_sol_to_rng(&self) -> bool {
+        self.side == LIMIT_ORDER_SIDE_SOL_TO_RNG
+    }
+
+    /// The slice due at `slot` as (input_amount, swap_amount, bounty), or None if
+    /// the next slice is not due yet or the budget is spent.
+    pub fn next_slice(&self, slot: u64) -> Option<(u64, u64, u64)> {
+        if self.remaining_budget == 0 || slot < self.next_execution_slot {
+            return None;
+        }
+        let input_amount = self.amount_per_interval.min(self.remaining_budget);
+        let bounty = ((input_amount as u128) * (EXCHANGE_DCA_CRANK_BOUNTY_BPS as u128)
+            / 10000) as u64;
+        Some((input_amount, input_amount - bounty, bounty))
+    }
+
+    /// Minimum output a slice of `input_amount` must deliver under the price floor.
+    pub fn min_slice_out(&self, input_amount: u64) -> Option<u64> {
+        let min_out = (input_amount as u128)
+            .checked_mul(self.min_price())?
+            .div_ceil(1u128 << 64);
+        u64::try_from(min_out).ok()
+    }
+
+    /// Record an executed slice and schedule the next one from `slot`.
+    /// Scheduling from the execution slot means a late crank never triggers a burst.
+    pub fn record_slice(&mut self, input_amount: u64, swap_amount: u64, amount_out: u64, slot: u64) {
+        self.remaining_budget -= input_amount;
+        self.next_execution_slot = slot.saturating_add(self.interval_slots);
+        self.executions += 1;
+        self.total_in += swap_amount;
+        self.total_out += amount_out;
+    }
+}
+
+/// Derive the DCA order PDA for an owner and order id.
+pub fn dca_order_pda(authority: Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DCA_ORDER, &authority.to_bytes(), &id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+account!(OreAccount, DcaOrder);
+account_size!(DcaOrder, 136);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slices_follow_schedule_until_budget_spent() {
+        let mut order = DcaOrder::zeroed();
+        order.amount_per_interval = 1_000_000;
+        order.interval_slots = 150;
+        order.remaining_budget = 2_500_000;
+        order.next_execution_slot = 100;
+
+        assert_eq!(order.next_slice(99), None);
+        assert_eq!(order.next_slice(100), Some((1_000_000, 999_000, 1_000)));
+
+        // A late crank schedules from when it ran.
+        order.record_slice(1_000_000, 999_000, 4_000_000, 400);
+        assert_eq!(order.next_execution_slot, 550);
+        assert_eq!(order.next_slice(549), None);
+
+        order.record_slice(1_000_000, 999_000, 4_000_000, 550);
+        // The final slice is whatever is left.
+        assert_eq!(order.next_slice(700), Some((500_000, 499_500, 500)));
+        order.record_slice(500_000, 499_500, 2_000_000, 700);
+        assert_eq!(order.next_slice(u64::MAX), None);
+        assert_eq!(order.executions, 3);
+        assert_eq!(order.total_in, 2_497_500);
+        assert_eq!(order.total_out, 10_000_000);
+    }
+
+    #[test]
+    fn test_price_floor_is_optional() {
+        let mut order = DcaOrder::zeroed();
+        assert_eq!(order.min_slice_out(1_000), Some(0));
+        order.set_min_price(4u128 << 64);
+        assert_eq!(order.min_slice_out(1_000), Some(4_000));
+    }
+}
@@ -1,20 +1,61 @@
 use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
 use solana_program::pubkey::Pubkey;
 use steel::*;
 
 use super::OreAccount;
 
+/// Maximum swap fee numerator (1000 / 10000 = 10%).
+pub const EXCHANGE_MAX_FEE_NUMERATOR: u64 = 1000;
+
+/// Default share of the swap fee that goes to the protocol, in basis points.
+pub const EXCHANGE_DEFAULT_PROTOCOL_FEE_SHARE_BPS: u64 = 5000;
+
+/// Delay before a fee change scheduled by the admin can take effect (48 hours).
+pub const EXCHANGE_FEE_TIMELOCK_SECONDS: i64 = 48 * 60 * 60;
+
+/// Default share of protocol fees streamed to staked LPs, in basis points.
+pub const EXCHANGE_DEFAULT_LP_STAKER_FEE_SHARE_BPS: u64 = 5000;
+
+/// Maximum share of the protocol fee paid to swap referrers, in basis points.
+pub const EXCHANGE_MAX_REFERRAL_FEE_SHARE_BPS: u64 = 5000;
+
+/// Protocol SOL fees (lamports) that must accrue before they can be skimmed to the treasury.
+pub const EXCHANGE_FEE_SKIM_THRESHOLD_SOL: u64 = 1_000_000_000;
+
+/// Protocol RNG fees (base units) that must accrue before they can be skimmed to the treasury.
+pub const EXCHANGE_FEE_SKIM_THRESHOLD_RNG: u64 = 1_000_000_000_000;
+
+/// Shortest interval an admin can set between CRAP buybacks (1 hour).
+pub const EXCHANGE_MIN_BUYBACK_INTERVAL_SECONDS: i64 = 60 * 60;
+
+/// Default cap on SOL notional swapped in one slot, as basis points of the SOL reserve.
+pub const EXCHANGE_DEFAULT_SLOT_NOTIONAL_CAP_BPS: u64 = 2500;
+
+/// Number of hourly buckets kept for rolling pool statistics (24 hours).
+pub const EXCHANGE_STATS_BUCKETS: usize = 24;
+
+/// Width of a statistics bucket, in seconds.
+pub const EXCHANGE_STATS_BUCKET_SECONDS: i64 = 60 * 60;
+
+/// Number of game tokens minted 1:1 against the RNG vault (see GameTokenType).
+pub const NUM_GAME_TOKENS: usize = 9;
+
 /// Exchange pool state for the Constant Product AMM (CPMM).
 ///
 /// This pool maintains SOL/RNG liquidity using the x*y=k formula.
 /// Liquidity providers deposit both tokens and receive LP tokens.
 /// Swaps execute at the marginal rate determined by the reserves.
 ///
-/// Fee structure: 1% total fee
+/// Fee structure: 1% total fee by default
 /// - 50% stays in pool (LP rewards)
 /// - 50% goes to protocol (stakers/treasury)
+///
+/// The admin can change the fee and the LP/protocol split via SetPoolFees,
+/// subject to EXCHANGE_FEE_TIMELOCK_SECONDS.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
 pub struct ExchangePool {
     /// The SOL vault token account (wSOL).
     pub sol_vault: Pubkey,
@@ -82,6 +123,222 @@ pub struct ExchangePool {
 
     /// Padding for alignment.
     pub _padding: [u8; 6],
+
+    /// Cumulative SOL price (RNG per SOL, Q64.64) weighted by seconds.
+    /// Stored as u128 split into two u64 parts; wraps on overflow.
+    pub price_sol_cumulative_low: u64,
+    pub price_sol_cumulative_high: u64,
+
+    /// Cumulative RNG price (SOL per RNG, Q64.64) weighted by seconds.
+    /// Stored as u128 split into two u64 parts; wraps on overflow.
+    pub price_rng_cumulative_low: u64,
+    pub price_rng_cumulative_high: u64,
+
+    /// Timestamp the price accumulators were last updated.
+    pub last_observation_at: i64,
+
+    /// Guardian that can pause the pool during incidents (default = none).
+    /// Only the admin can unpause or deprecate.
+    pub guardian: Pubkey,
+
+    /// Share of each swap fee that goes to the protocol, in basis points.
+    /// The remainder stays in the pool for LPs.
+    pub protocol_fee_share_bps: u64,
+
+    /// Fee numerator scheduled by the admin, applied once the timelock expires.
+    pub pending_fee_numerator: u64,
+
+    /// Protocol fee share scheduled by the admin, applied with the pending fee.
+    pub pending_protocol_fee_share_bps: u64,
+
+    /// Timestamp the pending fee change can be applied (0 = none pending).
+    pub pending_fees_effective_at: i64,
+
+    /// Share of each protocol fee streamed to staked LPs, in basis points.
+    pub lp_staker_fee_share_bps: u64,
+
+    /// Total LP staking weight for the protocol fee share: staked LP tokens
+    /// plus locked LP tokens scaled by their lock boost.
+    pub total_lp_staked: u64,
+
+    /// Cumulative SOL fees per staked LP token.
+    pub lp_rewards_factor_sol: Numeric,
+
+    /// Cumulative RNG fees per staked LP token.
+    pub lp_rewards_factor_rng: Numeric,
+
+    /// SOL fees owed to LP stakers, held in the SOL vault outside the reserves.
+    pub lp_rewards_owed_sol: u64,
+
+    /// RNG fees owed to LP stakers, held in the RNG vault outside the reserves.
+    pub lp_rewards_owed_rng: u64,
+
+    /// Share of the protocol fee paid to a swap's referrer, in basis points.
+    pub referral_fee_share_bps: u64,
+
+    /// SOL fees owed to referrers, held in the SOL vault outside the reserves.
+    pub referral_fees_owed_sol: u64,
+
+    /// RNG fees owed to referrers, held in the RNG vault outside the reserves.
+    pub referral_fees_owed_rng: u64,
+
+    /// Circuit breaker: maximum SOL price move within a window, in basis points (0 = disabled).
+    pub max_price_move_bps: u64,
+
+    /// Circuit breaker: length of the price window in seconds.
+    pub price_window_seconds: i64,
+
+    /// Circuit breaker: timestamp the current price window started.
+    pub price_window_start_at: i64,
+
+    /// Circuit breaker: SOL price (RNG per SOL, Q64.64) at the start of the window.
+    /// Split into two u64 parts for Pod compatibility.
+    pub price_window_reference_low: u64,
+    pub price_window_reference_high: u64,
+
+    /// Maximum swap size as basis points of the input reserve (admin-tunable).
+    pub max_swap_bps: u64,
+
+    /// RNG held in the RNG vault backing each game token's 1:1 supply,
+    /// indexed by game token type. Separate from rng_reserve.
+    pub game_token_backing: [u64; NUM_GAME_TOKENS],
+
+    /// Game tokens minted through the 1:1 swaps and not yet redeemed.
+    pub game_token_outstanding: [u64; NUM_GAME_TOKENS],
+
+    /// Share of accrued protocol SOL fees spent per CRAP buyback, in basis points.
+    /// Zero disables buybacks.
+    pub buyback_share_bps: u64,
+
+    /// Most SOL (lamports) a single buyback can spend.
+    pub buyback_max_sol: u64,
+
+    /// Minimum seconds between buybacks.
+    pub buyback_interval_seconds: i64,
+
+    /// Timestamp of the last buyback.
+    pub last_buyback_at: i64,
+
+    /// Total protocol SOL spent on buybacks (lifetime).
+    pub total_buyback_sol: u64,
+
+    /// Total CRAP bought back and burned (lifetime).
+    pub total_crap_burned: u64,
+
+    /// Hour (unix time / 3600) each statistics bucket currently holds.
+    pub stats_bucket_hours: [i64; EXCHANGE_STATS_BUCKETS],
+
+    /// Swap volume in SOL per hourly bucket (ring buffer indexed by hour).
+    pub stats_volume_sol: [u64; EXCHANGE_STATS_BUCKETS],
+
+    /// Swap fees (LP and protocol) in SOL terms per hourly bucket.
+    pub stats_fees_sol: [u64; EXCHANGE_STATS_BUCKETS],
+
+    /// Cap on SOL notional swapped within one slot, as basis points of the SOL
+    /// reserve. Zero disables the cap.
+    pub slot_notional_cap_bps: u64,
+
+    /// Slot the notional counter applies to.
+    pub notional_slot: u64,
+
+    /// SOL notional swapped so far in `notional_slot`.
+    pub slot_notional_sol: u64,
+}
+
+/// Backing of a game token's 1:1 supply, as returned by the QuoteGameTokenBacking instruction.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct GameTokenBacking {
+    /// RNG in the vault backing the game token.
+    pub backing: u64,
+
+    /// Game tokens outstanding.
+    pub outstanding: u64,
+
+    /// Backing over outstanding supply, in basis points (u64::MAX when nothing is outstanding).
+    pub collateralization_bps: u64,
+}
+
+impl GameTokenBacking {
+    pub fn to_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+
+    /// Parses the return data of a simulated QuoteGameTokenBacking transaction.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        bytemuck::try_pod_read_unaligned(data).ok()
+    }
+}
+
+/// A snapshot of the pool's price accumulators at a point in time.
+/// Two observations taken at different times give the TWAP between them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PriceObservation {
+    /// Timestamp of the observation.
+    pub timestamp: i64,
+
+    /// Cumulative SOL price (RNG per SOL, Q64.64) at `timestamp`.
+    pub price_sol_cumulative: u128,
+
+    /// Cumulative RNG price (SOL per RNG, Q64.64) at `timestamp`.
+    pub price_rng_cumulative: u128,
+}
+
+impl PriceObservation {
+    /// Time-weighted average prices between an earlier observation and this one.
+    /// Returns (SOL price in RNG, RNG price in SOL) as Q64.64, or None if no time elapsed.
+    pub fn twap_since(&self, start: &PriceObservation) -> Option<(u128, u128)> {
+        let elapsed = self.timestamp.checked_sub(start.timestamp)?;
+        if elapsed <= 0 {
+            return None;
+        }
+        // Accumulators wrap, so differences are taken with wrapping arithmetic.
+        let sol = self
+            .price_sol_cumulative
+            .wrapping_sub(start.price_sol_cumulative)
+            / elapsed as u128;
+        let rng = self
+            .price_rng_cumulative
+            .wrapping_sub(start.price_rng_cumulative)
+            / elapsed as u128;
+        Some((sol, rng))
+    }
+}
+
+/// Expected result of a swap, as returned by `ExchangePool::quote` and the QuoteSwap instruction.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct SwapQuote {
+    /// Input amount.
+    pub amount_in: u64,
+
+    /// Expected output amount.
+    pub amount_out: u64,
+
+    /// Fee kept by liquidity providers (in the input token).
+    pub lp_fee: u64,
+
+    /// Fee tracked for the protocol (in the input token).
+    pub protocol_fee: u64,
+
+    /// Shortfall of the output versus the spot price, in basis points (fees included).
+    pub price_impact_bps: u64,
+}
+
+impl SwapQuote {
+    pub fn to_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+
+    /// Parses the return data of a simulated QuoteSwap transaction.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        bytemuck::try_pod_read_unaligned(data).ok()
+    }
+}
+
+/// Converts a Q64.64 price to a floating point value (for display only).
+pub fn q64_to_f64(price: u128) -> f64 {
+    price as f64 / (1u128 << 64) as f64
 }
 
 impl ExchangePool {
@@ -96,6 +353,67 @@ impl ExchangePool {
         self.k_high = (k >> 64) as u64;
     }
 
+    /// Product of the current reserves. Swaps must never decrease it.
+    pub fn reserve_product(&self) -> Option<u128> {
+        (self.sol_reserve as u128).checked_mul(self.rng_reserve as u128)
+    }
+
+    /// Get the cumulative SOL price as u128.
+    pub fn price_sol_cumulative(&self) -> u128 {
+        ((self.price_sol_cumulative_high as u128) << 64) | (self.price_sol_cumulative_low as u128)
+    }
+
+    /// Get the cumulative RNG price as u128.
+    pub fn price_rng_cumulative(&self) -> u128 {
+        ((self.price_rng_cumulative_high as u128) << 64) | (self.price_rng_cumulative_low as u128)
+    }
+
+    /// Current spot prices as Q64.64: (SOL price in RNG, RNG price in SOL).
+    pub fn spot_prices(&self) -> Option<(u128, u128)> {
+        if self.sol_reserve == 0 || self.rng_reserve == 0 {
+            return None;
+        }
+        let sol = ((self.rng_reserve as u128) << 64) / self.sol_reserve as u128;
+        let rng = ((self.sol_reserve as u128) << 64) / self.rng_reserve as u128;
+        Some((sol, rng))
+    }
+
+    /// Accumulators as of `now`, including the time since the last on-chain update.
+    /// Lets consumers observe the pool without waiting for a swap.
+    pub fn observe(&self, now: i64) -> PriceObservation {
+        let mut sol = self.price_sol_cumulative();
+        let mut rng = self.price_rng_cumulative();
+        let elapsed = now.saturating_sub(self.last_observation_at);
+        if elapsed > 0 && self.last_observation_at > 0 {
+            if let Some((sol_price, rng_price)) = self.spot_prices() {
+                sol = sol.wrapping_add(sol_price.wrapping_mul(elapsed as u128));
+                rng = rng.wrapping_add(rng_price.wrapping_mul(elapsed as u128));
+            }
+        }
+        PriceObservation {
+            timestamp: now,
+            price_sol_cumulative: sol,
+            price_rng_cumulative: rng,
+        }
+    }
+
+    /// Accrue the current spot price into the accumulators up to `now`.
+    /// Must be called before the reserves change so the elapsed time is
+    /// weighted by the price that held during it.
+    pub fn update_price_accumulators(&mut self, now: i64) {
+        if now <= self.last_observation_at {
+            return;
+        }
+        let observation = self.observe(now);
+        let sol = observation.price_sol_cumulative;
+        let rng = observation.price_rng_cumulative;
+        self.price_sol_cumulative_low = sol as u64;
+        self.price_sol_cumulative_high = (sol >> 64) as u64;
+        self.price_rng_cumulative_low = rng as u64;
+        self.price_rng_cumulative_high = (rng >> 64) as u64;
+        self.last_observation_at = now;
+    }
+
     /// Calculate output amount for a swap using CPMM formula.
     /// Returns (output_amount, lp_fee, protocol_fee).
     pub fn calculate_swap_output(
@@ -104,28 +422,275 @@ impl ExchangePool {
         input_reserve: u64,
         output_reserve: u64,
     ) -> Option<(u64, u64, u64)> {
-        if input_amount == 0 || input_reserve == 0 || output_reserve == 0 {
+        cpmm_swap_output(
+            input_amount,
+            input_reserve,
+            output_reserve,
+            self.fee_numerator,
+            self.fee_denominator,
+            self.protocol_fee_share_bps,
+        )
+    }
+
+    /// Calculate the input needed to receive exactly `output_amount` from a swap.
+    /// Inverts the CPMM formula and returns the smallest input whose output is at
+    /// least `output_amount`, as (input_amount, lp_fee, protocol_fee).
+    pub fn calculate_swap_input(
+        &self,
+        output_amount: u64,
+        input_reserve: u64,
+        output_reserve: u64,
+    ) -> Option<(u64, u64, u64)> {
+        if output_amount == 0 || input_reserve == 0 || output_amount >= output_reserve {
             return None;
         }
 
-        // Calculate total fee (1% = 100/10000)
-        let total_fee = input_amount
-            .checked_mul(self.fee_numerator)?
-            .checked_div(self.fee_denominator)?;
+        // Input that must reach the pool: ceil(input_reserve * output / (output_reserve - output))
+        let numerator = (input_reserve as u128).checked_mul(output_amount as u128)?;
+        let denominator = (output_reserve - output_amount) as u128;
+        let input_to_pool = numerator.div_ceil(denominator);
 
-        // Split fee: 50% to LP, 50% to protocol
-        let protocol_fee = total_fee / 2;
-        let lp_fee = total_fee - protocol_fee;
+        // Gross up for the protocol share of the fee, which does not enter the pool.
+        let fee_denominator = (self.fee_denominator as u128).checked_mul(10000)?;
+        let protocol_share = (self.fee_numerator as u128)
+            .checked_mul(self.protocol_fee_share_bps as u128)?;
+        let kept = fee_denominator.checked_sub(protocol_share)?;
+        if kept == 0 {
+            return None;
+        }
+        let mut input_amount: u64 = input_to_pool
+            .checked_mul(fee_denominator)?
+            .div_ceil(kept)
+            .try_into()
+            .ok()?;
 
-        // Input after fee goes into pool
-        let input_with_lp_fee = input_amount.checked_sub(protocol_fee)?;
+        // Fee rounding can make the estimate overshoot by a unit or two.
+        while input_amount > 1 {
+            match self.calculate_swap_output(input_amount - 1, input_reserve, output_reserve) {
+                Some((output, _, _)) if output >= output_amount => input_amount -= 1,
+                _ => break,
+            }
+        }
 
-        // CPMM formula: output = (output_reserve * input_with_fee) / (input_reserve + input_with_fee)
-        let numerator = (output_reserve as u128).checked_mul(input_with_lp_fee as u128)?;
-        let denominator = (input_reserve as u128).checked_add(input_with_lp_fee as u128)?;
-        let output = numerator.checked_div(denominator)? as u64;
+        let (output, lp_fee, protocol_fee) =
+            self.calculate_swap_output(input_amount, input_reserve, output_reserve)?;
+        if output < output_amount {
+            return None;
+        }
+
+        Some((input_amount, lp_fee, protocol_fee))
+    }
+
+    /// Quote a swap at the current reserves using the same math as the swap instructions.
+    /// `sol_to_rng` selects the direction; returns None if the swap cannot execute.
+    pub fn quote(&self, input_amount: u64, sol_to_rng: bool) -> Option<SwapQuote> {
+        let (input_reserve, output_reserve) = if sol_to_rng {
+            (self.sol_reserve, self.rng_reserve)
+        } else {
+            (self.rng_reserve, self.sol_reserve)
+        };
+        let (amount_out, lp_fee, protocol_fee) =
+            self.calculate_swap_output(input_amount, input_reserve, output_reserve)?;
+
+        // Output at the spot price, before fees and slippage.
+        let spot_out = (input_amount as u128)
+            .checked_mul(output_reserve as u128)?
+            .checked_div(input_reserve as u128)?;
+        let price_impact_bps = if spot_out == 0 {
+            0
+        } else {
+            (spot_out.saturating_sub(amount_out as u128) * 10000 / spot_out) as u64
+        };
 
-        Some((output, lp_fee, protocol_fee))
+        Some(SwapQuote {
+            amount_in: input_amount,
+            amount_out,
+            lp_fee,
+            protocol_fee,
+            price_impact_bps,
+        })
+    }
+
+    /// Calculate how much of a single-sided deposit to swap so the remainder and the
+    /// swap output match the post-swap reserve ratio. Returns the amount to swap.
+    pub fn calculate_zap_swap_amount(
+        &self,
+        amount_in: u64,
+        input_reserve: u64,
+        output_reserve: u64,
+    ) -> Option<u64> {
+        if amount_in < 2 || input_reserve == 0 || output_reserve == 0 {
+            return None;
+        }
+
+        // Swapping more makes the output side larger relative to the remaining input,
+        // so binary search for the largest swap that leaves the input side no smaller.
+        let balanced = |swap_amount: u64| -> Option<bool> {
+            let (output, _, protocol_fee) =
+                self.calculate_swap_output(swap_amount, input_reserve, output_reserve)?;
+            let remaining = (amount_in - swap_amount) as u128;
+            let new_input_reserve = (input_reserve as u128) + (swap_amount - protocol_fee) as u128;
+            let new_output_reserve = (output_reserve - output) as u128;
+            Some(remaining * new_output_reserve >= (output as u128) * new_input_reserve)
+        };
+
+        let (mut low, mut high) = (1u64, amount_in - 1);
+        if !balanced(low)? {
+            return None;
+        }
+        while low < high {
+            let mid = low + (high - low + 1) / 2;
+            if balanced(mid)? {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        Some(low)
+    }
+
+    /// Split a total fee into (lp_fee, protocol_fee) using the pool's protocol share.
+    pub fn split_fee(&self, total_fee: u64) -> Option<(u64, u64)> {
+        let protocol_fee = (total_fee as u128)
+            .checked_mul(self.protocol_fee_share_bps as u128)?
+            .checked_div(10000)? as u64;
+        let lp_fee = total_fee.checked_sub(protocol_fee)?;
+        Some((lp_fee, protocol_fee))
+    }
+
+    /// Check that a fee configuration is within bounds.
+    pub fn validate_fees(fee_numerator: u64, protocol_fee_share_bps: u64) -> bool {
+        fee_numerator <= EXCHANGE_MAX_FEE_NUMERATOR && protocol_fee_share_bps <= 10000
+    }
+
+    /// Schedule a fee change that can be applied after the timelock.
+    pub fn schedule_fees(
+        &mut self,
+        fee_numerator: u64,
+        protocol_fee_share_bps: u64,
+        now: i64,
+    ) -> Option<i64> {
+        if !Self::validate_fees(fee_numerator, protocol_fee_share_bps) {
+            return None;
+        }
+        let effective_at = now.checked_add(EXCHANGE_FEE_TIMELOCK_SECONDS)?;
+        self.pending_fee_numerator = fee_numerator;
+        self.pending_protocol_fee_share_bps = protocol_fee_share_bps;
+        self.pending_fees_effective_at = effective_at;
+        Some(effective_at)
+    }
+
+    /// Apply the pending fee change if its timelock has expired.
+    /// Returns true if the fees were updated.
+    pub fn apply_pending_fees(&mut self, now: i64) -> bool {
+        if self.pending_fees_effective_at == 0 || now < self.pending_fees_effective_at {
+            return false;
+        }
+        self.fee_numerator = self.pending_fee_numerator;
+        self.protocol_fee_share_bps = self.pending_protocol_fee_share_bps;
+        self.pending_fee_numerator = 0;
+        self.pending_protocol_fee_share_bps = 0;
+        self.pending_fees_effective_at = 0;
+        true
+    }
+
+    /// Stream the LP stakers' share of a protocol fee out of the protocol's balance.
+    /// `sol` selects the fee token. Returns the amount routed to LP stakers.
+    pub fn accrue_lp_staker_fees(&mut self, protocol_fee: u64, sol: bool) -> Option<u64> {
+        if self.total_lp_staked == 0 || protocol_fee == 0 {
+            return Some(0);
+        }
+        let amount = (protocol_fee as u128)
+            .checked_mul(self.lp_staker_fee_share_bps as u128)?
+            .checked_div(10000)? as u64;
+        if amount == 0 {
+            return Some(0);
+        }
+        let rewards_per_token = Numeric::from_fraction(amount, self.total_lp_staked);
+        if sol {
+            self.protocol_fees_sol = self.protocol_fees_sol.checked_sub(amount)?;
+            self.lp_rewards_owed_sol = self.lp_rewards_owed_sol.checked_add(amount)?;
+            self.lp_rewards_factor_sol = self.lp_rewards_factor_sol + rewards_per_token;
+        } else {
+            self.protocol_fees_rng = self.protocol_fees_rng.checked_sub(amount)?;
+            self.lp_rewards_owed_rng = self.lp_rewards_owed_rng.checked_add(amount)?;
+            self.lp_rewards_factor_rng = self.lp_rewards_factor_rng + rewards_per_token;
+        }
+        Some(amount)
+    }
+
+    /// Largest swap input allowed against `input_reserve` (prevents large impact swaps).
+    pub fn max_swap_amount(&self, input_reserve: u64) -> Option<u64> {
+        let max_swap = (input_reserve as u128)
+            .checked_mul(self.max_swap_bps as u128)?
+            .checked_div(10000)?;
+        u64::try_from(max_swap).ok()
+    }
+
+    pub fn price_window_reference(&self) -> u128 {
+        (self.price_window_reference_high as u128) << 64 | self.price_window_reference_low as u128
+    }
+
+    /// Start a new circuit breaker window at the current price if the last one expired.
+    /// Call before a swap changes the reserves.
+    pub fn roll_price_window(&mut self, now: i64) {
+        if self.max_price_move_bps == 0 {
+            return;
+        }
+        let expired = now.saturating_sub(self.price_window_start_at) >= self.price_window_seconds;
+        if expired || self.price_window_reference() == 0 {
+            let (sol_price, _) = self.spot_prices().unwrap_or((0, 0));
+            self.price_window_reference_low = sol_price as u64;
+            self.price_window_reference_high = (sol_price >> 64) as u64;
+            self.price_window_start_at = now;
+        }
+    }
+
+    /// Check the current price against the window's reference price.
+    /// Call after a swap changes the reserves; false means the swap moved the price
+    /// further than max_price_move_bps within the window and must be rejected.
+    pub fn within_price_band(&self) -> bool {
+        let reference = self.price_window_reference();
+        if self.max_price_move_bps == 0 || reference == 0 {
+            return true;
+        }
+        let Some((sol_price, _)) = self.spot_prices() else {
+            return false;
+        };
+        let moved = sol_price.abs_diff(reference);
+        moved.saturating_mul(10000) / reference <= self.max_price_move_bps as u128
+    }
+
+    /// Move the referrer's share of a protocol fee out of the protocol's balance.
+    /// `sol` selects the fee token. Returns the amount owed to the referrer.
+    pub fn accrue_referral_fee(&mut self, protocol_fee: u64, sol: bool) -> Option<u64> {
+        let amount = (protocol_fee as u128)
+            .checked_mul(self.referral_fee_share_bps as u128)?
+            .checked_div(10000)? as u64;
+        if sol {
+            self.protocol_fees_sol = self.protocol_fees_sol.checked_sub(amount)?;
+            self.referral_fees_owed_sol = self.referral_fees_owed_sol.checked_add(amount)?;
+        } else {
+            self.protocol_fees_rng = self.protocol_fees_rng.checked_sub(amount)?;
+            self.referral_fees_owed_rng = self.referral_fees_owed_rng.checked_add(amount)?;
+        }
+        Some(amount)
+    }
+
+    /// Take the protocol fees that have crossed their skim thresholds.
+    /// Returns (sol, rng) to move to the treasury; fees below threshold keep accruing.
+    pub fn skim_protocol_fees(&mut self) -> (u64, u64) {
+        let sol = if self.protocol_fees_sol >= EXCHANGE_FEE_SKIM_THRESHOLD_SOL {
+            std::mem::take(&mut self.protocol_fees_sol)
+        } else {
+            0
+        };
+        let rng = if self.protocol_fees_rng >= EXCHANGE_FEE_SKIM_THRESHOLD_RNG {
+            std::mem::take(&mut self.protocol_fees_rng)
+        } else {
+            0
+        };
+        (sol, rng)
     }
 
     /// Calculate LP tokens to mint for liquidity deposit.
@@ -173,10 +738,194 @@ impl ExchangePool {
         Some((sol_amount, rng_amount))
     }
 
+    /// Calculate an emergency withdrawal while the pool is paused or deprecated.
+    /// The final withdrawer also takes the reserves backing the locked minimum
+    /// liquidity, emptying the pool. Returns (sol_amount, rng_amount, lp_supply_removed).
+    pub fn calculate_emergency_withdraw_amounts(
+        &self,
+        lp_amount: u64,
+    ) -> Option<(u64, u64, u64)> {
+        let outstanding = self.total_lp_supply.checked_sub(self.minimum_liquidity)?;
+        if lp_amount > outstanding {
+            return None;
+        }
+        if lp_amount == outstanding {
+            if lp_amount == 0 {
+                return None;
+            }
+            return Some((self.sol_reserve, self.rng_reserve, self.total_lp_supply));
+        }
+        let (sol_amount, rng_amount) = self.calculate_withdraw_amounts(lp_amount)?;
+        Some((sol_amount, rng_amount, lp_amount))
+    }
+
+    /// Record game tokens minted 1:1 against `backing` RNG left in the vault.
+    pub fn record_game_token_mint(&mut self, index: usize, backing: u64, minted: u64) -> Option<()> {
+        let backing_total = self.game_token_backing.get_mut(index)?;
+        *backing_total = backing_total.checked_add(backing)?;
+        let outstanding = &mut self.game_token_outstanding[index];
+        *outstanding = outstanding.checked_add(minted)?;
+        Some(())
+    }
+
+    /// Record game tokens burned for `released` RNG leaving the backing.
+    /// Returns None if the redemption would leave the remaining supply under-backed.
+    pub fn record_game_token_redeem(&mut self, index: usize, burned: u64, released: u64) -> Option<()> {
+        let backing = self.game_token_backing.get(index)?.checked_sub(released)?;
+        let outstanding = self.game_token_outstanding[index].checked_sub(burned)?;
+        if backing < outstanding {
+            return None;
+        }
+        self.game_token_backing[index] = backing;
+        self.game_token_outstanding[index] = outstanding;
+        Some(())
+    }
+
+    /// Current backing of a game token's supply.
+    pub fn game_token_backing(&self, index: usize) -> Option<GameTokenBacking> {
+        let backing = *self.game_token_backing.get(index)?;
+        let outstanding = self.game_token_outstanding[index];
+        let collateralization_bps = if outstanding == 0 {
+            u64::MAX
+        } else {
+            u64::try_from((backing as u128) * 10000 / (outstanding as u128)).unwrap_or(u64::MAX)
+        };
+        Some(GameTokenBacking {
+            backing,
+            outstanding,
+            collateralization_bps,
+        })
+    }
+
+    /// Protocol SOL available for a CRAP buyback at `now`, or None if buybacks are
+    /// disabled, rate limited, or there is nothing to spend.
+    pub fn buyback_amount(&self, now: i64) -> Option<u64> {
+        if self.buyback_share_bps == 0
+            || now < self.last_buyback_at.checked_add(self.buyback_interval_seconds)?
+        {
+            return None;
+        }
+        let share = (self.protocol_fees_sol as u128)
+            .checked_mul(self.buyback_share_bps as u128)?
+            .checked_div(10000)? as u64;
+        let amount = share.min(self.buyback_max_sol);
+        (amount > 0).then_some(amount)
+    }
+
+    /// Add a swap's SOL notional to the counter for `slot`, resetting it on a new slot.
+    /// Call before the swap changes the reserves; false means the slot is over its cap.
+    pub fn record_slot_notional(&mut self, slot: u64, notional_sol: u64) -> bool {
+        if slot != self.notional_slot {
+            self.notional_slot = slot;
+            self.slot_notional_sol = 0;
+        }
+        self.slot_notional_sol = self.slot_notional_sol.saturating_add(notional_sol);
+        if self.slot_notional_cap_bps == 0 {
+            return true;
+        }
+        let cap = (self.sol_reserve as u128) * (self.slot_notional_cap_bps as u128) / 10000;
+        self.slot_notional_sol as u128 <= cap
+    }
+
+    /// Add a swap's SOL volume and fees to the current hourly bucket,
+    /// clearing the bucket first if it still holds an hour from a day ago.
+    pub fn record_stats(&mut self, now: i64, volume_sol: u64, fees_sol: u64) {
+        let hour = now.div_euclid(EXCHANGE_STATS_BUCKET_SECONDS);
+        let index = hour.rem_euclid(EXCHANGE_STATS_BUCKETS as i64) as usize;
+        if self.stats_bucket_hours[index] != hour {
+            self.stats_bucket_hours[index] = hour;
+            self.stats_volume_sol[index] = 0;
+            self.stats_fees_sol[index] = 0;
+        }
+        self.stats_volume_sol[index] = self.stats_volume_sol[index].saturating_add(volume_sol);
+        self.stats_fees_sol[index] = self.stats_fees_sol[index].saturating_add(fees_sol);
+    }
+
+    /// SOL volume and fees over the last 24 hours, as (volume, fees).
+    pub fn rolling_24h_stats(&self, now: i64) -> (u64, u64) {
+        let hour = now.div_euclid(EXCHANGE_STATS_BUCKET_SECONDS);
+        let oldest = hour - EXCHANGE_STATS_BUCKETS as i64;
+        (0..EXCHANGE_STATS_BUCKETS)
+            .filter(|&i| self.stats_bucket_hours[i] > oldest && self.stats_bucket_hours[i] <= hour)
+            .fold((0u64, 0u64), |(volume, fees), i| {
+                (
+                    volume.saturating_add(self.stats_volume_sol[i]),
+                    fees.saturating_add(self.stats_fees_sol[i]),
+                )
+            })
+    }
+
+    /// LP fee APR in basis points, annualizing the last 24 hours of fees over
+    /// pool liquidity valued at twice the SOL reserve. None for an empty pool.
+    pub fn fee_apr_bps(&self, now: i64) -> Option<u64> {
+        let (_, fees) = self.rolling_24h_stats(now);
+        let liquidity = (self.sol_reserve as u128).checked_mul(2)?;
+        if liquidity == 0 {
+            return None;
+        }
+        let apr = (fees as u128)
+            .checked_mul(365)?
+            .checked_mul(10000)?
+            .checked_div(liquidity)?;
+        Some(u64::try_from(apr).unwrap_or(u64::MAX))
+    }
+
     /// Check if pool is active.
     pub fn is_active(&self) -> bool {
         self.status == 0
     }
+
+    /// Check if pool is paused.
+    pub fn is_paused(&self) -> bool {
+        self.status == 1
+    }
+
+    /// Check if pool is deprecated.
+    pub fn is_deprecated(&self) -> bool {
+        self.status == 2
+    }
+
+    /// Check if the signer may pause the pool (admin or guardian).
+    pub fn can_pause(&self, signer: &Pubkey) -> bool {
+        *signer == self.admin || (self.guardian != Pubkey::default() && *signer == self.guardian)
+    }
+}
+
+/// CPMM swap output shared by the SOL/RNG pool and the game token pools.
+/// The protocol share of the fee is taken out before the input reaches the pool.
+/// Returns (output_amount, lp_fee, protocol_fee).
+pub fn cpmm_swap_output(
+    input_amount: u64,
+    input_reserve: u64,
+    output_reserve: u64,
+    fee_numerator: u64,
+    fee_denominator: u64,
+    protocol_fee_share_bps: u64,
+) -> Option<(u64, u64, u64)> {
+    if input_amount == 0 || input_reserve == 0 || output_reserve == 0 {
+        return None;
+    }
+
+    // Calculate total fee (default 1% = 100/10000)
+    let total_fee = input_amount
+        .checked_mul(fee_numerator)?
+        .checked_div(fee_denominator)?;
+
+    // Split fee between LPs and protocol
+    let protocol_fee = (total_fee as u128)
+        .checked_mul(protocol_fee_share_bps as u128)?
+        .checked_div(10000)? as u64;
+    let lp_fee = total_fee.checked_sub(protocol_fee)?;
+
+    // Input after fee goes into pool
+    let input_with_lp_fee = input_amount.checked_sub(protocol_fee)?;
+
+    // CPMM formula: output = (output_reserve * input_with_fee) / (input_reserve + input_with_fee)
+    let numerator = (output_reserve as u128).checked_mul(input_with_lp_fee as u128)?;
+    let denominator = (input_reserve as u128).checked_add(input_with_lp_fee as u128)?;
+    let output = numerator.checked_div(denominator)? as u64;
+
+    Some((output, lp_fee, protocol_fee))
 }
 
 /// Integer square root using Newton's method.
@@ -194,3 +943,349 @@ fn integer_sqrt(n: u128) -> u128 {
 }
 
 account!(OreAccount, ExchangePool);
+account_size!(ExchangePool, 1296);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(sol_reserve: u64, rng_reserve: u64) -> ExchangePool {
+        let mut pool = ExchangePool::zeroed();
+        pool.sol_reserve = sol_reserve;
+        pool.rng_reserve = rng_reserve;
+        pool.last_observation_at = 1_000;
+        pool.protocol_fee_share_bps = EXCHANGE_DEFAULT_PROTOCOL_FEE_SHARE_BPS;
+        pool
+    }
+
+    #[test]
+    fn test_quote_matches_swap_output() {
+        let mut pool = pool(1_000_000, 4_000_000);
+        pool.fee_numerator = 100;
+        pool.fee_denominator = 10000;
+
+        let quote = pool.quote(10_000, true).unwrap();
+        let (out, lp_fee, protocol_fee) = pool
+            .calculate_swap_output(10_000, pool.sol_reserve, pool.rng_reserve)
+            .unwrap();
+        assert_eq!((quote.amount_out, quote.lp_fee, quote.protocol_fee), (out, lp_fee, protocol_fee));
+        // 1% fee (half stays in the pool) plus ~1% slippage.
+        assert!(quote.price_impact_bps > 100 && quote.price_impact_bps < 200);
+
+        let reverse = pool.quote(40_000, false).unwrap();
+        assert!(reverse.amount_out < 10_000);
+        assert_eq!(SwapQuote::from_bytes(quote.to_bytes()), Some(quote));
+    }
+
+    #[test]
+    fn test_swap_input_inverts_output() {
+        let mut pool = pool(1_000_000, 4_000_000);
+        pool.fee_numerator = 100;
+        pool.fee_denominator = 10000;
+
+        for output in [1, 999, 40_000, 250_000] {
+            let (input, lp_fee, protocol_fee) = pool
+                .calculate_swap_input(output, pool.sol_reserve, pool.rng_reserve)
+                .unwrap();
+            let (actual, out_lp_fee, out_protocol_fee) = pool
+                .calculate_swap_output(input, pool.sol_reserve, pool.rng_reserve)
+                .unwrap();
+            assert!(actual >= output);
+            assert_eq!((lp_fee, protocol_fee), (out_lp_fee, out_protocol_fee));
+            // One less unit of input falls short.
+            let short = pool.calculate_swap_output(input - 1, pool.sol_reserve, pool.rng_reserve);
+            assert!(short.map_or(true, |(out, _, _)| out < output));
+        }
+    }
+
+    #[test]
+    fn test_swap_input_rejects_draining_output() {
+        let pool = pool(1_000, 1_000);
+        assert!(pool.calculate_swap_input(1_000, 1_000, 1_000).is_none());
+        assert!(pool.calculate_swap_input(0, 1_000, 1_000).is_none());
+    }
+
+    #[test]
+    fn test_fee_change_respects_timelock() {
+        let mut pool = pool(100, 100);
+        pool.fee_numerator = 100;
+        pool.fee_denominator = 10000;
+
+        assert!(pool.schedule_fees(EXCHANGE_MAX_FEE_NUMERATOR + 1, 5000, 0).is_none());
+        assert!(pool.schedule_fees(100, 10001, 0).is_none());
+
+        let effective_at = pool.schedule_fees(30, 2000, 1_000).unwrap();
+        assert_eq!(effective_at, 1_000 + EXCHANGE_FEE_TIMELOCK_SECONDS);
+        assert!(!pool.apply_pending_fees(effective_at - 1));
+        assert_eq!(pool.fee_numerator, 100);
+        assert!(pool.apply_pending_fees(effective_at));
+        assert_eq!((pool.fee_numerator, pool.protocol_fee_share_bps), (30, 2000));
+        assert!(!pool.apply_pending_fees(effective_at + 1));
+    }
+
+    #[test]
+    fn test_split_fee_uses_protocol_share() {
+        let mut pool = pool(100, 100);
+        assert_eq!(pool.split_fee(101), Some((51, 50)));
+        pool.protocol_fee_share_bps = 2000;
+        assert_eq!(pool.split_fee(100), Some((80, 20)));
+        pool.protocol_fee_share_bps = 0;
+        assert_eq!(pool.split_fee(100), Some((100, 0)));
+    }
+
+    #[test]
+    fn test_lp_staker_fees_stream_from_protocol_share() {
+        let mut pool = pool(100, 100);
+        pool.lp_staker_fee_share_bps = EXCHANGE_DEFAULT_LP_STAKER_FEE_SHARE_BPS;
+        pool.protocol_fees_sol = 100;
+
+        // Nothing streams while no LP tokens are staked.
+        assert_eq!(pool.accrue_lp_staker_fees(100, true), Some(0));
+        assert_eq!(pool.protocol_fees_sol, 100);
+
+        pool.total_lp_staked = 25;
+        assert_eq!(pool.accrue_lp_staker_fees(100, true), Some(50));
+        assert_eq!(pool.protocol_fees_sol, 50);
+        assert_eq!(pool.lp_rewards_owed_sol, 50);
+        assert_eq!(pool.lp_rewards_factor_sol, Numeric::from_u64(2));
+        assert_eq!(pool.lp_rewards_factor_rng, Numeric::ZERO);
+    }
+
+    #[test]
+    fn test_zap_swap_amount_balances_deposit() {
+        let mut pool = pool(1_000_000, 4_000_000);
+        pool.fee_numerator = 100;
+        pool.fee_denominator = 10000;
+
+        let amount = 20_000;
+        let swap = pool
+            .calculate_zap_swap_amount(amount, pool.sol_reserve, pool.rng_reserve)
+            .unwrap();
+        // About half; price impact and the protocol fee shift it slightly.
+        assert!(swap > amount * 49 / 100 && swap < amount * 51 / 100);
+
+        let (out, _, protocol_fee) = pool
+            .calculate_swap_output(swap, pool.sol_reserve, pool.rng_reserve)
+            .unwrap();
+        let sol_reserve = (pool.sol_reserve + swap - protocol_fee) as u128;
+        let rng_reserve = (pool.rng_reserve - out) as u128;
+        let remaining = (amount - swap) as u128;
+        // Remaining input matches the output at the new price to within rounding.
+        let implied_rng = remaining * rng_reserve / sol_reserve;
+        assert!(implied_rng >= out as u128 && implied_rng - (out as u128) <= 4);
+    }
+
+    #[test]
+    fn test_skim_only_above_threshold() {
+        let mut pool = pool(100, 100);
+        pool.protocol_fees_sol = EXCHANGE_FEE_SKIM_THRESHOLD_SOL - 1;
+        pool.protocol_fees_rng = EXCHANGE_FEE_SKIM_THRESHOLD_RNG;
+        assert_eq!(pool.skim_protocol_fees(), (0, EXCHANGE_FEE_SKIM_THRESHOLD_RNG));
+        assert_eq!(pool.protocol_fees_sol, EXCHANGE_FEE_SKIM_THRESHOLD_SOL - 1);
+        assert_eq!(pool.protocol_fees_rng, 0);
+        assert_eq!(pool.skim_protocol_fees(), (0, 0));
+    }
+
+    #[test]
+    fn test_referral_fee_comes_from_protocol_share() {
+        let mut pool = pool(100, 100);
+        pool.referral_fee_share_bps = 2000;
+        pool.protocol_fees_rng = 500;
+        assert_eq!(pool.accrue_referral_fee(500, false), Some(100));
+        assert_eq!(pool.protocol_fees_rng, 400);
+        assert_eq!(pool.referral_fees_owed_rng, 100);
+        assert_eq!(pool.referral_fees_owed_sol, 0);
+    }
+
+    #[test]
+    fn test_circuit_breaker_rolling_window() {
+        let mut pool = pool(1_000_000, 1_000_000);
+        pool.max_price_move_bps = 500;
+        pool.price_window_seconds = 60;
+
+        pool.roll_price_window(1_000);
+        assert_eq!(pool.price_window_reference(), 1 << 64);
+
+        // 4% move is allowed, a further move past 5% within the window is not.
+        pool.rng_reserve = 960_000;
+        assert!(pool.within_price_band());
+        pool.roll_price_window(1_030);
+        pool.rng_reserve = 940_000;
+        assert!(!pool.within_price_band());
+
+        // Once the window expires the reference resets to the current price.
+        pool.roll_price_window(1_060);
+        assert!(pool.within_price_band());
+        assert_eq!(pool.price_window_start_at, 1_060);
+    }
+
+    #[test]
+    fn test_circuit_breaker_disabled_by_default() {
+        let mut pool = pool(1_000_000, 1_000_000);
+        pool.roll_price_window(1_000);
+        pool.rng_reserve = 1;
+        assert!(pool.within_price_band());
+    }
+
+    #[test]
+    fn test_game_token_redemptions_stay_backed() {
+        let mut pool = pool(0, 0);
+        // 1000 RNG in, 1% fee: 990 minted, 995 stays as backing after the protocol share.
+        pool.record_game_token_mint(0, 995, 990).unwrap();
+        let view = pool.game_token_backing(0).unwrap();
+        assert_eq!((view.backing, view.outstanding), (995, 990));
+        assert_eq!(view.collateralization_bps, 10050);
+
+        // Burn 500 for 495 out plus 2 protocol fee.
+        pool.record_game_token_redeem(0, 500, 497).unwrap();
+        assert_eq!(pool.game_token_backing[0], 498);
+        assert_eq!(pool.game_token_outstanding[0], 490);
+
+        // Releasing more RNG than the remaining supply can spare is rejected.
+        assert_eq!(pool.record_game_token_redeem(0, 10, 20), None);
+        // As is burning tokens this pool never minted.
+        assert_eq!(pool.record_game_token_redeem(0, 491, 0), None);
+        // Each game token is accounted separately.
+        assert_eq!(pool.record_game_token_redeem(1, 1, 1), None);
+        assert_eq!(pool.game_token_backing(1).unwrap().collateralization_bps, u64::MAX);
+        assert_eq!(pool.game_token_backing(NUM_GAME_TOKENS), None);
+    }
+
+    #[test]
+    fn test_buyback_amount_is_capped_and_rate_limited() {
+        let mut pool = pool(0, 0);
+        pool.protocol_fees_sol = 10_000;
+        assert_eq!(pool.buyback_amount(0), None);
+
+        pool.buyback_share_bps = 5000;
+        pool.buyback_max_sol = 3_000;
+        pool.buyback_interval_seconds = EXCHANGE_MIN_BUYBACK_INTERVAL_SECONDS;
+        pool.last_buyback_at = 1_000;
+        assert_eq!(pool.buyback_amount(1_000 + EXCHANGE_MIN_BUYBACK_INTERVAL_SECONDS - 1), None);
+        assert_eq!(pool.buyback_amount(1_000 + EXCHANGE_MIN_BUYBACK_INTERVAL_SECONDS), Some(3_000));
+
+        pool.protocol_fees_sol = 4_000;
+        assert_eq!(pool.buyback_amount(i64::MAX - 1), Some(2_000));
+        pool.protocol_fees_sol = 1;
+        assert_eq!(pool.buyback_amount(i64::MAX - 1), None);
+    }
+
+    #[test]
+    fn test_rolling_stats_drop_hours_older_than_a_day() {
+        let mut pool = pool(1_000_000, 4_000_000);
+        let hour = EXCHANGE_STATS_BUCKET_SECONDS;
+        let start = 1_000 * hour;
+
+        pool.record_stats(start, 100, 1);
+        pool.record_stats(start + hour / 2, 200, 2);
+        pool.record_stats(start + 5 * hour, 400, 4);
+        assert_eq!(pool.rolling_24h_stats(start + 5 * hour), (700, 7));
+
+        // 24 hours after the first bucket it falls out of the window.
+        assert_eq!(pool.rolling_24h_stats(start + 24 * hour), (400, 4));
+
+        // Writing the same slot a day later replaces the stale bucket.
+        pool.record_stats(start + 24 * hour, 50, 5);
+        assert_eq!(pool.rolling_24h_stats(start + 24 * hour), (450, 9));
+        assert_eq!(pool.rolling_24h_stats(start + 48 * hour), (0, 0));
+    }
+
+    #[test]
+    fn test_fee_apr() {
+        let mut pool = pool(1_000_000, 4_000_000);
+        // 100 SOL of fees a day on 2,000,000 SOL of liquidity: 0.005% daily, 1.825% APR.
+        pool.record_stats(0, 10_000, 100);
+        assert_eq!(pool.fee_apr_bps(0), Some(182));
+        assert_eq!(ExchangePool::zeroed().fee_apr_bps(0), None);
+    }
+
+    #[test]
+    fn test_max_swap_amount_scales_with_reserve() {
+        let mut pool = pool(1_000_000, 4_000_000);
+        pool.max_swap_bps = 1000;
+        assert_eq!(pool.max_swap_amount(pool.sol_reserve), Some(100_000));
+        assert_eq!(pool.max_swap_amount(pool.rng_reserve), Some(400_000));
+        pool.max_swap_bps = 0;
+        assert_eq!(pool.max_swap_amount(pool.sol_reserve), Some(0));
+    }
+
+    #[test]
+    fn test_emergency_withdraw_releases_locked_liquidity_to_final_lp() {
+        let mut pool = pool(1_000_000, 4_000_000);
+        pool.minimum_liquidity = 1_000;
+        pool.total_lp_supply = 2_001_000;
+
+        // Partial exits stay proportional and leave the floor in place.
+        assert_eq!(
+            pool.calculate_emergency_withdraw_amounts(1_000_000),
+            Some((499_750, 1_999_000, 1_000_000))
+        );
+        // The last outstanding LP token holder empties the pool.
+        assert_eq!(
+            pool.calculate_emergency_withdraw_amounts(2_000_000),
+            Some((1_000_000, 4_000_000, 2_001_000))
+        );
+        // Nobody can burn the locked tokens themselves.
+        assert_eq!(pool.calculate_emergency_withdraw_amounts(2_000_001), None);
+        assert_eq!(pool.calculate_emergency_withdraw_amounts(0), None);
+    }
+
+    #[test]
+    fn test_quote_empty_pool() {
+        assert!(pool(0, 0).quote(100, true).is_none());
+    }
+
+    #[test]
+    fn test_guardian_can_pause() {
+        let mut pool = pool(100, 100);
+        let admin = Pubkey::new_unique();
+        let guardian = Pubkey::new_unique();
+        pool.admin = admin;
+        assert!(pool.can_pause(&admin));
+        assert!(!pool.can_pause(&Pubkey::default()));
+        assert!(!pool.can_pause(&guardian));
+        pool.guardian = guardian;
+        assert!(pool.can_pause(&guardian));
+    }
+
+    #[test]
+    fn test_twap_constant_price() {
+        let mut pool = pool(100, 400);
+        let start = pool.observe(1_000);
+        pool.update_price_accumulators(1_060);
+        let (sol, rng) = pool.observe(1_060).twap_since(&start).unwrap();
+        assert_eq!(sol, 4 << 64);
+        assert_eq!(rng, 1 << 62);
+    }
+
+    #[test]
+    fn test_twap_weights_by_time() {
+        let mut pool = pool(100, 100);
+        let start = pool.observe(1_000);
+        // Price 1 for 30s, then price 4 for 10s.
+        pool.update_price_accumulators(1_030);
+        pool.rng_reserve = 400;
+        pool.update_price_accumulators(1_040);
+        let (sol, _) = pool.observe(1_040).twap_since(&start).unwrap();
+        assert_eq!(sol, (7 << 64) / 4);
+    }
+
+    #[test]
+    fn test_observe_includes_time_since_update() {
+        let pool = pool(100, 200);
+        let start = pool.observe(1_000);
+        let (sol, _) = pool.observe(1_100).twap_since(&start).unwrap();
+        assert_eq!(sol, 2 << 64);
+        assert!(pool.observe(1_000).twap_since(&start).is_none());
+    }
+
+    #[test]
+    fn test_twap_survives_accumulator_wrap() {
+        let mut pool = pool(1, u64::MAX);
+        pool.price_sol_cumulative_low = u64::MAX;
+        pool.price_sol_cumulative_high = u64::MAX;
+        let start = pool.observe(1_000);
+        let (sol, _) = pool.observe(1_001).twap_since(&start).unwrap();
+        assert_eq!(sol, (u64::MAX as u128) << 64);
+    }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use steel::*;
+
+use crate::consts::FAUCET_COOLDOWN_SLOTS;
+use crate::state::faucet_claim_pda;
+
+use super::OreAccount;
+
+/// FaucetClaim rate-limits a tester's devnet faucet drips, one per token per cooldown.
+/// It is only written by programs built with the `devnet` feature.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct FaucetClaim {
+    /// The tester this account belongs to.
+    pub authority: Pubkey,
+
+    /// The first slot at which the next RNG drip can be claimed.
+    pub next_rng_slot: u64,
+
+    /// The first slot at which the next CRAP drip can be claimed.
+    pub next_crap_slot: u64,
+
+    /// The total amount of RNG dripped to this tester.
+    pub total_rng: u64,
+
+    /// The total amount of CRAP dripped to this tester.
+    pub total_crap: u64,
+}
+
+impl FaucetClaim {
+    pub fn pda(&self) -> (Pubkey, u8) {
+        faucet_claim_pda(self.authority)
+    }
+
+    /// Records an RNG drip of `amount` at `slot`. Returns false if still cooling down.
+    pub fn claim_rng(&mut self, amount: u64, slot: u64) -> bool {
+        drip(&mut self.next_rng_slot, &mut self.total_rng, amount, slot)
+    }
+
+    /// Records a CRAP drip of `amount` at `slot`. Returns false if still cooling down.
+    pub fn claim_crap(&mut self, amount: u64, slot: u64) -> bool {
+        drip(&mut self.next_crap_slot, &mut self.total_crap, amount, slot)
+    }
+}
+
+fn drip(next_slot: &mut u64, total: &mut u64, amount: u64, slot: u64) -> bool {
+    if slot < *next_slot {
+        return false;
+    }
+    *next_slot = slot.saturating_add(FAUCET_COOLDOWN_SLOTS);
+    *total = total.saturating_add(amount);
+    true
+}
+
+account!(OreAccount, FaucetClaim);
+account_size!(FaucetClaim, 72);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claims_respect_cooldown() {
+        let mut claim = FaucetClaim::zeroed();
+        assert!(claim.claim_rng(10, 100));
+        assert!(!claim.claim_rng(10, 100 + FAUCET_COOLDOWN_SLOTS - 1));
+        assert!(claim.claim_rng(10, 100 + FAUCET_COOLDOWN_SLOTS));
+        assert_eq!(claim.total_rng, 20);
+
+        // Each token cools down on its own.
+        assert!(claim.claim_crap(5, 101));
+        assert_eq!(claim.total_crap, 5);
+        assert_eq!(claim.next_crap_slot, 101 + FAUCET_COOLDOWN_SLOTS);
+    }
+}
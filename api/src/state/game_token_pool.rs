@@ -0,0 +1,202 @@
+use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use solana_program::pubkey::Pubkey;
+use steel::*;
+
+use crate::consts::{GAME_TOKEN_POOL, GAME_TOKEN_POOL_GAME_VAULT, GAME_TOKEN_POOL_RNG_VAULT};
+
+use super::exchange_pool::cpmm_swap_output;
+use super::OreAccount;
+
+/// CPMM pool pairing RNG with a single game token, keyed by the game token mint.
+///
+/// Lets each game token trade at a market price instead of the fixed 1:1 rate.
+/// Liquidity is protocol-owned and seeded by the exchange admin. The 1:1 mint/burn
+/// swaps remain available as a fallback for game tokens without a pool.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct GameTokenPool {
+    /// The game token mint this pool trades against RNG.
+    pub game_mint: Pubkey,
+
+    /// The RNG vault token account.
+    pub rng_vault: Pubkey,
+
+    /// The game token vault token account.
+    pub game_vault: Pubkey,
+
+    /// The admin/authority that can update pool parameters.
+    pub admin: Pubkey,
+
+    /// Current RNG reserve (in base units).
+    pub rng_reserve: u64,
+
+    /// Current game token reserve (in base units).
+    pub game_reserve: u64,
+
+    /// Constant product k = rng_reserve * game_reserve.
+    /// Split into two u64 parts for Pod compatibility.
+    pub k_low: u64,
+    pub k_high: u64,
+
+    /// Fee numerator (100 = 1%).
+    pub fee_numerator: u64,
+
+    /// Fee denominator (10000 = 100%).
+    pub fee_denominator: u64,
+
+    /// Share of each swap fee that goes to the protocol, in basis points.
+    pub protocol_fee_share_bps: u64,
+
+    /// Accumulated protocol fees in RNG.
+    pub protocol_fees_rng: u64,
+
+    /// Accumulated protocol fees in game tokens.
+    pub protocol_fees_game: u64,
+
+    /// Total trading volume in RNG (lifetime).
+    pub total_volume_rng: u64,
+
+    /// Total swaps executed (lifetime).
+    pub total_swaps: u64,
+
+    /// Pool creation timestamp.
+    pub created_at: i64,
+
+    /// Last swap timestamp.
+    pub last_swap_at: i64,
+
+    /// Pool bump seed for PDA derivation.
+    pub bump: u8,
+
+    /// Pool status: 0 = active, 1 = paused, 2 = deprecated.
+    pub status: u8,
+
+    /// Padding for alignment.
+    pub _padding: [u8; 6],
+}
+
+impl GameTokenPool {
+    /// Derive the pool PDA for a game token mint.
+    pub fn pda(game_mint: &Pubkey) -> (Pubkey, u8) {
+        game_token_pool_pda(game_mint)
+    }
+
+    pub fn k(&self) -> u128 {
+        (self.k_high as u128) << 64 | self.k_low as u128
+    }
+
+    pub fn set_k(&mut self, k: u128) {
+        self.k_low = k as u64;
+        self.k_high = (k >> 64) as u64;
+    }
+
+    /// Calculate output for a swap using the CPMM formula.
+    /// `rng_to_game` selects the direction. Returns (output_amount, lp_fee, protocol_fee).
+    pub fn calculate_swap_output(
+        &self,
+        input_amount: u64,
+        rng_to_game: bool,
+    ) -> Option<(u64, u64, u64)> {
+        let (input_reserve, output_reserve) = if rng_to_game {
+            (self.rng_reserve, self.game_reserve)
+        } else {
+            (self.game_reserve, self.rng_reserve)
+        };
+        cpmm_swap_output(
+            input_amount,
+            input_reserve,
+            output_reserve,
+            self.fee_numerator,
+            self.fee_denominator,
+            self.protocol_fee_share_bps,
+        )
+    }
+
+    /// Apply a swap to the reserves and fee tracking. Returns the new k.
+    pub fn apply_swap(
+        &mut self,
+        input_amount: u64,
+        output_amount: u64,
+        protocol_fee: u64,
+        rng_to_game: bool,
+    ) -> Option<u128> {
+        let input_to_pool = input_amount.checked_sub(protocol_fee)?;
+        if rng_to_game {
+            self.rng_reserve = self.rng_reserve.checked_add(input_to_pool)?;
+            self.game_reserve = self.game_reserve.checked_sub(output_amount)?;
+            self.protocol_fees_rng = self.protocol_fees_rng.checked_add(protocol_fee)?;
+            self.total_volume_rng = self.total_volume_rng.checked_add(input_amount)?;
+        } else {
+            self.game_reserve = self.game_reserve.checked_add(input_to_pool)?;
+            self.rng_reserve = self.rng_reserve.checked_sub(output_amount)?;
+            self.protocol_fees_game = self.protocol_fees_game.checked_add(protocol_fee)?;
+            self.total_volume_rng = self.total_volume_rng.checked_add(output_amount)?;
+        }
+        let k = (self.rng_reserve as u128).checked_mul(self.game_reserve as u128)?;
+        self.set_k(k);
+        self.total_swaps = self.total_swaps.checked_add(1)?;
+        Some(k)
+    }
+
+    /// Check if pool is active.
+    pub fn is_active(&self) -> bool {
+        self.status == 0
+    }
+}
+
+/// Derive the game token pool PDA for a game token mint.
+pub fn game_token_pool_pda(game_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GAME_TOKEN_POOL, game_mint.as_ref()], &crate::ID)
+}
+
+/// Derive the RNG vault PDA for a game token pool.
+pub fn game_token_pool_rng_vault_pda(game_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GAME_TOKEN_POOL_RNG_VAULT, game_mint.as_ref()], &crate::ID)
+}
+
+/// Derive the game token vault PDA for a game token pool.
+pub fn game_token_pool_game_vault_pda(game_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GAME_TOKEN_POOL_GAME_VAULT, game_mint.as_ref()], &crate::ID)
+}
+
+account!(OreAccount, GameTokenPool);
+account_size!(GameTokenPool, 248);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(rng_reserve: u64, game_reserve: u64) -> GameTokenPool {
+        let mut pool = GameTokenPool::zeroed();
+        pool.rng_reserve = rng_reserve;
+        pool.game_reserve = game_reserve;
+        pool.fee_numerator = 100;
+        pool.fee_denominator = 10000;
+        pool.protocol_fee_share_bps = 5000;
+        pool
+    }
+
+    #[test]
+    fn test_price_follows_reserves() {
+        // Game tokens scarce relative to RNG: each costs more than 1 RNG.
+        let pool = pool(2_000_000, 1_000_000);
+        let (out, _, _) = pool.calculate_swap_output(10_000, true).unwrap();
+        assert!(out < 5_000);
+        let (out, _, _) = pool.calculate_swap_output(10_000, false).unwrap();
+        assert!(out > 19_000);
+    }
+
+    #[test]
+    fn test_apply_swap_keeps_k_nondecreasing() {
+        let mut pool = pool(1_000_000, 1_000_000);
+        let k = (pool.rng_reserve as u128) * (pool.game_reserve as u128);
+        let (out, _, protocol_fee) = pool.calculate_swap_output(50_000, true).unwrap();
+        let new_k = pool.apply_swap(50_000, out, protocol_fee, true).unwrap();
+        assert!(new_k >= k);
+        assert_eq!(pool.protocol_fees_rng, protocol_fee);
+        assert_eq!(pool.total_swaps, 1);
+        assert_eq!(pool.game_reserve, 1_000_000 - out);
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use steel::*;
+
+use crate::state::insurance_fund_pda;
+
+use super::OreAccount;
+
+/// Holds the SOL share of protocol revenue set aside to cover shortfalls.
+/// The lamports above rent are the fund; `balance` tracks them.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct InsuranceFund {
+    /// The amount of SOL currently held by the fund.
+    pub balance: u64,
+
+    /// The cumulative SOL ever paid into the fund.
+    pub total_deposited: u64,
+
+    /// Space for future fields, so they can be added without a realloc.
+    pub _reserved: [u64; 8],
+}
+
+impl InsuranceFund {
+    pub fn pda() -> (Pubkey, u8) {
+        insurance_fund_pda()
+    }
+}
+
+account!(OreAccount, InsuranceFund);
+account_size!(InsuranceFund, 88);
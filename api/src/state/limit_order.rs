@@ -0,0 +1,134 @@
+use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use solana_program::pubkey::Pubkey;
+use steel::*;
+
+use crate::consts::LIMIT_ORDER;
+
+use super::OreAccount;
+
+/// Share of a limit order's input paid to the filler, in basis points (0.1%).
+pub const EXCHANGE_LIMIT_ORDER_BOUNTY_BPS: u64 = 10;
+
+/// Limit order side: sell SOL for RNG.
+pub const LIMIT_ORDER_SIDE_SOL_TO_RNG: u8 = 0;
+
+/// Limit order side: sell RNG for SOL.
+pub const LIMIT_ORDER_SIDE_RNG_TO_SOL: u8 = 1;
+
+/// A resting order to swap against the exchange pool once the price reaches a limit.
+///
+/// The input is escrowed in the order's token account. Anyone can fill the order
+/// once the pool pays at least `limit_price` per input token, earning a bounty
+/// taken from the input.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct LimitOrder {
+    /// The owner of this order.
+    pub authority: Pubkey,
+
+    /// Owner-chosen id, so one owner can have several open orders.
+    pub id: u64,
+
+    /// LIMIT_ORDER_SIDE_SOL_TO_RNG or LIMIT_ORDER_SIDE_RNG_TO_SOL.
+    pub side: u8,
+
+    pub _padding: [u8; 7],
+
+    /// Input tokens escrowed, including the filler bounty.
+    pub amount_in: u64,
+
+    /// Minimum output per input token, Q64.64 (low/high words).
+    pub limit_price_low: u64,
+    pub limit_price_high: u64,
+
+    /// Timestamp after which the order can no longer be filled.
+    pub expires_at: i64,
+
+    /// Timestamp the order was placed.
+    pub created_at: i64,
+}
+
+impl LimitOrder {
+    pub fn pda(&self) -> (Pubkey, u8) {
+        limit_order_pda(self.authority, self.id)
+    }
+
+    pub fn limit_price(&self) -> u128 {
+        ((self.limit_price_high as u128) << 64) | (self.limit_price_low as u128)
+    }
+
+    pub fn set_limit_price(&mut self, price: u128) {
+        self.limit_price_low = price as u64;
+        self.limit_price_high = (price >> 64) as u64;
+    }
+
+    pub fn is_sol_to_rng(&self) -> bool {
+        self.side == LIMIT_ORDER_SIDE_SOL_TO_RNG
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now > self.expires_at
+    }
+
+    /// Split the escrowed input into (swap_amount, bounty).
+    pub fn split_bounty(&self) -> (u64, u64) {
+        let bounty = ((self.amount_in as u128) * (EXCHANGE_LIMIT_ORDER_BOUNTY_BPS as u128)
+            / 10000) as u64;
+        (self.amount_in - bounty, bounty)
+    }
+
+    /// Minimum output a fill must deliver: amount_in * limit_price.
+    /// Measured against the full input, so the owner's limit covers the bounty.
+    /// Returns None if the limit cannot be met by any u64 output.
+    pub fn min_amount_out(&self) -> Option<u64> {
+        let min_out = (self.amount_in as u128)
+            .checked_mul(self.limit_price())?
+            .div_ceil(1u128 << 64);
+        u64::try_from(min_out).ok()
+    }
+}
+
+/// Derive the limit order PDA for an owner and order id.
+pub fn limit_order_pda(authority: Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LIMIT_ORDER, &authority.to_bytes(), &id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+account!(OreAccount, LimitOrder);
+account_size!(LimitOrder, 96);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(amount_in: u64, limit_price: u128) -> LimitOrder {
+        let mut order = LimitOrder::zeroed();
+        order.amount_in = amount_in;
+        order.set_limit_price(limit_price);
+        order.expires_at = 100;
+        order
+    }
+
+    #[test]
+    fn test_min_amount_out_rounds_up() {
+        // 4 RNG per SOL.
+        assert_eq!(order(1_000, 4u128 << 64).min_amount_out(), Some(4_000));
+        // 0.3 per input: 1000 * 0.3 = 300, rounded up past the fixed-point error.
+        let price = (3u128 << 64) / 10;
+        assert_eq!(order(1_000, price).min_amount_out(), Some(300));
+        // A limit no u64 output can meet is never fillable.
+        assert_eq!(order(u64::MAX, 2u128 << 64).min_amount_out(), None);
+    }
+
+    #[test]
+    fn test_bounty_comes_out_of_input() {
+        let order = order(1_000_000, 1u128 << 64);
+        assert_eq!(order.split_bounty(), (999_000, 1_000));
+        assert!(!order.is_expired(100));
+        assert!(order.is_expired(101));
+    }
+}
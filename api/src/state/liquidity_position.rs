@@ -1,4 +1,6 @@
 use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
 use solana_program::pubkey::Pubkey;
 use steel::*;
 
@@ -9,7 +11,7 @@ use super::OreAccount;
 /// Tracks a user's LP token holdings and deposit/withdrawal history.
 /// LP tokens represent proportional ownership of the pool reserves.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
 pub struct LiquidityPosition {
     /// The liquidity provider's authority (wallet).
     pub authority: Pubkey,
@@ -125,3 +127,4 @@ impl LiquidityPosition {
 }
 
 account!(OreAccount, LiquidityPosition);
+account_size!(LiquidityPosition, 168);
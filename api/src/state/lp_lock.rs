@@ -0,0 +1,195 @@
+use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use solana_program::pubkey::Pubkey;
+use steel::*;
+
+use crate::consts::LP_LOCK;
+
+use super::exchange_pool::ExchangePool;
+use super::OreAccount;
+
+/// Shortest LP lock, in seconds (7 days).
+pub const EXCHANGE_LP_LOCK_MIN_DURATION: i64 = 7 * 24 * 60 * 60;
+
+/// Longest LP lock, in seconds (365 days). Earns the maximum boost.
+pub const EXCHANGE_LP_LOCK_MAX_DURATION: i64 = 365 * 24 * 60 * 60;
+
+/// Extra fee share weight at the maximum lock duration, in basis points (2x total).
+pub const EXCHANGE_LP_LOCK_MAX_BOOST_BPS: u64 = 10000;
+
+/// Share of locked LP tokens burned on early exit, in basis points.
+/// Burning leaves the underlying reserves to the remaining LPs.
+pub const EXCHANGE_LP_LOCK_EARLY_EXIT_PENALTY_BPS: u64 = 1000;
+
+/// LP tokens locked for a fixed term in exchange for a boosted share of the
+/// LP stakers' protocol fees.
+///
+/// A lock earns from the same rewards factor as LpStake, weighted by
+/// `balance * boost_bps / 10000`. Unlocking early burns a penalty from the balance.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct LpLock {
+    /// The authority of this lock.
+    pub authority: Pubkey,
+
+    /// Owner-chosen id, so one owner can hold several locks.
+    pub id: u64,
+
+    /// LP tokens locked.
+    pub balance: u64,
+
+    /// Reward weight multiplier in basis points (10000 = 1x).
+    pub boost_bps: u64,
+
+    /// Staking weight this lock contributes to the pool.
+    pub weight: u64,
+
+    /// The timestamp the tokens were locked.
+    pub locked_at: i64,
+
+    /// The timestamp the tokens can be withdrawn without penalty.
+    pub unlock_at: i64,
+
+    /// The pool's SOL rewards factor last time rewards were updated.
+    pub rewards_factor_sol: Numeric,
+
+    /// The pool's RNG rewards factor last time rewards were updated.
+    pub rewards_factor_rng: Numeric,
+
+    /// SOL this lock can claim.
+    pub rewards_sol: u64,
+
+    /// RNG this lock can claim.
+    pub rewards_rng: u64,
+}
+
+impl LpLock {
+    pub fn pda(&self) -> (Pubkey, u8) {
+        lp_lock_pda(self.authority, self.id)
+    }
+
+    /// Boost for a lock of `duration` seconds, scaling linearly up to the max duration.
+    pub fn boost_for_duration(duration: i64) -> Option<u64> {
+        if !(EXCHANGE_LP_LOCK_MIN_DURATION..=EXCHANGE_LP_LOCK_MAX_DURATION).contains(&duration) {
+            return None;
+        }
+        let extra = (EXCHANGE_LP_LOCK_MAX_BOOST_BPS as u128) * (duration as u128)
+            / (EXCHANGE_LP_LOCK_MAX_DURATION as u128);
+        Some(10000 + extra as u64)
+    }
+
+    /// Lock `amount` LP tokens for `duration` seconds, adding its weight to the pool.
+    pub fn lock(&mut self, amount: u64, duration: i64, now: i64, pool: &mut ExchangePool) -> Option<()> {
+        let boost_bps = Self::boost_for_duration(duration)?;
+        let weight = (amount as u128) * (boost_bps as u128) / 10000;
+        self.weight = u64::try_from(weight).ok()?;
+        self.balance = amount;
+        self.boost_bps = boost_bps;
+        self.locked_at = now;
+        self.unlock_at = now.checked_add(duration)?;
+        self.rewards_factor_sol = pool.lp_rewards_factor_sol;
+        self.rewards_factor_rng = pool.lp_rewards_factor_rng;
+        pool.total_lp_staked = pool.total_lp_staked.checked_add(self.weight)?;
+        Some(())
+    }
+
+    /// Release the lock, removing its weight from the pool and settling rewards.
+    /// Returns (lp_returned, lp_penalty); the penalty only applies before unlock_at.
+    pub fn unlock(&mut self, now: i64, pool: &mut ExchangePool) -> (u64, u64) {
+        self.update_rewards(pool);
+        let penalty = if now < self.unlock_at {
+            ((self.balance as u128) * (EXCHANGE_LP_LOCK_EARLY_EXIT_PENALTY_BPS as u128) / 10000)
+                as u64
+        } else {
+            0
+        };
+        let returned = self.balance - penalty;
+        pool.total_lp_staked -= self.weight;
+        self.balance = 0;
+        self.weight = 0;
+        (returned, penalty)
+    }
+
+    /// Take all accrued rewards. Returns (sol, rng).
+    pub fn claim(&mut self, pool: &ExchangePool) -> (u64, u64) {
+        self.update_rewards(pool);
+        let claimed = (self.rewards_sol, self.rewards_rng);
+        self.rewards_sol = 0;
+        self.rewards_rng = 0;
+        claimed
+    }
+
+    pub fn update_rewards(&mut self, pool: &ExchangePool) {
+        // Accumulate rewards, weighted by boosted balance.
+        if pool.lp_rewards_factor_sol > self.rewards_factor_sol {
+            let accumulated = pool.lp_rewards_factor_sol - self.rewards_factor_sol;
+            self.rewards_sol += (accumulated * Numeric::from_u64(self.weight)).to_u64();
+        }
+        if pool.lp_rewards_factor_rng > self.rewards_factor_rng {
+            let accumulated = pool.lp_rewards_factor_rng - self.rewards_factor_rng;
+            self.rewards_rng += (accumulated * Numeric::from_u64(self.weight)).to_u64();
+        }
+
+        // Update this account's last seen rewards factors.
+        self.rewards_factor_sol = pool.lp_rewards_factor_sol;
+        self.rewards_factor_rng = pool.lp_rewards_factor_rng;
+    }
+}
+
+/// Derive the LP lock PDA for an authority and lock id.
+pub fn lp_lock_pda(authority: Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LP_LOCK, &authority.to_bytes(), &id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+account!(OreAccount, LpLock);
+account_size!(LpLock, 136);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::LpStake;
+
+    #[test]
+    fn test_boost_scales_with_duration() {
+        assert_eq!(LpLock::boost_for_duration(EXCHANGE_LP_LOCK_MIN_DURATION - 1), None);
+        assert_eq!(LpLock::boost_for_duration(EXCHANGE_LP_LOCK_MAX_DURATION + 1), None);
+        assert_eq!(LpLock::boost_for_duration(EXCHANGE_LP_LOCK_MAX_DURATION), Some(20000));
+        assert_eq!(LpLock::boost_for_duration(EXCHANGE_LP_LOCK_MAX_DURATION / 2), Some(15000));
+    }
+
+    #[test]
+    fn test_locked_lp_earns_boosted_share() {
+        let mut pool = ExchangePool::zeroed();
+        pool.lp_staker_fee_share_bps = 10000;
+        pool.protocol_fees_sol = 1_000;
+
+        let mut staker = LpStake::zeroed();
+        let mut locker = LpLock::zeroed();
+        staker.deposit(100, 0, &mut pool);
+        locker
+            .lock(100, EXCHANGE_LP_LOCK_MAX_DURATION, 0, &mut pool)
+            .unwrap();
+        assert_eq!(pool.total_lp_staked, 300);
+
+        pool.accrue_lp_staker_fees(300, true).unwrap();
+        assert_eq!(staker.claim(1, &pool), (100, 0));
+        assert_eq!(locker.claim(&pool), (200, 0));
+    }
+
+    #[test]
+    fn test_early_unlock_burns_penalty() {
+        let mut pool = ExchangePool::zeroed();
+        let mut early = LpLock::zeroed();
+        early.lock(1_000, EXCHANGE_LP_LOCK_MIN_DURATION, 0, &mut pool).unwrap();
+        assert_eq!(early.unlock(EXCHANGE_LP_LOCK_MIN_DURATION - 1, &mut pool), (900, 100));
+        assert_eq!(pool.total_lp_staked, 0);
+
+        let mut matured = LpLock::zeroed();
+        matured.lock(1_000, EXCHANGE_LP_LOCK_MIN_DURATION, 0, &mut pool).unwrap();
+        assert_eq!(matured.unlock(EXCHANGE_LP_LOCK_MIN_DURATION, &mut pool), (1_000, 0));
+    }
+}
@@ -0,0 +1,157 @@
+use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use solana_program::pubkey::Pubkey;
+use steel::*;
+
+use crate::consts::LP_STAKE;
+
+use super::exchange_pool::ExchangePool;
+use super::OreAccount;
+
+/// LP tokens staked by a liquidity provider to earn a share of protocol swap fees.
+///
+/// Staked LP tokens still earn the in-pool LP fee through the reserves; staking
+/// adds the LP stakers' share of the protocol fee, paid in SOL and RNG.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct LpStake {
+    /// The authority of this LP stake account.
+    pub authority: Pubkey,
+
+    /// LP tokens staked.
+    pub balance: u64,
+
+    /// The timestamp of last claim.
+    pub last_claim_at: i64,
+
+    /// The timestamp the last time this staker deposited.
+    pub last_deposit_at: i64,
+
+    /// The timestamp the last time this staker withdrew.
+    pub last_withdraw_at: i64,
+
+    /// The pool's SOL rewards factor last time rewards were updated.
+    pub rewards_factor_sol: Numeric,
+
+    /// The pool's RNG rewards factor last time rewards were updated.
+    pub rewards_factor_rng: Numeric,
+
+    /// SOL this staker can claim.
+    pub rewards_sol: u64,
+
+    /// RNG this staker can claim.
+    pub rewards_rng: u64,
+
+    /// Total SOL earned over the account's lifetime.
+    pub lifetime_rewards_sol: u64,
+
+    /// Total RNG earned over the account's lifetime.
+    pub lifetime_rewards_rng: u64,
+}
+
+impl LpStake {
+    pub fn pda(&self) -> (Pubkey, u8) {
+        lp_stake_pda(self.authority)
+    }
+
+    pub fn deposit(&mut self, amount: u64, now: i64, pool: &mut ExchangePool) {
+        self.update_rewards(pool);
+        self.balance += amount;
+        self.last_deposit_at = now;
+        pool.total_lp_staked += amount;
+    }
+
+    pub fn withdraw(&mut self, amount: u64, now: i64, pool: &mut ExchangePool) -> u64 {
+        self.update_rewards(pool);
+        let amount = self.balance.min(amount);
+        self.balance -= amount;
+        self.last_withdraw_at = now;
+        pool.total_lp_staked -= amount;
+        amount
+    }
+
+    /// Claim all accrued rewards. Returns (sol, rng).
+    pub fn claim(&mut self, now: i64, pool: &ExchangePool) -> (u64, u64) {
+        self.update_rewards(pool);
+        let claimed = (self.rewards_sol, self.rewards_rng);
+        self.rewards_sol = 0;
+        self.rewards_rng = 0;
+        self.last_claim_at = now;
+        claimed
+    }
+
+    pub fn update_rewards(&mut self, pool: &ExchangePool) {
+        // Accumulate rewards, weighted by staked balance.
+        if pool.lp_rewards_factor_sol > self.rewards_factor_sol {
+            let accumulated = pool.lp_rewards_factor_sol - self.rewards_factor_sol;
+            let personal_rewards = (accumulated * Numeric::from_u64(self.balance)).to_u64();
+            self.rewards_sol += personal_rewards;
+            self.lifetime_rewards_sol += personal_rewards;
+        }
+        if pool.lp_rewards_factor_rng > self.rewards_factor_rng {
+            let accumulated = pool.lp_rewards_factor_rng - self.rewards_factor_rng;
+            let personal_rewards = (accumulated * Numeric::from_u64(self.balance)).to_u64();
+            self.rewards_rng += personal_rewards;
+            self.lifetime_rewards_rng += personal_rewards;
+        }
+
+        // Update this account's last seen rewards factors.
+        self.rewards_factor_sol = pool.lp_rewards_factor_sol;
+        self.rewards_factor_rng = pool.lp_rewards_factor_rng;
+    }
+}
+
+/// Derive the LP stake PDA for an authority.
+pub fn lp_stake_pda(authority: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LP_STAKE, &authority.to_bytes()], &crate::ID)
+}
+
+account!(OreAccount, LpStake);
+account_size!(LpStake, 136);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewards_split_by_stake_balance() {
+        let mut pool = ExchangePool::zeroed();
+        pool.lp_staker_fee_share_bps = 10000;
+        pool.protocol_fees_sol = 1_000;
+        pool.protocol_fees_rng = 1_000;
+
+        let mut alice = LpStake::zeroed();
+        let mut bob = LpStake::zeroed();
+        alice.deposit(300, 0, &mut pool);
+        bob.deposit(100, 0, &mut pool);
+
+        pool.accrue_lp_staker_fees(400, true).unwrap();
+        pool.accrue_lp_staker_fees(800, false).unwrap();
+
+        assert_eq!(alice.claim(10, &pool), (300, 600));
+        assert_eq!(bob.claim(10, &pool), (100, 200));
+        assert_eq!(alice.claim(20, &pool), (0, 0));
+    }
+
+    #[test]
+    fn test_late_staker_earns_only_new_fees() {
+        let mut pool = ExchangePool::zeroed();
+        pool.lp_staker_fee_share_bps = 10000;
+        pool.protocol_fees_sol = 1_000;
+
+        let mut early = LpStake::zeroed();
+        early.deposit(100, 0, &mut pool);
+        pool.accrue_lp_staker_fees(100, true).unwrap();
+
+        let mut late = LpStake::zeroed();
+        late.deposit(100, 0, &mut pool);
+        pool.accrue_lp_staker_fees(100, true).unwrap();
+
+        assert_eq!(early.claim(0, &pool).0, 150);
+        assert_eq!(late.claim(0, &pool).0, 50);
+
+        assert_eq!(early.withdraw(500, 0, &mut pool), 100);
+        assert_eq!(pool.total_lp_staked, 100);
+    }
+}
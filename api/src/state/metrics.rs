@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use steel::*;
+
+use crate::state::metrics_pda;
+
+use super::OreAccount;
+
+/// Metrics holds protocol-wide KPI counters, so dashboards can be built from RPC alone.
+/// Every counter only ever increases, and `sequence` bumps on each update, so a reader can
+/// tell when two snapshots are the same state.
+///
+/// Craps instructions update it when it is passed as a trailing account. They already
+/// write-lock the craps game, so this adds no lock contention of its own.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct Metrics {
+    /// The number of updates applied to this account.
+    pub sequence: u64,
+
+    /// The number of bets placed.
+    pub total_bets: u64,
+
+    /// The total amount of CRAP wagered.
+    pub total_volume: u64,
+
+    /// The number of craps positions opened.
+    pub unique_positions: u64,
+
+    /// The number of settlements that resolved at least one bet.
+    pub total_settled: u64,
+
+    /// The total amount of CRAP the house has won from resolved bets.
+    pub house_won: u64,
+
+    /// The total amount of CRAP the house has paid out beyond returned stakes.
+    pub house_lost: u64,
+}
+
+impl Metrics {
+    pub fn pda() -> (Pubkey, u8) {
+        metrics_pda()
+    }
+
+    /// Records a bet of `amount`. `new_position` is set when the bet opened a position.
+    pub fn record_bet(&mut self, amount: u64, new_position: bool) {
+        self.total_bets = self.total_bets.saturating_add(1);
+        self.total_volume = self.total_volume.saturating_add(amount);
+        if new_position {
+            self.unique_positions = self.unique_positions.saturating_add(1);
+        }
+        self.sequence = self.sequence.saturating_add(1);
+    }
+
+    /// Records a settlement that resolved `stake` worth of bets and returned `returned` to
+    /// the player, stakes included. Settlements that resolve nothing are not counted.
+    pub fn record_settlement(&mut self, stake: u64, returned: u64) {
+        if stake == 0 && returned == 0 {
+            return;
+        }
+        self.total_settled = self.total_settled.saturating_add(1);
+        if stake >= returned {
+            self.house_won = self.house_won.saturating_add(stake - returned);
+        } else {
+            self.house_lost = self.house_lost.saturating_add(returned - stake);
+        }
+        self.sequence = self.sequence.saturating_add(1);
+    }
+
+    /// The house's net profit across all resolved bets.
+    pub fn house_pnl(&self) -> i128 {
+        self.house_won as i128 - self.house_lost as i128
+    }
+}
+
+account!(OreAccount, Metrics);
+account_size!(Metrics, 64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_counters() {
+        let mut metrics = Metrics::zeroed();
+        metrics.record_bet(100, true);
+        metrics.record_bet(50, false);
+        assert_eq!(metrics.total_bets, 2);
+        assert_eq!(metrics.total_volume, 150);
+        assert_eq!(metrics.unique_positions, 1);
+
+        // A lost bet, a winning bet paying even money, and a settlement with nothing due.
+        metrics.record_settlement(100, 0);
+        metrics.record_settlement(50, 100);
+        metrics.record_settlement(0, 0);
+        assert_eq!(metrics.total_settled, 2);
+        assert_eq!(metrics.house_won, 100);
+        assert_eq!(metrics.house_lost, 50);
+        assert_eq!(metrics.house_pnl(), 50);
+        assert_eq!(metrics.sequence, 4);
+    }
+}
@@ -0,0 +1,176 @@
+use steel::*;
+
+use super::*;
+
+/// Byte of the 8-byte account header that holds the layout version. Byte 0 is the
+/// discriminator and the rest were always zero, so unversioned accounts read as 0.
+pub const ACCOUNT_VERSION_OFFSET: usize = 1;
+
+/// An account whose layout can be upgraded in place.
+///
+/// Layouts only grow: new fields are appended and zero-filled when the account is
+/// reallocated. A change that needs nonzero defaults bumps `VERSION` and backfills
+/// them in `migrate_from`. `CrapsPosition` is not migratable this way, since its
+/// compact layout moved fields out rather than appending. Accounts created at a nonzero version must be stamped with
+/// `stamp_account_version`, or their first touch will rerun the backfill.
+pub trait Migratable: Discriminator + Pod {
+    /// The current layout version.
+    const VERSION: u8 = 0;
+
+    /// Upgrades the account from `version` to `version + 1`.
+    fn migrate_from(&mut self, _version: u8) {}
+}
+
+impl Migratable for AdminMultisig {}
+impl Migratable for AuditLog {}
+impl Migratable for Automation {}
+impl Migratable for Board {}
+impl Migratable for Config {}
+impl Migratable for CrapsGame {}
+impl Migratable for CrapsSideBets {}
+impl Migratable for DcaOrder {}
+impl Migratable for ExchangePool {}
+impl Migratable for FaucetClaim {}
+impl Migratable for GameTokenPool {}
+impl Migratable for InsuranceFund {}
+impl Migratable for LimitOrder {}
+impl Migratable for LiquidityPosition {}
+impl Migratable for LpLock {}
+impl Migratable for LpStake {}
+impl Migratable for Metrics {}
+impl Migratable for Miner {}
+impl Migratable for Param {}
+impl Migratable for Pool {}
+impl Migratable for PoolMember {}
+impl Migratable for Referrer {}
+impl Migratable for Roles {}
+impl Migratable for RollProof {}
+impl Migratable for Round {}
+impl Migratable for RoundArchive {}
+impl Migratable for Session {}
+impl Migratable for Stake {}
+impl Migratable for TestRollOracle {}
+impl Migratable for Treasury {}
+impl Migratable for Vesting {}
+
+/// The layout version recorded in an account's header.
+pub fn account_version(data: &[u8]) -> u8 {
+    data.get(ACCOUNT_VERSION_OFFSET).copied().unwrap_or(0)
+}
+
+/// Writes the current layout version into an account's header.
+pub fn stamp_account_version<T: Migratable>(data: &mut [u8]) {
+    data[ACCOUNT_VERSION_OFFSET] = T::VERSION;
+}
+
+/// Upgrades account data that has already been grown to the current size, running
+/// each backfill step from its recorded version. Returns the version it upgraded
+/// from, or `None` if it was already current.
+pub fn migrate_account_data<T: Migratable>(data: &mut [u8]) -> Result<Option<u8>, ProgramError> {
+    if data.len() != 8 + std::mem::size_of::<T>() || data[0] != T::discriminator() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let version = account_version(data);
+    if version > T::VERSION {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if version == T::VERSION {
+        return Ok(None);
+    }
+    let account = bytemuck::try_from_bytes_mut::<T>(&mut data[8..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    for step in version..T::VERSION {
+        account.migrate_from(step);
+    }
+    stamp_account_version::<T>(data);
+    Ok(Some(version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Simulates a realloc of a legacy account: the old bytes are kept and the new
+    /// tail is zero-filled.
+    fn grow<T: Pod>(legacy: &[u8]) -> Vec<u8> {
+        let mut data = vec![0; 8 + std::mem::size_of::<T>()];
+        data[..legacy.len()].copy_from_slice(legacy);
+        data
+    }
+
+    #[test]
+    fn test_migrate_legacy_stake_layout() {
+        // Stake accounts predating pending withdrawals end at `is_seeker`.
+        let mut stake = Stake::zeroed();
+        stake.authority = Pubkey::new_unique();
+        stake.balance = 42;
+        stake.lifetime_rewards = 7;
+        stake.operator = Pubkey::new_unique();
+        let mut current = vec![Stake::discriminator(), 0, 0, 0, 0, 0, 0, 0];
+        current.extend_from_slice(bytemuck::bytes_of(&stake));
+        let legacy_len = 8 + std::mem::offset_of!(Stake, pending_withdrawal);
+
+        let mut data = grow::<Stake>(&current[..legacy_len]);
+        assert_eq!(migrate_account_data::<Stake>(&mut data), Ok(None));
+        let migrated = Stake::try_from_bytes(&data).unwrap();
+        assert_eq!(migrated.authority, stake.authority);
+        assert_eq!(migrated.balance, 42);
+        assert_eq!(migrated.lifetime_rewards, 7);
+        assert_eq!(migrated.operator, Pubkey::default());
+    }
+
+    #[test]
+    fn test_migrate_rejects_wrong_size_or_type() {
+        let mut data = grow::<Miner>(&[Miner::discriminator()]);
+        assert!(migrate_account_data::<Miner>(&mut data[..16]).is_err());
+        assert!(migrate_account_data::<Stake>(&mut data).is_err());
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+    struct Versioned {
+        a: u64,
+        b: u64,
+    }
+
+    impl Discriminator for Versioned {
+        fn discriminator() -> u8 {
+            u8::MAX
+        }
+    }
+
+    impl Migratable for Versioned {
+        const VERSION: u8 = 2;
+
+        fn migrate_from(&mut self, version: u8) {
+            match version {
+                0 => self.a = 10,
+                1 => self.b = self.a + 1,
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn test_migrate_runs_each_step_once() {
+        let mut data = grow::<Versioned>(&[u8::MAX]);
+        assert_eq!(migrate_account_data::<Versioned>(&mut data), Ok(Some(0)));
+        assert_eq!(account_version(&data), 2);
+        let account = bytemuck::from_bytes::<Versioned>(&data[8..]);
+        assert_eq!((account.a, account.b), (10, 11));
+
+        // Already current.
+        assert_eq!(migrate_account_data::<Versioned>(&mut data), Ok(None));
+
+        // From the middle version only the later step runs.
+        let mut data = grow::<Versioned>(&[u8::MAX, 1]);
+        data[8..16].copy_from_slice(&5u64.to_le_bytes());
+        assert_eq!(migrate_account_data::<Versioned>(&mut data), Ok(Some(1)));
+        let account = bytemuck::from_bytes::<Versioned>(&data[8..]);
+        assert_eq!((account.a, account.b), (5, 6));
+
+        // Newer than this build understands.
+        data[ACCOUNT_VERSION_OFFSET] = 3;
+        assert!(migrate_account_data::<Versioned>(&mut data).is_err());
+    }
+}
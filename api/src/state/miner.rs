@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use serde_big_array::BigArray;
+use shank::ShankAccount;
 use steel::*;
 
 use crate::consts::BOARD_SIZE;
@@ -8,7 +9,7 @@ use crate::state::{miner_pda, Treasury};
 use super::OreAccount;
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
 pub struct Miner {
     /// The authority of this miner account.
     pub authority: Pubkey,
@@ -57,8 +58,30 @@ pub struct Miner {
     /// The miner's dice prediction for the current round (2-12, or 0 for safe mode).
     pub dice_prediction: u8,
 
+    /// Whether checkpoint rolls winnings into the current round (1) instead of
+    /// crediting them to the claimable balance (0).
+    pub auto_redeploy: u8,
+
     /// Padding for alignment.
-    pub _padding: [u8; 7],
+    pub _padding: [u8; 6],
+
+    /// The number of rounds this miner has checkpointed with a deployment.
+    pub rounds_played: u64,
+
+    /// The total amount this miner has deployed across all checkpointed rounds.
+    pub lifetime_deployed: u64,
+
+    /// The total SOL this miner has won on winning squares.
+    pub lifetime_won: u64,
+
+    /// The number of rounds in which this miner shared a motherlode.
+    pub motherlodes_hit: u64,
+
+    /// The largest SOL amount this miner has won in a single round.
+    pub best_round_won: u64,
+
+    /// The round in which the miner won `best_round_won`.
+    pub best_round_id: u64,
 }
 
 impl Miner {
@@ -67,6 +90,16 @@ impl Miner {
     }
 
     pub fn claim_ore(&mut self, clock: &Clock, treasury: &mut Treasury) -> u64 {
+        self.claim_ore_discounted(clock, treasury, 0)
+    }
+
+    /// Claims ORE with the claim fee reduced by `discount_bps`.
+    pub fn claim_ore_discounted(
+        &mut self,
+        clock: &Clock,
+        treasury: &mut Treasury,
+        discount_bps: u64,
+    ) -> u64 {
         self.update_rewards(treasury);
         let refined_ore = self.refined_ore;
         let rewards_ore = self.rewards_ore;
@@ -77,9 +110,10 @@ impl Miner {
         treasury.total_refined -= refined_ore;
         self.last_claim_ore_at = clock.unix_timestamp;
 
-        // Charge a 10% fee and share with miners who haven't claimed yet.
+        // Charge a 10% fee, less any discount, and share with miners who haven't claimed yet.
         if treasury.total_unclaimed > 0 {
-            let fee = rewards_ore / 10;
+            let fee = ((rewards_ore / 10) as u128 * (10000 - discount_bps.min(10000)) as u128
+                / 10000) as u64;
             amount -= fee;
             treasury.miner_rewards_factor += Numeric::from_fraction(fee, treasury.total_unclaimed);
             treasury.total_refined += fee;
@@ -96,6 +130,76 @@ impl Miner {
         amount
     }
 
+    /// Forfeits ORE rewards below `min_claim` and shares them with miners who have
+    /// not claimed, like the claim fee. Returns the amount swept.
+    pub fn sweep_dust_ore(&mut self, treasury: &mut Treasury, min_claim: u64) -> u64 {
+        self.update_rewards(treasury);
+        let dust = self.rewards_ore + self.refined_ore;
+        if dust == 0 || dust >= min_claim {
+            return 0;
+        }
+        treasury.total_unclaimed -= self.rewards_ore;
+        treasury.total_refined -= self.refined_ore;
+        self.lifetime_rewards_ore = self.lifetime_rewards_ore.saturating_sub(dust);
+        self.rewards_ore = 0;
+        self.refined_ore = 0;
+        if treasury.total_unclaimed > 0 {
+            treasury.miner_rewards_factor += Numeric::from_fraction(dust, treasury.total_unclaimed);
+            treasury.total_refined += dust;
+        }
+        dust
+    }
+
+    /// Records lifetime statistics for a checkpointed round.
+    pub fn record_round(&mut self, round_id: u64, won: u64, hit_motherlode: bool) {
+        let deployed = self.deployed.iter().sum::<u64>();
+        if deployed == 0 {
+            return;
+        }
+        self.rounds_played += 1;
+        self.lifetime_deployed += deployed;
+        self.lifetime_won += won;
+        if hit_motherlode {
+            self.motherlodes_hit += 1;
+        }
+        if won > self.best_round_won {
+            self.best_round_won = won;
+            self.best_round_id = round_id;
+        }
+    }
+
+    /// Bitmask of the squares deployed to this round.
+    pub fn deployed_mask(&self) -> u64 {
+        self.deployed
+            .iter()
+            .enumerate()
+            .filter(|(_, &deployed)| deployed > 0)
+            .fold(0, |mask, (i, _)| mask | 1 << i)
+    }
+
+    /// Splits `amount` evenly across the squares deployed to this round, with the
+    /// remainder going one unit at a time to the lowest squares.
+    pub fn redeploy_amounts(&self, amount: u64) -> [u64; BOARD_SIZE] {
+        let mut amounts = [0; BOARD_SIZE];
+        let count = self.deployed.iter().filter(|&&d| d > 0).count() as u64;
+        if count == 0 {
+            return amounts;
+        }
+        let base = amount / count;
+        let mut remainder = amount % count;
+        for (i, &deployed) in self.deployed.iter().enumerate() {
+            if deployed == 0 {
+                continue;
+            }
+            amounts[i] = base;
+            if remainder > 0 {
+                amounts[i] += 1;
+                remainder -= 1;
+            }
+        }
+        amounts
+    }
+
     pub fn update_rewards(&mut self, treasury: &Treasury) {
         // Accumulate rewards, weighted by stake balance.
         if treasury.miner_rewards_factor > self.rewards_factor {
@@ -114,3 +218,78 @@ impl Miner {
 }
 
 account!(OreAccount, Miner);
+account_size!(Miner, 768);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claim_ore_discount_reduces_fee() {
+        let clock = Clock::default();
+        let mut treasury = Treasury::zeroed();
+        treasury.total_unclaimed = 2_000;
+        let mut miner = Miner::zeroed();
+        miner.rewards_ore = 1_000;
+        miner.lifetime_rewards_ore = 1_000;
+
+        // Half of the 100 fee is waived.
+        assert_eq!(miner.claim_ore_discounted(&clock, &mut treasury, 5000), 950);
+        assert_eq!(treasury.total_refined, 50);
+        assert_eq!(miner.lifetime_rewards_ore, 950);
+    }
+
+    #[test]
+    fn test_sweep_dust_ore_shares_with_unclaimed() {
+        let mut treasury = Treasury::zeroed();
+        treasury.total_unclaimed = 1_005;
+        let mut miner = Miner::zeroed();
+        miner.rewards_ore = 5;
+        miner.lifetime_rewards_ore = 5;
+
+        // Balances at or above the minimum are not dust.
+        assert_eq!(miner.sweep_dust_ore(&mut treasury, 5), 0);
+        assert_eq!(miner.rewards_ore, 5);
+
+        assert_eq!(miner.sweep_dust_ore(&mut treasury, 10), 5);
+        assert_eq!(miner.rewards_ore, 0);
+        assert_eq!(miner.lifetime_rewards_ore, 0);
+        assert_eq!(treasury.total_unclaimed, 1_000);
+        assert_eq!(treasury.total_refined, 5);
+        assert!(treasury.miner_rewards_factor > Numeric::ZERO);
+    }
+
+    #[test]
+    fn test_record_round_tracks_best_round() {
+        let mut miner = Miner::zeroed();
+        miner.record_round(1, 0, false);
+        assert_eq!(miner.rounds_played, 0);
+
+        miner.deployed[4] = 100;
+        miner.record_round(2, 0, false);
+        miner.record_round(3, 500, true);
+        miner.record_round(4, 200, false);
+        assert_eq!(miner.rounds_played, 3);
+        assert_eq!(miner.lifetime_deployed, 300);
+        assert_eq!(miner.lifetime_won, 700);
+        assert_eq!(miner.motherlodes_hit, 1);
+        assert_eq!((miner.best_round_won, miner.best_round_id), (500, 3));
+    }
+
+    #[test]
+    fn test_redeploy_amounts_follow_deployed_squares() {
+        let mut miner = Miner::zeroed();
+        miner.deployed[3] = 10;
+        miner.deployed[20] = 50;
+        miner.deployed[35] = 1;
+
+        let amounts = miner.redeploy_amounts(1_000);
+        assert_eq!(amounts.iter().sum::<u64>(), 1_000);
+        assert_eq!(amounts[3], 334);
+        assert_eq!(amounts[20], 333);
+        assert_eq!(amounts[35], 333);
+        assert_eq!(amounts[0], 0);
+
+        assert_eq!(Miner::zeroed().redeploy_amounts(1_000), [0; BOARD_SIZE]);
+    }
+}
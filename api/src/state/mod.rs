@@ -1,22 +1,86 @@
+/// Declares `SIZE`, an account's length in bytes including the 8-byte discriminator
+/// header, and checks it against the layout at compile time. Changing a layout means
+/// updating its size here, so reallocs and migrations never drift from the struct.
+macro_rules! account_size {
+    ($name:ident, $size:expr) => {
+        impl $name {
+            pub const SIZE: usize = $size;
+        }
+
+        const _: () = assert!(
+            $name::SIZE == 8 + std::mem::size_of::<$name>(),
+            concat!(stringify!($name), " layout changed; update its SIZE")
+        );
+    };
+}
+
+mod admin_multisig;
+mod audit_log;
 mod automation;
 mod board;
 mod config;
 mod craps_game;
 mod craps_position;
+mod craps_side_bets;
+mod dca_order;
+mod exchange_pool;
+mod faucet_claim;
+mod game_token_pool;
+mod insurance_fund;
+mod limit_order;
+mod liquidity_position;
+mod lp_lock;
+mod lp_stake;
+mod metrics;
+mod migration;
 mod miner;
+mod param;
+mod pool;
+mod reader;
+mod referrer;
+mod roles;
+mod roll_proof;
 mod round;
+mod round_archive;
+mod session;
 mod stake;
+mod test_roll_oracle;
 mod treasury;
+mod vesting;
 
+pub use admin_multisig::*;
+pub use audit_log::*;
 pub use automation::*;
 pub use board::*;
 pub use config::*;
 pub use craps_game::*;
 pub use craps_position::*;
+pub use craps_side_bets::*;
+pub use dca_order::*;
+pub use exchange_pool::*;
+pub use faucet_claim::*;
+pub use game_token_pool::*;
+pub use insurance_fund::*;
+pub use limit_order::*;
+pub use liquidity_position::*;
+pub use lp_lock::*;
+pub use lp_stake::*;
+pub use metrics::*;
+pub use migration::*;
 pub use miner::*;
+pub use param::*;
+pub use pool::*;
+pub use reader::*;
+pub use referrer::*;
+pub use roles::*;
+pub use roll_proof::*;
 pub use round::*;
+pub use round_archive::*;
+pub use session::*;
 pub use stake::*;
+pub use test_roll_oracle::*;
 pub use treasury::*;
+pub use vesting::*;
 
 use crate::consts::*;
 
@@ -34,6 +98,37 @@ pub enum OreAccount {
     Round = 109,
     CrapsGame = 110,
     CrapsPosition = 111,
+    TestRollOracle = 112,
+    RollProof = 113,
+    CrapsSideBets = 114,
+    Pool = 115,
+    PoolMember = 116,
+    RoundArchive = 117,
+    Vesting = 118,
+    AdminMultisig = 119,
+    Param = 120,
+    AuditLog = 121,
+    InsuranceFund = 122,
+    Roles = 123,
+    FaucetClaim = 124,
+    Metrics = 125,
+    Session = 126,
+    ExchangePool = 127,
+    LiquidityPosition = 128,
+    GameTokenPool = 129,
+    LpStake = 130,
+    Referrer = 131,
+    LimitOrder = 132,
+    DcaOrder = 133,
+    LpLock = 134,
+}
+
+pub fn admin_multisig_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ADMIN_MULTISIG], &crate::ID)
+}
+
+pub fn audit_log_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AUDIT_LOG], &crate::ID)
 }
 
 pub fn automation_pda(authority: Pubkey) -> (Pubkey, u8) {
@@ -48,26 +143,129 @@ pub fn config_pda() -> (Pubkey, u8) {
     Pubkey::find_program_address(&[CONFIG], &crate::ID)
 }
 
+pub fn exchange_pool_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EXCHANGE_POOL], &crate::ID)
+}
+
+pub fn exchange_lp_mint_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EXCHANGE_LP_MINT], &crate::ID)
+}
+
+pub fn exchange_sol_vault_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EXCHANGE_SOL_VAULT], &crate::ID)
+}
+
+pub fn exchange_rng_vault_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EXCHANGE_RNG_VAULT], &crate::ID)
+}
+
+pub fn faucet_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FAUCET], &crate::ID)
+}
+
+pub fn faucet_claim_pda(authority: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FAUCET_CLAIM, &authority.to_bytes()], &crate::ID)
+}
+
+pub fn insurance_fund_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[INSURANCE_FUND], &crate::ID)
+}
+
+pub fn metrics_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[METRICS], &crate::ID)
+}
+
 pub fn miner_pda(authority: Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[MINER, &authority.to_bytes()], &crate::ID)
 }
 
+pub fn param_pda(key: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PARAM, &key.to_le_bytes()], &crate::ID)
+}
+
+pub fn pool_pda(authority: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL, &authority.to_bytes()], &crate::ID)
+}
+
+pub fn pool_member_pda(pool: Pubkey, authority: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_MEMBER, &pool.to_bytes(), &authority.to_bytes()], &crate::ID)
+}
+
+pub fn roles_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ROLES], &crate::ID)
+}
+
 pub fn round_pda(id: u64) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[ROUND, &id.to_le_bytes()], &crate::ID)
 }
 
+pub fn round_archive_pda(id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ROUND_ARCHIVE, &id.to_le_bytes()], &crate::ID)
+}
+
+/// The roll proof slot a round's proof is written to.
+pub fn roll_proof_pda(round_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ROLL_PROOF, &(round_id % ROLL_PROOF_SLOTS).to_le_bytes()], &crate::ID)
+}
+
+pub fn session_pda(authority: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SESSION, &authority.to_bytes()], &crate::ID)
+}
+
 pub fn stake_pda(authority: Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[STAKE, &authority.to_bytes()], &crate::ID)
 }
 
+pub fn stake_receipt_mint_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKE_RECEIPT_MINT], &crate::ID)
+}
+
 pub fn treasury_pda() -> (Pubkey, u8) {
     Pubkey::find_program_address(&[TREASURY], &crate::ID)
 }
 
+pub fn vesting_pda(authority: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VESTING, &authority.to_bytes()], &crate::ID)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
 pub fn treasury_tokens_address() -> Pubkey {
     spl_associated_token_account::get_associated_token_address(&TREASURY_ADDRESS, &MINT_ADDRESS)
 }
 
+/// The treasury's wrapped SOL token account, which holds SOL awaiting a buyback.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn treasury_sol_address() -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(&TREASURY_ADDRESS, &SOL_MINT)
+}
+
+/// The RNG token account of a round, which holds the RNG deployed to it.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn round_rng_tokens_address(id: u64) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(
+        &round_pda(id).0,
+        &RNG_MINT_ADDRESS,
+    )
+}
+
+/// The ORE token account of a stake account.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn stake_tokens_address(authority: Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(
+        &stake_pda(authority).0,
+        &MINT_ADDRESS,
+    )
+}
+
+/// The RNG token account of a stake account.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn stake_rng_tokens_address(authority: Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(
+        &stake_pda(authority).0,
+        &RNG_MINT_ADDRESS,
+    )
+}
+
 pub fn craps_game_pda() -> (Pubkey, u8) {
     Pubkey::find_program_address(&[CRAPS_GAME], &crate::ID)
 }
@@ -76,7 +274,94 @@ pub fn craps_position_pda(authority: Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[CRAPS_POSITION, &authority.to_bytes()], &crate::ID)
 }
 
+pub fn craps_side_bets_pda(authority: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CRAPS_SIDE_BETS, &authority.to_bytes()], &crate::ID)
+}
+
 /// The PDA for the craps vault token account (holds CRAP tokens for the house).
 pub fn craps_vault_pda() -> (Pubkey, u8) {
     Pubkey::find_program_address(&[CRAPS_VAULT], &crate::ID)
 }
+
+/// The craps vault's CRAP token account, which holds the house bankroll and player stakes.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn craps_vault_tokens_address() -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(
+        &craps_vault_pda().0,
+        &CRAP_MINT_ADDRESS,
+    )
+}
+
+/// The PDA for the test roll oracle (only used by localnet/devnet builds).
+pub fn test_roll_oracle_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TEST_ROLL_ORACLE], &crate::ID)
+}
+
+/// Every program address tied to one user, so clients don't re-derive seeds by hand.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UserAddresses {
+    pub authority: Pubkey,
+    pub automation: Pubkey,
+    pub craps_position: Pubkey,
+    pub craps_side_bets: Pubkey,
+    pub miner: Pubkey,
+    pub pool: Pubkey,
+    pub session: Pubkey,
+    pub stake: Pubkey,
+    pub stake_tokens: Pubkey,
+    pub stake_rng_tokens: Pubkey,
+    pub vesting: Pubkey,
+
+    // The user's own token accounts.
+    pub ore_tokens: Pubkey,
+    pub rng_tokens: Pubkey,
+    pub crap_tokens: Pubkey,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl UserAddresses {
+    pub fn new(authority: Pubkey) -> Self {
+        let ata = |mint: &Pubkey| {
+            spl_associated_token_account::get_associated_token_address(&authority, mint)
+        };
+        Self {
+            authority,
+            automation: automation_pda(authority).0,
+            craps_position: craps_position_pda(authority).0,
+            craps_side_bets: craps_side_bets_pda(authority).0,
+            miner: miner_pda(authority).0,
+            pool: pool_pda(authority).0,
+            session: session_pda(authority).0,
+            stake: stake_pda(authority).0,
+            stake_tokens: stake_tokens_address(authority),
+            stake_rng_tokens: stake_rng_tokens_address(authority),
+            vesting: vesting_pda(authority).0,
+            ore_tokens: ata(&MINT_ADDRESS),
+            rng_tokens: ata(&RNG_MINT_ADDRESS),
+            crap_tokens: ata(&CRAP_MINT_ADDRESS),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_addresses() {
+        let authority = Pubkey::new_unique();
+        let addresses = UserAddresses::new(authority);
+        assert_eq!(addresses.miner, miner_pda(authority).0);
+        assert_eq!(addresses.session, session_pda(authority).0);
+        assert_eq!(
+            addresses.stake_tokens,
+            spl_associated_token_account::get_associated_token_address(
+                &addresses.stake,
+                &MINT_ADDRESS
+            )
+        );
+        assert_ne!(addresses.stake_tokens, addresses.stake_rng_tokens);
+        assert_ne!(addresses.miner, UserAddresses::new(Pubkey::new_unique()).miner);
+    }
+}
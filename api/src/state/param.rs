@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use steel::*;
+
+use crate::consts::{
+    CLAIM_CRANK_TIP_ORE, CLAIM_CRANK_TIP_SOL, MAX_ROLLS_PER_ROUND, ONE_ORE, ROLLS_PER_ROUND,
+    ROUND_CLOSE_BOUNTY, ROUND_RESET_BOUNTY,
+};
+use crate::state::param_pda;
+
+use super::OreAccount;
+
+/// Param value kinds.
+pub const PARAM_KIND_U64: u8 = 0;
+pub const PARAM_KIND_BPS: u8 = 1;
+pub const PARAM_KIND_PUBKEY: u8 = 2;
+
+/// Param keys.
+pub const PARAM_ROUND_CLOSE_BOUNTY: u64 = 1;
+pub const PARAM_CLAIM_CRANK_TIP_SOL: u64 = 2;
+pub const PARAM_CLAIM_CRANK_TIP_ORE: u64 = 3;
+pub const PARAM_ROUND_RESET_BOUNTY: u64 = 4;
+pub const PARAM_ROLLS_PER_ROUND: u64 = 5;
+
+/// The type and bounds of a tunable parameter. Numeric params read as `default`
+/// until the admin sets them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParamSchema {
+    pub key: u64,
+    pub kind: u8,
+    pub min: u64,
+    pub max: u64,
+    pub default: u64,
+}
+
+/// Every parameter the admin can set with `SetParam`.
+pub const PARAM_SCHEMAS: &[ParamSchema] = &[
+    ParamSchema {
+        key: PARAM_ROUND_CLOSE_BOUNTY,
+        kind: PARAM_KIND_U64,
+        min: 0,
+        max: 1_000_000,
+        default: ROUND_CLOSE_BOUNTY,
+    },
+    ParamSchema {
+        key: PARAM_CLAIM_CRANK_TIP_SOL,
+        kind: PARAM_KIND_U64,
+        min: 0,
+        max: 100_000,
+        default: CLAIM_CRANK_TIP_SOL,
+    },
+    ParamSchema {
+        key: PARAM_CLAIM_CRANK_TIP_ORE,
+        kind: PARAM_KIND_U64,
+        min: 0,
+        max: ONE_ORE / 1_000,
+        default: CLAIM_CRANK_TIP_ORE,
+    },
+    ParamSchema {
+        key: PARAM_ROUND_RESET_BOUNTY,
+        kind: PARAM_KIND_U64,
+        min: 0,
+        max: 10_000_000,
+        default: ROUND_RESET_BOUNTY,
+    },
+    ParamSchema {
+        key: PARAM_ROLLS_PER_ROUND,
+        kind: PARAM_KIND_U64,
+        min: 1,
+        max: MAX_ROLLS_PER_ROUND as u64,
+        default: ROLLS_PER_ROUND as u64,
+    },
+];
+
+/// Looks up the schema for `key`.
+pub fn param_schema(key: u64) -> Option<&'static ParamSchema> {
+    PARAM_SCHEMAS.iter().find(|schema| schema.key == key)
+}
+
+impl ParamSchema {
+    /// Whether `value` is a valid setting for this parameter.
+    pub fn validate(&self, value: &[u8; 32]) -> bool {
+        match self.kind {
+            PARAM_KIND_U64 | PARAM_KIND_BPS => {
+                let (number, rest) = value.split_at(8);
+                let number = u64::from_le_bytes(number.try_into().unwrap());
+                let max = if self.kind == PARAM_KIND_BPS {
+                    self.max.min(10_000)
+                } else {
+                    self.max
+                };
+                rest.iter().all(|b| *b == 0) && number >= self.min && number <= max
+            }
+            PARAM_KIND_PUBKEY => true,
+            _ => false,
+        }
+    }
+}
+
+/// A tunable parameter set by the admin. Numeric kinds store a little-endian u64 in
+/// the first 8 bytes of `value`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct Param {
+    /// The parameter key (`PARAM_*`).
+    pub key: u64,
+
+    /// The value kind (`PARAM_KIND_*`).
+    pub kind: u8,
+
+    /// Padding for alignment.
+    pub _padding: [u8; 7],
+
+    /// The encoded value.
+    pub value: [u8; 32],
+}
+
+impl Param {
+    pub fn pda(key: u64) -> (Pubkey, u8) {
+        param_pda(key)
+    }
+
+    /// The value of a numeric parameter.
+    pub fn as_u64(&self) -> u64 {
+        u64::from_le_bytes(self.value[..8].try_into().unwrap())
+    }
+
+    /// The value of a pubkey parameter.
+    pub fn as_pubkey(&self) -> Pubkey {
+        Pubkey::new_from_array(self.value)
+    }
+}
+
+account!(OreAccount, Param);
+account_size!(Param, 56);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(value: u64) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        bytes[..8].copy_from_slice(&value.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_schema_enforces_bounds() {
+        let schema = param_schema(PARAM_CLAIM_CRANK_TIP_SOL).unwrap();
+        assert!(schema.validate(&encode(0)));
+        assert!(schema.validate(&encode(schema.max)));
+        assert!(!schema.validate(&encode(schema.max + 1)));
+
+        // Numeric values must not carry trailing bytes.
+        let mut value = encode(1);
+        value[31] = 1;
+        assert!(!schema.validate(&value));
+
+        let bps = ParamSchema {
+            key: 0,
+            kind: PARAM_KIND_BPS,
+            min: 0,
+            max: u64::MAX,
+            default: 0,
+        };
+        assert!(bps.validate(&encode(10_000)));
+        assert!(!bps.validate(&encode(10_001)));
+        assert!(param_schema(0).is_none());
+    }
+}
@@ -0,0 +1,226 @@
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use steel::*;
+
+use crate::state::{pool_member_pda, pool_pda};
+
+use super::OreAccount;
+
+/// A mining pool. Members deposit RNG for shares, the pool authority deploys the
+/// pooled RNG through the pool's own miner account, and round winnings are
+/// distributed to members pro-rata by shares.
+///
+/// Shares are priced on the undeployed balance alone. A deploy spends RNG from the
+/// balance for good: rounds pay a winning deployment back in SOL along with its
+/// winnings, so the returned principal is harvested and shared as rewards instead
+/// of being credited back to the balance.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct Pool {
+    /// The authority that chooses where the pool deploys.
+    pub authority: Pubkey,
+
+    /// The total shares held by members.
+    pub total_shares: u64,
+
+    /// The undeployed RNG held in the pool's token account, backing the shares.
+    /// Deploys reduce it and harvests never restore it.
+    pub balance: u64,
+
+    /// Cumulative SOL distributed per share.
+    pub sol_rewards_factor: Numeric,
+
+    /// Cumulative ORE distributed per share.
+    pub ore_rewards_factor: Numeric,
+
+    /// The round the pool last deployed to.
+    pub deployed_round_id: u64,
+
+    /// Whether the pool has deployed to a round whose winnings are not yet harvested (1)
+    /// or not (0). Members cannot join or leave while a harvest is pending.
+    pub pending_harvest: u64,
+
+    /// The total RNG the pool has deployed over its lifetime.
+    pub lifetime_deployed: u64,
+
+    /// The total SOL the pool has harvested over its lifetime.
+    pub lifetime_rewards_sol: u64,
+
+    /// The total ORE the pool has harvested over its lifetime.
+    pub lifetime_rewards_ore: u64,
+}
+
+/// A member's shares in a mining pool.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct PoolMember {
+    /// The authority of this member account.
+    pub authority: Pubkey,
+
+    /// The pool this member belongs to.
+    pub pool: Pubkey,
+
+    /// The member's shares of the pool.
+    pub shares: u64,
+
+    /// The pool's SOL rewards factor last time rewards were updated on this member.
+    pub sol_rewards_factor: Numeric,
+
+    /// The pool's ORE rewards factor last time rewards were updated on this member.
+    pub ore_rewards_factor: Numeric,
+
+    /// The amount of SOL this member can claim.
+    pub rewards_sol: u64,
+
+    /// The amount of ORE this member can claim.
+    pub rewards_ore: u64,
+}
+
+impl Pool {
+    pub fn pda(&self) -> (Pubkey, u8) {
+        pool_pda(self.authority)
+    }
+
+    /// Adds RNG to the pool and credits the member with shares at the current
+    /// share price. Returns the shares issued, or None if the pool's balance has
+    /// been fully deployed and lost, so shares have no price.
+    pub fn deposit(&mut self, member: &mut PoolMember, amount: u64) -> Option<u64> {
+        let shares = if self.total_shares == 0 {
+            amount
+        } else if self.balance == 0 {
+            return None;
+        } else {
+            ((amount as u128 * self.total_shares as u128) / self.balance as u128) as u64
+        };
+        member.update_rewards(self);
+        member.shares += shares;
+        self.total_shares += shares;
+        self.balance += amount;
+        Some(shares)
+    }
+
+    /// Burns the member's shares and returns their portion of the pool's balance.
+    pub fn withdraw(&mut self, member: &mut PoolMember, shares: u64) -> u64 {
+        let shares = shares.min(member.shares);
+        if shares == 0 {
+            return 0;
+        }
+        let amount = ((shares as u128 * self.balance as u128) / self.total_shares as u128) as u64;
+        member.update_rewards(self);
+        member.shares -= shares;
+        self.total_shares -= shares;
+        self.balance -= amount;
+        amount
+    }
+
+    /// Distributes harvested winnings, including any principal the round paid back,
+    /// to all shares.
+    pub fn distribute(&mut self, amount_sol: u64, amount_ore: u64) {
+        if self.total_shares == 0 {
+            return;
+        }
+        if amount_sol > 0 {
+            self.sol_rewards_factor += Numeric::from_fraction(amount_sol, self.total_shares);
+        }
+        if amount_ore > 0 {
+            self.ore_rewards_factor += Numeric::from_fraction(amount_ore, self.total_shares);
+        }
+        self.lifetime_rewards_sol += amount_sol;
+        self.lifetime_rewards_ore += amount_ore;
+    }
+}
+
+impl PoolMember {
+    pub fn pda(&self) -> (Pubkey, u8) {
+        pool_member_pda(self.pool, self.authority)
+    }
+
+    /// Accrues the member's share of winnings distributed since the last update.
+    pub fn update_rewards(&mut self, pool: &Pool) {
+        if pool.sol_rewards_factor > self.sol_rewards_factor {
+            let accumulated = pool.sol_rewards_factor - self.sol_rewards_factor;
+            self.rewards_sol += (accumulated * Numeric::from_u64(self.shares)).to_u64();
+        }
+        if pool.ore_rewards_factor > self.ore_rewards_factor {
+            let accumulated = pool.ore_rewards_factor - self.ore_rewards_factor;
+            self.rewards_ore += (accumulated * Numeric::from_u64(self.shares)).to_u64();
+        }
+        self.sol_rewards_factor = pool.sol_rewards_factor;
+        self.ore_rewards_factor = pool.ore_rewards_factor;
+    }
+}
+
+account!(OreAccount, Pool);
+account_size!(Pool, 128);
+account!(OreAccount, PoolMember);
+account_size!(PoolMember, 128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_shares_track_balance() {
+        let mut pool = Pool::zeroed();
+        let mut alice = PoolMember::zeroed();
+        let mut bob = PoolMember::zeroed();
+
+        assert_eq!(pool.deposit(&mut alice, 1_000), Some(1_000));
+
+        // The pool loses half its balance to a deploy, so shares are cheaper.
+        pool.balance = 500;
+        assert_eq!(pool.deposit(&mut bob, 500), Some(1_000));
+        assert_eq!(pool.total_shares, 2_000);
+
+        assert_eq!(pool.withdraw(&mut alice, u64::MAX), 500);
+        assert_eq!(pool.withdraw(&mut bob, 1_000), 500);
+        assert_eq!(pool.total_shares, 0);
+        assert_eq!(pool.balance, 0);
+    }
+
+    #[test]
+    fn test_pool_rejects_deposit_when_depleted() {
+        let mut pool = Pool::zeroed();
+        let mut alice = PoolMember::zeroed();
+        pool.deposit(&mut alice, 1_000);
+        pool.balance = 0;
+        assert_eq!(pool.deposit(&mut PoolMember::zeroed(), 1_000), None);
+    }
+
+    #[test]
+    fn test_deployed_principal_returns_as_rewards() {
+        let mut pool = Pool::zeroed();
+        let mut alice = PoolMember::zeroed();
+        pool.deposit(&mut alice, 1_024);
+
+        // 512 is deployed and the round pays 507 back in SOL, after its 1% fee.
+        pool.balance -= 512;
+        pool.distribute(507, 0);
+
+        // Alice leaves with the undeployed RNG and keeps the returned principal as rewards.
+        assert_eq!(pool.withdraw(&mut alice, u64::MAX), 512);
+        assert_eq!(alice.rewards_sol, 507);
+        assert_eq!(pool.total_shares, 0);
+    }
+
+    #[test]
+    fn test_pool_distributes_pro_rata() {
+        let mut pool = Pool::zeroed();
+        let mut alice = PoolMember::zeroed();
+        let mut bob = PoolMember::zeroed();
+        pool.deposit(&mut alice, 256);
+        pool.deposit(&mut bob, 768);
+
+        pool.distribute(1_024, 2_048);
+        alice.update_rewards(&pool);
+        bob.update_rewards(&pool);
+        assert_eq!((alice.rewards_sol, alice.rewards_ore), (256, 512));
+        assert_eq!((bob.rewards_sol, bob.rewards_ore), (768, 1_536));
+
+        // A later joiner earns nothing from past distributions.
+        let mut carol = PoolMember::zeroed();
+        pool.deposit(&mut carol, 1_024);
+        carol.update_rewards(&pool);
+        assert_eq!(carol.rewards_sol, 0);
+    }
+}
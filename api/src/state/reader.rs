@@ -0,0 +1,105 @@
+use steel::*;
+
+/// Checks that raw account data holds a `T` owned by this program: the account is
+/// initialized, owned by the program, carries `T`'s discriminator, and is at least as
+/// long as `T`. Longer data is accepted, since layouts only grow by appending fields.
+pub fn check_account_data<T: Discriminator + Pod>(
+    owner: &Pubkey,
+    data: &[u8],
+) -> Result<(), ProgramError> {
+    if data.is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    if *owner != crate::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if data[0] != T::discriminator() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if data.len() < 8 + std::mem::size_of::<T>() {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    Ok(())
+}
+
+/// Checked reads of program accounts from raw account data, for off-chain tools that
+/// fetch accounts over RPC. Unlike `try_from_bytes`, these verify the owner and accept
+/// accounts that have grown past the layout being read.
+pub trait AccountReader: Discriminator + Pod {
+    /// Borrows the account. The data must be aligned for `Self`.
+    fn read<'a>(owner: &Pubkey, data: &'a [u8]) -> Result<&'a Self, ProgramError> {
+        check_account_data::<Self>(owner, data)?;
+        bytemuck::try_from_bytes(&data[8..8 + std::mem::size_of::<Self>()])
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Copies the account out of data with any alignment.
+    fn read_unaligned(owner: &Pubkey, data: &[u8]) -> Result<Self, ProgramError> {
+        check_account_data::<Self>(owner, data)?;
+        bytemuck::try_pod_read_unaligned(&data[8..8 + std::mem::size_of::<Self>()])
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+}
+
+impl<T: Discriminator + Pod> AccountReader for T {}
+
+/// Checked loading of program accounts in processors.
+#[cfg(not(feature = "wasm"))]
+pub trait AccountInfoReader {
+    /// Loads a program account for writing, after `check_account_data`.
+    fn as_checked_account_mut<T: Discriminator + Pod>(&self) -> Result<&mut T, ProgramError>;
+}
+
+#[cfg(not(feature = "wasm"))]
+impl AccountInfoReader for AccountInfo<'_> {
+    fn as_checked_account_mut<T: Discriminator + Pod>(&self) -> Result<&mut T, ProgramError> {
+        check_account_data::<T>(self.owner, &self.try_borrow_data()?)?;
+        self.as_account_mut::<T>(&crate::ID)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{Board, Treasury};
+
+    fn board_data(board: Board) -> Vec<u8> {
+        let mut data = vec![0u8; 8];
+        data[0] = Board::discriminator();
+        data.extend_from_slice(bytemuck::bytes_of(&board));
+        data
+    }
+
+    #[test]
+    fn test_account_reader() {
+        let board = Board {
+            round_id: 7,
+            start_slot: 100,
+            end_slot: 250,
+        };
+        let data = board_data(board);
+        assert_eq!(Board::read_unaligned(&crate::ID, &data), Ok(board));
+
+        // Accounts grown past the layout still read.
+        let mut grown = data.clone();
+        grown.extend_from_slice(&[0; 16]);
+        assert_eq!(Board::read_unaligned(&crate::ID, &grown), Ok(board));
+
+        assert_eq!(
+            Board::read_unaligned(&Pubkey::new_unique(), &data),
+            Err(ProgramError::IncorrectProgramId)
+        );
+        assert_eq!(
+            Treasury::read_unaligned(&crate::ID, &data),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            Board::read_unaligned(&crate::ID, &data[..data.len() - 1]),
+            Err(ProgramError::AccountDataTooSmall)
+        );
+        assert_eq!(
+            Board::read_unaligned(&crate::ID, &[]),
+            Err(ProgramError::UninitializedAccount)
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use bytemuck::{Pod, Zeroable};
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use solana_program::pubkey::Pubkey;
+use steel::*;
+
+use crate::consts::REFERRER;
+
+use super::OreAccount;
+
+/// Referral fees earned by a swap referrer.
+///
+/// Swaps that pass a registered referrer credit it with a share of the protocol
+/// fee; the referrer claims the accrued SOL and RNG from the pool vaults.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct Referrer {
+    /// The referrer's authority (wallet).
+    pub authority: Pubkey,
+
+    /// SOL fees ready to claim.
+    pub pending_sol: u64,
+
+    /// RNG fees ready to claim.
+    pub pending_rng: u64,
+
+    /// Total SOL fees earned over the account's lifetime.
+    pub lifetime_sol: u64,
+
+    /// Total RNG fees earned over the account's lifetime.
+    pub lifetime_rng: u64,
+
+    /// Number of swaps referred.
+    pub total_referred_swaps: u64,
+
+    /// Timestamp of last claim.
+    pub last_claim_at: i64,
+}
+
+impl Referrer {
+    pub fn pda(&self) -> (Pubkey, u8) {
+        referrer_pda(self.authority)
+    }
+
+    /// Credit a referral fee. `sol` selects the fee token.
+    pub fn credit(&mut self, amount: u64, sol: bool) {
+        if sol {
+            self.pending_sol += amount;
+            self.lifetime_sol += amount;
+        } else {
+            self.pending_rng += amount;
+            self.lifetime_rng += amount;
+        }
+        self.total_referred_swaps += 1;
+    }
+
+    /// Claim all pending fees. Returns (sol, rng).
+    pub fn claim(&mut self, now: i64) -> (u64, u64) {
+        let claimed = (self.pending_sol, self.pending_rng);
+        self.pending_sol = 0;
+        self.pending_rng = 0;
+        self.last_claim_at = now;
+        claimed
+    }
+}
+
+/// Derive the referrer PDA for an authority.
+pub fn referrer_pda(authority: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REFERRER, &authority.to_bytes()], &crate::ID)
+}
+
+account!(OreAccount, Referrer);
+account_size!(Referrer, 88);
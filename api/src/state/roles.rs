@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use steel::*;
+
+use crate::state::roles_pda;
+
+use super::OreAccount;
+
+/// Maximum number of keys the roles account can hold.
+pub const MAX_ROLE_MEMBERS: usize = 16;
+
+/// Role bits. The config admin implicitly holds every role, and `ROLE_ADMIN` cannot
+/// be granted; the admin changes only through `SetAdmin` and `AcceptAdmin`.
+pub const ROLE_ADMIN: u64 = 1 << 0;
+/// Runs the round lifecycle: starting rounds and opening entropy vars.
+pub const ROLE_OPERATOR: u64 = 1 << 1;
+/// Cranks permissionless-by-policy maintenance, such as resetting rounds.
+pub const ROLE_CRANKER: u64 = 1 << 2;
+/// Can pause modules, like the config guardian.
+pub const ROLE_GUARDIAN: u64 = 1 << 3;
+
+/// Roles that can be granted through `SetRole`.
+pub const GRANTABLE_ROLES: u64 = ROLE_OPERATOR | ROLE_CRANKER | ROLE_GUARDIAN;
+
+/// Maps operational keys to the roles they hold, so they don't need full admin power.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct Roles {
+    /// The keys holding roles. Only the first `num_members` are used.
+    pub members: [Pubkey; MAX_ROLE_MEMBERS],
+
+    /// The role bits (`ROLE_*`) held by the key at the same index.
+    pub roles: [u64; MAX_ROLE_MEMBERS],
+
+    /// The number of keys holding roles.
+    pub num_members: u64,
+}
+
+impl Roles {
+    pub fn pda() -> (Pubkey, u8) {
+        roles_pda()
+    }
+
+    /// The role bits held by `key`.
+    pub fn roles_of(&self, key: &Pubkey) -> u64 {
+        let len = (self.num_members as usize).min(MAX_ROLE_MEMBERS);
+        self.members[..len]
+            .iter()
+            .position(|member| member == key)
+            .map_or(0, |i| self.roles[i])
+    }
+
+    /// Whether `key` holds any of the `role` bits.
+    pub fn has_role(&self, key: &Pubkey, role: u64) -> bool {
+        self.roles_of(key) & role != 0
+    }
+
+    /// Replaces the roles held by `key`, removing it when `roles` is zero.
+    /// Returns the previous roles, or `None` if the account is full.
+    pub fn set_roles(&mut self, key: Pubkey, roles: u64) -> Option<u64> {
+        let len = (self.num_members as usize).min(MAX_ROLE_MEMBERS);
+        match self.members[..len].iter().position(|member| *member == key) {
+            Some(i) => {
+                let old_roles = self.roles[i];
+                if roles == 0 {
+                    // Swap the last member into the freed slot.
+                    self.members[i] = self.members[len - 1];
+                    self.roles[i] = self.roles[len - 1];
+                    self.members[len - 1] = Pubkey::default();
+                    self.roles[len - 1] = 0;
+                    self.num_members = len as u64 - 1;
+                } else {
+                    self.roles[i] = roles;
+                }
+                Some(old_roles)
+            }
+            None if roles == 0 => Some(0),
+            None if len == MAX_ROLE_MEMBERS => None,
+            None => {
+                self.members[len] = key;
+                self.roles[len] = roles;
+                self.num_members = len as u64 + 1;
+                Some(0)
+            }
+        }
+    }
+}
+
+account!(OreAccount, Roles);
+account_size!(Roles, 656);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_roles() {
+        let mut roles = Roles::zeroed();
+        let operator = Pubkey::new_unique();
+        let cranker = Pubkey::new_unique();
+        assert_eq!(roles.set_roles(operator, ROLE_OPERATOR), Some(0));
+        assert_eq!(roles.set_roles(cranker, ROLE_CRANKER), Some(0));
+        assert!(roles.has_role(&operator, ROLE_OPERATOR));
+        assert!(!roles.has_role(&operator, ROLE_CRANKER));
+
+        // Removing a member keeps the others.
+        assert_eq!(roles.set_roles(operator, 0), Some(ROLE_OPERATOR));
+        assert_eq!(roles.num_members, 1);
+        assert!(!roles.has_role(&operator, ROLE_OPERATOR));
+        assert!(roles.has_role(&cranker, ROLE_CRANKER));
+
+        // New members are rejected once full.
+        for _ in 1..MAX_ROLE_MEMBERS {
+            assert!(roles.set_roles(Pubkey::new_unique(), ROLE_GUARDIAN).is_some());
+        }
+        assert!(roles.set_roles(Pubkey::new_unique(), ROLE_GUARDIAN).is_none());
+        assert_eq!(roles.set_roles(cranker, ROLE_CRANKER | ROLE_GUARDIAN), Some(ROLE_CRANKER));
+    }
+}
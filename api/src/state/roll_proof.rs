@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use steel::*;
+
+use crate::state::{die_domain_hash, roll_proof_pda, Round, DIE1_DOMAIN, DIE2_DOMAIN};
+
+use super::OreAccount;
+
+/// RollProof is a compact record of how a round's dice result was derived. Reset writes
+/// it to one of `ROLL_PROOF_SLOTS` accounts, reusing each slot once its proof is that many
+/// rounds old, so third parties can audit recent fairness without replaying ledger state
+/// or keeping closed Round accounts, and the rent held by proofs stays bounded.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct RollProof {
+    /// The round this proof belongs to.
+    pub round_id: u64,
+
+    /// The slot hash (finalized entropy value) the roll was derived from.
+    pub slot_hash: [u8; 32],
+
+    /// keccak(slot_hash || 0), the first hash die 1 was sampled from.
+    pub die1_hash: [u8; 32],
+
+    /// keccak(slot_hash || 1), the first hash die 2 was sampled from.
+    pub die2_hash: [u8; 32],
+
+    /// The dice results [die1, die2].
+    pub dice_results: [u8; 2],
+
+    /// The sum of the dice (2-12).
+    pub dice_sum: u8,
+
+    /// The winning square (0-35) derived from the dice.
+    pub winning_square: u8,
+
+    /// Whether the roll was injected by the test roll oracle (1) rather than derived (0).
+    pub is_injected: u8,
+
+    /// The number of craps sub-rolls in the round. Later sub-rolls use domains (2k, 2k + 1).
+    pub roll_count: u8,
+
+    /// Padding for alignment.
+    pub _padding: [u8; 2],
+}
+
+impl RollProof {
+    pub fn pda(&self) -> (Pubkey, u8) {
+        roll_proof_pda(self.round_id)
+    }
+
+    /// Recomputes the roll from the recorded slot hash and checks every recorded field.
+    /// Injected rolls only check that the dice, sum, and square are consistent.
+    pub fn verify(&self) -> bool {
+        let [die1, die2] = self.dice_results;
+        if !(1..=6).contains(&die1) || !(1..=6).contains(&die2) {
+            return false;
+        }
+        if self.dice_sum != die1 + die2
+            || self.winning_square as usize != (die1 as usize - 1) * 6 + (die2 as usize - 1)
+        {
+            return false;
+        }
+        if self.is_injected == 1 {
+            return true;
+        }
+        if self.die1_hash != die_domain_hash(&self.slot_hash, DIE1_DOMAIN)
+            || self.die2_hash != die_domain_hash(&self.slot_hash, DIE2_DOMAIN)
+        {
+            return false;
+        }
+        let mut round = Round::zeroed();
+        round.slot_hash = self.slot_hash;
+        round.roll_dice() == (die1, die2, self.dice_sum)
+    }
+}
+
+account!(OreAccount, RollProof);
+account_size!(RollProof, 120);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proof_for(slot_hash: [u8; 32]) -> RollProof {
+        let mut round = Round::zeroed();
+        round.slot_hash = slot_hash;
+        let (die1, die2, sum) = round.roll_dice();
+        RollProof {
+            round_id: 1,
+            slot_hash,
+            die1_hash: die_domain_hash(&slot_hash, DIE1_DOMAIN),
+            die2_hash: die_domain_hash(&slot_hash, DIE2_DOMAIN),
+            dice_results: [die1, die2],
+            dice_sum: sum,
+            winning_square: round.winning_square(0) as u8,
+            is_injected: 0,
+            roll_count: 1,
+            _padding: [0; 2],
+        }
+    }
+
+    #[test]
+    fn test_verify_derived_roll() {
+        let proof = proof_for([7; 32]);
+        assert!(proof.verify());
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_roll() {
+        let mut proof = proof_for([7; 32]);
+        proof.slot_hash[0] ^= 1;
+        assert!(!proof.verify());
+
+        let mut proof = proof_for([7; 32]);
+        proof.winning_square = (proof.winning_square + 1) % 36;
+        assert!(!proof.verify());
+    }
+
+    #[test]
+    fn test_proof_slots_are_reused() {
+        assert_eq!(roll_proof_pda(3), roll_proof_pda(3 + crate::consts::ROLL_PROOF_SLOTS));
+        assert_ne!(roll_proof_pda(3), roll_proof_pda(4));
+    }
+
+    #[test]
+    fn test_verify_injected_roll() {
+        let mut proof = RollProof::zeroed();
+        proof.dice_results = [3, 4];
+        proof.dice_sum = 7;
+        proof.winning_square = 15;
+        proof.is_injected = 1;
+        assert!(proof.verify());
+    }
+}
@@ -1,14 +1,15 @@
 use serde::{Deserialize, Serialize};
 use serde_big_array::BigArray;
+use shank::ShankAccount;
 use steel::*;
 
-use crate::consts::BOARD_SIZE;
+use crate::consts::{BOARD_SIZE, MAX_ROLLS_PER_ROUND, SPLIT_ADDRESS};
 use crate::state::round_pda;
 
 use super::OreAccount;
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
 pub struct Round {
     /// The round number.
     pub id: u64,
@@ -54,8 +55,25 @@ pub struct Round {
     /// The sum of the dice roll (2-12).
     pub dice_sum: u8,
 
+    /// The winning square (0-35) recorded at reset. Only valid when dice_sum is non-zero.
+    pub winning_square_index: u8,
+
+    /// The number of craps sub-rolls in the round (fast table mode). Sub-roll 0 is the winning square.
+    pub roll_count: u8,
+
+    /// Percent of the ORE reward paid to the top miner if the round was not split.
+    pub top_miner_share_pct: u8,
+
     /// Padding for alignment.
-    pub _padding: [u8; 5],
+    pub _padding: [u8; 2],
+
+    /// Squares injected for the round's later sub-rolls in test builds, stored as square + 1.
+    /// Zero means the sub-roll is derived from the slot hash. Index 0 is unused, since
+    /// sub-roll 0 is the winning square.
+    pub injected_sub_rolls: [u8; MAX_ROLLS_PER_ROUND as usize],
+
+    /// Padding for alignment.
+    pub _sub_roll_padding: [u8; 4],
 }
 
 impl Round {
@@ -76,21 +94,9 @@ impl Round {
     }
 
     pub fn winning_square(&self, _rng: u64) -> usize {
-        // Use keccak hash for better distribution
-        let hash = solana_program::keccak::hash(&self.slot_hash);
-        let sample = u64::from_le_bytes(hash.to_bytes()[0..8].try_into().unwrap());
-
-        // Rejection sampling to eliminate modulo bias
-        let board_size = BOARD_SIZE as u64;
-        let max_valid = (u64::MAX / board_size) * board_size;
-        if sample < max_valid {
-            (sample % board_size) as usize
-        } else {
-            // Use hash of hash for retry (deterministic)
-            let hash2 = solana_program::keccak::hash(&hash.to_bytes());
-            let sample2 = u64::from_le_bytes(hash2.to_bytes()[0..8].try_into().unwrap());
-            (sample2 % board_size) as usize
-        }
+        // The square is the (die1, die2) cell of the 6x6 grid, so it is uniform over all 36 squares.
+        let (die1, die2, _) = self.roll_dice();
+        (die1 as usize - 1) * 6 + (die2 as usize - 1)
     }
 
     pub fn top_miner_sample(&self, rng: u64, winning_square: usize) -> u64 {
@@ -110,28 +116,88 @@ impl Round {
         total_winnings
     }
 
-    pub fn is_split_reward(&self, rng: u64) -> bool {
-        // One out of four rounds get split rewards.
+    /// Whether the round's ORE reward is split, with `split_bps` / 10000 chance.
+    pub fn is_split_reward(&self, rng: u64, split_bps: u64) -> bool {
         let rng = rng.reverse_bits().to_le_bytes();
-        let r1 = u16::from_le_bytes(rng[0..2].try_into().unwrap());
-        let r2 = u16::from_le_bytes(rng[2..4].try_into().unwrap());
-        let r3 = u16::from_le_bytes(rng[4..6].try_into().unwrap());
-        let r4 = u16::from_le_bytes(rng[6..8].try_into().unwrap());
-        let r = r1 ^ r2 ^ r3 ^ r4;
-        r % 2 == 0
+        let r1 = u32::from_le_bytes(rng[0..4].try_into().unwrap());
+        let r2 = u32::from_le_bytes(rng[4..8].try_into().unwrap());
+        let r = r1 ^ r2;
+        (r as u64 % 10000) < split_bps
+    }
+
+    /// Splits the ORE reward into the top miner's share and the pro-rata share.
+    /// Split rounds have no top miner share.
+    pub fn top_miner_split(&self) -> (u64, u64) {
+        if self.top_miner == SPLIT_ADDRESS {
+            return (0, self.top_miner_reward);
+        }
+        let top_share = self.top_miner_reward * (self.top_miner_share_pct.min(100) as u64) / 100;
+        (top_share, self.top_miner_reward - top_share)
+    }
+
+    /// Whether the round hits the motherlode, with 1 in `odds` chance.
+    pub fn did_hit_motherlode(&self, rng: u64, odds: u64) -> bool {
+        rng.reverse_bits() % odds.max(1) == 0
     }
 
-    pub fn did_hit_motherlode(&self, rng: u64) -> bool {
-        rng.reverse_bits() % 625 == 0
+    /// Rolls two dice from the slot hash and returns (die1, die2, sum).
+    /// Each die is sampled from its own hash domain so the outcomes are independent.
+    pub fn roll_dice(&self) -> (u8, u8, u8) {
+        self.roll_dice_at(0)
     }
 
-    /// Rolls two dice using the RNG and returns (die1, die2, sum).
-    pub fn roll_dice(&self, rng: u64) -> (u8, u8, u8) {
-        // Use different bits of the RNG for each die to ensure independence
-        let die1 = ((rng % 6) + 1) as u8;
-        let die2 = (((rng >> 16) % 6) + 1) as u8;
-        let sum = die1 + die2;
-        (die1, die2, sum)
+    /// Rolls the dice for a sub-roll, using domains (2 * index, 2 * index + 1).
+    /// Sub-roll 0 uses the same domains as `roll_dice`.
+    pub fn roll_dice_at(&self, index: u8) -> (u8, u8, u8) {
+        let die1 = sample_die(&self.slot_hash, DIE1_DOMAIN + 2 * index);
+        let die2 = sample_die(&self.slot_hash, DIE2_DOMAIN + 2 * index);
+        (die1, die2, die1 + die2)
+    }
+
+    /// Records the canonical dice result for the round from the winning square.
+    /// Square index = (die1 - 1) * 6 + (die2 - 1).
+    pub fn set_dice_result(&mut self, winning_square: usize) {
+        let die1 = (winning_square / 6) as u8 + 1;
+        let die2 = (winning_square % 6) as u8 + 1;
+        self.dice_results = [die1, die2];
+        self.dice_sum = die1 + die2;
+        self.winning_square_index = winning_square as u8;
+    }
+
+    /// Returns the winning square recorded at reset, or None if the round has not been rolled.
+    pub fn dice_result_square(&self) -> Option<usize> {
+        if self.dice_sum == 0 {
+            return None;
+        }
+        Some(self.winning_square_index as usize)
+    }
+
+    /// Returns the number of craps sub-rolls in the round, or 0 if the round has not been rolled.
+    /// Rounds recorded before fast table mode have a single roll.
+    pub fn num_rolls(&self) -> u8 {
+        if self.dice_sum == 0 {
+            return 0;
+        }
+        self.roll_count.max(1)
+    }
+
+    /// Returns the winning square of a sub-roll, or None if the sub-roll does not exist.
+    /// Sub-roll 0 is the recorded winning square; later sub-rolls are derived from the slot hash
+    /// unless they were injected.
+    pub fn sub_roll_square(&self, index: u8) -> Option<usize> {
+        if index >= self.num_rolls() {
+            return None;
+        }
+        if index == 0 {
+            return self.dice_result_square();
+        }
+        if let Some(&injected) = self.injected_sub_rolls.get(index as usize) {
+            if injected != 0 {
+                return Some(injected as usize - 1);
+            }
+        }
+        let (die1, die2, _) = self.roll_dice_at(index);
+        Some((die1 as usize - 1) * 6 + (die2 as usize - 1))
     }
 
     /// Calculates the dice payout multiplier for a given prediction.
@@ -195,7 +261,85 @@ impl Round {
     }
 }
 
+/// Named sets of squares that a total deployment can be spread across.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum DeployPreset {
+    /// Every square on the board.
+    AllSquares = 0,
+    /// The six combinations that sum to 7.
+    AllSevens = 1,
+    /// The hard 4, 6, 8 and 10 (2-2, 3-3, 4-4, 5-5).
+    Hardways = 2,
+}
+
+impl DeployPreset {
+    /// Returns whether each square belongs to the preset.
+    /// Square index = (die1 - 1) * 6 + (die2 - 1).
+    pub fn squares(&self) -> [bool; BOARD_SIZE] {
+        let mut squares = [false; BOARD_SIZE];
+        for (i, square) in squares.iter_mut().enumerate() {
+            let die1 = i / 6 + 1;
+            let die2 = i % 6 + 1;
+            *square = match self {
+                DeployPreset::AllSquares => true,
+                DeployPreset::AllSevens => die1 + die2 == 7,
+                DeployPreset::Hardways => die1 == die2 && (2..=5).contains(&die1),
+            };
+        }
+        squares
+    }
+
+    /// Splits `total` evenly across the preset's squares. The remainder goes one unit
+    /// at a time to the lowest squares, so the amounts always sum to `total`.
+    pub fn split(&self, total: u64) -> [u64; BOARD_SIZE] {
+        let squares = self.squares();
+        let count = squares.iter().filter(|&&s| s).count() as u64;
+        let base = total / count;
+        let mut remainder = total % count;
+        let mut amounts = [0; BOARD_SIZE];
+        for (i, &selected) in squares.iter().enumerate() {
+            if !selected {
+                continue;
+            }
+            amounts[i] = base;
+            if remainder > 0 {
+                amounts[i] += 1;
+                remainder -= 1;
+            }
+        }
+        amounts
+    }
+}
+
+/// Domain separators for each die's hash.
+pub const DIE1_DOMAIN: u8 = 0;
+pub const DIE2_DOMAIN: u8 = 1;
+
+/// Returns keccak(slot_hash || domain), the first hash a die is sampled from.
+pub fn die_domain_hash(slot_hash: &[u8; 32], domain: u8) -> [u8; 32] {
+    solana_program::keccak::hashv(&[slot_hash, &[domain]]).to_bytes()
+}
+
+/// Samples a die (1-6) from keccak(slot_hash || domain).
+/// Uses rejection sampling to eliminate modulo bias, walking the 8-byte words of the
+/// hash and re-hashing deterministically if every word is rejected.
+fn sample_die(slot_hash: &[u8; 32], domain: u8) -> u8 {
+    let max_valid = (u64::MAX / 6) * 6;
+    let mut hash = die_domain_hash(slot_hash, domain);
+    loop {
+        for word in hash.chunks_exact(8) {
+            let sample = u64::from_le_bytes(word.try_into().unwrap());
+            if sample < max_valid {
+                return (sample % 6) as u8 + 1;
+            }
+        }
+        hash = solana_program::keccak::hash(&hash).to_bytes();
+    }
+}
+
 account!(OreAccount, Round);
+account_size!(Round, 752);
 
 #[cfg(test)]
 mod tests {
@@ -205,12 +349,60 @@ mod tests {
 
     #[test]
     fn test_rent() {
-        let size_of_round = 8 + std::mem::size_of::<Round>();
-        let required_rent = Rent::default().minimum_balance(size_of_round);
+        let required_rent = Rent::default().minimum_balance(Round::SIZE);
         println!("required_rent: {}", required_rent);
         // Just print, don't fail
     }
 
+    #[test]
+    fn test_split_reward_probability() {
+        let round = Round::zeroed();
+        assert!(round.is_split_reward(12345, 10000));
+        assert!(!round.is_split_reward(12345, 0));
+    }
+
+    #[test]
+    fn test_top_miner_split() {
+        let mut round = Round::zeroed();
+        round.top_miner_reward = 1_000;
+        round.top_miner_share_pct = 30;
+        assert_eq!(round.top_miner_split(), (300, 700));
+        round.top_miner = SPLIT_ADDRESS;
+        assert_eq!(round.top_miner_split(), (0, 1_000));
+    }
+
+    #[test]
+    fn test_motherlode_odds() {
+        let round = Round::zeroed();
+        // reverse_bits(1 << 63) == 1, which only hits at odds of 1.
+        assert!(round.did_hit_motherlode(1 << 63, 1));
+        assert!(!round.did_hit_motherlode(1 << 63, 625));
+        assert!(round.did_hit_motherlode(0, 625));
+        assert!(round.did_hit_motherlode((625u64 * 3).reverse_bits(), 625));
+    }
+
+    #[test]
+    fn test_deploy_preset_squares() {
+        let count = |p: DeployPreset| p.squares().iter().filter(|&&s| s).count();
+        assert_eq!(count(DeployPreset::AllSquares), 36);
+        assert_eq!(count(DeployPreset::AllSevens), 6);
+        assert_eq!(count(DeployPreset::Hardways), 4);
+        let hardways = DeployPreset::Hardways.squares();
+        for square in [7, 14, 21, 28] {
+            assert!(hardways[square]);
+        }
+    }
+
+    #[test]
+    fn test_deploy_preset_split_sums_to_total() {
+        let amounts = DeployPreset::AllSevens.split(1_000_003);
+        assert_eq!(amounts.iter().sum::<u64>(), 1_000_003);
+        // 1_000_003 = 6 * 166_667 + 1, so only the first seven gets the extra unit.
+        assert_eq!(amounts[5], 166_668);
+        assert_eq!(amounts[10], 166_667);
+        assert_eq!(amounts[0], 0);
+    }
+
     #[test]
     fn test_dice_multipliers() {
         // Test all valid multipliers
@@ -285,30 +477,22 @@ mod tests {
         assert_eq!(Round::calculate_dice_payout(255, 7, base_reward), 0);
     }
 
+    fn round_with_slot_hash(slot_hash: [u8; 32]) -> Round {
+        let mut round = Round::zeroed();
+        round.slot_hash = slot_hash;
+        round
+    }
+
+    fn slot_hash_for(i: u64) -> [u8; 32] {
+        solana_program::keccak::hash(&i.to_le_bytes()).to_bytes()
+    }
+
     #[test]
     fn test_roll_dice_valid_range() {
         // Test that dice rolls are in valid range (1-6 for each die, 2-12 for sum)
-        let round = Round {
-            id: 0,
-            deployed: [0; 36],
-            slot_hash: [0; 32],
-            count: [0; 36],
-            expires_at: 0,
-            motherlode: 0,
-            rent_payer: Pubkey::default(),
-            top_miner: Pubkey::default(),
-            top_miner_reward: 0,
-            total_deployed: 0,
-            total_vaulted: 0,
-            total_winnings: 0,
-            dice_results: [0; 2],
-            dice_sum: 0,
-            _padding: [0; 5],
-        };
-
-        // Test various RNG values
-        for rng in [0u64, 1, 5, 10, 100, 1000, u64::MAX / 2, u64::MAX] {
-            let (die1, die2, sum) = round.roll_dice(rng);
+        for i in 0..1000u64 {
+            let round = round_with_slot_hash(slot_hash_for(i));
+            let (die1, die2, sum) = round.roll_dice();
 
             assert!(die1 >= 1 && die1 <= 6, "Die 1 out of range: {}", die1);
             assert!(die2 >= 1 && die2 <= 6, "Die 2 out of range: {}", die2);
@@ -317,6 +501,121 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_roll_dice_deterministic() {
+        let round = round_with_slot_hash(slot_hash_for(42));
+        assert_eq!(round.roll_dice(), round.roll_dice());
+    }
+
+    #[test]
+    fn test_roll_dice_distribution() {
+        // Each die face and each of the 36 (die1, die2) pairs should appear uniformly.
+        const SAMPLES: u64 = 36_000;
+        let mut die1_counts = [0u64; 6];
+        let mut die2_counts = [0u64; 6];
+        let mut pair_counts = [0u64; 36];
+        for i in 0..SAMPLES {
+            let round = round_with_slot_hash(slot_hash_for(i));
+            let (die1, die2, _) = round.roll_dice();
+            die1_counts[die1 as usize - 1] += 1;
+            die2_counts[die2 as usize - 1] += 1;
+            pair_counts[(die1 as usize - 1) * 6 + (die2 as usize - 1)] += 1;
+        }
+
+        // Expected 6000 per face; allow +/-5%.
+        for count in die1_counts.iter().chain(die2_counts.iter()) {
+            assert!((5700..=6300).contains(count), "Die face count out of range: {}", count);
+        }
+
+        // Expected 1000 per pair; allow +/-15%.
+        for (square, count) in pair_counts.iter().enumerate() {
+            assert!((850..=1150).contains(count), "Pair {} count out of range: {}", square, count);
+        }
+
+        // Chi-squared over the 36 pairs with 35 degrees of freedom (p=0.001 critical value ~66.6).
+        let expected = (SAMPLES / 36) as f64;
+        let chi_squared: f64 = pair_counts
+            .iter()
+            .map(|&c| (c as f64 - expected).powi(2) / expected)
+            .sum();
+        assert!(chi_squared < 66.6, "Dice pairs not independent/uniform: chi^2 = {}", chi_squared);
+    }
+
+    #[test]
+    fn test_winning_square_matches_dice() {
+        for i in 0..1000u64 {
+            let round = round_with_slot_hash(slot_hash_for(i));
+            let (die1, die2, _) = round.roll_dice();
+            let square = round.winning_square(0);
+            assert!(square < BOARD_SIZE);
+            assert_eq!(square / 6 + 1, die1 as usize);
+            assert_eq!(square % 6 + 1, die2 as usize);
+        }
+    }
+
+    #[test]
+    fn test_set_dice_result_matches_square() {
+        let mut round = Round::zeroed();
+        assert_eq!(round.dice_result_square(), None);
+
+        for square in 0..BOARD_SIZE {
+            round.set_dice_result(square);
+            let [die1, die2] = round.dice_results;
+            assert_eq!((die1 as usize - 1) * 6 + (die2 as usize - 1), square);
+            assert_eq!(round.dice_sum, die1 + die2);
+            assert_eq!(round.dice_result_square(), Some(square));
+        }
+    }
+
+    #[test]
+    fn test_sub_rolls() {
+        let mut round = round_with_slot_hash(slot_hash_for(9));
+        assert_eq!(round.num_rolls(), 0);
+        assert_eq!(round.sub_roll_square(0), None);
+
+        // Rounds recorded before fast table mode have a single roll.
+        round.set_dice_result(round.winning_square(0));
+        assert_eq!(round.num_rolls(), 1);
+        assert_eq!(round.sub_roll_square(0), Some(round.winning_square(0)));
+        assert_eq!(round.sub_roll_square(1), None);
+
+        round.roll_count = 4;
+        assert_eq!(round.num_rolls(), 4);
+        for index in 1..4 {
+            let (die1, die2, _) = round.roll_dice_at(index);
+            let square = round.sub_roll_square(index).unwrap();
+            assert!(square < BOARD_SIZE);
+            assert_eq!(square, (die1 as usize - 1) * 6 + (die2 as usize - 1));
+        }
+        assert_eq!(round.sub_roll_square(4), None);
+
+        // Injected sub-rolls replace the derived ones.
+        round.injected_sub_rolls = [0, 1, 0, 36];
+        assert_eq!(round.sub_roll_square(1), Some(0));
+        assert_eq!(round.sub_roll_square(3), Some(35));
+        let (die1, die2, _) = round.roll_dice_at(2);
+        let derived = (die1 as usize - 1) * 6 + (die2 as usize - 1);
+        assert_eq!(round.sub_roll_square(2), Some(derived));
+    }
+
+    #[test]
+    fn test_sub_roll_distribution() {
+        // Later sub-rolls must be as uniform as the first.
+        const SAMPLES: u64 = 36_000;
+        let mut counts = [0u64; 36];
+        for i in 0..SAMPLES {
+            let round = round_with_slot_hash(slot_hash_for(i));
+            let (die1, die2, _) = round.roll_dice_at(1);
+            counts[(die1 as usize - 1) * 6 + (die2 as usize - 1)] += 1;
+        }
+        let expected = (SAMPLES / 36) as f64;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|&c| (c as f64 - expected).powi(2) / expected)
+            .sum();
+        assert!(chi_squared < 66.6, "Sub-roll not uniform: chi^2 = {}", chi_squared);
+    }
+
     #[test]
     fn test_dice_expected_value() {
         // Verify expected value equals base reward for all predictions
@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+use solana_program::keccak::hashv;
+use shank::ShankAccount;
+use steel::*;
+
+use crate::consts::BOARD_SIZE;
+use crate::state::{round_archive_pda, Round};
+
+use super::OreAccount;
+
+/// Depth of the per-square Merkle tree. The 36 squares are padded to 64 leaves.
+pub const ROUND_ARCHIVE_TREE_DEPTH: usize = 6;
+
+/// A compact record of a closed round. Per-square results are committed to by a
+/// Merkle root, so miners who checkpoint late prove their square's totals instead
+/// of reading them from the round account.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct RoundArchive {
+    /// The round number.
+    pub id: u64,
+
+    /// Merkle root of (square, deployed, count) for every square.
+    pub root: [u8; 32],
+
+    /// The round's slot hash, used for random number generation.
+    pub slot_hash: [u8; 32],
+
+    /// The slot at which claims for this round end.
+    pub expires_at: u64,
+
+    /// The account that paid rent for the archive.
+    pub rent_payer: Pubkey,
+
+    /// The amount of ORE in the motherlode.
+    pub motherlode: u64,
+
+    /// The top miner of the round.
+    pub top_miner: Pubkey,
+
+    /// The amount of ORE to distribute to the top miner.
+    pub top_miner_reward: u64,
+
+    /// The total amount won by miners for the round.
+    pub total_winnings: u64,
+
+    /// The dice roll results for the round [die1, die2].
+    pub dice_results: [u8; 2],
+
+    /// The sum of the dice roll (2-12).
+    pub dice_sum: u8,
+
+    /// The winning square (0-35).
+    pub winning_square_index: u8,
+
+    /// The number of craps sub-rolls in the round.
+    pub roll_count: u8,
+
+    /// Percent of the ORE reward paid to the top miner if the round was not split.
+    pub top_miner_share_pct: u8,
+
+    /// Padding for alignment.
+    pub _padding: [u8; 2],
+}
+
+impl RoundArchive {
+    pub fn pda(&self) -> (Pubkey, u8) {
+        round_archive_pda(self.id)
+    }
+
+    /// Records a round's results and the Merkle root of its per-square totals.
+    pub fn from_round(round: &Round, rent_payer: Pubkey) -> Self {
+        Self {
+            id: round.id,
+            root: round_merkle_root(round),
+            slot_hash: round.slot_hash,
+            expires_at: round.expires_at,
+            rent_payer,
+            motherlode: round.motherlode,
+            top_miner: round.top_miner,
+            top_miner_reward: round.top_miner_reward,
+            total_winnings: round.total_winnings,
+            dice_results: round.dice_results,
+            dice_sum: round.dice_sum,
+            winning_square_index: round.winning_square_index,
+            roll_count: round.roll_count,
+            top_miner_share_pct: round.top_miner_share_pct,
+            _padding: [0; 2],
+        }
+    }
+
+    /// Rebuilds the round for checkpointing, with one square's proven totals. Other
+    /// squares read as empty.
+    pub fn to_round(&self, square: usize, deployed: u64, count: u64) -> Round {
+        let mut round = Round::zeroed();
+        round.id = self.id;
+        round.slot_hash = self.slot_hash;
+        round.expires_at = self.expires_at;
+        round.motherlode = self.motherlode;
+        round.top_miner = self.top_miner;
+        round.top_miner_reward = self.top_miner_reward;
+        round.total_winnings = self.total_winnings;
+        round.dice_results = self.dice_results;
+        round.dice_sum = self.dice_sum;
+        round.winning_square_index = self.winning_square_index;
+        round.roll_count = self.roll_count;
+        round.top_miner_share_pct = self.top_miner_share_pct;
+        if square < BOARD_SIZE {
+            round.deployed[square] = deployed;
+            round.count[square] = count;
+        }
+        round
+    }
+
+    /// Returns the winning square, or None if the round was refunded.
+    pub fn winning_square(&self) -> Option<usize> {
+        let round = self.to_round(0, 0, 0);
+        round
+            .rng()
+            .map(|r| round.dice_result_square().unwrap_or_else(|| round.winning_square(r)))
+    }
+
+    /// Verifies a square's totals against the archived root.
+    pub fn verify_square(
+        &self,
+        square: usize,
+        deployed: u64,
+        count: u64,
+        proof: &[[u8; 32]; ROUND_ARCHIVE_TREE_DEPTH],
+    ) -> bool {
+        if square >= BOARD_SIZE {
+            return false;
+        }
+        let mut node = round_square_leaf(square, deployed, count);
+        let mut index = square;
+        for sibling in proof.iter() {
+            node = if index % 2 == 0 {
+                hashv(&[&node, sibling]).to_bytes()
+            } else {
+                hashv(&[sibling, &node]).to_bytes()
+            };
+            index /= 2;
+        }
+        node == self.root
+    }
+}
+
+/// The Merkle leaf for a square's totals.
+pub fn round_square_leaf(square: usize, deployed: u64, count: u64) -> [u8; 32] {
+    hashv(&[&[square as u8], &deployed.to_le_bytes(), &count.to_le_bytes()]).to_bytes()
+}
+
+/// Builds every level of the per-square tree, leaves first. Padding leaves are zero.
+fn round_merkle_levels(round: &Round) -> Vec<Vec<[u8; 32]>> {
+    let mut level = vec![[0u8; 32]; 1 << ROUND_ARCHIVE_TREE_DEPTH];
+    for (square, leaf) in level.iter_mut().enumerate().take(BOARD_SIZE) {
+        *leaf = round_square_leaf(square, round.deployed[square], round.count[square]);
+    }
+    let mut levels = vec![level];
+    while levels.last().unwrap().len() > 1 {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks_exact(2)
+            .map(|pair| hashv(&[&pair[0], &pair[1]]).to_bytes())
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+/// The Merkle root of a round's per-square totals.
+pub fn round_merkle_root(round: &Round) -> [u8; 32] {
+    round_merkle_levels(round).last().unwrap()[0]
+}
+
+/// The Merkle proof for a square's totals, for use with `RoundArchive::verify_square`.
+pub fn round_square_proof(round: &Round, square: usize) -> [[u8; 32]; ROUND_ARCHIVE_TREE_DEPTH] {
+    let levels = round_merkle_levels(round);
+    let mut proof = [[0u8; 32]; ROUND_ARCHIVE_TREE_DEPTH];
+    let mut index = square;
+    for (depth, sibling) in proof.iter_mut().enumerate() {
+        *sibling = levels[depth][index ^ 1];
+        index /= 2;
+    }
+    proof
+}
+
+account!(OreAccount, RoundArchive);
+account_size!(RoundArchive, 184);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square_proofs_verify_against_root() {
+        let mut round = Round::zeroed();
+        round.id = 7;
+        for square in 0..BOARD_SIZE {
+            round.deployed[square] = (square as u64 + 1) * 1_000;
+            round.count[square] = square as u64 % 5;
+        }
+        let archive = RoundArchive::from_round(&round, Pubkey::default());
+
+        for square in [0, 1, 17, 35] {
+            let proof = round_square_proof(&round, square);
+            let (deployed, count) = (round.deployed[square], round.count[square]);
+            assert!(archive.verify_square(square, deployed, count, &proof));
+            assert!(!archive.verify_square(square, deployed + 1, count, &proof));
+            assert!(!archive.verify_square(square ^ 1, deployed, count, &proof));
+        }
+        assert!(!archive.verify_square(36, 0, 0, &[[0; 32]; ROUND_ARCHIVE_TREE_DEPTH]));
+    }
+
+    #[test]
+    fn test_to_round_restores_proven_square() {
+        let mut round = Round::zeroed();
+        round.id = 3;
+        round.winning_square_index = 9;
+        round.dice_sum = 6;
+        round.deployed[9] = 500;
+        round.count[9] = 2;
+        let restored = RoundArchive::from_round(&round, Pubkey::default()).to_round(9, 500, 2);
+        assert_eq!(restored.id, 3);
+        assert_eq!(restored.dice_result_square(), Some(9));
+        assert_eq!(restored.deployed[9], 500);
+        assert_eq!(restored.count[9], 2);
+    }
+}
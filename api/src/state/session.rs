@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
 use steel::*;
 
 use super::OreAccount;
@@ -8,7 +9,7 @@ use super::OreAccount;
 ///
 /// PDA: ["session", user.to_bytes()]
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
 pub struct Session {
     /// The user who owns this session.
     pub authority: Pubkey,
@@ -23,15 +24,17 @@ pub struct Session {
     pub created_at: i64,
 
     /// Bitmask of allowed operations.
-    /// Bit 0: Games (betting, hitting, folding, etc.)
-    /// Bit 1: Swaps (exchange operations)
-    /// Bit 2: Staking (deposits only, NOT withdrawals)
-    /// Bit 3: Mining (deploy, automate, etc.)
-    /// Withdrawals are NEVER allowed via session.
+    /// Bit 3: Mining (Deploy for a Delegated automation, within `deploy_budget`)
+    /// Bit 4: Staking yield (claim and compound to the authority, NOT principal)
+    /// Bit 5: Claims (ClaimORE/ClaimSOL, paid to the authority only)
+    /// Bits 0-2 are retired. Withdrawals are NEVER allowed via session.
     pub allowed_operations: u64,
 
+    /// Lamports the delegate may still deploy through a Delegated automation.
+    pub deploy_budget: u64,
+
     /// Reserved for future use.
-    pub _reserved: [u8; 32],
+    pub _reserved: [u8; 24],
 }
 
 impl Session {
@@ -45,27 +48,25 @@ impl Session {
         (self.allowed_operations & (1u64 << (op as u8))) != 0
     }
 
-    /// Create the allowed operations bitmask for all non-withdrawal operations.
+    /// Create the allowed operations bitmask for every operation a session can grant.
     pub fn all_operations() -> u64 {
-        (1u64 << SessionOperation::Games as u8)
-            | (1u64 << SessionOperation::Swaps as u8)
-            | (1u64 << SessionOperation::StakingDeposit as u8)
-            | (1u64 << SessionOperation::Mining as u8)
+        (1u64 << SessionOperation::Mining as u8)
+            | (1u64 << SessionOperation::StakingYield as u8)
+            | (1u64 << SessionOperation::Claims as u8)
     }
 }
 
-/// Session operation types (for bitmask).
+/// Session operation types (for bitmask). Values 0-2 are retired.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum SessionOperation {
-    /// All game betting/playing operations.
-    Games = 0,
-    /// Exchange/swap operations.
-    Swaps = 1,
-    /// Staking deposits only (NOT withdrawals).
-    StakingDeposit = 2,
-    /// Mining operations (deploy, automate, etc.).
+    /// Deploying for a Delegated automation, within the session's deploy budget.
     Mining = 3,
+    /// Claiming and compounding staking yield (NOT principal withdrawals).
+    StakingYield = 4,
+    /// Claiming mining rewards, always paid to the authority.
+    Claims = 5,
 }
 
 account!(OreAccount, Session);
+account_size!(Session, 128);
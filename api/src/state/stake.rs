@@ -1,12 +1,35 @@
 use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
 use steel::*;
 
+use crate::consts::{ONE_ORE, ONE_RNG};
+use crate::error::OreError;
 use crate::state::{stake_pda, Treasury};
 
 use super::OreAccount;
 
+/// Maximum bonus stake weight from dual staking RNG alongside ORE (25%).
+pub const DUAL_STAKE_MAX_BOOST_BPS: u64 = 2500;
+
+/// Whole RNG tokens staked per whole ORE staked for the full dual staking boost.
+pub const DUAL_STAKE_RNG_PER_ORE: u64 = 1;
+
+/// Maximum boost on a staker's mining ORE rewards (10%), paid from the motherlode pool.
+pub const MINING_STAKER_BOOST_MAX_BPS: u64 = 1000;
+
+/// Stake weight that earns the full mining boost (100 ORE).
+pub const MINING_STAKER_BOOST_FULL_STAKE: u64 = 100 * ONE_ORE;
+
+/// Discounts on the ORE claim fee by stake weight, as (minimum weight, discount bps),
+/// highest tier first.
+pub const CLAIM_FEE_DISCOUNT_TIERS: [(u64, u64); 3] = [
+    (1000 * ONE_ORE, 7500),
+    (100 * ONE_ORE, 5000),
+    (10 * ONE_ORE, 2500),
+];
+
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
 pub struct Stake {
     /// The authority of this miner account.
     pub authority: Pubkey,
@@ -35,6 +58,26 @@ pub struct Stake {
     /// Flag indicating whether this staker is associated with a Solana Seeker.
     #[deprecated(since = "3.7.5", note = "This field is no longer used")]
     pub is_seeker: u64,
+
+    /// ORE withdrawn from the balance and waiting out the unstake cooldown.
+    pub pending_withdrawal: u64,
+
+    /// The slot at which the pending withdrawal becomes claimable.
+    pub withdraw_unlock_slot: u64,
+
+    /// Optional key that may claim and compound yield on the authority's behalf.
+    /// It can never withdraw principal. Pubkey::default() means no operator.
+    pub operator: Pubkey,
+
+    /// Portion of the balance represented by outstanding receipt tokens. It cannot be
+    /// withdrawn until the receipts are redeemed.
+    pub receipts_outstanding: u64,
+
+    /// The amount of RNG staked alongside ORE for the dual staking boost.
+    pub rng_balance: u64,
+
+    /// Bonus stake weight from the dual staking boost, counted in the treasury's total stake.
+    pub boost_weight: u64,
 }
 
 impl Stake {
@@ -42,6 +85,11 @@ impl Stake {
         stake_pda(self.authority)
     }
 
+    /// Whether `key` may claim or compound yield: the authority or its designated operator.
+    pub fn can_manage_yield(&self, key: &Pubkey) -> bool {
+        *key == self.authority || (self.operator != Pubkey::default() && *key == self.operator)
+    }
+
     pub fn claim(&mut self, amount: u64, clock: &Clock, treasury: &Treasury) -> u64 {
         self.update_rewards(treasury);
         let amount = self.rewards.min(amount);
@@ -50,38 +98,229 @@ impl Stake {
         amount
     }
 
+    /// Moves up to `amount` of claimable rewards into the stake balance.
+    pub fn compound(
+        &mut self,
+        amount: u64,
+        clock: &Clock,
+        treasury: &mut Treasury,
+    ) -> Result<u64, ProgramError> {
+        self.update_rewards(treasury);
+        let amount = self.rewards.min(amount);
+        self.rewards -= amount;
+        self.balance += amount;
+        self.last_claim_at = clock.unix_timestamp;
+        self.last_deposit_at = clock.unix_timestamp;
+        treasury.total_staked += amount;
+        self.sync_boost(treasury)?;
+        Ok(amount)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn deposit(
         &mut self,
         amount: u64,
         clock: &Clock,
         treasury: &mut Treasury,
         sender: &TokenAccount,
-    ) -> u64 {
+    ) -> Result<u64, ProgramError> {
         self.update_rewards(treasury);
         let amount = sender.amount().min(amount);
         self.balance += amount;
         self.last_deposit_at = clock.unix_timestamp;
         treasury.total_staked += amount;
-        amount
+        self.sync_boost(treasury)?;
+        Ok(amount)
     }
 
-    pub fn withdraw(&mut self, amount: u64, clock: &Clock, treasury: &mut Treasury) -> u64 {
+    /// Withdraws up to `amount` of the balance. Rewards are settled on the prior balance
+    /// first, so a partial withdrawal only reduces the stake's share going forward.
+    pub fn withdraw(
+        &mut self,
+        amount: u64,
+        clock: &Clock,
+        treasury: &mut Treasury,
+    ) -> Result<u64, ProgramError> {
         self.update_rewards(treasury);
-        let amount = self.balance.min(amount);
+        let amount = self.withdrawable().min(amount);
         self.balance -= amount;
         self.last_withdraw_at = clock.unix_timestamp;
         treasury.total_staked -= amount;
+        self.sync_boost(treasury)?;
+        Ok(amount)
+    }
+
+    /// Balance not backing outstanding receipt tokens.
+    pub fn withdrawable(&self) -> u64 {
+        self.balance.saturating_sub(self.receipts_outstanding)
+    }
+
+    /// Marks up to `amount` of the withdrawable balance as backing receipt tokens.
+    pub fn tokenize(&mut self, amount: u64) -> u64 {
+        let amount = self.withdrawable().min(amount);
+        self.receipts_outstanding += amount;
+        amount
+    }
+
+    /// Releases `amount` of receipt-backed balance for a redemption. Rewards are settled
+    /// first so this stake keeps what it earned. Fails with InsufficientFunds if not
+    /// enough is tokenized.
+    pub fn release_receipts(
+        &mut self,
+        amount: u64,
+        treasury: &mut Treasury,
+    ) -> Result<u64, ProgramError> {
+        if amount > self.receipts_outstanding || amount > self.balance {
+            return Err(ProgramError::InsufficientFunds);
+        }
+        self.update_rewards(treasury);
+        self.receipts_outstanding -= amount;
+        self.balance -= amount;
+        self.sync_boost(treasury)?;
+        Ok(amount)
+    }
+
+    /// Credits redeemed principal to this stake. Total staked only changes by any boost
+    /// difference, since the principal just moves between stake accounts.
+    pub fn credit_receipts(
+        &mut self,
+        amount: u64,
+        clock: &Clock,
+        treasury: &mut Treasury,
+    ) -> Result<(), ProgramError> {
+        self.update_rewards(treasury);
+        self.balance += amount;
+        self.last_deposit_at = clock.unix_timestamp;
+        self.sync_boost(treasury)
+    }
+
+    /// Stakes up to `amount` of RNG alongside the ORE balance.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn deposit_rng(
+        &mut self,
+        amount: u64,
+        clock: &Clock,
+        treasury: &mut Treasury,
+        sender: &TokenAccount,
+    ) -> Result<u64, ProgramError> {
+        self.update_rewards(treasury);
+        let amount = sender.amount().min(amount);
+        self.rng_balance += amount;
+        self.last_deposit_at = clock.unix_timestamp;
+        self.sync_boost(treasury)?;
+        Ok(amount)
+    }
+
+    /// Unstakes up to `amount` of RNG, reducing the dual staking boost.
+    pub fn withdraw_rng(
+        &mut self,
+        amount: u64,
+        clock: &Clock,
+        treasury: &mut Treasury,
+    ) -> Result<u64, ProgramError> {
+        self.update_rewards(treasury);
+        let amount = self.rng_balance.min(amount);
+        self.rng_balance -= amount;
+        self.last_withdraw_at = clock.unix_timestamp;
+        self.sync_boost(treasury)?;
+        Ok(amount)
+    }
+
+    /// Stake weight used for yield: the ORE balance plus the dual staking boost.
+    pub fn weight(&self) -> u64 {
+        self.balance + self.boost_weight
+    }
+
+    /// Bonus weight earned by the staked RNG. The boost scales linearly up to
+    /// DUAL_STAKE_MAX_BOOST_BPS once RNG matches the ORE balance at DUAL_STAKE_RNG_PER_ORE.
+    pub fn target_boost_weight(&self) -> u64 {
+        if self.balance == 0 || self.rng_balance == 0 {
+            return 0;
+        }
+        let required_rng = (self.balance as u128) * (ONE_RNG as u128)
+            * (DUAL_STAKE_RNG_PER_ORE as u128)
+            / (ONE_ORE as u128);
+        if required_rng == 0 {
+            return 0;
+        }
+        let coverage = (self.rng_balance as u128).min(required_rng);
+        ((self.balance as u128) * (DUAL_STAKE_MAX_BOOST_BPS as u128) * coverage
+            / (required_rng * 10000)) as u64
+    }
+
+    /// Boost on mining ORE rewards, scaling linearly with stake weight up to
+    /// MINING_STAKER_BOOST_FULL_STAKE.
+    pub fn mining_boost_bps(&self) -> u64 {
+        let weight = self.weight().min(MINING_STAKER_BOOST_FULL_STAKE);
+        ((weight as u128) * (MINING_STAKER_BOOST_MAX_BPS as u128)
+            / (MINING_STAKER_BOOST_FULL_STAKE as u128)) as u64
+    }
+
+    /// Discount (bps) on the ORE claim fee for the highest tier this stake's weight reaches.
+    pub fn claim_fee_discount_bps(&self) -> u64 {
+        let weight = self.weight();
+        CLAIM_FEE_DISCOUNT_TIERS
+            .iter()
+            .find(|(min_weight, _)| weight >= *min_weight)
+            .map_or(0, |(_, discount_bps)| *discount_bps)
+    }
+
+    /// Recompute the boost and move the difference into the treasury's total stake.
+    /// Rewards must be settled before calling this.
+    fn sync_boost(&mut self, treasury: &mut Treasury) -> Result<(), ProgramError> {
+        let boost_weight = self.target_boost_weight();
+        treasury.total_staked = treasury
+            .total_staked
+            .checked_sub(self.boost_weight)
+            .and_then(|total| total.checked_add(boost_weight))
+            .ok_or(OreError::ArithmeticOverflow)?;
+        self.boost_weight = boost_weight;
+        Ok(())
+    }
+
+    /// Moves up to `amount` of the balance into the pending withdrawal and restarts the
+    /// cooldown. Pending ORE earns no rewards.
+    pub fn queue_withdraw(
+        &mut self,
+        amount: u64,
+        clock: &Clock,
+        treasury: &mut Treasury,
+        cooldown_slots: u64,
+    ) -> Result<u64, ProgramError> {
+        let amount = self.withdraw(amount, clock, treasury)?;
+        self.pending_withdrawal += amount;
+        self.withdraw_unlock_slot = clock.slot.saturating_add(cooldown_slots);
+        Ok(amount)
+    }
+
+    /// Whether the stake holds nothing and owes nothing, so it can be closed.
+    /// Rewards must be settled first.
+    pub fn is_empty(&self) -> bool {
+        self.balance == 0
+            && self.rewards == 0
+            && self.pending_withdrawal == 0
+            && self.receipts_outstanding == 0
+            && self.rng_balance == 0
+    }
+
+    /// Releases the pending withdrawal if its cooldown has elapsed, otherwise returns 0.
+    pub fn claim_withdrawal(&mut self, clock: &Clock) -> u64 {
+        if clock.slot < self.withdraw_unlock_slot {
+            return 0;
+        }
+        let amount = self.pending_withdrawal;
+        self.pending_withdrawal = 0;
         amount
     }
 
     pub fn update_rewards(&mut self, treasury: &Treasury) {
-        // Accumulate rewards, weighted by stake balance.
+        // Accumulate rewards, weighted by stake balance and boost.
         if treasury.stake_rewards_factor > self.rewards_factor {
             let accumulated_rewards = treasury.stake_rewards_factor - self.rewards_factor;
             if accumulated_rewards < Numeric::ZERO {
                 panic!("Accumulated rewards is negative");
             }
-            let personal_rewards = accumulated_rewards * Numeric::from_u64(self.balance);
+            let personal_rewards = accumulated_rewards * Numeric::from_u64(self.weight());
             self.rewards += personal_rewards.to_u64();
             self.lifetime_rewards += personal_rewards.to_u64();
         }
@@ -92,3 +331,305 @@ impl Stake {
 }
 
 account!(OreAccount, Stake);
+account_size!(Stake, 184);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claim_fee_discount_tiers() {
+        let mut stake = Stake::zeroed();
+        assert_eq!(stake.claim_fee_discount_bps(), 0);
+        stake.balance = 10 * ONE_ORE - 1;
+        assert_eq!(stake.claim_fee_discount_bps(), 0);
+        stake.balance = 10 * ONE_ORE;
+        assert_eq!(stake.claim_fee_discount_bps(), 2500);
+        stake.balance = 500 * ONE_ORE;
+        assert_eq!(stake.claim_fee_discount_bps(), 5000);
+        stake.balance = 1000 * ONE_ORE;
+        assert_eq!(stake.claim_fee_discount_bps(), 7500);
+    }
+
+    #[test]
+    fn test_compound_moves_rewards_into_balance() {
+        let mut treasury = Treasury::zeroed();
+        let mut stake = Stake::zeroed();
+        stake.balance = 100;
+        treasury.total_staked = 100;
+        treasury.stake_rewards_factor = Numeric::from_u64(2);
+
+        let clock = Clock {
+            unix_timestamp: 1_000,
+            ..Clock::default()
+        };
+
+        // Partial compound leaves the remainder claimable.
+        assert_eq!(stake.compound(150, &clock, &mut treasury), Ok(150));
+        assert_eq!(stake.rewards, 50);
+        assert_eq!(stake.balance, 250);
+        assert_eq!(treasury.total_staked, 250);
+        assert_eq!(stake.last_claim_at, 1_000);
+
+        // Requests above accrued rewards are clamped.
+        assert_eq!(stake.compound(u64::MAX, &clock, &mut treasury), Ok(50));
+        assert_eq!(stake.rewards, 0);
+        assert_eq!(stake.balance, 300);
+        assert_eq!(treasury.total_staked, 300);
+    }
+
+    #[test]
+    fn test_rewards_settle_in_one_step_across_distributions() {
+        let mut treasury = Treasury::zeroed();
+        let mut alice = Stake::zeroed();
+        let mut bob = Stake::zeroed();
+        alice.balance = 300;
+        bob.balance = 100;
+        treasury.total_staked = 400;
+
+        // Many distributions between claims.
+        for _ in 0..50 {
+            assert_eq!(treasury.distribute_stake_rewards(100), 100);
+        }
+
+        // A single settlement picks up every distribution, pro rata.
+        alice.update_rewards(&treasury);
+        bob.update_rewards(&treasury);
+        assert_eq!(alice.rewards, 3_750);
+        assert_eq!(bob.rewards, 1_250);
+
+        // Settling again without new distributions adds nothing.
+        alice.update_rewards(&treasury);
+        assert_eq!(alice.rewards, 3_750);
+
+        // Nothing is distributed when no one is staked.
+        treasury.total_staked = 0;
+        assert_eq!(treasury.distribute_stake_rewards(100), 0);
+    }
+
+    #[test]
+    fn test_dual_stake_boost_scales_with_rng() {
+        let mut treasury = Treasury::zeroed();
+        let mut stake = Stake::zeroed();
+        stake.balance = 100 * ONE_ORE;
+        treasury.total_staked = 100 * ONE_ORE;
+        let clock = Clock::default();
+
+        // Half the RNG needed earns half the max boost (12.5%).
+        stake.rng_balance = 50 * ONE_RNG;
+        assert_eq!(stake.withdraw_rng(0, &clock, &mut treasury), Ok(0));
+        assert_eq!(stake.boost_weight, 125 * ONE_ORE / 10);
+        assert_eq!(treasury.total_staked, 1125 * ONE_ORE / 10);
+
+        // Excess RNG is capped at the max boost.
+        stake.rng_balance = 200 * ONE_RNG;
+        stake.withdraw_rng(0, &clock, &mut treasury).unwrap();
+        assert_eq!(stake.weight(), 125 * ONE_ORE);
+        assert_eq!(treasury.total_staked, 125 * ONE_ORE);
+
+        // Yield accrues on the boosted weight.
+        treasury.stake_rewards_factor = Numeric::from_u64(1);
+        stake.update_rewards(&treasury);
+        assert_eq!(stake.rewards, 125 * ONE_ORE);
+
+        // Withdrawing ORE shrinks the boost with it.
+        stake.withdraw(50 * ONE_ORE, &clock, &mut treasury).unwrap();
+        assert_eq!(stake.weight(), 625 * ONE_ORE / 10);
+        assert_eq!(treasury.total_staked, 625 * ONE_ORE / 10);
+
+        // Unstaking all RNG removes the boost.
+        assert_eq!(stake.withdraw_rng(u64::MAX, &clock, &mut treasury), Ok(200 * ONE_RNG));
+        assert_eq!(stake.boost_weight, 0);
+        assert_eq!(treasury.total_staked, 50 * ONE_ORE);
+    }
+
+    #[test]
+    fn test_mining_boost_scales_with_stake() {
+        let mut stake = Stake::zeroed();
+        assert_eq!(stake.mining_boost_bps(), 0);
+
+        stake.balance = 25 * ONE_ORE;
+        assert_eq!(stake.mining_boost_bps(), 250);
+
+        // Dual staking weight counts toward the mining boost.
+        stake.boost_weight = 25 * ONE_ORE;
+        assert_eq!(stake.mining_boost_bps(), 500);
+
+        stake.balance = 1_000 * ONE_ORE;
+        assert_eq!(stake.mining_boost_bps(), MINING_STAKER_BOOST_MAX_BPS);
+    }
+
+    #[test]
+    fn test_is_empty_requires_no_balance_or_yield() {
+        let mut treasury = Treasury::zeroed();
+        let mut stake = Stake::zeroed();
+        assert!(stake.is_empty());
+
+        stake.balance = 100;
+        treasury.total_staked = 100;
+        treasury.stake_rewards_factor = Numeric::from_u64(1);
+        assert!(!stake.is_empty());
+
+        // Withdrawing settles yield, which must still be claimed.
+        stake.withdraw(100, &Clock::default(), &mut treasury).unwrap();
+        assert_eq!(stake.rewards, 100);
+        assert!(!stake.is_empty());
+
+        stake.claim(100, &Clock::default(), &treasury);
+        assert!(stake.is_empty());
+
+        stake.pending_withdrawal = 1;
+        assert!(!stake.is_empty());
+    }
+
+    #[test]
+    fn test_operator_can_manage_yield() {
+        let mut stake = Stake::zeroed();
+        stake.authority = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+
+        assert!(stake.can_manage_yield(&stake.authority.clone()));
+        assert!(!stake.can_manage_yield(&operator));
+        assert!(!stake.can_manage_yield(&Pubkey::default()));
+
+        stake.operator = operator;
+        assert!(stake.can_manage_yield(&operator));
+        assert!(!stake.can_manage_yield(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_receipts_lock_principal_until_redeemed() {
+        let mut treasury = Treasury::zeroed();
+        let mut seller = Stake::zeroed();
+        let mut buyer = Stake::zeroed();
+        seller.balance = 1_000;
+        treasury.total_staked = 1_000;
+        let clock = Clock::default();
+
+        assert_eq!(seller.tokenize(600), 600);
+        assert_eq!(seller.withdrawable(), 400);
+
+        // Tokenized principal cannot be withdrawn or tokenized twice.
+        assert_eq!(seller.withdraw(u64::MAX, &clock, &mut treasury), Ok(400));
+        assert_eq!(seller.balance, 600);
+        assert_eq!(seller.tokenize(1), 0);
+
+        // Rewards accrued before redemption stay with the seller.
+        treasury.stake_rewards_factor = Numeric::from_u64(1);
+        assert_eq!(
+            seller.release_receipts(700, &mut treasury),
+            Err(ProgramError::InsufficientFunds)
+        );
+        assert_eq!(seller.release_receipts(600, &mut treasury), Ok(600));
+        buyer.credit_receipts(600, &clock, &mut treasury).unwrap();
+        assert_eq!(seller.balance, 0);
+        assert_eq!(seller.receipts_outstanding, 0);
+        assert_eq!(seller.rewards, 600);
+        assert_eq!(buyer.balance, 600);
+        assert_eq!(buyer.rewards, 0);
+        assert_eq!(treasury.total_staked, 600);
+    }
+
+    #[test]
+    fn test_boosted_redemption_moves_boost_with_principal() {
+        let mut treasury = Treasury::zeroed();
+        let mut seller = Stake::zeroed();
+        let mut buyer = Stake::zeroed();
+        let clock = Clock::default();
+
+        // Both sides hold enough RNG for the full boost on 100 ORE.
+        seller.balance = 100 * ONE_ORE;
+        seller.rng_balance = 100 * ONE_RNG;
+        buyer.rng_balance = 100 * ONE_RNG;
+        treasury.total_staked = 100 * ONE_ORE;
+        seller.withdraw_rng(0, &clock, &mut treasury).unwrap();
+        assert_eq!(treasury.total_staked, 125 * ONE_ORE);
+
+        // Redeeming moves the principal and its boost, so total stake is unchanged.
+        assert_eq!(seller.tokenize(100 * ONE_ORE), 100 * ONE_ORE);
+        seller.release_receipts(100 * ONE_ORE, &mut treasury).unwrap();
+        buyer.credit_receipts(100 * ONE_ORE, &clock, &mut treasury).unwrap();
+        assert_eq!(seller.weight(), 0);
+        assert_eq!(buyer.weight(), 125 * ONE_ORE);
+        assert_eq!(treasury.total_staked, 125 * ONE_ORE);
+    }
+
+    #[test]
+    fn test_sync_boost_rejects_inconsistent_total_stake() {
+        let mut treasury = Treasury::zeroed();
+        let mut stake = Stake::zeroed();
+        stake.balance = 100 * ONE_ORE;
+        stake.boost_weight = 25 * ONE_ORE;
+
+        // A boost the treasury never counted can't be removed from it.
+        assert_eq!(
+            stake.withdraw_rng(0, &Clock::default(), &mut treasury),
+            Err(OreError::ArithmeticOverflow.into())
+        );
+    }
+
+    #[test]
+    fn test_partial_withdraw_keeps_yield_proportional() {
+        let mut treasury = Treasury::zeroed();
+        let mut stake = Stake::zeroed();
+        stake.balance = 1_000;
+        treasury.total_staked = 1_000;
+        let clock = Clock::default();
+
+        // 1 ORE per staked ORE accrues on the full balance before the trim.
+        treasury.stake_rewards_factor = Numeric::from_u64(1);
+        assert_eq!(stake.withdraw(400, &clock, &mut treasury), Ok(400));
+        assert_eq!(stake.balance, 600);
+        assert_eq!(stake.rewards, 1_000);
+        assert_eq!(treasury.total_staked, 600);
+
+        // Later distributions accrue on the remaining balance only.
+        treasury.stake_rewards_factor = Numeric::from_u64(2);
+        stake.update_rewards(&treasury);
+        assert_eq!(stake.rewards, 1_600);
+
+        // Withdrawing more than the balance is clamped rather than rejected.
+        assert_eq!(stake.withdraw(u64::MAX, &clock, &mut treasury), Ok(600));
+        assert_eq!(stake.balance, 0);
+        assert_eq!(treasury.total_staked, 0);
+    }
+
+    #[test]
+    fn test_queue_withdraw_respects_cooldown() {
+        let mut treasury = Treasury::zeroed();
+        let mut stake = Stake::zeroed();
+        stake.balance = 1_000;
+        treasury.total_staked = 1_000;
+
+        let clock = Clock {
+            slot: 100,
+            ..Clock::default()
+        };
+        assert_eq!(stake.queue_withdraw(400, &clock, &mut treasury, 50), Ok(400));
+        assert_eq!(stake.balance, 600);
+        assert_eq!(stake.pending_withdrawal, 400);
+        assert_eq!(treasury.total_staked, 600);
+        assert_eq!(stake.withdraw_unlock_slot, 150);
+
+        // Still locked.
+        let early = Clock {
+            slot: 149,
+            ..Clock::default()
+        };
+        assert_eq!(stake.claim_withdrawal(&early), 0);
+        assert_eq!(stake.pending_withdrawal, 400);
+
+        // Queuing more restarts the timer for the whole pending amount.
+        assert_eq!(stake.queue_withdraw(100, &early, &mut treasury, 50), Ok(100));
+        assert_eq!(stake.pending_withdrawal, 500);
+        assert_eq!(stake.withdraw_unlock_slot, 199);
+
+        let unlocked = Clock {
+            slot: 199,
+            ..Clock::default()
+        };
+        assert_eq!(stake.claim_withdrawal(&unlocked), 500);
+        assert_eq!(stake.pending_withdrawal, 0);
+        assert_eq!(stake.claim_withdrawal(&unlocked), 0);
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use steel::*;
+
+use crate::consts::{BOARD_SIZE, MAX_ROLLS_PER_ROUND};
+use crate::state::test_roll_oracle_pda;
+
+use super::OreAccount;
+
+/// TestRollOracle is a singleton account holding admin-injected dice rolls for a round.
+/// It is only written and read by programs built with the `test-oracle` feature (localnet/devnet),
+/// letting test clusters drive reset and settlement through the same validation as mainnet.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct TestRollOracle {
+    /// The round the injected roll applies to.
+    pub round_id: u64,
+
+    /// The injected winning square (0-35).
+    pub winning_square: u64,
+
+    /// The injected squares of the round's later sub-rolls, stored as square + 1. Zero leaves
+    /// the sub-roll to the slot hash. Index 0 is unused, since sub-roll 0 is the winning square.
+    pub sub_rolls: [u8; MAX_ROLLS_PER_ROUND as usize],
+
+    /// Padding for alignment.
+    pub _padding: [u8; 4],
+}
+
+impl TestRollOracle {
+    pub fn pda() -> (Pubkey, u8) {
+        test_roll_oracle_pda()
+    }
+
+    /// Returns the injected winning square if it applies to the given round.
+    pub fn roll_for(&self, round_id: u64) -> Option<usize> {
+        if self.round_id != round_id || self.winning_square >= BOARD_SIZE as u64 {
+            return None;
+        }
+        Some(self.winning_square as usize)
+    }
+
+    /// Returns the injected sub-rolls if the winning square applies to the given round.
+    pub fn sub_rolls_for(&self, round_id: u64) -> Option<[u8; MAX_ROLLS_PER_ROUND as usize]> {
+        self.roll_for(round_id)?;
+        let mut sub_rolls = self.sub_rolls;
+        sub_rolls[0] = 0;
+        if sub_rolls.iter().any(|&s| s as usize > BOARD_SIZE) {
+            return None;
+        }
+        Some(sub_rolls)
+    }
+}
+
+account!(OreAccount, TestRollOracle);
+account_size!(TestRollOracle, 32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roll_for_matches_round() {
+        let oracle = TestRollOracle {
+            round_id: 7,
+            winning_square: 14,
+            sub_rolls: [0, 3, 0, 36],
+            _padding: [0; 4],
+        };
+        assert_eq!(oracle.roll_for(7), Some(14));
+        assert_eq!(oracle.roll_for(8), None);
+        assert_eq!(oracle.sub_rolls_for(7), Some([0, 3, 0, 36]));
+        assert_eq!(oracle.sub_rolls_for(8), None);
+
+        let invalid = TestRollOracle {
+            round_id: 7,
+            winning_square: BOARD_SIZE as u64,
+            sub_rolls: [0; 4],
+            _padding: [0; 4],
+        };
+        assert_eq!(invalid.roll_for(7), None);
+
+        let invalid_sub_roll = TestRollOracle {
+            sub_rolls: [0, BOARD_SIZE as u8 + 1, 0, 0],
+            ..oracle
+        };
+        assert_eq!(invalid_sub_roll.sub_rolls_for(7), None);
+    }
+}
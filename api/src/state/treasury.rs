@@ -1,14 +1,18 @@
 use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
 use steel::*;
 
 use super::OreAccount;
 
+/// Current Treasury layout version, set by `Initialize` and `MigrateConfig`.
+pub const TREASURY_VERSION: u8 = 1;
+
 /// Treasury is a singleton account which is the mint authority for the ORE token and the authority of
 /// the program's global token account.
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
 pub struct Treasury {
-    // The amount of SOL collected for buy-bury operations.
+    // The amount of SOL revenue collected but not yet routed by `DistributeRevenue`.
     pub balance: u64,
 
     /// The amount of ORE in the motherlode rewards pool.
@@ -28,6 +32,34 @@ pub struct Treasury {
 
     /// The current total amount of refined ORE mining rewards.
     pub total_refined: u64,
+
+    /// Layout version of this account. Zero for accounts that predate versioning.
+    pub version: u8,
+
+    pub _padding: [u8; 7],
+
+    /// The amount of SOL routed to buy-bury operations.
+    pub buyback_balance: u64,
+
+    /// The cumulative SOL routed to the insurance fund.
+    pub total_insurance: u64,
+
+    /// Space for future fields, so they can be added without a realloc.
+    pub _reserved: [u64; 14],
+}
+
+impl Treasury {
+    /// Distribute `amount` ORE to stakers by advancing the reward-per-share accumulator.
+    /// Stakers settle lazily against it, so claims are O(1) however many distributions
+    /// have happened. Returns the amount distributed, which is 0 when nothing is staked.
+    pub fn distribute_stake_rewards(&mut self, amount: u64) -> u64 {
+        if self.total_staked == 0 || amount == 0 {
+            return 0;
+        }
+        self.stake_rewards_factor += Numeric::from_fraction(amount, self.total_staked);
+        amount
+    }
 }
 
 account!(OreAccount, Treasury);
+account_size!(Treasury, 216);
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use shank::ShankAccount;
+use steel::*;
+
+use crate::state::vesting_pda;
+
+use super::OreAccount;
+
+/// ORE from large claims, released linearly over a schedule.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, Serialize, Deserialize, ShankAccount)]
+pub struct Vesting {
+    /// The authority entitled to the vesting ORE.
+    pub authority: Pubkey,
+
+    /// The amount of ORE in the current schedule.
+    pub total: u64,
+
+    /// The amount of the current schedule already released.
+    pub released: u64,
+
+    /// The slot at which the current schedule started.
+    pub start_slot: u64,
+
+    /// The slot at which the current schedule is fully vested.
+    pub end_slot: u64,
+}
+
+impl Vesting {
+    pub fn pda(&self) -> (Pubkey, u8) {
+        vesting_pda(self.authority)
+    }
+
+    /// The amount of the schedule vested as of `slot`.
+    pub fn vested(&self, slot: u64) -> u64 {
+        if slot >= self.end_slot {
+            return self.total;
+        }
+        if slot <= self.start_slot {
+            return 0;
+        }
+        let elapsed = (slot - self.start_slot) as u128;
+        let duration = (self.end_slot - self.start_slot) as u128;
+        ((self.total as u128 * elapsed) / duration) as u64
+    }
+
+    /// The amount vested but not yet released as of `slot`.
+    pub fn claimable(&self, slot: u64) -> u64 {
+        self.vested(slot).saturating_sub(self.released)
+    }
+
+    /// Releases everything vested as of `slot` and returns the amount.
+    pub fn release(&mut self, slot: u64) -> u64 {
+        let amount = self.claimable(slot);
+        self.released += amount;
+        amount
+    }
+
+    /// Whether every ORE in the schedule has been released.
+    pub fn is_complete(&self) -> bool {
+        self.released >= self.total
+    }
+
+    /// Adds `amount` to the schedule. Anything still unvested, plus `amount`, restarts
+    /// vesting over `duration_slots` from `slot`. Vested but unreleased ORE must be
+    /// released first.
+    pub fn add(&mut self, amount: u64, slot: u64, duration_slots: u64) {
+        let remaining = self.total - self.released;
+        self.total = remaining + amount;
+        self.released = 0;
+        self.start_slot = slot;
+        self.end_slot = slot + duration_slots;
+    }
+}
+
+account!(OreAccount, Vesting);
+account_size!(Vesting, 72);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vesting_releases_linearly() {
+        let mut vesting = Vesting::zeroed();
+        vesting.add(1_000, 100, 100);
+        assert_eq!(vesting.claimable(100), 0);
+        assert_eq!(vesting.claimable(150), 500);
+        assert_eq!(vesting.release(150), 500);
+        assert_eq!(vesting.claimable(150), 0);
+        assert_eq!(vesting.release(175), 250);
+        assert_eq!(vesting.release(1_000), 250);
+        assert!(vesting.is_complete());
+    }
+
+    #[test]
+    fn test_vesting_add_restarts_remaining() {
+        let mut vesting = Vesting::zeroed();
+        vesting.add(1_000, 0, 100);
+        assert_eq!(vesting.release(40), 400);
+
+        // The unvested 600 and the new 400 vest together from slot 40.
+        vesting.add(400, 40, 100);
+        assert_eq!(vesting.total, 1_000);
+        assert_eq!(vesting.released, 0);
+        assert_eq!(vesting.claimable(90), 500);
+        assert_eq!(vesting.claimable(140), 1_000);
+    }
+}
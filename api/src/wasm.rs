@@ -0,0 +1,28 @@
+//! Payout previews for browser frontends.
+//!
+//! Enable with the `wasm` feature and build with `default-features = false` so the
+//! instruction builders and their CPI dependencies are left out:
+//! `wasm-pack build api --no-default-features --features wasm`.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{house_edge, payout};
+
+/// The maximum a craps bet can return, stake included, as reserved on-chain.
+#[wasm_bindgen(js_name = maxPayout)]
+pub fn max_payout(bet_type: u8, point: u8, amount: u64) -> Result<u64, JsError> {
+    payout::max_payout(bet_type, point, amount).map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// Winnings on a bet at `payout_num:payout_den` odds, not including the stake.
+#[wasm_bindgen(js_name = calculatePayout)]
+pub fn calculate_payout(bet_amount: u64, payout_num: u64, payout_den: u64) -> u64 {
+    payout::calculate_payout(bet_amount, payout_num, payout_den)
+}
+
+/// The house edge of a craps bet on `point`, as a fraction of the stake, or `undefined`
+/// for side bets and points the bet can't be placed on.
+#[wasm_bindgen(js_name = houseEdge)]
+pub fn house_edge(bet_type: u8, point: u8) -> Option<f64> {
+    house_edge::house_edge(bet_type, point)
+}
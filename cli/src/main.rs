@@ -32,6 +32,8 @@ use steel::{AccountDeserialize, AccountMeta, Clock, Discriminator, Instruction};
 #[derive(Debug, Clone, Copy)]
 enum Command {
     Initialize,
+    Bootstrap,
+    Faucet,
     Automations,
     Clock,
     Claim,
@@ -65,6 +67,8 @@ impl Command {
     fn from_str(s: &str) -> Option<Self> {
         match s {
             "initialize" => Some(Self::Initialize),
+            "bootstrap" => Some(Self::Bootstrap),
+            "faucet" => Some(Self::Faucet),
             "automations" => Some(Self::Automations),
             "clock" => Some(Self::Clock),
             "claim" => Some(Self::Claim),
@@ -103,6 +107,8 @@ impl Command {
     ) -> Result<(), anyhow::Error> {
         match self {
             Self::Initialize => initialize(rpc, payer).await,
+            Self::Bootstrap => bootstrap(rpc, payer).await,
+            Self::Faucet => faucet(rpc, payer).await,
             Self::Automations => log_automations(rpc).await,
             Self::Clock => log_clock(rpc).await,
             Self::Claim => claim(rpc, payer).await,
@@ -186,6 +192,62 @@ async fn initialize(
     Ok(())
 }
 
+/// Initializes the program and opens a funded craps table in one transaction.
+/// Requires a program built with `--features localnet` and the payer as CRAP mint authority.
+async fn bootstrap(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    // House bankroll in whole CRAP (default: 1,000,000 CRAP)
+    let house_bankroll = std::env::var("HOUSE_BANKROLL")
+        .unwrap_or("1000000".to_string())
+        .parse::<u64>()
+        .expect("Invalid HOUSE_BANKROLL");
+
+    let board_pda = ore_api::state::board_pda();
+    if let Ok(_) = rpc.get_account(&board_pda.0).await {
+        println!(
+            "Program already initialized! Board account exists at: {}",
+            board_pda.0
+        );
+        return Ok(());
+    }
+
+    println!("Bootstrapping with a house bankroll of {} CRAP...", house_bankroll);
+    let ix = ore_api::sdk::bootstrap(payer.pubkey(), house_bankroll * ONE_CRAP);
+    let sig = submit_transaction(rpc, payer, &[ix]).await?;
+    println!("Bootstrap transaction: {:?}", sig);
+
+    println!("\nCreated accounts:");
+    println!("  Board: {}", board_pda.0);
+    println!("  Config: {}", ore_api::state::config_pda().0);
+    println!("  Treasury: {}", ore_api::state::treasury_pda().0);
+    println!("  Round 0: {}", ore_api::state::round_pda(0).0);
+    println!("  Craps game: {}", ore_api::state::craps_game_pda().0);
+    println!("  Craps vault tokens: {}", ore_api::state::craps_vault_tokens_address());
+
+    Ok(())
+}
+
+/// Claims test RNG and CRAP from the devnet faucet.
+async fn faucet(
+    rpc: &RpcClient,
+    payer: &solana_sdk::signer::keypair::Keypair,
+) -> Result<(), anyhow::Error> {
+    let ixs = [
+        ore_api::sdk::faucet_rng(payer.pubkey()),
+        ore_api::sdk::faucet_crap(payer.pubkey()),
+    ];
+    let sig = submit_transaction(rpc, payer, &ixs).await?;
+    println!("Faucet transaction: {:?}", sig);
+    println!(
+        "Minted {} RNG and {} CRAP",
+        amount_to_ui_amount(FAUCET_RNG_AMOUNT, RNG_TOKEN_DECIMALS),
+        amount_to_ui_amount(FAUCET_CRAP_AMOUNT, CRAP_TOKEN_DECIMALS)
+    );
+    Ok(())
+}
+
 async fn lut(
     rpc: &RpcClient,
     payer: &solana_sdk::signer::keypair::Keypair,
@@ -877,7 +939,16 @@ async fn log_round(rpc: &RpcClient) -> Result<(), anyhow::Error> {
     } else {
         println!("  Dice roll: (not yet rolled)");
     }
-    if let Some(rng) = rng {
+    for index in 1..round.num_rolls() {
+        let Some(square) = round.sub_roll_square(index) else {
+            break;
+        };
+        let (die1, die2) = (square / 6 + 1, square % 6 + 1);
+        println!("  Sub-roll {}: {} + {} = {}", index, die1, die2, die1 + die2);
+    }
+    if let Some(square) = round.dice_result_square() {
+        println!("  Winning square: {}", square);
+    } else if let Some(rng) = rng {
         println!("  Winning square: {}", round.winning_square(rng));
     }
     Ok(())
@@ -998,8 +1069,8 @@ async fn get_automations(rpc: &RpcClient) -> Result<Vec<(Pubkey, Automation)>, a
 async fn get_board(rpc: &RpcClient) -> Result<Board, anyhow::Error> {
     let board_pda = ore_api::state::board_pda();
     let account = rpc.get_account(&board_pda.0).await?;
-    let board = Board::try_from_bytes(&account.data)?;
-    Ok(*board)
+    let board = Board::read_unaligned(&account.owner, &account.data)?;
+    Ok(board)
 }
 
 async fn get_var(rpc: &RpcClient, address: Pubkey) -> Result<Var, anyhow::Error> {
@@ -1011,29 +1082,29 @@ async fn get_var(rpc: &RpcClient, address: Pubkey) -> Result<Var, anyhow::Error>
 async fn get_round(rpc: &RpcClient, id: u64) -> Result<Round, anyhow::Error> {
     let round_pda = ore_api::state::round_pda(id);
     let account = rpc.get_account(&round_pda.0).await?;
-    let round = Round::try_from_bytes(&account.data)?;
-    Ok(*round)
+    let round = Round::read_unaligned(&account.owner, &account.data)?;
+    Ok(round)
 }
 
 async fn get_treasury(rpc: &RpcClient) -> Result<Treasury, anyhow::Error> {
     let treasury_pda = ore_api::state::treasury_pda();
     let account = rpc.get_account(&treasury_pda.0).await?;
-    let treasury = Treasury::try_from_bytes(&account.data)?;
-    Ok(*treasury)
+    let treasury = Treasury::read_unaligned(&account.owner, &account.data)?;
+    Ok(treasury)
 }
 
 async fn get_config(rpc: &RpcClient) -> Result<Config, anyhow::Error> {
     let config_pda = ore_api::state::config_pda();
     let account = rpc.get_account(&config_pda.0).await?;
-    let config = Config::try_from_bytes(&account.data)?;
-    Ok(*config)
+    let config = Config::read_unaligned(&account.owner, &account.data)?;
+    Ok(config)
 }
 
 async fn get_miner(rpc: &RpcClient, authority: Pubkey) -> Result<Miner, anyhow::Error> {
     let miner_pda = ore_api::state::miner_pda(authority);
     let account = rpc.get_account(&miner_pda.0).await?;
-    let miner = Miner::try_from_bytes(&account.data)?;
-    Ok(*miner)
+    let miner = Miner::read_unaligned(&account.owner, &account.data)?;
+    Ok(miner)
 }
 
 async fn get_clock(rpc: &RpcClient) -> Result<Clock, anyhow::Error> {
@@ -1045,8 +1116,8 @@ async fn get_clock(rpc: &RpcClient) -> Result<Clock, anyhow::Error> {
 async fn get_stake(rpc: &RpcClient, authority: Pubkey) -> Result<Stake, anyhow::Error> {
     let stake_pda = ore_api::state::stake_pda(authority);
     let account = rpc.get_account(&stake_pda.0).await?;
-    let stake = Stake::try_from_bytes(&account.data)?;
-    Ok(*stake)
+    let stake = Stake::read_unaligned(&account.owner, &account.data)?;
+    Ok(stake)
 }
 
 async fn get_rounds(rpc: &RpcClient) -> Result<Vec<(Pubkey, Round)>, anyhow::Error> {
@@ -1225,7 +1296,7 @@ pub async fn get_program_accounts<T>(
     filters: Vec<RpcFilterType>,
 ) -> Result<Vec<(Pubkey, T)>, anyhow::Error>
 where
-    T: AccountDeserialize + Discriminator + Clone,
+    T: AccountReader,
 {
     let mut all_filters = vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
         0,
@@ -1251,11 +1322,9 @@ where
             let accounts = accounts
                 .into_iter()
                 .filter_map(|(pubkey, account)| {
-                    if let Ok(account) = T::try_from_bytes(&account.data) {
-                        Some((pubkey, account.clone()))
-                    } else {
-                        None
-                    }
+                    T::read_unaligned(&account.owner, &account.data)
+                        .ok()
+                        .map(|account| (pubkey, account))
                 })
                 .collect();
             Ok(accounts)
@@ -13,8 +13,10 @@ import { useWallet } from "@solana/wallet-adapter-react";
 import {
   crapsGamePDA,
   crapsPositionPDA,
+  crapsSideBetsPDA,
   parseCrapsGame,
   parseCrapsPosition,
+  parseCrapsSideBets,
   CrapsGame,
   CrapsPosition,
   type Address,
@@ -94,8 +96,8 @@ export function useCraps() {
 
         const [crapsGameAddress] = crapsGamePDA();
 
-        // Fetch craps game and position in parallel for scalability
-        const { gameAccount, positionAccount } = await withFallback(
+        // Fetch craps game, position and side bets in parallel for scalability
+        const { gameAccount, positionAccount, sideBetsAccount } = await withFallback(
           async (conn) => {
             // Build list of accounts to fetch in parallel
             const fetchPromises: Promise<Awaited<ReturnType<typeof conn.getAccountInfo>>>[] = [
@@ -105,7 +107,9 @@ export function useCraps() {
             // Add position fetch if wallet is connected
             if (publicKey) {
               const [crapsPositionAddress] = crapsPositionPDA(publicKey);
+              const [crapsSideBetsAddress] = crapsSideBetsPDA(publicKey);
               fetchPromises.push(conn.getAccountInfo(crapsPositionAddress));
+              fetchPromises.push(conn.getAccountInfo(crapsSideBetsAddress));
             }
 
             // Parallel fetch for better performance at scale
@@ -114,6 +118,7 @@ export function useCraps() {
             return {
               gameAccount: results[0],
               positionAccount: publicKey ? results[1] : null,
+              sideBetsAccount: publicKey ? results[2] : null,
             };
           }
         );
@@ -126,8 +131,12 @@ export function useCraps() {
         }
 
         if (positionAccount) {
+          const parsedSideBets = sideBetsAccount
+            ? parseCrapsSideBets(Buffer.from(sideBetsAccount.data))
+            : null;
           const parsedPosition = parseCrapsPosition(
-            Buffer.from(positionAccount.data)
+            Buffer.from(positionAccount.data),
+            parsedSideBets
           );
           setCrapsPosition(parsedPosition);
         } else {
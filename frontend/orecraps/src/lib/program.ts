@@ -172,18 +172,46 @@ export interface CrapsPosition {
   rideTheLineBet: bigint;    // wins based on pass line wins before seven-out
   rideWinsCount: number;     // count of pass line wins
   mugsyBet: bigint;          // wins on 7 (different payouts based on phase)
-  mugsyState: number;        // 0=come-out, 1=point phase, 2=resolved
   hotHandBet: bigint;        // wins if all 10 totals hit before 7
   hotHandHits: number;       // bitmask of totals hit (bits 0-9)
   replayBet: bigint;         // wins when same point made multiple times
   replayCounts: number[];    // count of times each point was made (6 elements)
   fieldersChoice: bigint[];  // 3 single-roll bets: [0]=2,3,4 | [1]=4,9,10 | [2]=10,11,12
+  // Table sync
+  phaseId: number;           // CrapsGame phase_id of the next roll to settle
+  activeBets: number;        // bitmask of active bet categories
   // Tracking
   pendingWinnings: bigint;
   totalWagered: bigint;
   totalWon: bigint;
   totalLost: bigint;
   lastUpdatedRound: bigint;
+  unpaidDebt: bigint;
+  reservedPayouts: bigint;
+}
+
+// Exotic craps bets (matches on-chain CrapsSideBets struct). The account is created
+// with the first exotic bet; CrapsPosition carries these fields when it is passed in.
+export interface CrapsSideBets {
+  authority: PublicKey;
+  epochId: bigint;
+  bonusSmall: bigint;
+  bonusTall: bigint;
+  bonusAll: bigint;
+  smallHits: number;
+  tallHits: number;
+  fireBet: bigint;
+  firePointsMade: number;
+  diffDoublesBet: bigint;
+  diffDoublesHits: number;
+  rideTheLineBet: bigint;
+  rideWinsCount: number;
+  mugsyBet: bigint;
+  hotHandBet: bigint;
+  hotHandHits: number;
+  replayBet: bigint;
+  replayCounts: number[];
+  fieldersChoice: bigint[];
 }
 
 // Payout constants (matching on-chain)
@@ -485,6 +513,14 @@ export function crapsPositionPDA(authority: PublicKey): [PublicKey, number] {
   );
 }
 
+// Craps Side Bets PDA (per user), created with the first exotic bet
+export function crapsSideBetsPDA(authority: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("craps_side_bets"), authority.toBuffer()],
+    ORE_PROGRAM_ID
+  );
+}
+
 // Craps Vault PDA (token account authority)
 export function crapsVaultPDA(): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
@@ -697,8 +733,10 @@ export function createFundCrapsHouseInstruction(
 
 // Minimum expected sizes for account validation (8-byte discriminator)
 const CRAPS_GAME_MIN_SIZE = 8 + 8 + 1 + 1 + 6 + 8 + 8 + 8 + 8; // 64 bytes
-// CrapsPosition: 864 bytes total (8 discriminator + 856 data)
-const CRAPS_POSITION_MIN_SIZE = 864;
+// CrapsPosition: 736 bytes total (8 discriminator + 728 data)
+const CRAPS_POSITION_SIZE = 736;
+// CrapsSideBets: 192 bytes total (8 discriminator + 184 data)
+const CRAPS_SIDE_BETS_SIZE = 192;
 
 // Helper to read BigUInt64LE from any array-like data (works in browser & Node.js)
 function readBigUInt64LE(data: Uint8Array | Buffer, offset: number): bigint {
@@ -754,8 +792,53 @@ export function parseCrapsGame(data: Uint8Array | Buffer): CrapsGame {
   };
 }
 
-// Legacy account size before yes/no/next bets were expanded to 11 elements
-const CRAPS_POSITION_LEGACY_SIZE = 600;
+// Create empty side bets (for positions that never placed an exotic bet)
+function createEmptySideBets(authority: PublicKey, epochId: bigint): CrapsSideBets {
+  return {
+    authority,
+    epochId,
+    bonusSmall: BigInt(0),
+    bonusTall: BigInt(0),
+    bonusAll: BigInt(0),
+    smallHits: 0,
+    tallHits: 0,
+    fireBet: BigInt(0),
+    firePointsMade: 0,
+    diffDoublesBet: BigInt(0),
+    diffDoublesHits: 0,
+    rideTheLineBet: BigInt(0),
+    rideWinsCount: 0,
+    mugsyBet: BigInt(0),
+    hotHandBet: BigInt(0),
+    hotHandHits: 0,
+    replayBet: BigInt(0),
+    replayCounts: Array(NUM_POINTS).fill(0),
+    fieldersChoice: Array(3).fill(BigInt(0)),
+  };
+}
+
+// The side bet fields CrapsPosition carries
+function sideBetFields(sideBets: CrapsSideBets) {
+  return {
+    bonusSmall: sideBets.bonusSmall,
+    bonusTall: sideBets.bonusTall,
+    bonusAll: sideBets.bonusAll,
+    smallHits: sideBets.smallHits,
+    tallHits: sideBets.tallHits,
+    fireBet: sideBets.fireBet,
+    firePointsMade: sideBets.firePointsMade,
+    diffDoublesBet: sideBets.diffDoublesBet,
+    diffDoublesHits: sideBets.diffDoublesHits,
+    rideTheLineBet: sideBets.rideTheLineBet,
+    rideWinsCount: sideBets.rideWinsCount,
+    mugsyBet: sideBets.mugsyBet,
+    hotHandBet: sideBets.hotHandBet,
+    hotHandHits: sideBets.hotHandHits,
+    replayBet: sideBets.replayBet,
+    replayCounts: sideBets.replayCounts,
+    fieldersChoice: sideBets.fieldersChoice,
+  };
+}
 
 // Create an empty CrapsPosition with all bets zeroed (for legacy account fallback)
 function createEmptyPosition(authority: PublicKey, epochId: bigint): CrapsPosition {
@@ -782,55 +865,55 @@ function createEmptyPosition(authority: PublicKey, epochId: bigint): CrapsPositi
     yoEleven: BigInt(0),
     aces: BigInt(0),
     twelve: BigInt(0),
-    bonusSmall: BigInt(0),
-    bonusTall: BigInt(0),
-    bonusAll: BigInt(0),
-    smallHits: 0,
-    tallHits: 0,
-    fireBet: BigInt(0),
-    firePointsMade: 0,
-    diffDoublesBet: BigInt(0),
-    diffDoublesHits: 0,
-    rideTheLineBet: BigInt(0),
-    rideWinsCount: 0,
-    mugsyBet: BigInt(0),
-    mugsyState: 0,
-    hotHandBet: BigInt(0),
-    hotHandHits: 0,
-    replayBet: BigInt(0),
-    replayCounts: Array(NUM_POINTS).fill(0),
-    fieldersChoice: Array(3).fill(BigInt(0)),
+    ...sideBetFields(createEmptySideBets(authority, epochId)),
+    phaseId: 0,
+    activeBets: 0,
     pendingWinnings: BigInt(0),
     totalWagered: BigInt(0),
     totalWon: BigInt(0),
     totalLost: BigInt(0),
     lastUpdatedRound: BigInt(0),
+    unpaidDebt: BigInt(0),
+    reservedPayouts: BigInt(0),
   };
 }
 
+function readU64Array(data: Uint8Array | Buffer, offset: number, length: number): bigint[] {
+  const values: bigint[] = [];
+  for (let i = 0; i < length; i++) {
+    values.push(readBigUInt64LE(data, offset + i * 8));
+  }
+  return values;
+}
+
+function readU32LE(data: Uint8Array | Buffer, offset: number): number {
+  return (data[offset] | (data[offset + 1] << 8) | (data[offset + 2] << 16) | (data[offset + 3] << 24)) >>> 0;
+}
+
 // Parse CrapsPosition account data (matches Rust struct order exactly)
-// For legacy accounts (<864 bytes), returns empty position that needs migration
-export function parseCrapsPosition(data: Uint8Array | Buffer): CrapsPosition {
+// Exotic bets live on the CrapsSideBets account; pass it in to fill those fields.
+// Accounts still in the pre-compact layout return an empty position until the program
+// migrates them (MigrateCrapsPosition, or on the next bet or settlement).
+export function parseCrapsPosition(
+  data: Uint8Array | Buffer,
+  sideBets?: CrapsSideBets | null
+): CrapsPosition {
   // SECURITY: Validate minimum buffer length
   if (!data || data.length < 48) { // At least discriminator + authority + epoch_id
     throw new Error(`Invalid CrapsPosition data: expected at least 48 bytes, got ${data?.length ?? 0}`);
   }
 
+  const authority = new PublicKey(data.subarray(8, 40));
+  const epochId = readBigUInt64LE(data, 40);
+
   // Check if this is a legacy account that needs migration
-  const isLegacyAccount = data.length < CRAPS_POSITION_MIN_SIZE;
-  if (isLegacyAccount) {
-    console.warn(`[CrapsPosition] Legacy account detected (${data.length} bytes). Returning empty position - account needs migration via close+recreate.`);
-    // Return a mostly-empty position with just the authority and epoch from the old account
-    const authority = new PublicKey(data.subarray(8, 40));
-    const epochId = readBigUInt64LE(data, 40);
+  if (data.length !== CRAPS_POSITION_SIZE) {
+    console.warn(`[CrapsPosition] Legacy account detected (${data.length} bytes). Returning empty position - account needs MigrateCrapsPosition.`);
     return createEmptyPosition(authority, epochId);
   }
 
-  // Skip discriminator (8 bytes - Anchor style)
-  let offset = 8;
-
-  const authority = new PublicKey(data.subarray(offset, offset + 32)); offset += 32;
-  const epochId = readBigUInt64LE(data, offset); offset += 8;
+  // Skip discriminator, authority and epoch_id
+  let offset = 48;
 
   // Line bets
   const passLine = readBigUInt64LE(data, offset); offset += 8;
@@ -839,51 +922,23 @@ export function parseCrapsPosition(data: Uint8Array | Buffer): CrapsPosition {
   const dontPassOdds = readBigUInt64LE(data, offset); offset += 8;
 
   // Come bets
-  const comeBets: bigint[] = [];
-  for (let i = 0; i < NUM_POINTS; i++) {
-    comeBets.push(readBigUInt64LE(data, offset)); offset += 8;
-  }
-  const comeOdds: bigint[] = [];
-  for (let i = 0; i < NUM_POINTS; i++) {
-    comeOdds.push(readBigUInt64LE(data, offset)); offset += 8;
-  }
-  const dontComeBets: bigint[] = [];
-  for (let i = 0; i < NUM_POINTS; i++) {
-    dontComeBets.push(readBigUInt64LE(data, offset)); offset += 8;
-  }
-  const dontComeOdds: bigint[] = [];
-  for (let i = 0; i < NUM_POINTS; i++) {
-    dontComeOdds.push(readBigUInt64LE(data, offset)); offset += 8;
-  }
+  const comeBets = readU64Array(data, offset, NUM_POINTS); offset += NUM_POINTS * 8;
+  const comeOdds = readU64Array(data, offset, NUM_POINTS); offset += NUM_POINTS * 8;
+  const dontComeBets = readU64Array(data, offset, NUM_POINTS); offset += NUM_POINTS * 8;
+  const dontComeOdds = readU64Array(data, offset, NUM_POINTS); offset += NUM_POINTS * 8;
 
   // Place bets
-  const placeBets: bigint[] = [];
-  for (let i = 0; i < NUM_POINTS; i++) {
-    placeBets.push(readBigUInt64LE(data, offset)); offset += 8;
-  }
+  const placeBets = readU64Array(data, offset, NUM_POINTS); offset += NUM_POINTS * 8;
   const placeWorking = data[offset] === 1; offset += 1;
-  offset += 7; // _padding1
+  offset += 7; // bundle_credit
 
   // True odds bets (Yes/No/Next) - 11 elements each for sums 2-12
-  // NOTE: In Rust struct, these come BEFORE hardways
-  const yesBets: bigint[] = [];
-  for (let i = 0; i < 11; i++) {
-    yesBets.push(readBigUInt64LE(data, offset)); offset += 8;
-  }
-  const noBets: bigint[] = [];
-  for (let i = 0; i < 11; i++) {
-    noBets.push(readBigUInt64LE(data, offset)); offset += 8;
-  }
-  const nextBets: bigint[] = [];
-  for (let i = 0; i < 11; i++) {
-    nextBets.push(readBigUInt64LE(data, offset)); offset += 8;
-  }
+  const yesBets = readU64Array(data, offset, 11); offset += 11 * 8;
+  const noBets = readU64Array(data, offset, 11); offset += 11 * 8;
+  const nextBets = readU64Array(data, offset, 11); offset += 11 * 8;
 
   // Hardways (4 elements for hard 4, 6, 8, 10)
-  const hardways: bigint[] = [];
-  for (let i = 0; i < NUM_HARDWAYS; i++) {
-    hardways.push(readBigUInt64LE(data, offset)); offset += 8;
-  }
+  const hardways = readU64Array(data, offset, NUM_HARDWAYS); offset += NUM_HARDWAYS * 8;
 
   // Single-roll bets
   const fieldBet = readBigUInt64LE(data, offset); offset += 8;
@@ -893,6 +948,68 @@ export function parseCrapsPosition(data: Uint8Array | Buffer): CrapsPosition {
   const aces = readBigUInt64LE(data, offset); offset += 8;
   const twelve = readBigUInt64LE(data, offset); offset += 8;
 
+  // Table sync
+  const phaseId = readU32LE(data, offset); offset += 4;
+  const activeBets = readU32LE(data, offset); offset += 4;
+
+  // Tracking
+  const pendingWinnings = readBigUInt64LE(data, offset); offset += 8;
+  const totalWagered = readBigUInt64LE(data, offset); offset += 8;
+  const totalWon = readBigUInt64LE(data, offset); offset += 8;
+  const totalLost = readBigUInt64LE(data, offset); offset += 8;
+  const lastUpdatedRound = readBigUInt64LE(data, offset); offset += 8;
+  const unpaidDebt = readBigUInt64LE(data, offset); offset += 8;
+  const reservedPayouts = readBigUInt64LE(data, offset); offset += 8;
+
+  return {
+    authority,
+    epochId,
+    passLine,
+    dontPass,
+    passOdds,
+    dontPassOdds,
+    comeBets,
+    comeOdds,
+    dontComeBets,
+    dontComeOdds,
+    placeBets,
+    placeWorking,
+    yesBets,
+    noBets,
+    nextBets,
+    hardways,
+    fieldBet,
+    anySeven,
+    anyCraps,
+    yoEleven,
+    aces,
+    twelve,
+    ...sideBetFields(sideBets ?? createEmptySideBets(authority, epochId)),
+    phaseId,
+    activeBets,
+    pendingWinnings,
+    totalWagered,
+    totalWon,
+    totalLost,
+    lastUpdatedRound,
+    unpaidDebt,
+    reservedPayouts,
+  };
+}
+
+// Parse CrapsSideBets account data (matches Rust struct order exactly)
+export function parseCrapsSideBets(data: Uint8Array | Buffer): CrapsSideBets {
+  // SECURITY: Validate buffer length before parsing
+  if (!data || data.length < CRAPS_SIDE_BETS_SIZE) {
+    throw new Error(`Invalid CrapsSideBets data: expected ${CRAPS_SIDE_BETS_SIZE} bytes, got ${data?.length ?? 0}`);
+  }
+
+  // Skip discriminator (8 bytes)
+  let offset = 8;
+
+  const authority = new PublicKey(data.subarray(offset, offset + 32)); offset += 32;
+  const epochId = readBigUInt64LE(data, offset); offset += 8;
+
   // Bonus craps side bets
   const bonusSmall = readBigUInt64LE(data, offset); offset += 8;
   const bonusTall = readBigUInt64LE(data, offset); offset += 8;
@@ -918,12 +1035,9 @@ export function parseCrapsPosition(data: Uint8Array | Buffer): CrapsPosition {
 
   // Mugsy's Corner Bet
   const mugsyBet = readBigUInt64LE(data, offset); offset += 8;
-  const mugsyState = data[offset]; offset += 1;
-  offset += 7; // _pad_mugsy
 
   // Hot Hand Bet
   const hotHandBet = readBigUInt64LE(data, offset); offset += 8;
-  // Read 2 bytes for u16
   const hotHandHits = data[offset] | (data[offset + 1] << 8); offset += 2;
   offset += 6; // _pad_hot
 
@@ -936,41 +1050,11 @@ export function parseCrapsPosition(data: Uint8Array | Buffer): CrapsPosition {
   offset += 2; // _pad_replay
 
   // Fielder's Choice bets (3 single-roll bets)
-  const fieldersChoice: bigint[] = [];
-  for (let i = 0; i < 3; i++) {
-    fieldersChoice.push(readBigUInt64LE(data, offset)); offset += 8;
-  }
-
-  // Tracking
-  const pendingWinnings = readBigUInt64LE(data, offset); offset += 8;
-  const totalWagered = readBigUInt64LE(data, offset); offset += 8;
-  const totalWon = readBigUInt64LE(data, offset); offset += 8;
-  const totalLost = readBigUInt64LE(data, offset); offset += 8;
-  const lastUpdatedRound = readBigUInt64LE(data, offset); offset += 8;
+  const fieldersChoice = readU64Array(data, offset, 3); offset += 3 * 8;
 
   return {
     authority,
     epochId,
-    passLine,
-    dontPass,
-    passOdds,
-    dontPassOdds,
-    comeBets,
-    comeOdds,
-    dontComeBets,
-    dontComeOdds,
-    placeBets,
-    placeWorking,
-    yesBets,
-    noBets,
-    nextBets,
-    hardways,
-    fieldBet,
-    anySeven,
-    anyCraps,
-    yoEleven,
-    aces,
-    twelve,
     bonusSmall,
     bonusTall,
     bonusAll,
@@ -983,17 +1067,11 @@ export function parseCrapsPosition(data: Uint8Array | Buffer): CrapsPosition {
     rideTheLineBet,
     rideWinsCount,
     mugsyBet,
-    mugsyState,
     hotHandBet,
     hotHandHits,
     replayBet,
     replayCounts,
     fieldersChoice,
-    pendingWinnings,
-    totalWagered,
-    totalWon,
-    totalLost,
-    lastUpdatedRound,
   };
 }
 
@@ -7,8 +7,10 @@ import {
   createSettleCrapsInstruction,
   crapsGamePDA,
   crapsPositionPDA,
+  crapsSideBetsPDA,
   parseCrapsGame,
   parseCrapsPosition,
+  parseCrapsSideBets,
   CrapsGame,
   CrapsPosition,
   CrapsBetType,
@@ -36,9 +38,14 @@ export class CrapsGameService {
 
   async getPositionState(authority: PublicKey): Promise<CrapsPosition | null> {
     const [positionAddress] = crapsPositionPDA(authority);
-    const account = await this.connection.getAccountInfo(positionAddress);
+    const [sideBetsAddress] = crapsSideBetsPDA(authority);
+    const [account, sideBetsAccount] = await this.connection.getMultipleAccountsInfo([
+      positionAddress,
+      sideBetsAddress,
+    ]);
     if (!account) return null;
-    return parseCrapsPosition(account.data);
+    const sideBets = sideBetsAccount ? parseCrapsSideBets(sideBetsAccount.data) : null;
+    return parseCrapsPosition(account.data, sideBets);
   }
 
   async placeBets(
@@ -44,10 +44,10 @@ export const SWAP_FEE_BPS = 100; // 1% = 100 basis points
 export const FEE_DISTRIBUTION_BPS = 5000; // 50% to LP, 50% to protocol
 
 // Instruction discriminators
-const SWAP_SOL_TO_RNG = 79;
-const SWAP_RNG_TO_SOL = 80;
-const SWAP_RNG_TO_GAME_TOKEN = 81;
-const SWAP_GAME_TOKEN_TO_RNG = 82;
+const SWAP_SOL_TO_RNG = 96;
+const SWAP_RNG_TO_SOL = 97;
+const SWAP_RNG_TO_GAME_TOKEN = 98;
+const SWAP_GAME_TOKEN_TO_RNG = 99;
 
 // Game token configuration
 export const GAME_TOKENS = {
@@ -380,11 +380,12 @@ export class ExchangeService {
    * 1: exchange_pool (PDA, writable)
    * 2: sol_vault (PDA, writable)
    * 3: rng_vault (PDA, writable)
-   * 4: user_rng_ata (writable)
-   * 5: rng_mint
-   * 6: sol_mint
-   * 7: system_program
-   * 8: token_program
+   * 4: user_sol_ata (writable) - wSOL destination
+   * 5: user_rng_ata (writable)
+   * 6: rng_mint
+   * 7: sol_mint
+   * 8: system_program
+   * 9: token_program
    */
   async buildSwapRngToSolInstruction(
     user: PublicKey,
@@ -394,6 +395,7 @@ export class ExchangeService {
     const exchangePool = this.getPoolAddress();
     const solVault = this.getSolVaultAddress();
     const rngVault = this.getRngVaultAddress();
+    const userSolAta = await getAssociatedTokenAddress(SOL_MINT, user);
     const userRngAta = await getAssociatedTokenAddress(RNG_MINT, user);
 
     // Instruction data: discriminator(1) + rng_amount(8) + min_sol_out(8)
@@ -409,6 +411,7 @@ export class ExchangeService {
         { pubkey: exchangePool, isSigner: false, isWritable: true },
         { pubkey: solVault, isSigner: false, isWritable: true },
         { pubkey: rngVault, isSigner: false, isWritable: true },
+        { pubkey: userSolAta, isSigner: false, isWritable: true },
         { pubkey: userRngAta, isSigner: false, isWritable: true },
         { pubkey: RNG_MINT, isSigner: false, isWritable: false },
         { pubkey: SOL_MINT, isSigner: false, isWritable: false },
@@ -447,8 +450,8 @@ export class ExchangeService {
         { pubkey: rngVault, isSigner: false, isWritable: true },
         { pubkey: userRngAta, isSigner: false, isWritable: true },
         { pubkey: userGameAta, isSigner: false, isWritable: true },
-        { pubkey: RNG_MINT, isSigner: false, isWritable: false },
         { pubkey: gameInfo.mint, isSigner: false, isWritable: true },
+        { pubkey: RNG_MINT, isSigner: false, isWritable: false },
         { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
       ],
       data: Buffer.from(data),
@@ -483,8 +486,8 @@ export class ExchangeService {
         { pubkey: rngVault, isSigner: false, isWritable: true },
         { pubkey: userRngAta, isSigner: false, isWritable: true },
         { pubkey: userGameAta, isSigner: false, isWritable: true },
-        { pubkey: RNG_MINT, isSigner: false, isWritable: true },
         { pubkey: gameInfo.mint, isSigner: false, isWritable: true },
+        { pubkey: RNG_MINT, isSigner: false, isWritable: false },
         { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
       ],
       data: Buffer.from(data),
@@ -524,6 +527,14 @@ export class ExchangeService {
     minSolOut: bigint
   ): Promise<Transaction> {
     const tx = new Transaction();
+
+    // Ensure user has a wSOL ATA to receive the swap output
+    const userSolAta = await getAssociatedTokenAddress(SOL_MINT, user);
+    const ataInfo = await this.connection.getAccountInfo(userSolAta);
+    if (!ataInfo) {
+      tx.add(createAssociatedTokenAccountInstruction(user, userSolAta, user, SOL_MINT));
+    }
+
     const swapIx = await this.buildSwapRngToSolInstruction(user, rngAmount, minSolOut);
     tx.add(swapIx);
     return tx;
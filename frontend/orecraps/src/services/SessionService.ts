@@ -25,10 +25,9 @@ const REVOKE_SESSION = 89;
 
 // Session operation flags
 export enum SessionOperation {
-  Games = 0,
-  Swaps = 1,
-  StakingDeposit = 2,
   Mining = 3,
+  StakingYield = 4,
+  Claims = 5,
 }
 
 // Session PDA seed
@@ -161,10 +160,9 @@ export function buildRevokeSessionInstruction(
  */
 export function getAllOperations(): bigint {
   return (
-    (1n << BigInt(SessionOperation.Games)) |
-    (1n << BigInt(SessionOperation.Swaps)) |
-    (1n << BigInt(SessionOperation.StakingDeposit)) |
-    (1n << BigInt(SessionOperation.Mining))
+    (1n << BigInt(SessionOperation.Mining)) |
+    (1n << BigInt(SessionOperation.StakingYield)) |
+    (1n << BigInt(SessionOperation.Claims))
   );
 }
 
@@ -0,0 +1,56 @@
+//! Feeds arbitrary account data through the checked account readers.
+//!
+//! Reads must never panic, a successful read must have matched the type's
+//! discriminator and minimum size, and no data may read as two account types.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ore_api::state::*;
+use steel::*;
+
+/// Reads `data` as `T` and returns whether it was accepted.
+fn accepts<T: AccountReader>(owner: &Pubkey, data: &[u8]) -> bool {
+    // The aligned read borrows in place, so it may also fail on alignment alone.
+    let aligned = T::read(owner, data).is_ok();
+    match T::read_unaligned(owner, data) {
+        Ok(_) => {
+            assert_eq!(*owner, ore_api::ID);
+            assert_eq!(data[0], T::discriminator());
+            assert!(data.len() >= 8 + std::mem::size_of::<T>());
+            true
+        }
+        Err(_) => {
+            assert!(!aligned);
+            false
+        }
+    }
+}
+
+macro_rules! count_accepted {
+    ($owner:expr, $data:expr, [$($name:ident),* $(,)?]) => {
+        0 $(+ accepts::<$name>($owner, $data) as usize)*
+    };
+}
+
+fuzz_target!(|data: &[u8]| {
+    // Data owned by another program never reads as ours.
+    let other_program = Pubkey::new_from_array([7; 32]);
+    for owner in [ore_api::ID, other_program] {
+        let accepted = count_accepted!(
+            &owner,
+            data,
+            [
+                AdminMultisig, AuditLog, Automation, Board, Config, CrapsGame, CrapsPosition,
+                CrapsSideBets, DcaOrder, ExchangePool, FaucetClaim, GameTokenPool, InsuranceFund,
+                LimitOrder, LiquidityPosition, LpLock, LpStake, Metrics, Miner, Param, Pool,
+                PoolMember, Referrer, Roles, RollProof, Round, RoundArchive, Session, Stake,
+                TestRollOracle, Treasury, Vesting,
+            ]
+        );
+        assert!(accepted <= 1, "data read as {} account types", accepted);
+        if owner != ore_api::ID {
+            assert_eq!(accepted, 0);
+        }
+    }
+});
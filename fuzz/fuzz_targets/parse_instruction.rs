@@ -0,0 +1,81 @@
+//! Feeds arbitrary instruction data through the program's dispatch path.
+//!
+//! `parse_instruction` must never panic, must only accept a known tag, and must hand
+//! the processor exactly the bytes after it. Each variant's args must then decode
+//! through `try_from_bytes` without panicking and only at their exact size, so
+//! truncated or padded data can't be read as a different layout.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ore_api::instruction::*;
+use steel::*;
+
+/// Decodes the args for `ix`. The match is exhaustive, so a new instruction can't be
+/// added without being fuzzed here.
+macro_rules! check_args {
+    ($ix:expr, $data:expr, [$($name:ident),* $(,)?]) => {
+        match $ix {
+            $(
+                OreInstruction::$name => {
+                    let args = $name::try_from_bytes($data);
+                    if $data.len() != std::mem::size_of::<$name>() {
+                        assert!(
+                            args.is_err(),
+                            "{} decoded from {} bytes",
+                            stringify!($name),
+                            $data.len()
+                        );
+                    }
+                }
+            )*
+        }
+    };
+}
+
+fuzz_target!(|data: &[u8]| {
+    // Instructions addressed to another program are rejected before the tag is read.
+    let other_program = Pubkey::new_from_array([7; 32]);
+    assert!(parse_instruction::<OreInstruction>(&ore_api::ID, &other_program, data).is_err());
+
+    let Ok((ix, args)) = parse_instruction::<OreInstruction>(&ore_api::ID, &ore_api::ID, data)
+    else {
+        assert!(data.first().map_or(true, |tag| OreInstruction::try_from(*tag).is_err()));
+        return;
+    };
+    assert_eq!(ix as u8, data[0]);
+    assert_eq!(args, &data[1..]);
+
+    check_args!(
+        ix,
+        args,
+        [
+            Initialize, StartRound, Automate, Close, Checkpoint, ClaimSOL, ClaimORE,
+            RecycleSOL, Deploy, DeployMany, DeploySpread, SetAutoRedeploy, CheckpointBatch,
+            ArchiveRound, CheckpointArchived, OpenPool, JoinPool, LeavePool, ClaimPoolRewards,
+            PoolDeploy, HarvestPool, Log, Wrap, Bury, Reset, SetAdmin, SetFeeCollector,
+            Deposit, Withdraw, ClaimYield, ClaimYieldAndRestake, ClaimWithdrawal,
+            SetStakeOperator, MintStakeReceipt, RedeemStakeReceipt, DepositRng, WithdrawRng,
+            CloseStake, NewVar, SetAdminFee, SetUnstakeCooldown, SetMotherlodeParams,
+            SetRewardSplitPolicy, SetVestingPolicy, ClaimVested, SetClaimMinimums,
+            SetAdminMultisig, AcceptAdmin, Pause, ScheduleUnpause, ApplyUnpause, SetGuardian,
+            ApplyGuardian, SetParam, SetRevenuePolicy, DistributeRevenue, SetRole,
+            SetRoundPermission, SweepDust, CrankClaims, SetSwapProgram, SetVarAddress,
+            RouteSwap, PlaceCrapsBet, SettleCraps, SettleCrapsSingleRoll, SimulateSettle,
+            ClaimCrapsWinnings, FundCrapsHouse, ScheduleHouseWithdrawal, WithdrawHouseBankroll,
+            AssertSolvency, SetHouseProfitShare, ForceSettleCraps, ClaimCrapsDebt, SetTestRoll,
+            MigrateRound, MigrateMiner, MigrateCrapsPosition, MigrateStake, MigrateConfig,
+            MigrateAccount, Bootstrap, FaucetRng, FaucetCrap, OpenMetrics, PlaceCrapsBundle,
+            DeployWithSol, ClaimYieldAsSol, DistributeHouseProfit, CreateSession, RevokeSession,
+            InitializeExchangePool, AddLiquidity, RemoveLiquidity, SwapSolToRng, SwapRngToSol,
+            SwapRngToGameToken, SwapGameTokenToRng, ClaimProtocolFees, QuoteSwap,
+            SwapSolToGameToken, SwapSolToRngExactOut, SwapRngToSolExactOut, SetPoolStatus,
+            SetPoolGuardian, SetPoolFees, ApplyPoolFees, InitializeGameTokenPool, SwapGameTokenPool,
+            StakeLp, UnstakeLp, ClaimLpRewards, ZapLiquidity, RemoveLiquiditySingle,
+            SkimProtocolFees, RegisterReferrer, ClaimReferralFees, SetReferralFee,
+            SetCircuitBreaker, SetMaxSwap, EmergencyRemoveLiquidity, PlaceLimitOrder,
+            FillLimitOrder, CancelLimitOrder, OpenDcaOrder, CrankDcaOrder, CancelDcaOrder, LockLp,
+            UnlockLp, QuoteGameTokenBacking, SetBuybackConfig, BuybackCrap, SetSlotNotionalCap,
+        ]
+    );
+});
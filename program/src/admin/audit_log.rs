@@ -0,0 +1,46 @@
+use ore_api::prelude::*;
+use steel::*;
+
+/// Opens the admin audit log if it doesn't exist yet.
+pub(crate) fn open_audit_log(
+    payer_info: &AccountInfo<'_>,
+    audit_log_info: &AccountInfo<'_>,
+    system_program: &AccountInfo<'_>,
+) -> ProgramResult {
+    audit_log_info
+        .is_writable()?
+        .has_seeds(&[AUDIT_LOG], &ore_api::ID)?;
+    if audit_log_info.data_is_empty() {
+        create_program_account::<AuditLog>(
+            audit_log_info,
+            system_program,
+            payer_info,
+            &ore_api::ID,
+            &[AUDIT_LOG],
+        )?;
+    }
+    Ok(())
+}
+
+/// Appends an admin action to the audit log.
+pub(crate) fn log_admin_action(
+    audit_log_info: &AccountInfo<'_>,
+    actor: &Pubkey,
+    instruction: OreInstruction,
+    old_value: [u8; 32],
+    new_value: [u8; 32],
+) -> ProgramResult {
+    let audit_log = audit_log_info
+        .is_writable()?
+        .has_seeds(&[AUDIT_LOG], &ore_api::ID)?
+        .as_account_mut::<AuditLog>(&ore_api::ID)?;
+    audit_log.push(AuditEntry {
+        actor: *actor,
+        slot: Clock::get()?.slot,
+        instruction: instruction as u8,
+        _padding: [0; 7],
+        old_value,
+        new_value,
+    });
+    Ok(())
+}
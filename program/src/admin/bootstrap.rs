@@ -0,0 +1,98 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use solana_program::program::invoke;
+use steel::*;
+
+use super::process_initialize;
+use crate::craps::create_craps_game;
+
+/// Initializes the program and opens a funded craps table in one transaction: the
+/// Initialize accounts, the craps game, the vault and treasury token accounts, and a
+/// house bankroll minted straight into the vault.
+/// Only compiled with the `localnet` feature, where the signer holds the CRAP mint authority.
+pub fn process_bootstrap(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = Bootstrap::try_from_bytes(data)?;
+    let house_bankroll = u64::from_le_bytes(args.house_bankroll);
+
+    // Load accounts.
+    let [signer_info, board_info, config_info, treasury_info, round_info, system_program, audit_log_info, craps_game_info, craps_vault_info, vault_crap_ata, crap_mint, treasury_tokens_info, mint_info, token_program, associated_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    craps_game_info
+        .is_empty()?
+        .is_writable()?
+        .has_seeds(&[CRAPS_GAME], &ore_api::ID)?;
+    craps_vault_info.has_seeds(&[CRAPS_VAULT], &ore_api::ID)?;
+    vault_crap_ata.is_writable()?;
+    crap_mint.is_writable()?.has_address(&CRAP_MINT_ADDRESS)?;
+    treasury_tokens_info.is_writable()?;
+    mint_info.has_address(&MINT_ADDRESS)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    sol_log("Bootstrap: initializing program and craps table");
+
+    // Board, config, treasury, round 0 and the audit log.
+    process_initialize(
+        &[
+            signer_info.clone(),
+            board_info.clone(),
+            config_info.clone(),
+            treasury_info.clone(),
+            round_info.clone(),
+            system_program.clone(),
+            audit_log_info.clone(),
+        ],
+        &[],
+    )?;
+
+    // Token accounts the treasury and craps vault need before the first round.
+    create_associated_token_account(
+        signer_info,
+        treasury_info,
+        treasury_tokens_info,
+        mint_info,
+        system_program,
+        token_program,
+        associated_token_program,
+    )?;
+    create_associated_token_account(
+        signer_info,
+        craps_vault_info,
+        vault_crap_ata,
+        crap_mint,
+        system_program,
+        token_program,
+        associated_token_program,
+    )?;
+
+    // Open the table and mint its bankroll.
+    create_craps_game(signer_info, craps_game_info, system_program)?;
+    if house_bankroll > 0 {
+        invoke(
+            &spl_token::instruction::mint_to(
+                &spl_token::ID,
+                crap_mint.key,
+                vault_crap_ata.key,
+                signer_info.key,
+                &[],
+                house_bankroll,
+            )?,
+            &[
+                crap_mint.clone(),
+                vault_crap_ata.clone(),
+                signer_info.clone(),
+                token_program.clone(),
+            ],
+        )?;
+    }
+    let craps_game = craps_game_info.as_account_mut::<CrapsGame>(&ore_api::ID)?;
+    craps_game.house_bankroll = house_bankroll;
+
+    sol_log(&format!("Bootstrap complete: house bankroll {} CRAP", house_bankroll).as_str());
+
+    Ok(())
+}
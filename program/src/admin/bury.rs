@@ -108,13 +108,8 @@ pub fn process_bury(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         .as_str(),
     );
 
-    // Share some ORE with stakers.
-    let mut shared_amount = 0;
-    if treasury.total_staked > 0 {
-        shared_amount = total_ore / 10; // Share 10% of buyback ORE with stakers
-        treasury.stake_rewards_factor +=
-            Numeric::from_fraction(shared_amount, treasury.total_staked);
-    }
+    // Share the revenue policy's staking portion of the buyback ORE with stakers.
+    let shared_amount = treasury.distribute_stake_rewards(config.buyback_stake_share(total_ore));
 
     sol_log(&format!(
         "💰 Shared {} ORE",
@@ -0,0 +1,70 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use solana_program::native_token::lamports_to_sol;
+use solana_program::rent::Rent;
+use steel::*;
+
+/// Routes the treasury's undistributed SOL revenue according to the revenue policy.
+///
+/// The insurance share is paid into the insurance fund. The rest is set aside for
+/// `Wrap` and `Bury`, which split the bought-back ORE between burning and stakers.
+/// SOL sent to the treasury outside the program, such as admin fees when the fee
+/// collector is the treasury, is picked up as revenue too.
+pub fn process_distribute_revenue(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, config_info, treasury_info, insurance_fund_info, system_program] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    let treasury = treasury_info.as_account_mut::<Treasury>(&ore_api::ID)?;
+    insurance_fund_info
+        .is_writable()?
+        .has_seeds(&[INSURANCE_FUND], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Collect tracked revenue and any untracked lamports above rent.
+    let min_balance = Rent::get()?.minimum_balance(treasury_info.data_len());
+    let untracked = treasury_info
+        .lamports()
+        .saturating_sub(min_balance)
+        .saturating_sub(treasury.balance)
+        .saturating_sub(treasury.buyback_balance);
+    let amount = treasury.balance + untracked;
+    if amount == 0 {
+        return Ok(());
+    }
+
+    // Split revenue.
+    let (buyback, insurance) = config.split_revenue(amount);
+    treasury.balance = 0;
+    treasury.buyback_balance += buyback;
+
+    // Pay the insurance fund.
+    if insurance > 0 {
+        if insurance_fund_info.data_is_empty() {
+            create_program_account::<InsuranceFund>(
+                insurance_fund_info,
+                system_program,
+                signer_info,
+                &ore_api::ID,
+                &[INSURANCE_FUND],
+            )?;
+        }
+        let insurance_fund = insurance_fund_info.as_account_mut::<InsuranceFund>(&ore_api::ID)?;
+        treasury_info.send(insurance, insurance_fund_info);
+        insurance_fund.balance += insurance;
+        insurance_fund.total_deposited += insurance;
+        treasury.total_insurance += insurance;
+    }
+
+    sol_log(&format!(
+        "Distributed {} SOL: {} SOL to buyback, {} SOL to insurance",
+        lamports_to_sol(amount),
+        lamports_to_sol(buyback),
+        lamports_to_sol(insurance)
+    ));
+
+    Ok(())
+}
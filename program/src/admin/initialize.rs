@@ -3,11 +3,13 @@ use ore_api::prelude::*;
 use solana_program::log::sol_log;
 use steel::*;
 
-/// Initialize the program accounts (Board, Config, Treasury, Round 0).
+use super::open_audit_log;
+
+/// Initialize the program accounts (Board, Config, Treasury, Round 0, audit log).
 /// Can only be called once by the program deployer.
 pub fn process_initialize(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts
-    let [signer_info, board_info, config_info, treasury_info, round_info, system_program] =
+    let [signer_info, board_info, config_info, treasury_info, round_info, system_program, audit_log_info] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -67,6 +69,27 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
     config.swap_program = Pubkey::default();
     config.var_address = Pubkey::default();
     config.admin_fee = 100; // 1% (100 bps)
+    config.unstake_cooldown_slots = 0;
+    config.motherlode_odds = DEFAULT_MOTHERLODE_ODDS;
+    config.motherlode_funding = DEFAULT_MOTHERLODE_FUNDING;
+    config.split_reward_bps = DEFAULT_SPLIT_REWARD_BPS;
+    config.top_miner_share_pct = 0;
+    config.vesting_threshold = 0;
+    config.vesting_duration_slots = 0;
+    config.min_claim_sol = 0;
+    config.min_claim_ore = 0;
+    config.pending_admin = Pubkey::default();
+    config.guardian = Pubkey::default();
+    config.paused = 0;
+    config.pending_unpause = 0;
+    config.unpause_effective_at = 0;
+    config.pending_guardian = Pubkey::default();
+    config.guardian_effective_at = 0;
+    config.revenue_burn_bps = 0;
+    config.revenue_stake_bps = 0;
+    config.revenue_insurance_bps = 0;
+    config.round_permission = ROUND_PERMISSION_OPEN;
+    config.version = CONFIG_VERSION;
     sol_log(&format!("Config created at {}", config_info.key));
 
     // Create Treasury account
@@ -85,8 +108,14 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
     treasury.total_staked = 0;
     treasury.total_unclaimed = 0;
     treasury.total_refined = 0;
+    treasury.buyback_balance = 0;
+    treasury.total_insurance = 0;
+    treasury.version = TREASURY_VERSION;
     sol_log(&format!("Treasury created at {}", treasury_info.key));
 
+    // Create audit log
+    open_audit_log(signer_info, audit_log_info, system_program)?;
+
     // Create Round 0 account
     create_program_account::<Round>(
         round_info,
@@ -0,0 +1,91 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::grow_account;
+
+/// Grows an account to its type's current size and runs any pending layout upgrades,
+/// with the signer paying any additional rent. It is a no-op for current accounts, so
+/// instructions can call it on first touch of accounts that may predate a layout change.
+pub(crate) fn migrate_account<T: Migratable>(
+    signer_info: &AccountInfo<'_>,
+    account_info: &AccountInfo<'_>,
+    system_program: &AccountInfo<'_>,
+) -> ProgramResult {
+    let size = 8 + std::mem::size_of::<T>();
+    if account_info.data_len() > size {
+        sol_log("Account is larger than its current layout");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    grow_account(signer_info, account_info, system_program, size)?;
+    let mut data = account_info.try_borrow_mut_data()?;
+    if let Some(version) = migrate_account_data::<T>(&mut data)? {
+        sol_log(&format!(
+            "Migrated {} from version {} to {}",
+            account_info.key,
+            version,
+            T::VERSION
+        ));
+    }
+    Ok(())
+}
+
+/// Migrates any program account, dispatching on its discriminator. Anyone can call
+/// this, since growing an account only appends zeroed fields and backfills defaults.
+pub fn process_migrate_account(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, account_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    account_info.is_writable()?;
+    if account_info.owner != &ore_api::ID {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    system_program.is_program(&system_program::ID)?;
+
+    // Dispatch on the account type.
+    let discriminator = *account_info
+        .try_borrow_data()?
+        .first()
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let account_type =
+        OreAccount::try_from(discriminator).map_err(|_| ProgramError::InvalidAccountData)?;
+    match account_type {
+        OreAccount::AdminMultisig => migrate_account::<AdminMultisig>(signer_info, account_info, system_program),
+        OreAccount::AuditLog => migrate_account::<AuditLog>(signer_info, account_info, system_program),
+        OreAccount::Automation => migrate_account::<Automation>(signer_info, account_info, system_program),
+        OreAccount::Board => migrate_account::<Board>(signer_info, account_info, system_program),
+        OreAccount::Config => migrate_account::<Config>(signer_info, account_info, system_program),
+        OreAccount::CrapsGame => migrate_account::<CrapsGame>(signer_info, account_info, system_program),
+        OreAccount::CrapsSideBets => migrate_account::<CrapsSideBets>(signer_info, account_info, system_program),
+        OreAccount::DcaOrder => migrate_account::<DcaOrder>(signer_info, account_info, system_program),
+        OreAccount::ExchangePool => migrate_account::<ExchangePool>(signer_info, account_info, system_program),
+        OreAccount::FaucetClaim => migrate_account::<FaucetClaim>(signer_info, account_info, system_program),
+        OreAccount::GameTokenPool => migrate_account::<GameTokenPool>(signer_info, account_info, system_program),
+        OreAccount::InsuranceFund => migrate_account::<InsuranceFund>(signer_info, account_info, system_program),
+        OreAccount::LimitOrder => migrate_account::<LimitOrder>(signer_info, account_info, system_program),
+        OreAccount::LiquidityPosition => migrate_account::<LiquidityPosition>(signer_info, account_info, system_program),
+        OreAccount::LpLock => migrate_account::<LpLock>(signer_info, account_info, system_program),
+        OreAccount::LpStake => migrate_account::<LpStake>(signer_info, account_info, system_program),
+        OreAccount::Metrics => migrate_account::<Metrics>(signer_info, account_info, system_program),
+        OreAccount::Miner => migrate_account::<Miner>(signer_info, account_info, system_program),
+        OreAccount::Param => migrate_account::<Param>(signer_info, account_info, system_program),
+        OreAccount::Pool => migrate_account::<Pool>(signer_info, account_info, system_program),
+        OreAccount::PoolMember => migrate_account::<PoolMember>(signer_info, account_info, system_program),
+        OreAccount::Referrer => migrate_account::<Referrer>(signer_info, account_info, system_program),
+        OreAccount::Roles => migrate_account::<Roles>(signer_info, account_info, system_program),
+        OreAccount::RollProof => migrate_account::<RollProof>(signer_info, account_info, system_program),
+        OreAccount::Round => migrate_account::<Round>(signer_info, account_info, system_program),
+        OreAccount::RoundArchive => migrate_account::<RoundArchive>(signer_info, account_info, system_program),
+        OreAccount::Session => migrate_account::<Session>(signer_info, account_info, system_program),
+        OreAccount::Stake => migrate_account::<Stake>(signer_info, account_info, system_program),
+        OreAccount::TestRollOracle => migrate_account::<TestRollOracle>(signer_info, account_info, system_program),
+        OreAccount::Treasury => migrate_account::<Treasury>(signer_info, account_info, system_program),
+        OreAccount::Vesting => migrate_account::<Vesting>(signer_info, account_info, system_program),
+        OreAccount::CrapsPosition => {
+            sol_log("Craps positions migrate with MigrateCrapsPosition");
+            Err(ProgramError::InvalidAccountData)
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::{assert_admin, open_audit_log};
+
+/// Grows an account to `size`, with the signer paying any additional rent.
+/// New bytes are zero-initialized.
+pub(crate) fn grow_account(
+    signer_info: &AccountInfo<'_>,
+    account_info: &AccountInfo<'_>,
+    system_program: &AccountInfo<'_>,
+    size: usize,
+) -> ProgramResult {
+    let current_size = account_info.data_len();
+    if current_size >= size {
+        return Ok(());
+    }
+    let rent = solana_program::rent::Rent::get()?;
+    let additional_rent = rent
+        .minimum_balance(size)
+        .saturating_sub(account_info.lamports());
+    sol_log(&format!(
+        "Reallocation: {} -> {} bytes, additional rent: {} lamports",
+        current_size, size, additional_rent
+    ));
+    if additional_rent > 0 {
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(
+                signer_info.key,
+                account_info.key,
+                additional_rent,
+            ),
+            &[
+                signer_info.clone(),
+                account_info.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+    account_info.realloc(size, false)?;
+    Ok(())
+}
+
+/// Grows a legacy Config account to the current size, with the signer paying any
+/// additional rent. New fields are zero-initialized.
+pub(crate) fn grow_config(
+    signer_info: &AccountInfo<'_>,
+    config_info: &AccountInfo<'_>,
+    system_program: &AccountInfo<'_>,
+) -> ProgramResult {
+    grow_account(signer_info, config_info, system_program, Config::SIZE)
+}
+
+/// Upgrades the Config and Treasury accounts to the current layout versions, and
+/// opens the admin audit log if it doesn't exist yet.
+///
+/// Accounts are grown to the current size and any fields added since their version
+/// are backfilled. New parameters should be carved out of `_reserved` and, if zero
+/// is not a safe default, given a step here keyed on the version they arrive in.
+pub fn process_migrate_config(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, config_info, treasury_info, system_program, audit_log_info, admin_accounts @ ..] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG], &ore_api::ID)?;
+    treasury_info
+        .is_writable()?
+        .has_seeds(&[TREASURY], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Grow accounts.
+    grow_config(signer_info, config_info, system_program)?;
+    grow_account(signer_info, treasury_info, system_program, Treasury::SIZE)?;
+
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+    let treasury = treasury_info.as_account_mut::<Treasury>(&ore_api::ID)?;
+    open_audit_log(signer_info, audit_log_info, system_program)?;
+
+    // Version 1 adds the version and reserved space; every field it covers is
+    // zero-means-default, so there is nothing to backfill.
+    let (config_version, treasury_version) = (config.version, treasury.version);
+    config.version = CONFIG_VERSION;
+    treasury.version = TREASURY_VERSION;
+    sol_log(&format!(
+        "Config version {} -> {}, Treasury version {} -> {}",
+        config_version, config.version, treasury_version, treasury.version
+    ));
+
+    Ok(())
+}
@@ -3,9 +3,6 @@ use ore_api::state::Treasury;
 use solana_program::log::sol_log;
 use steel::*;
 
-/// Expected size of the Treasury struct (with discriminator).
-const TREASURY_SIZE: usize = 8 + std::mem::size_of::<Treasury>();
-
 /// Migrate the Treasury account to the new struct size.
 /// This reallocates the account to add the RNG rewards fields.
 /// Admin-only instruction.
@@ -26,11 +23,11 @@ pub fn process_migrate_treasury(accounts: &[AccountInfo<'_>], _data: &[u8]) -> P
     let current_size = treasury_info.data_len();
     sol_log(&format!(
         "Current treasury size: {}, expected: {}",
-        current_size, TREASURY_SIZE
+        current_size, Treasury::SIZE
     ));
 
     // Check if migration is needed
-    if current_size >= TREASURY_SIZE {
+    if current_size >= Treasury::SIZE {
         sol_log("Treasury account already at correct size, no migration needed");
         return Ok(());
     }
@@ -38,12 +35,12 @@ pub fn process_migrate_treasury(accounts: &[AccountInfo<'_>], _data: &[u8]) -> P
     // Calculate additional rent needed
     let rent = solana_program::rent::Rent::get()?;
     let current_rent = rent.minimum_balance(current_size);
-    let new_rent = rent.minimum_balance(TREASURY_SIZE);
+    let new_rent = rent.minimum_balance(Treasury::SIZE);
     let additional_rent = new_rent.saturating_sub(current_rent);
 
     sol_log(&format!(
         "Reallocation: {} -> {} bytes, additional rent: {} lamports",
-        current_size, TREASURY_SIZE, additional_rent
+        current_size, Treasury::SIZE, additional_rent
     ));
 
     // Transfer additional rent if needed
@@ -63,7 +60,7 @@ pub fn process_migrate_treasury(accounts: &[AccountInfo<'_>], _data: &[u8]) -> P
     }
 
     // Reallocate the account
-    treasury_info.realloc(TREASURY_SIZE, false)?;
+    treasury_info.realloc(Treasury::SIZE, false)?;
 
     // The new bytes are already zero-initialized by realloc:
     // - rng_rewards_factor: Numeric (16 bytes) = 0
@@ -71,7 +68,7 @@ pub fn process_migrate_treasury(accounts: &[AccountInfo<'_>], _data: &[u8]) -> P
     // - rng_rewards_pool: u64 (8 bytes) = 0
     sol_log(&format!(
         "Successfully migrated treasury to {} bytes",
-        TREASURY_SIZE
+        Treasury::SIZE
     ));
 
     Ok(())
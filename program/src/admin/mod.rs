@@ -1,25 +1,67 @@
 //! Admin module - administrative functions
 
+mod audit_log;
 mod initialize;
 mod set_admin;
+mod multisig;
+mod pause;
+mod roles;
 mod set_admin_fee;
+mod set_param;
+mod set_revenue_policy;
+mod set_unstake_cooldown;
+mod set_motherlode_params;
+mod set_reward_split_policy;
+mod set_round_permission;
+mod set_vesting_policy;
+mod set_claim_minimums;
 mod set_fee_collector;
 mod set_swap_program;
 mod set_var_address;
 mod new_var;
 mod bury;
+mod distribute_revenue;
+mod route_swap;
 mod wrap;
+mod migrate_account;
+mod migrate_config;
 mod migrate_round;
 mod migrate_miner;
+mod migrate_stake;
+#[cfg(feature = "test-oracle")]
+mod set_test_roll;
+#[cfg(feature = "localnet")]
+mod bootstrap;
 
+pub use audit_log::*;
 pub use initialize::*;
 pub use set_admin::*;
+pub use multisig::*;
+pub use pause::*;
+pub use roles::*;
 pub use set_admin_fee::*;
+pub use set_param::*;
+pub use set_revenue_policy::*;
+pub use set_unstake_cooldown::*;
+pub use set_motherlode_params::*;
+pub use set_reward_split_policy::*;
+pub use set_round_permission::*;
+pub use set_vesting_policy::*;
+pub use set_claim_minimums::*;
 pub use set_fee_collector::*;
 pub use set_swap_program::*;
 pub use set_var_address::*;
 pub use new_var::*;
 pub use bury::*;
+pub use distribute_revenue::*;
+pub use route_swap::*;
 pub use wrap::*;
+pub use migrate_account::*;
+pub use migrate_config::*;
 pub use migrate_round::*;
 pub use migrate_miner::*;
+pub use migrate_stake::*;
+#[cfg(feature = "test-oracle")]
+pub use set_test_roll::*;
+#[cfg(feature = "localnet")]
+pub use bootstrap::*;
@@ -0,0 +1,127 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::log_admin_action;
+
+/// Asserts the config admin approved this instruction.
+///
+/// If the admin is a single key, it must be the signer. If the admin is the admin
+/// multisig, `admin_accounts` holds the multisig account followed by member
+/// signers, and the signer plus those members must meet the threshold.
+pub(crate) fn assert_admin(
+    config: &Config,
+    signer_info: &AccountInfo<'_>,
+    admin_accounts: &[AccountInfo<'_>],
+) -> ProgramResult {
+    assert_authority(&config.admin, signer_info, admin_accounts)
+}
+
+/// Asserts `authority` approved this instruction, either as the signer or, if it is
+/// the admin multisig, through member signatures as for `assert_admin`.
+pub(crate) fn assert_authority(
+    authority: &Pubkey,
+    signer_info: &AccountInfo<'_>,
+    admin_accounts: &[AccountInfo<'_>],
+) -> ProgramResult {
+    if *authority == *signer_info.key && signer_info.is_signer {
+        return Ok(());
+    }
+
+    // Check multisig approvals.
+    let Some((multisig_info, member_infos)) = admin_accounts.split_first() else {
+        sol_log("Signer is not the admin");
+        return Err(OreError::InvalidAuthority.into());
+    };
+    let multisig = multisig_info
+        .has_address(authority)?
+        .has_seeds(&[ADMIN_MULTISIG], &ore_api::ID)?
+        .as_account::<AdminMultisig>(&ore_api::ID)?;
+    let signers: Vec<Pubkey> = std::iter::once(signer_info)
+        .chain(member_infos.iter())
+        .filter(|info| info.is_signer)
+        .map(|info| *info.key)
+        .collect();
+    if !multisig.is_approved(&signers) {
+        sol_log(&format!(
+            "Admin multisig approvals: {} of {}",
+            multisig.approvals(&signers),
+            multisig.threshold
+        ));
+        return Err(OreError::InvalidAuthority.into());
+    }
+
+    Ok(())
+}
+
+/// Creates or updates the admin multisig. The change must be approved by the
+/// current admin, which is the multisig itself once `SetAdmin` points to it.
+pub fn process_set_admin_multisig(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetAdminMultisig::try_from_bytes(data)?;
+    let num_signers = args.num_signers as usize;
+    let threshold = args.threshold;
+    if num_signers == 0 || num_signers > MAX_ADMIN_SIGNERS {
+        sol_log("Admin multisig must have between 1 and 10 members");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if threshold == 0 || threshold as usize > num_signers {
+        sol_log("Threshold must be between 1 and the number of members");
+        return Err(ProgramError::InvalidArgument);
+    }
+    let mut signers = [Pubkey::default(); MAX_ADMIN_SIGNERS];
+    for (i, signer) in args.signers[..num_signers].iter().enumerate() {
+        let signer = Pubkey::new_from_array(*signer);
+        if signer == Pubkey::default() || signers[..i].contains(&signer) {
+            sol_log("Members must be distinct and non-default");
+            return Err(ProgramError::InvalidArgument);
+        }
+        signers[i] = signer;
+    }
+
+    // Load accounts.
+    // Member signers approving the change follow the multisig account.
+    let [signer_info, config_info, system_program, audit_log_info, multisig_info, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+    multisig_info
+        .is_writable()?
+        .has_seeds(&[ADMIN_MULTISIG], &ore_api::ID)?;
+
+    // Approve with the current admin, which may be a single key or this multisig.
+    if multisig_info.data_is_empty() {
+        assert_admin(config, signer_info, &[])?;
+        create_program_account::<AdminMultisig>(
+            multisig_info,
+            system_program,
+            signer_info,
+            &ore_api::ID,
+            &[ADMIN_MULTISIG],
+        )?;
+    } else {
+        assert_admin(config, signer_info, &accounts[4..])?;
+    }
+
+    // Set members.
+    let multisig = multisig_info.as_account_mut::<AdminMultisig>(&ore_api::ID)?;
+    let old_value = audit_value(&[multisig.threshold as u64, multisig.num_signers as u64]);
+    multisig.signers = signers;
+    multisig.num_signers = num_signers as u8;
+    multisig.threshold = threshold;
+    sol_log(&format!(
+        "Admin multisig: {} of {} members",
+        threshold, num_signers
+    ));
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetAdminMultisig,
+        old_value,
+        audit_value(&[threshold as u64, num_signers as u64]),
+    )?;
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use ore_api::prelude::*;
 use steel::*;
 
+use super::assert_role;
+
 /// Creates a new var account.
 pub fn process_new_var(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse data.
@@ -10,19 +12,15 @@ pub fn process_new_var(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
     let samples = u64::from_le_bytes(args.samples);
 
     // Load accounts.
-    let [signer_info, board_info, config_info, provider_info, var_info, system_program, entropy_program] =
+    let [signer_info, board_info, config_info, provider_info, var_info, system_program, entropy_program, roles_info, admin_accounts @ ..] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
     let board = board_info.as_account_mut::<Board>(&ore_api::ID)?;
-    config_info
-        .as_account_mut::<Config>(&ore_api::ID)?
-        .assert_mut_err(
-            |c| c.admin == *signer_info.key,
-            OreError::InvalidAuthority.into(),
-        )?;
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_role(config, roles_info, signer_info, ROLE_OPERATOR, admin_accounts)?;
     entropy_program.is_program(&entropy_api::ID)?;
     system_program.is_program(&system_program::ID)?;
 
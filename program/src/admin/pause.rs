@@ -0,0 +1,182 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::{assert_admin, assert_role, grow_config, log_admin_action};
+
+/// Fails if any of the `modules` are paused.
+pub(crate) fn assert_not_paused(config: &Config, modules: u64) -> ProgramResult {
+    if config.is_paused(modules) {
+        sol_log(&format!("Module paused: {:#x}", config.paused & modules));
+        return Err(OreError::ModulePaused.into());
+    }
+    Ok(())
+}
+
+/// Pauses modules at once. The guardian, or any key holding `ROLE_GUARDIAN`, can pause
+/// without the admin, but only the admin can unpause, and only through `ScheduleUnpause`.
+pub fn process_pause(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = Pause::try_from_bytes(data)?;
+    let modules = u64::from_le_bytes(args.modules);
+    if modules == 0 || modules & !PAUSE_ALL != 0 {
+        sol_log("Invalid pause modules");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [signer_info, config_info, system_program, roles_info, audit_log_info, admin_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Reallocate legacy config accounts that predate the pause flags.
+    grow_config(signer_info, config_info, system_program)?;
+
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    if config.guardian != *signer_info.key || config.guardian == Pubkey::default() {
+        assert_role(config, roles_info, signer_info, ROLE_GUARDIAN, admin_accounts)?;
+    }
+
+    // Pause modules.
+    let old_paused = config.paused;
+    config.pause(modules);
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::Pause,
+        audit_value(&[old_paused]),
+        audit_value(&[config.paused]),
+    )?;
+    sol_log(&format!("Paused: {:#x}", config.paused));
+
+    Ok(())
+}
+
+/// Schedules paused modules to unpause once `UNPAUSE_TIMELOCK_SECONDS` have passed.
+pub fn process_schedule_unpause(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = ScheduleUnpause::try_from_bytes(data)?;
+    let modules = u64::from_le_bytes(args.modules);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, config_info, audit_log_info, admin_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+
+    // Schedule unpause.
+    let Some(effective_at) = config.schedule_unpause(modules, clock.unix_timestamp) else {
+        sol_log("None of the modules are paused");
+        return Err(ProgramError::InvalidArgument);
+    };
+    sol_log(&format!(
+        "Unpause of {:#x} scheduled for {}",
+        config.pending_unpause, effective_at
+    ));
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::ScheduleUnpause,
+        audit_value(&[config.paused]),
+        audit_value(&[config.pending_unpause, effective_at as u64]),
+    )?;
+
+    Ok(())
+}
+
+/// Applies a scheduled unpause after its timelock.
+/// Permissionless so the scheduled change cannot be held back.
+pub fn process_apply_unpause(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, config_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+
+    // Apply unpause.
+    if !config.apply_pending_unpause(clock.unix_timestamp) {
+        sol_log(&format!(
+            "No unpause ready (effective at {})",
+            config.unpause_effective_at
+        ));
+        return Err(OreError::TimelockNotExpired.into());
+    }
+    sol_log(&format!("Paused: {:#x}", config.paused));
+
+    Ok(())
+}
+
+/// Schedules a new guardian, applied once `GUARDIAN_TIMELOCK_SECONDS` have passed.
+/// The default pubkey removes the guardian.
+pub fn process_set_guardian(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetGuardian::try_from_bytes(data)?;
+    let guardian = Pubkey::new_from_array(args.guardian);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, config_info, system_program, audit_log_info, admin_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Reallocate legacy config accounts that predate the guardian.
+    grow_config(signer_info, config_info, system_program)?;
+
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+
+    // Schedule guardian.
+    let old_guardian = config.guardian;
+    let Some(effective_at) = config.schedule_guardian(guardian, clock.unix_timestamp) else {
+        return Err(ProgramError::InvalidArgument);
+    };
+    sol_log(&format!("Guardian {} scheduled for {}", guardian, effective_at));
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetGuardian,
+        old_guardian.to_bytes(),
+        guardian.to_bytes(),
+    )?;
+
+    Ok(())
+}
+
+/// Applies a scheduled guardian after its timelock.
+/// Permissionless so the scheduled change cannot be held back.
+pub fn process_apply_guardian(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, config_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+
+    // Apply guardian.
+    if !config.apply_pending_guardian(clock.unix_timestamp) {
+        sol_log(&format!(
+            "No guardian change ready (effective at {})",
+            config.guardian_effective_at
+        ));
+        return Err(OreError::TimelockNotExpired.into());
+    }
+    sol_log(&format!("Guardian: {}", config.guardian));
+
+    Ok(())
+}
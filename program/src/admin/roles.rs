@@ -0,0 +1,76 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::{assert_admin, log_admin_action};
+
+/// Asserts the signer holds any of the `role` bits, or otherwise that the config
+/// admin approved this instruction as for `assert_admin`.
+pub(crate) fn assert_role(
+    config: &Config,
+    roles_info: &AccountInfo<'_>,
+    signer_info: &AccountInfo<'_>,
+    role: u64,
+    admin_accounts: &[AccountInfo<'_>],
+) -> ProgramResult {
+    roles_info.has_seeds(&[ROLES], &ore_api::ID)?;
+    if signer_info.is_signer && !roles_info.data_is_empty() {
+        let roles = roles_info.as_account::<Roles>(&ore_api::ID)?;
+        if roles.has_role(signer_info.key, role) {
+            return Ok(());
+        }
+    }
+    assert_admin(config, signer_info, admin_accounts)
+}
+
+/// Grants or revokes the roles held by a key.
+pub fn process_set_role(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetRole::try_from_bytes(data)?;
+    let authority = Pubkey::new_from_array(args.authority);
+    let new_roles = u64::from_le_bytes(args.roles);
+    if authority == Pubkey::default() || new_roles & !GRANTABLE_ROLES != 0 {
+        sol_log("Invalid role assignment");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [signer_info, config_info, roles_info, system_program, audit_log_info, admin_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+    roles_info
+        .is_writable()?
+        .has_seeds(&[ROLES], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Open roles account.
+    if roles_info.data_is_empty() {
+        create_program_account::<Roles>(
+            roles_info,
+            system_program,
+            signer_info,
+            &ore_api::ID,
+            &[ROLES],
+        )?;
+    }
+
+    // Set roles.
+    let roles = roles_info.as_account_mut::<Roles>(&ore_api::ID)?;
+    let Some(old_roles) = roles.set_roles(authority, new_roles) else {
+        sol_log("Roles account is full");
+        return Err(ProgramError::InvalidArgument);
+    };
+    sol_log(&format!("Roles for {}: {:#x} -> {:#x}", authority, old_roles, new_roles));
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetRole,
+        authority.to_bytes(),
+        audit_value(&[old_roles, new_roles]),
+    )?;
+
+    Ok(())
+}
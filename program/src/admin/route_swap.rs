@@ -0,0 +1,140 @@
+use ore_api::prelude::*;
+use solana_program::log::{sol_log, sol_log_data};
+use steel::*;
+
+use super::assert_not_paused;
+
+/// Swap treasury tokens through the configured external swap program.
+///
+/// The swap program and its accounts are supplied by the caller, so the CPI is
+/// fenced in: the program must match `config.swap_program`, the treasury is the
+/// only account that signs, and the treasury's balances are checked on both sides
+/// of the swap against `amount_in` and `min_amount_out`.
+pub fn process_route_swap(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data. Anything after the args is forwarded to the swap program.
+    if data.len() < std::mem::size_of::<RouteSwap>() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (args_data, swap_data) = data.split_at(std::mem::size_of::<RouteSwap>());
+    let args = RouteSwap::try_from_bytes(args_data)?;
+    let amount_in = u64::from_le_bytes(args.amount_in);
+    let min_amount_out = u64::from_le_bytes(args.min_amount_out);
+    if amount_in == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    if accounts.len() < 9 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let (ore_accounts, swap_accounts) = accounts.split_at(9);
+    let [signer_info, config_info, treasury_info, treasury_input_info, treasury_output_info, input_mint_info, output_mint_info, token_program, swap_program_info] =
+        ore_accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info
+        .as_account::<Config>(&ore_api::ID)?
+        .assert_err(
+            |c| c.bury_authority == *signer_info.key,
+            OreError::InvalidAuthority.into(),
+        )?;
+    assert_not_paused(config, PAUSE_EXCHANGE)?;
+    treasury_info.has_address(&TREASURY_ADDRESS)?;
+    if config.swap_program == Pubkey::default()
+        || *swap_program_info.key != config.swap_program
+        || input_mint_info.key == output_mint_info.key
+    {
+        return Err(OreError::InvalidAccount.into());
+    }
+    swap_program_info.is_executable()?;
+    input_mint_info.as_mint()?;
+    output_mint_info.as_mint()?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // The route may not call back into this program.
+    if swap_accounts.iter().any(|acc| *acc.key == ore_api::ID) {
+        return Err(OreError::InvalidAccount.into());
+    }
+
+    // Record pre-swap balances.
+    let pre_swap_input_balance = treasury_input_info
+        .as_associated_token_account(treasury_info.key, input_mint_info.key)?
+        .amount();
+    let pre_swap_output_balance = treasury_output_info
+        .as_associated_token_account(treasury_info.key, output_mint_info.key)?
+        .amount();
+    let pre_swap_treasury_lamports = treasury_info.lamports();
+    if pre_swap_input_balance < amount_in {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    // Build swap accounts. Only the treasury is signed for.
+    let accounts: Vec<AccountMeta> = swap_accounts
+        .iter()
+        .map(|acc| AccountMeta {
+            pubkey: *acc.key,
+            is_signer: acc.key == treasury_info.key,
+            is_writable: acc.is_writable,
+        })
+        .collect();
+    let mut accounts_infos: Vec<AccountInfo> = swap_accounts.to_vec();
+    accounts_infos.push(swap_program_info.clone());
+
+    // Invoke swap program.
+    invoke_signed(
+        &Instruction {
+            program_id: config.swap_program,
+            accounts,
+            data: swap_data.to_vec(),
+        },
+        &accounts_infos,
+        &ore_api::ID,
+        &[TREASURY],
+    )?;
+
+    // The swap may only move the treasury's tokens, never its lamports.
+    if treasury_info.lamports() != pre_swap_treasury_lamports {
+        sol_log("Treasury lamports changed during swap");
+        return Err(OreError::InvalidAccount.into());
+    }
+
+    // Check the swap stayed within the requested bounds.
+    let post_swap_input_balance = treasury_input_info
+        .as_associated_token_account(treasury_info.key, input_mint_info.key)?
+        .amount();
+    let post_swap_output_balance = treasury_output_info
+        .as_associated_token_account(treasury_info.key, output_mint_info.key)?
+        .amount();
+    let amount_spent = pre_swap_input_balance
+        .checked_sub(post_swap_input_balance)
+        .ok_or(OreError::ArithmeticOverflow)?;
+    let amount_received = post_swap_output_balance
+        .checked_sub(pre_swap_output_balance)
+        .ok_or(OreError::ArithmeticOverflow)?;
+    if amount_spent > amount_in {
+        sol_log(&format!("Swap spent {} > {}", amount_spent, amount_in));
+        return Err(OreError::SlippageExceeded.into());
+    }
+    if amount_received < min_amount_out {
+        sol_log(&format!("Swap received {} < {}", amount_received, min_amount_out));
+        return Err(OreError::SlippageExceeded.into());
+    }
+
+    sol_log(&format!(
+        "Routed swap: spent {} of {}, received {} of {}",
+        amount_spent, input_mint_info.key, amount_received, output_mint_info.key
+    ));
+    sol_log_data(&[SwapExecutedEvent {
+        disc: OreEvent::SwapExecuted as u64,
+        input_mint: *input_mint_info.key,
+        output_mint: *output_mint_info.key,
+        amount_in: amount_spent,
+        amount_out: amount_received,
+        ts: Clock::get()?.unix_timestamp,
+    }
+    .to_bytes()]);
+
+    Ok(())
+}
@@ -1,27 +1,73 @@
 use ore_api::prelude::*;
+use solana_program::log::sol_log;
 use steel::*;
 
-/// Sets the admin.
+use super::{assert_admin, assert_authority, grow_config, log_admin_action};
+
+/// Proposes a new admin. The current admin stays in control until the proposed
+/// admin accepts with `AcceptAdmin`, so a mistyped address cannot take over the
+/// program. Proposing the default pubkey cancels a pending transfer.
 pub fn process_set_admin(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse data.
     let args = SetAdmin::try_from_bytes(data)?;
     let new_admin = Pubkey::new_from_array(args.admin);
 
     // Load accounts.
-    let [signer_info, config_info, system_program] = accounts else {
+    let [signer_info, config_info, system_program, audit_log_info, admin_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Reallocate legacy config accounts that predate the pending admin.
+    grow_config(signer_info, config_info, system_program)?;
+
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+
+    // Propose admin.
+    let old_pending_admin = config.pending_admin;
+    config.pending_admin = new_admin;
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetAdmin,
+        old_pending_admin.to_bytes(),
+        new_admin.to_bytes(),
+    )?;
+    sol_log(&format!("Proposed admin: {}", new_admin));
+
+    Ok(())
+}
+
+/// Completes an admin transfer. Must be approved by the proposed admin, or by its
+/// members if the proposed admin is the admin multisig.
+pub fn process_accept_admin(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, config_info, audit_log_info, admin_accounts @ ..] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
     let config = config_info
         .as_account_mut::<Config>(&ore_api::ID)?
-        .assert_mut_err(
-            |c| c.admin == *signer_info.key,
-            OreError::InvalidAuthority.into(),
-        )?;
-    system_program.is_program(&system_program::ID)?;
+        .assert_mut(|c| c.pending_admin != Pubkey::default())?;
+    assert_authority(&config.pending_admin, signer_info, admin_accounts)?;
 
     // Set admin.
-    config.admin = new_admin;
+    let old_admin = config.admin;
+    config.admin = config.pending_admin;
+    config.pending_admin = Pubkey::default();
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::AcceptAdmin,
+        old_admin.to_bytes(),
+        config.admin.to_bytes(),
+    )?;
+    sol_log(&format!("Admin accepted: {}", config.admin));
 
     Ok(())
 }
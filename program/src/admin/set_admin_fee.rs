@@ -1,30 +1,49 @@
 use ore_api::prelude::*;
+use solana_program::log::{sol_log, sol_log_data};
 use steel::*;
 
-/// Sets the admin fee.
+use super::{assert_admin, log_admin_action};
+
+/// Sets the admin fee. Fees above `MAX_ADMIN_FEE_BPS` are rejected.
 pub fn process_set_admin_fee(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse data.
     let args = SetAdminFee::try_from_bytes(data)?;
     let new_admin_fee = u64::from_le_bytes(args.admin_fee);
+    if new_admin_fee > MAX_ADMIN_FEE_BPS {
+        sol_log(&format!(
+            "Admin fee {} bps exceeds the {} bps ceiling",
+            new_admin_fee, MAX_ADMIN_FEE_BPS
+        ));
+        return Err(ProgramError::InvalidArgument);
+    }
 
     // Load accounts.
-    let [signer_info, config_info, system_program] = accounts else {
+    let [signer_info, config_info, system_program, audit_log_info, admin_accounts @ ..] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
-    let config = config_info
-        .as_account_mut::<Config>(&ore_api::ID)?
-        .assert_mut_err(
-            |c| c.admin == *signer_info.key,
-            OreError::InvalidAuthority.into(),
-        )?;
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
     system_program.is_program(&system_program::ID)?;
 
-    // Cap admin fee at 1%.
-    let new_admin_fee = new_admin_fee.min(100);
-
     // Set admin fee.
+    let old_admin_fee = config.admin_fee;
     config.admin_fee = new_admin_fee;
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetAdminFee,
+        audit_value(&[old_admin_fee]),
+        audit_value(&[new_admin_fee]),
+    )?;
+
+    sol_log_data(&[AdminFeeSetEvent {
+        disc: OreEvent::AdminFeeSet as u64,
+        old_admin_fee,
+        new_admin_fee,
+        ts: Clock::get()?.unix_timestamp,
+    }
+    .to_bytes()]);
 
     Ok(())
 }
@@ -0,0 +1,52 @@
+use ore_api::prelude::*;
+use solana_program::{log::sol_log, native_token::LAMPORTS_PER_SOL};
+use steel::*;
+
+use super::{assert_admin, grow_config, log_admin_action};
+
+/// Sets the smallest SOL and ORE claims allowed. Balances below these can be swept
+/// from inactive miners with `SweepDust`.
+pub fn process_set_claim_minimums(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetClaimMinimums::try_from_bytes(data)?;
+    let min_sol = u64::from_le_bytes(args.min_sol);
+    let min_ore = u64::from_le_bytes(args.min_ore);
+    if min_sol > LAMPORTS_PER_SOL / 10 || min_ore > ONE_ORE / 10 {
+        sol_log("Claim minimums cannot exceed 0.1 SOL or 0.1 ORE");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [signer_info, config_info, system_program, audit_log_info, admin_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Reallocate legacy config accounts that predate the claim minimums.
+    grow_config(signer_info, config_info, system_program)?;
+
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+
+    // Set claim minimums.
+    let old_value = audit_value(&[config.min_claim_sol, config.min_claim_ore]);
+    config.min_claim_sol = min_sol;
+    config.min_claim_ore = min_ore;
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetClaimMinimums,
+        old_value,
+        audit_value(&[min_sol, min_ore]),
+    )?;
+    sol_log(&format!(
+        "Claim minimums: {} lamports, {} ORE units",
+        min_sol, min_ore
+    ));
+
+    Ok(())
+}
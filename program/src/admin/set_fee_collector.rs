@@ -1,6 +1,8 @@
 use ore_api::prelude::*;
 use steel::*;
 
+use super::{assert_admin, log_admin_action};
+
 /// Sets the fee collector.
 pub fn process_set_fee_collector(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse data.
@@ -8,20 +10,24 @@ pub fn process_set_fee_collector(accounts: &[AccountInfo<'_>], data: &[u8]) -> P
     let new_fee_collector = Pubkey::new_from_array(args.fee_collector);
 
     // Load accounts.
-    let [signer_info, config_info, system_program] = accounts else {
+    let [signer_info, config_info, system_program, audit_log_info, admin_accounts @ ..] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
-    let config = config_info
-        .as_account_mut::<Config>(&ore_api::ID)?
-        .assert_mut_err(
-            |c| c.admin == *signer_info.key,
-            OreError::InvalidAuthority.into(),
-        )?;
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
     system_program.is_program(&system_program::ID)?;
 
     // Set fee collector.
+    let old_fee_collector = config.fee_collector;
     config.fee_collector = new_fee_collector;
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetFeeCollector,
+        old_fee_collector.to_bytes(),
+        new_fee_collector.to_bytes(),
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,59 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::{assert_admin, grow_config, log_admin_action};
+
+/// Sets the motherlode odds and the ORE minted into the motherlode pool each round.
+/// Both are bounded so tokenomics can be tuned but not broken.
+pub fn process_set_motherlode_params(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetMotherlodeParams::try_from_bytes(data)?;
+    let odds = u64::from_le_bytes(args.odds);
+    let funding = u64::from_le_bytes(args.funding);
+    if !(MIN_MOTHERLODE_ODDS..=MAX_MOTHERLODE_ODDS).contains(&odds) {
+        sol_log(&format!(
+            "Motherlode odds must be between 1 in {} and 1 in {}",
+            MIN_MOTHERLODE_ODDS, MAX_MOTHERLODE_ODDS
+        ));
+        return Err(ProgramError::InvalidArgument);
+    }
+    if funding == 0 || funding > MAX_MOTHERLODE_FUNDING {
+        sol_log("Motherlode funding out of bounds");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [signer_info, config_info, system_program, audit_log_info, admin_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Reallocate legacy config accounts that predate the motherlode fields.
+    grow_config(signer_info, config_info, system_program)?;
+
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+
+    // Set motherlode params.
+    let old_value = audit_value(&[config.motherlode_odds, config.motherlode_funding]);
+    config.motherlode_odds = odds;
+    config.motherlode_funding = funding;
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetMotherlodeParams,
+        old_value,
+        audit_value(&[odds, funding]),
+    )?;
+    sol_log(&format!(
+        "Motherlode odds set to 1 in {}, funding {} per round",
+        odds, funding
+    ));
+
+    Ok(())
+}
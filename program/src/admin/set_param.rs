@@ -0,0 +1,72 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::{assert_admin, log_admin_action};
+
+/// Reads a numeric parameter. An unset parameter reads as its schema default.
+pub(crate) fn param_u64(param_info: &AccountInfo<'_>, key: u64) -> Result<u64, ProgramError> {
+    let schema = param_schema(key).ok_or(ProgramError::InvalidArgument)?;
+    param_info.has_seeds(&[PARAM, &key.to_le_bytes()], &ore_api::ID)?;
+    if param_info.data_is_empty() {
+        return Ok(schema.default);
+    }
+    let param = param_info.as_account::<Param>(&ore_api::ID)?;
+    Ok(param.as_u64())
+}
+
+/// Sets a tunable parameter, creating its account on first use. The value must
+/// match the parameter's schema in `PARAM_SCHEMAS`.
+pub fn process_set_param(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetParam::try_from_bytes(data)?;
+    let key = u64::from_le_bytes(args.key);
+    let Some(schema) = param_schema(key) else {
+        sol_log("Unknown param");
+        return Err(ProgramError::InvalidArgument);
+    };
+    if !schema.validate(&args.value) {
+        sol_log("Param value is out of bounds");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [signer_info, config_info, param_info, system_program, audit_log_info, admin_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+    param_info
+        .is_writable()?
+        .has_seeds(&[PARAM, &key.to_le_bytes()], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Open param account.
+    if param_info.data_is_empty() {
+        create_program_account::<Param>(
+            param_info,
+            system_program,
+            signer_info,
+            &ore_api::ID,
+            &[PARAM, &key.to_le_bytes()],
+        )?;
+    }
+
+    // Set param.
+    let param = param_info.as_account_mut::<Param>(&ore_api::ID)?;
+    let old_value = param.value;
+    param.key = key;
+    param.kind = schema.kind;
+    param.value = args.value;
+    sol_log(&format!("Param {} set", key));
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetParam,
+        old_value,
+        args.value,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::{assert_admin, grow_config, log_admin_action};
+
+/// Sets how protocol revenue is split between buyback-burn, staking yield and the
+/// insurance fund.
+pub fn process_set_revenue_policy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetRevenuePolicy::try_from_bytes(data)?;
+    let burn_bps = u64::from_le_bytes(args.burn_bps);
+    let stake_bps = u64::from_le_bytes(args.stake_bps);
+    let insurance_bps = u64::from_le_bytes(args.insurance_bps);
+    if burn_bps.checked_add(stake_bps).and_then(|bps| bps.checked_add(insurance_bps)) != Some(10000) {
+        sol_log("Revenue shares must sum to 10000 bps");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [signer_info, config_info, system_program, audit_log_info, admin_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Reallocate legacy config accounts that predate the revenue policy fields.
+    grow_config(signer_info, config_info, system_program)?;
+
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+
+    // Set revenue policy.
+    let (old_burn_bps, old_stake_bps, old_insurance_bps) = config.revenue_split();
+    config.revenue_burn_bps = burn_bps;
+    config.revenue_stake_bps = stake_bps;
+    config.revenue_insurance_bps = insurance_bps;
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetRevenuePolicy,
+        audit_value(&[old_burn_bps, old_stake_bps, old_insurance_bps]),
+        audit_value(&[burn_bps, stake_bps, insurance_bps]),
+    )?;
+    sol_log(&format!(
+        "Revenue policy: {} bps burn, {} bps stake, {} bps insurance",
+        burn_bps, stake_bps, insurance_bps
+    ));
+
+    Ok(())
+}
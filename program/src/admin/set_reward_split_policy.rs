@@ -0,0 +1,56 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::{assert_admin, grow_config, log_admin_action};
+
+/// Sets how often a round's ORE reward is split among all winners, and what share
+/// of an unsplit reward goes to the top miner.
+pub fn process_set_reward_split_policy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetRewardSplitPolicy::try_from_bytes(data)?;
+    let split_bps = u64::from_le_bytes(args.split_bps);
+    let top_miner_share_pct = u64::from_le_bytes(args.top_miner_share_pct);
+    if split_bps == 0 || split_bps > 10000 {
+        sol_log("Split probability must be between 1 and 10000 bps");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if top_miner_share_pct > 100 {
+        sol_log("Top miner share cannot exceed 100%");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [signer_info, config_info, system_program, audit_log_info, admin_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Reallocate legacy config accounts that predate the split policy fields.
+    grow_config(signer_info, config_info, system_program)?;
+
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+
+    // Set split policy.
+    let old_value = audit_value(&[config.split_reward_bps, config.top_miner_share_pct]);
+    config.split_reward_bps = split_bps;
+    config.top_miner_share_pct = top_miner_share_pct;
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetRewardSplitPolicy,
+        old_value,
+        audit_value(&[split_bps, top_miner_share_pct]),
+    )?;
+    sol_log(&format!(
+        "Reward split policy: {} bps split odds, {}% top miner share",
+        split_bps, top_miner_share_pct
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::{assert_admin, grow_config, log_admin_action};
+
+/// Sets who may reset rounds: anyone, or only operators and crankers.
+pub fn process_set_round_permission(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetRoundPermission::try_from_bytes(data)?;
+    let mode = u64::from_le_bytes(args.mode);
+    if mode != ROUND_PERMISSION_OPEN && mode != ROUND_PERMISSION_OPERATORS {
+        sol_log("Invalid round permission");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [signer_info, config_info, system_program, audit_log_info, admin_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Reallocate legacy config accounts that predate the round permission field.
+    grow_config(signer_info, config_info, system_program)?;
+
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+
+    // Set round permission.
+    let old_mode = config.round_permission;
+    config.round_permission = mode;
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetRoundPermission,
+        audit_value(&[old_mode]),
+        audit_value(&[mode]),
+    )?;
+    sol_log(&format!("Round permission: {} -> {}", old_mode, mode));
+
+    Ok(())
+}
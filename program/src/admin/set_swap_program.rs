@@ -1,23 +1,29 @@
 use ore_api::prelude::*;
 use steel::*;
 
+use super::{assert_admin, log_admin_action};
+
 /// Sets the swap program.
 pub fn process_set_swap_program(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
-    let [signer_info, config_info, new_swap_program_info] = accounts else {
+    let [signer_info, config_info, new_swap_program_info, audit_log_info, admin_accounts @ ..] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
-    let config = config_info
-        .as_account_mut::<Config>(&ore_api::ID)?
-        .assert_mut_err(
-            |c| c.admin == *signer_info.key,
-            OreError::InvalidAuthority.into(),
-        )?;
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
     new_swap_program_info.is_executable()?;
 
     // Set swap program.
+    let old_swap_program = config.swap_program;
     config.swap_program = *new_swap_program_info.key;
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetSwapProgram,
+        old_swap_program.to_bytes(),
+        config.swap_program.to_bytes(),
+    )?;
 
     Ok(())
 }
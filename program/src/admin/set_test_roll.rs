@@ -0,0 +1,60 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::{assert_admin, migrate_account};
+
+/// Injects dice rolls for a round into the test roll oracle (admin only).
+/// Only compiled with the `test-oracle` feature, so mainnet builds cannot inject rolls.
+pub fn process_set_test_roll(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetTestRoll::try_from_bytes(data)?;
+    let round_id = u64::from_le_bytes(args.round_id);
+    let winning_square = u64::from_le_bytes(args.winning_square);
+    if winning_square >= BOARD_SIZE as u64 {
+        sol_log("Winning square out of range");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if args.sub_rolls.iter().any(|&s| s as usize > BOARD_SIZE) {
+        sol_log("Sub-roll square out of range");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [signer_info, config_info, test_roll_oracle_info, system_program, admin_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+    test_roll_oracle_info
+        .is_writable()?
+        .has_seeds(&[TEST_ROLL_ORACLE], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Open the oracle on first use, and grow one that predates sub-rolls.
+    if test_roll_oracle_info.data_is_empty() {
+        create_program_account::<TestRollOracle>(
+            test_roll_oracle_info,
+            system_program,
+            signer_info,
+            &ore_api::ID,
+            &[TEST_ROLL_ORACLE],
+        )?;
+    } else {
+        migrate_account::<TestRollOracle>(signer_info, test_roll_oracle_info, system_program)?;
+    }
+
+    // Record the injected rolls.
+    let oracle = test_roll_oracle_info.as_account_mut::<TestRollOracle>(&ore_api::ID)?;
+    oracle.round_id = round_id;
+    oracle.winning_square = winning_square;
+    oracle.sub_rolls = args.sub_rolls;
+    oracle.sub_rolls[0] = 0;
+
+    sol_log(&format!("TEST MODE: Injected square {} for round {}", winning_square, round_id).as_str());
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::{assert_admin, grow_config, log_admin_action};
+
+/// Maximum unstake cooldown (~7 days of 400ms slots).
+const MAX_UNSTAKE_COOLDOWN_SLOTS: u64 = 1_512_000;
+
+/// Sets the number of slots a stake withdrawal must wait before it can be claimed.
+/// Grows a legacy Config account to the current size on first use.
+pub fn process_set_unstake_cooldown(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetUnstakeCooldown::try_from_bytes(data)?;
+    let cooldown_slots = u64::from_le_bytes(args.cooldown_slots);
+
+    // Load accounts.
+    let [signer_info, config_info, system_program, audit_log_info, admin_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Reallocate legacy config accounts that predate the cooldown field.
+    grow_config(signer_info, config_info, system_program)?;
+
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+
+    // Cap cooldown so withdrawals cannot be locked indefinitely.
+    let cooldown_slots = cooldown_slots.min(MAX_UNSTAKE_COOLDOWN_SLOTS);
+
+    // Set cooldown.
+    let old_cooldown_slots = config.unstake_cooldown_slots;
+    config.unstake_cooldown_slots = cooldown_slots;
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetUnstakeCooldown,
+        audit_value(&[old_cooldown_slots]),
+        audit_value(&[cooldown_slots]),
+    )?;
+    sol_log(&format!("Unstake cooldown set to {} slots", cooldown_slots));
+
+    Ok(())
+}
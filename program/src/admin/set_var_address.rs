@@ -2,27 +2,35 @@ use entropy_api::state::Var;
 use ore_api::prelude::*;
 use steel::*;
 
+use super::{assert_admin, log_admin_action};
+
 /// Sets the swap program.
 pub fn process_set_var_address(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
-    let [signer_info, board_info, config_info, new_var_address_info] = accounts else {
+    let [signer_info, board_info, config_info, new_var_address_info, audit_log_info, admin_accounts @ ..] =
+        accounts
+    else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
     board_info.as_account::<Board>(&ore_api::ID)?;
-    let config = config_info
-        .as_account_mut::<Config>(&ore_api::ID)?
-        .assert_mut_err(
-            |c| c.admin == *signer_info.key,
-            OreError::InvalidAuthority.into(),
-        )?;
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
     new_var_address_info
         .as_account::<Var>(&entropy_api::ID)?
         .assert(|v| v.authority == *board_info.key)?
         .assert(|v| v.samples > 0)?;
 
     // Set var address.
+    let old_var_address = config.var_address;
     config.var_address = *new_var_address_info.key;
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetVarAddress,
+        old_var_address.to_bytes(),
+        config.var_address.to_bytes(),
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,52 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::{assert_admin, grow_config, log_admin_action};
+
+/// Sets the ORE claim size above which claims vest, and how long they vest for.
+/// A zero threshold disables vesting.
+pub fn process_set_vesting_policy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetVestingPolicy::try_from_bytes(data)?;
+    let threshold = u64::from_le_bytes(args.threshold);
+    let duration_slots = u64::from_le_bytes(args.duration_slots);
+    if threshold > 0 && (duration_slots == 0 || duration_slots > MAX_VESTING_DURATION_SLOTS) {
+        sol_log("Vesting duration must be between 1 slot and ~30 days");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [signer_info, config_info, system_program, audit_log_info, admin_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Reallocate legacy config accounts that predate the vesting fields.
+    grow_config(signer_info, config_info, system_program)?;
+
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+
+    // Set vesting policy.
+    let old_value = audit_value(&[config.vesting_threshold, config.vesting_duration_slots]);
+    config.vesting_threshold = threshold;
+    config.vesting_duration_slots = duration_slots;
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetVestingPolicy,
+        old_value,
+        audit_value(&[threshold, duration_slots]),
+    )?;
+    sol_log(&format!(
+        "Vesting policy: claims above {} vest over {} slots",
+        threshold, duration_slots
+    ));
+
+    Ok(())
+}
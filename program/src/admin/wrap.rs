@@ -21,20 +21,20 @@ pub fn process_wrap(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult
 
     // Get amount
     let one_sol = 1_000_000_000;
-    let amount = (one_sol * 100).min(treasury.balance);
+    let amount = (one_sol * 100).min(treasury.buyback_balance);
 
     // Send SOL to the WSOL account.
     treasury_info.send(amount, treasury_sol_info);
 
     // Check min balance.
-    let min_balance = Rent::get()?.minimum_balance(std::mem::size_of::<Treasury>());
+    let min_balance = Rent::get()?.minimum_balance(Treasury::SIZE);
     assert!(
         treasury_info.lamports() >= min_balance,
         "Insufficient SOL balance"
     );
 
     // Update treasury.
-    treasury.balance -= amount;
+    treasury.buyback_balance -= amount;
 
     Ok(())
 }
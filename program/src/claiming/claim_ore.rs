@@ -3,19 +3,29 @@ use solana_program::log::sol_log;
 use spl_token::amount_to_ui_amount;
 use steel::*;
 
+use crate::admin::assert_not_paused;
+use crate::session::validate_claim_signer;
+
 /// Claims a block reward.
+///
+/// Claims above the configured vesting threshold are added to the claimant's
+/// vesting schedule instead of paying out, and are released with `ClaimVested`.
+/// An optional trailing stake account for the miner authority discounts the claim
+/// fee by stake tier. A session delegate of the authority with the Claims
+/// permission may also claim, passing the session account after the stake;
+/// proceeds always go to the authority's token account.
 pub fn process_claim_ore(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
     let clock = Clock::get()?;
-    let [signer_info, miner_info, mint_info, recipient_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program] =
+    let [signer_info, miner_info, mint_info, recipient_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program, config_info, vesting_info, optional_accounts @ ..] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
-    let miner = miner_info
-        .as_account_mut::<Miner>(&ore_api::ID)?
-        .assert_mut(|m| m.authority == *signer_info.key)?;
+    let miner = miner_info.as_account_mut::<Miner>(&ore_api::ID)?;
+    let authority = miner.authority;
+    validate_claim_signer(signer_info, miner, optional_accounts.get(1))?;
     mint_info.has_address(&MINT_ADDRESS)?.as_mint()?;
     recipient_info.is_writable()?;
     let treasury = treasury_info.as_account_mut::<Treasury>(&ore_api::ID)?;
@@ -23,9 +33,18 @@ pub fn process_claim_ore(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramR
     system_program.is_program(&system_program::ID)?;
     token_program.is_program(&spl_token::ID)?;
     associated_token_program.is_program(&spl_associated_token_account::ID)?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_CLAIMING)?;
+    vesting_info
+        .is_writable()?
+        .has_seeds(&[VESTING, &authority.to_bytes()], &ore_api::ID)?;
 
-    // Load recipient.
+    // Load recipient. Delegates cannot open it for the authority.
     if recipient_info.data_is_empty() {
+        if authority != *signer_info.key {
+            sol_log("Authority token account must exist for delegated claims");
+            return Err(ProgramError::UninitializedAccount);
+        }
         create_associated_token_account(
             signer_info,
             signer_info,
@@ -36,11 +55,30 @@ pub fn process_claim_ore(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramR
             associated_token_program,
         )?;
     } else {
-        recipient_info.as_associated_token_account(signer_info.key, mint_info.key)?;
+        recipient_info.as_associated_token_account(&authority, mint_info.key)?;
     }
 
+    // Read the claim fee discount from the authority's stake, if any.
+    let discount_bps = match optional_accounts.first() {
+        Some(stake_info) => {
+            stake_info.has_seeds(&[STAKE, &authority.to_bytes()], &ore_api::ID)?;
+            if stake_info.data_is_empty() {
+                0
+            } else {
+                stake_info
+                    .as_account::<Stake>(&ore_api::ID)?
+                    .claim_fee_discount_bps()
+            }
+        }
+        None => 0,
+    };
+
     // Normalize amount.
-    let amount = miner.claim_ore(&clock, treasury);
+    let amount = miner.claim_ore_discounted(&clock, treasury, discount_bps);
+    if amount < config.min_claim_ore {
+        sol_log("ORE claim is below the minimum");
+        return Err(OreError::ClaimBelowMinimum.into());
+    }
 
     sol_log(
         &format!(
@@ -50,6 +88,47 @@ pub fn process_claim_ore(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramR
         .as_str(),
     );
 
+    // Large claims vest. The ORE stays in the treasury until released.
+    if config.should_vest(amount) {
+        let vesting = if vesting_info.data_is_empty() {
+            create_program_account::<Vesting>(
+                vesting_info,
+                system_program,
+                signer_info,
+                &ore_api::ID,
+                &[VESTING, &authority.to_bytes()],
+            )?;
+            let vesting = vesting_info.as_account_mut::<Vesting>(&ore_api::ID)?;
+            vesting.authority = authority;
+            vesting
+        } else {
+            vesting_info.as_account_mut::<Vesting>(&ore_api::ID)?
+        };
+
+        // Pay out anything already vested before restarting the schedule.
+        let vested = vesting.release(clock.slot);
+        vesting.add(amount, clock.slot, config.vesting_duration_slots);
+        sol_log(
+            &format!(
+                "Vesting {} ORE over {} slots",
+                amount_to_ui_amount(vesting.total, TOKEN_DECIMALS),
+                config.vesting_duration_slots
+            )
+            .as_str(),
+        );
+        if vested > 0 {
+            transfer_signed(
+                treasury_info,
+                treasury_tokens_info,
+                recipient_info,
+                token_program,
+                vested,
+                &[TREASURY],
+            )?;
+        }
+        return Ok(());
+    }
+
     // Transfer reward to recipient.
     transfer_signed(
         treasury_info,
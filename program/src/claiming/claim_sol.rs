@@ -2,26 +2,50 @@ use ore_api::prelude::*;
 use solana_program::{log::sol_log, native_token::lamports_to_sol};
 use steel::*;
 
+use crate::admin::assert_not_paused;
+use crate::session::validate_claim_signer;
+
 /// Claims a block reward.
+///
+/// A session delegate of the miner authority with the Claims permission may also
+/// claim, passing the authority and session accounts after the config. Proceeds
+/// always go to the authority.
 pub fn process_claim_sol(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
     let clock = Clock::get()?;
-    let [signer_info, miner_info, system_program] = accounts else {
+    let [signer_info, miner_info, system_program, config_info, optional_accounts @ ..] = accounts
+    else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
-    let miner = miner_info
-        .as_account_mut::<Miner>(&ore_api::ID)?
-        .assert_mut(|m| m.authority == *signer_info.key)?;
+    let miner = miner_info.as_account_mut::<Miner>(&ore_api::ID)?;
+    validate_claim_signer(signer_info, miner, optional_accounts.get(1))?;
     system_program.is_program(&system_program::ID)?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_CLAIMING)?;
+
+    // Pay the authority, whoever signs.
+    let recipient_info = if miner.authority == *signer_info.key {
+        signer_info
+    } else {
+        optional_accounts
+            .first()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?
+            .is_writable()?
+            .has_address(&miner.authority)?
+    };
 
     // Normalize amount.
     let amount = miner.claim_sol(&clock);
+    if amount < config.min_claim_sol {
+        sol_log("SOL claim is below the minimum");
+        return Err(OreError::ClaimBelowMinimum.into());
+    }
 
     sol_log(&format!("Claiming {} SOL", lamports_to_sol(amount)).as_str());
 
     // Transfer reward to recipient.
-    miner_info.send(amount, signer_info);
+    miner_info.send(amount, recipient_info);
 
     Ok(())
 }
@@ -0,0 +1,77 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use spl_token::amount_to_ui_amount;
+use steel::*;
+
+use crate::admin::assert_not_paused;
+
+/// Releases vested ORE from a large claim. The vesting account is closed once the
+/// schedule has been released in full.
+pub fn process_claim_vested(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, vesting_info, mint_info, recipient_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program, config_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let vesting = vesting_info
+        .as_account_mut::<Vesting>(&ore_api::ID)?
+        .assert_mut(|v| v.authority == *signer_info.key)?;
+    mint_info.has_address(&MINT_ADDRESS)?.as_mint()?;
+    recipient_info.is_writable()?;
+    treasury_info.as_account::<Treasury>(&ore_api::ID)?;
+    treasury_tokens_info.as_associated_token_account(&treasury_info.key, &mint_info.key)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_CLAIMING)?;
+
+    // Load recipient.
+    if recipient_info.data_is_empty() {
+        create_associated_token_account(
+            signer_info,
+            signer_info,
+            recipient_info,
+            mint_info,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+    } else {
+        recipient_info.as_associated_token_account(signer_info.key, mint_info.key)?;
+    }
+
+    // Release vested amount.
+    let amount = vesting.release(clock.slot);
+    let complete = vesting.is_complete();
+
+    sol_log(
+        &format!(
+            "Claiming {} vested ORE",
+            amount_to_ui_amount(amount, TOKEN_DECIMALS)
+        )
+        .as_str(),
+    );
+
+    // Transfer vested ORE to recipient.
+    if amount > 0 {
+        transfer_signed(
+            treasury_info,
+            treasury_tokens_info,
+            recipient_info,
+            token_program,
+            amount,
+            &[TREASURY],
+        )?;
+    }
+
+    // Close the vesting account once fully released.
+    if complete {
+        vesting_info.close(signer_info)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use ore_api::prelude::*;
+use solana_program::{log::sol_log, native_token::lamports_to_sol};
+use spl_token::amount_to_ui_amount;
+use steel::*;
+
+use crate::admin::{assert_not_paused, param_u64};
+
+/// Pushes matured claims out to many owners at once, tipping the signer for each
+/// account paid.
+///
+/// For `CLAIM_CRANK_SOL`, entries are (miner, authority) pairs after the config and
+/// tip param. Checkpointed miners with SOL rewards at or above the claim minimum are
+/// paid to their authority, less the `PARAM_CLAIM_CRANK_TIP_SOL` tip.
+///
+/// For `CLAIM_CRANK_VESTED`, entries are (vesting, authority ORE token account) pairs
+/// after the token accounts. Everything vested so far is released to the authority,
+/// less the `PARAM_CLAIM_CRANK_TIP_ORE` tip, which is paid to the signer's ORE token account.
+///
+/// Entries whose payout would not cover the tip twice over are skipped.
+pub fn process_crank_claims(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = CrankClaims::try_from_bytes(data)?;
+
+    match args.kind {
+        CLAIM_CRANK_SOL => crank_sol_claims(accounts),
+        CLAIM_CRANK_VESTED => crank_vested_claims(accounts),
+        _ => {
+            sol_log("Invalid claim crank kind");
+            Err(ProgramError::InvalidArgument)
+        }
+    }
+}
+
+fn crank_sol_claims(accounts: &[AccountInfo<'_>]) -> ProgramResult {
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, config_info, param_info, entries @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_CLAIMING)?;
+    let tip = param_u64(param_info, PARAM_CLAIM_CRANK_TIP_SOL)?;
+    if entries.is_empty() || entries.len() % 2 != 0 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    // Pay each miner's authority.
+    let mut total_paid = 0;
+    let mut total_tip = 0;
+    for pair in entries.chunks_exact(2) {
+        let [miner_info, authority_info] = pair else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let miner = miner_info.as_account_mut::<Miner>(&ore_api::ID)?;
+        authority_info.is_writable()?.has_address(&miner.authority)?;
+        if miner.checkpoint_id != miner.round_id
+            || miner.rewards_sol < config.min_claim_sol
+            || miner.rewards_sol < 2 * tip
+        {
+            continue;
+        }
+
+        let amount = miner.claim_sol(&clock);
+        miner_info.send(amount - tip, authority_info);
+        miner_info.send(tip, signer_info);
+        total_paid += amount - tip;
+        total_tip += tip;
+    }
+
+    sol_log(
+        &format!(
+            "Pushed {} SOL in claims, tip: {} SOL",
+            lamports_to_sol(total_paid),
+            lamports_to_sol(total_tip)
+        )
+        .as_str(),
+    );
+
+    Ok(())
+}
+
+fn crank_vested_claims(accounts: &[AccountInfo<'_>]) -> ProgramResult {
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, config_info, param_info, signer_tokens_info, mint_info, treasury_info, treasury_tokens_info, token_program, entries @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_CLAIMING)?;
+    let tip = param_u64(param_info, PARAM_CLAIM_CRANK_TIP_ORE)?;
+    mint_info.has_address(&MINT_ADDRESS)?.as_mint()?;
+    signer_tokens_info
+        .is_writable()?
+        .as_associated_token_account(signer_info.key, mint_info.key)?;
+    treasury_info.as_account::<Treasury>(&ore_api::ID)?;
+    treasury_tokens_info
+        .is_writable()?
+        .as_associated_token_account(&treasury_info.key, &mint_info.key)?;
+    token_program.is_program(&spl_token::ID)?;
+    if entries.is_empty() || entries.len() % 2 != 0 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    // Release each vesting schedule to its authority.
+    let mut total_paid = 0;
+    let mut total_tip = 0;
+    for pair in entries.chunks_exact(2) {
+        let [vesting_info, recipient_info] = pair else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let vesting = vesting_info.as_account_mut::<Vesting>(&ore_api::ID)?;
+        recipient_info
+            .is_writable()?
+            .as_associated_token_account(&vesting.authority, mint_info.key)?;
+        if vesting.claimable(clock.slot) < 2 * tip {
+            continue;
+        }
+
+        let amount = vesting.release(clock.slot);
+        transfer_signed(
+            treasury_info,
+            treasury_tokens_info,
+            recipient_info,
+            token_program,
+            amount - tip,
+            &[TREASURY],
+        )?;
+        total_paid += amount - tip;
+        total_tip += tip;
+    }
+
+    // Pay the tip in one transfer.
+    if total_tip > 0 {
+        transfer_signed(
+            treasury_info,
+            treasury_tokens_info,
+            signer_tokens_info,
+            token_program,
+            total_tip,
+            &[TREASURY],
+        )?;
+    }
+
+    sol_log(
+        &format!(
+            "Released {} vested ORE, tip: {} ORE",
+            amount_to_ui_amount(total_paid, TOKEN_DECIMALS),
+            amount_to_ui_amount(total_tip, TOKEN_DECIMALS)
+        )
+        .as_str(),
+    );
+
+    Ok(())
+}
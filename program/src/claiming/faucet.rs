@@ -0,0 +1,94 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use spl_token::amount_to_ui_amount;
+use steel::*;
+
+/// Mints test RNG to the signer, once per faucet cooldown.
+pub fn process_faucet_rng(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    process_faucet(accounts, RNG_MINT_ADDRESS)
+}
+
+/// Mints test CRAP to the signer, once per faucet cooldown.
+pub fn process_faucet_crap(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    process_faucet(accounts, CRAP_MINT_ADDRESS)
+}
+
+/// Drips a fixed amount of `mint` to the signer, so testers can play without access to
+/// the admin key. Only compiled with the `devnet` feature: the faucet PDA must hold the
+/// mint authority of the devnet test mints.
+fn process_faucet(accounts: &[AccountInfo<'_>], mint: Pubkey) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, faucet_claim_info, faucet_info, mint_info, recipient_info, system_program, token_program, associated_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    faucet_claim_info
+        .is_writable()?
+        .has_seeds(&[FAUCET_CLAIM, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    faucet_info.has_seeds(&[FAUCET], &ore_api::ID)?;
+    mint_info.is_writable()?.has_address(&mint)?.as_mint()?;
+    recipient_info.is_writable()?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    // Open the claim account on first use.
+    if faucet_claim_info.data_is_empty() {
+        create_program_account::<FaucetClaim>(
+            faucet_claim_info,
+            system_program,
+            signer_info,
+            &ore_api::ID,
+            &[FAUCET_CLAIM, &signer_info.key.to_bytes()],
+        )?;
+        faucet_claim_info.as_account_mut::<FaucetClaim>(&ore_api::ID)?.authority = *signer_info.key;
+    }
+    let faucet_claim = faucet_claim_info
+        .as_account_mut::<FaucetClaim>(&ore_api::ID)?
+        .assert_mut(|c| c.authority == *signer_info.key)?;
+
+    // Enforce the cooldown.
+    let clock = Clock::get()?;
+    let (amount, decimals, claimed) = if mint == RNG_MINT_ADDRESS {
+        let claimed = faucet_claim.claim_rng(FAUCET_RNG_AMOUNT, clock.slot);
+        (FAUCET_RNG_AMOUNT, RNG_TOKEN_DECIMALS, claimed)
+    } else {
+        let claimed = faucet_claim.claim_crap(FAUCET_CRAP_AMOUNT, clock.slot);
+        (FAUCET_CRAP_AMOUNT, CRAP_TOKEN_DECIMALS, claimed)
+    };
+    if !claimed {
+        sol_log("Faucet is cooling down for this tester");
+        return Err(OreError::FaucetCooldown.into());
+    }
+
+    // Open the recipient token account.
+    if recipient_info.data_is_empty() {
+        create_associated_token_account(
+            signer_info,
+            signer_info,
+            recipient_info,
+            mint_info,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+    } else {
+        recipient_info.as_associated_token_account(signer_info.key, mint_info.key)?;
+    }
+
+    // Mint the drip.
+    mint_to_signed(
+        mint_info,
+        recipient_info,
+        faucet_info,
+        token_program,
+        amount,
+        &[FAUCET],
+    )?;
+
+    sol_log(&format!("Faucet: minted {} to {}", amount_to_ui_amount(amount, decimals), signer_info.key).as_str());
+
+    Ok(())
+}
@@ -2,6 +2,16 @@
 
 mod claim_sol;
 mod claim_ore;
+mod claim_vested;
+mod sweep_dust;
+mod crank_claims;
+#[cfg(feature = "devnet")]
+mod faucet;
 
 pub use claim_sol::*;
 pub use claim_ore::*;
+pub use claim_vested::*;
+pub use sweep_dust::*;
+pub use crank_claims::*;
+#[cfg(feature = "devnet")]
+pub use faucet::*;
@@ -0,0 +1,65 @@
+use ore_api::prelude::*;
+use solana_program::{log::sol_log, native_token::lamports_to_sol};
+use spl_token::amount_to_ui_amount;
+use steel::*;
+
+use crate::admin::assert_not_paused;
+
+/// Sweeps sub-minimum reward balances from inactive miners.
+///
+/// Miners are passed as trailing accounts. A miner is swept only once it has sat
+/// out `DUST_SWEEP_INACTIVE_ROUNDS` rounds and has no unsettled round. SOL dust is
+/// vaulted in the treasury, and ORE dust is shared with miners who have not
+/// claimed, like the claim fee. Miners with balances at or above the minimums are
+/// skipped.
+pub fn process_sweep_dust(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, board_info, config_info, treasury_info, miner_accounts @ ..] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let board = board_info.as_account::<Board>(&ore_api::ID)?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_CLAIMING)?;
+    let treasury = treasury_info.as_account_mut::<Treasury>(&ore_api::ID)?;
+    if miner_accounts.is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    // Sweep each inactive miner.
+    let mut total_sol = 0;
+    let mut total_ore = 0;
+    for miner_info in miner_accounts {
+        let miner = miner_info.as_account_mut::<Miner>(&ore_api::ID)?;
+        if miner.checkpoint_id != miner.round_id
+            || miner.round_id + DUST_SWEEP_INACTIVE_ROUNDS > board.round_id
+        {
+            continue;
+        }
+
+        // Sweep SOL dust into the treasury.
+        if miner.rewards_sol > 0 && miner.rewards_sol < config.min_claim_sol {
+            let dust = miner.rewards_sol;
+            miner.rewards_sol = 0;
+            miner.lifetime_rewards_sol = miner.lifetime_rewards_sol.saturating_sub(dust);
+            miner_info.send(dust, treasury_info);
+            treasury.balance += dust;
+            total_sol += dust;
+        }
+
+        // Share ORE dust with unclaimed miners.
+        total_ore += miner.sweep_dust_ore(treasury, config.min_claim_ore);
+    }
+
+    sol_log(
+        &format!(
+            "Swept {} SOL and {} ORE of dust",
+            lamports_to_sol(total_sol),
+            amount_to_ui_amount(total_ore, TOKEN_DECIMALS)
+        )
+        .as_str(),
+    );
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+//! Read-only solvency check
+
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+/// Fails unless the house bankroll covers reserved payouts and the craps vault holds at
+/// least the bankroll plus escrow. No account is modified, so monitoring bots can run
+/// it through simulateTransaction and tests can use it as an invariant.
+pub fn process_assert_solvency(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = AssertSolvency::try_from_bytes(data)?;
+    let escrow = u64::from_le_bytes(args.escrow);
+
+    // Load accounts.
+    let [craps_game_info, vault_crap_ata] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let craps_game = craps_game_info
+        .has_seeds(&[CRAPS_GAME], &ore_api::ID)?
+        .as_account::<CrapsGame>(&ore_api::ID)?;
+    let vault_balance = vault_crap_ata
+        .as_associated_token_account(&craps_vault_pda().0, &CRAP_MINT_ADDRESS)?
+        .amount();
+
+    sol_log(&format!(
+        "Bankroll: {}, reserved: {}, vault: {}, escrow: {}",
+        craps_game.house_bankroll, craps_game.reserved_payouts, vault_balance, escrow
+    ));
+    craps_game.check_solvency(vault_balance, escrow)?;
+
+    Ok(())
+}
@@ -39,27 +39,8 @@ pub fn process_claim_craps_winnings(accounts: &[AccountInfo<'_>], _data: &[u8])
     let (_, craps_vault_bump) = ore_api::state::craps_vault_pda();
 
     // Load accounts.
-    if craps_game_info.data_is_empty() {
-        sol_log("Craps game not initialized");
-        return Err(ProgramError::UninitializedAccount);
-    }
-    if craps_position_info.data_is_empty() {
-        sol_log("Craps position not initialized");
-        return Err(ProgramError::UninitializedAccount);
-    }
-
-    // Verify account ownership
-    if craps_game_info.owner != &ore_api::ID {
-        sol_log("CrapsGame account not owned by program");
-        return Err(ProgramError::IncorrectProgramId);
-    }
-    if craps_position_info.owner != &ore_api::ID {
-        sol_log("CrapsPosition account not owned by program");
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
-    let craps_game = craps_game_info.as_account_mut::<CrapsGame>(&ore_api::ID)?;
-    let craps_position = craps_position_info.as_account_mut::<CrapsPosition>(&ore_api::ID)?;
+    let craps_game = craps_game_info.as_checked_account_mut::<CrapsGame>()?;
+    let craps_position = craps_position_info.as_checked_account_mut::<CrapsPosition>()?;
 
     // Check authority.
     if craps_position.authority != *signer_info.key {
@@ -71,13 +52,13 @@ pub fn process_claim_craps_winnings(accounts: &[AccountInfo<'_>], _data: &[u8])
     let amount = craps_position.pending_winnings;
     if amount == 0 {
         sol_log("No pending winnings to claim");
-        return Err(ProgramError::InvalidArgument);
+        return Err(OreError::NoPendingWinnings.into());
     }
 
     // Verify house bankroll has enough for the payout.
     if craps_game.house_bankroll < amount {
         sol_log("Insufficient house bankroll for payout");
-        return Err(ProgramError::InsufficientFunds);
+        return Err(OreError::InsufficientBankroll.into());
     }
 
     sol_log(&format!("Claiming {} CRAP tokens from craps vault", amount).as_str());
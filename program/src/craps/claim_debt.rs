@@ -39,23 +39,8 @@ pub fn process_claim_craps_debt(accounts: &[AccountInfo<'_>], _data: &[u8]) -> P
     vault_crap_ata.is_writable()?;
     token_program.is_program(&spl_token::ID)?;
 
-    // Verify account ownership
-    if craps_game_info.owner != &ore_api::ID {
-        sol_log("CrapsGame account not owned by program");
-        return Err(ProgramError::IncorrectProgramId);
-    }
-    if craps_position_info.owner != &ore_api::ID {
-        sol_log("CrapsPosition account not owned by program");
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
-    if craps_game_info.data_is_empty() || craps_position_info.data_is_empty() {
-        sol_log("Accounts not initialized");
-        return Err(ProgramError::UninitializedAccount);
-    }
-
-    let craps_game = craps_game_info.as_account_mut::<CrapsGame>(&ore_api::ID)?;
-    let craps_position = craps_position_info.as_account_mut::<CrapsPosition>(&ore_api::ID)?;
+    let craps_game = craps_game_info.as_checked_account_mut::<CrapsGame>()?;
+    let craps_position = craps_position_info.as_checked_account_mut::<CrapsPosition>()?;
 
     // Verify signer is the position authority
     if craps_position.authority != *signer_info.key {
@@ -85,7 +70,7 @@ pub fn process_claim_craps_debt(accounts: &[AccountInfo<'_>], _data: &[u8]) -> P
     }
 
     // Transfer tokens from vault to user
-    let vault_bump = Pubkey::find_program_address(&[CRAPS_VAULT], &ore_api::ID).1;
+    let vault_bump = craps_vault_pda().1;
     invoke_signed(
         &spl_token::instruction::transfer(
             &spl_token::ID,
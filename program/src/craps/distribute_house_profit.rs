@@ -0,0 +1,204 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use solana_program::program::invoke_signed;
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::amount_to_ui_amount;
+use steel::*;
+
+/// Routes the stakers' share of net craps house wins into the staking yield.
+///
+/// Once per craps epoch, the configured share of net wins above the last distribution is
+/// taken from the house bankroll, swapped CRAP -> ORE through the configured swap
+/// program, and credited to stakers through the Treasury's stake rewards factor.
+///
+/// The swap is fenced in like `RouteSwap`: only the bury authority may run it, the
+/// treasury is the only account that signs, it may not call back into this program, and
+/// it must deliver at least `min_ore_out`. CRAP the route leaves unspent goes back to
+/// the vault and stays owed.
+///
+/// Account layout:
+/// 0: signer - config bury authority
+/// 1: config
+/// 2: craps_game (PDA, writable)
+/// 3: craps_vault (PDA) - authority of the vault CRAP account
+/// 4: vault_crap_tokens (writable) - the house bankroll
+/// 5: treasury (PDA, writable)
+/// 6: treasury_crap_tokens (writable) - swap input
+/// 7: treasury_tokens (writable) - swap output, the stakers' ORE yield
+/// 8: crap_mint
+/// 9: mint - ORE mint
+/// 10: token_program
+/// 11: swap_program
+/// 12..: the swap program's accounts
+pub fn process_distribute_house_profit(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data. Anything after the args is forwarded to the swap program.
+    if data.len() < std::mem::size_of::<DistributeHouseProfit>() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (args_data, swap_data) = data.split_at(std::mem::size_of::<DistributeHouseProfit>());
+    let args = DistributeHouseProfit::try_from_bytes(args_data)?;
+    let min_ore_out = u64::from_le_bytes(args.min_ore_out);
+
+    // Load accounts.
+    if accounts.len() < 12 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let (ore_accounts, swap_accounts) = accounts.split_at(12);
+    let [signer_info, config_info, craps_game_info, craps_vault_info, vault_crap_ata, treasury_info, treasury_crap_ata, treasury_tokens_info, crap_mint, mint_info, token_program, swap_program_info] =
+        ore_accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info
+        .as_account::<Config>(&ore_api::ID)?
+        .assert_err(
+            |c| c.bury_authority == *signer_info.key,
+            OreError::InvalidAuthority.into(),
+        )?;
+    let craps_game = craps_game_info
+        .is_writable()?
+        .has_seeds(&[CRAPS_GAME], &ore_api::ID)?
+        .as_account_mut::<CrapsGame>(&ore_api::ID)?;
+    craps_vault_info.has_seeds(&[CRAPS_VAULT], &ore_api::ID)?;
+    vault_crap_ata
+        .is_writable()?
+        .has_address(&get_associated_token_address(craps_vault_info.key, &CRAP_MINT_ADDRESS))?;
+    let treasury = treasury_info
+        .is_writable()?
+        .has_address(&TREASURY_ADDRESS)?
+        .as_account_mut::<Treasury>(&ore_api::ID)?;
+    crap_mint.has_address(&CRAP_MINT_ADDRESS)?;
+    mint_info.has_address(&MINT_ADDRESS)?;
+    token_program.is_program(&spl_token::ID)?;
+    if config.swap_program == Pubkey::default() || *swap_program_info.key != config.swap_program {
+        return Err(OreError::InvalidAccount.into());
+    }
+    swap_program_info.is_executable()?;
+
+    // The route may not call back into this program.
+    if swap_accounts.iter().any(|acc| *acc.key == ore_api::ID) {
+        return Err(OreError::InvalidAccount.into());
+    }
+
+    // Amount of CRAP owed to stakers this epoch.
+    let Some(amount) = craps_game.staker_distribution() else {
+        sol_log("No house profit to distribute");
+        return Ok(());
+    };
+    if treasury.total_staked == 0 {
+        sol_log("No stakers to distribute to");
+        return Ok(());
+    }
+
+    // Record pre-swap balances.
+    let pre_swap_crap_balance = treasury_crap_ata
+        .is_writable()?
+        .as_associated_token_account(treasury_info.key, crap_mint.key)?
+        .amount();
+    let pre_swap_ore_balance = treasury_tokens_info
+        .is_writable()?
+        .as_associated_token_account(treasury_info.key, mint_info.key)?
+        .amount();
+    let pre_swap_treasury_lamports = treasury_info.lamports();
+
+    // Move the CRAP from the house vault to the treasury.
+    let (_, craps_vault_bump) = craps_vault_pda();
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            &spl_token::ID,
+            vault_crap_ata.key,
+            treasury_crap_ata.key,
+            craps_vault_info.key,
+            &[],
+            amount,
+        )?,
+        &[
+            vault_crap_ata.clone(),
+            treasury_crap_ata.clone(),
+            craps_vault_info.clone(),
+            token_program.clone(),
+        ],
+        &[&[CRAPS_VAULT, &[craps_vault_bump]]],
+    )?;
+
+    // Swap it. Only the treasury signs.
+    let metas: Vec<AccountMeta> = swap_accounts
+        .iter()
+        .map(|acc| AccountMeta {
+            pubkey: *acc.key,
+            is_signer: acc.key == treasury_info.key,
+            is_writable: acc.is_writable,
+        })
+        .collect();
+    let mut account_infos: Vec<AccountInfo> = swap_accounts.to_vec();
+    account_infos.push(swap_program_info.clone());
+    invoke_signed(
+        &Instruction {
+            program_id: config.swap_program,
+            accounts: metas,
+            data: swap_data.to_vec(),
+        },
+        &account_infos,
+        &ore_api::ID,
+        &[TREASURY],
+    )?;
+
+    // The swap may only move the CRAP it was given, never the treasury's lamports.
+    if treasury_info.lamports() != pre_swap_treasury_lamports {
+        sol_log("Treasury lamports changed during swap");
+        return Err(OreError::InvalidAccount.into());
+    }
+    let post_swap_crap_balance = treasury_crap_ata
+        .as_associated_token_account(treasury_info.key, crap_mint.key)?
+        .amount();
+    let unspent = post_swap_crap_balance
+        .checked_sub(pre_swap_crap_balance)
+        .ok_or(OreError::SlippageExceeded)?;
+    let spent = amount
+        .checked_sub(unspent)
+        .ok_or(OreError::ArithmeticOverflow)?;
+    if spent == 0 {
+        sol_log("Swap spent no CRAP");
+        return Err(OreError::SlippageExceeded.into());
+    }
+    let ore_out = treasury_tokens_info
+        .as_associated_token_account(treasury_info.key, mint_info.key)?
+        .amount()
+        .checked_sub(pre_swap_ore_balance)
+        .ok_or(OreError::ArithmeticOverflow)?;
+    if ore_out < min_ore_out {
+        sol_log(&format!("Swap received {} < {}", ore_out, min_ore_out));
+        return Err(OreError::SlippageExceeded.into());
+    }
+
+    // Return what the route left to the vault. It stays owed.
+    if unspent > 0 {
+        transfer_signed(
+            treasury_info,
+            treasury_crap_ata,
+            vault_crap_ata,
+            token_program,
+            unspent,
+            &[TREASURY],
+        )?;
+    }
+
+    // Update the house bankroll and distribution high-water mark.
+    craps_game
+        .record_staker_distribution(spent)
+        .ok_or(OreError::ArithmeticOverflow)?;
+
+    // Credit stakers.
+    treasury.distribute_stake_rewards(ore_out);
+
+    sol_log(&format!(
+        "House profit distributed: epoch={}, {} CRAP as {} ORE, total_to_stakers={} CRAP",
+        craps_game.epoch_id,
+        spent,
+        amount_to_ui_amount(ore_out, TOKEN_DECIMALS),
+        craps_game.total_distributed_to_stakers
+    ));
+
+    Ok(())
+}
@@ -4,6 +4,9 @@
 //! that hasn't been settled within the round's expiry window. This prevents
 //! the "Reserved Payout DoS" attack where malicious users place bets and
 //! never settle, permanently locking up house bankroll.
+//!
+//! While craps is paused, the guardian can also force settle before expiry.
+//! Those bets are refunded to the position instead of forfeited.
 
 use ore_api::prelude::*;
 use solana_program::clock::Clock;
@@ -11,6 +14,9 @@ use solana_program::log::sol_log;
 use solana_program::sysvar::Sysvar;
 use steel::*;
 
+use super::metrics::record_metrics;
+use super::settle::{load_side_bets, release_position_reservations};
+
 /// Force settle a craps position after round expiry.
 /// This can be called by anyone (permissionless crank) to release reserved payouts.
 pub fn process_force_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
@@ -26,7 +32,9 @@ pub fn process_force_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) ->
     // 1: craps_game - game state PDA
     // 2: craps_position - user position PDA (for ANY user)
     // 3: round_info - round account for validation
-    let [caller_info, craps_game_info, craps_position_info, round_info] = accounts else {
+    // 4: config - for the guardian and craps pause flag
+    // 5: craps_side_bets - (optional) the position's side bets, required if it holds any
+    let [caller_info, craps_game_info, craps_position_info, round_info, config_info, side_bets_info @ ..] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
@@ -37,46 +45,39 @@ pub fn process_force_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) ->
     craps_position_info.is_writable()?;
     // Note: craps_position can be ANY user's position, verified by program owner check
 
-    // Verify accounts are program-owned
-    if craps_game_info.owner != &ore_api::ID {
-        sol_log("CrapsGame account not owned by program");
-        return Err(ProgramError::IncorrectProgramId);
-    }
-    if craps_position_info.owner != &ore_api::ID {
-        sol_log("CrapsPosition account not owned by program");
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
     // Load accounts
-    if craps_game_info.data_is_empty() || craps_position_info.data_is_empty() {
-        sol_log("Accounts not initialized");
-        return Err(ProgramError::UninitializedAccount);
-    }
-
-    let craps_game = craps_game_info.as_account_mut::<CrapsGame>(&ore_api::ID)?;
-    let craps_position = craps_position_info.as_account_mut::<CrapsPosition>(&ore_api::ID)?;
+    let craps_game = craps_game_info.as_checked_account_mut::<CrapsGame>()?;
+    let craps_position = craps_position_info.as_checked_account_mut::<CrapsPosition>()?;
     let round = round_info.as_account::<Round>(&ore_api::ID)?;
-
-    // Validate that the winning square matches the round's result.
-    #[cfg(not(any(feature = "localnet", feature = "devnet")))]
-    {
-        let Some(rng) = round.rng() else {
-            sol_log("Round has no valid RNG");
-            return Err(ProgramError::InvalidAccountData);
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    let guardian_settle = config.guardian != Pubkey::default()
+        && config.guardian == *caller_info.key
+        && config.is_paused(PAUSE_CRAPS);
+    let mut empty_side_bets = CrapsSideBets::zeroed();
+    let side_bets = load_side_bets(side_bets_info.first(), craps_position, &mut empty_side_bets)?;
+
+    // The guardian's emergency settle refunds bets, so it needs neither a result nor expiry.
+    if guardian_settle {
+        sol_log("Guardian settlement while craps is paused");
+    } else {
+        // Validate that the winning square matches the round's result.
+        // The dice result recorded on the round at reset is the single source of truth.
+        let Some(recorded_square) = round.dice_result_square() else {
+            sol_log("Round has no recorded dice result");
+            return Err(OreError::RollNotRecorded.into());
         };
-        let actual_winning_square = round.winning_square(rng);
-        if actual_winning_square != winning_square {
+        if recorded_square != winning_square {
             sol_log("Winning square mismatch");
-            return Err(ProgramError::InvalidArgument);
+            return Err(OreError::WinningSquareMismatch.into());
         }
-    }
 
-    // CRITICAL CHECK: Round must be expired before force settle is allowed
-    // This ensures users have had sufficient time to settle their own positions
-    let clock = Clock::get()?;
-    if clock.slot <= round.expires_at {
-        sol_log("ERROR: Round has not expired yet - cannot force settle");
-        return Err(ProgramError::Custom(2)); // Error code 2: ROUND_NOT_EXPIRED
+        // CRITICAL CHECK: Round must be expired before force settle is allowed
+        // This ensures users have had sufficient time to settle their own positions
+        let clock = Clock::get()?;
+        if clock.slot <= round.expires_at {
+            sol_log("ERROR: Round has not expired yet - cannot force settle");
+            return Err(OreError::RoundNotExpired.into());
+        }
     }
 
     // Check if position has any active bets that need settling
@@ -95,7 +96,8 @@ pub fn process_force_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) ->
         || craps_position.yes_bets.iter().any(|&x| x > 0)
         || craps_position.no_bets.iter().any(|&x| x > 0)
         || craps_position.next_bets.iter().any(|&x| x > 0)
-        || craps_position.hardways.iter().any(|&x| x > 0);
+        || craps_position.hardways.iter().any(|&x| x > 0)
+        || side_bets.has_any_bets();
 
     if !has_any_bets {
         sol_log("No active bets to force settle");
@@ -149,6 +151,12 @@ pub fn process_force_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) ->
         total_forfeited = total_forfeited.saturating_add(*bet);
     }
 
+    // Side bets
+    total_forfeited = total_forfeited.saturating_add(side_bets.total_bets());
+
+    // Release exactly the reservations this position's bets held.
+    release_position_reservations(craps_game, craps_position);
+
     // Clear all bets (forfeited due to not settling in time)
     craps_position.pass_line = 0;
     craps_position.dont_pass = 0;
@@ -169,19 +177,28 @@ pub fn process_force_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) ->
     craps_position.no_bets = [0; 11];
     craps_position.next_bets = [0; 11];
     craps_position.hardways = [0; 4];
+    side_bets.clear_all_bets();
+    craps_position.set_side_bets_active(false);
+
+    craps_position.last_updated_round = round.id;
+
+    let returned = if guardian_settle { total_forfeited } else { 0 };
+    record_metrics(side_bets_info, |metrics| metrics.record_settlement(total_forfeited, returned))?;
+
+    // Guardian settlements refund the bets; the player never had a chance to settle.
+    if guardian_settle {
+        craps_position.pending_winnings = craps_position.pending_winnings
+            .saturating_add(total_forfeited);
+        sol_log(&format!(
+            "Force settled: refunded={}, reserved released",
+            total_forfeited
+        ).as_str());
+        return Ok(());
+    }
 
     // Update tracking
     craps_position.total_lost = craps_position.total_lost
         .saturating_add(total_forfeited);
-    craps_position.last_updated_round = round.id;
-
-    // Release ALL reserved payouts for this position
-    // Since bets are forfeited, the house keeps the tokens and reserved amount is released
-    // We use a conservative estimate - release reserved based on forfeited amount
-    // (This is a simplification - in reality we'd track exact reserved amounts)
-    craps_game.reserved_payouts = craps_game.reserved_payouts.saturating_sub(
-        total_forfeited.saturating_mul(2) // Approximate max payout was 2x for most bets
-    );
 
     // House keeps forfeited bets (already in house_bankroll from place_bet)
     craps_game.total_collected = craps_game.total_collected
@@ -46,27 +46,10 @@ pub fn process_fund_craps_house(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pr
     }
 
     // Load or create craps game.
-    let craps_game = if craps_game_info.data_is_empty() {
-        // Initialize craps game if it doesn't exist.
-        create_program_account::<CrapsGame>(
-            craps_game_info,
-            system_program,
-            signer_info,
-            &ore_api::ID,
-            &[CRAPS_GAME],
-        )?;
-        let craps_game = craps_game_info.as_account_mut::<CrapsGame>(&ore_api::ID)?;
-        craps_game.epoch_id = 1;
-        craps_game.point = 0;
-        craps_game.is_come_out = 1; // Start in come-out phase
-        craps_game.epoch_start_round = 0;
-        craps_game.house_bankroll = 0;
-        craps_game.total_payouts = 0;
-        craps_game.total_collected = 0;
-        craps_game
-    } else {
-        craps_game_info.as_account_mut::<CrapsGame>(&ore_api::ID)?
-    };
+    if craps_game_info.data_is_empty() {
+        create_craps_game(signer_info, craps_game_info, system_program)?;
+    }
+    let craps_game = craps_game_info.as_account_mut::<CrapsGame>(&ore_api::ID)?;
 
     // Create vault's CRAP token account if it doesn't exist.
     if vault_crap_ata.data_is_empty() {
@@ -109,3 +92,27 @@ pub fn process_fund_craps_house(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pr
 
     Ok(())
 }
+
+/// Creates the craps game account with an empty bankroll, in the come-out phase of epoch 1.
+pub(crate) fn create_craps_game(
+    signer_info: &AccountInfo<'_>,
+    craps_game_info: &AccountInfo<'_>,
+    system_program: &AccountInfo<'_>,
+) -> ProgramResult {
+    create_program_account::<CrapsGame>(
+        craps_game_info,
+        system_program,
+        signer_info,
+        &ore_api::ID,
+        &[CRAPS_GAME],
+    )?;
+    let craps_game = craps_game_info.as_account_mut::<CrapsGame>(&ore_api::ID)?;
+    craps_game.epoch_id = 1;
+    craps_game.point = 0;
+    craps_game.is_come_out = 1; // Start in come-out phase
+    craps_game.epoch_start_round = 0;
+    craps_game.house_bankroll = 0;
+    craps_game.total_payouts = 0;
+    craps_game.total_collected = 0;
+    Ok(())
+}
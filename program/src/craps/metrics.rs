@@ -0,0 +1,49 @@
+//! Protocol KPI counters
+
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+/// Opens the protocol metrics account. Anyone may pay for it.
+pub fn process_open_metrics(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, metrics_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    metrics_info
+        .is_empty()?
+        .is_writable()?
+        .has_seeds(&[METRICS], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    create_program_account::<Metrics>(
+        metrics_info,
+        system_program,
+        signer_info,
+        &ore_api::ID,
+        &[METRICS],
+    )?;
+
+    sol_log("Metrics account opened");
+
+    Ok(())
+}
+
+/// Applies `update` to the metrics account if it is among `accounts`.
+/// Metrics are best-effort: when the account is missing or not yet opened, nothing is
+/// recorded and the instruction proceeds as usual.
+pub(crate) fn record_metrics(
+    accounts: &[AccountInfo<'_>],
+    update: impl FnOnce(&mut Metrics),
+) -> ProgramResult {
+    let Some(metrics_info) = accounts.iter().find(|a| *a.key == METRICS_ADDRESS) else {
+        return Ok(());
+    };
+    if metrics_info.data_is_empty() {
+        return Ok(());
+    }
+    let metrics = metrics_info.is_writable()?.as_account_mut::<Metrics>(&ore_api::ID)?;
+    update(metrics);
+    Ok(())
+}
@@ -0,0 +1,266 @@
+//! Migrate a CrapsPosition to the compact layout
+
+use ore_api::prelude::*;
+use ore_api::state::{NUM_HARDWAYS, NUM_POINTS};
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::place_bet::load_or_create_side_bets;
+
+/// Layout of CrapsPosition before the exotic bets moved to CrapsSideBets.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+struct LegacyCrapsPosition {
+    authority: Pubkey,
+    epoch_id: u64,
+    pass_line: u64,
+    dont_pass: u64,
+    pass_odds: u64,
+    dont_pass_odds: u64,
+    come_bets: [u64; NUM_POINTS],
+    come_odds: [u64; NUM_POINTS],
+    dont_come_bets: [u64; NUM_POINTS],
+    dont_come_odds: [u64; NUM_POINTS],
+    place_bets: [u64; NUM_POINTS],
+    place_working: u8,
+    _padding1: [u8; 7],
+    yes_bets: [u64; 11],
+    no_bets: [u64; 11],
+    next_bets: [u64; 11],
+    hardways: [u64; NUM_HARDWAYS],
+    field_bet: u64,
+    any_seven: u64,
+    any_craps: u64,
+    yo_eleven: u64,
+    aces: u64,
+    twelve: u64,
+    bonus_small: u64,
+    bonus_tall: u64,
+    bonus_all: u64,
+    small_hits: u8,
+    tall_hits: u8,
+    _padding2: [u8; 6],
+    fire_bet: u64,
+    fire_points_made: u8,
+    _pad_fire: [u8; 7],
+    diff_doubles_bet: u64,
+    diff_doubles_hits: u8,
+    _pad_diff: [u8; 7],
+    ride_the_line_bet: u64,
+    ride_wins_count: u8,
+    _pad_ride: [u8; 7],
+    mugsy_bet: u64,
+    phase_id: u32,
+    active_bets: u32,
+    hot_hand_bet: u64,
+    hot_hand_hits: u16,
+    _pad_hot: [u8; 6],
+    replay_bet: u64,
+    replay_counts: [u8; NUM_POINTS],
+    _pad_replay: [u8; 2],
+    fielders_choice: [u64; 3],
+    pending_winnings: u64,
+    total_wagered: u64,
+    total_won: u64,
+    total_lost: u64,
+    last_updated_round: u64,
+    unpaid_debt: u64,
+    reserved_payouts: u64,
+}
+
+impl LegacyCrapsPosition {
+    /// Splits the legacy layout into the compact position and its side bets.
+    fn split(&self) -> (CrapsPosition, CrapsSideBets) {
+        let mut position = CrapsPosition::zeroed();
+        position.authority = self.authority;
+        position.epoch_id = self.epoch_id;
+        position.pass_line = self.pass_line;
+        position.dont_pass = self.dont_pass;
+        position.pass_odds = self.pass_odds;
+        position.dont_pass_odds = self.dont_pass_odds;
+        position.come_bets = self.come_bets;
+        position.come_odds = self.come_odds;
+        position.dont_come_bets = self.dont_come_bets;
+        position.dont_come_odds = self.dont_come_odds;
+        position.place_bets = self.place_bets;
+        position.place_working = self.place_working;
+        position.yes_bets = self.yes_bets;
+        position.no_bets = self.no_bets;
+        position.next_bets = self.next_bets;
+        position.hardways = self.hardways;
+        position.field_bet = self.field_bet;
+        position.any_seven = self.any_seven;
+        position.any_craps = self.any_craps;
+        position.yo_eleven = self.yo_eleven;
+        position.aces = self.aces;
+        position.twelve = self.twelve;
+        position.phase_id = self.phase_id;
+        position.pending_winnings = self.pending_winnings;
+        position.total_wagered = self.total_wagered;
+        position.total_won = self.total_won;
+        position.total_lost = self.total_lost;
+        position.last_updated_round = self.last_updated_round;
+        position.unpaid_debt = self.unpaid_debt;
+        position.reserved_payouts = self.reserved_payouts;
+        // The old bitmap used different category bits, so rebuild it from the bets.
+        position.refresh_active_bets();
+
+        let mut side_bets = CrapsSideBets::zeroed();
+        side_bets.authority = self.authority;
+        side_bets.epoch_id = self.epoch_id;
+        side_bets.bonus_small = self.bonus_small;
+        side_bets.bonus_tall = self.bonus_tall;
+        side_bets.bonus_all = self.bonus_all;
+        side_bets.small_hits = self.small_hits;
+        side_bets.tall_hits = self.tall_hits;
+        side_bets.fire_bet = self.fire_bet;
+        side_bets.fire_points_made = self.fire_points_made;
+        side_bets.diff_doubles_bet = self.diff_doubles_bet;
+        side_bets.diff_doubles_hits = self.diff_doubles_hits;
+        side_bets.ride_the_line_bet = self.ride_the_line_bet;
+        side_bets.ride_wins_count = self.ride_wins_count;
+        side_bets.mugsy_bet = self.mugsy_bet;
+        side_bets.hot_hand_bet = self.hot_hand_bet;
+        side_bets.hot_hand_hits = self.hot_hand_hits;
+        side_bets.replay_bet = self.replay_bet;
+        side_bets.replay_counts = self.replay_counts;
+        side_bets.fielders_choice = self.fielders_choice;
+
+        (position, side_bets)
+    }
+}
+
+/// Migrate a CrapsPosition account to the compact layout.
+/// Anyone can call this on their own position; excess rent is returned to them.
+pub fn process_migrate_craps_position(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts
+    let [signer_info, craps_position_info, side_bets_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    signer_info.is_signer()?;
+    craps_position_info
+        .is_writable()?
+        .has_seeds(&[CRAPS_POSITION, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    if craps_position_info.data_is_empty() {
+        sol_log("Craps position not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    if craps_position_info.data_len() == CrapsPosition::SIZE {
+        sol_log("CrapsPosition already at correct size, no migration needed");
+        return Ok(());
+    }
+
+    migrate_craps_position(signer_info, craps_position_info, Some(side_bets_info), system_program)
+}
+
+/// Rewrites a legacy CrapsPosition in the compact layout, moving any open side bets
+/// to the signer's CrapsSideBets account (created if needed).
+pub(crate) fn migrate_craps_position<'info>(
+    signer_info: &AccountInfo<'info>,
+    craps_position_info: &AccountInfo<'info>,
+    side_bets_info: Option<&AccountInfo<'info>>,
+    system_program: &AccountInfo<'info>,
+) -> ProgramResult {
+    if craps_position_info.owner != &ore_api::ID {
+        sol_log("CrapsPosition account not owned by program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let current_size = craps_position_info.data_len();
+    sol_log(&format!(
+        "Migrating CrapsPosition: {} -> {} bytes",
+        current_size, CrapsPosition::SIZE
+    ));
+
+    // Read the legacy layout. Accounts from before later fields were added are zero-extended.
+    let mut legacy = LegacyCrapsPosition::zeroed();
+    {
+        let data = craps_position_info.try_borrow_data()?;
+        let body = data.get(8..).ok_or(ProgramError::InvalidAccountData)?;
+        let len = body.len().min(std::mem::size_of::<LegacyCrapsPosition>());
+        bytemuck::bytes_of_mut(&mut legacy)[..len].copy_from_slice(&body[..len]);
+    }
+    if legacy.authority != *signer_info.key {
+        sol_log("Signer is not the position authority");
+        return Err(ProgramError::IllegalOwner);
+    }
+    let (mut position, legacy_side_bets) = legacy.split();
+
+    // Move open side bets to the companion account.
+    if legacy_side_bets.has_any_bets() {
+        let Some(side_bets_info) = side_bets_info else {
+            sol_log("CrapsSideBets account required to migrate side bets");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let side_bets =
+            load_or_create_side_bets(signer_info, side_bets_info, system_program, position.epoch_id)?;
+        *side_bets = legacy_side_bets;
+        position.set_side_bets_active(true);
+        sol_log(&format!("Moved {} in side bets to CrapsSideBets", side_bets.total_bets()));
+    }
+
+    // Resize the account, topping up or refunding rent to match.
+    let rent = solana_program::rent::Rent::get()?;
+    let new_rent = rent.minimum_balance(CrapsPosition::SIZE);
+    let current_lamports = craps_position_info.lamports();
+    if current_lamports < new_rent {
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(
+                signer_info.key,
+                craps_position_info.key,
+                new_rent - current_lamports,
+            ),
+            &[signer_info.clone(), craps_position_info.clone(), system_program.clone()],
+        )?;
+    }
+    craps_position_info.realloc(CrapsPosition::SIZE, false)?;
+    craps_position_info.try_borrow_mut_data()?[8..].copy_from_slice(bytemuck::bytes_of(&position));
+    if current_lamports > new_rent {
+        craps_position_info.send(current_lamports - new_rent, signer_info);
+    }
+
+    sol_log("CrapsPosition migration complete");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_layout_is_smaller() {
+        assert_eq!(std::mem::size_of::<LegacyCrapsPosition>(), 872);
+        assert!(std::mem::size_of::<CrapsPosition>() < std::mem::size_of::<LegacyCrapsPosition>());
+    }
+
+    #[test]
+    fn test_split_moves_side_bets() {
+        let mut legacy = LegacyCrapsPosition::zeroed();
+        legacy.epoch_id = 3;
+        legacy.pass_line = 100;
+        legacy.place_bets[2] = 60;
+        legacy.place_working = 1;
+        legacy.fire_bet = 10;
+        legacy.fire_points_made = 0b101;
+        legacy.fielders_choice[1] = 5;
+        legacy.pending_winnings = 40;
+        legacy.reserved_payouts = 500;
+
+        let (position, side_bets) = legacy.split();
+
+        assert_eq!(position.epoch_id, 3);
+        assert_eq!(position.total_active_bets(), 160);
+        assert!(position.are_place_bets_working());
+        assert!(position.has_active_bets(BET_MASK_LINE | BET_MASK_PLACE));
+        assert_eq!(position.pending_winnings, 40);
+        assert_eq!(position.reserved_payouts, 500);
+        assert_eq!(side_bets.epoch_id, 3);
+        assert_eq!(side_bets.total_bets(), 15);
+        assert_eq!(side_bets.fire_points_count(), 2);
+    }
+}
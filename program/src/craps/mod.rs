@@ -1,21 +1,37 @@
 //! Craps game module - dice betting functionality
 
 mod place_bet;
+mod place_bundle;
+mod migrate_position;
 mod settle;
 mod settle_single_roll;
+mod simulate_settle;
 mod claim;
 mod fund_house;
+mod set_house_profit_share;
+mod distribute_house_profit;
 mod start_round;
 mod force_settle;
 mod claim_debt;
+mod withdraw_house;
+mod assert_solvency;
+mod metrics;
 mod utils;
 
 pub use place_bet::*;
+pub use place_bundle::*;
+pub use migrate_position::*;
 pub use settle::*;
 pub use settle_single_roll::*;
+pub use simulate_settle::*;
 pub use claim::*;
 pub use fund_house::*;
+pub use set_house_profit_share::*;
+pub use distribute_house_profit::*;
 pub use start_round::*;
 pub use force_settle::*;
 pub use claim_debt::*;
+pub use withdraw_house::*;
+pub use assert_solvency::*;
+pub use metrics::*;
 pub use utils::*;
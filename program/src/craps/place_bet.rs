@@ -1,165 +1,52 @@
 use ore_api::error::OreError;
 use ore_api::prelude::*;
 use solana_program::clock::Clock;
-use solana_program::log::sol_log;
+use solana_program::log::{sol_log, sol_log_data};
 use solana_program::program::invoke;
 use solana_program::sysvar::Sysvar;
 use steel::*;
 
+use super::metrics::record_metrics;
+use super::migrate_position::migrate_craps_position;
+use super::settle::release_position_reservations;
 use super::utils::{point_to_index, sum_to_index, is_valid_yes_no_sum};
-
-/// Expected size of the CrapsPosition struct (with 8-byte discriminator).
-const CRAPS_POSITION_SIZE: usize = 8 + std::mem::size_of::<CrapsPosition>();
+use crate::admin::assert_not_paused;
 
 /// Calculate the maximum potential payout for a bet type and amount.
 /// This helps ensure the house has sufficient bankroll to cover all possible outcomes.
-fn calculate_max_payout(bet_type: u8, point: u8, amount: u64) -> Result<u64, ProgramError> {
-    // Helper to calculate payout: amount * (numerator / denominator) + amount
-    let calc = |num: u64, den: u64| -> Result<u64, ProgramError> {
-        let payout = amount
-            .checked_mul(num)
-            .ok_or(OreError::ArithmeticOverflow)?
-            .checked_div(den)
-            .ok_or(OreError::ArithmeticOverflow)?;
-        amount
-            .checked_add(payout)
-            .ok_or(OreError::ArithmeticOverflow.into())
-    };
+pub(crate) fn calculate_max_payout(bet_type: u8, point: u8, amount: u64) -> Result<u64, ProgramError> {
+    Ok(max_payout(bet_type, point, amount)?)
+}
 
-    match bet_type {
-        // Pass Line (1:1)
-        0 => calc(PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN),
-        // Don't Pass (1:1)
-        1 => calc(PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN),
-        // Pass Odds - depends on point (2:1, 3:2, or 6:5)
-        2 => {
-            let (num, den) = match point {
-                4 | 10 => (TRUE_ODDS_4_10_NUM, TRUE_ODDS_4_10_DEN),
-                5 | 9 => (TRUE_ODDS_5_9_NUM, TRUE_ODDS_5_9_DEN),
-                6 | 8 => (TRUE_ODDS_6_8_NUM, TRUE_ODDS_6_8_DEN),
-                _ => return Ok(amount), // Shouldn't happen, but safe fallback
-            };
-            calc(num, den)
-        }
-        // Don't Pass Odds - inverse, but for reservation use same as pass odds
-        3 => {
-            let (num, den) = match point {
-                4 | 10 => (TRUE_ODDS_4_10_NUM, TRUE_ODDS_4_10_DEN),
-                5 | 9 => (TRUE_ODDS_5_9_NUM, TRUE_ODDS_5_9_DEN),
-                6 | 8 => (TRUE_ODDS_6_8_NUM, TRUE_ODDS_6_8_DEN),
-                _ => return Ok(amount),
-            };
-            calc(num, den)
-        }
-        // Come (1:1)
-        4 => calc(PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN),
-        // Don't Come (1:1)
-        5 => calc(PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN),
-        // Come Odds
-        6 => {
-            let (num, den) = match point {
-                4 | 10 => (TRUE_ODDS_4_10_NUM, TRUE_ODDS_4_10_DEN),
-                5 | 9 => (TRUE_ODDS_5_9_NUM, TRUE_ODDS_5_9_DEN),
-                6 | 8 => (TRUE_ODDS_6_8_NUM, TRUE_ODDS_6_8_DEN),
-                _ => return Ok(amount),
-            };
-            calc(num, den)
-        }
-        // Don't Come Odds
-        7 => {
-            let (num, den) = match point {
-                4 | 10 => (TRUE_ODDS_4_10_NUM, TRUE_ODDS_4_10_DEN),
-                5 | 9 => (TRUE_ODDS_5_9_NUM, TRUE_ODDS_5_9_DEN),
-                6 | 8 => (TRUE_ODDS_6_8_NUM, TRUE_ODDS_6_8_DEN),
-                _ => return Ok(amount),
-            };
-            calc(num, den)
-        }
-        // Place bet
-        8 => {
-            let (num, den) = match point {
-                4 | 10 => (PLACE_4_10_PAYOUT_NUM, PLACE_4_10_PAYOUT_DEN),
-                5 | 9 => (PLACE_5_9_PAYOUT_NUM, PLACE_5_9_PAYOUT_DEN),
-                6 | 8 => (PLACE_6_8_PAYOUT_NUM, PLACE_6_8_PAYOUT_DEN),
-                _ => return Ok(amount),
-            };
-            calc(num, den)
-        }
-        // Hardway
-        9 => {
-            let (num, den) = match point {
-                4 | 10 => (HARD_4_10_PAYOUT_NUM, HARD_4_10_PAYOUT_DEN),
-                6 | 8 => (HARD_6_8_PAYOUT_NUM, HARD_6_8_PAYOUT_DEN),
-                _ => return Ok(amount),
-            };
-            calc(num, den)
-        }
-        // Field - worst case is 2:1
-        10 => calc(FIELD_PAYOUT_2_12_NUM, FIELD_PAYOUT_2_12_DEN),
-        // Any Seven (4:1)
-        11 => calc(ANY_SEVEN_PAYOUT_NUM, ANY_SEVEN_PAYOUT_DEN),
-        // Any Craps (7:1)
-        12 => calc(ANY_CRAPS_PAYOUT_NUM, ANY_CRAPS_PAYOUT_DEN),
-        // Yo Eleven (15:1)
-        13 => calc(YO_ELEVEN_PAYOUT_NUM, YO_ELEVEN_PAYOUT_DEN),
-        // Aces (30:1)
-        14 => calc(ACES_PAYOUT_NUM, ACES_PAYOUT_DEN),
-        // Twelve (30:1)
-        15 => calc(TWELVE_PAYOUT_NUM, TWELVE_PAYOUT_DEN),
-        // Yes bet (true odds) - sum before 7
-        26 => {
-            let (num, den) = match point {
-                2 => (YES_2_PAYOUT_NUM, YES_2_PAYOUT_DEN),
-                3 => (YES_3_PAYOUT_NUM, YES_3_PAYOUT_DEN),
-                4 => (YES_4_PAYOUT_NUM, YES_4_PAYOUT_DEN),
-                5 => (YES_5_PAYOUT_NUM, YES_5_PAYOUT_DEN),
-                6 => (YES_6_PAYOUT_NUM, YES_6_PAYOUT_DEN),
-                8 => (YES_8_PAYOUT_NUM, YES_8_PAYOUT_DEN),
-                9 => (YES_9_PAYOUT_NUM, YES_9_PAYOUT_DEN),
-                10 => (YES_10_PAYOUT_NUM, YES_10_PAYOUT_DEN),
-                11 => (YES_11_PAYOUT_NUM, YES_11_PAYOUT_DEN),
-                12 => (YES_12_PAYOUT_NUM, YES_12_PAYOUT_DEN),
-                _ => return Ok(amount), // 7 is invalid
-            };
-            calc(num, den)
-        }
-        // No bet (inverse true odds) - 7 before sum
-        27 => {
-            let (num, den) = match point {
-                2 => (NO_2_PAYOUT_NUM, NO_2_PAYOUT_DEN),
-                3 => (NO_3_PAYOUT_NUM, NO_3_PAYOUT_DEN),
-                4 => (NO_4_PAYOUT_NUM, NO_4_PAYOUT_DEN),
-                5 => (NO_5_PAYOUT_NUM, NO_5_PAYOUT_DEN),
-                6 => (NO_6_PAYOUT_NUM, NO_6_PAYOUT_DEN),
-                8 => (NO_8_PAYOUT_NUM, NO_8_PAYOUT_DEN),
-                9 => (NO_9_PAYOUT_NUM, NO_9_PAYOUT_DEN),
-                10 => (NO_10_PAYOUT_NUM, NO_10_PAYOUT_DEN),
-                11 => (NO_11_PAYOUT_NUM, NO_11_PAYOUT_DEN),
-                12 => (NO_12_PAYOUT_NUM, NO_12_PAYOUT_DEN),
-                _ => return Ok(amount), // 7 is invalid
-            };
-            calc(num, den)
-        }
-        // Next bet (single-roll true odds)
-        28 => {
-            let (num, den) = match point {
-                2 => (HOP_2_PAYOUT_NUM, HOP_2_PAYOUT_DEN),
-                3 => (HOP_3_PAYOUT_NUM, HOP_3_PAYOUT_DEN),
-                4 => (HOP_4_PAYOUT_NUM, HOP_4_PAYOUT_DEN),
-                5 => (HOP_5_PAYOUT_NUM, HOP_5_PAYOUT_DEN),
-                6 => (HOP_6_PAYOUT_NUM, HOP_6_PAYOUT_DEN),
-                7 => (HOP_7_PAYOUT_NUM, HOP_7_PAYOUT_DEN),
-                8 => (HOP_8_PAYOUT_NUM, HOP_8_PAYOUT_DEN),
-                9 => (HOP_9_PAYOUT_NUM, HOP_9_PAYOUT_DEN),
-                10 => (HOP_10_PAYOUT_NUM, HOP_10_PAYOUT_DEN),
-                11 => (HOP_11_PAYOUT_NUM, HOP_11_PAYOUT_DEN),
-                12 => (HOP_12_PAYOUT_NUM, HOP_12_PAYOUT_DEN),
-                _ => return Ok(amount),
-            };
-            calc(num, den)
-        }
-        _ => Ok(amount), // Invalid bet type, will be caught later
+/// Loads the signer's CrapsSideBets account, creating it on first use.
+/// Bets left over from a finished epoch are cleared.
+pub(crate) fn load_or_create_side_bets<'a, 'info>(
+    signer_info: &AccountInfo<'info>,
+    side_bets_info: &'a AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    epoch_id: u64,
+) -> Result<&'a mut CrapsSideBets, ProgramError> {
+    side_bets_info
+        .is_writable()?
+        .has_seeds(&[CRAPS_SIDE_BETS, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    if side_bets_info.data_is_empty() {
+        create_program_account::<CrapsSideBets>(
+            side_bets_info,
+            system_program,
+            signer_info,
+            &ore_api::ID,
+            &[CRAPS_SIDE_BETS, &signer_info.key.to_bytes()],
+        )?;
+        let side_bets = side_bets_info.as_account_mut::<CrapsSideBets>(&ore_api::ID)?;
+        side_bets.authority = *signer_info.key;
+        side_bets.epoch_id = epoch_id;
+        return Ok(side_bets);
     }
+    let side_bets = side_bets_info.as_account_mut::<CrapsSideBets>(&ore_api::ID)?;
+    if side_bets.epoch_id != epoch_id {
+        side_bets.reset_for_epoch(epoch_id);
+    }
+    Ok(side_bets)
 }
 
 /// Places a craps bet for the user.
@@ -172,6 +59,19 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
 
     sol_log(&format!("PlaceCrapsBet: type={}, point={}, amount={}", bet_type, point, amount).as_str());
 
+    place_bet(accounts, bet_type, point, amount, true)
+}
+
+/// Validates a bet, adds it to the position and moves its stake into the vault.
+/// With `reserve` set, the bet's max payout is checked against and reserved from the house
+/// bankroll; bundles leave that to the caller, which reserves all their legs at once.
+pub(crate) fn place_bet(
+    accounts: &[AccountInfo<'_>],
+    bet_type: u8,
+    point: u8,
+    amount: u64,
+    reserve: bool,
+) -> ProgramResult {
     // Load accounts.
     // Account layout:
     // 0: signer
@@ -185,7 +85,10 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
     // 8: system_program
     // 9: token_program
     // 10: associated_token_program
-    let [signer_info, craps_game_info, craps_position_info, craps_vault_info, signer_crap_ata, vault_crap_ata, crap_mint, board_info, system_program, token_program, associated_token_program] = accounts else {
+    // 11: config - for the craps pause flag
+    // 12: craps_side_bets - (optional) side bets PDA, required for bet types 16-25
+    //     and when migrating a position that holds side bets
+    let [signer_info, craps_game_info, craps_position_info, craps_vault_info, signer_crap_ata, vault_crap_ata, crap_mint, board_info, system_program, token_program, associated_token_program, config_info, side_bets_info @ ..] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
@@ -204,6 +107,8 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
     system_program.is_program(&system_program::ID)?;
     token_program.is_program(&spl_token::ID)?;
     associated_token_program.is_program(&spl_associated_token_account::ID)?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_CRAPS)?;
 
     // SECURITY FIX 1.1: Validate bet is placed within active round window
     // This prevents "late betting" where users bet after knowing the round result
@@ -243,7 +148,8 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
     };
 
     // Load or create craps position account.
-    let craps_position = if craps_position_info.data_is_empty() {
+    let is_new_position = craps_position_info.data_is_empty();
+    let craps_position = if is_new_position {
         create_program_account::<CrapsPosition>(
             craps_position_info,
             system_program,
@@ -254,37 +160,12 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
         let position = craps_position_info.as_account_mut::<CrapsPosition>(&ore_api::ID)?;
         position.authority = *signer_info.key;
         position.epoch_id = craps_game.epoch_id;
+        position.phase_id = craps_game.phase_id;
         position
     } else {
-        // Check if account needs migration (legacy 600-byte accounts)
-        let current_size = craps_position_info.data_len();
-        if current_size < CRAPS_POSITION_SIZE {
-            sol_log(&format!(
-                "Migrating CrapsPosition: {} -> {} bytes",
-                current_size, CRAPS_POSITION_SIZE
-            ));
-
-            // Calculate additional rent needed
-            let rent = solana_program::rent::Rent::get()?;
-            let current_rent = rent.minimum_balance(current_size);
-            let new_rent = rent.minimum_balance(CRAPS_POSITION_SIZE);
-            let additional_rent = new_rent.saturating_sub(current_rent);
-
-            // Transfer additional rent if needed
-            if additional_rent > 0 {
-                solana_program::program::invoke(
-                    &solana_program::system_instruction::transfer(
-                        signer_info.key,
-                        craps_position_info.key,
-                        additional_rent,
-                    ),
-                    &[signer_info.clone(), craps_position_info.clone(), system_program.clone()],
-                )?;
-            }
-
-            // Reallocate the account (new bytes are zero-initialized)
-            craps_position_info.realloc(CRAPS_POSITION_SIZE, false)?;
-            sol_log("CrapsPosition migration complete");
+        // Accounts written with an older layout are migrated to the compact one first.
+        if craps_position_info.data_len() != CrapsPosition::SIZE {
+            migrate_craps_position(signer_info, craps_position_info, side_bets_info.first(), system_program)?;
         }
 
         let position = craps_position_info.as_account_mut::<CrapsPosition>(&ore_api::ID)?;
@@ -293,8 +174,18 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
             sol_log("Signer is not the position authority");
             return Err(ProgramError::IllegalOwner);
         }
-        // If position is from old epoch, reset it.
+        // Bets settle against the table's rolls in order, so a position holding bets must
+        // settle the rolls it has missed before betting on the next one.
+        if position.phase_id != craps_game.phase_id {
+            if position.total_active_bets() > 0 || position.has_active_bets(BET_MASK_SIDE) {
+                sol_log("Settle outstanding rolls before placing new bets");
+                return Err(OreError::RollsUnsettled.into());
+            }
+            position.phase_id = craps_game.phase_id;
+        }
+        // If position is from old epoch, reset it and release the reservations its bets held.
         if position.epoch_id != craps_game.epoch_id {
+            release_position_reservations(craps_game, position);
             position.reset_for_epoch(craps_game.epoch_id);
         }
         position
@@ -311,8 +202,13 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
         return Err(OreError::InvalidBetAmount.into());
     }
 
-    // Calculate max potential payout for this bet
-    let max_payout = calculate_max_payout(bet_type, point, amount)?;
+    // Calculate max potential payout for this bet.
+    // Line odds settle against the table point, so reserve against it too.
+    let reserve_point = match bet_type {
+        2 | 3 => craps_game.point,
+        _ => point,
+    };
+    let max_payout = calculate_max_payout(bet_type, reserve_point, amount)?;
 
     // Calculate available bankroll (total minus already reserved for pending bets)
     let available_bankroll = craps_game.house_bankroll
@@ -320,7 +216,7 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
         .ok_or(OreError::InsufficientBankroll)?;
 
     // Check if this bet's max payout fits in available bankroll
-    if max_payout > available_bankroll {
+    if reserve && max_payout > available_bankroll {
         sol_log("Bet exceeds available house bankroll (after reserved payouts)");
         return Err(OreError::InsufficientBankroll.into());
     }
@@ -340,7 +236,6 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
             craps_position.pass_line = craps_position.pass_line
                 .checked_add(amount)
                 .ok_or(OreError::ArithmeticOverflow)?;
-            sol_log(&format!("Pass Line bet placed: {}", amount).as_str());
         }
         // Don't Pass - only allowed during come-out
         1 => { // DontPass
@@ -351,7 +246,6 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
             craps_position.dont_pass = craps_position.dont_pass
                 .checked_add(amount)
                 .ok_or(OreError::ArithmeticOverflow)?;
-            sol_log(&format!("Don't Pass bet placed: {}", amount).as_str());
         }
         // Pass Odds - only allowed after point established
         2 => { // PassOdds
@@ -366,7 +260,6 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
             craps_position.pass_odds = craps_position.pass_odds
                 .checked_add(amount)
                 .ok_or(OreError::ArithmeticOverflow)?;
-            sol_log(&format!("Pass Odds bet placed: {}", amount).as_str());
         }
         // Don't Pass Odds - only allowed after point established
         3 => { // DontPassOdds
@@ -381,7 +274,6 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
             craps_position.dont_pass_odds = craps_position.dont_pass_odds
                 .checked_add(amount)
                 .ok_or(OreError::ArithmeticOverflow)?;
-            sol_log(&format!("Don't Pass Odds bet placed: {}", amount).as_str());
         }
         // Come - only allowed after point established (not during come-out)
         4 => { // Come
@@ -394,7 +286,6 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
                 craps_position.come_bets[idx] = craps_position.come_bets[idx]
                     .checked_add(amount)
                     .ok_or(OreError::ArithmeticOverflow)?;
-                sol_log(&format!("Come bet placed on {}: {}", point, amount).as_str());
             } else {
                 sol_log("Invalid point for Come bet");
                 return Err(OreError::InvalidBetType.into());
@@ -406,7 +297,6 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
                 craps_position.dont_come_bets[idx] = craps_position.dont_come_bets[idx]
                     .checked_add(amount)
                     .ok_or(OreError::ArithmeticOverflow)?;
-                sol_log(&format!("Don't Come bet placed on {}: {}", point, amount).as_str());
             } else {
                 sol_log("Invalid point for Don't Come bet");
                 return Err(OreError::InvalidBetType.into());
@@ -422,7 +312,6 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
                 craps_position.come_odds[idx] = craps_position.come_odds[idx]
                     .checked_add(amount)
                     .ok_or(OreError::ArithmeticOverflow)?;
-                sol_log(&format!("Come Odds placed on {}: {}", point, amount).as_str());
             } else {
                 sol_log("Invalid point for Come Odds");
                 return Err(OreError::InvalidBetType.into());
@@ -438,7 +327,6 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
                 craps_position.dont_come_odds[idx] = craps_position.dont_come_odds[idx]
                     .checked_add(amount)
                     .ok_or(OreError::ArithmeticOverflow)?;
-                sol_log(&format!("Don't Come Odds placed on {}: {}", point, amount).as_str());
             } else {
                 sol_log("Invalid point for Don't Come Odds");
                 return Err(OreError::InvalidBetType.into());
@@ -451,7 +339,6 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
                     .checked_add(amount)
                     .ok_or(OreError::ArithmeticOverflow)?;
                 craps_position.set_place_working(true);
-                sol_log(&format!("Place bet on {}: {}", point, amount).as_str());
             } else {
                 sol_log("Invalid point for Place bet");
                 return Err(OreError::InvalidBetType.into());
@@ -470,7 +357,6 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
                 craps_position.hardways[idx] = craps_position.hardways[idx]
                     .checked_add(amount)
                     .ok_or(OreError::ArithmeticOverflow)?;
-                sol_log(&format!("Hardway bet on {}: {}", point, amount).as_str());
             } else {
                 sol_log("Invalid hardway number (must be 4, 6, 8, or 10)");
                 return Err(OreError::InvalidBetType.into());
@@ -481,42 +367,36 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
             craps_position.field_bet = craps_position.field_bet
                 .checked_add(amount)
                 .ok_or(OreError::ArithmeticOverflow)?;
-            sol_log(&format!("Field bet placed: {}", amount).as_str());
         }
         // Any Seven - single roll bet
         11 => { // AnySeven
             craps_position.any_seven = craps_position.any_seven
                 .checked_add(amount)
                 .ok_or(OreError::ArithmeticOverflow)?;
-            sol_log(&format!("Any Seven bet placed: {}", amount).as_str());
         }
         // Any Craps - single roll bet
         12 => { // AnyCraps
             craps_position.any_craps = craps_position.any_craps
                 .checked_add(amount)
                 .ok_or(OreError::ArithmeticOverflow)?;
-            sol_log(&format!("Any Craps bet placed: {}", amount).as_str());
         }
         // Yo Eleven - single roll bet
         13 => { // YoEleven
             craps_position.yo_eleven = craps_position.yo_eleven
                 .checked_add(amount)
                 .ok_or(OreError::ArithmeticOverflow)?;
-            sol_log(&format!("Yo Eleven bet placed: {}", amount).as_str());
         }
         // Aces (2) - single roll bet
         14 => { // Aces
             craps_position.aces = craps_position.aces
                 .checked_add(amount)
                 .ok_or(OreError::ArithmeticOverflow)?;
-            sol_log(&format!("Aces (2) bet placed: {}", amount).as_str());
         }
         // Twelve - single roll bet
         15 => { // Twelve
             craps_position.twelve = craps_position.twelve
                 .checked_add(amount)
                 .ok_or(OreError::ArithmeticOverflow)?;
-            sol_log(&format!("Twelve bet placed: {}", amount).as_str());
         }
         // Yes bet (true odds) - sum rolls before 7
         26 => { // Yes (formerly Buy)
@@ -526,7 +406,6 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
                     craps_position.yes_bets[idx] = craps_position.yes_bets[idx]
                         .checked_add(amount)
                         .ok_or(OreError::ArithmeticOverflow)?;
-                    sol_log(&format!("Yes bet on sum {}: {}", point, amount).as_str());
                 } else {
                     sol_log("Invalid sum for Yes bet");
                     return Err(OreError::InvalidBetType.into());
@@ -544,7 +423,6 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
                     craps_position.no_bets[idx] = craps_position.no_bets[idx]
                         .checked_add(amount)
                         .ok_or(OreError::ArithmeticOverflow)?;
-                    sol_log(&format!("No bet on sum {}: {}", point, amount).as_str());
                 } else {
                     sol_log("Invalid sum for No bet");
                     return Err(OreError::InvalidBetType.into());
@@ -561,27 +439,69 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
                 craps_position.next_bets[idx] = craps_position.next_bets[idx]
                     .checked_add(amount)
                     .ok_or(OreError::ArithmeticOverflow)?;
-                sol_log(&format!("Next bet on sum {}: {}", point, amount).as_str());
             } else {
                 sol_log("Invalid sum for Next bet (must be 2-12)");
                 return Err(OreError::InvalidBetType.into());
             }
         }
+        // Side bets - held on the lazily-created CrapsSideBets account
+        16..=25 => {
+            // Everything but Fielder's Choice is come-out only.
+            if bet_type != 25 && !is_come_out {
+                sol_log("Side bet only allowed during come-out");
+                return Err(OreError::InvalidBetType.into());
+            }
+            let Some(side_bets_info) = side_bets_info.first() else {
+                sol_log("CrapsSideBets account required for side bets");
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            let side_bets =
+                load_or_create_side_bets(signer_info, side_bets_info, system_program, craps_game.epoch_id)?;
+            let bet = match bet_type {
+                16 => &mut side_bets.bonus_small,
+                17 => &mut side_bets.bonus_tall,
+                18 => &mut side_bets.bonus_all,
+                19 => &mut side_bets.fire_bet,
+                20 => &mut side_bets.diff_doubles_bet,
+                21 => &mut side_bets.ride_the_line_bet,
+                22 => &mut side_bets.mugsy_bet,
+                23 => &mut side_bets.hot_hand_bet,
+                24 => &mut side_bets.replay_bet,
+                _ => match side_bets.fielders_choice.get_mut(point as usize) {
+                    Some(bet) => bet,
+                    None => {
+                        sol_log("Invalid Fielder's Choice (must be 0, 1, or 2)");
+                        return Err(OreError::InvalidBetType.into());
+                    }
+                },
+            };
+            *bet = bet
+                .checked_add(amount)
+                .ok_or(OreError::ArithmeticOverflow)?;
+        }
         _ => {
             sol_log("Invalid bet type");
             return Err(OreError::InvalidBetType.into());
         }
     }
 
+    // Flag the bet's category so settlement scans it.
+    craps_position.mark_active_bets(bet_type_to_mask(bet_type));
+
     // Update totals.
     craps_position.total_wagered = craps_position.total_wagered
         .checked_add(amount)
         .ok_or(OreError::ArithmeticOverflow)?;
 
-    // Reserve this payout in the house bankroll
-    craps_game.reserved_payouts = craps_game.reserved_payouts
-        .checked_add(max_payout)
-        .ok_or(OreError::ArithmeticOverflow)?;
+    // Reserve this payout in the house bankroll and record it on the position's ledger.
+    if reserve {
+        craps_game.reserved_payouts = craps_game.reserved_payouts
+            .checked_add(max_payout)
+            .ok_or(OreError::ArithmeticOverflow)?;
+        craps_position.reserved_payouts = craps_position.reserved_payouts
+            .checked_add(max_payout)
+            .ok_or(OreError::ArithmeticOverflow)?;
+    }
 
     // Create vault's CRAP token account if it doesn't exist.
     if vault_crap_ata.data_is_empty() {
@@ -620,11 +540,19 @@ pub fn process_place_craps_bet(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
         .checked_add(amount)
         .ok_or(OreError::ArithmeticOverflow)?;
 
-    sol_log(&format!("Total wagered: {}, House bankroll: {}, Reserved payouts: {}",
-        craps_position.total_wagered,
-        craps_game.house_bankroll,
-        craps_game.reserved_payouts
-    ).as_str());
+    sol_log_data(&[BetPlacedEvent {
+        disc: OreEvent::BetPlaced as u64,
+        authority: *signer_info.key,
+        amount,
+        epoch_id: craps_game.epoch_id,
+        ts: clock.unix_timestamp,
+        bet_type,
+        point,
+        _event_padding: [0; 6],
+    }
+    .to_bytes()]);
+
+    record_metrics(side_bets_info, |metrics| metrics.record_bet(amount, is_new_position))?;
 
     Ok(())
 }
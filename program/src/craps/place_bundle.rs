@@ -0,0 +1,101 @@
+//! Hedged craps bet bundles
+
+use ore_api::prelude::*;
+use ore_api::simulator::bundle_reservation;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::place_bet::place_bet;
+
+/// Places up to `MAX_BUNDLE_LEGS` bets as one hedged bundle. Each leg is validated and
+/// staked like a PlaceCrapsBet, but the house reserves the most the bundle can pay on any
+/// roll (see `bundle_reservation`) instead of the sum of each leg's maximum. For a Pass
+/// Line hedged with Any Craps, that is whichever leg pays more, not both.
+///
+/// The position's ledger holds the netted amount until its next settlement, which reserves
+/// the difference again before resolving the round's first sub-roll. The bundle's price
+/// only has to cover that roll: whatever it is, the bets left standing need no more than
+/// the bundle reserved, and each later sub-roll of the round settles them at their own
+/// reservations, as if they had been placed one by one.
+pub fn process_place_craps_bundle(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = PlaceCrapsBundle::try_from_bytes(data)?;
+
+    // Load accounts. The rest are validated by each leg's placement.
+    let [_, craps_game_info, craps_position_info, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Place each leg, collecting them on their own so they can be priced together.
+    let mut legs = CrapsPosition::zeroed();
+    let mut total_staked: u64 = 0;
+    for i in 0..MAX_BUNDLE_LEGS {
+        let bet_type = args.bet_types[i];
+        let point = args.points[i];
+        let amount = u64::from_le_bytes(args.amounts[i]);
+        if amount == 0 {
+            continue;
+        }
+
+        sol_log(&format!("PlaceCrapsBundle: type={}, point={}, amount={}", bet_type, point, amount).as_str());
+
+        // Side bets settle off the position, where bundles can't price them.
+        let Some(leg) = legs.bet_mut(bet_type, point) else {
+            sol_log("Bet type can't be bundled");
+            return Err(OreError::InvalidBetType.into());
+        };
+        *leg = leg.checked_add(amount).ok_or(OreError::ArithmeticOverflow)?;
+        if bet_type == 8 {
+            legs.set_place_working(true);
+        }
+
+        place_bet(accounts, bet_type, point, amount, false)?;
+        total_staked = total_staked
+            .checked_add(amount)
+            .ok_or(OreError::ArithmeticOverflow)?;
+    }
+    if total_staked == 0 {
+        sol_log("Bundle has no legs");
+        return Err(OreError::InvalidBetAmount.into());
+    }
+
+    let craps_game = craps_game_info.as_account_mut::<CrapsGame>(&ore_api::ID)?;
+    let craps_position = craps_position_info.as_account_mut::<CrapsPosition>(&ore_api::ID)?;
+
+    // Price the bundle as a whole.
+    legs.epoch_id = craps_game.epoch_id;
+    let reservation = bundle_reservation(&legs, craps_game)?;
+    let gross = position_max_payout(&legs, craps_game.point)?;
+
+    // Check it against the bankroll as it stood before the legs' stakes came in,
+    // as a single bet would be.
+    let available_bankroll = craps_game.house_bankroll
+        .saturating_sub(total_staked)
+        .checked_sub(craps_game.reserved_payouts)
+        .ok_or(OreError::InsufficientBankroll)?;
+    if reservation > available_bankroll {
+        sol_log("Bundle exceeds available house bankroll (after reserved payouts)");
+        return Err(OreError::InsufficientBankroll.into());
+    }
+
+    // Reserve it in the house bankroll and record it on the position's ledger, keeping
+    // what it saved as credit for settlement to reserve again.
+    craps_game.reserved_payouts = craps_game.reserved_payouts
+        .checked_add(reservation)
+        .ok_or(OreError::ArithmeticOverflow)?;
+    craps_position.reserved_payouts = craps_position.reserved_payouts
+        .checked_add(reservation)
+        .ok_or(OreError::ArithmeticOverflow)?;
+    let saved = gross
+        .checked_sub(reservation)
+        .ok_or(OreError::ArithmeticOverflow)?;
+    let credit = craps_position
+        .get_bundle_credit()
+        .checked_add(saved)
+        .ok_or(OreError::ArithmeticOverflow)?;
+    craps_position.set_bundle_credit(credit)?;
+
+    sol_log(&format!("Bundle reserved {} of {}", reservation, gross).as_str());
+
+    Ok(())
+}
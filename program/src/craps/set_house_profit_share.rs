@@ -0,0 +1,88 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use crate::admin::{assert_admin, log_admin_action};
+
+/// Sets the share of net house wins routed to stakers each epoch.
+/// Admin only. Grows a legacy CrapsGame account to the current size on first use.
+pub fn process_set_house_profit_share(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = SetHouseProfitShare::try_from_bytes(data)?;
+    let share_bps = u64::from_le_bytes(args.share_bps);
+
+    sol_log(&format!("SetHouseProfitShare: share_bps={}", share_bps).as_str());
+
+    // Load accounts.
+    // Account layout:
+    // 0: signer - config admin
+    // 1: config - config PDA
+    // 2: craps_game - game state PDA
+    // 3: system_program
+    // 4: audit_log - admin audit log PDA
+    let [signer_info, config_info, craps_game_info, system_program, audit_log_info, admin_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+    craps_game_info
+        .is_writable()?
+        .has_seeds(&[CRAPS_GAME], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    if share_bps > MAX_HOUSE_PROFIT_STAKER_SHARE_BPS {
+        sol_log("Staker share exceeds maximum");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Reallocate legacy game accounts that predate the distribution fields.
+    let current_size = craps_game_info.data_len();
+    if current_size < CrapsGame::SIZE {
+        let rent = solana_program::rent::Rent::get()?;
+        let additional_rent = rent
+            .minimum_balance(CrapsGame::SIZE)
+            .saturating_sub(craps_game_info.lamports());
+        sol_log(&format!(
+            "Reallocation: {} -> {} bytes, additional rent: {} lamports",
+            current_size, CrapsGame::SIZE, additional_rent
+        ));
+        if additional_rent > 0 {
+            solana_program::program::invoke(
+                &solana_program::system_instruction::transfer(
+                    signer_info.key,
+                    craps_game_info.key,
+                    additional_rent,
+                ),
+                &[
+                    signer_info.clone(),
+                    craps_game_info.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        }
+        craps_game_info.realloc(CrapsGame::SIZE, false)?;
+    }
+
+    let craps_game = craps_game_info.as_account_mut::<CrapsGame>(&ore_api::ID)?;
+
+    // Start sharing from current net wins so past profit is not distributed retroactively.
+    if craps_game.staker_share_bps == 0 && craps_game.distributed_net_wins == 0 {
+        craps_game.distributed_net_wins = craps_game.net_wins();
+        craps_game.last_distribution_epoch = craps_game.epoch_id;
+    }
+    let old_share_bps = craps_game.staker_share_bps;
+    craps_game.staker_share_bps = share_bps;
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::SetHouseProfitShare,
+        audit_value(&[old_share_bps]),
+        audit_value(&[share_bps]),
+    )?;
+
+    Ok(())
+}
@@ -1,8 +1,12 @@
 use ore_api::prelude::*;
-use ore_api::state::{index_to_point, NUM_POINTS, NUM_HARDWAYS};
-use solana_program::log::sol_log;
+use ore_api::state::{index_to_point, index_to_sum, NUM_POINTS, NUM_HARDWAYS};
+use solana_program::clock::Clock;
+use solana_program::log::{sol_log, sol_log_data};
+use solana_program::sysvar::Sysvar;
 use steel::*;
 
+use super::metrics::record_metrics;
+use super::place_bet::calculate_max_payout;
 use super::utils::{
     square_to_dice_sum, square_to_dice, is_hardway, is_craps, is_natural, is_point_number,
     is_field_winner, hardway_loses, calculate_payout,
@@ -11,7 +15,7 @@ use super::utils::{
 /// SECURITY FIX 3.2: Helper to calculate and release reserved payout for a settled bet.
 /// Uses checked_sub to detect accounting errors. If reserved_payouts would go negative,
 /// this indicates a critical bug in the reservation system - we log a warning and clamp to 0.
-fn release_reserved_payout(craps_game: &mut CrapsGame, bet_amount: u64, payout_num: u64, payout_den: u64) {
+pub(crate) fn release_reserved_payout(craps_game: &mut CrapsGame, bet_amount: u64, payout_num: u64, payout_den: u64) {
     // Calculate the max payout that was reserved (bet + winnings)
     let payout = bet_amount
         .saturating_mul(payout_num)
@@ -32,6 +36,119 @@ fn release_reserved_payout(craps_game: &mut CrapsGame, bet_amount: u64, payout_n
     }
 }
 
+/// Releases every reservation held by a position and clears its ledger.
+/// Bets placed before the ledger existed are missing from it, so a position releases
+/// whichever is larger: its ledger, or the reservations estimated from its open bets,
+/// less the credit hedged bundles saved.
+pub(crate) fn release_position_reservations(craps_game: &mut CrapsGame, craps_position: &mut CrapsPosition) {
+    let estimated = estimate_reserved_payouts(craps_position)
+        .saturating_sub(craps_position.get_bundle_credit());
+    let held = craps_position.reserved_payouts.max(estimated);
+    match craps_game.reserved_payouts.checked_sub(held) {
+        Some(new_reserved) => {
+            craps_game.reserved_payouts = new_reserved;
+        }
+        None => {
+            sol_log("WARNING: reserved_payouts underflow detected - possible accounting bug");
+            craps_game.reserved_payouts = 0;
+        }
+    }
+    craps_position.reserved_payouts = 0;
+    craps_position.bundle_credit = [0; 7];
+}
+
+/// Reserves the credit hedged bundles saved a position again, so its ledger once more
+/// holds each bet's maximum, which is what resolving the bets releases. Whatever the roll,
+/// the bets left standing need no more than their bundle reserved.
+///
+/// The credit is reserved again before the round's first sub-roll resolves, so it has to
+/// fit the house bankroll like any other reservation.
+pub(crate) fn restore_bundle_credit(
+    craps_game: &mut CrapsGame,
+    craps_position: &mut CrapsPosition,
+) -> ProgramResult {
+    let credit = craps_position.get_bundle_credit();
+    if credit == 0 {
+        return Ok(());
+    }
+    let reserved_payouts = craps_game.reserved_payouts
+        .checked_add(credit)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if reserved_payouts > craps_game.house_bankroll {
+        sol_log("Bundle credit exceeds available house bankroll");
+        return Err(OreError::InsufficientBankroll.into());
+    }
+    craps_game.reserved_payouts = reserved_payouts;
+    craps_position.reserved_payouts = craps_position.reserved_payouts
+        .checked_add(credit)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    craps_position.bundle_credit = [0; 7];
+    Ok(())
+}
+
+/// Estimates the reservations held by a position's open bets using the placement math.
+/// Line odds don't record their point, so they use the smallest possible reservation.
+fn estimate_reserved_payouts(craps_position: &CrapsPosition) -> u64 {
+    let reserve = |bet_type: u8, point: u8, amount: u64| -> u64 {
+        if amount == 0 {
+            return 0;
+        }
+        calculate_max_payout(bet_type, point, amount).unwrap_or(amount)
+    };
+
+    let mut total = reserve(0, 0, craps_position.pass_line)
+        .saturating_add(reserve(1, 0, craps_position.dont_pass))
+        .saturating_add(reserve(2, 6, craps_position.pass_odds))
+        .saturating_add(reserve(3, 4, craps_position.dont_pass_odds))
+        .saturating_add(reserve(10, 0, craps_position.field_bet))
+        .saturating_add(reserve(11, 0, craps_position.any_seven))
+        .saturating_add(reserve(12, 0, craps_position.any_craps))
+        .saturating_add(reserve(13, 0, craps_position.yo_eleven))
+        .saturating_add(reserve(14, 0, craps_position.aces))
+        .saturating_add(reserve(15, 0, craps_position.twelve));
+
+    for i in 0..NUM_POINTS {
+        let point = index_to_point(i).unwrap_or(0);
+        total = total
+            .saturating_add(reserve(4, point, craps_position.come_bets[i]))
+            .saturating_add(reserve(5, point, craps_position.dont_come_bets[i]))
+            .saturating_add(reserve(6, point, craps_position.come_odds[i]))
+            .saturating_add(reserve(7, point, craps_position.dont_come_odds[i]))
+            .saturating_add(reserve(8, point, craps_position.place_bets[i]));
+    }
+    for (i, hardway) in [4u8, 6, 8, 10].iter().enumerate().take(NUM_HARDWAYS) {
+        total = total.saturating_add(reserve(9, *hardway, craps_position.hardways[i]));
+    }
+    for i in 0..11 {
+        let sum = index_to_sum(i).unwrap_or(0);
+        total = total
+            .saturating_add(reserve(26, sum, craps_position.yes_bets[i]))
+            .saturating_add(reserve(27, sum, craps_position.no_bets[i]))
+            .saturating_add(reserve(28, sum, craps_position.next_bets[i]));
+    }
+    total
+}
+
+/// Loads the CrapsSideBets account passed after the required accounts.
+/// Positions without side bets may omit it and settle against an empty `fallback`.
+pub(crate) fn load_side_bets<'a>(
+    side_bets_info: Option<&'a AccountInfo<'_>>,
+    craps_position: &CrapsPosition,
+    fallback: &'a mut CrapsSideBets,
+) -> Result<&'a mut CrapsSideBets, ProgramError> {
+    if !craps_position.has_active_bets(BET_MASK_SIDE) {
+        return Ok(fallback);
+    }
+    let Some(side_bets_info) = side_bets_info else {
+        sol_log("CrapsSideBets account required to settle side bets");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    side_bets_info
+        .is_writable()?
+        .has_seeds(&[CRAPS_SIDE_BETS, &craps_position.authority.to_bytes()], &ore_api::ID)?;
+    side_bets_info.as_account_mut::<CrapsSideBets>(&ore_api::ID)
+}
+
 /// Settles craps bets for a user after a round is complete.
 /// This should be called after reset() determines the winning square.
 pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
@@ -43,7 +160,8 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
     sol_log(&format!("SettleCraps: winning_square={}", winning_square).as_str());
 
     // Load accounts.
-    let [signer_info, craps_game_info, craps_position_info, round_info] = accounts else {
+    // The CrapsSideBets account is only required when the position holds side bets.
+    let [signer_info, craps_game_info, craps_position_info, round_info, side_bets_info @ ..] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
@@ -57,167 +175,164 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
     // Round info is just for verification that settlement is valid.
     let round = round_info.as_account::<Round>(&ore_api::ID)?;
 
-    // Validate that the winning square matches the round's result.
-    // In localnet/devnet mode, skip RNG validation to allow testing with any winning_square.
-    // Devnet's slot_hash returns zeros just like localnet.
-    #[cfg(not(any(feature = "localnet", feature = "devnet")))]
-    {
-        let Some(rng) = round.rng() else {
-            sol_log("Round has no valid RNG");
-            return Err(ProgramError::InvalidAccountData);
-        };
-        let actual_winning_square = round.winning_square(rng);
-        if actual_winning_square != winning_square {
-            sol_log(&format!("Winning square mismatch: expected {}, got {}", actual_winning_square, winning_square).as_str());
-            return Err(ProgramError::InvalidArgument);
+    // The dice result recorded on the round at reset is the single source of truth.
+    // The winning square argument must match it. Localnet/devnet builds record an
+    // injected roll from the TestRollOracle at reset, so they share this validation.
+    let Some(recorded_square) = round.dice_result_square() else {
+        sol_log("Round has no recorded dice result");
+        return Err(OreError::RollNotRecorded.into());
+    };
+    if recorded_square != winning_square {
+        sol_log(&format!("Winning square mismatch: expected {}, got {}", recorded_square, winning_square).as_str());
+        return Err(OreError::WinningSquareMismatch.into());
+    }
+
+    // Load craps game and position.
+    let craps_game = craps_game_info.as_checked_account_mut::<CrapsGame>()?;
+    let craps_position = craps_position_info.as_checked_account_mut::<CrapsPosition>()?;
+    let mut empty_side_bets = CrapsSideBets::zeroed();
+    let side_bets = load_side_bets(side_bets_info.first(), craps_position, &mut empty_side_bets)?;
+
+    // Positions settle the table's rolls in order, from the first roll they have not
+    // seen, against the phase each roll was thrown in.
+    let Some(first_phase) = craps_game.recorded_phase(craps_position.phase_id).copied() else {
+        if craps_position.phase_id == craps_game.phase_id {
+            sol_log("Already settled for this round");
+            return Err(OreError::AlreadySettled.into());
         }
+        // The rolls this position missed have left the history and can't be settled.
+        sol_log("Position fell behind the table's phase history");
+        refund_old_epoch(craps_game, craps_position, side_bets, round.id, craps_game.epoch_id);
+        craps_position.phase_id = craps_game.phase_id;
+        return Ok(());
+    };
+
+    // SECURITY FIX 1.2: Settle each roll exactly once, and in order. A round whose rolls
+    // the position has already settled is rejected, and so is a round after its next
+    // unsettled roll, so no roll can be skipped or replayed.
+    if first_phase.round_id > round.id {
+        sol_log("Already settled for this round");
+        return Err(OreError::AlreadySettled.into());
     }
-    #[cfg(any(feature = "localnet", feature = "devnet"))]
-    {
-        sol_log("TEST MODE: Skipping RNG validation (localnet/devnet)");
-        let _ = round; // Suppress unused warning
+    if first_phase.round_id < round.id {
+        sol_log(&format!("Settle round {} first", first_phase.round_id).as_str());
+        return Err(OreError::RollsUnsettled.into());
     }
 
-    // Load craps game and position.
-    if craps_game_info.data_is_empty() {
-        sol_log("Craps game not initialized");
-        return Err(ProgramError::UninitializedAccount);
-    }
-    if craps_position_info.data_is_empty() {
-        sol_log("Craps position not initialized");
-        return Err(ProgramError::UninitializedAccount);
-    }
-
-    // Verify account ownership
-    if craps_game_info.owner != &ore_api::ID {
-        sol_log("CrapsGame account not owned by program");
-        return Err(ProgramError::IncorrectProgramId);
-    }
-    if craps_position_info.owner != &ore_api::ID {
-        sol_log("CrapsPosition account not owned by program");
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
-    let craps_game = craps_game_info.as_account_mut::<CrapsGame>(&ore_api::ID)?;
-    let craps_position = craps_position_info.as_account_mut::<CrapsPosition>(&ore_api::ID)?;
-
-    // Check if position is for current epoch.
-    if craps_position.epoch_id != craps_game.epoch_id {
-        sol_log("Position from different epoch - refunding active bets");
-
-        // Calculate total active bets that need refund
-        let total_refund = craps_position.pass_line
-            .checked_add(craps_position.dont_pass).unwrap_or(0)
-            .checked_add(craps_position.pass_odds).unwrap_or(0)
-            .checked_add(craps_position.dont_pass_odds).unwrap_or(0)
-            .checked_add(craps_position.field_bet).unwrap_or(0)
-            .checked_add(craps_position.any_seven).unwrap_or(0)
-            .checked_add(craps_position.any_craps).unwrap_or(0)
-            .checked_add(craps_position.yo_eleven).unwrap_or(0)
-            .checked_add(craps_position.aces).unwrap_or(0)
-            .checked_add(craps_position.twelve).unwrap_or(0)
-            .checked_add(craps_position.bonus_small).unwrap_or(0)
-            .checked_add(craps_position.bonus_tall).unwrap_or(0)
-            .checked_add(craps_position.bonus_all).unwrap_or(0);
-
-        // Add array bets
-        let array_total: u64 = craps_position.come_bets.iter().sum::<u64>()
-            + craps_position.come_odds.iter().sum::<u64>()
-            + craps_position.dont_come_bets.iter().sum::<u64>()
-            + craps_position.dont_come_odds.iter().sum::<u64>()
-            + craps_position.place_bets.iter().sum::<u64>()
-            + craps_position.yes_bets.iter().sum::<u64>()
-            + craps_position.no_bets.iter().sum::<u64>()
-            + craps_position.next_bets.iter().sum::<u64>()
-            + craps_position.hardways.iter().sum::<u64>();
-
-        let total_refund = total_refund.checked_add(array_total).unwrap_or(total_refund);
-
-        if total_refund > 0 {
-            // Refund via pending_winnings
-            craps_position.pending_winnings = craps_position.pending_winnings
-                .checked_add(total_refund)
-                .unwrap_or(craps_position.pending_winnings);
-
-            sol_log(&format!("Refunded {} lamports from old epoch", total_refund).as_str());
+    // Settle each sub-roll of the round in order (fast table mode).
+    let ts = Clock::get()?.unix_timestamp;
+    let stake_before = craps_position.total_active_bets() + side_bets.total_bets();
+    let mut returned: u64 = 0;
+    while let Some(phase) = craps_game.recorded_phase(craps_position.phase_id).copied() {
+        if phase.round_id != round.id {
+            break;
         }
+        let Some(square) = round.sub_roll_square(phase.roll_index) else {
+            break;
+        };
 
-        // Reset position for new epoch
-        craps_position.epoch_id = craps_game.epoch_id;
-        craps_position.last_updated_round = round.id;
+        // Bets from an epoch the table has finished are refunded.
+        if craps_position.epoch_id != phase.epoch_id {
+            refund_old_epoch(craps_game, craps_position, side_bets, round.id, phase.epoch_id);
+        }
 
-        // Clear all bets
-        craps_position.pass_line = 0;
-        craps_position.dont_pass = 0;
-        craps_position.pass_odds = 0;
-        craps_position.dont_pass_odds = 0;
-        craps_position.field_bet = 0;
-        craps_position.any_seven = 0;
-        craps_position.any_craps = 0;
-        craps_position.yo_eleven = 0;
-        craps_position.aces = 0;
-        craps_position.twelve = 0;
-        craps_position.come_bets = [0; 6];
-        craps_position.come_odds = [0; 6];
-        craps_position.dont_come_bets = [0; 6];
-        craps_position.dont_come_odds = [0; 6];
-        craps_position.place_bets = [0; 6];
-        craps_position.yes_bets = [0; 11];
-        craps_position.no_bets = [0; 11];
-        craps_position.next_bets = [0; 11];
-        craps_position.hardways = [0; 4];
-        craps_position.clear_bonus_bets();
+        let winnings = settle_roll(craps_game, craps_position, side_bets, square, &phase, round.id, ts)?;
+        returned = returned.saturating_add(winnings);
+        craps_position.phase_id = phase.phase_id.wrapping_add(1);
+    }
+    craps_position.last_updated_round = round.id;
 
-        return Ok(());
+    let stake_after = craps_position.total_active_bets() + side_bets.total_bets();
+    record_metrics(side_bets_info, |metrics| {
+        metrics.record_settlement(stake_before.saturating_sub(stake_after), returned)
+    })?;
+
+    Ok(())
+}
+
+/// Settles a position's bets against a single roll thrown in `phase` and applies the result
+/// to the house bankroll. Returns the amount won, stakes included.
+fn settle_roll(
+    craps_game: &mut CrapsGame,
+    craps_position: &mut CrapsPosition,
+    side_bets: &mut CrapsSideBets,
+    winning_square: usize,
+    phase: &TablePhase,
+    round_id: u64,
+    ts: i64,
+) -> Result<u64, ProgramError> {
+    restore_bundle_credit(craps_game, craps_position)?;
+    let reserved_before = craps_game.reserved_payouts;
+    let debt_before = craps_position.unpaid_debt;
+    let (total_winnings, total_lost) = resolve_roll(craps_game, craps_position, side_bets, winning_square, phase)?;
+
+    // Everything released above came from this position's ledger.
+    sync_position_reservations(craps_game, craps_position, reserved_before);
+
+    apply_settlement(craps_game, craps_position, total_winnings, total_lost)?;
+
+    log_settlement(craps_position, round_id, winning_square, total_winnings, total_lost, debt_before, ts);
+
+    Ok(total_winnings)
+}
+
+/// Emits BetSettled for a resolved roll, and DebtRecorded if the house could not cover it.
+pub(crate) fn log_settlement(
+    craps_position: &CrapsPosition,
+    round_id: u64,
+    winning_square: usize,
+    total_winnings: u64,
+    total_lost: u64,
+    debt_before: u64,
+    ts: i64,
+) {
+    if total_winnings == 0 && total_lost == 0 {
+        return;
+    }
+    sol_log_data(&[BetSettledEvent {
+        disc: OreEvent::BetSettled as u64,
+        authority: craps_position.authority,
+        round_id,
+        winning_square: winning_square as u64,
+        winnings: total_winnings,
+        lost: total_lost,
+        pending_winnings: craps_position.pending_winnings,
+        ts,
+    }
+    .to_bytes()]);
+    if craps_position.unpaid_debt > debt_before {
+        sol_log_data(&[DebtRecordedEvent {
+            disc: OreEvent::DebtRecorded as u64,
+            authority: craps_position.authority,
+            amount: craps_position.unpaid_debt - debt_before,
+            unpaid_debt: craps_position.unpaid_debt,
+            ts,
+        }
+        .to_bytes()]);
     }
+}
 
-    // SECURITY FIX 1.2: Check if already settled for this round.
-    // Must use >= to prevent re-settling the same round multiple times.
-    // This prevents the attack where a user places a late bet and settles repeatedly.
-    // Special case: Allow first settlement when last_updated_round == 0 and round.id == 0
-    // (new positions start with last_updated_round = 0, and first round.id is also 0)
-    let is_first_settlement = craps_position.last_updated_round == 0 && round.id == 0;
-    if !is_first_settlement && craps_position.last_updated_round >= round.id {
-        sol_log("Already settled for this round");
-        return Err(ProgramError::Custom(1)); // Error code 1: ALREADY_SETTLED
-    }
-
-    // Early exit if no bets to settle - optimization to avoid iterating through empty bet slots
-    let has_any_bets = craps_position.pass_line > 0
-        || craps_position.dont_pass > 0
-        || craps_position.pass_odds > 0
-        || craps_position.dont_pass_odds > 0
-        || craps_position.field_bet > 0
-        || craps_position.any_seven > 0
-        || craps_position.any_craps > 0
-        || craps_position.yo_eleven > 0
-        || craps_position.aces > 0
-        || craps_position.twelve > 0
-        || craps_position.bonus_small > 0
-        || craps_position.bonus_tall > 0
-        || craps_position.bonus_all > 0
-        || craps_position.fire_bet > 0
-        || craps_position.diff_doubles_bet > 0
-        || craps_position.ride_the_line_bet > 0
-        || craps_position.mugsy_bet > 0
-        || craps_position.hot_hand_bet > 0
-        || craps_position.replay_bet > 0
-        || craps_position.fielders_choice.iter().any(|&x| x > 0)
-        || craps_position.hardways.iter().any(|&x| x > 0)
-        || craps_position.place_bets.iter().any(|&x| x > 0)
-        || craps_position.yes_bets.iter().any(|&x| x > 0)
-        || craps_position.no_bets.iter().any(|&x| x > 0)
-        || craps_position.next_bets.iter().any(|&x| x > 0)
-        || craps_position.come_bets.iter().any(|&x| x > 0)
-        || craps_position.come_odds.iter().any(|&x| x > 0)
-        || craps_position.dont_come_bets.iter().any(|&x| x > 0)
-        || craps_position.dont_come_odds.iter().any(|&x| x > 0);
-
-    if !has_any_bets {
+/// Resolves a position's bets against a single roll thrown in `phase`, releasing their
+/// reservations. The table itself is not moved; reset does that once per roll. Returns
+/// (total_winnings, total_lost) without touching the position's pending winnings or the
+/// house bankroll.
+pub(crate) fn resolve_roll(
+    craps_game: &mut CrapsGame,
+    craps_position: &mut CrapsPosition,
+    side_bets: &mut CrapsSideBets,
+    winning_square: usize,
+    phase: &TablePhase,
+) -> Result<(u64, u64), ProgramError> {
+    // Positions written before the active-bet bitmap existed rebuild it once.
+    if !craps_position.is_tracking_active_bets() {
+        craps_position.refresh_active_bets();
+    }
+
+    // Early exit if no bets to settle - the bitmap avoids scanning every bet slot.
+    if !craps_position.has_active_bets(!BET_MASK_TRACKED) {
         sol_log("No active bets to settle");
-        craps_position.last_updated_round = round.id;
         // Skip all settlement logic
-        return Ok(());
+        return Ok((0, 0));
     }
 
     // Get dice info from winning square.
@@ -372,32 +487,34 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
     // "Next" bets - win if the specific dice sum is rolled, pays true odds.
     // Index: 0=sum2, 1=sum3, ..., 10=sum12
 
-    for next_idx in 0..11usize {
-        let next_sum = (next_idx + 2) as u8; // 0->2, 1->3, ..., 10->12
-        if craps_position.next_bets[next_idx] > 0 {
-            let (num, den) = get_next_payout(next_sum);
+    if craps_position.has_active_bets(BET_MASK_NEXT) {
+        for next_idx in 0..11usize {
+            let next_sum = (next_idx + 2) as u8; // 0->2, 1->3, ..., 10->12
+            if craps_position.next_bets[next_idx] > 0 {
+                let (num, den) = get_next_payout(next_sum);
 
-            if dice_sum == next_sum {
-                // Next bet won!
-                let payout = calculate_payout(craps_position.next_bets[next_idx], num, den);
-                let win_amount = craps_position.next_bets[next_idx]
-                    .checked_add(payout)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                total_winnings = total_winnings
-                    .checked_add(win_amount)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                #[cfg(feature = "debug")]
-                sol_log(&format!("Next {} won: {} + {}", next_sum, craps_position.next_bets[next_idx], payout).as_str());
-            } else {
-                // Next bet lost (single-roll bet)
-                total_lost = total_lost
-                    .checked_add(craps_position.next_bets[next_idx])
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                #[cfg(feature = "debug")]
-                sol_log(&format!("Next {} lost", next_sum).as_str());
+                if dice_sum == next_sum {
+                    // Next bet won!
+                    let payout = calculate_payout(craps_position.next_bets[next_idx], num, den);
+                    let win_amount = craps_position.next_bets[next_idx]
+                        .checked_add(payout)
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    total_winnings = total_winnings
+                        .checked_add(win_amount)
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    #[cfg(feature = "debug")]
+                    sol_log(&format!("Next {} won: {} + {}", next_sum, craps_position.next_bets[next_idx], payout).as_str());
+                } else {
+                    // Next bet lost (single-roll bet)
+                    total_lost = total_lost
+                        .checked_add(craps_position.next_bets[next_idx])
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    #[cfg(feature = "debug")]
+                    sol_log(&format!("Next {} lost", next_sum).as_str());
+                }
+                release_reserved_payout(craps_game, craps_position.next_bets[next_idx], num, den);
+                craps_position.next_bets[next_idx] = 0;
             }
-            release_reserved_payout(craps_game, craps_position.next_bets[next_idx], num, den);
-            craps_position.next_bets[next_idx] = 0;
         }
     }
 
@@ -406,169 +523,171 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
     // Tall: Win if 8,9,10,11,12 all hit before 7. Pays 30:1.
     // All: Win if all Small + Tall totals hit before 7. Pays 150:1.
 
-    if craps_position.has_bonus_bets() {
+    if side_bets.has_bonus_bets() {
         if dice_sum == 7 {
             // Seven out - all bonus bets lose
-            if craps_position.bonus_small > 0 {
+            if side_bets.bonus_small > 0 {
                 total_lost = total_lost
-                    .checked_add(craps_position.bonus_small)
+                    .checked_add(side_bets.bonus_small)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 #[cfg(feature = "debug")]
-                sol_log(&format!("Bonus Small lost on 7: {}", craps_position.bonus_small).as_str());
-                release_reserved_payout(craps_game, craps_position.bonus_small, BONUS_SMALL_PAYOUT_NUM, BONUS_SMALL_PAYOUT_DEN);
+                sol_log(&format!("Bonus Small lost on 7: {}", side_bets.bonus_small).as_str());
+                release_reserved_payout(craps_game, side_bets.bonus_small, BONUS_SMALL_PAYOUT_NUM, BONUS_SMALL_PAYOUT_DEN);
             }
-            if craps_position.bonus_tall > 0 {
+            if side_bets.bonus_tall > 0 {
                 total_lost = total_lost
-                    .checked_add(craps_position.bonus_tall)
+                    .checked_add(side_bets.bonus_tall)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 #[cfg(feature = "debug")]
-                sol_log(&format!("Bonus Tall lost on 7: {}", craps_position.bonus_tall).as_str());
-                release_reserved_payout(craps_game, craps_position.bonus_tall, BONUS_TALL_PAYOUT_NUM, BONUS_TALL_PAYOUT_DEN);
+                sol_log(&format!("Bonus Tall lost on 7: {}", side_bets.bonus_tall).as_str());
+                release_reserved_payout(craps_game, side_bets.bonus_tall, BONUS_TALL_PAYOUT_NUM, BONUS_TALL_PAYOUT_DEN);
             }
-            if craps_position.bonus_all > 0 {
+            if side_bets.bonus_all > 0 {
                 total_lost = total_lost
-                    .checked_add(craps_position.bonus_all)
+                    .checked_add(side_bets.bonus_all)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 #[cfg(feature = "debug")]
-                sol_log(&format!("Bonus All lost on 7: {}", craps_position.bonus_all).as_str());
-                release_reserved_payout(craps_game, craps_position.bonus_all, BONUS_ALL_PAYOUT_NUM, BONUS_ALL_PAYOUT_DEN);
+                sol_log(&format!("Bonus All lost on 7: {}", side_bets.bonus_all).as_str());
+                release_reserved_payout(craps_game, side_bets.bonus_all, BONUS_ALL_PAYOUT_NUM, BONUS_ALL_PAYOUT_DEN);
             }
-            craps_position.clear_bonus_bets();
+            side_bets.clear_bonus_bets();
         } else {
             // Record this hit and check for wins
-            let (small_just_complete, tall_just_complete) = craps_position.record_bonus_hit(dice_sum);
+            let (small_just_complete, tall_just_complete) = side_bets.record_bonus_hit(dice_sum);
 
             // Check if Small bet won (all 2,3,4,5,6 have been hit)
-            if small_just_complete && craps_position.bonus_small > 0 {
-                let payout = calculate_payout(craps_position.bonus_small, BONUS_SMALL_PAYOUT_NUM, BONUS_SMALL_PAYOUT_DEN);
-                let win_amount = craps_position.bonus_small
+            if small_just_complete && side_bets.bonus_small > 0 {
+                let payout = calculate_payout(side_bets.bonus_small, BONUS_SMALL_PAYOUT_NUM, BONUS_SMALL_PAYOUT_DEN);
+                let win_amount = side_bets.bonus_small
                     .checked_add(payout)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 total_winnings = total_winnings
                     .checked_add(win_amount)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 #[cfg(feature = "debug")]
-                sol_log(&format!("Bonus Small won! {} + {}", craps_position.bonus_small, payout).as_str());
-                release_reserved_payout(craps_game, craps_position.bonus_small, BONUS_SMALL_PAYOUT_NUM, BONUS_SMALL_PAYOUT_DEN);
-                craps_position.bonus_small = 0;
+                sol_log(&format!("Bonus Small won! {} + {}", side_bets.bonus_small, payout).as_str());
+                release_reserved_payout(craps_game, side_bets.bonus_small, BONUS_SMALL_PAYOUT_NUM, BONUS_SMALL_PAYOUT_DEN);
+                side_bets.bonus_small = 0;
             }
 
             // Check if Tall bet won (all 8,9,10,11,12 have been hit)
-            if tall_just_complete && craps_position.bonus_tall > 0 {
-                let payout = calculate_payout(craps_position.bonus_tall, BONUS_TALL_PAYOUT_NUM, BONUS_TALL_PAYOUT_DEN);
-                let win_amount = craps_position.bonus_tall
+            if tall_just_complete && side_bets.bonus_tall > 0 {
+                let payout = calculate_payout(side_bets.bonus_tall, BONUS_TALL_PAYOUT_NUM, BONUS_TALL_PAYOUT_DEN);
+                let win_amount = side_bets.bonus_tall
                     .checked_add(payout)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 total_winnings = total_winnings
                     .checked_add(win_amount)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 #[cfg(feature = "debug")]
-                sol_log(&format!("Bonus Tall won! {} + {}", craps_position.bonus_tall, payout).as_str());
-                release_reserved_payout(craps_game, craps_position.bonus_tall, BONUS_TALL_PAYOUT_NUM, BONUS_TALL_PAYOUT_DEN);
-                craps_position.bonus_tall = 0;
+                sol_log(&format!("Bonus Tall won! {} + {}", side_bets.bonus_tall, payout).as_str());
+                release_reserved_payout(craps_game, side_bets.bonus_tall, BONUS_TALL_PAYOUT_NUM, BONUS_TALL_PAYOUT_DEN);
+                side_bets.bonus_tall = 0;
             }
 
             // Check if All bet won (both Small and Tall complete)
-            if craps_position.is_all_complete() && craps_position.bonus_all > 0 {
-                let payout = calculate_payout(craps_position.bonus_all, BONUS_ALL_PAYOUT_NUM, BONUS_ALL_PAYOUT_DEN);
-                let win_amount = craps_position.bonus_all
+            if side_bets.is_all_complete() && side_bets.bonus_all > 0 {
+                let payout = calculate_payout(side_bets.bonus_all, BONUS_ALL_PAYOUT_NUM, BONUS_ALL_PAYOUT_DEN);
+                let win_amount = side_bets.bonus_all
                     .checked_add(payout)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 total_winnings = total_winnings
                     .checked_add(win_amount)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 #[cfg(feature = "debug")]
-                sol_log(&format!("Bonus All won! {} + {}", craps_position.bonus_all, payout).as_str());
-                release_reserved_payout(craps_game, craps_position.bonus_all, BONUS_ALL_PAYOUT_NUM, BONUS_ALL_PAYOUT_DEN);
-                craps_position.bonus_all = 0;
+                sol_log(&format!("Bonus All won! {} + {}", side_bets.bonus_all, payout).as_str());
+                release_reserved_payout(craps_game, side_bets.bonus_all, BONUS_ALL_PAYOUT_NUM, BONUS_ALL_PAYOUT_DEN);
+                side_bets.bonus_all = 0;
             }
         }
     }
 
     // ==================== FIELDER'S CHOICE (Single-Roll) ====================
     // [0] = 2,3,4 pays 4:1 | [1] = 4,9,10 pays 2:1 | [2] = 10,11,12 pays 4:1
-    for i in 0..3 {
-        if craps_position.fielders_choice[i] > 0 {
-            let wins = match i {
-                0 => dice_sum == 2 || dice_sum == 3 || dice_sum == 4,
-                1 => dice_sum == 4 || dice_sum == 9 || dice_sum == 10,
-                2 => dice_sum == 10 || dice_sum == 11 || dice_sum == 12,
-                _ => false,
-            };
-            let (num, den) = match i {
-                0 => (FIELDERS_1_PAYOUT_NUM, FIELDERS_1_PAYOUT_DEN),
-                1 => (FIELDERS_2_PAYOUT_NUM, FIELDERS_2_PAYOUT_DEN),
-                2 => (FIELDERS_3_PAYOUT_NUM, FIELDERS_3_PAYOUT_DEN),
-                _ => (0, 1),
-            };
+    if craps_position.has_active_bets(BET_MASK_SIDE) {
+        for i in 0..3 {
+            if side_bets.fielders_choice[i] > 0 {
+                let wins = match i {
+                    0 => dice_sum == 2 || dice_sum == 3 || dice_sum == 4,
+                    1 => dice_sum == 4 || dice_sum == 9 || dice_sum == 10,
+                    2 => dice_sum == 10 || dice_sum == 11 || dice_sum == 12,
+                    _ => false,
+                };
+                let (num, den) = match i {
+                    0 => (FIELDERS_1_PAYOUT_NUM, FIELDERS_1_PAYOUT_DEN),
+                    1 => (FIELDERS_2_PAYOUT_NUM, FIELDERS_2_PAYOUT_DEN),
+                    2 => (FIELDERS_3_PAYOUT_NUM, FIELDERS_3_PAYOUT_DEN),
+                    _ => (0, 1),
+                };
 
-            if wins {
-                let payout = calculate_payout(craps_position.fielders_choice[i], num, den);
-                let win_amount = craps_position.fielders_choice[i]
-                    .checked_add(payout)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                total_winnings = total_winnings
-                    .checked_add(win_amount)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                #[cfg(feature = "debug")]
-                sol_log(&format!("Fielder's Choice {} won: {} + {}", i, craps_position.fielders_choice[i], payout).as_str());
-            } else {
-                total_lost = total_lost
-                    .checked_add(craps_position.fielders_choice[i])
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
+                if wins {
+                    let payout = calculate_payout(side_bets.fielders_choice[i], num, den);
+                    let win_amount = side_bets.fielders_choice[i]
+                        .checked_add(payout)
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    total_winnings = total_winnings
+                        .checked_add(win_amount)
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    #[cfg(feature = "debug")]
+                    sol_log(&format!("Fielder's Choice {} won: {} + {}", i, side_bets.fielders_choice[i], payout).as_str());
+                } else {
+                    total_lost = total_lost
+                        .checked_add(side_bets.fielders_choice[i])
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                }
+                release_reserved_payout(craps_game, side_bets.fielders_choice[i], num, den);
+                side_bets.fielders_choice[i] = 0;
             }
-            release_reserved_payout(craps_game, craps_position.fielders_choice[i], num, den);
-            craps_position.fielders_choice[i] = 0;
         }
     }
 
     // ==================== DIFFERENT DOUBLES ====================
     // Track unique doubles rolled. Win on 3+ unique doubles before 7.
     // Loses on 7. Payouts: 3=4:1, 4=8:1, 5=15:1, 6=100:1.
-    if craps_position.diff_doubles_bet > 0 {
+    if side_bets.diff_doubles_bet > 0 {
         if dice_sum == 7 {
             // Check for payout before losing
-            let count = craps_position.diff_doubles_count();
+            let count = side_bets.diff_doubles_count();
             if count >= 3 {
                 let (num, den) = get_diff_doubles_payout(count);
-                let payout = calculate_payout(craps_position.diff_doubles_bet, num, den);
-                let win_amount = craps_position.diff_doubles_bet
+                let payout = calculate_payout(side_bets.diff_doubles_bet, num, den);
+                let win_amount = side_bets.diff_doubles_bet
                     .checked_add(payout)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 total_winnings = total_winnings
                     .checked_add(win_amount)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 #[cfg(feature = "debug")]
-                sol_log(&format!("Different Doubles {} won on 7: {} + {}", count, craps_position.diff_doubles_bet, payout).as_str());
+                sol_log(&format!("Different Doubles {} won on 7: {} + {}", count, side_bets.diff_doubles_bet, payout).as_str());
             } else {
                 total_lost = total_lost
-                    .checked_add(craps_position.diff_doubles_bet)
+                    .checked_add(side_bets.diff_doubles_bet)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 #[cfg(feature = "debug")]
                 sol_log(&format!("Different Doubles lost on 7 with only {} doubles", count).as_str());
             }
-            release_reserved_payout(craps_game, craps_position.diff_doubles_bet, DIFF_DOUBLES_6_PAYOUT_NUM, DIFF_DOUBLES_6_PAYOUT_DEN);
-            craps_position.diff_doubles_bet = 0;
-            craps_position.diff_doubles_hits = 0;
+            release_reserved_payout(craps_game, side_bets.diff_doubles_bet, DIFF_DOUBLES_6_PAYOUT_NUM, DIFF_DOUBLES_6_PAYOUT_DEN);
+            side_bets.diff_doubles_bet = 0;
+            side_bets.diff_doubles_hits = 0;
         } else if die1 == die2 {
             // Record the double
-            let count = craps_position.record_double(die1);
+            let count = side_bets.record_double(die1);
             #[cfg(feature = "debug")]
             sol_log(&format!("Different Doubles: recorded {}-{}, now {} unique", die1, die2, count).as_str());
             // Check for all 6 doubles - auto win
             if count == 6 {
-                let payout = calculate_payout(craps_position.diff_doubles_bet, DIFF_DOUBLES_6_PAYOUT_NUM, DIFF_DOUBLES_6_PAYOUT_DEN);
-                let win_amount = craps_position.diff_doubles_bet
+                let payout = calculate_payout(side_bets.diff_doubles_bet, DIFF_DOUBLES_6_PAYOUT_NUM, DIFF_DOUBLES_6_PAYOUT_DEN);
+                let win_amount = side_bets.diff_doubles_bet
                     .checked_add(payout)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 total_winnings = total_winnings
                     .checked_add(win_amount)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 #[cfg(feature = "debug")]
-                sol_log(&format!("Different Doubles 6 won! {} + {}", craps_position.diff_doubles_bet, payout).as_str());
-                release_reserved_payout(craps_game, craps_position.diff_doubles_bet, DIFF_DOUBLES_6_PAYOUT_NUM, DIFF_DOUBLES_6_PAYOUT_DEN);
-                craps_position.diff_doubles_bet = 0;
-                craps_position.diff_doubles_hits = 0;
+                sol_log(&format!("Different Doubles 6 won! {} + {}", side_bets.diff_doubles_bet, payout).as_str());
+                release_reserved_payout(craps_game, side_bets.diff_doubles_bet, DIFF_DOUBLES_6_PAYOUT_NUM, DIFF_DOUBLES_6_PAYOUT_DEN);
+                side_bets.diff_doubles_bet = 0;
+                side_bets.diff_doubles_hits = 0;
             }
         }
     }
@@ -576,130 +695,131 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
     // ==================== HOT HAND ====================
     // Must hit all 10 totals (2-6, 8-12) before 7. Loses on 7.
     // Payouts: 9 totals = 20:1, 10 totals = 80:1.
-    if craps_position.hot_hand_bet > 0 {
+    if side_bets.hot_hand_bet > 0 {
         if dice_sum == 7 {
             // Check for partial payout (9 totals)
-            let count = craps_position.hot_hand_count();
+            let count = side_bets.hot_hand_count();
             if count >= 9 {
                 let (num, den) = if count >= 10 {
                     (HOT_HAND_10_PAYOUT_NUM, HOT_HAND_10_PAYOUT_DEN)
                 } else {
                     (HOT_HAND_9_PAYOUT_NUM, HOT_HAND_9_PAYOUT_DEN)
                 };
-                let payout = calculate_payout(craps_position.hot_hand_bet, num, den);
-                let win_amount = craps_position.hot_hand_bet
+                let payout = calculate_payout(side_bets.hot_hand_bet, num, den);
+                let win_amount = side_bets.hot_hand_bet
                     .checked_add(payout)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 total_winnings = total_winnings
                     .checked_add(win_amount)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 #[cfg(feature = "debug")]
-                sol_log(&format!("Hot Hand {} won on 7: {} + {}", count, craps_position.hot_hand_bet, payout).as_str());
+                sol_log(&format!("Hot Hand {} won on 7: {} + {}", count, side_bets.hot_hand_bet, payout).as_str());
             } else {
                 total_lost = total_lost
-                    .checked_add(craps_position.hot_hand_bet)
+                    .checked_add(side_bets.hot_hand_bet)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 #[cfg(feature = "debug")]
                 sol_log(&format!("Hot Hand lost on 7 with only {} totals", count).as_str());
             }
-            release_reserved_payout(craps_game, craps_position.hot_hand_bet, HOT_HAND_10_PAYOUT_NUM, HOT_HAND_10_PAYOUT_DEN);
-            craps_position.hot_hand_bet = 0;
-            craps_position.hot_hand_hits = 0;
+            release_reserved_payout(craps_game, side_bets.hot_hand_bet, HOT_HAND_10_PAYOUT_NUM, HOT_HAND_10_PAYOUT_DEN);
+            side_bets.hot_hand_bet = 0;
+            side_bets.hot_hand_hits = 0;
         } else {
             // Record the total hit
-            let complete = craps_position.record_hot_hand_hit(dice_sum);
+            let complete = side_bets.record_hot_hand_hit(dice_sum);
             if complete {
-                let payout = calculate_payout(craps_position.hot_hand_bet, HOT_HAND_10_PAYOUT_NUM, HOT_HAND_10_PAYOUT_DEN);
-                let win_amount = craps_position.hot_hand_bet
+                let payout = calculate_payout(side_bets.hot_hand_bet, HOT_HAND_10_PAYOUT_NUM, HOT_HAND_10_PAYOUT_DEN);
+                let win_amount = side_bets.hot_hand_bet
                     .checked_add(payout)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 total_winnings = total_winnings
                     .checked_add(win_amount)
                     .ok_or(ProgramError::ArithmeticOverflow)?;
                 #[cfg(feature = "debug")]
-                sol_log(&format!("Hot Hand complete! {} + {}", craps_position.hot_hand_bet, payout).as_str());
-                release_reserved_payout(craps_game, craps_position.hot_hand_bet, HOT_HAND_10_PAYOUT_NUM, HOT_HAND_10_PAYOUT_DEN);
-                craps_position.hot_hand_bet = 0;
-                craps_position.hot_hand_hits = 0;
+                sol_log(&format!("Hot Hand complete! {} + {}", side_bets.hot_hand_bet, payout).as_str());
+                release_reserved_payout(craps_game, side_bets.hot_hand_bet, HOT_HAND_10_PAYOUT_NUM, HOT_HAND_10_PAYOUT_DEN);
+                side_bets.hot_hand_bet = 0;
+                side_bets.hot_hand_hits = 0;
             }
         }
     }
 
     // ==================== MUGSY'S CORNER ====================
     // Wins on 7. Come-out 7 = 2:1, Point phase 7 = 3:1.
-    if craps_position.mugsy_bet > 0 {
+    if side_bets.mugsy_bet > 0 {
         if dice_sum == 7 {
-            let (num, den) = if craps_position.is_mugsy_comeout() {
+            // The table phase for this roll comes from the game's recorded phase.
+            let (num, den) = if phase.is_coming_out() {
                 (MUGSY_COMEOUT_7_PAYOUT_NUM, MUGSY_COMEOUT_7_PAYOUT_DEN)
             } else {
                 (MUGSY_POINT_7_PAYOUT_NUM, MUGSY_POINT_7_PAYOUT_DEN)
             };
-            let payout = calculate_payout(craps_position.mugsy_bet, num, den);
-            let win_amount = craps_position.mugsy_bet
+            let payout = calculate_payout(side_bets.mugsy_bet, num, den);
+            let win_amount = side_bets.mugsy_bet
                 .checked_add(payout)
                 .ok_or(ProgramError::ArithmeticOverflow)?;
             total_winnings = total_winnings
                 .checked_add(win_amount)
                 .ok_or(ProgramError::ArithmeticOverflow)?;
             #[cfg(feature = "debug")]
-            sol_log(&format!("Mugsy's Corner won on 7: {} + {}", craps_position.mugsy_bet, payout).as_str());
-            release_reserved_payout(craps_game, craps_position.mugsy_bet, MUGSY_POINT_7_PAYOUT_NUM, MUGSY_POINT_7_PAYOUT_DEN);
-            craps_position.mugsy_bet = 0;
-            craps_position.mugsy_state = 0;
+            sol_log(&format!("Mugsy's Corner won on 7: {} + {}", side_bets.mugsy_bet, payout).as_str());
+            release_reserved_payout(craps_game, side_bets.mugsy_bet, MUGSY_POINT_7_PAYOUT_NUM, MUGSY_POINT_7_PAYOUT_DEN);
+            side_bets.mugsy_bet = 0;
         }
-        // Note: Mugsy state transitions happen in the LINE BETS section when point is established
     }
 
     // ==================== HARDWAYS ====================
     // Lose on 7 or easy way, win on hardway.
 
-    for i in 0..NUM_HARDWAYS {
-        if craps_position.hardways[i] > 0 {
-            let hardway_num = match i {
-                0 => 4,
-                1 => 6,
-                2 => 8,
-                3 => 10,
-                _ => continue,
-            };
+    if craps_position.has_active_bets(BET_MASK_HARDWAY) {
+        for i in 0..NUM_HARDWAYS {
+            if craps_position.hardways[i] > 0 {
+                let hardway_num = match i {
+                    0 => 4,
+                    1 => 6,
+                    2 => 8,
+                    3 => 10,
+                    _ => continue,
+                };
 
-            let (num, den) = if hardway_num == 4 || hardway_num == 10 {
-                (HARD_4_10_PAYOUT_NUM, HARD_4_10_PAYOUT_DEN)
-            } else {
-                (HARD_6_8_PAYOUT_NUM, HARD_6_8_PAYOUT_DEN)
-            };
+                let (num, den) = if hardway_num == 4 || hardway_num == 10 {
+                    (HARD_4_10_PAYOUT_NUM, HARD_4_10_PAYOUT_DEN)
+                } else {
+                    (HARD_6_8_PAYOUT_NUM, HARD_6_8_PAYOUT_DEN)
+                };
 
-            if dice_sum == hardway_num && is_hard {
-                // Won! Hardway hit.
-                let payout = calculate_payout(craps_position.hardways[i], num, den);
-                let win_amount = craps_position.hardways[i]
-                    .checked_add(payout)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                total_winnings = total_winnings
-                    .checked_add(win_amount)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                #[cfg(feature = "debug")]
-                sol_log(&format!("Hard {} won: {} + {}", hardway_num, craps_position.hardways[i], payout).as_str());
-                release_reserved_payout(craps_game, craps_position.hardways[i], num, den);
-                craps_position.hardways[i] = 0;
-            } else if hardway_loses(winning_square, hardway_num) {
-                // Lost on 7 or easy way.
-                total_lost = total_lost
-                    .checked_add(craps_position.hardways[i])
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                #[cfg(feature = "debug")]
-                sol_log(&format!("Hard {} lost", hardway_num).as_str());
-                release_reserved_payout(craps_game, craps_position.hardways[i], num, den);
-                craps_position.hardways[i] = 0;
+                if dice_sum == hardway_num && is_hard {
+                    // Won! Hardway hit.
+                    let payout = calculate_payout(craps_position.hardways[i], num, den);
+                    let win_amount = craps_position.hardways[i]
+                        .checked_add(payout)
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    total_winnings = total_winnings
+                        .checked_add(win_amount)
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    #[cfg(feature = "debug")]
+                    sol_log(&format!("Hard {} won: {} + {}", hardway_num, craps_position.hardways[i], payout).as_str());
+                    release_reserved_payout(craps_game, craps_position.hardways[i], num, den);
+                    craps_position.hardways[i] = 0;
+                } else if hardway_loses(winning_square, hardway_num) {
+                    // Lost on 7 or easy way.
+                    total_lost = total_lost
+                        .checked_add(craps_position.hardways[i])
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    #[cfg(feature = "debug")]
+                    sol_log(&format!("Hard {} lost", hardway_num).as_str());
+                    release_reserved_payout(craps_game, craps_position.hardways[i], num, den);
+                    craps_position.hardways[i] = 0;
+                }
+                // Otherwise bet stays active.
             }
-            // Otherwise bet stays active.
         }
     }
 
     // ==================== PLACE BETS ====================
     // Win if number hits, lose on 7.
 
-    if craps_position.are_place_bets_working() {
+    if craps_position.has_active_bets(BET_MASK_PLACE) && craps_position.are_place_bets_working() {
         for i in 0..NUM_POINTS {
             if craps_position.place_bets[i] > 0 {
                 let point_num = match index_to_point(i) {
@@ -740,37 +860,39 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
     // Win if chosen sum hits before 7, pays true odds.
     // Index: 0=sum2, 1=sum3, ..., 10=sum12 (7 is invalid, always 0)
 
-    for i in 0..11usize {
-        let bet_sum = (i + 2) as u8; // 0->2, 1->3, ..., 10->12
-        // Skip sum 7 (index 5) as it's invalid for Yes bets
-        if bet_sum == 7 {
-            continue;
-        }
-        if craps_position.yes_bets[i] > 0 {
-            let (num, den) = get_yes_payout(bet_sum);
+    if craps_position.has_active_bets(BET_MASK_YES) {
+        for i in 0..11usize {
+            let bet_sum = (i + 2) as u8; // 0->2, 1->3, ..., 10->12
+            // Skip sum 7 (index 5) as it's invalid for Yes bets
+            if bet_sum == 7 {
+                continue;
+            }
+            if craps_position.yes_bets[i] > 0 {
+                let (num, den) = get_yes_payout(bet_sum);
 
-            if dice_sum == bet_sum {
-                // Yes bet won!
-                let payout = calculate_payout(craps_position.yes_bets[i], num, den);
-                let win_amount = craps_position.yes_bets[i]
-                    .checked_add(payout)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                total_winnings = total_winnings
-                    .checked_add(win_amount)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                #[cfg(feature = "debug")]
-                sol_log(&format!("Yes {} won: {} + {}", bet_sum, craps_position.yes_bets[i], payout).as_str());
-                release_reserved_payout(craps_game, craps_position.yes_bets[i], num, den);
-                craps_position.yes_bets[i] = 0;
-            } else if dice_sum == 7 {
-                // Yes bet lost on 7.
-                total_lost = total_lost
-                    .checked_add(craps_position.yes_bets[i])
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                #[cfg(feature = "debug")]
-                sol_log(&format!("Yes {} lost on 7", bet_sum).as_str());
-                release_reserved_payout(craps_game, craps_position.yes_bets[i], num, den);
-                craps_position.yes_bets[i] = 0;
+                if dice_sum == bet_sum {
+                    // Yes bet won!
+                    let payout = calculate_payout(craps_position.yes_bets[i], num, den);
+                    let win_amount = craps_position.yes_bets[i]
+                        .checked_add(payout)
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    total_winnings = total_winnings
+                        .checked_add(win_amount)
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    #[cfg(feature = "debug")]
+                    sol_log(&format!("Yes {} won: {} + {}", bet_sum, craps_position.yes_bets[i], payout).as_str());
+                    release_reserved_payout(craps_game, craps_position.yes_bets[i], num, den);
+                    craps_position.yes_bets[i] = 0;
+                } else if dice_sum == 7 {
+                    // Yes bet lost on 7.
+                    total_lost = total_lost
+                        .checked_add(craps_position.yes_bets[i])
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    #[cfg(feature = "debug")]
+                    sol_log(&format!("Yes {} lost on 7", bet_sum).as_str());
+                    release_reserved_payout(craps_game, craps_position.yes_bets[i], num, den);
+                    craps_position.yes_bets[i] = 0;
+                }
             }
         }
     }
@@ -779,37 +901,39 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
     // Win if 7 hits before chosen sum, pays inverse true odds.
     // Index: 0=sum2, 1=sum3, ..., 10=sum12 (7 is invalid, always 0)
 
-    for i in 0..11usize {
-        let bet_sum = (i + 2) as u8; // 0->2, 1->3, ..., 10->12
-        // Skip sum 7 (index 5) as it's invalid for No bets
-        if bet_sum == 7 {
-            continue;
-        }
-        if craps_position.no_bets[i] > 0 {
-            let (num, den) = get_no_payout(bet_sum);
+    if craps_position.has_active_bets(BET_MASK_NO) {
+        for i in 0..11usize {
+            let bet_sum = (i + 2) as u8; // 0->2, 1->3, ..., 10->12
+            // Skip sum 7 (index 5) as it's invalid for No bets
+            if bet_sum == 7 {
+                continue;
+            }
+            if craps_position.no_bets[i] > 0 {
+                let (num, den) = get_no_payout(bet_sum);
 
-            if dice_sum == 7 {
-                // No bet won! (7 came before sum)
-                let payout = calculate_payout(craps_position.no_bets[i], num, den);
-                let win_amount = craps_position.no_bets[i]
-                    .checked_add(payout)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                total_winnings = total_winnings
-                    .checked_add(win_amount)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                #[cfg(feature = "debug")]
-                sol_log(&format!("No {} won on 7: {} + {}", bet_sum, craps_position.no_bets[i], payout).as_str());
-                release_reserved_payout(craps_game, craps_position.no_bets[i], num, den);
-                craps_position.no_bets[i] = 0;
-            } else if dice_sum == bet_sum {
-                // No bet lost (sum hit before 7).
-                total_lost = total_lost
-                    .checked_add(craps_position.no_bets[i])
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                #[cfg(feature = "debug")]
-                sol_log(&format!("No {} lost on sum", bet_sum).as_str());
-                release_reserved_payout(craps_game, craps_position.no_bets[i], num, den);
-                craps_position.no_bets[i] = 0;
+                if dice_sum == 7 {
+                    // No bet won! (7 came before sum)
+                    let payout = calculate_payout(craps_position.no_bets[i], num, den);
+                    let win_amount = craps_position.no_bets[i]
+                        .checked_add(payout)
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    total_winnings = total_winnings
+                        .checked_add(win_amount)
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    #[cfg(feature = "debug")]
+                    sol_log(&format!("No {} won on 7: {} + {}", bet_sum, craps_position.no_bets[i], payout).as_str());
+                    release_reserved_payout(craps_game, craps_position.no_bets[i], num, den);
+                    craps_position.no_bets[i] = 0;
+                } else if dice_sum == bet_sum {
+                    // No bet lost (sum hit before 7).
+                    total_lost = total_lost
+                        .checked_add(craps_position.no_bets[i])
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    #[cfg(feature = "debug")]
+                    sol_log(&format!("No {} lost on sum", bet_sum).as_str());
+                    release_reserved_payout(craps_game, craps_position.no_bets[i], num, den);
+                    craps_position.no_bets[i] = 0;
+                }
             }
         }
     }
@@ -817,121 +941,123 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
     // ==================== COME BETS ====================
     // Win if number hits, lose on 7.
 
-    for i in 0..NUM_POINTS {
-        // Come bets
-        if craps_position.come_bets[i] > 0 {
-            let point_num = match index_to_point(i) {
-                Some(p) => p,
-                None => continue,
-            };
+    if craps_position.has_active_bets(BET_MASK_COME) {
+        for i in 0..NUM_POINTS {
+            // Come bets
+            if craps_position.come_bets[i] > 0 {
+                let point_num = match index_to_point(i) {
+                    Some(p) => p,
+                    None => continue,
+                };
 
-            if dice_sum == point_num {
-                // Come bet won!
-                let payout = craps_position.come_bets[i]; // 1:1
-                let win_amount = craps_position.come_bets[i]
-                    .checked_add(payout)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                total_winnings = total_winnings
-                    .checked_add(win_amount)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                // Release come bet reservation (1:1 payout)
-                release_reserved_payout(craps_game, craps_position.come_bets[i], PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN);
-
-                // Also pay come odds if any.
-                if craps_position.come_odds[i] > 0 {
-                    let (num, den) = get_true_odds_payout(point_num);
-                    let odds_payout = calculate_payout(craps_position.come_odds[i], num, den);
-                    let odds_win_amount = craps_position.come_odds[i]
-                        .checked_add(odds_payout)
+                if dice_sum == point_num {
+                    // Come bet won!
+                    let payout = craps_position.come_bets[i]; // 1:1
+                    let win_amount = craps_position.come_bets[i]
+                        .checked_add(payout)
                         .ok_or(ProgramError::ArithmeticOverflow)?;
                     total_winnings = total_winnings
-                        .checked_add(odds_win_amount)
+                        .checked_add(win_amount)
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    // Release come bet reservation (1:1 payout)
+                    release_reserved_payout(craps_game, craps_position.come_bets[i], PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN);
+
+                    // Also pay come odds if any.
+                    if craps_position.come_odds[i] > 0 {
+                        let (num, den) = get_true_odds_payout(point_num);
+                        let odds_payout = calculate_payout(craps_position.come_odds[i], num, den);
+                        let odds_win_amount = craps_position.come_odds[i]
+                            .checked_add(odds_payout)
+                            .ok_or(ProgramError::ArithmeticOverflow)?;
+                        total_winnings = total_winnings
+                            .checked_add(odds_win_amount)
+                            .ok_or(ProgramError::ArithmeticOverflow)?;
+                        #[cfg(feature = "debug")]
+                        sol_log(&format!("Come {} + odds won: {} + {}", point_num, craps_position.come_bets[i] + craps_position.come_odds[i], payout + odds_payout).as_str());
+                        // Release come odds reservation
+                        release_reserved_payout(craps_game, craps_position.come_odds[i], num, den);
+                        craps_position.come_odds[i] = 0;
+                    }
+                    craps_position.come_bets[i] = 0;
+                } else if dice_sum == 7 {
+                    // Come bet lost on 7.
+                    let lost_amount = craps_position.come_bets[i]
+                        .checked_add(craps_position.come_odds[i])
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    total_lost = total_lost
+                        .checked_add(lost_amount)
                         .ok_or(ProgramError::ArithmeticOverflow)?;
                     #[cfg(feature = "debug")]
-                    sol_log(&format!("Come {} + odds won: {} + {}", point_num, craps_position.come_bets[i] + craps_position.come_odds[i], payout + odds_payout).as_str());
-                    // Release come odds reservation
-                    release_reserved_payout(craps_game, craps_position.come_odds[i], num, den);
+                    sol_log(&format!("Come {} lost on 7", point_num).as_str());
+                    // Release come bet reservation
+                    release_reserved_payout(craps_game, craps_position.come_bets[i], PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN);
+                    // Release come odds reservation if any
+                    if craps_position.come_odds[i] > 0 {
+                        let (num, den) = get_true_odds_payout(point_num);
+                        release_reserved_payout(craps_game, craps_position.come_odds[i], num, den);
+                    }
+                    craps_position.come_bets[i] = 0;
                     craps_position.come_odds[i] = 0;
                 }
-                craps_position.come_bets[i] = 0;
-            } else if dice_sum == 7 {
-                // Come bet lost on 7.
-                let lost_amount = craps_position.come_bets[i]
-                    .checked_add(craps_position.come_odds[i])
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                total_lost = total_lost
-                    .checked_add(lost_amount)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                #[cfg(feature = "debug")]
-                sol_log(&format!("Come {} lost on 7", point_num).as_str());
-                // Release come bet reservation
-                release_reserved_payout(craps_game, craps_position.come_bets[i], PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN);
-                // Release come odds reservation if any
-                if craps_position.come_odds[i] > 0 {
-                    let (num, den) = get_true_odds_payout(point_num);
-                    release_reserved_payout(craps_game, craps_position.come_odds[i], num, den);
-                }
-                craps_position.come_bets[i] = 0;
-                craps_position.come_odds[i] = 0;
             }
-        }
 
-        // Don't Come bets
-        if craps_position.dont_come_bets[i] > 0 {
-            let point_num = match index_to_point(i) {
-                Some(p) => p,
-                None => continue,
-            };
+            // Don't Come bets
+            if craps_position.dont_come_bets[i] > 0 {
+                let point_num = match index_to_point(i) {
+                    Some(p) => p,
+                    None => continue,
+                };
 
-            if dice_sum == 7 {
-                // Don't Come bet won!
-                let payout = craps_position.dont_come_bets[i]; // 1:1
-                let win_amount = craps_position.dont_come_bets[i]
-                    .checked_add(payout)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                total_winnings = total_winnings
-                    .checked_add(win_amount)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                // Release don't come bet reservation
-                release_reserved_payout(craps_game, craps_position.dont_come_bets[i], PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN);
-
-                // Also pay don't come odds if any.
-                if craps_position.dont_come_odds[i] > 0 {
-                    // For reservation, use the same payout as regular odds (worst case)
-                    let (num, den) = get_true_odds_payout(point_num);
-                    let odds_payout = calculate_payout(craps_position.dont_come_odds[i], num, den);
-                    let odds_win_amount = craps_position.dont_come_odds[i]
-                        .checked_add(odds_payout)
+                if dice_sum == 7 {
+                    // Don't Come bet won!
+                    let payout = craps_position.dont_come_bets[i]; // 1:1
+                    let win_amount = craps_position.dont_come_bets[i]
+                        .checked_add(payout)
                         .ok_or(ProgramError::ArithmeticOverflow)?;
                     total_winnings = total_winnings
-                        .checked_add(odds_win_amount)
+                        .checked_add(win_amount)
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    // Release don't come bet reservation
+                    release_reserved_payout(craps_game, craps_position.dont_come_bets[i], PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN);
+
+                    // Also pay don't come odds if any.
+                    if craps_position.dont_come_odds[i] > 0 {
+                        // Don't come odds are laid, so they pay lay odds like don't pass odds.
+                        let (num, den) = get_dont_true_odds_payout(point_num);
+                        let odds_payout = calculate_payout(craps_position.dont_come_odds[i], num, den);
+                        let odds_win_amount = craps_position.dont_come_odds[i]
+                            .checked_add(odds_payout)
+                            .ok_or(ProgramError::ArithmeticOverflow)?;
+                        total_winnings = total_winnings
+                            .checked_add(odds_win_amount)
+                            .ok_or(ProgramError::ArithmeticOverflow)?;
+                        #[cfg(feature = "debug")]
+                        sol_log(&format!("Don't Come {} + odds won: {}", point_num, payout + odds_payout).as_str());
+                        // Release don't come odds reservation
+                        release_reserved_payout(craps_game, craps_position.dont_come_odds[i], num, den);
+                        craps_position.dont_come_odds[i] = 0;
+                    }
+                    craps_position.dont_come_bets[i] = 0;
+                } else if dice_sum == point_num {
+                    // Don't Come bet lost when point hit.
+                    let lost_amount = craps_position.dont_come_bets[i]
+                        .checked_add(craps_position.dont_come_odds[i])
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                    total_lost = total_lost
+                        .checked_add(lost_amount)
                         .ok_or(ProgramError::ArithmeticOverflow)?;
                     #[cfg(feature = "debug")]
-                    sol_log(&format!("Don't Come {} + odds won: {}", point_num, payout + odds_payout).as_str());
-                    // Release don't come odds reservation
-                    release_reserved_payout(craps_game, craps_position.dont_come_odds[i], num, den);
+                    sol_log(&format!("Don't Come {} lost on point", point_num).as_str());
+                    // Release don't come bet reservation
+                    release_reserved_payout(craps_game, craps_position.dont_come_bets[i], PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN);
+                    // Release don't come odds reservation if any
+                    if craps_position.dont_come_odds[i] > 0 {
+                        let (num, den) = get_dont_true_odds_payout(point_num);
+                        release_reserved_payout(craps_game, craps_position.dont_come_odds[i], num, den);
+                    }
+                    craps_position.dont_come_bets[i] = 0;
                     craps_position.dont_come_odds[i] = 0;
                 }
-                craps_position.dont_come_bets[i] = 0;
-            } else if dice_sum == point_num {
-                // Don't Come bet lost when point hit.
-                let lost_amount = craps_position.dont_come_bets[i]
-                    .checked_add(craps_position.dont_come_odds[i])
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                total_lost = total_lost
-                    .checked_add(lost_amount)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
-                #[cfg(feature = "debug")]
-                sol_log(&format!("Don't Come {} lost on point", point_num).as_str());
-                // Release don't come bet reservation
-                release_reserved_payout(craps_game, craps_position.dont_come_bets[i], PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN);
-                // Release don't come odds reservation if any
-                if craps_position.dont_come_odds[i] > 0 {
-                    let (num, den) = get_true_odds_payout(point_num);
-                    release_reserved_payout(craps_game, craps_position.dont_come_odds[i], num, den);
-                }
-                craps_position.dont_come_bets[i] = 0;
-                craps_position.dont_come_odds[i] = 0;
             }
         }
     }
@@ -939,8 +1065,8 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
     // ==================== LINE BETS ====================
     // These depend on the game state (come-out vs point phase).
 
-    let is_come_out = craps_game.is_coming_out();
-    let current_point = craps_game.get_point();
+    let is_come_out = phase.is_coming_out();
+    let current_point = phase.get_point();
 
     if is_come_out {
         // Come-out roll rules:
@@ -1010,15 +1136,9 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
             }
         } else if is_point_number(dice_sum) {
             // Point is established.
-            craps_game.set_point(dice_sum);
             #[cfg(feature = "debug")]
             sol_log(&format!("Point established: {}", dice_sum).as_str());
             // Line bets stay active.
-
-            // Update Mugsy state to point phase.
-            if craps_position.mugsy_bet > 0 {
-                craps_position.set_mugsy_point_phase();
-            }
         }
     } else {
         // Point phase rules:
@@ -1072,8 +1192,7 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
                 sol_log(&format!("Don't Pass lost on point {}", point).as_str());
                 release_reserved_payout(craps_game, craps_position.dont_pass, PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN);
                 if craps_position.dont_pass_odds > 0 {
-                    // Use true odds payout for reservation (worst case)
-                    let (num, den) = get_true_odds_payout(point);
+                    let (num, den) = get_dont_true_odds_payout(point);
                     release_reserved_payout(craps_game, craps_position.dont_pass_odds, num, den);
                 }
                 craps_position.dont_pass = 0;
@@ -1081,28 +1200,27 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
             }
 
             // Point was made - return to come-out for same shooter.
-            craps_game.clear_point();
             sol_log("Point made! Returning to come-out.".to_string().as_str());
 
             // ========== FIRE BET: Record point made ==========
-            if craps_position.fire_bet > 0 {
-                let fire_count = craps_position.record_fire_point(point);
+            if side_bets.fire_bet > 0 {
+                let fire_count = side_bets.record_fire_point(point);
                 #[cfg(feature = "debug")]
                 sol_log(&format!("Fire Bet: point {} made, now {} unique points", point, fire_count).as_str());
             }
 
             // ========== REPLAY BET: Record point made ==========
-            if craps_position.replay_bet > 0 {
-                let replay_count = craps_position.record_replay_point(point);
+            if side_bets.replay_bet > 0 {
+                let replay_count = side_bets.record_replay_point(point);
                 #[cfg(feature = "debug")]
                 sol_log(&format!("Replay Bet: point {} made {} times", point, replay_count).as_str());
             }
 
             // ========== RIDE THE LINE: Record pass line win ==========
-            if craps_position.ride_the_line_bet > 0 {
-                craps_position.record_ride_win();
+            if side_bets.ride_the_line_bet > 0 {
+                side_bets.record_ride_win();
                 #[cfg(feature = "debug")]
-                sol_log(&format!("Ride the Line: {} wins", craps_position.ride_wins_count).as_str());
+                sol_log(&format!("Ride the Line: {} wins", side_bets.ride_wins_count).as_str());
             }
 
         } else if dice_sum == 7 {
@@ -1147,9 +1265,7 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
                         .ok_or(ProgramError::ArithmeticOverflow)?;
                     #[cfg(feature = "debug")]
                     sol_log(&format!("Don't Pass Odds won: {} + {}", craps_position.dont_pass_odds, odds_payout).as_str());
-                    // Use true odds for reservation (worst case)
-                    let (num_res, den_res) = get_true_odds_payout(point);
-                    release_reserved_payout(craps_game, craps_position.dont_pass_odds, num_res, den_res);
+                    release_reserved_payout(craps_game, craps_position.dont_pass_odds, num, den);
                     craps_position.dont_pass_odds = 0;
                 }
                 release_reserved_payout(craps_game, craps_position.dont_pass, PASS_LINE_PAYOUT_NUM, PASS_LINE_PAYOUT_DEN);
@@ -1159,88 +1275,198 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
             }
 
             // ========== FIRE BET: Settle on seven-out ==========
-            if craps_position.fire_bet > 0 {
-                let fire_count = craps_position.fire_points_count();
+            if side_bets.fire_bet > 0 {
+                let fire_count = side_bets.fire_points_count();
                 if fire_count >= 4 {
                     let (num, den) = get_fire_bet_payout(fire_count);
-                    let payout = calculate_payout(craps_position.fire_bet, num, den);
-                    let win_amount = craps_position.fire_bet
+                    let payout = calculate_payout(side_bets.fire_bet, num, den);
+                    let win_amount = side_bets.fire_bet
                         .checked_add(payout)
                         .ok_or(ProgramError::ArithmeticOverflow)?;
                     total_winnings = total_winnings
                         .checked_add(win_amount)
                         .ok_or(ProgramError::ArithmeticOverflow)?;
                     #[cfg(feature = "debug")]
-                    sol_log(&format!("Fire Bet {} points won: {} + {}", fire_count, craps_position.fire_bet, payout).as_str());
+                    sol_log(&format!("Fire Bet {} points won: {} + {}", fire_count, side_bets.fire_bet, payout).as_str());
                 } else {
                     total_lost = total_lost
-                        .checked_add(craps_position.fire_bet)
+                        .checked_add(side_bets.fire_bet)
                         .ok_or(ProgramError::ArithmeticOverflow)?;
                     #[cfg(feature = "debug")]
                     sol_log(&format!("Fire Bet lost with only {} points", fire_count).as_str());
                 }
-                release_reserved_payout(craps_game, craps_position.fire_bet, FIRE_6_POINTS_PAYOUT_NUM, FIRE_6_POINTS_PAYOUT_DEN);
+                release_reserved_payout(craps_game, side_bets.fire_bet, FIRE_6_POINTS_PAYOUT_NUM, FIRE_6_POINTS_PAYOUT_DEN);
             }
 
             // ========== RIDE THE LINE: Settle on seven-out ==========
-            if craps_position.ride_the_line_bet > 0 {
-                let wins = craps_position.ride_wins_count;
+            if side_bets.ride_the_line_bet > 0 {
+                let wins = side_bets.ride_wins_count;
                 if wins >= 3 {
                     let (num, den) = get_ride_the_line_payout(wins);
-                    let payout = calculate_payout(craps_position.ride_the_line_bet, num, den);
-                    let win_amount = craps_position.ride_the_line_bet
+                    let payout = calculate_payout(side_bets.ride_the_line_bet, num, den);
+                    let win_amount = side_bets.ride_the_line_bet
                         .checked_add(payout)
                         .ok_or(ProgramError::ArithmeticOverflow)?;
                     total_winnings = total_winnings
                         .checked_add(win_amount)
                         .ok_or(ProgramError::ArithmeticOverflow)?;
                     #[cfg(feature = "debug")]
-                    sol_log(&format!("Ride the Line {} wins won: {} + {}", wins, craps_position.ride_the_line_bet, payout).as_str());
+                    sol_log(&format!("Ride the Line {} wins won: {} + {}", wins, side_bets.ride_the_line_bet, payout).as_str());
                 } else {
                     total_lost = total_lost
-                        .checked_add(craps_position.ride_the_line_bet)
+                        .checked_add(side_bets.ride_the_line_bet)
                         .ok_or(ProgramError::ArithmeticOverflow)?;
                     #[cfg(feature = "debug")]
                     sol_log(&format!("Ride the Line lost with only {} wins", wins).as_str());
                 }
-                release_reserved_payout(craps_game, craps_position.ride_the_line_bet, RIDE_11_WINS_PAYOUT_NUM, RIDE_11_WINS_PAYOUT_DEN);
+                release_reserved_payout(craps_game, side_bets.ride_the_line_bet, RIDE_11_WINS_PAYOUT_NUM, RIDE_11_WINS_PAYOUT_DEN);
             }
 
             // ========== REPLAY BET: Settle on seven-out ==========
-            if craps_position.replay_bet > 0 {
-                let max_count = craps_position.max_replay_count();
+            if side_bets.replay_bet > 0 {
+                let max_count = side_bets.max_replay_count();
                 if max_count >= 3 {
                     // Find which point had the max count and calculate payout
-                    let (num, den) = get_replay_bet_payout(&craps_position.replay_counts);
-                    let payout = calculate_payout(craps_position.replay_bet, num, den);
-                    let win_amount = craps_position.replay_bet
+                    let (num, den) = get_replay_bet_payout(&side_bets.replay_counts);
+                    let payout = calculate_payout(side_bets.replay_bet, num, den);
+                    let win_amount = side_bets.replay_bet
                         .checked_add(payout)
                         .ok_or(ProgramError::ArithmeticOverflow)?;
                     total_winnings = total_winnings
                         .checked_add(win_amount)
                         .ok_or(ProgramError::ArithmeticOverflow)?;
                     #[cfg(feature = "debug")]
-                    sol_log(&format!("Replay Bet won with max {} repeats: {} + {}", max_count, craps_position.replay_bet, payout).as_str());
+                    sol_log(&format!("Replay Bet won with max {} repeats: {} + {}", max_count, side_bets.replay_bet, payout).as_str());
                 } else {
                     total_lost = total_lost
-                        .checked_add(craps_position.replay_bet)
+                        .checked_add(side_bets.replay_bet)
                         .ok_or(ProgramError::ArithmeticOverflow)?;
                     #[cfg(feature = "debug")]
                     sol_log(&format!("Replay Bet lost with max {} repeats", max_count).as_str());
                 }
-                release_reserved_payout(craps_game, craps_position.replay_bet, REPLAY_4_10_4X_PAYOUT_NUM, REPLAY_4_10_4X_PAYOUT_DEN);
+                release_reserved_payout(craps_game, side_bets.replay_bet, REPLAY_4_10_4X_PAYOUT_NUM, REPLAY_4_10_4X_PAYOUT_DEN);
             }
 
             // New epoch - seven out ends the shooter's turn.
-            craps_game.start_new_epoch(round.id);
+            let next_epoch = phase.epoch_id + 1;
             #[cfg(feature = "debug")]
-            sol_log(&format!("Seven-out! New epoch: {}", craps_game.epoch_id).as_str());
+            sol_log(&format!("Seven-out! New epoch: {}", next_epoch).as_str());
 
             // Reset position for new epoch.
-            craps_position.reset_for_epoch(craps_game.epoch_id);
+            craps_position.reset_for_epoch(next_epoch);
+            side_bets.reset_for_epoch(next_epoch);
         }
     }
 
+    // Drop the categories this roll emptied so later rolls skip them.
+    craps_position.refresh_active_bets();
+    craps_position.set_side_bets_active(side_bets.has_any_bets());
+
+    Ok((total_winnings, total_lost))
+}
+
+/// Refunds a position's bets from a finished epoch, releases the reservations they held,
+/// and moves the position to `epoch_id`.
+pub(crate) fn refund_old_epoch(
+    craps_game: &mut CrapsGame,
+    craps_position: &mut CrapsPosition,
+    side_bets: &mut CrapsSideBets,
+    round_id: u64,
+    epoch_id: u64,
+) {
+    sol_log("Position from different epoch - refunding active bets");
+
+    // Calculate total active bets that need refund
+    let total_refund = craps_position.pass_line
+        .checked_add(craps_position.dont_pass).unwrap_or(0)
+        .checked_add(craps_position.pass_odds).unwrap_or(0)
+        .checked_add(craps_position.dont_pass_odds).unwrap_or(0)
+        .checked_add(craps_position.field_bet).unwrap_or(0)
+        .checked_add(craps_position.any_seven).unwrap_or(0)
+        .checked_add(craps_position.any_craps).unwrap_or(0)
+        .checked_add(craps_position.yo_eleven).unwrap_or(0)
+        .checked_add(craps_position.aces).unwrap_or(0)
+        .checked_add(craps_position.twelve).unwrap_or(0)
+        .checked_add(side_bets.total_bets()).unwrap_or(0);
+
+    // Add array bets
+    let array_total: u64 = craps_position.come_bets.iter().sum::<u64>()
+        + craps_position.come_odds.iter().sum::<u64>()
+        + craps_position.dont_come_bets.iter().sum::<u64>()
+        + craps_position.dont_come_odds.iter().sum::<u64>()
+        + craps_position.place_bets.iter().sum::<u64>()
+        + craps_position.yes_bets.iter().sum::<u64>()
+        + craps_position.no_bets.iter().sum::<u64>()
+        + craps_position.next_bets.iter().sum::<u64>()
+        + craps_position.hardways.iter().sum::<u64>();
+
+    let total_refund = total_refund.checked_add(array_total).unwrap_or(total_refund);
+
+    if total_refund > 0 {
+        // Refund via pending_winnings
+        craps_position.pending_winnings = craps_position.pending_winnings
+            .checked_add(total_refund)
+            .unwrap_or(craps_position.pending_winnings);
+
+        sol_log(&format!("Refunded {} lamports from old epoch", total_refund).as_str());
+    }
+
+    // Release the reservations the refunded bets were holding.
+    release_position_reservations(craps_game, craps_position);
+
+    // Reset position for new epoch
+    craps_position.epoch_id = epoch_id;
+    craps_position.last_updated_round = round_id;
+
+    // Clear all bets
+    craps_position.pass_line = 0;
+    craps_position.dont_pass = 0;
+    craps_position.pass_odds = 0;
+    craps_position.dont_pass_odds = 0;
+    craps_position.field_bet = 0;
+    craps_position.any_seven = 0;
+    craps_position.any_craps = 0;
+    craps_position.yo_eleven = 0;
+    craps_position.aces = 0;
+    craps_position.twelve = 0;
+    craps_position.come_bets = [0; 6];
+    craps_position.come_odds = [0; 6];
+    craps_position.dont_come_bets = [0; 6];
+    craps_position.dont_come_odds = [0; 6];
+    craps_position.place_bets = [0; 6];
+    craps_position.yes_bets = [0; 11];
+    craps_position.no_bets = [0; 11];
+    craps_position.next_bets = [0; 11];
+    craps_position.hardways = [0; 4];
+    side_bets.reset_for_epoch(epoch_id);
+    craps_position.set_side_bets_active(false);
+}
+
+/// Deducts the reservations released since `reserved_before` from the position's ledger.
+/// A position left with no open bets (side bets included) cannot hold reservations, so any
+/// remainder is released too.
+pub(crate) fn sync_position_reservations(
+    craps_game: &mut CrapsGame,
+    craps_position: &mut CrapsPosition,
+    reserved_before: u64,
+) {
+    let released = reserved_before.saturating_sub(craps_game.reserved_payouts);
+    craps_position.reserved_payouts = craps_position.reserved_payouts.saturating_sub(released);
+    if craps_position.total_active_bets() == 0
+        && !craps_position.has_active_bets(BET_MASK_SIDE)
+        && craps_position.reserved_payouts > 0
+    {
+        release_position_reservations(craps_game, craps_position);
+    }
+}
+
+/// Credits a settlement's winnings to the position and moves the net result through the house bankroll.
+pub(crate) fn apply_settlement(
+    craps_game: &mut CrapsGame,
+    craps_position: &mut CrapsPosition,
+    total_winnings: u64,
+    total_lost: u64,
+) -> ProgramResult {
     // Update position tracking.
     craps_position.pending_winnings = craps_position.pending_winnings
         .checked_add(total_winnings)
@@ -1251,7 +1477,6 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
     craps_position.total_lost = craps_position.total_lost
         .checked_add(total_lost)
         .ok_or(ProgramError::ArithmeticOverflow)?;
-    craps_position.last_updated_round = round.id;
 
     // Update house bankroll.
     craps_game.total_payouts = craps_game.total_payouts
@@ -1313,10 +1538,6 @@ pub fn process_settle_craps(accounts: &[AccountInfo<'_>], data: &[u8]) -> Progra
             .ok_or(ProgramError::ArithmeticOverflow)?;
     }
 
-    #[cfg(feature = "debug")]
-    sol_log(&format!("Settlement complete: won={}, lost={}, pending={}",
-        total_winnings, total_lost, craps_position.pending_winnings).as_str());
-
     Ok(())
 }
 
@@ -1486,3 +1707,548 @@ fn get_no_payout(sum: u8) -> (u64, u64) {
         _ => (0, 1), // 7 is invalid for No bets
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::place_bet::calculate_max_payout;
+    use super::*;
+
+    /// Square for 3 + 4 = 7.
+    const SEVEN: usize = 15;
+    /// Square for 2 + 4 = 6 (easy).
+    const SIX: usize = 9;
+    /// Square for 1 + 4 = 5.
+    const FIVE: usize = 3;
+
+    fn setup(point: u8) -> (CrapsGame, CrapsPosition) {
+        let mut game = CrapsGame::zeroed();
+        game.epoch_id = 1;
+        game.house_bankroll = 1_000_000_000;
+        game.set_point(point);
+        let mut position = CrapsPosition::zeroed();
+        position.epoch_id = 1;
+        (game, position)
+    }
+
+    /// Throws `square` on the table as reset does, returning the phase it was thrown in.
+    fn roll(game: &mut CrapsGame, square: usize) -> TablePhase {
+        let sum = ore_api::simulator::Roll::from_square(square).unwrap().sum();
+        game.apply_roll(1, 0, sum)
+    }
+
+    fn reserve(game: &mut CrapsGame, bet_type: u8, point: u8, amount: u64) {
+        game.reserved_payouts += calculate_max_payout(bet_type, point, amount).unwrap();
+    }
+
+    /// Places a bet the way place_bet does: reserve on the game and record it on the ledger.
+    fn place(game: &mut CrapsGame, position: &mut CrapsPosition, bet_type: u8, point: u8, amount: u64) {
+        let max_payout = calculate_max_payout(bet_type, point, amount).unwrap();
+        game.reserved_payouts += max_payout;
+        position.reserved_payouts += max_payout;
+        let point_idx = ore_api::state::point_to_index(point);
+        let sum_idx = ore_api::state::sum_to_index(point);
+        match bet_type {
+            0 => position.pass_line += amount,
+            1 => position.dont_pass += amount,
+            2 => position.pass_odds += amount,
+            3 => position.dont_pass_odds += amount,
+            4 => position.come_bets[point_idx.unwrap()] += amount,
+            5 => position.dont_come_bets[point_idx.unwrap()] += amount,
+            6 => position.come_odds[point_idx.unwrap()] += amount,
+            7 => position.dont_come_odds[point_idx.unwrap()] += amount,
+            8 => {
+                position.place_bets[point_idx.unwrap()] += amount;
+                position.set_place_working(true);
+            }
+            9 => position.hardways[ore_api::state::hardway_to_index(point).unwrap()] += amount,
+            10 => position.field_bet += amount,
+            11 => position.any_seven += amount,
+            12 => position.any_craps += amount,
+            13 => position.yo_eleven += amount,
+            14 => position.aces += amount,
+            15 => position.twelve += amount,
+            26 => position.yes_bets[sum_idx.unwrap()] += amount,
+            27 => position.no_bets[sum_idx.unwrap()] += amount,
+            28 => position.next_bets[sum_idx.unwrap()] += amount,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_dont_odds_reserved_at_lay_odds() {
+        // Laying 100 against a 4 wins 50; against a 6 wins 83.
+        assert_eq!(calculate_max_payout(3, 4, 100).unwrap(), 150);
+        assert_eq!(calculate_max_payout(3, 6, 120).unwrap(), 220);
+        assert_eq!(calculate_max_payout(7, 9, 90).unwrap(), 150);
+    }
+
+    #[test]
+    fn test_dont_pass_odds_reservation_released_on_seven_out() {
+        let (mut game, mut position) = setup(6);
+        position.dont_pass = 100;
+        reserve(&mut game, 1, 0, 100);
+        position.dont_pass_odds = 120;
+        reserve(&mut game, 3, 6, 120);
+
+        let phase = roll(&mut game, SEVEN);
+        settle_roll(&mut game, &mut position, &mut CrapsSideBets::zeroed(), SEVEN, &phase, 1, 0).unwrap();
+
+        assert_eq!(game.reserved_payouts, 0);
+        // 100 + 100 on the line, 120 + 100 on the lay odds.
+        assert_eq!(position.pending_winnings, 420);
+    }
+
+    #[test]
+    fn test_dont_pass_odds_reservation_released_on_point() {
+        let (mut game, mut position) = setup(6);
+        position.dont_pass = 100;
+        reserve(&mut game, 1, 0, 100);
+        position.dont_pass_odds = 120;
+        reserve(&mut game, 3, 6, 120);
+
+        let phase = roll(&mut game, SIX);
+        settle_roll(&mut game, &mut position, &mut CrapsSideBets::zeroed(), SIX, &phase, 1, 0).unwrap();
+
+        assert_eq!(game.reserved_payouts, 0);
+        assert_eq!(position.pending_winnings, 0);
+    }
+
+    #[test]
+    fn test_dont_come_odds_reservation_released() {
+        for square in [SEVEN, SIX] {
+            let (mut game, mut position) = setup(4);
+            position.dont_come_bets[2] = 100;
+            reserve(&mut game, 5, 6, 100);
+            position.dont_come_odds[2] = 60;
+            reserve(&mut game, 7, 6, 60);
+
+            let phase = roll(&mut game, square);
+            settle_roll(&mut game, &mut position, &mut CrapsSideBets::zeroed(), square, &phase, 1, 0).unwrap();
+
+            assert_eq!(game.reserved_payouts, 0);
+            if square == SEVEN {
+                // 100 + 100 on the don't come, 60 + 50 on the lay odds.
+                assert_eq!(position.pending_winnings, 310);
+            } else {
+                assert_eq!(position.pending_winnings, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dont_come_odds_pay_like_the_simulator() {
+        for point in [4, 5, 6, 8, 9, 10] {
+            let (mut game, mut position) = setup(4);
+            let i = point_to_index(point).unwrap();
+            position.dont_come_bets[i] = 100;
+            reserve(&mut game, 5, point, 100);
+            position.dont_come_odds[i] = 60;
+            reserve(&mut game, 7, point, 60);
+            let expected = ore_api::simulator::settle(&position, &game, ore_api::simulator::Roll::new(3, 4).unwrap()).unwrap();
+
+            let phase = roll(&mut game, SEVEN);
+            settle_roll(&mut game, &mut position, &mut CrapsSideBets::zeroed(), SEVEN, &phase, 1, 0).unwrap();
+
+            // The odds are laid, so they win less than they stake.
+            assert!(expected.winnings < 200 + 2 * 60, "point {}", point);
+            assert_eq!(position.pending_winnings, expected.winnings, "point {}", point);
+        }
+    }
+
+    #[test]
+    fn test_reservations_return_to_zero_after_full_settlement() {
+        let mut game = CrapsGame::zeroed();
+        game.epoch_id = 1;
+        game.house_bankroll = 1_000_000_000;
+        game.clear_point();
+        let mut position = CrapsPosition::zeroed();
+        position.epoch_id = 1;
+
+        place(&mut game, &mut position, 0, 0, 100);
+        place(&mut game, &mut position, 1, 0, 100);
+        place(&mut game, &mut position, 4, 5, 100);
+        place(&mut game, &mut position, 8, 6, 60);
+        place(&mut game, &mut position, 9, 8, 50);
+        place(&mut game, &mut position, 10, 0, 50);
+        place(&mut game, &mut position, 26, 4, 30);
+        place(&mut game, &mut position, 27, 10, 30);
+        place(&mut game, &mut position, 28, 11, 20);
+
+        // Point 6, come 5 hits, point made, then a new point and seven-out.
+        for square in [SIX, FIVE, SIX, FIVE, SEVEN] {
+            let phase = roll(&mut game, square);
+            settle_roll(&mut game, &mut position, &mut CrapsSideBets::zeroed(), square, &phase, 1, 0).unwrap();
+            // The game only ever holds this position's reservations.
+            assert_eq!(game.reserved_payouts, position.reserved_payouts);
+        }
+
+        assert_eq!(position.total_active_bets(), 0);
+        assert_eq!(position.reserved_payouts, 0);
+        assert_eq!(game.reserved_payouts, 0);
+    }
+
+    #[test]
+    fn test_old_epoch_refund_releases_reservations() {
+        let (mut game, mut position) = setup(6);
+        place(&mut game, &mut position, 0, 0, 100);
+        place(&mut game, &mut position, 8, 9, 50);
+        place(&mut game, &mut position, 10, 0, 25);
+        game.start_new_epoch(5);
+        let epoch_id = game.epoch_id;
+
+        refund_old_epoch(&mut game, &mut position, &mut CrapsSideBets::zeroed(), 6, epoch_id);
+
+        assert_eq!(position.pending_winnings, 175);
+        assert_eq!(position.total_active_bets(), 0);
+        assert_eq!(position.reserved_payouts, 0);
+        assert_eq!(game.reserved_payouts, 0);
+        assert_eq!(position.epoch_id, game.epoch_id);
+    }
+
+    #[test]
+    fn test_legacy_position_reservations_estimated() {
+        // Positions from before the ledger hold reservations but have an empty ledger.
+        let (mut game, mut position) = setup(6);
+        place(&mut game, &mut position, 8, 6, 60);
+        place(&mut game, &mut position, 27, 4, 30);
+        position.reserved_payouts = 0;
+
+        release_position_reservations(&mut game, &mut position);
+
+        assert_eq!(game.reserved_payouts, 0);
+    }
+
+    #[test]
+    fn test_partial_legacy_ledger_releases_estimate() {
+        // A bet from before the ledger, then one recorded on it.
+        let (mut game, mut position) = setup(6);
+        place(&mut game, &mut position, 8, 6, 60);
+        position.reserved_payouts = 0;
+        place(&mut game, &mut position, 10, 0, 50);
+
+        release_position_reservations(&mut game, &mut position);
+
+        assert_eq!(game.reserved_payouts, 0);
+        assert_eq!(position.reserved_payouts, 0);
+    }
+
+    #[test]
+    fn test_bundle_release_leaves_other_reservations() {
+        let (mut game, mut position) = setup(0);
+        game.clear_point();
+        place_bundle(&mut game, &mut position);
+        game.reserved_payouts += 1_000;
+
+        release_position_reservations(&mut game, &mut position);
+
+        assert_eq!(game.reserved_payouts, 1_000);
+    }
+
+    #[test]
+    fn test_legacy_position_rebuilds_active_bets() {
+        let (mut game, mut position) = setup(6);
+        position.field_bet = 50;
+        position.place_bets[ore_api::state::point_to_index(8).unwrap()] = 60;
+        position.set_place_working(true);
+        assert!(!position.is_tracking_active_bets());
+
+        let phase = roll(&mut game, SIX);
+        settle_roll(&mut game, &mut position, &mut CrapsSideBets::zeroed(), SIX, &phase, 1, 0).unwrap();
+
+        // The field bet resolved; the place bet on 8 is still up.
+        assert!(position.is_tracking_active_bets());
+        assert!(position.has_active_bets(BET_MASK_PLACE));
+        assert!(!position.has_active_bets(BET_MASK_SINGLE_ROLL));
+    }
+
+    #[test]
+    fn test_active_bets_cleared_on_seven_out() {
+        let (mut game, mut position) = setup(6);
+        place(&mut game, &mut position, 0, 0, 100);
+        place(&mut game, &mut position, 9, 8, 50);
+        position.mark_active_bets(BET_MASK_LINE | BET_MASK_HARDWAY);
+
+        let phase = roll(&mut game, SEVEN);
+        settle_roll(&mut game, &mut position, &mut CrapsSideBets::zeroed(), SEVEN, &phase, 1, 0).unwrap();
+
+        assert_eq!(position.active_bets, BET_MASK_TRACKED);
+    }
+
+    #[test]
+    fn test_positions_settle_against_one_table_roll() {
+        let (mut game, mut first) = setup(6);
+        let mut second = first;
+        place(&mut game, &mut first, 0, 0, 100);
+        place(&mut game, &mut second, 1, 0, 100);
+
+        // One seven-out on the table, settled by both positions.
+        let phase = roll(&mut game, SEVEN);
+        settle_roll(&mut game, &mut first, &mut CrapsSideBets::zeroed(), SEVEN, &phase, 1, 0).unwrap();
+        settle_roll(&mut game, &mut second, &mut CrapsSideBets::zeroed(), SEVEN, &phase, 1, 0).unwrap();
+
+        assert_eq!(game.epoch_id, 2);
+        assert_eq!((first.epoch_id, second.epoch_id), (2, 2));
+        assert_eq!(first.pending_winnings, 0);
+        assert_eq!(second.pending_winnings, 200);
+        assert_eq!(game.reserved_payouts, 0);
+    }
+
+    #[test]
+    fn test_side_bets_settle_on_companion_account() {
+        let (mut game, mut position) = setup(6);
+        let mut side_bets = CrapsSideBets::zeroed();
+        side_bets.epoch_id = 1;
+        side_bets.fire_bet = 10;
+        side_bets.mugsy_bet = 10;
+        for bet_type in [19, 22] {
+            let max_payout = calculate_max_payout(bet_type, 0, 10).unwrap();
+            game.reserved_payouts += max_payout;
+            position.reserved_payouts += max_payout;
+        }
+        position.mark_active_bets(BET_MASK_SIDE);
+
+        let phase = roll(&mut game, SEVEN);
+        settle_roll(&mut game, &mut position, &mut side_bets, SEVEN, &phase, 1, 0).unwrap();
+
+        // Mugsy's Corner pays 3:1 on a point-phase 7; the Fire Bet made no points.
+        assert_eq!(position.pending_winnings, 40);
+        assert!(!side_bets.has_any_bets());
+        assert!(!position.has_active_bets(BET_MASK_SIDE));
+        assert_eq!(position.reserved_payouts, 0);
+        assert_eq!(game.reserved_payouts, 0);
+    }
+
+    /// Places Pass 100 and Any Craps 10 as a hedged bundle: 200 reserved of 280.
+    fn place_bundle(game: &mut CrapsGame, position: &mut CrapsPosition) {
+        place(game, position, 0, 0, 100);
+        place(game, position, 12, 0, 10);
+        game.reserved_payouts -= 80;
+        position.reserved_payouts -= 80;
+        position.set_bundle_credit(80).unwrap();
+    }
+
+    #[test]
+    fn test_bundle_reservation_covers_every_sub_roll() {
+        let (mut game, mut position) = setup(0);
+        game.clear_point();
+        place_bundle(&mut game, &mut position);
+
+        // Point 5, two rolls that leave it standing, then a seven-out: one round of sub-rolls.
+        for square in [FIVE, SIX, SIX, SEVEN] {
+            let phase = roll(&mut game, square);
+            settle_roll(&mut game, &mut position, &mut CrapsSideBets::zeroed(), square, &phase, 1, 0).unwrap();
+            // The bets left standing never need more than the bundle reserved.
+            assert_eq!(position.get_bundle_credit(), 0);
+            assert_eq!(game.reserved_payouts, position.reserved_payouts);
+            assert!(position.reserved_payouts <= 200);
+        }
+
+        assert_eq!(position.total_active_bets(), 0);
+        assert_eq!(game.reserved_payouts, 0);
+    }
+
+    #[test]
+    fn test_bundle_credit_must_fit_bankroll() {
+        let (mut game, mut position) = setup(0);
+        game.clear_point();
+        place_bundle(&mut game, &mut position);
+        game.house_bankroll = game.reserved_payouts + 79;
+
+        assert_eq!(
+            restore_bundle_credit(&mut game, &mut position),
+            Err(OreError::InsufficientBankroll.into())
+        );
+        assert_eq!(position.get_bundle_credit(), 80);
+        assert_eq!(game.reserved_payouts, 200);
+
+        game.house_bankroll += 1;
+        restore_bundle_credit(&mut game, &mut position).unwrap();
+        assert_eq!(position.get_bundle_credit(), 0);
+        assert_eq!(game.reserved_payouts, 280);
+        assert_eq!(position.reserved_payouts, 280);
+    }
+
+    #[test]
+    fn test_bet_type_masks_cover_every_bet_type() {
+        for bet_type in 0..=28u8 {
+            assert_ne!(bet_type_to_mask(bet_type), 0, "bet type {} has no category", bet_type);
+        }
+        assert_eq!(bet_type_to_mask(29), 0);
+    }
+
+    #[test]
+    fn test_resolve_roll_matches_simulator() {
+        let mut position = CrapsPosition::zeroed();
+        position.epoch_id = 1;
+        position.pass_line = 100;
+        position.dont_pass = 110;
+        position.pass_odds = 50;
+        position.dont_pass_odds = 60;
+        position.field_bet = 10;
+        position.any_seven = 11;
+        position.any_craps = 12;
+        position.yo_eleven = 13;
+        position.aces = 14;
+        position.twelve = 15;
+        for i in 0..NUM_POINTS {
+            position.come_bets[i] = 20 + i as u64;
+            position.come_odds[i] = 30 + i as u64;
+            position.dont_come_bets[i] = 40 + i as u64;
+            position.dont_come_odds[i] = 50 + i as u64;
+            position.place_bets[i] = 60 + i as u64;
+        }
+        for i in 0..NUM_HARDWAYS {
+            position.hardways[i] = 70 + i as u64;
+        }
+        for i in 0..11 {
+            // Sum 7 can't carry Yes or No bets.
+            if i != 5 {
+                position.yes_bets[i] = 80 + i as u64;
+                position.no_bets[i] = 90 + i as u64;
+            }
+            position.next_bets[i] = 100 + i as u64;
+        }
+        position.set_place_working(true);
+        position.refresh_active_bets();
+
+        for point in [0, 4, 5, 6, 8, 9, 10] {
+            let (mut game, _) = setup(4);
+            match point {
+                0 => game.clear_point(),
+                _ => game.set_point(point),
+            }
+            game.reserved_payouts = u64::MAX / 2;
+            for square in 0..BOARD_SIZE {
+                let roll = ore_api::simulator::Roll::from_square(square).unwrap();
+                let expected = ore_api::simulator::settle(&position, &game, roll).unwrap();
+
+                let (mut game, mut position) = (game, position);
+                let phase = roll(&mut game, square);
+                let (winnings, lost) =
+                    resolve_roll(&mut game, &mut position, &mut CrapsSideBets::zeroed(), square, &phase).unwrap();
+
+                let case = format!("point {} square {}", point, square);
+                assert_eq!((winnings, lost), (expected.winnings, expected.lost), "{}", case);
+                assert_eq!(position, expected.position, "{}", case);
+                assert_eq!(game.point, expected.game.point, "{}", case);
+                assert_eq!(game.is_come_out, expected.game.is_come_out, "{}", case);
+                assert_eq!(game.epoch_id, expected.game.epoch_id, "{}", case);
+            }
+        }
+    }
+
+    /// What a bet returns on a roll: stakes plus winnings, stakes lost, or nothing yet.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Return {
+        Pays(u64),
+        Loses(u64),
+        Stands,
+    }
+
+    /// Every bet's return on every sum, written out by hand for a stake of 60 on each bet
+    /// in the row. Rolls are easy unless the row is marked hard, and a table point of 0 is
+    /// the come-out. A change to the payout constants or the rules has to change this table.
+    #[test]
+    fn test_golden_returns() {
+        use Return::{Loses as L, Pays as W, Stands as S};
+        const STAKE: u64 = 60;
+
+        #[rustfmt::skip]
+        let golden: &[(&str, u8, &[(u8, u8)], bool, [Return; 11])] = &[
+            //                                                           2        3        4        5        6        7        8        9        10       11       12
+            ("pass, come-out", 0, &[(0, 0)], false, [                    L(60),   L(60),   S,       S,       S,       W(120),  S,       S,       S,       W(120),  L(60)]),
+            ("don't pass, come-out", 0, &[(1, 0)], false, [              W(120),  W(120),  S,       S,       S,       L(60),   S,       S,       S,       L(60),   W(60)]),
+            ("pass, point 6", 6, &[(0, 0)], false, [                     S,       S,       S,       S,       W(120),  L(60),   S,       S,       S,       S,       S]),
+            ("don't pass, point 6", 6, &[(1, 0)], false, [               S,       S,       S,       S,       L(60),   W(120),  S,       S,       S,       S,       S]),
+            ("pass + odds, point 4", 4, &[(0, 0), (2, 4)], false, [      S,       S,       W(300),  S,       S,       L(120),  S,       S,       S,       S,       S]),
+            ("pass + odds, point 5", 5, &[(0, 0), (2, 5)], false, [      S,       S,       S,       W(270),  S,       L(120),  S,       S,       S,       S,       S]),
+            ("pass + odds, point 6", 6, &[(0, 0), (2, 6)], false, [      S,       S,       S,       S,       W(252),  L(120),  S,       S,       S,       S,       S]),
+            ("don't pass + odds, point 4", 4, &[(1, 0), (3, 4)], false, [S,       S,       L(120),  S,       S,       W(210),  S,       S,       S,       S,       S]),
+            ("don't pass + odds, point 5", 5, &[(1, 0), (3, 5)], false, [S,       S,       S,       L(120),  S,       W(220),  S,       S,       S,       S,       S]),
+            ("don't pass + odds, point 6", 6, &[(1, 0), (3, 6)], false, [S,       S,       S,       S,       L(120),  W(230),  S,       S,       S,       S,       S]),
+            ("come 5 + odds", 6, &[(4, 5), (6, 5)], false, [             S,       S,       S,       W(270),  S,       L(120),  S,       S,       S,       S,       S]),
+            ("don't come 10 + odds", 6, &[(5, 10), (7, 10)], false, [    S,       S,       S,       S,       S,       W(210),  S,       S,       L(120),  S,       S]),
+            ("place 4", 6, &[(8, 4)], false, [                           S,       S,       W(168),  S,       S,       L(60),   S,       S,       S,       S,       S]),
+            ("place 5", 6, &[(8, 5)], false, [                           S,       S,       S,       W(144),  S,       L(60),   S,       S,       S,       S,       S]),
+            ("place 6", 4, &[(8, 6)], false, [                           S,       S,       S,       S,       W(130),  L(60),   S,       S,       S,       S,       S]),
+            ("place 8", 4, &[(8, 8)], false, [                           S,       S,       S,       S,       S,       L(60),   W(130),  S,       S,       S,       S]),
+            ("place 9", 6, &[(8, 9)], false, [                           S,       S,       S,       S,       S,       L(60),   S,       W(144),  S,       S,       S]),
+            ("place 10", 6, &[(8, 10)], false, [                         S,       S,       S,       S,       S,       L(60),   S,       S,       W(168),  S,       S]),
+            ("hard 4, easy rolls", 6, &[(9, 4)], false, [                S,       S,       L(60),   S,       S,       L(60),   S,       S,       S,       S,       S]),
+            ("hard 4, hard rolls", 6, &[(9, 4)], true, [                 S,       S,       W(480),  S,       S,       L(60),   S,       S,       S,       S,       S]),
+            ("hard 6, easy rolls", 4, &[(9, 6)], false, [                S,       S,       S,       S,       L(60),   L(60),   S,       S,       S,       S,       S]),
+            ("hard 6, hard rolls", 4, &[(9, 6)], true, [                 S,       S,       S,       S,       W(600),  L(60),   S,       S,       S,       S,       S]),
+            ("hard 8, easy rolls", 4, &[(9, 8)], false, [                S,       S,       S,       S,       S,       L(60),   L(60),   S,       S,       S,       S]),
+            ("hard 8, hard rolls", 4, &[(9, 8)], true, [                 S,       S,       S,       S,       S,       L(60),   W(600),  S,       S,       S,       S]),
+            ("hard 10, easy rolls", 6, &[(9, 10)], false, [              S,       S,       S,       S,       S,       L(60),   S,       S,       L(60),   S,       S]),
+            ("hard 10, hard rolls", 6, &[(9, 10)], true, [               S,       S,       S,       S,       S,       L(60),   S,       S,       W(480),  S,       S]),
+            ("field", 6, &[(10, 0)], false, [                            W(180),  W(120),  W(120),  L(60),   L(60),   L(60),   L(60),   W(120),  W(120),  W(120),  W(180)]),
+            ("any seven", 6, &[(11, 0)], false, [                        L(60),   L(60),   L(60),   L(60),   L(60),   W(300),  L(60),   L(60),   L(60),   L(60),   L(60)]),
+            ("any craps", 6, &[(12, 0)], false, [                        W(480),  W(480),  L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   W(480)]),
+            ("yo", 6, &[(13, 0)], false, [                               L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   W(960),  L(60)]),
+            ("aces", 6, &[(14, 0)], false, [                             W(1860), L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60)]),
+            ("twelve", 6, &[(15, 0)], false, [                           L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   W(1860)]),
+            ("yes 2", 6, &[(26, 2)], false, [                            W(420),  S,       S,       S,       S,       L(60),   S,       S,       S,       S,       S]),
+            ("yes 3", 6, &[(26, 3)], false, [                            S,       W(240),  S,       S,       S,       L(60),   S,       S,       S,       S,       S]),
+            ("yes 4", 6, &[(26, 4)], false, [                            S,       S,       W(180),  S,       S,       L(60),   S,       S,       S,       S,       S]),
+            ("yes 5", 6, &[(26, 5)], false, [                            S,       S,       S,       W(150),  S,       L(60),   S,       S,       S,       S,       S]),
+            ("yes 6", 6, &[(26, 6)], false, [                            S,       S,       S,       S,       W(132),  L(60),   S,       S,       S,       S,       S]),
+            ("yes 8", 6, &[(26, 8)], false, [                            S,       S,       S,       S,       S,       L(60),   W(132),  S,       S,       S,       S]),
+            ("yes 9", 6, &[(26, 9)], false, [                            S,       S,       S,       S,       S,       L(60),   S,       W(150),  S,       S,       S]),
+            ("yes 10", 6, &[(26, 10)], false, [                          S,       S,       S,       S,       S,       L(60),   S,       S,       W(180),  S,       S]),
+            ("yes 11", 6, &[(26, 11)], false, [                          S,       S,       S,       S,       S,       L(60),   S,       S,       S,       W(240),  S]),
+            ("yes 12", 6, &[(26, 12)], false, [                          S,       S,       S,       S,       S,       L(60),   S,       S,       S,       S,       W(420)]),
+            ("no 2", 6, &[(27, 2)], false, [                             L(60),   S,       S,       S,       S,       W(70),   S,       S,       S,       S,       S]),
+            ("no 3", 6, &[(27, 3)], false, [                             S,       L(60),   S,       S,       S,       W(80),   S,       S,       S,       S,       S]),
+            ("no 4", 6, &[(27, 4)], false, [                             S,       S,       L(60),   S,       S,       W(90),   S,       S,       S,       S,       S]),
+            ("no 5", 6, &[(27, 5)], false, [                             S,       S,       S,       L(60),   S,       W(100),  S,       S,       S,       S,       S]),
+            ("no 6", 6, &[(27, 6)], false, [                             S,       S,       S,       S,       L(60),   W(110),  S,       S,       S,       S,       S]),
+            ("no 8", 6, &[(27, 8)], false, [                             S,       S,       S,       S,       S,       W(110),  L(60),   S,       S,       S,       S]),
+            ("no 9", 6, &[(27, 9)], false, [                             S,       S,       S,       S,       S,       W(100),  S,       L(60),   S,       S,       S]),
+            ("no 10", 6, &[(27, 10)], false, [                           S,       S,       S,       S,       S,       W(90),   S,       S,       L(60),   S,       S]),
+            ("no 11", 6, &[(27, 11)], false, [                           S,       S,       S,       S,       S,       W(80),   S,       S,       S,       L(60),   S]),
+            ("no 12", 6, &[(27, 12)], false, [                           S,       S,       S,       S,       S,       W(70),   S,       S,       S,       S,       L(60)]),
+            ("hop 2", 6, &[(28, 2)], false, [                            W(2160), L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60)]),
+            ("hop 3", 6, &[(28, 3)], false, [                            L(60),   W(1080), L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60)]),
+            ("hop 4", 6, &[(28, 4)], false, [                            L(60),   L(60),   W(720),  L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60)]),
+            ("hop 5", 6, &[(28, 5)], false, [                            L(60),   L(60),   L(60),   W(540),  L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60)]),
+            ("hop 6", 6, &[(28, 6)], false, [                            L(60),   L(60),   L(60),   L(60),   W(432),  L(60),   L(60),   L(60),   L(60),   L(60),   L(60)]),
+            ("hop 7", 6, &[(28, 7)], false, [                            L(60),   L(60),   L(60),   L(60),   L(60),   W(360),  L(60),   L(60),   L(60),   L(60),   L(60)]),
+            ("hop 8", 6, &[(28, 8)], false, [                            L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   W(432),  L(60),   L(60),   L(60),   L(60)]),
+            ("hop 9", 6, &[(28, 9)], false, [                            L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   W(540),  L(60),   L(60),   L(60)]),
+            ("hop 10", 6, &[(28, 10)], false, [                          L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   W(720),  L(60),   L(60)]),
+            ("hop 11", 6, &[(28, 11)], false, [                          L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   W(1080), L(60)]),
+            ("hop 12", 6, &[(28, 12)], false, [                          L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   L(60),   W(2160)]),
+        ];
+
+        for &(name, table_point, bets, hard, returns) in golden {
+            for (sum, expected) in (2u8..=12).zip(returns) {
+                let (mut game, mut position) = setup(table_point);
+                if table_point == 0 {
+                    game.clear_point();
+                }
+                for &(bet_type, point) in bets {
+                    place(&mut game, &mut position, bet_type, point, STAKE);
+                }
+
+                // Doubles only when the row asks for them, or when the sum can't be rolled otherwise.
+                let die1 = match sum {
+                    _ if hard && sum % 2 == 0 => sum / 2,
+                    2..=7 => 1,
+                    _ => sum - 6,
+                };
+                let square = (die1 as usize - 1) * 6 + (sum - die1) as usize - 1;
+                let phase = game.phase();
+                let result = resolve_roll(&mut game, &mut position, &mut CrapsSideBets::zeroed(), square, &phase)
+                    .unwrap();
+
+                let expected = match expected {
+                    W(amount) => (amount, 0),
+                    L(amount) => (0, amount),
+                    S => (0, 0),
+                };
+                assert_eq!(result, expected, "{} on {}", name, sum);
+            }
+        }
+    }
+}
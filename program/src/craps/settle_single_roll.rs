@@ -1,10 +1,149 @@
-//! Helper functions for settling single-roll craps bets
+//! Settlement of single-roll craps bets
 //! (Field, Any Seven, Any Craps, Yo, Aces, Twelve)
 
-#![allow(dead_code)]
+use ore_api::prelude::*;
+use solana_program::clock::Clock;
+use solana_program::log::sol_log;
+use solana_program::sysvar::Sysvar;
+use steel::*;
+
+use super::metrics::record_metrics;
+use super::settle::{
+    apply_settlement, log_settlement, release_reserved_payout, restore_bundle_credit,
+    sync_position_reservations,
+};
+use super::utils::{calculate_payout, is_field_winner, is_craps, square_to_dice_sum};
+
+/// Settles a position that holds only single-roll bets against its next roll, the first
+/// roll of the round. Those bets can't outlive that roll, so the rest of the round is
+/// marked settled without walking it. Positions holding any other bet use SettleCraps.
+pub fn process_settle_craps_single_roll(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    // The metrics account may follow as an optional trailing account.
+    let [signer_info, craps_game_info, craps_position_info, round_info, optional_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    craps_game_info
+        .is_writable()?
+        .has_seeds(&[CRAPS_GAME], &ore_api::ID)?;
+    craps_position_info
+        .is_writable()?
+        .has_seeds(&[CRAPS_POSITION, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    let round = round_info.as_account::<Round>(&ore_api::ID)?;
+    let craps_game = craps_game_info.as_checked_account_mut::<CrapsGame>()?;
+    let craps_position = craps_position_info.as_checked_account_mut::<CrapsPosition>()?;
+
+    // Any other bet needs every roll of the round, which only SettleCraps walks.
+    let single_roll_bets = craps_position.field_bet
+        + craps_position.any_seven
+        + craps_position.any_craps
+        + craps_position.yo_eleven
+        + craps_position.aces
+        + craps_position.twelve;
+    if craps_position.total_active_bets() > single_roll_bets || craps_position.has_active_bets(BET_MASK_SIDE) {
+        sol_log("Position holds multi-roll bets - use SettleCraps");
+        return Err(OreError::RollsUnsettled.into());
+    }
+
+    // SECURITY FIX 1.2: Same replay protection as SettleCraps. The position's next roll
+    // has to belong to this round.
+    let Some(phase) = craps_game.recorded_phase(craps_position.phase_id).copied() else {
+        if craps_position.phase_id == craps_game.phase_id {
+            sol_log("Already settled for this round");
+            return Err(OreError::AlreadySettled.into());
+        }
+        sol_log("Position fell behind the table's phase history - use SettleCraps");
+        return Err(OreError::RollsUnsettled.into());
+    };
+    if phase.round_id > round.id {
+        sol_log("Already settled for this round");
+        return Err(OreError::AlreadySettled.into());
+    }
+    if phase.round_id < round.id {
+        sol_log(&format!("Settle round {} first", phase.round_id).as_str());
+        return Err(OreError::RollsUnsettled.into());
+    }
+
+    // Old-epoch positions must go through SettleCraps, which handles the refund.
+    if craps_position.epoch_id != phase.epoch_id {
+        sol_log("Position from different epoch - use SettleCraps");
+        return Err(OreError::EpochMismatch.into());
+    }
+
+    let Some(winning_square) = round.sub_roll_square(phase.roll_index) else {
+        sol_log("Round has no recorded dice result");
+        return Err(OreError::RollNotRecorded.into());
+    };
 
-use ore_api::consts::*;
-use super::utils::{calculate_payout, is_field_winner, is_craps};
+    let dice_sum = square_to_dice_sum(winning_square);
+    restore_bundle_credit(craps_game, craps_position)?;
+    let reserved_before = craps_game.reserved_payouts;
+    let debt_before = craps_position.unpaid_debt;
+    let mut total_winnings: u64 = 0;
+    let mut total_lost: u64 = 0;
+    let mut resolved: u64 = 0;
+
+    // (bet, payout fn, reserved payout ratio) for each single-roll bet.
+    type PayoutFn = fn(u64, u8) -> Result<(u64, bool), ProgramError>;
+    let bets: [(&mut u64, PayoutFn, u64, u64); 6] = [
+        (&mut craps_position.field_bet, calculate_field_payout, FIELD_PAYOUT_2_12_NUM, FIELD_PAYOUT_2_12_DEN),
+        (&mut craps_position.any_seven, calculate_any_seven_payout, ANY_SEVEN_PAYOUT_NUM, ANY_SEVEN_PAYOUT_DEN),
+        (&mut craps_position.any_craps, calculate_any_craps_payout, ANY_CRAPS_PAYOUT_NUM, ANY_CRAPS_PAYOUT_DEN),
+        (&mut craps_position.yo_eleven, calculate_yo_payout, YO_ELEVEN_PAYOUT_NUM, YO_ELEVEN_PAYOUT_DEN),
+        (&mut craps_position.aces, calculate_aces_payout, ACES_PAYOUT_NUM, ACES_PAYOUT_DEN),
+        (&mut craps_position.twelve, calculate_twelve_payout, TWELVE_PAYOUT_NUM, TWELVE_PAYOUT_DEN),
+    ];
+    for (bet, payout_fn, num, den) in bets {
+        if *bet == 0 {
+            continue;
+        }
+        let (total_return, won) = payout_fn(*bet, dice_sum)?;
+        if won {
+            total_winnings = total_winnings
+                .checked_add(total_return)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        } else {
+            total_lost = total_lost
+                .checked_add(*bet)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+        resolved = resolved.saturating_add(*bet);
+        release_reserved_payout(craps_game, *bet, num, den);
+        *bet = 0;
+    }
+
+    sync_position_reservations(craps_game, craps_position, reserved_before);
+    apply_settlement(craps_game, craps_position, total_winnings, total_lost)?;
+    craps_position.refresh_active_bets();
+
+    // Nothing is left on the position, so the round's later rolls are settled too.
+    let mut next_phase = phase.phase_id.wrapping_add(1);
+    while craps_game
+        .recorded_phase(next_phase)
+        .is_some_and(|later| later.round_id == round.id)
+    {
+        next_phase = next_phase.wrapping_add(1);
+    }
+    craps_position.phase_id = next_phase;
+    craps_position.last_updated_round = round.id;
+    log_settlement(
+        craps_position,
+        round.id,
+        winning_square,
+        total_winnings,
+        total_lost,
+        debt_before,
+        Clock::get()?.unix_timestamp,
+    );
+
+    record_metrics(optional_accounts, |metrics| metrics.record_settlement(resolved, total_winnings))?;
+
+    #[cfg(feature = "debug")]
+    sol_log(&format!("Single-roll settlement: sum={}, won={}, lost={}", dice_sum, total_winnings, total_lost).as_str());
+
+    Ok(())
+}
 
 /// Calculate field bet payout
 /// Returns (total_return, is_winner) where total_return includes original bet if won
@@ -0,0 +1,212 @@
+//! Read-only settlement preview
+
+use ore_api::prelude::*;
+use solana_program::{log::sol_log, program::set_return_data};
+use steel::*;
+
+use super::settle::{refund_old_epoch, resolve_roll};
+
+/// Previews what a position would win or lose if the dice landed on the given square.
+/// Runs the same settlement math as SettleCraps against copies of the accounts and
+/// writes a `SettlePreview` to the return data; no account is modified.
+pub fn process_simulate_settle(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = SimulateSettle::try_from_bytes(data)?;
+    let winning_square = u64::from_le_bytes(args.winning_square) as usize;
+    if winning_square >= BOARD_SIZE {
+        sol_log("Invalid winning square");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    // The CrapsSideBets account is only required when the position holds side bets.
+    let [craps_game_info, craps_position_info, side_bets_info @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    craps_game_info.has_seeds(&[CRAPS_GAME], &ore_api::ID)?;
+    let craps_game = craps_game_info.as_account::<CrapsGame>(&ore_api::ID)?;
+    let craps_position = craps_position_info.as_account::<CrapsPosition>(&ore_api::ID)?;
+    let side_bets = match side_bets_info.first() {
+        _ if !craps_position.has_active_bets(BET_MASK_SIDE) => CrapsSideBets::zeroed(),
+        Some(side_bets_info) => {
+            side_bets_info
+                .has_seeds(&[CRAPS_SIDE_BETS, &craps_position.authority.to_bytes()], &ore_api::ID)?;
+            *side_bets_info.as_account::<CrapsSideBets>(&ore_api::ID)?
+        }
+        None => {
+            sol_log("CrapsSideBets account required to preview side bets");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+    };
+
+    let preview = preview_settlement(craps_game, craps_position, &side_bets, winning_square)?;
+    set_return_data(preview.to_bytes());
+
+    Ok(())
+}
+
+/// Settles copies of the game and position against a single roll and reports the outcome.
+pub(crate) fn preview_settlement(
+    craps_game: &CrapsGame,
+    craps_position: &CrapsPosition,
+    side_bets: &CrapsSideBets,
+    winning_square: usize,
+) -> Result<SettlePreview, ProgramError> {
+    let mut game = *craps_game;
+    let mut position = *craps_position;
+    let mut side_bets = *side_bets;
+    let round_id = game.epoch_start_round;
+
+    // The roll is previewed in the phase of the next roll the position has to settle:
+    // a recorded one if the table has already thrown it, the table's current phase if not.
+    let phase = game
+        .recorded_phase(position.phase_id)
+        .copied()
+        .unwrap_or_else(|| game.phase());
+
+    // Old-epoch positions are refunded rather than settled.
+    if position.epoch_id != phase.epoch_id {
+        refund_old_epoch(&mut game, &mut position, &mut side_bets, round_id, phase.epoch_id);
+        return Ok(SettlePreview {
+            winnings: position
+                .pending_winnings
+                .saturating_sub(craps_position.pending_winnings),
+            pending_winnings: position.pending_winnings,
+            ..Default::default()
+        });
+    }
+
+    let (winnings, lost) = resolve_roll(&mut game, &mut position, &mut side_bets, winning_square, &phase)?;
+    let pending_winnings = position
+        .pending_winnings
+        .checked_add(winnings)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(SettlePreview {
+        winnings,
+        lost,
+        remaining_bets: position.total_active_bets() + side_bets.total_bets(),
+        pending_winnings,
+        seven_out: (position.epoch_id != phase.epoch_id) as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Square for 3 + 4 = 7.
+    const SEVEN: usize = 15;
+    /// Square for 2 + 4 = 6 (easy).
+    const SIX: usize = 9;
+
+    fn setup() -> (CrapsGame, CrapsPosition) {
+        let mut game = CrapsGame::zeroed();
+        game.epoch_id = 1;
+        game.house_bankroll = 1_000_000_000;
+        game.set_point(6);
+        let mut position = CrapsPosition::zeroed();
+        position.epoch_id = 1;
+        position.pass_line = 100;
+        position.field_bet = 50;
+        position.place_bets[point_to_index(8).unwrap()] = 60;
+        position.set_place_working(true);
+        (game, position)
+    }
+
+    #[test]
+    fn test_preview_point_made() {
+        let (game, position) = setup();
+        let preview = preview_settlement(&game, &position, &CrapsSideBets::zeroed(), SIX).unwrap();
+
+        // Pass line pays 1:1, field loses on 6, place 8 stays up.
+        assert_eq!(preview.winnings, 200);
+        assert_eq!(preview.lost, 50);
+        assert_eq!(preview.remaining_bets, 60);
+        assert_eq!(preview.pending_winnings, 200);
+        assert_eq!(preview.seven_out, 0);
+    }
+
+    #[test]
+    fn test_preview_seven_out() {
+        let (game, position) = setup();
+        let preview = preview_settlement(&game, &position, &CrapsSideBets::zeroed(), SEVEN).unwrap();
+
+        assert_eq!(preview.winnings, 0);
+        assert_eq!(preview.lost, 210);
+        assert_eq!(preview.remaining_bets, 0);
+        assert_eq!(preview.seven_out, 1);
+    }
+
+    /// Serializes an account as the runtime holds it, in words so its body is aligned.
+    fn account_data<T: Discriminator + Pod>(account: &T) -> Vec<u64> {
+        let mut data = vec![0u64; 1 + std::mem::size_of::<T>().div_ceil(8)];
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut data);
+        bytes[0] = T::discriminator();
+        bytes[8..8 + std::mem::size_of::<T>()].copy_from_slice(bytemuck::bytes_of(account));
+        data
+    }
+
+    #[test]
+    fn test_preview_does_not_mutate() {
+        let (game, position) = setup();
+        let mut game_data = account_data(&game);
+        let mut position_data = account_data(&position);
+        let (game_before, position_before) = (game_data.clone(), position_data.clone());
+
+        // Pass both accounts writable, so nothing but the processor keeps them unchanged.
+        let game_key = craps_game_pda().0;
+        let position_key = craps_position_pda(Pubkey::new_unique()).0;
+        let (mut game_lamports, mut position_lamports) = (1, 1);
+        let game_len = 8 + std::mem::size_of::<CrapsGame>();
+        let position_len = 8 + std::mem::size_of::<CrapsPosition>();
+        let accounts = [
+            AccountInfo::new(
+                &game_key,
+                false,
+                true,
+                &mut game_lamports,
+                &mut bytemuck::cast_slice_mut(&mut game_data)[..game_len],
+                &ore_api::ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &position_key,
+                false,
+                true,
+                &mut position_lamports,
+                &mut bytemuck::cast_slice_mut(&mut position_data)[..position_len],
+                &ore_api::ID,
+                false,
+                0,
+            ),
+        ];
+        let args = SimulateSettle {
+            winning_square: (SEVEN as u64).to_le_bytes(),
+        };
+        process_simulate_settle(&accounts, bytemuck::bytes_of(&args)).unwrap();
+        drop(accounts);
+
+        assert_eq!(game_data, game_before);
+        assert_eq!(position_data, position_before);
+    }
+
+    #[test]
+    fn test_preview_old_epoch_refund() {
+        let (mut game, position) = setup();
+        game.start_new_epoch(3);
+        let preview = preview_settlement(&game, &position, &CrapsSideBets::zeroed(), SIX).unwrap();
+
+        assert_eq!(preview.winnings, 210);
+        assert_eq!(preview.lost, 0);
+        assert_eq!(preview.pending_winnings, 210);
+    }
+
+    #[test]
+    fn test_preview_round_trips_return_data() {
+        let (game, position) = setup();
+        let preview = preview_settlement(&game, &position, &CrapsSideBets::zeroed(), SIX).unwrap();
+        assert_eq!(SettlePreview::from_bytes(preview.to_bytes()), Some(preview));
+    }
+}
@@ -2,7 +2,9 @@ use ore_api::prelude::*;
 use solana_program::log::sol_log;
 use steel::*;
 
-/// Admin-only instruction to manually start a round.
+use crate::admin::assert_role;
+
+/// Operator instruction to manually start a round.
 /// This bypasses the entropy requirement for devnet testing.
 pub fn process_start_round(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse args
@@ -10,7 +12,7 @@ pub fn process_start_round(accounts: &[AccountInfo<'_>], data: &[u8]) -> Program
     let duration = u64::from_le_bytes(args.duration);
 
     // Load accounts
-    let [signer_info, board_info, config_info, round_info] = accounts else {
+    let [signer_info, board_info, config_info, round_info, roles_info, admin_accounts @ ..] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
@@ -22,11 +24,8 @@ pub fn process_start_round(accounts: &[AccountInfo<'_>], data: &[u8]) -> Program
 
     let config = config_info.as_account::<Config>(&ore_api::ID)?;
 
-    // Only admin can start rounds manually
-    if config.admin != *signer_info.key {
-        sol_log("Error: Only admin can start rounds");
-        return Err(ProgramError::InvalidAccountData);
-    }
+    // Only operators (or the admin) can start rounds manually
+    assert_role(config, roles_info, signer_info, ROLE_OPERATOR, admin_accounts)?;
 
     let board = board_info.as_account_mut::<Board>(&ore_api::ID)?;
     let round_id = board.round_id;
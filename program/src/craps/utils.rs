@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use ore_api::consts::BOARD_SIZE;
+pub use ore_api::payout::calculate_payout;
 
 /// Convert a board square index (0-35) to dice sum (2-12).
 /// Square index = (die1 - 1) * 6 + (die2 - 1)
@@ -158,14 +159,6 @@ pub fn is_valid_yes_no_sum(sum: u8) -> bool {
     sum >= 2 && sum <= 12 && sum != 7
 }
 
-/// Calculate payout for a winning bet.
-/// Returns the amount won (not including original bet).
-pub fn calculate_payout(bet_amount: u64, payout_num: u64, payout_den: u64) -> u64 {
-    // payout = bet_amount * payout_num / payout_den
-    // Use u128 to avoid overflow
-    ((bet_amount as u128 * payout_num as u128) / payout_den as u128) as u64
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
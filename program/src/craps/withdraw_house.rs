@@ -0,0 +1,134 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use solana_program::program::invoke_signed;
+use spl_associated_token_account::get_associated_token_address;
+use steel::*;
+
+use crate::admin::{assert_admin, log_admin_action};
+
+/// Schedules a withdrawal of excess house bankroll, executable with
+/// `WithdrawHouseBankroll` after `HOUSE_WITHDRAWAL_TIMELOCK_SECONDS`.
+/// Scheduling zero cancels a pending withdrawal.
+pub fn process_schedule_house_withdrawal(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = ScheduleHouseWithdrawal::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, config_info, craps_game_info, audit_log_info, admin_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+    let craps_game = craps_game_info.as_account::<CrapsGame>(&ore_api::ID)?;
+    if amount > craps_game.withdrawable_bankroll() {
+        sol_log(&format!(
+            "Withdrawal exceeds excess bankroll of {}",
+            craps_game.withdrawable_bankroll()
+        ));
+        return Err(OreError::InsufficientBankroll.into());
+    }
+
+    // Schedule withdrawal.
+    let old_amount = config.house_withdrawal_amount;
+    config.house_withdrawal_amount = amount;
+    config.house_withdrawal_effective_at = if amount == 0 {
+        0
+    } else {
+        clock.unix_timestamp + HOUSE_WITHDRAWAL_TIMELOCK_SECONDS
+    };
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::ScheduleHouseWithdrawal,
+        audit_value(&[old_amount]),
+        audit_value(&[amount, config.house_withdrawal_effective_at as u64]),
+    )?;
+    sol_log(&format!(
+        "House withdrawal of {} scheduled for {}",
+        amount, config.house_withdrawal_effective_at
+    ));
+
+    Ok(())
+}
+
+/// Executes a scheduled house bankroll withdrawal, paying the fee collector's CRAP
+/// token account. The excess is rechecked, so reservations made since scheduling
+/// stay covered.
+pub fn process_withdraw_house_bankroll(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, config_info, craps_game_info, craps_vault_info, vault_crap_ata, recipient_crap_ata, token_program, audit_log_info, admin_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    assert_admin(config, signer_info, admin_accounts)?;
+    let craps_game = craps_game_info
+        .is_writable()?
+        .has_seeds(&[CRAPS_GAME], &ore_api::ID)?
+        .as_account_mut::<CrapsGame>(&ore_api::ID)?;
+    craps_vault_info.has_seeds(&[CRAPS_VAULT], &ore_api::ID)?;
+    vault_crap_ata
+        .is_writable()?
+        .has_address(&get_associated_token_address(craps_vault_info.key, &CRAP_MINT_ADDRESS))?;
+    recipient_crap_ata
+        .is_writable()?
+        .has_address(&get_associated_token_address(&config.fee_collector, &CRAP_MINT_ADDRESS))?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Check the timelock and the excess.
+    let amount = config.house_withdrawal_amount;
+    if amount == 0 || clock.unix_timestamp < config.house_withdrawal_effective_at {
+        sol_log(&format!(
+            "No house withdrawal ready (effective at {})",
+            config.house_withdrawal_effective_at
+        ));
+        return Err(OreError::TimelockNotExpired.into());
+    }
+    if amount > craps_game.withdrawable_bankroll() {
+        sol_log("Withdrawal exceeds excess bankroll");
+        return Err(OreError::InsufficientBankroll.into());
+    }
+
+    // Update bankroll before the transfer.
+    let old_bankroll = craps_game.house_bankroll;
+    craps_game.house_bankroll -= amount;
+    config.house_withdrawal_amount = 0;
+    config.house_withdrawal_effective_at = 0;
+
+    // Transfer CRAP from the vault.
+    let (_, craps_vault_bump) = craps_vault_pda();
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            &spl_token::ID,
+            vault_crap_ata.key,
+            recipient_crap_ata.key,
+            craps_vault_info.key,
+            &[],
+            amount,
+        )?,
+        &[
+            vault_crap_ata.clone(),
+            recipient_crap_ata.clone(),
+            craps_vault_info.clone(),
+            token_program.clone(),
+        ],
+        &[&[CRAPS_VAULT, &[craps_vault_bump]]],
+    )?;
+
+    log_admin_action(
+        audit_log_info,
+        signer_info.key,
+        OreInstruction::WithdrawHouseBankroll,
+        audit_value(&[old_bankroll]),
+        audit_value(&[craps_game.house_bankroll]),
+    )?;
+    sol_log(&format!("Withdrew {} CRAP from the house bankroll", amount));
+
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use ore_api::prelude::*;
+use solana_program::clock::Clock;
+use solana_program::log::sol_log;
+use solana_program::sysvar::Sysvar;
+use steel::*;
+
+use super::game_token_pool::record_game_pool_swap;
+use super::remove_liquidity::transfer_from_vault;
+use super::swap_sol_rng::record_sol_to_rng_swap;
+
+/// Configures the CRAP buyback-and-burn. Admin-only instruction.
+///
+/// Account layout:
+/// 0: admin (signer) - must match pool admin
+/// 1: exchange_pool (PDA, writable)
+pub fn process_set_buyback_config(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = SetBuybackConfig::try_from_bytes(data)?;
+    let share_bps = u64::from_le_bytes(args.share_bps);
+    let max_sol = u64::from_le_bytes(args.max_sol);
+    let interval_seconds = i64::from_le_bytes(args.interval_seconds);
+
+    sol_log(&format!(
+        "SetBuybackConfig: share_bps={}, max_sol={}, interval_seconds={}",
+        share_bps, max_sol, interval_seconds
+    ));
+
+    if share_bps > 10000 || interval_seconds < EXCHANGE_MIN_BUYBACK_INTERVAL_SECONDS {
+        sol_log("Invalid buyback config");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [admin_info, exchange_pool_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    admin_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+
+    // Pool must exist.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    if exchange_pool.admin != *admin_info.key {
+        sol_log("Only admin can configure buybacks");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    exchange_pool.buyback_share_bps = share_bps;
+    exchange_pool.buyback_max_sol = max_sol;
+    exchange_pool.buyback_interval_seconds = interval_seconds;
+
+    Ok(())
+}
+
+/// Spends accrued protocol SOL fees buying CRAP and burns it. Permissionless.
+///
+/// The SOL already sits in the SOL vault, so the first leg swaps it into the
+/// SOL/RNG reserves in place. The RNG bought is then swapped through the CRAP
+/// game token pool, and the CRAP out is burned straight from that pool's vault.
+/// Spending is bounded by the admin's share, per-buyback cap, and interval.
+///
+/// Account layout:
+/// 0: signer
+/// 1: exchange_pool (PDA, writable)
+/// 2: rng_vault (PDA, writable)
+/// 3: game_pool (PDA, writable) - CRAP game token pool
+/// 4: game_pool_rng_vault (PDA, writable)
+/// 5: game_pool_game_vault (PDA, writable)
+/// 6: crap_mint (writable)
+/// 7: token_program
+pub fn process_buyback_crap(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, exchange_pool_info, rng_vault_info, game_pool_info, game_pool_rng_vault_info, game_pool_game_vault_info, crap_mint_info, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    signer_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+    rng_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_RNG_VAULT], &ore_api::ID)?;
+    crap_mint_info
+        .is_writable()?
+        .has_address(&CRAP_MINT_ADDRESS)?;
+    game_pool_info
+        .is_writable()?
+        .has_seeds(&[GAME_TOKEN_POOL, CRAP_MINT_ADDRESS.as_ref()], &ore_api::ID)?;
+    game_pool_rng_vault_info
+        .is_writable()?
+        .has_seeds(&[GAME_TOKEN_POOL_RNG_VAULT, CRAP_MINT_ADDRESS.as_ref()], &ore_api::ID)?;
+    game_pool_game_vault_info
+        .is_writable()?
+        .has_seeds(&[GAME_TOKEN_POOL_GAME_VAULT, CRAP_MINT_ADDRESS.as_ref()], &ore_api::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Both pools must exist and be active.
+    if exchange_pool_info.data_is_empty() || game_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    // Get pool bump for signing. The pool PDA is the token authority of both vaults.
+    let (_, pool_bump) = exchange_pool_pda();
+
+    let exchange_pool = exchange_pool_info.as_account::<ExchangePool>(&ore_api::ID)?;
+    let game_pool = game_pool_info.as_account::<GameTokenPool>(&ore_api::ID)?;
+    if !exchange_pool.is_active() || !game_pool.is_active() {
+        sol_log("Pool is not active");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Amount of protocol SOL to spend.
+    let Some(sol_amount) = exchange_pool.buyback_amount(clock.unix_timestamp) else {
+        sol_log("No buyback available");
+        return Err(ProgramError::InvalidArgument);
+    };
+
+    // First leg: SOL -> RNG, within the max swap size.
+    let max_swap = exchange_pool
+        .max_swap_amount(exchange_pool.sol_reserve)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if sol_amount > max_swap {
+        sol_log(&format!("Swap too large: {} > max {}", sol_amount, max_swap));
+        return Err(ProgramError::InvalidArgument);
+    }
+    let (rng_out, lp_fee, protocol_fee) = exchange_pool
+        .calculate_swap_output(sol_amount, exchange_pool.sol_reserve, exchange_pool.rng_reserve)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Second leg: RNG -> CRAP, within the game pool's max swap size.
+    let max_game_swap = game_pool
+        .rng_reserve
+        .checked_mul(EXCHANGE_MAX_SWAP_BPS)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .checked_div(10000)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if rng_out > max_game_swap {
+        sol_log(&format!("Swap too large: {} > max {}", rng_out, max_game_swap));
+        return Err(ProgramError::InvalidArgument);
+    }
+    let (crap_out, _, game_protocol_fee) = game_pool
+        .calculate_swap_output(rng_out, true)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    sol_log(&format!(
+        "Buyback: sol_in={}, rng_mid={}, crap_burned={}",
+        sol_amount, rng_out, crap_out
+    ));
+
+    // Move the RNG into the CRAP pool.
+    transfer_from_vault(
+        exchange_pool_info,
+        rng_vault_info,
+        game_pool_rng_vault_info,
+        token_program,
+        rng_out,
+        pool_bump,
+    )?;
+
+    // Burn the CRAP bought.
+    burn_signed(
+        game_pool_game_vault_info,
+        crap_mint_info,
+        game_pool_info,
+        token_program,
+        crap_out,
+        &[GAME_TOKEN_POOL, CRAP_MINT_ADDRESS.as_ref()],
+    )?;
+
+    // Update the SOL/RNG pool. The SOL moves from protocol fees into the reserves.
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    exchange_pool.protocol_fees_sol = exchange_pool
+        .protocol_fees_sol
+        .checked_sub(sol_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    record_sol_to_rng_swap(
+        exchange_pool,
+        sol_amount,
+        rng_out,
+        lp_fee,
+        protocol_fee,
+        &clock,
+    )?;
+    exchange_pool.last_buyback_at = clock.unix_timestamp;
+    exchange_pool.total_buyback_sol = exchange_pool
+        .total_buyback_sol
+        .checked_add(sol_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    exchange_pool.total_crap_burned = exchange_pool
+        .total_crap_burned
+        .checked_add(crap_out)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Update the CRAP pool.
+    let game_pool = game_pool_info.as_account_mut::<GameTokenPool>(&ore_api::ID)?;
+    record_game_pool_swap(
+        game_pool,
+        rng_out,
+        crap_out,
+        game_protocol_fee,
+        true,
+        clock.unix_timestamp,
+    )?;
+
+    sol_log(&format!(
+        "Buyback complete: burned {} CRAP for {} SOL",
+        crap_out, sol_amount
+    ));
+
+    Ok(())
+}
@@ -1,8 +1,9 @@
 use ore_api::prelude::*;
 use solana_program::log::sol_log;
-use solana_program::program::invoke_signed;
 use steel::*;
 
+use super::remove_liquidity::transfer_from_vault;
+
 /// Claims accumulated protocol fees from the exchange pool.
 /// Admin-only instruction.
 ///
@@ -49,9 +50,8 @@ pub fn process_claim_protocol_fees(accounts: &[AccountInfo<'_>], _data: &[u8]) -
         return Err(ProgramError::UninitializedAccount);
     }
 
-    // Get bumps for signing.
+    // Get pool bump for signing. The pool PDA is the token authority of both vaults.
     let (_, pool_bump) = exchange_pool_pda();
-    let (_, sol_vault_bump) = exchange_sol_vault_pda();
 
     // Load pool state and verify admin.
     let exchange_pool = exchange_pool_info.as_account::<ExchangePool>(&ore_api::ID)?;
@@ -71,46 +71,26 @@ pub fn process_claim_protocol_fees(accounts: &[AccountInfo<'_>], _data: &[u8]) -
 
     // Transfer SOL fees if any.
     if sol_fees > 0 {
-        let sol_vault_seeds = &[EXCHANGE_SOL_VAULT, &[sol_vault_bump]];
-        invoke_signed(
-            &spl_token::instruction::transfer(
-                &spl_token::ID,
-                sol_vault_info.key,
-                admin_sol_ata.key,
-                sol_vault_info.key,
-                &[],
-                sol_fees,
-            )?,
-            &[
-                sol_vault_info.clone(),
-                admin_sol_ata.clone(),
-                sol_vault_info.clone(),
-                token_program.clone(),
-            ],
-            &[sol_vault_seeds],
+        transfer_from_vault(
+            exchange_pool_info,
+            sol_vault_info,
+            admin_sol_ata,
+            token_program,
+            sol_fees,
+            pool_bump,
         )?;
         sol_log(&format!("Transferred {} SOL fees to admin", sol_fees));
     }
 
     // Transfer RNG fees if any.
     if rng_fees > 0 {
-        let pool_seeds = &[EXCHANGE_POOL, &[pool_bump]];
-        invoke_signed(
-            &spl_token::instruction::transfer(
-                &spl_token::ID,
-                rng_vault_info.key,
-                admin_rng_ata.key,
-                exchange_pool_info.key,
-                &[],
-                rng_fees,
-            )?,
-            &[
-                rng_vault_info.clone(),
-                admin_rng_ata.clone(),
-                exchange_pool_info.clone(),
-                token_program.clone(),
-            ],
-            &[pool_seeds],
+        transfer_from_vault(
+            exchange_pool_info,
+            rng_vault_info,
+            admin_rng_ata,
+            token_program,
+            rng_fees,
+            pool_bump,
         )?;
         sol_log(&format!("Transferred {} RNG fees to admin", rng_fees));
     }
@@ -0,0 +1,378 @@
+use ore_api::prelude::*;
+use solana_program::clock::Clock;
+use solana_program::log::sol_log;
+use solana_program::sysvar::Sysvar;
+use steel::*;
+
+use super::limit_order::close_with_escrow;
+use super::remove_liquidity::transfer_from_vault;
+use super::swap_sol_rng::{record_rng_to_sol_swap, record_sol_to_rng_swap};
+
+/// Opens a DCA order that swaps its budget through the pool in fixed slices.
+/// The first slice is due immediately.
+///
+/// Account layout:
+/// 0: signer
+/// 1: dca_order (PDA, writable)
+/// 2: escrow (writable) - dca_order's input token ATA
+/// 3: user_input_ata (writable) - user's input source
+/// 4: user_output_ata (writable) - user's output destination, created if missing
+/// 5: input_mint - wrapped SOL or RNG mint
+/// 6: output_mint - the other of the two
+/// 7: system_program
+/// 8: token_program
+/// 9: associated_token_program
+pub fn process_open_dca_order(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = OpenDcaOrder::try_from_bytes(data)?;
+    let id = u64::from_le_bytes(args.id);
+    let amount_per_interval = u64::from_le_bytes(args.amount_per_interval);
+    let interval_slots = u64::from_le_bytes(args.interval_slots);
+    let budget = u64::from_le_bytes(args.budget);
+    let min_price = u128::from_le_bytes(args.min_price);
+    let side = args.side;
+
+    sol_log(&format!(
+        "OpenDcaOrder: id={}, side={}, amount_per_interval={}, interval_slots={}, budget={}",
+        id, side, amount_per_interval, interval_slots, budget
+    ));
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, dca_order_info, escrow_info, user_input_ata, user_output_ata, input_mint_info, output_mint_info, system_program, token_program, associated_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    dca_order_info.is_writable()?.is_empty()?.has_seeds(
+        &[DCA_ORDER, &signer_info.key.to_bytes(), &id.to_le_bytes()],
+        &ore_api::ID,
+    )?;
+    let (input_mint, output_mint) = match side {
+        LIMIT_ORDER_SIDE_SOL_TO_RNG => (SOL_MINT, RNG_MINT_ADDRESS),
+        LIMIT_ORDER_SIDE_RNG_TO_SOL => (RNG_MINT_ADDRESS, SOL_MINT),
+        _ => {
+            sol_log("Invalid order side");
+            return Err(ProgramError::InvalidArgument);
+        }
+    };
+    input_mint_info.has_address(&input_mint)?.as_mint()?;
+    output_mint_info.has_address(&output_mint)?.as_mint()?;
+    user_input_ata
+        .is_writable()?
+        .as_associated_token_account(signer_info.key, &input_mint)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    // Validate schedule.
+    if amount_per_interval == 0 || interval_slots == 0 || budget < amount_per_interval {
+        sol_log("Invalid DCA schedule");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Open DCA order account.
+    create_program_account::<DcaOrder>(
+        dca_order_info,
+        system_program,
+        signer_info,
+        &ore_api::ID,
+        &[DCA_ORDER, &signer_info.key.to_bytes(), &id.to_le_bytes()],
+    )?;
+    let dca_order = dca_order_info.as_account_mut::<DcaOrder>(&ore_api::ID)?;
+    dca_order.authority = *signer_info.key;
+    dca_order.id = id;
+    dca_order.side = side;
+    dca_order.amount_per_interval = amount_per_interval;
+    dca_order.interval_slots = interval_slots;
+    dca_order.remaining_budget = budget;
+    dca_order.next_execution_slot = clock.slot;
+    dca_order.set_min_price(min_price);
+    dca_order.created_at = clock.unix_timestamp;
+
+    // Create escrow and make sure slices have somewhere to pay out.
+    create_associated_token_account(
+        signer_info,
+        dca_order_info,
+        escrow_info,
+        input_mint_info,
+        system_program,
+        token_program,
+        associated_token_program,
+    )?;
+    if user_output_ata.data_is_empty() {
+        create_associated_token_account(
+            signer_info,
+            signer_info,
+            user_output_ata,
+            output_mint_info,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+    } else {
+        user_output_ata.as_associated_token_account(signer_info.key, &output_mint)?;
+    }
+
+    // Escrow the budget.
+    transfer(signer_info, user_input_ata, escrow_info, token_program, budget)?;
+
+    Ok(())
+}
+
+/// Executes the next due slice of a DCA order. Permissionless; the cranker
+/// earns a bounty from the slice. Closes the order once the budget is spent.
+///
+/// Account layout:
+/// 0: cranker (signer)
+/// 1: exchange_pool (PDA, writable)
+/// 2: sol_vault (PDA, writable)
+/// 3: rng_vault (PDA, writable)
+/// 4: dca_order (PDA, writable)
+/// 5: escrow (writable) - dca_order's input token ATA
+/// 6: owner (writable) - order owner, receives the rent when done
+/// 7: owner_output_ata (writable) - owner's output destination
+/// 8: cranker_input_ata (writable) - cranker's bounty destination
+/// 9: token_program
+pub fn process_crank_dca_order(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [cranker_info, exchange_pool_info, sol_vault_info, rng_vault_info, dca_order_info, escrow_info, owner_info, owner_output_ata, cranker_input_ata, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    cranker_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+    sol_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_SOL_VAULT], &ore_api::ID)?;
+    rng_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_RNG_VAULT], &ore_api::ID)?;
+    let dca_order = dca_order_info
+        .is_writable()?
+        .as_account_mut::<DcaOrder>(&ore_api::ID)?
+        .assert_mut(|o| o.authority == *owner_info.key)?;
+    owner_info.is_writable()?;
+    token_program.is_program(&spl_token::ID)?;
+
+    let sol_to_rng = dca_order.is_sol_to_rng();
+    let (input_mint, output_mint) = if sol_to_rng {
+        (SOL_MINT, RNG_MINT_ADDRESS)
+    } else {
+        (RNG_MINT_ADDRESS, SOL_MINT)
+    };
+    let (input_vault, output_vault) = if sol_to_rng {
+        (sol_vault_info, rng_vault_info)
+    } else {
+        (rng_vault_info, sol_vault_info)
+    };
+    escrow_info
+        .is_writable()?
+        .as_associated_token_account(dca_order_info.key, &input_mint)?;
+    owner_output_ata
+        .is_writable()?
+        .as_associated_token_account(owner_info.key, &output_mint)?;
+    cranker_input_ata
+        .is_writable()?
+        .as_associated_token_account(cranker_info.key, &input_mint)?;
+
+    // Next slice must be due.
+    let Some((input_amount, swap_amount, bounty)) = dca_order.next_slice(clock.slot) else {
+        sol_log(&format!(
+            "No slice due: next_execution_slot={}, remaining_budget={}",
+            dca_order.next_execution_slot, dca_order.remaining_budget
+        ));
+        return Err(ProgramError::InvalidArgument);
+    };
+
+    // Get pool bump for signing. The pool PDA is the token authority of both vaults.
+    let (_, pool_bump) = exchange_pool_pda();
+
+    // Load pool state.
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    if !exchange_pool.is_active() {
+        sol_log("Pool is not active");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let (input_reserve, output_reserve) = if sol_to_rng {
+        (exchange_pool.sol_reserve, exchange_pool.rng_reserve)
+    } else {
+        (exchange_pool.rng_reserve, exchange_pool.sol_reserve)
+    };
+
+    // Check max swap size (prevent large impact swaps).
+    let max_swap = exchange_pool
+        .max_swap_amount(input_reserve)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if swap_amount > max_swap {
+        sol_log(&format!("Swap too large: {} > max {}", swap_amount, max_swap));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Calculate output and check the price floor.
+    let (amount_out, lp_fee, protocol_fee) = exchange_pool
+        .calculate_swap_output(swap_amount, input_reserve, output_reserve)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let min_amount_out = dca_order
+        .min_slice_out(input_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if amount_out < min_amount_out {
+        sol_log(&format!(
+            "Price below floor: {} < {}",
+            amount_out, min_amount_out
+        ));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    sol_log(&format!(
+        "CrankDcaOrder: id={}, swap_amount={}, amount_out={}, bounty={}",
+        dca_order.id, swap_amount, amount_out, bounty
+    ));
+
+    // Move the slice into the pool and pay the cranker.
+    let authority = dca_order.authority.to_bytes();
+    let id = dca_order.id.to_le_bytes();
+    let order_seeds: &[&[u8]] = &[DCA_ORDER, &authority, &id];
+    transfer_signed(
+        dca_order_info,
+        escrow_info,
+        input_vault,
+        token_program,
+        swap_amount,
+        order_seeds,
+    )?;
+    if bounty > 0 {
+        transfer_signed(
+            dca_order_info,
+            escrow_info,
+            cranker_input_ata,
+            token_program,
+            bounty,
+            order_seeds,
+        )?;
+    }
+
+    // Pay the owner from the pool.
+    transfer_from_vault(
+        exchange_pool_info,
+        output_vault,
+        owner_output_ata,
+        token_program,
+        amount_out,
+        pool_bump,
+    )?;
+
+    // Update pool state.
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    let new_k = if sol_to_rng {
+        record_sol_to_rng_swap(
+            exchange_pool,
+            swap_amount,
+            amount_out,
+            lp_fee,
+            protocol_fee,
+            &clock,
+        )?
+    } else {
+        record_rng_to_sol_swap(
+            exchange_pool,
+            swap_amount,
+            amount_out,
+            protocol_fee,
+            &clock,
+        )?
+    };
+
+    // Record the slice, closing the order once the budget is spent.
+    let dca_order = dca_order_info.as_account_mut::<DcaOrder>(&ore_api::ID)?;
+    dca_order.record_slice(input_amount, swap_amount, amount_out, clock.slot);
+    let done = dca_order.remaining_budget == 0;
+    let (_, bump) = dca_order.pda();
+    if done {
+        close_with_escrow(
+            dca_order_info,
+            escrow_info,
+            owner_info,
+            token_program,
+            &[DCA_ORDER, &authority, &id, &[bump]],
+        )?;
+    }
+
+    sol_log(&format!(
+        "DCA slice executed, done={}, new_k={}",
+        done, new_k
+    ));
+
+    Ok(())
+}
+
+/// Cancels a DCA order and refunds the remaining budget. Owner-only.
+///
+/// Account layout:
+/// 0: signer - order owner, receives the rent
+/// 1: dca_order (PDA, writable)
+/// 2: escrow (writable) - dca_order's input token ATA
+/// 3: owner_input_ata (writable) - owner's refund destination
+/// 4: token_program
+pub fn process_cancel_dca_order(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, dca_order_info, escrow_info, owner_input_ata, token_program] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?.is_writable()?;
+    let dca_order = *dca_order_info
+        .is_writable()?
+        .as_account::<DcaOrder>(&ore_api::ID)?
+        .assert(|o| o.authority == *signer_info.key)?;
+    token_program.is_program(&spl_token::ID)?;
+
+    let input_mint = if dca_order.is_sol_to_rng() {
+        SOL_MINT
+    } else {
+        RNG_MINT_ADDRESS
+    };
+    let escrow = escrow_info
+        .is_writable()?
+        .as_associated_token_account(dca_order_info.key, &input_mint)?;
+    owner_input_ata
+        .is_writable()?
+        .as_associated_token_account(signer_info.key, &input_mint)?;
+
+    // Refund the escrow.
+    let (_, bump) = dca_order.pda();
+    let authority = dca_order.authority.to_bytes();
+    let id = dca_order.id.to_le_bytes();
+    let refund = escrow.amount();
+    if refund > 0 {
+        transfer_signed(
+            dca_order_info,
+            escrow_info,
+            owner_input_ata,
+            token_program,
+            refund,
+            &[DCA_ORDER, &authority, &id],
+        )?;
+    }
+
+    // Close the order.
+    close_with_escrow(
+        dca_order_info,
+        escrow_info,
+        signer_info,
+        token_program,
+        &[DCA_ORDER, &authority, &id, &[bump]],
+    )?;
+
+    sol_log(&format!(
+        "DCA order {} cancelled after {} slices, refunded {}",
+        dca_order.id, dca_order.executions, refund
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,377 @@
+use ore_api::prelude::*;
+use solana_program::clock::Clock;
+use solana_program::log::sol_log;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::program_pack::Pack;
+use solana_program::sysvar::Sysvar;
+use steel::*;
+
+use super::swap_game_token::{get_game_token_mint, GameTokenType};
+
+/// Initializes a CPMM pool between RNG and a game token, keyed by the game token mint.
+/// Admin-only instruction (exchange pool admin). Seeds the pool with protocol-owned
+/// liquidity from the admin's token accounts.
+///
+/// Account layout:
+/// 0: admin (signer, payer) - must match exchange pool admin
+/// 1: exchange_pool (PDA)
+/// 2: game_token_pool (PDA, writable)
+/// 3: rng_vault (PDA, writable) - pool's RNG token account
+/// 4: game_vault (PDA, writable) - pool's game token account
+/// 5: admin_rng_ata (writable) - admin's RNG source
+/// 6: admin_game_ata (writable) - admin's game token source
+/// 7: game_mint - game token mint
+/// 8: rng_mint - RNG token mint
+/// 9: system_program
+/// 10: token_program
+/// 11: rent
+pub fn process_initialize_game_token_pool(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = InitializeGameTokenPool::try_from_bytes(data)?;
+    let rng_amount = u64::from_le_bytes(args.rng_amount);
+    let game_amount = u64::from_le_bytes(args.game_amount);
+    let game_token_type = GameTokenType::try_from(args.game_token_type)?;
+
+    sol_log(&format!(
+        "InitializeGameTokenPool: rng={}, game={}, game_type={:?}",
+        rng_amount, game_amount, game_token_type
+    ));
+
+    // Validate minimum initial liquidity.
+    if rng_amount < EXCHANGE_MIN_INITIAL_RNG || game_amount < EXCHANGE_MIN_INITIAL_RNG {
+        sol_log("Initial liquidity below minimum");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [admin_info, exchange_pool_info, game_pool_info, rng_vault_info, game_vault_info, admin_rng_ata, admin_game_ata, game_mint, rng_mint, system_program, token_program, rent_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate game token mint.
+    let game_mint_address = get_game_token_mint(game_token_type);
+    game_mint.has_address(&game_mint_address)?;
+
+    // Validate accounts.
+    admin_info.is_signer()?;
+    exchange_pool_info.has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+    game_pool_info
+        .is_writable()?
+        .has_seeds(&[GAME_TOKEN_POOL, game_mint_address.as_ref()], &ore_api::ID)?;
+    rng_vault_info
+        .is_writable()?
+        .has_seeds(&[GAME_TOKEN_POOL_RNG_VAULT, game_mint_address.as_ref()], &ore_api::ID)?;
+    game_vault_info
+        .is_writable()?
+        .has_seeds(&[GAME_TOKEN_POOL_GAME_VAULT, game_mint_address.as_ref()], &ore_api::ID)?;
+    admin_rng_ata.is_writable()?;
+    admin_game_ata.is_writable()?;
+    rng_mint.has_address(&RNG_MINT_ADDRESS)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Only the exchange admin can create game token pools.
+    let exchange_pool = exchange_pool_info.as_account::<ExchangePool>(&ore_api::ID)?;
+    if exchange_pool.admin != *admin_info.key {
+        sol_log("Only admin can initialize game token pools");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let fee_numerator = exchange_pool.fee_numerator;
+    let fee_denominator = exchange_pool.fee_denominator;
+    let protocol_fee_share_bps = exchange_pool.protocol_fee_share_bps;
+
+    // Pool must not already exist.
+    if !game_pool_info.data_is_empty() {
+        sol_log("Game token pool already initialized");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    // Get bumps for PDAs.
+    let (_, pool_bump) = game_token_pool_pda(&game_mint_address);
+    let (_, rng_vault_bump) = game_token_pool_rng_vault_pda(&game_mint_address);
+    let (_, game_vault_bump) = game_token_pool_game_vault_pda(&game_mint_address);
+
+    // Create pool account.
+    create_program_account::<GameTokenPool>(
+        game_pool_info,
+        system_program,
+        admin_info,
+        &ore_api::ID,
+        &[GAME_TOKEN_POOL, game_mint_address.as_ref()],
+    )?;
+    sol_log("Created game token pool account");
+
+    // Create the RNG and game token vaults as PDA token accounts owned by the pool.
+    for (vault_info, mint_info, seed, bump) in [
+        (rng_vault_info, rng_mint, GAME_TOKEN_POOL_RNG_VAULT, rng_vault_bump),
+        (game_vault_info, game_mint, GAME_TOKEN_POOL_GAME_VAULT, game_vault_bump),
+    ] {
+        let vault_seeds = &[seed, game_mint_address.as_ref(), &[bump]];
+        invoke_signed(
+            &solana_program::system_instruction::create_account(
+                admin_info.key,
+                vault_info.key,
+                solana_program::rent::Rent::get()?.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::ID,
+            ),
+            &[admin_info.clone(), vault_info.clone(), system_program.clone()],
+            &[vault_seeds],
+        )?;
+        invoke_signed(
+            &spl_token::instruction::initialize_account(
+                &spl_token::ID,
+                vault_info.key,
+                mint_info.key,
+                game_pool_info.key, // pool is owner
+            )?,
+            &[
+                vault_info.clone(),
+                mint_info.clone(),
+                game_pool_info.clone(),
+                rent_info.clone(),
+            ],
+            &[vault_seeds],
+        )?;
+    }
+    sol_log("Created pool vaults");
+
+    // Transfer initial liquidity from admin to vaults.
+    for (source, vault_info, amount) in [
+        (admin_rng_ata, rng_vault_info, rng_amount),
+        (admin_game_ata, game_vault_info, game_amount),
+    ] {
+        invoke(
+            &spl_token::instruction::transfer(
+                &spl_token::ID,
+                source.key,
+                vault_info.key,
+                admin_info.key,
+                &[],
+                amount,
+            )?,
+            &[
+                source.clone(),
+                vault_info.clone(),
+                admin_info.clone(),
+                token_program.clone(),
+            ],
+        )?;
+    }
+
+    // Initialize pool state. Fees start at the exchange pool's configuration.
+    let clock = Clock::get()?;
+    let game_pool = game_pool_info.as_account_mut::<GameTokenPool>(&ore_api::ID)?;
+    game_pool.game_mint = game_mint_address;
+    game_pool.rng_vault = *rng_vault_info.key;
+    game_pool.game_vault = *game_vault_info.key;
+    game_pool.admin = *admin_info.key;
+    game_pool.rng_reserve = rng_amount;
+    game_pool.game_reserve = game_amount;
+    let k = (rng_amount as u128)
+        .checked_mul(game_amount as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    game_pool.set_k(k);
+    game_pool.fee_numerator = fee_numerator;
+    game_pool.fee_denominator = fee_denominator;
+    game_pool.protocol_fee_share_bps = protocol_fee_share_bps;
+    game_pool.created_at = clock.unix_timestamp;
+    game_pool.bump = pool_bump;
+    game_pool.status = EXCHANGE_STATUS_ACTIVE;
+
+    sol_log(&format!("Game token pool initialized: k={}", k));
+
+    Ok(())
+}
+
+/// Swaps between RNG and a game token using the game token pool's CPMM pricing.
+/// Games without a pool keep using the 1:1 SwapRngToGameToken / SwapGameTokenToRng path.
+///
+/// Account layout:
+/// 0: user (signer)
+/// 1: game_token_pool (PDA, writable)
+/// 2: rng_vault (PDA, writable)
+/// 3: game_vault (PDA, writable)
+/// 4: user_rng_ata (writable)
+/// 5: user_game_ata (writable)
+/// 6: game_mint - game token mint
+/// 7: token_program
+pub fn process_swap_game_token_pool(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = SwapGameTokenPool::try_from_bytes(data)?;
+    let amount_in = u64::from_le_bytes(args.amount_in);
+    let min_amount_out = u64::from_le_bytes(args.min_amount_out);
+    let rng_to_game = args.rng_to_game != 0;
+
+    sol_log(&format!(
+        "SwapGameTokenPool: amount_in={}, min_amount_out={}, rng_to_game={}",
+        amount_in, min_amount_out, rng_to_game
+    ));
+
+    // Validate amounts.
+    if amount_in == 0 {
+        sol_log("Amount must be greater than 0");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [user_info, game_pool_info, rng_vault_info, game_vault_info, user_rng_ata, user_game_ata, game_mint, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    user_info.is_signer()?;
+    game_pool_info
+        .is_writable()?
+        .has_seeds(&[GAME_TOKEN_POOL, game_mint.key.as_ref()], &ore_api::ID)?;
+    rng_vault_info
+        .is_writable()?
+        .has_seeds(&[GAME_TOKEN_POOL_RNG_VAULT, game_mint.key.as_ref()], &ore_api::ID)?;
+    game_vault_info
+        .is_writable()?
+        .has_seeds(&[GAME_TOKEN_POOL_GAME_VAULT, game_mint.key.as_ref()], &ore_api::ID)?;
+    user_rng_ata.is_writable()?;
+    user_game_ata.is_writable()?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Pool must exist and be active.
+    if game_pool_info.data_is_empty() {
+        sol_log("Game token pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let game_pool = game_pool_info.as_account_mut::<GameTokenPool>(&ore_api::ID)?;
+
+    if !game_pool.is_active() {
+        sol_log("Pool is not active");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let pool_bump = game_pool.bump;
+
+    // Check max swap size (prevent large impact swaps).
+    let input_reserve = if rng_to_game {
+        game_pool.rng_reserve
+    } else {
+        game_pool.game_reserve
+    };
+    let max_swap = input_reserve
+        .checked_mul(EXCHANGE_MAX_SWAP_BPS)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .checked_div(10000)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if amount_in > max_swap {
+        sol_log(&format!("Swap too large: {} > max {}", amount_in, max_swap));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Calculate output using CPMM formula.
+    let (amount_out, lp_fee, protocol_fee) = game_pool
+        .calculate_swap_output(amount_in, rng_to_game)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    sol_log(&format!(
+        "Swap output: amount_out={}, lp_fee={}, protocol_fee={}",
+        amount_out, lp_fee, protocol_fee
+    ));
+
+    // Check slippage.
+    if amount_out < min_amount_out {
+        sol_log(&format!(
+            "Slippage check failed: {} < {}",
+            amount_out, min_amount_out
+        ));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (user_source, vault_in, vault_out, user_destination) = if rng_to_game {
+        (user_rng_ata, rng_vault_info, game_vault_info, user_game_ata)
+    } else {
+        (user_game_ata, game_vault_info, rng_vault_info, user_rng_ata)
+    };
+
+    // Transfer input from user to vault.
+    invoke(
+        &spl_token::instruction::transfer(
+            &spl_token::ID,
+            user_source.key,
+            vault_in.key,
+            user_info.key,
+            &[],
+            amount_in,
+        )?,
+        &[
+            user_source.clone(),
+            vault_in.clone(),
+            user_info.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    // Transfer output from vault to user.
+    let pool_seeds = &[GAME_TOKEN_POOL, game_mint.key.as_ref(), &[pool_bump]];
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            &spl_token::ID,
+            vault_out.key,
+            user_destination.key,
+            game_pool_info.key,
+            &[],
+            amount_out,
+        )?,
+        &[
+            vault_out.clone(),
+            user_destination.clone(),
+            game_pool_info.clone(),
+            token_program.clone(),
+        ],
+        &[pool_seeds],
+    )?;
+
+    // Update pool state.
+    let clock = Clock::get()?;
+    let game_pool = game_pool_info.as_account_mut::<GameTokenPool>(&ore_api::ID)?;
+    let new_k = record_game_pool_swap(
+        game_pool,
+        amount_in,
+        amount_out,
+        protocol_fee,
+        rng_to_game,
+        clock.unix_timestamp,
+    )?;
+
+    sol_log(&format!(
+        "Swap complete: amount_in={}, amount_out={}, new_k={}",
+        amount_in, amount_out, new_k
+    ));
+
+    Ok(())
+}
+
+/// Applies a swap to a game token pool's reserves, fees, and stats, failing if
+/// the reserve product would decrease. Returns the new constant product.
+pub(crate) fn record_game_pool_swap(
+    game_pool: &mut GameTokenPool,
+    amount_in: u64,
+    amount_out: u64,
+    protocol_fee: u64,
+    rng_to_game: bool,
+    now: i64,
+) -> Result<u128, ProgramError> {
+    let old_k = (game_pool.rng_reserve as u128)
+        .checked_mul(game_pool.game_reserve as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let new_k = game_pool
+        .apply_swap(amount_in, amount_out, protocol_fee, rng_to_game)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if new_k < old_k {
+        sol_log(&format!("K invariant violated: old_k={}, new_k={}", old_k, new_k));
+        return Err(ProgramError::InvalidAccountData);
+    }
+    game_pool.last_swap_at = now;
+    Ok(new_k)
+}
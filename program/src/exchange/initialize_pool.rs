@@ -229,7 +229,7 @@ pub fn process_initialize_exchange_pool(accounts: &[AccountInfo<'_>], data: &[u8
     )?;
     sol_log(&format!("Deposited {} RNG to RNG vault", rng_amount));
 
-    // Calculate initial LP tokens: sqrt(sol * rng) - MINIMUM_LIQUIDITY
+    // Calculate initial LP tokens: sqrt(sol * rng) - EXCHANGE_MINIMUM_LIQUIDITY
     let product = (sol_amount as u128)
         .checked_mul(rng_amount as u128)
         .ok_or(ProgramError::ArithmeticOverflow)?;
@@ -279,6 +279,10 @@ pub fn process_initialize_exchange_pool(accounts: &[AccountInfo<'_>], data: &[u8
     exchange_pool.total_lp_supply = lp_tokens + EXCHANGE_MINIMUM_LIQUIDITY; // Include locked minimum
     exchange_pool.fee_numerator = EXCHANGE_DEFAULT_FEE_NUMERATOR;
     exchange_pool.fee_denominator = EXCHANGE_DEFAULT_FEE_DENOMINATOR;
+    exchange_pool.protocol_fee_share_bps = EXCHANGE_DEFAULT_PROTOCOL_FEE_SHARE_BPS;
+    exchange_pool.lp_staker_fee_share_bps = EXCHANGE_DEFAULT_LP_STAKER_FEE_SHARE_BPS;
+    exchange_pool.max_swap_bps = EXCHANGE_MAX_SWAP_BPS;
+    exchange_pool.slot_notional_cap_bps = EXCHANGE_DEFAULT_SLOT_NOTIONAL_CAP_BPS;
     exchange_pool.protocol_fees_sol = 0;
     exchange_pool.protocol_fees_rng = 0;
     exchange_pool.total_volume_sol = 0;
@@ -287,8 +291,10 @@ pub fn process_initialize_exchange_pool(accounts: &[AccountInfo<'_>], data: &[u8
     exchange_pool.minimum_liquidity = EXCHANGE_MINIMUM_LIQUIDITY;
     exchange_pool.created_at = clock.unix_timestamp;
     exchange_pool.last_swap_at = 0;
+    exchange_pool.last_observation_at = clock.unix_timestamp;
     exchange_pool.bump = pool_bump;
     exchange_pool.status = EXCHANGE_STATUS_ACTIVE;
+    exchange_pool.guardian = Pubkey::default();
 
     sol_log(&format!(
         "Pool initialized: k={}, LP supply={}",
@@ -0,0 +1,430 @@
+use ore_api::prelude::*;
+use solana_program::clock::Clock;
+use solana_program::log::sol_log;
+use solana_program::program::invoke_signed;
+use solana_program::sysvar::Sysvar;
+use steel::*;
+
+use super::remove_liquidity::transfer_from_vault;
+use super::swap_sol_rng::{record_rng_to_sol_swap, record_sol_to_rng_swap};
+
+/// Places a limit order against the exchange pool, escrowing the input.
+///
+/// Account layout:
+/// 0: signer
+/// 1: limit_order (PDA, writable)
+/// 2: escrow (writable) - limit_order's input token ATA
+/// 3: user_input_ata (writable) - user's input source
+/// 4: user_output_ata (writable) - user's output destination, created if missing
+/// 5: input_mint - wrapped SOL or RNG mint
+/// 6: output_mint - the other of the two
+/// 7: system_program
+/// 8: token_program
+/// 9: associated_token_program
+pub fn process_place_limit_order(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = PlaceLimitOrder::try_from_bytes(data)?;
+    let id = u64::from_le_bytes(args.id);
+    let amount_in = u64::from_le_bytes(args.amount_in);
+    let limit_price = u128::from_le_bytes(args.limit_price);
+    let expires_at = i64::from_le_bytes(args.expires_at);
+    let side = args.side;
+
+    sol_log(&format!(
+        "PlaceLimitOrder: id={}, side={}, amount_in={}, limit_price={}, expires_at={}",
+        id, side, amount_in, limit_price, expires_at
+    ));
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, limit_order_info, escrow_info, user_input_ata, user_output_ata, input_mint_info, output_mint_info, system_program, token_program, associated_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    limit_order_info.is_writable()?.is_empty()?.has_seeds(
+        &[LIMIT_ORDER, &signer_info.key.to_bytes(), &id.to_le_bytes()],
+        &ore_api::ID,
+    )?;
+    let (input_mint, output_mint) = match side {
+        LIMIT_ORDER_SIDE_SOL_TO_RNG => (SOL_MINT, RNG_MINT_ADDRESS),
+        LIMIT_ORDER_SIDE_RNG_TO_SOL => (RNG_MINT_ADDRESS, SOL_MINT),
+        _ => {
+            sol_log("Invalid order side");
+            return Err(ProgramError::InvalidArgument);
+        }
+    };
+    input_mint_info.has_address(&input_mint)?.as_mint()?;
+    output_mint_info.has_address(&output_mint)?.as_mint()?;
+    user_input_ata
+        .is_writable()?
+        .as_associated_token_account(signer_info.key, &input_mint)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    // Validate order terms.
+    if amount_in == 0 || limit_price == 0 {
+        sol_log("Amount and limit price must be greater than 0");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if expires_at <= clock.unix_timestamp {
+        sol_log("Order already expired");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Open limit order account.
+    create_program_account::<LimitOrder>(
+        limit_order_info,
+        system_program,
+        signer_info,
+        &ore_api::ID,
+        &[LIMIT_ORDER, &signer_info.key.to_bytes(), &id.to_le_bytes()],
+    )?;
+    let limit_order = limit_order_info.as_account_mut::<LimitOrder>(&ore_api::ID)?;
+    limit_order.authority = *signer_info.key;
+    limit_order.id = id;
+    limit_order.side = side;
+    limit_order.amount_in = amount_in;
+    limit_order.set_limit_price(limit_price);
+    limit_order.expires_at = expires_at;
+    limit_order.created_at = clock.unix_timestamp;
+    if limit_order.min_amount_out().is_none() {
+        sol_log("Limit price can never be met");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Create escrow and make sure fills have somewhere to pay out.
+    create_associated_token_account(
+        signer_info,
+        limit_order_info,
+        escrow_info,
+        input_mint_info,
+        system_program,
+        token_program,
+        associated_token_program,
+    )?;
+    if user_output_ata.data_is_empty() {
+        create_associated_token_account(
+            signer_info,
+            signer_info,
+            user_output_ata,
+            output_mint_info,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+    } else {
+        user_output_ata.as_associated_token_account(signer_info.key, &output_mint)?;
+    }
+
+    // Escrow the input.
+    transfer(
+        signer_info,
+        user_input_ata,
+        escrow_info,
+        token_program,
+        amount_in,
+    )?;
+
+    Ok(())
+}
+
+/// Fills a limit order against the pool once the price reaches its limit.
+/// Permissionless; the filler earns a bounty from the order's input.
+///
+/// Account layout:
+/// 0: filler (signer)
+/// 1: exchange_pool (PDA, writable)
+/// 2: sol_vault (PDA, writable)
+/// 3: rng_vault (PDA, writable)
+/// 4: limit_order (PDA, writable)
+/// 5: escrow (writable) - limit_order's input token ATA
+/// 6: owner (writable) - order owner, receives the rent
+/// 7: owner_output_ata (writable) - owner's output destination
+/// 8: filler_input_ata (writable) - filler's bounty destination
+/// 9: token_program
+pub fn process_fill_limit_order(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [filler_info, exchange_pool_info, sol_vault_info, rng_vault_info, limit_order_info, escrow_info, owner_info, owner_output_ata, filler_input_ata, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    filler_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+    sol_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_SOL_VAULT], &ore_api::ID)?;
+    rng_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_RNG_VAULT], &ore_api::ID)?;
+    let limit_order = *limit_order_info
+        .is_writable()?
+        .as_account::<LimitOrder>(&ore_api::ID)?
+        .assert(|o| o.authority == *owner_info.key)?;
+    owner_info.is_writable()?;
+    token_program.is_program(&spl_token::ID)?;
+
+    let sol_to_rng = limit_order.is_sol_to_rng();
+    let (input_mint, output_mint) = if sol_to_rng {
+        (SOL_MINT, RNG_MINT_ADDRESS)
+    } else {
+        (RNG_MINT_ADDRESS, SOL_MINT)
+    };
+    let (input_vault, output_vault) = if sol_to_rng {
+        (sol_vault_info, rng_vault_info)
+    } else {
+        (rng_vault_info, sol_vault_info)
+    };
+    escrow_info
+        .is_writable()?
+        .as_associated_token_account(limit_order_info.key, &input_mint)?;
+    owner_output_ata
+        .is_writable()?
+        .as_associated_token_account(owner_info.key, &output_mint)?;
+    filler_input_ata
+        .is_writable()?
+        .as_associated_token_account(filler_info.key, &input_mint)?;
+
+    if limit_order.is_expired(clock.unix_timestamp) {
+        sol_log("Limit order expired");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Get pool bump for signing. The pool PDA is the token authority of both vaults.
+    let (_, pool_bump) = exchange_pool_pda();
+
+    // Load pool state.
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    if !exchange_pool.is_active() {
+        sol_log("Pool is not active");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let (input_reserve, output_reserve) = if sol_to_rng {
+        (exchange_pool.sol_reserve, exchange_pool.rng_reserve)
+    } else {
+        (exchange_pool.rng_reserve, exchange_pool.sol_reserve)
+    };
+
+    // The filler bounty comes out of the input; the rest is swapped.
+    let (swap_amount, bounty) = limit_order.split_bounty();
+
+    // Check max swap size (prevent large impact swaps).
+    let max_swap = exchange_pool
+        .max_swap_amount(input_reserve)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if swap_amount > max_swap {
+        sol_log(&format!("Swap too large: {} > max {}", swap_amount, max_swap));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Calculate output and check it against the limit.
+    let (amount_out, lp_fee, protocol_fee) = exchange_pool
+        .calculate_swap_output(swap_amount, input_reserve, output_reserve)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let min_amount_out = limit_order
+        .min_amount_out()
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if amount_out < min_amount_out {
+        sol_log(&format!(
+            "Limit not reached: {} < {}",
+            amount_out, min_amount_out
+        ));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    sol_log(&format!(
+        "FillLimitOrder: id={}, swap_amount={}, amount_out={}, bounty={}",
+        limit_order.id, swap_amount, amount_out, bounty
+    ));
+
+    // Move the escrow into the pool and pay the filler.
+    let order_seeds: &[&[u8]] = &[
+        LIMIT_ORDER,
+        &limit_order.authority.to_bytes(),
+        &limit_order.id.to_le_bytes(),
+    ];
+    transfer_signed(
+        limit_order_info,
+        escrow_info,
+        input_vault,
+        token_program,
+        swap_amount,
+        order_seeds,
+    )?;
+    if bounty > 0 {
+        transfer_signed(
+            limit_order_info,
+            escrow_info,
+            filler_input_ata,
+            token_program,
+            bounty,
+            order_seeds,
+        )?;
+    }
+
+    // Pay the owner from the pool.
+    transfer_from_vault(
+        exchange_pool_info,
+        output_vault,
+        owner_output_ata,
+        token_program,
+        amount_out,
+        pool_bump,
+    )?;
+
+    // Update pool state.
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    let new_k = if sol_to_rng {
+        record_sol_to_rng_swap(
+            exchange_pool,
+            swap_amount,
+            amount_out,
+            lp_fee,
+            protocol_fee,
+            &clock,
+        )?
+    } else {
+        record_rng_to_sol_swap(
+            exchange_pool,
+            swap_amount,
+            amount_out,
+            protocol_fee,
+            &clock,
+        )?
+    };
+
+    // Close the order.
+    close_order(limit_order_info, escrow_info, owner_info, token_program, &limit_order)?;
+
+    sol_log(&format!("Limit order filled, new_k={}", new_k));
+
+    Ok(())
+}
+
+/// Cancels a limit order and refunds the escrow.
+/// The owner can cancel at any time; anyone can clean up an expired order.
+///
+/// Account layout:
+/// 0: signer
+/// 1: limit_order (PDA, writable)
+/// 2: escrow (writable) - limit_order's input token ATA
+/// 3: owner (writable) - order owner, receives the rent
+/// 4: owner_input_ata (writable) - owner's refund destination
+/// 5: token_program
+pub fn process_cancel_limit_order(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, limit_order_info, escrow_info, owner_info, owner_input_ata, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let limit_order = *limit_order_info
+        .is_writable()?
+        .as_account::<LimitOrder>(&ore_api::ID)?
+        .assert(|o| o.authority == *owner_info.key)?;
+    owner_info.is_writable()?;
+    token_program.is_program(&spl_token::ID)?;
+
+    if *signer_info.key != limit_order.authority && !limit_order.is_expired(clock.unix_timestamp) {
+        sol_log("Only the owner can cancel an open order");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let input_mint = if limit_order.is_sol_to_rng() {
+        SOL_MINT
+    } else {
+        RNG_MINT_ADDRESS
+    };
+    let escrow = escrow_info
+        .is_writable()?
+        .as_associated_token_account(limit_order_info.key, &input_mint)?;
+    owner_input_ata
+        .is_writable()?
+        .as_associated_token_account(owner_info.key, &input_mint)?;
+
+    // Refund the escrow.
+    let refund = escrow.amount();
+    if refund > 0 {
+        transfer_signed(
+            limit_order_info,
+            escrow_info,
+            owner_input_ata,
+            token_program,
+            refund,
+            &[
+                LIMIT_ORDER,
+                &limit_order.authority.to_bytes(),
+                &limit_order.id.to_le_bytes(),
+            ],
+        )?;
+    }
+
+    // Close the order.
+    close_order(limit_order_info, escrow_info, owner_info, token_program, &limit_order)?;
+
+    sol_log(&format!(
+        "Limit order {} cancelled, refunded {}",
+        limit_order.id, refund
+    ));
+
+    Ok(())
+}
+
+/// Closes a PDA's (empty) token escrow and the PDA itself, returning rent to the owner.
+/// `order_seeds` are the PDA's seeds including the bump.
+pub(crate) fn close_with_escrow<'info>(
+    order_info: &AccountInfo<'info>,
+    escrow_info: &AccountInfo<'info>,
+    owner_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    order_seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &spl_token::instruction::close_account(
+            &spl_token::ID,
+            escrow_info.key,
+            owner_info.key,
+            order_info.key,
+            &[],
+        )?,
+        &[
+            escrow_info.clone(),
+            owner_info.clone(),
+            order_info.clone(),
+            token_program.clone(),
+        ],
+        &[order_seeds],
+    )?;
+    order_info.close(owner_info)
+}
+
+/// Closes a limit order and its escrow.
+fn close_order<'info>(
+    limit_order_info: &AccountInfo<'info>,
+    escrow_info: &AccountInfo<'info>,
+    owner_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    limit_order: &LimitOrder,
+) -> ProgramResult {
+    let (_, bump) = limit_order.pda();
+    close_with_escrow(
+        limit_order_info,
+        escrow_info,
+        owner_info,
+        token_program,
+        &[
+            LIMIT_ORDER,
+            &limit_order.authority.to_bytes(),
+            &limit_order.id.to_le_bytes(),
+            &[bump],
+        ],
+    )
+}
@@ -0,0 +1,235 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::limit_order::close_with_escrow;
+
+/// Locks LP tokens for a fixed term to earn a boosted share of LP staker fees.
+///
+/// Account layout:
+/// 0: signer
+/// 1: exchange_pool (PDA, writable)
+/// 2: lp_lock (PDA, writable)
+/// 3: lp_lock_tokens (writable) - lp_lock's LP token ATA
+/// 4: user_lp_ata (writable) - user's LP source
+/// 5: lp_mint - LP token mint
+/// 6: system_program
+/// 7: token_program
+/// 8: associated_token_program
+pub fn process_lock_lp(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = LockLp::try_from_bytes(data)?;
+    let id = u64::from_le_bytes(args.id);
+    let amount = u64::from_le_bytes(args.amount);
+    let duration = i64::from_le_bytes(args.duration);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, exchange_pool_info, lp_lock_info, lp_lock_tokens_info, user_lp_ata, lp_mint_info, system_program, token_program, associated_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let exchange_pool = exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?
+        .as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    lp_lock_info.is_writable()?.is_empty()?.has_seeds(
+        &[LP_LOCK, &signer_info.key.to_bytes(), &id.to_le_bytes()],
+        &ore_api::ID,
+    )?;
+    lp_mint_info.has_address(&exchange_pool.lp_mint)?.as_mint()?;
+    let sender = user_lp_ata
+        .is_writable()?
+        .as_associated_token_account(signer_info.key, lp_mint_info.key)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    if amount == 0 || amount > sender.amount() {
+        sol_log("Invalid lock amount");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Open LP lock account.
+    create_program_account::<LpLock>(
+        lp_lock_info,
+        system_program,
+        signer_info,
+        &ore_api::ID,
+        &[LP_LOCK, &signer_info.key.to_bytes(), &id.to_le_bytes()],
+    )?;
+    let lp_lock = lp_lock_info.as_account_mut::<LpLock>(&ore_api::ID)?;
+    lp_lock.authority = *signer_info.key;
+    lp_lock.id = id;
+    if lp_lock
+        .lock(amount, duration, clock.unix_timestamp, exchange_pool)
+        .is_none()
+    {
+        sol_log(&format!(
+            "Lock duration must be between {} and {} seconds",
+            EXCHANGE_LP_LOCK_MIN_DURATION, EXCHANGE_LP_LOCK_MAX_DURATION
+        ));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Create LP lock tokens account.
+    create_associated_token_account(
+        signer_info,
+        lp_lock_info,
+        lp_lock_tokens_info,
+        lp_mint_info,
+        system_program,
+        token_program,
+        associated_token_program,
+    )?;
+
+    // Transfer LP tokens to the lock.
+    transfer(
+        signer_info,
+        user_lp_ata,
+        lp_lock_tokens_info,
+        token_program,
+        amount,
+    )?;
+
+    sol_log(&format!(
+        "Locked {} LP tokens until {} at {}bps boost",
+        amount, lp_lock.unlock_at, lp_lock.boost_bps
+    ));
+
+    Ok(())
+}
+
+/// Releases an LP lock, returning the LP tokens and paying accrued rewards.
+/// Unlocking before the term ends burns EXCHANGE_LP_LOCK_EARLY_EXIT_PENALTY_BPS
+/// of the locked tokens, leaving their share of the reserves to remaining LPs.
+///
+/// Account layout:
+/// 0: signer (writable) - lock authority, receives the rent
+/// 1: exchange_pool (PDA, writable)
+/// 2: lp_lock (PDA, writable)
+/// 3: lp_lock_tokens (writable) - lp_lock's LP token ATA
+/// 4: user_lp_ata (writable) - user's LP destination
+/// 5: lp_mint (PDA, writable)
+/// 6: sol_vault (PDA, writable) - source of SOL rewards
+/// 7: rng_vault (PDA, writable) - source of RNG rewards
+/// 8: user_sol_ata (writable) - user's wSOL destination
+/// 9: user_rng_ata (writable) - user's RNG destination
+/// 10: token_program
+pub fn process_unlock_lp(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, exchange_pool_info, lp_lock_info, lp_lock_tokens_info, user_lp_ata, lp_mint_info, sol_vault_info, rng_vault_info, user_sol_ata, user_rng_ata, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?.is_writable()?;
+    let exchange_pool = exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?
+        .as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    let lp_lock = lp_lock_info
+        .is_writable()?
+        .as_account_mut::<LpLock>(&ore_api::ID)?
+        .assert_mut(|l| l.authority == *signer_info.key)?;
+    lp_mint_info
+        .is_writable()?
+        .has_address(&exchange_pool.lp_mint)?
+        .as_mint()?;
+    lp_lock_tokens_info
+        .is_writable()?
+        .as_associated_token_account(lp_lock_info.key, lp_mint_info.key)?;
+    user_lp_ata
+        .is_writable()?
+        .as_associated_token_account(signer_info.key, lp_mint_info.key)?;
+    sol_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_SOL_VAULT], &ore_api::ID)?;
+    rng_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_RNG_VAULT], &ore_api::ID)?;
+    user_sol_ata.is_writable()?;
+    user_rng_ata.is_writable()?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Release the lock and settle rewards.
+    let (lp_returned, lp_penalty) = lp_lock.unlock(clock.unix_timestamp, exchange_pool);
+    let (sol_amount, rng_amount) = lp_lock.claim(exchange_pool);
+    exchange_pool.lp_rewards_owed_sol = exchange_pool
+        .lp_rewards_owed_sol
+        .checked_sub(sol_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    exchange_pool.lp_rewards_owed_rng = exchange_pool
+        .lp_rewards_owed_rng
+        .checked_sub(rng_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    exchange_pool.total_lp_supply = exchange_pool
+        .total_lp_supply
+        .checked_sub(lp_penalty)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Return LP tokens and burn the penalty.
+    let (_, bump) = lp_lock.pda();
+    let authority = lp_lock.authority.to_bytes();
+    let id = lp_lock.id.to_le_bytes();
+    let lock_seeds: &[&[u8]] = &[LP_LOCK, &authority, &id];
+    transfer_signed(
+        lp_lock_info,
+        lp_lock_tokens_info,
+        user_lp_ata,
+        token_program,
+        lp_returned,
+        lock_seeds,
+    )?;
+    if lp_penalty > 0 {
+        burn_signed(
+            lp_lock_tokens_info,
+            lp_mint_info,
+            lp_lock_info,
+            token_program,
+            lp_penalty,
+            lock_seeds,
+        )?;
+    }
+
+    // Transfer rewards from the pool vaults.
+    if sol_amount > 0 {
+        transfer_signed(
+            exchange_pool_info,
+            sol_vault_info,
+            user_sol_ata,
+            token_program,
+            sol_amount,
+            &[EXCHANGE_POOL],
+        )?;
+    }
+    if rng_amount > 0 {
+        transfer_signed(
+            exchange_pool_info,
+            rng_vault_info,
+            user_rng_ata,
+            token_program,
+            rng_amount,
+            &[EXCHANGE_POOL],
+        )?;
+    }
+
+    // Close the lock.
+    close_with_escrow(
+        lp_lock_info,
+        lp_lock_tokens_info,
+        signer_info,
+        token_program,
+        &[LP_LOCK, &authority, &id, &[bump]],
+    )?;
+
+    sol_log(&format!(
+        "Unlocked LP: returned={}, burned={}, rewards sol={}, rng={}",
+        lp_returned, lp_penalty, sol_amount, rng_amount
+    ));
+
+    Ok(())
+}
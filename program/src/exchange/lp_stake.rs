@@ -0,0 +1,244 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+/// Stakes LP tokens to earn the LP stakers' share of protocol swap fees.
+///
+/// Account layout:
+/// 0: signer
+/// 1: exchange_pool (PDA, writable)
+/// 2: lp_stake (PDA, writable) - created on first stake
+/// 3: lp_stake_tokens (writable) - lp_stake's LP token ATA
+/// 4: user_lp_ata (writable) - user's LP source
+/// 5: lp_mint - LP token mint
+/// 6: system_program
+/// 7: token_program
+/// 8: associated_token_program
+pub fn process_stake_lp(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = StakeLp::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, exchange_pool_info, lp_stake_info, lp_stake_tokens_info, user_lp_ata, lp_mint_info, system_program, token_program, associated_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let exchange_pool = exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?
+        .as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    lp_stake_info
+        .is_writable()?
+        .has_seeds(&[LP_STAKE, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    lp_mint_info.has_address(&exchange_pool.lp_mint)?.as_mint()?;
+    let sender = user_lp_ata
+        .is_writable()?
+        .as_associated_token_account(signer_info.key, lp_mint_info.key)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    let amount = sender.amount().min(amount);
+    if amount == 0 {
+        sol_log("Nothing to stake");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Open LP stake account.
+    let lp_stake = if lp_stake_info.data_is_empty() {
+        create_program_account::<LpStake>(
+            lp_stake_info,
+            system_program,
+            signer_info,
+            &ore_api::ID,
+            &[LP_STAKE, &signer_info.key.to_bytes()],
+        )?;
+        let lp_stake = lp_stake_info.as_account_mut::<LpStake>(&ore_api::ID)?;
+        lp_stake.authority = *signer_info.key;
+        lp_stake.rewards_factor_sol = exchange_pool.lp_rewards_factor_sol;
+        lp_stake.rewards_factor_rng = exchange_pool.lp_rewards_factor_rng;
+        lp_stake
+    } else {
+        lp_stake_info
+            .as_account_mut::<LpStake>(&ore_api::ID)?
+            .assert_mut(|s| s.authority == *signer_info.key)?
+    };
+
+    // Create LP stake tokens account.
+    if lp_stake_tokens_info.data_is_empty() {
+        create_associated_token_account(
+            signer_info,
+            lp_stake_info,
+            lp_stake_tokens_info,
+            lp_mint_info,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+    } else {
+        lp_stake_tokens_info.as_associated_token_account(lp_stake_info.key, lp_mint_info.key)?;
+    }
+
+    // Deposit into LP stake account.
+    lp_stake.deposit(amount, clock.unix_timestamp, exchange_pool);
+
+    // Transfer LP tokens to the stake account.
+    transfer(
+        signer_info,
+        user_lp_ata,
+        lp_stake_tokens_info,
+        token_program,
+        amount,
+    )?;
+
+    sol_log(&format!(
+        "Staked {} LP tokens, total staked={}",
+        amount, exchange_pool.total_lp_staked
+    ));
+
+    Ok(())
+}
+
+/// Unstakes LP tokens. Accrued fee rewards stay claimable.
+///
+/// Account layout:
+/// 0: signer
+/// 1: exchange_pool (PDA, writable)
+/// 2: lp_stake (PDA, writable)
+/// 3: lp_stake_tokens (writable) - lp_stake's LP token ATA
+/// 4: user_lp_ata (writable) - user's LP destination
+/// 5: lp_mint - LP token mint
+/// 6: token_program
+pub fn process_unstake_lp(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = UnstakeLp::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, exchange_pool_info, lp_stake_info, lp_stake_tokens_info, user_lp_ata, lp_mint_info, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let exchange_pool = exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?
+        .as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    let lp_stake = lp_stake_info
+        .is_writable()?
+        .as_account_mut::<LpStake>(&ore_api::ID)?
+        .assert_mut(|s| s.authority == *signer_info.key)?;
+    lp_mint_info.has_address(&exchange_pool.lp_mint)?.as_mint()?;
+    lp_stake_tokens_info
+        .is_writable()?
+        .as_associated_token_account(lp_stake_info.key, lp_mint_info.key)?;
+    user_lp_ata
+        .is_writable()?
+        .as_associated_token_account(signer_info.key, lp_mint_info.key)?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Withdraw from LP stake account.
+    let amount = lp_stake.withdraw(amount, clock.unix_timestamp, exchange_pool);
+
+    // Transfer LP tokens back to the user.
+    transfer_signed(
+        lp_stake_info,
+        lp_stake_tokens_info,
+        user_lp_ata,
+        token_program,
+        amount,
+        &[LP_STAKE, &signer_info.key.to_bytes()],
+    )?;
+
+    sol_log(&format!(
+        "Unstaked {} LP tokens, total staked={}",
+        amount, exchange_pool.total_lp_staked
+    ));
+
+    Ok(())
+}
+
+/// Claims the LP stakers' share of protocol fees accrued to an LP stake account.
+///
+/// Account layout:
+/// 0: signer
+/// 1: exchange_pool (PDA, writable)
+/// 2: lp_stake (PDA, writable)
+/// 3: sol_vault (PDA, writable) - source of SOL rewards
+/// 4: rng_vault (PDA, writable) - source of RNG rewards
+/// 5: user_sol_ata (writable) - user's wSOL destination
+/// 6: user_rng_ata (writable) - user's RNG destination
+/// 7: token_program
+pub fn process_claim_lp_rewards(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, exchange_pool_info, lp_stake_info, sol_vault_info, rng_vault_info, user_sol_ata, user_rng_ata, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let exchange_pool = exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?
+        .as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    let lp_stake = lp_stake_info
+        .is_writable()?
+        .as_account_mut::<LpStake>(&ore_api::ID)?
+        .assert_mut(|s| s.authority == *signer_info.key)?;
+    sol_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_SOL_VAULT], &ore_api::ID)?;
+    rng_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_RNG_VAULT], &ore_api::ID)?;
+    user_sol_ata.is_writable()?;
+    user_rng_ata.is_writable()?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Claim rewards from LP stake account.
+    let (sol_amount, rng_amount) = lp_stake.claim(clock.unix_timestamp, exchange_pool);
+    exchange_pool.lp_rewards_owed_sol = exchange_pool
+        .lp_rewards_owed_sol
+        .checked_sub(sol_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    exchange_pool.lp_rewards_owed_rng = exchange_pool
+        .lp_rewards_owed_rng
+        .checked_sub(rng_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Transfer rewards from the pool vaults.
+    if sol_amount > 0 {
+        transfer_signed(
+            exchange_pool_info,
+            sol_vault_info,
+            user_sol_ata,
+            token_program,
+            sol_amount,
+            &[EXCHANGE_POOL],
+        )?;
+    }
+    if rng_amount > 0 {
+        transfer_signed(
+            exchange_pool_info,
+            rng_vault_info,
+            user_rng_ata,
+            token_program,
+            rng_amount,
+            &[EXCHANGE_POOL],
+        )?;
+    }
+
+    sol_log(&format!(
+        "Claimed LP rewards: sol={}, rng={}",
+        sol_amount, rng_amount
+    ));
+
+    Ok(())
+}
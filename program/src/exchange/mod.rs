@@ -1,15 +1,45 @@
 mod add_liquidity;
+mod buyback;
 mod claim_fees;
-mod distribute_fees;
+mod dca_order;
+mod emergency_withdraw;
+mod game_token_pool;
 mod initialize_pool;
+mod limit_order;
+mod lp_lock;
+mod lp_stake;
+mod quote_swap;
+mod referral;
 mod remove_liquidity;
+mod remove_liquidity_single;
+mod set_pool_fees;
+mod set_pool_status;
+mod skim_fees;
+mod swap_exact_out;
 mod swap_game_token;
+mod swap_sol_game_token;
 mod swap_sol_rng;
+mod zap_liquidity;
 
 pub use add_liquidity::*;
+pub use buyback::*;
 pub use claim_fees::*;
-pub use distribute_fees::*;
+pub use dca_order::*;
+pub use emergency_withdraw::*;
+pub use game_token_pool::*;
 pub use initialize_pool::*;
+pub use limit_order::*;
+pub use lp_lock::*;
+pub use lp_stake::*;
+pub use quote_swap::*;
+pub use referral::*;
 pub use remove_liquidity::*;
+pub use remove_liquidity_single::*;
+pub use set_pool_fees::*;
+pub use set_pool_status::*;
+pub use skim_fees::*;
+pub use swap_exact_out::*;
 pub use swap_game_token::*;
+pub use swap_sol_game_token::*;
 pub use swap_sol_rng::*;
+pub use zap_liquidity::*;
@@ -0,0 +1,91 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use solana_program::program::set_return_data;
+use steel::*;
+
+use super::swap_game_token::GameTokenType;
+
+/// Quotes a swap against the pool without executing it.
+/// Writes a `SwapQuote` to the return data; no account is modified.
+///
+/// Account layout:
+/// 0: exchange_pool (PDA)
+pub fn process_quote_swap(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = QuoteSwap::try_from_bytes(data)?;
+    let amount_in = u64::from_le_bytes(args.amount_in);
+    let sol_to_rng = args.sol_to_rng != 0;
+
+    // Load accounts.
+    let [exchange_pool_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    exchange_pool_info.has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+
+    // Pool must exist and be active.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+    let exchange_pool = exchange_pool_info.as_account::<ExchangePool>(&ore_api::ID)?;
+    if !exchange_pool.is_active() {
+        sol_log("Pool is not active");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Apply the same max swap size as the swap instructions.
+    let input_reserve = if sol_to_rng {
+        exchange_pool.sol_reserve
+    } else {
+        exchange_pool.rng_reserve
+    };
+    let max_swap = exchange_pool
+        .max_swap_amount(input_reserve)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if amount_in > max_swap {
+        sol_log(&format!("Swap too large: {} > max {}", amount_in, max_swap));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let quote = exchange_pool
+        .quote(amount_in, sol_to_rng)
+        .ok_or(ProgramError::InvalidArgument)?;
+    set_return_data(quote.to_bytes());
+
+    Ok(())
+}
+
+/// Reports how well a game token's 1:1 supply is backed by RNG in the vault.
+/// Writes a `GameTokenBacking` to the return data; no account is modified.
+///
+/// Account layout:
+/// 0: exchange_pool (PDA)
+pub fn process_quote_game_token_backing(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = QuoteGameTokenBacking::try_from_bytes(data)?;
+    let game_token_type = GameTokenType::try_from(args.game_token_type)?;
+
+    // Load accounts.
+    let [exchange_pool_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    exchange_pool_info.has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+
+    // Pool must exist.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+    let exchange_pool = exchange_pool_info.as_account::<ExchangePool>(&ore_api::ID)?;
+
+    let backing = exchange_pool
+        .game_token_backing(game_token_type as usize)
+        .ok_or(ProgramError::InvalidArgument)?;
+    sol_log(&format!(
+        "{:?} backing: rng={}, outstanding={}, collateralization_bps={}",
+        game_token_type, backing.backing, backing.outstanding, backing.collateralization_bps
+    ));
+    set_return_data(backing.to_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use ore_api::prelude::*;
+use solana_program::clock::Clock;
+use solana_program::log::sol_log;
+use solana_program::sysvar::Sysvar;
+use steel::*;
+
+use super::remove_liquidity::transfer_from_vault;
+
+/// Registers the signer as a swap referrer.
+///
+/// Account layout:
+/// 0: signer (payer)
+/// 1: referrer (PDA, writable)
+/// 2: system_program
+pub fn process_register_referrer(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, referrer_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    referrer_info
+        .is_writable()?
+        .is_empty()?
+        .has_seeds(&[REFERRER, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Create referrer account.
+    create_program_account::<Referrer>(
+        referrer_info,
+        system_program,
+        signer_info,
+        &ore_api::ID,
+        &[REFERRER, &signer_info.key.to_bytes()],
+    )?;
+    let referrer = referrer_info.as_account_mut::<Referrer>(&ore_api::ID)?;
+    referrer.authority = *signer_info.key;
+
+    sol_log(&format!("Registered referrer {}", signer_info.key));
+
+    Ok(())
+}
+
+/// Claims referral fees accrued to the signer's referrer account.
+///
+/// Account layout:
+/// 0: signer
+/// 1: exchange_pool (PDA, writable)
+/// 2: referrer (PDA, writable)
+/// 3: sol_vault (PDA, writable) - source of SOL fees
+/// 4: rng_vault (PDA, writable) - source of RNG fees
+/// 5: signer_sol_ata (writable) - signer's wSOL destination
+/// 6: signer_rng_ata (writable) - signer's RNG destination
+/// 7: token_program
+pub fn process_claim_referral_fees(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, exchange_pool_info, referrer_info, sol_vault_info, rng_vault_info, signer_sol_ata, signer_rng_ata, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let exchange_pool = exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?
+        .as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    let referrer = referrer_info
+        .is_writable()?
+        .as_account_mut::<Referrer>(&ore_api::ID)?
+        .assert_mut(|r| r.authority == *signer_info.key)?;
+    sol_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_SOL_VAULT], &ore_api::ID)?;
+    rng_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_RNG_VAULT], &ore_api::ID)?;
+    signer_sol_ata.is_writable()?;
+    signer_rng_ata.is_writable()?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Claim pending fees.
+    let (sol_amount, rng_amount) = referrer.claim(clock.unix_timestamp);
+    exchange_pool.referral_fees_owed_sol = exchange_pool
+        .referral_fees_owed_sol
+        .checked_sub(sol_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    exchange_pool.referral_fees_owed_rng = exchange_pool
+        .referral_fees_owed_rng
+        .checked_sub(rng_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Transfer fees from the pool vaults.
+    let (_, pool_bump) = exchange_pool_pda();
+    if sol_amount > 0 {
+        transfer_from_vault(
+            exchange_pool_info,
+            sol_vault_info,
+            signer_sol_ata,
+            token_program,
+            sol_amount,
+            pool_bump,
+        )?;
+    }
+    if rng_amount > 0 {
+        transfer_from_vault(
+            exchange_pool_info,
+            rng_vault_info,
+            signer_rng_ata,
+            token_program,
+            rng_amount,
+            pool_bump,
+        )?;
+    }
+
+    sol_log(&format!(
+        "Claimed referral fees: sol={}, rng={}",
+        sol_amount, rng_amount
+    ));
+
+    Ok(())
+}
+
+/// Credits a swap's referrer with its share of the protocol fee.
+/// Does nothing when no referrer account is passed. Self-referrals are rejected.
+pub(crate) fn credit_referrer(
+    exchange_pool: &mut ExchangePool,
+    referrer_info: Option<&AccountInfo<'_>>,
+    user: &Pubkey,
+    protocol_fee: u64,
+    sol: bool,
+) -> ProgramResult {
+    let Some(referrer_info) = referrer_info else {
+        return Ok(());
+    };
+    let referrer = referrer_info
+        .is_writable()?
+        .as_account_mut::<Referrer>(&ore_api::ID)?;
+    if referrer.authority == *user {
+        sol_log("Cannot refer your own swap");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let amount = exchange_pool
+        .accrue_referral_fee(protocol_fee, sol)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    referrer.credit(amount, sol);
+
+    sol_log(&format!("Referral fee: {} to {}", amount, referrer.authority));
+
+    Ok(())
+}
@@ -72,19 +72,13 @@ pub fn process_remove_liquidity(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pr
         return Err(ProgramError::UninitializedAccount);
     }
 
-    // Get bumps for signing.
+    // Get pool bump for signing. The pool PDA is the token authority of both vaults.
     let (_, pool_bump) = exchange_pool_pda();
-    let (_, sol_vault_bump) = exchange_sol_vault_pda();
-    let (_, _rng_vault_bump) = exchange_rng_vault_pda();
 
-    // Load pool state.
+    // Load pool state. Withdrawals stay open while the pool is paused or
+    // deprecated so LPs can always exit.
     let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
 
-    if !exchange_pool.is_active() {
-        sol_log("Pool is not active");
-        return Err(ProgramError::InvalidAccountData);
-    }
-
     // Calculate withdrawal amounts.
     let sol_amount = (lp_amount as u128)
         .checked_mul(exchange_pool.sol_reserve as u128)
@@ -147,44 +141,22 @@ pub fn process_remove_liquidity(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pr
         &[],
     )?;
 
-    // Transfer SOL from vault to provider.
-    let sol_vault_seeds = &[EXCHANGE_SOL_VAULT, &[sol_vault_bump]];
-    invoke_signed(
-        &spl_token::instruction::transfer(
-            &spl_token::ID,
-            sol_vault_info.key,
-            provider_sol_ata.key,
-            sol_vault_info.key,
-            &[],
-            sol_amount,
-        )?,
-        &[
-            sol_vault_info.clone(),
-            provider_sol_ata.clone(),
-            sol_vault_info.clone(),
-            token_program.clone(),
-        ],
-        &[sol_vault_seeds],
+    // Transfer SOL and RNG from the vaults to provider.
+    transfer_from_vault(
+        exchange_pool_info,
+        sol_vault_info,
+        provider_sol_ata,
+        token_program,
+        sol_amount,
+        pool_bump,
     )?;
-
-    // Transfer RNG from vault to provider.
-    let pool_seeds = &[EXCHANGE_POOL, &[pool_bump]];
-    invoke_signed(
-        &spl_token::instruction::transfer(
-            &spl_token::ID,
-            rng_vault_info.key,
-            provider_rng_ata.key,
-            exchange_pool_info.key,
-            &[],
-            rng_amount,
-        )?,
-        &[
-            rng_vault_info.clone(),
-            provider_rng_ata.clone(),
-            exchange_pool_info.clone(),
-            token_program.clone(),
-        ],
-        &[pool_seeds],
+    transfer_from_vault(
+        exchange_pool_info,
+        rng_vault_info,
+        provider_rng_ata,
+        token_program,
+        rng_amount,
+        pool_bump,
     )?;
 
     // Update pool state.
@@ -215,3 +187,69 @@ pub fn process_remove_liquidity(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pr
 
     Ok(())
 }
+
+/// Transfers tokens out of a pool vault, signed by the pool PDA.
+/// Both vaults are initialized with the pool as their token authority, so
+/// every withdrawal leg must sign with the pool seeds rather than the vault's own.
+pub(crate) fn transfer_from_vault<'info>(
+    exchange_pool_info: &AccountInfo<'info>,
+    vault_info: &AccountInfo<'info>,
+    destination_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+    pool_bump: u8,
+) -> ProgramResult {
+    let pool_seeds = &[EXCHANGE_POOL, &[pool_bump]];
+    invoke_signed(
+        &vault_transfer_instruction(
+            vault_info.key,
+            destination_info.key,
+            exchange_pool_info.key,
+            amount,
+        )?,
+        &[
+            vault_info.clone(),
+            destination_info.clone(),
+            exchange_pool_info.clone(),
+            token_program.clone(),
+        ],
+        &[pool_seeds],
+    )
+}
+
+/// Builds an SPL transfer out of a pool vault with the pool as authority.
+fn vault_transfer_instruction(
+    vault: &Pubkey,
+    destination: &Pubkey,
+    exchange_pool: &Pubkey,
+    amount: u64,
+) -> Result<Instruction, ProgramError> {
+    spl_token::instruction::transfer(&spl_token::ID, vault, destination, exchange_pool, &[], amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_transfers_use_pool_authority() {
+        let (pool, pool_bump) = exchange_pool_pda();
+        let (sol_vault, _) = exchange_sol_vault_pda();
+        let (rng_vault, _) = exchange_rng_vault_pda();
+        let destination = Pubkey::new_unique();
+
+        // The pool seeds must derive the authority named in each leg.
+        let derived =
+            Pubkey::create_program_address(&[EXCHANGE_POOL, &[pool_bump]], &ore_api::ID).unwrap();
+        assert_eq!(derived, pool);
+
+        for vault in [sol_vault, rng_vault] {
+            let ix = vault_transfer_instruction(&vault, &destination, &pool, 100).unwrap();
+            assert_eq!(ix.accounts[0].pubkey, vault);
+            assert_eq!(ix.accounts[1].pubkey, destination);
+            assert_eq!(ix.accounts[2].pubkey, pool);
+            assert!(ix.accounts[2].is_signer);
+            assert!(ix.accounts.iter().all(|meta| meta.pubkey != vault || !meta.is_signer));
+        }
+    }
+}
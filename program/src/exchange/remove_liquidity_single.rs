@@ -0,0 +1,218 @@
+use ore_api::prelude::*;
+use solana_program::clock::Clock;
+use solana_program::log::sol_log;
+use solana_program::program::invoke_signed;
+use solana_program::sysvar::Sysvar;
+use steel::*;
+
+use super::swap_sol_rng::{record_rng_to_sol_swap, record_sol_to_rng_swap};
+
+/// Removes liquidity from the exchange pool into a single token.
+/// Burns LP tokens, withdraws the proportional SOL and RNG, then swaps the unwanted
+/// leg back through the pool so the provider receives only SOL or only RNG.
+/// The swap leg is subject to the max swap size, and `min_amount_out` bounds the
+/// combined output. Requires an active pool; RemoveLiquidity stays available when paused.
+///
+/// Account layout:
+/// 0: provider (signer)
+/// 1: exchange_pool (PDA, writable)
+/// 2: lp_mint (PDA, writable)
+/// 3: sol_vault (PDA, writable)
+/// 4: rng_vault (PDA, writable)
+/// 5: provider_out_ata (writable) - provider's wSOL or RNG destination
+/// 6: provider_lp_ata (writable) - provider's LP source
+/// 7: rng_mint - RNG token mint
+/// 8: sol_mint - wrapped SOL mint
+/// 9: token_program
+pub fn process_remove_liquidity_single(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = RemoveLiquiditySingle::try_from_bytes(data)?;
+    let lp_amount = u64::from_le_bytes(args.lp_amount);
+    let min_amount_out = u64::from_le_bytes(args.min_amount_out);
+    let sol_out = args.sol_out != 0;
+
+    sol_log(&format!(
+        "RemoveLiquiditySingle: lp={}, min_out={}, sol_out={}",
+        lp_amount, min_amount_out, sol_out
+    ));
+
+    // Validate amounts.
+    if lp_amount == 0 {
+        sol_log("LP amount must be greater than 0");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [provider_info, exchange_pool_info, lp_mint_info, sol_vault_info, rng_vault_info, provider_out_ata, provider_lp_ata, rng_mint, sol_mint, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    provider_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+    lp_mint_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_LP_MINT], &ore_api::ID)?;
+    sol_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_SOL_VAULT], &ore_api::ID)?;
+    rng_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_RNG_VAULT], &ore_api::ID)?;
+    provider_out_ata.is_writable()?;
+    provider_lp_ata.is_writable()?;
+    rng_mint.has_address(&RNG_MINT_ADDRESS)?;
+    sol_mint.has_address(&SOL_MINT)?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Pool must exist and be active.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    // Get pool bump for signing.
+    let (_, pool_bump) = exchange_pool_pda();
+
+    // Load pool state.
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    if !exchange_pool.is_active() {
+        sol_log("Pool is not active");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Calculate proportional withdrawal amounts.
+    let (sol_amount, rng_amount) = exchange_pool
+        .calculate_withdraw_amounts(lp_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Ensure pool keeps minimum liquidity.
+    let remaining_lp = exchange_pool
+        .total_lp_supply
+        .checked_sub(lp_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if remaining_lp < exchange_pool.minimum_liquidity {
+        sol_log("Cannot remove liquidity below minimum");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Remove the withdrawn liquidity from the pool.
+    exchange_pool.sol_reserve = exchange_pool
+        .sol_reserve
+        .checked_sub(sol_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    exchange_pool.rng_reserve = exchange_pool
+        .rng_reserve
+        .checked_sub(rng_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    exchange_pool.total_lp_supply = remaining_lp;
+
+    // Swap the unwanted leg back into the pool at the post-withdrawal reserves.
+    let (swap_amount, kept_amount, input_reserve, output_reserve) = if sol_out {
+        (rng_amount, sol_amount, exchange_pool.rng_reserve, exchange_pool.sol_reserve)
+    } else {
+        (sol_amount, rng_amount, exchange_pool.sol_reserve, exchange_pool.rng_reserve)
+    };
+
+    // Check max swap size (prevent large impact swaps).
+    let max_swap = exchange_pool
+        .max_swap_amount(input_reserve)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if swap_amount > max_swap {
+        sol_log(&format!("Swap too large: {} > max {}", swap_amount, max_swap));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (swap_out, lp_fee, protocol_fee) = exchange_pool
+        .calculate_swap_output(swap_amount, input_reserve, output_reserve)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let clock = Clock::get()?;
+    let new_k = if sol_out {
+        record_rng_to_sol_swap(
+            exchange_pool,
+            swap_amount,
+            swap_out,
+            protocol_fee,
+            &clock,
+        )?
+    } else {
+        record_sol_to_rng_swap(
+            exchange_pool,
+            swap_amount,
+            swap_out,
+            lp_fee,
+            protocol_fee,
+            &clock,
+        )?
+    };
+
+    let amount_out = kept_amount
+        .checked_add(swap_out)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    sol_log(&format!(
+        "Withdrawal: sol={}, rng={}, swapped {} for {}, total out={}",
+        sol_amount, rng_amount, swap_amount, swap_out, amount_out
+    ));
+
+    // Check slippage.
+    if amount_out < min_amount_out {
+        sol_log(&format!(
+            "Slippage check failed: {} < {}",
+            amount_out, min_amount_out
+        ));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Burn LP tokens from provider.
+    invoke_signed(
+        &spl_token::instruction::burn(
+            &spl_token::ID,
+            provider_lp_ata.key,
+            lp_mint_info.key,
+            provider_info.key,
+            &[],
+            lp_amount,
+        )?,
+        &[
+            provider_lp_ata.clone(),
+            lp_mint_info.clone(),
+            provider_info.clone(),
+        ],
+        &[],
+    )?;
+
+    // Transfer the single output token from its vault to provider.
+    let vault_info = if sol_out { sol_vault_info } else { rng_vault_info };
+    let pool_seeds = &[EXCHANGE_POOL, &[pool_bump]];
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            &spl_token::ID,
+            vault_info.key,
+            provider_out_ata.key,
+            exchange_pool_info.key,
+            &[],
+            amount_out,
+        )?,
+        &[
+            vault_info.clone(),
+            provider_out_ata.clone(),
+            exchange_pool_info.clone(),
+            token_program.clone(),
+        ],
+        &[pool_seeds],
+    )?;
+
+    sol_log(&format!(
+        "Liquidity removed: out={}, lp_burned={}, new_k={}",
+        amount_out, lp_amount, new_k
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use ore_api::prelude::*;
+use solana_program::clock::Clock;
+use solana_program::log::sol_log;
+use solana_program::sysvar::Sysvar;
+use steel::*;
+
+/// Schedules a change to the pool's swap fee and LP/protocol split.
+/// Admin-only instruction. The change can be applied with ApplyPoolFees once
+/// EXCHANGE_FEE_TIMELOCK_SECONDS have passed, giving LPs and traders notice.
+/// Scheduling again replaces any pending change and restarts the timelock.
+///
+/// Account layout:
+/// 0: admin (signer) - must match pool admin
+/// 1: exchange_pool (PDA, writable)
+pub fn process_set_pool_fees(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = SetPoolFees::try_from_bytes(data)?;
+    let fee_numerator = u64::from_le_bytes(args.fee_numerator);
+    let protocol_fee_share_bps = u64::from_le_bytes(args.protocol_fee_share_bps);
+
+    sol_log(&format!(
+        "SetPoolFees: fee_numerator={}, protocol_fee_share_bps={}",
+        fee_numerator, protocol_fee_share_bps
+    ));
+
+    // Validate fee bounds.
+    if !ExchangePool::validate_fees(fee_numerator, protocol_fee_share_bps) {
+        sol_log(&format!(
+            "Fees out of bounds: max fee_numerator={}, max protocol share=10000",
+            EXCHANGE_MAX_FEE_NUMERATOR
+        ));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [admin_info, exchange_pool_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    admin_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+
+    // Pool must exist.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    if exchange_pool.admin != *admin_info.key {
+        sol_log("Only admin can set pool fees");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let clock = Clock::get()?;
+    let effective_at = exchange_pool
+        .schedule_fees(fee_numerator, protocol_fee_share_bps, clock.unix_timestamp)
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    sol_log(&format!("Fee change scheduled, effective at {}", effective_at));
+
+    Ok(())
+}
+
+/// Applies a pending fee change once its timelock has expired.
+/// Permissionless so the scheduled change cannot be held back.
+///
+/// Account layout:
+/// 0: signer
+/// 1: exchange_pool (PDA, writable)
+pub fn process_apply_pool_fees(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    sol_log("ApplyPoolFees");
+
+    // Load accounts.
+    let [signer_info, exchange_pool_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    signer_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+
+    // Pool must exist.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    let clock = Clock::get()?;
+    if !exchange_pool.apply_pending_fees(clock.unix_timestamp) {
+        sol_log(&format!(
+            "No fee change ready (effective at {})",
+            exchange_pool.pending_fees_effective_at
+        ));
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    sol_log(&format!(
+        "Pool fees updated: fee_numerator={}, protocol_fee_share_bps={}",
+        exchange_pool.fee_numerator, exchange_pool.protocol_fee_share_bps
+    ));
+
+    Ok(())
+}
+
+/// Sets the share of the protocol fee paid to swap referrers. Admin-only instruction.
+/// Takes effect immediately since it only redistributes the protocol's own share.
+///
+/// Account layout:
+/// 0: admin (signer) - must match pool admin
+/// 1: exchange_pool (PDA, writable)
+pub fn process_set_referral_fee(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = SetReferralFee::try_from_bytes(data)?;
+    let referral_fee_share_bps = u64::from_le_bytes(args.referral_fee_share_bps);
+
+    sol_log(&format!(
+        "SetReferralFee: referral_fee_share_bps={}",
+        referral_fee_share_bps
+    ));
+
+    if referral_fee_share_bps > EXCHANGE_MAX_REFERRAL_FEE_SHARE_BPS {
+        sol_log(&format!(
+            "Referral share out of bounds: max {}",
+            EXCHANGE_MAX_REFERRAL_FEE_SHARE_BPS
+        ));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [admin_info, exchange_pool_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    admin_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+
+    // Pool must exist.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    if exchange_pool.admin != *admin_info.key {
+        sol_log("Only admin can set the referral fee");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    exchange_pool.referral_fee_share_bps = referral_fee_share_bps;
+
+    Ok(())
+}
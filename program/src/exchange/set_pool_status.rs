@@ -0,0 +1,261 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+/// Sets the exchange pool status (0 = active, 1 = paused, 2 = deprecated).
+/// The admin can set any status; the guardian can only pause.
+/// Paused and deprecated pools reject swaps and deposits but still allow remove_liquidity.
+///
+/// Account layout:
+/// 0: signer - pool admin or guardian
+/// 1: exchange_pool (PDA, writable)
+pub fn process_set_pool_status(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = SetPoolStatus::try_from_bytes(data)?;
+    let status = args.status;
+
+    sol_log(&format!("SetPoolStatus: status={}", status));
+
+    if status > EXCHANGE_STATUS_DEPRECATED {
+        sol_log("Invalid pool status");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [signer_info, exchange_pool_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    signer_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+
+    // Pool must exist.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    // Guardian may only pause; everything else is admin-only.
+    let is_admin = exchange_pool.admin == *signer_info.key;
+    if status == EXCHANGE_STATUS_PAUSED {
+        if !exchange_pool.can_pause(signer_info.key) {
+            sol_log("Only admin or guardian can pause the pool");
+            return Err(ProgramError::InvalidAccountData);
+        }
+    } else if !is_admin {
+        sol_log("Only admin can change pool status");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Deprecation is permanent.
+    if exchange_pool.is_deprecated() {
+        sol_log("Pool is deprecated");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    exchange_pool.status = status;
+
+    sol_log(&format!("Pool status set to {}", status));
+
+    Ok(())
+}
+
+/// Sets the exchange pool guardian. Admin-only instruction.
+/// Pass the default pubkey to remove the guardian.
+///
+/// Account layout:
+/// 0: admin (signer) - must match pool admin
+/// 1: exchange_pool (PDA, writable)
+pub fn process_set_pool_guardian(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = SetPoolGuardian::try_from_bytes(data)?;
+    let guardian = Pubkey::new_from_array(args.guardian);
+
+    sol_log(&format!("SetPoolGuardian: guardian={}", guardian));
+
+    // Load accounts.
+    let [admin_info, exchange_pool_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    admin_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+
+    // Pool must exist.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    if exchange_pool.admin != *admin_info.key {
+        sol_log("Only admin can set the pool guardian");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    exchange_pool.guardian = guardian;
+
+    Ok(())
+}
+
+/// Configures the pool's price circuit breaker. Admin-only instruction.
+/// Swaps that move the SOL price more than `max_price_move_bps` from the price at the
+/// start of the current `window_seconds` window are rejected until the window rolls over.
+/// A `max_price_move_bps` of 0 disables the breaker.
+///
+/// Account layout:
+/// 0: admin (signer) - must match pool admin
+/// 1: exchange_pool (PDA, writable)
+pub fn process_set_circuit_breaker(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = SetCircuitBreaker::try_from_bytes(data)?;
+    let max_price_move_bps = u64::from_le_bytes(args.max_price_move_bps);
+    let window_seconds = i64::from_le_bytes(args.window_seconds);
+
+    sol_log(&format!(
+        "SetCircuitBreaker: max_price_move_bps={}, window_seconds={}",
+        max_price_move_bps, window_seconds
+    ));
+
+    if max_price_move_bps > 10000 || window_seconds <= 0 {
+        sol_log("Invalid circuit breaker config");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [admin_info, exchange_pool_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    admin_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+
+    // Pool must exist.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    if exchange_pool.admin != *admin_info.key {
+        sol_log("Only admin can configure the circuit breaker");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    exchange_pool.max_price_move_bps = max_price_move_bps;
+    exchange_pool.price_window_seconds = window_seconds;
+
+    // Start a fresh window from the current price on the next swap.
+    exchange_pool.price_window_reference_low = 0;
+    exchange_pool.price_window_reference_high = 0;
+
+    Ok(())
+}
+
+/// Sets the cap on SOL notional swapped within a single slot, as basis points of
+/// the SOL reserve. Zero disables the cap. Admin-only instruction.
+///
+/// Account layout:
+/// 0: admin (signer) - must match pool admin
+/// 1: exchange_pool (PDA, writable)
+pub fn process_set_slot_notional_cap(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = SetSlotNotionalCap::try_from_bytes(data)?;
+    let cap_bps = u64::from_le_bytes(args.cap_bps);
+
+    sol_log(&format!("SetSlotNotionalCap: cap_bps={}", cap_bps));
+
+    // Load accounts.
+    let [admin_info, exchange_pool_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    admin_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+
+    // Pool must exist.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    if exchange_pool.admin != *admin_info.key {
+        sol_log("Only admin can set the slot notional cap");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // A cap below the max swap size would block single swaps the pool otherwise allows.
+    if cap_bps != 0 && cap_bps < exchange_pool.max_swap_bps {
+        sol_log("Slot notional cap must be at least the max swap size");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    exchange_pool.slot_notional_cap_bps = cap_bps;
+
+    Ok(())
+}
+
+/// Sets the maximum swap size as basis points of the input reserve. Admin-only instruction.
+/// Lets the limit grow with liquidity without a redeploy.
+///
+/// Account layout:
+/// 0: admin (signer) - must match pool admin
+/// 1: exchange_pool (PDA, writable)
+pub fn process_set_max_swap(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = SetMaxSwap::try_from_bytes(data)?;
+    let max_swap_bps = u64::from_le_bytes(args.max_swap_bps);
+
+    sol_log(&format!("SetMaxSwap: max_swap_bps={}", max_swap_bps));
+
+    if max_swap_bps == 0 || max_swap_bps > 10000 {
+        sol_log("Invalid max swap size");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [admin_info, exchange_pool_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    admin_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+
+    // Pool must exist.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    if exchange_pool.admin != *admin_info.key {
+        sol_log("Only admin can set the max swap size");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    exchange_pool.max_swap_bps = max_swap_bps;
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::remove_liquidity::transfer_from_vault;
+
+/// Skims accrued protocol fees from the exchange pool to the treasury.
+/// Permissionless crank: each fee token moves once it crosses its skim threshold
+/// (EXCHANGE_FEE_SKIM_THRESHOLD_SOL / EXCHANGE_FEE_SKIM_THRESHOLD_RNG).
+///
+/// Account layout:
+/// 0: caller (signer, payer for treasury ATAs)
+/// 1: exchange_pool (PDA, writable)
+/// 2: sol_vault (PDA, writable) - source of SOL fees
+/// 3: rng_vault (PDA, writable) - source of RNG fees
+/// 4: treasury (PDA)
+/// 5: treasury_sol_ata (writable) - treasury's wSOL token account
+/// 6: treasury_rng_ata (writable) - treasury's RNG token account
+/// 7: sol_mint - wrapped SOL mint
+/// 8: rng_mint - RNG token mint
+/// 9: system_program
+/// 10: token_program
+/// 11: associated_token_program
+pub fn process_skim_protocol_fees(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    sol_log("SkimProtocolFees");
+
+    // Load accounts.
+    let [caller_info, exchange_pool_info, sol_vault_info, rng_vault_info, treasury_info, treasury_sol_ata, treasury_rng_ata, sol_mint, rng_mint, system_program, token_program, associated_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    caller_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+    sol_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_SOL_VAULT], &ore_api::ID)?;
+    rng_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_RNG_VAULT], &ore_api::ID)?;
+    treasury_info.has_address(&TREASURY_ADDRESS)?;
+    treasury_sol_ata.is_writable()?;
+    treasury_rng_ata.is_writable()?;
+    sol_mint.has_address(&SOL_MINT)?;
+    rng_mint.has_address(&RNG_MINT_ADDRESS)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    // Pool must exist.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    // Get pool bump for signing.
+    let (_, pool_bump) = exchange_pool_pda();
+
+    // Take the fees that crossed their thresholds.
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    let (sol_fees, rng_fees) = exchange_pool.skim_protocol_fees();
+
+    if sol_fees == 0 && rng_fees == 0 {
+        sol_log(&format!(
+            "Fees below skim threshold: sol={}, rng={}",
+            exchange_pool.protocol_fees_sol, exchange_pool.protocol_fees_rng
+        ));
+        return Ok(());
+    }
+
+    // Move each skimmed fee to the treasury, creating its ATA if needed.
+    for (fees, vault_info, treasury_ata, mint_info) in [
+        (sol_fees, sol_vault_info, treasury_sol_ata, sol_mint),
+        (rng_fees, rng_vault_info, treasury_rng_ata, rng_mint),
+    ] {
+        if fees == 0 {
+            continue;
+        }
+        if treasury_ata.data_is_empty() {
+            create_associated_token_account(
+                caller_info,
+                treasury_info,
+                treasury_ata,
+                mint_info,
+                system_program,
+                token_program,
+                associated_token_program,
+            )?;
+        } else {
+            treasury_ata.as_associated_token_account(treasury_info.key, mint_info.key)?;
+        }
+        transfer_from_vault(
+            exchange_pool_info,
+            vault_info,
+            treasury_ata,
+            token_program,
+            fees,
+            pool_bump,
+        )?;
+    }
+
+    sol_log(&format!(
+        "Skimmed protocol fees to treasury: sol={}, rng={}",
+        sol_fees, rng_fees
+    ));
+
+    Ok(())
+}
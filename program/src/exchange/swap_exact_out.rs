@@ -0,0 +1,340 @@
+use ore_api::prelude::*;
+use solana_program::clock::Clock;
+use solana_program::log::sol_log;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::sysvar::Sysvar;
+use steel::*;
+
+use super::referral::credit_referrer;
+use super::swap_sol_rng::{record_rng_to_sol_swap, record_sol_to_rng_swap};
+
+/// Swaps SOL for an exact amount of RNG using CPMM pricing.
+/// The SOL input is derived by inverting the CPMM formula and must not exceed `max_sol_in`.
+///
+/// Account layout:
+/// 0: user (signer)
+/// 1: exchange_pool (PDA, writable)
+/// 2: sol_vault (PDA, writable)
+/// 3: rng_vault (PDA, writable)
+/// 4: user_rng_ata (writable) - user's RNG destination
+/// 5: rng_mint - RNG token mint
+/// 6: sol_mint - wrapped SOL mint
+/// 7: system_program
+/// 8: token_program
+/// 9: referrer (optional, writable) - registered Referrer PDA
+pub fn process_swap_sol_to_rng_exact_out(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = SwapSolToRngExactOut::try_from_bytes(data)?;
+    let rng_out = u64::from_le_bytes(args.rng_out);
+    let max_sol_in = u64::from_le_bytes(args.max_sol_in);
+
+    sol_log(&format!(
+        "SwapSolToRngExactOut: rng_out={}, max_sol_in={}",
+        rng_out, max_sol_in
+    ));
+
+    // Validate amounts.
+    if rng_out == 0 {
+        sol_log("RNG amount must be greater than 0");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [user_info, exchange_pool_info, sol_vault_info, rng_vault_info, user_rng_ata, rng_mint, sol_mint, system_program, token_program, referrer_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    user_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+    sol_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_SOL_VAULT], &ore_api::ID)?;
+    rng_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_RNG_VAULT], &ore_api::ID)?;
+    user_rng_ata.is_writable()?;
+    rng_mint.has_address(&RNG_MINT_ADDRESS)?;
+    sol_mint.has_address(&SOL_MINT)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Pool must exist and be active.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    // Get bumps for signing.
+    let (_, pool_bump) = exchange_pool_pda();
+
+    // Load pool state.
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    if !exchange_pool.is_active() {
+        sol_log("Pool is not active");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Calculate required input by inverting the CPMM formula.
+    let (sol_amount, lp_fee, protocol_fee) = exchange_pool
+        .calculate_swap_input(rng_out, exchange_pool.sol_reserve, exchange_pool.rng_reserve)
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    sol_log(&format!(
+        "Swap input: sol_in={}, lp_fee={}, protocol_fee={}",
+        sol_amount, lp_fee, protocol_fee
+    ));
+
+    // Check max swap size (prevent large impact swaps).
+    let max_swap = exchange_pool
+        .max_swap_amount(exchange_pool.sol_reserve)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if sol_amount > max_swap {
+        sol_log(&format!("Swap too large: {} > max {}", sol_amount, max_swap));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Check slippage.
+    if sol_amount > max_sol_in {
+        sol_log(&format!(
+            "Slippage check failed: {} > {}",
+            sol_amount, max_sol_in
+        ));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Transfer SOL from user to vault.
+    invoke(
+        &solana_program::system_instruction::transfer(user_info.key, sol_vault_info.key, sol_amount),
+        &[user_info.clone(), sol_vault_info.clone()],
+    )?;
+    // Sync native to update token balance.
+    invoke(
+        &spl_token::instruction::sync_native(&spl_token::ID, sol_vault_info.key)?,
+        &[sol_vault_info.clone()],
+    )?;
+
+    // Transfer RNG from vault to user.
+    let pool_seeds = &[EXCHANGE_POOL, &[pool_bump]];
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            &spl_token::ID,
+            rng_vault_info.key,
+            user_rng_ata.key,
+            exchange_pool_info.key,
+            &[],
+            rng_out,
+        )?,
+        &[
+            rng_vault_info.clone(),
+            user_rng_ata.clone(),
+            exchange_pool_info.clone(),
+            token_program.clone(),
+        ],
+        &[pool_seeds],
+    )?;
+
+    // Update pool state.
+    let clock = Clock::get()?;
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    let new_k = record_sol_to_rng_swap(
+        exchange_pool,
+        sol_amount,
+        rng_out,
+        lp_fee,
+        protocol_fee,
+        &clock,
+    )?;
+
+    // Credit the referrer, if any.
+    credit_referrer(
+        exchange_pool,
+        referrer_accounts.first(),
+        user_info.key,
+        protocol_fee,
+        true,
+    )?;
+
+    sol_log(&format!(
+        "Swap complete: sol_in={}, rng_out={}, new_k={}",
+        sol_amount, rng_out, new_k
+    ));
+
+    Ok(())
+}
+
+/// Swaps RNG for an exact amount of SOL using CPMM pricing.
+/// The RNG input is derived by inverting the CPMM formula and must not exceed `max_rng_in`.
+///
+/// Account layout:
+/// 0: user (signer)
+/// 1: exchange_pool (PDA, writable)
+/// 2: sol_vault (PDA, writable)
+/// 3: rng_vault (PDA, writable)
+/// 4: user_sol_ata (writable) - user's wSOL destination
+/// 5: user_rng_ata (writable) - user's RNG source
+/// 6: rng_mint - RNG token mint
+/// 7: sol_mint - wrapped SOL mint
+/// 8: system_program
+/// 9: token_program
+/// 10: referrer (optional, writable) - registered Referrer PDA
+pub fn process_swap_rng_to_sol_exact_out(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = SwapRngToSolExactOut::try_from_bytes(data)?;
+    let sol_out = u64::from_le_bytes(args.sol_out);
+    let max_rng_in = u64::from_le_bytes(args.max_rng_in);
+
+    sol_log(&format!(
+        "SwapRngToSolExactOut: sol_out={}, max_rng_in={}",
+        sol_out, max_rng_in
+    ));
+
+    // Validate amounts.
+    if sol_out == 0 {
+        sol_log("SOL amount must be greater than 0");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [user_info, exchange_pool_info, sol_vault_info, rng_vault_info, user_sol_ata, user_rng_ata, rng_mint, sol_mint, system_program, token_program, referrer_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    user_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+    sol_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_SOL_VAULT], &ore_api::ID)?;
+    rng_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_RNG_VAULT], &ore_api::ID)?;
+    user_sol_ata.is_writable()?;
+    user_rng_ata.is_writable()?;
+    rng_mint.has_address(&RNG_MINT_ADDRESS)?;
+    sol_mint.has_address(&SOL_MINT)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Pool must exist and be active.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    // Get bumps for signing.
+    let (_, pool_bump) = exchange_pool_pda();
+
+    // Load pool state.
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    if !exchange_pool.is_active() {
+        sol_log("Pool is not active");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Calculate required input by inverting the CPMM formula.
+    let (rng_amount, lp_fee, protocol_fee) = exchange_pool
+        .calculate_swap_input(sol_out, exchange_pool.rng_reserve, exchange_pool.sol_reserve)
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    sol_log(&format!(
+        "Swap input: rng_in={}, lp_fee={}, protocol_fee={}",
+        rng_amount, lp_fee, protocol_fee
+    ));
+
+    // Check max swap size (prevent large impact swaps).
+    let max_swap = exchange_pool
+        .max_swap_amount(exchange_pool.rng_reserve)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if rng_amount > max_swap {
+        sol_log(&format!("Swap too large: {} > max {}", rng_amount, max_swap));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Check slippage.
+    if rng_amount > max_rng_in {
+        sol_log(&format!(
+            "Slippage check failed: {} > {}",
+            rng_amount, max_rng_in
+        ));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Transfer RNG from user to vault.
+    invoke(
+        &spl_token::instruction::transfer(
+            &spl_token::ID,
+            user_rng_ata.key,
+            rng_vault_info.key,
+            user_info.key,
+            &[],
+            rng_amount,
+        )?,
+        &[
+            user_rng_ata.clone(),
+            rng_vault_info.clone(),
+            user_info.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    // Transfer SOL from vault to user (as wSOL).
+    let pool_seeds = &[EXCHANGE_POOL, &[pool_bump]];
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            &spl_token::ID,
+            sol_vault_info.key,
+            user_sol_ata.key,
+            exchange_pool_info.key,
+            &[],
+            sol_out,
+        )?,
+        &[
+            sol_vault_info.clone(),
+            user_sol_ata.clone(),
+            exchange_pool_info.clone(),
+            token_program.clone(),
+        ],
+        &[pool_seeds],
+    )?;
+
+    // Update pool state.
+    let clock = Clock::get()?;
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    let new_k = record_rng_to_sol_swap(
+        exchange_pool,
+        rng_amount,
+        sol_out,
+        protocol_fee,
+        &clock,
+    )?;
+
+    // Credit the referrer, if any.
+    credit_referrer(
+        exchange_pool,
+        referrer_accounts.first(),
+        user_info.key,
+        protocol_fee,
+        false,
+    )?;
+
+    sol_log(&format!(
+        "Swap complete: rng_in={}, sol_out={}, new_k={}",
+        rng_amount, sol_out, new_k
+    ));
+
+    Ok(())
+}
@@ -40,7 +40,7 @@ impl TryFrom<u8> for GameTokenType {
 }
 
 /// Get the mint address for a game token type.
-fn get_game_token_mint(token_type: GameTokenType) -> Pubkey {
+pub(crate) fn get_game_token_mint(token_type: GameTokenType) -> Pubkey {
     match token_type {
         GameTokenType::Crap => CRAP_MINT_ADDRESS,
         GameTokenType::Carat => CARAT_MINT_ADDRESS,
@@ -54,7 +54,38 @@ fn get_game_token_mint(token_type: GameTokenType) -> Pubkey {
     }
 }
 
+/// Get the maximum supply for a game token type.
+pub(crate) fn get_game_token_max_supply(token_type: GameTokenType) -> u64 {
+    match token_type {
+        GameTokenType::Crap => MAX_CRAP_SUPPLY,
+        _ => MAX_GAME_TOKEN_SUPPLY,
+    }
+}
+
+/// Rejects minting `amount` game tokens if it would push the mint past its supply cap.
+pub(crate) fn check_game_token_supply(
+    game_mint: &AccountInfo<'_>,
+    token_type: GameTokenType,
+    amount: u64,
+) -> ProgramResult {
+    let supply = game_mint.as_mint()?.supply();
+    let max_supply = get_game_token_max_supply(token_type);
+    if exceeds_supply_cap(supply, amount, max_supply) {
+        sol_log(&format!(
+            "Mint would exceed {:?} max supply: {} + {} > {}",
+            token_type, supply, amount, max_supply
+        ));
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+fn exceeds_supply_cap(supply: u64, amount: u64, max_supply: u64) -> bool {
+    supply.checked_add(amount).map_or(true, |total| total > max_supply)
+}
+
 /// Swaps RNG for a game token at 1:1 rate (minus fee).
+/// Fallback path for game tokens without a GameTokenPool (see SwapGameTokenPool).
 /// Game tokens are minted to the user (RNG is burned/held).
 ///
 /// This uses a fixed 1:1 rate for simplicity - game tokens are
@@ -128,15 +159,17 @@ pub fn process_swap_rng_to_game_token(accounts: &[AccountInfo<'_>], data: &[u8])
         return Err(ProgramError::InvalidAccountData);
     }
 
-    // Calculate fee (1% of RNG amount).
+    // Calculate fee (default 1% of RNG amount).
     let total_fee = rng_amount
         .checked_mul(exchange_pool.fee_numerator)
         .ok_or(ProgramError::ArithmeticOverflow)?
         .checked_div(exchange_pool.fee_denominator)
         .ok_or(ProgramError::ArithmeticOverflow)?;
 
-    // 50% to protocol, 50% kept in RNG vault (for LPs when they provide RNG liquidity).
-    let protocol_fee = total_fee / 2;
+    // Protocol share to protocol, remainder kept in RNG vault (for LPs when they provide RNG liquidity).
+    let (_, protocol_fee) = exchange_pool
+        .split_fee(total_fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
 
     // Game tokens minted = RNG in - total fee (1:1 rate minus fee).
     let game_tokens_out = rng_amount
@@ -148,6 +181,9 @@ pub fn process_swap_rng_to_game_token(accounts: &[AccountInfo<'_>], data: &[u8])
         rng_amount, game_tokens_out, total_fee
     ));
 
+    // Enforce the game token's supply cap.
+    check_game_token_supply(game_mint, game_token_type, game_tokens_out)?;
+
     // Transfer RNG from user to vault.
     invoke(
         &spl_token::instruction::transfer(
@@ -186,12 +222,24 @@ pub fn process_swap_rng_to_game_token(accounts: &[AccountInfo<'_>], data: &[u8])
         &[pool_seeds],
     )?;
 
-    // Update pool state to track fees.
+    // Update pool state to track fees and backing. Everything but the protocol
+    // fee stays in the vault behind the minted tokens.
     let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    exchange_pool
+        .record_game_token_mint(
+            game_token_type as usize,
+            rng_amount - protocol_fee,
+            game_tokens_out,
+        )
+        .ok_or(ProgramError::ArithmeticOverflow)?;
     exchange_pool.protocol_fees_rng = exchange_pool
         .protocol_fees_rng
         .checked_add(protocol_fee)
         .ok_or(ProgramError::ArithmeticOverflow)?;
+    // Stream the LP stakers' share of the protocol fee.
+    exchange_pool
+        .accrue_lp_staker_fees(protocol_fee, false)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
     exchange_pool.total_swaps = exchange_pool
         .total_swaps
         .checked_add(1)
@@ -206,6 +254,7 @@ pub fn process_swap_rng_to_game_token(accounts: &[AccountInfo<'_>], data: &[u8])
 }
 
 /// Swaps a game token for RNG at 1:1 rate (minus fee).
+/// Fallback path for game tokens without a GameTokenPool (see SwapGameTokenPool).
 /// Game tokens are burned from user, RNG is transferred from vault.
 ///
 /// Account layout:
@@ -276,15 +325,17 @@ pub fn process_swap_game_token_to_rng(accounts: &[AccountInfo<'_>], data: &[u8])
         return Err(ProgramError::InvalidAccountData);
     }
 
-    // Calculate fee (1% of game token amount).
+    // Calculate fee (default 1% of game token amount).
     let total_fee = game_token_amount
         .checked_mul(exchange_pool.fee_numerator)
         .ok_or(ProgramError::ArithmeticOverflow)?
         .checked_div(exchange_pool.fee_denominator)
         .ok_or(ProgramError::ArithmeticOverflow)?;
 
-    // 50% to protocol, 50% kept in vault.
-    let protocol_fee = total_fee / 2;
+    // Protocol share to protocol, remainder kept in vault.
+    let (_, protocol_fee) = exchange_pool
+        .split_fee(total_fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
 
     // RNG out = game tokens in - total fee (1:1 rate minus fee).
     let rng_out = game_token_amount
@@ -333,12 +384,28 @@ pub fn process_swap_game_token_to_rng(accounts: &[AccountInfo<'_>], data: &[u8])
         &[pool_seeds],
     )?;
 
-    // Update pool state to track fees.
+    // Update pool state to track fees and backing. The RNG paid out and the
+    // protocol fee both leave the backing; the LP fee stays behind it.
     let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    if exchange_pool
+        .record_game_token_redeem(
+            game_token_type as usize,
+            game_token_amount,
+            rng_out + protocol_fee,
+        )
+        .is_none()
+    {
+        sol_log("Redemption would leave the game token under-backed");
+        return Err(ProgramError::InsufficientFunds);
+    }
     exchange_pool.protocol_fees_rng = exchange_pool
         .protocol_fees_rng
         .checked_add(protocol_fee)
         .ok_or(ProgramError::ArithmeticOverflow)?;
+    // Stream the LP stakers' share of the protocol fee.
+    exchange_pool
+        .accrue_lp_staker_fees(protocol_fee, false)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
     exchange_pool.total_swaps = exchange_pool
         .total_swaps
         .checked_add(1)
@@ -351,3 +418,19 @@ pub fn process_swap_game_token_to_rng(accounts: &[AccountInfo<'_>], data: &[u8])
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supply_cap() {
+        let max = get_game_token_max_supply(GameTokenType::Crap);
+        assert_eq!(max, MAX_CRAP_SUPPLY);
+        assert!(!exceeds_supply_cap(0, max, max));
+        assert!(!exceeds_supply_cap(max - 1, 1, max));
+        assert!(exceeds_supply_cap(max, 1, max));
+        assert!(exceeds_supply_cap(u64::MAX, 1, max));
+        assert_eq!(get_game_token_max_supply(GameTokenType::Uth), MAX_GAME_TOKEN_SUPPLY);
+    }
+}
@@ -0,0 +1,208 @@
+use ore_api::prelude::*;
+use solana_program::clock::Clock;
+use solana_program::log::sol_log;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::sysvar::Sysvar;
+use steel::*;
+
+use super::swap_game_token::{check_game_token_supply, get_game_token_mint, GameTokenType};
+use super::swap_sol_rng::record_sol_to_rng_swap;
+
+/// Swaps SOL for a game token in one instruction.
+/// Chains the SOL -> RNG CPMM swap with the 1:1 RNG -> game token mint. The
+/// intermediate RNG never leaves the vault, and a single slippage bound
+/// applies to the game tokens received.
+///
+/// Account layout:
+/// 0: user (signer)
+/// 1: exchange_pool (PDA, writable)
+/// 2: sol_vault (PDA, writable)
+/// 3: rng_vault (PDA, writable)
+/// 4: user_game_ata (writable) - user's game token destination
+/// 5: game_mint (writable) - game token mint (for minting)
+/// 6: rng_mint - RNG token mint
+/// 7: sol_mint - wrapped SOL mint
+/// 8: system_program
+/// 9: token_program
+pub fn process_swap_sol_to_game_token(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = SwapSolToGameToken::try_from_bytes(data)?;
+    let sol_amount = u64::from_le_bytes(args.sol_amount);
+    let min_game_out = u64::from_le_bytes(args.min_game_out);
+    let game_token_type = GameTokenType::try_from(args.game_token_type)?;
+
+    sol_log(&format!(
+        "SwapSolToGameToken: sol_in={}, min_game_out={}, game_type={:?}",
+        sol_amount, min_game_out, game_token_type
+    ));
+
+    // Validate amounts.
+    if sol_amount == 0 {
+        sol_log("SOL amount must be greater than 0");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [user_info, exchange_pool_info, sol_vault_info, rng_vault_info, user_game_ata, game_mint, rng_mint, sol_mint, system_program, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    user_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+    sol_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_SOL_VAULT], &ore_api::ID)?;
+    rng_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_RNG_VAULT], &ore_api::ID)?;
+    user_game_ata.is_writable()?;
+    rng_mint.has_address(&RNG_MINT_ADDRESS)?;
+    sol_mint.has_address(&SOL_MINT)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Validate game token mint.
+    let expected_mint = get_game_token_mint(game_token_type);
+    game_mint.has_address(&expected_mint)?;
+    game_mint.is_writable()?;
+
+    // Pool must exist and be active.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    // Get bumps for signing.
+    let (_, pool_bump) = exchange_pool_pda();
+
+    // Load pool state.
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    if !exchange_pool.is_active() {
+        sol_log("Pool is not active");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Check max swap size (prevent large impact swaps).
+    let max_swap = exchange_pool
+        .max_swap_amount(exchange_pool.sol_reserve)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if sol_amount > max_swap {
+        sol_log(&format!("Swap too large: {} > max {}", sol_amount, max_swap));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // First leg: SOL -> RNG using CPMM pricing.
+    let (rng_out, lp_fee, protocol_fee) = exchange_pool
+        .calculate_swap_output(sol_amount, exchange_pool.sol_reserve, exchange_pool.rng_reserve)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Second leg: RNG -> game token at 1:1 minus fee.
+    let game_fee = rng_out
+        .checked_mul(exchange_pool.fee_numerator)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .checked_div(exchange_pool.fee_denominator)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let (_, game_protocol_fee) = exchange_pool
+        .split_fee(game_fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let game_tokens_out = rng_out
+        .checked_sub(game_fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    sol_log(&format!(
+        "Route: sol_in={}, rng_mid={}, game_out={}",
+        sol_amount, rng_out, game_tokens_out
+    ));
+
+    // Check slippage on the final output only.
+    if game_tokens_out < min_game_out {
+        sol_log(&format!(
+            "Slippage check failed: {} < {}",
+            game_tokens_out, min_game_out
+        ));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Enforce the game token's supply cap.
+    check_game_token_supply(game_mint, game_token_type, game_tokens_out)?;
+
+    // Transfer SOL from user to vault.
+    invoke(
+        &solana_program::system_instruction::transfer(user_info.key, sol_vault_info.key, sol_amount),
+        &[user_info.clone(), sol_vault_info.clone()],
+    )?;
+    // Sync native to update token balance.
+    invoke(
+        &spl_token::instruction::sync_native(&spl_token::ID, sol_vault_info.key)?,
+        &[sol_vault_info.clone()],
+    )?;
+
+    // Mint game tokens to user. The RNG bought in the first leg stays in the
+    // vault, backing the game tokens the same way a direct RNG -> game swap does.
+    let pool_seeds = &[EXCHANGE_POOL, &[pool_bump]];
+    invoke_signed(
+        &spl_token::instruction::mint_to(
+            &spl_token::ID,
+            game_mint.key,
+            user_game_ata.key,
+            exchange_pool_info.key,
+            &[],
+            game_tokens_out,
+        )?,
+        &[
+            game_mint.clone(),
+            user_game_ata.clone(),
+            exchange_pool_info.clone(),
+        ],
+        &[pool_seeds],
+    )?;
+
+    // Update pool state.
+    let clock = Clock::get()?;
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    let new_k = record_sol_to_rng_swap(
+        exchange_pool,
+        sol_amount,
+        rng_out,
+        lp_fee,
+        protocol_fee,
+        &clock,
+    )?;
+
+    // Track the RNG left in the vault behind the minted game tokens.
+    exchange_pool
+        .record_game_token_mint(
+            game_token_type as usize,
+            rng_out - game_protocol_fee,
+            game_tokens_out,
+        )
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Track the game leg's protocol fee and count it as a second swap.
+    exchange_pool.protocol_fees_rng = exchange_pool
+        .protocol_fees_rng
+        .checked_add(game_protocol_fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    // Stream the LP stakers' share of the protocol fee.
+    exchange_pool
+        .accrue_lp_staker_fees(game_protocol_fee, false)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    exchange_pool.total_swaps = exchange_pool
+        .total_swaps
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    sol_log(&format!(
+        "Swap complete: sol_in={}, minted {} game tokens, new_k={}",
+        sol_amount, game_tokens_out, new_k
+    ));
+
+    Ok(())
+}
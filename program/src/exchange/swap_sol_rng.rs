@@ -6,6 +6,8 @@ use solana_program::program::invoke_signed;
 use solana_program::sysvar::Sysvar;
 use steel::*;
 
+use super::referral::credit_referrer;
+
 /// Swaps SOL for RNG using CPMM pricing.
 ///
 /// Account layout:
@@ -18,6 +20,7 @@ use steel::*;
 /// 6: sol_mint - wrapped SOL mint
 /// 7: system_program
 /// 8: token_program
+/// 9: referrer (optional, writable) - registered Referrer PDA
 pub fn process_swap_sol_to_rng(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse instruction data.
     let args = SwapSolToRng::try_from_bytes(data)?;
@@ -36,7 +39,7 @@ pub fn process_swap_sol_to_rng(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
     }
 
     // Load accounts.
-    let [user_info, exchange_pool_info, sol_vault_info, rng_vault_info, user_rng_ata, rng_mint, sol_mint, system_program, token_program] =
+    let [user_info, exchange_pool_info, sol_vault_info, rng_vault_info, user_rng_ata, rng_mint, sol_mint, system_program, token_program, referrer_accounts @ ..] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -78,10 +81,7 @@ pub fn process_swap_sol_to_rng(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
 
     // Check max swap size (prevent large impact swaps).
     let max_swap = exchange_pool
-        .sol_reserve
-        .checked_mul(EXCHANGE_MAX_SWAP_BPS)
-        .ok_or(ProgramError::ArithmeticOverflow)?
-        .checked_div(10000)
+        .max_swap_amount(exchange_pool.sol_reserve)
         .ok_or(ProgramError::ArithmeticOverflow)?;
     if sol_amount > max_swap {
         sol_log(&format!("Swap too large: {} > max {}", sol_amount, max_swap));
@@ -142,47 +142,23 @@ pub fn process_swap_sol_to_rng(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
     let clock = Clock::get()?;
     let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
 
-    // SOL goes in (minus protocol fee which stays tracked separately).
-    let sol_in_to_pool = sol_amount
-        .checked_sub(protocol_fee)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    exchange_pool.sol_reserve = exchange_pool
-        .sol_reserve
-        .checked_add(sol_in_to_pool)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    // RNG goes out.
-    exchange_pool.rng_reserve = exchange_pool
-        .rng_reserve
-        .checked_sub(rng_out)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-
-    // Track protocol fees (in SOL for this swap direction).
-    exchange_pool.protocol_fees_sol = exchange_pool
-        .protocol_fees_sol
-        .checked_add(protocol_fee)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-
-    // Update k (will change slightly due to fees going to LPs).
-    let new_k = (exchange_pool.sol_reserve as u128)
-        .checked_mul(exchange_pool.rng_reserve as u128)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    exchange_pool.set_k(new_k);
+    let new_k = record_sol_to_rng_swap(
+        exchange_pool,
+        sol_amount,
+        rng_out,
+        lp_fee,
+        protocol_fee,
+        &clock,
+    )?;
 
-    // Update stats.
-    exchange_pool.total_volume_sol = exchange_pool
-        .total_volume_sol
-        .checked_add(sol_amount)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    exchange_pool.total_fees_collected_sol = exchange_pool
-        .total_fees_collected_sol
-        .checked_add(lp_fee)
-        .checked_and_then(|v| v.checked_add(protocol_fee))
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    exchange_pool.total_swaps = exchange_pool
-        .total_swaps
-        .checked_add(1)
-        .ok_or(ProgramError::ArithmeticOverflow)?;
-    exchange_pool.last_swap_at = clock.unix_timestamp;
+    // Credit the referrer, if any.
+    credit_referrer(
+        exchange_pool,
+        referrer_accounts.first(),
+        user_info.key,
+        protocol_fee,
+        true,
+    )?;
 
     sol_log(&format!(
         "Swap complete: sol_in={}, rng_out={}, new_k={}",
@@ -205,6 +181,7 @@ pub fn process_swap_sol_to_rng(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
 /// 7: sol_mint - wrapped SOL mint
 /// 8: system_program
 /// 9: token_program
+/// 10: referrer (optional, writable) - registered Referrer PDA
 pub fn process_swap_rng_to_sol(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse instruction data.
     let args = SwapRngToSol::try_from_bytes(data)?;
@@ -223,7 +200,7 @@ pub fn process_swap_rng_to_sol(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
     }
 
     // Load accounts.
-    let [user_info, exchange_pool_info, sol_vault_info, rng_vault_info, user_sol_ata, user_rng_ata, rng_mint, sol_mint, system_program, token_program] =
+    let [user_info, exchange_pool_info, sol_vault_info, rng_vault_info, user_sol_ata, user_rng_ata, rng_mint, sol_mint, system_program, token_program, referrer_accounts @ ..] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -266,10 +243,7 @@ pub fn process_swap_rng_to_sol(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
 
     // Check max swap size (prevent large impact swaps).
     let max_swap = exchange_pool
-        .rng_reserve
-        .checked_mul(EXCHANGE_MAX_SWAP_BPS)
-        .ok_or(ProgramError::ArithmeticOverflow)?
-        .checked_div(10000)
+        .max_swap_amount(exchange_pool.rng_reserve)
         .ok_or(ProgramError::ArithmeticOverflow)?;
     if rng_amount > max_swap {
         sol_log(&format!("Swap too large: {} > max {}", rng_amount, max_swap));
@@ -338,6 +312,128 @@ pub fn process_swap_rng_to_sol(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
     let clock = Clock::get()?;
     let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
 
+    let new_k = record_rng_to_sol_swap(
+        exchange_pool,
+        rng_amount,
+        sol_out,
+        protocol_fee,
+        &clock,
+    )?;
+
+    // Credit the referrer, if any.
+    credit_referrer(
+        exchange_pool,
+        referrer_accounts.first(),
+        user_info.key,
+        protocol_fee,
+        false,
+    )?;
+
+    sol_log(&format!(
+        "Swap complete: rng_in={}, sol_out={}, new_k={}",
+        rng_amount, sol_out, new_k
+    ));
+
+    Ok(())
+}
+
+/// Applies a SOL -> RNG swap to the pool's reserves, fees, and stats.
+/// Returns the new constant product.
+pub(crate) fn record_sol_to_rng_swap(
+    exchange_pool: &mut ExchangePool,
+    sol_amount: u64,
+    rng_out: u64,
+    lp_fee: u64,
+    protocol_fee: u64,
+    clock: &Clock,
+) -> Result<u128, ProgramError> {
+    let now = clock.unix_timestamp;
+    let old_k = exchange_pool
+        .reserve_product()
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Cap the SOL notional swapped within a single slot.
+    check_slot_notional(exchange_pool, clock.slot, sol_amount)?;
+
+    // Accrue the pre-swap price into the TWAP accumulators.
+    exchange_pool.update_price_accumulators(now);
+    exchange_pool.roll_price_window(now);
+
+    // SOL goes in (minus protocol fee which stays tracked separately).
+    let sol_in_to_pool = sol_amount
+        .checked_sub(protocol_fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    exchange_pool.sol_reserve = exchange_pool
+        .sol_reserve
+        .checked_add(sol_in_to_pool)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    // RNG goes out.
+    exchange_pool.rng_reserve = exchange_pool
+        .rng_reserve
+        .checked_sub(rng_out)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Reject swaps that move the price past the circuit breaker band.
+    if !exchange_pool.within_price_band() {
+        sol_log("Circuit breaker: price moved too far within the window");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Track protocol fees (in SOL for this swap direction).
+    exchange_pool.protocol_fees_sol = exchange_pool
+        .protocol_fees_sol
+        .checked_add(protocol_fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    // Stream the LP stakers' share of the protocol fee.
+    exchange_pool
+        .accrue_lp_staker_fees(protocol_fee, true)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Update k (grows slightly as LP fees stay in the pool).
+    let new_k = checked_new_k(exchange_pool, old_k)?;
+    exchange_pool.set_k(new_k);
+
+    // Update stats.
+    exchange_pool.total_volume_sol = exchange_pool
+        .total_volume_sol
+        .checked_add(sol_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    exchange_pool.total_fees_collected_sol = exchange_pool
+        .total_fees_collected_sol
+        .checked_add(lp_fee)
+        .checked_and_then(|v| v.checked_add(protocol_fee))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    exchange_pool.record_stats(now, sol_amount, lp_fee.saturating_add(protocol_fee));
+    exchange_pool.total_swaps = exchange_pool
+        .total_swaps
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    exchange_pool.last_swap_at = now;
+
+    Ok(new_k)
+}
+
+/// Applies an RNG -> SOL swap to the pool's reserves, fees, and stats.
+/// Returns the new constant product.
+pub(crate) fn record_rng_to_sol_swap(
+    exchange_pool: &mut ExchangePool,
+    rng_amount: u64,
+    sol_out: u64,
+    protocol_fee: u64,
+    clock: &Clock,
+) -> Result<u128, ProgramError> {
+    let now = clock.unix_timestamp;
+    let old_k = exchange_pool
+        .reserve_product()
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Cap the SOL notional swapped within a single slot.
+    check_slot_notional(exchange_pool, clock.slot, sol_out)?;
+
+    // Accrue the pre-swap price into the TWAP accumulators.
+    exchange_pool.update_price_accumulators(now);
+    exchange_pool.roll_price_window(now);
+
     // RNG goes in (minus protocol fee which stays tracked separately).
     let rng_in_to_pool = rng_amount
         .checked_sub(protocol_fee)
@@ -352,16 +448,24 @@ pub fn process_swap_rng_to_sol(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
         .checked_sub(sol_out)
         .ok_or(ProgramError::ArithmeticOverflow)?;
 
+    // Reject swaps that move the price past the circuit breaker band.
+    if !exchange_pool.within_price_band() {
+        sol_log("Circuit breaker: price moved too far within the window");
+        return Err(ProgramError::InvalidArgument);
+    }
+
     // Track protocol fees (in RNG for this swap direction).
     exchange_pool.protocol_fees_rng = exchange_pool
         .protocol_fees_rng
         .checked_add(protocol_fee)
         .ok_or(ProgramError::ArithmeticOverflow)?;
-
-    // Update k.
-    let new_k = (exchange_pool.sol_reserve as u128)
-        .checked_mul(exchange_pool.rng_reserve as u128)
+    // Stream the LP stakers' share of the protocol fee.
+    exchange_pool
+        .accrue_lp_staker_fees(protocol_fee, false)
         .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Update k (grows slightly as LP fees stay in the pool).
+    let new_k = checked_new_k(exchange_pool, old_k)?;
     exchange_pool.set_k(new_k);
 
     // Update stats (convert to SOL equivalent for volume tracking).
@@ -370,20 +474,51 @@ pub fn process_swap_rng_to_sol(accounts: &[AccountInfo<'_>], data: &[u8]) -> Pro
         .total_volume_sol
         .checked_add(sol_equivalent)
         .ok_or(ProgramError::ArithmeticOverflow)?;
+    // The fee is paid in RNG; value it at this swap's execution price.
+    let fee_rng = (rng_amount as u128) * (exchange_pool.fee_numerator as u128)
+        / (exchange_pool.fee_denominator.max(1) as u128);
+    let fee_sol = (fee_rng * sol_out as u128 / rng_amount.max(1) as u128) as u64;
+    exchange_pool.record_stats(now, sol_equivalent, fee_sol);
     exchange_pool.total_swaps = exchange_pool
         .total_swaps
         .checked_add(1)
         .ok_or(ProgramError::ArithmeticOverflow)?;
-    exchange_pool.last_swap_at = clock.unix_timestamp;
+    exchange_pool.last_swap_at = now;
 
-    sol_log(&format!(
-        "Swap complete: rng_in={}, sol_out={}, new_k={}",
-        rng_amount, sol_out, new_k
-    ));
+    Ok(new_k)
+}
 
+/// Adds a swap's SOL notional to the current slot's total, failing once the slot
+/// exceeds the pool's cap. Limits how much a sandwich can move around a victim.
+fn check_slot_notional(
+    exchange_pool: &mut ExchangePool,
+    slot: u64,
+    notional_sol: u64,
+) -> ProgramResult {
+    if !exchange_pool.record_slot_notional(slot, notional_sol) {
+        sol_log(&format!(
+            "Slot notional cap exceeded: {} SOL swapped in slot {}",
+            exchange_pool.slot_notional_sol, slot
+        ));
+        return Err(ProgramError::InvalidArgument);
+    }
     Ok(())
 }
 
+/// Returns the post-swap reserve product, failing if it fell below `old_k`.
+/// The protocol fee never enters the reserves, so the LP fee alone must cover
+/// rounding; a smaller product means the swap paid out more than it took in.
+fn checked_new_k(exchange_pool: &ExchangePool, old_k: u128) -> Result<u128, ProgramError> {
+    let new_k = exchange_pool
+        .reserve_product()
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if new_k < old_k {
+        sol_log(&format!("K invariant violated: old_k={}, new_k={}", old_k, new_k));
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(new_k)
+}
+
 /// Helper trait for checked arithmetic chains.
 trait CheckedAnd {
     fn checked_and_then<F>(self, f: F) -> Option<u64>
@@ -399,3 +534,117 @@ impl CheckedAnd for Option<u64> {
         self.and_then(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(sol_reserve: u64, rng_reserve: u64, fee_numerator: u64) -> ExchangePool {
+        let mut pool = ExchangePool::zeroed();
+        pool.sol_reserve = sol_reserve;
+        pool.rng_reserve = rng_reserve;
+        pool.fee_numerator = fee_numerator;
+        pool.fee_denominator = 10000;
+        pool.protocol_fee_share_bps = EXCHANGE_DEFAULT_PROTOCOL_FEE_SHARE_BPS;
+        pool.set_k(pool.reserve_product().unwrap());
+        pool
+    }
+
+    /// Reserve shapes and input sizes chosen to stress floor division in the CPMM formula.
+    const ADVERSARIAL_CASES: [(u64, u64, u64, u64); 8] = [
+        (1, 1, 0, 1),
+        (3, 7, 0, 2),
+        (1_000, 1, 100, 999),
+        (1, 1_000, 100, 1),
+        (999_999, 1_000_001, 1, 3),
+        (1_000_000_007, 13, 30, 1_000_000_006),
+        (u32::MAX as u64, u32::MAX as u64 - 1, 100, 97),
+        (u64::MAX / 4, 3, 0, u64::MAX / 8),
+    ];
+
+    #[test]
+    fn test_honest_swaps_never_decrease_k() {
+        for (sol, rng, fee, amount) in ADVERSARIAL_CASES {
+            let mut forward = pool(sol, rng, fee);
+            if let Some((out, lp_fee, protocol_fee)) =
+                forward.calculate_swap_output(amount, sol, rng)
+            {
+                let old_k = forward.k();
+                let new_k =
+                    record_sol_to_rng_swap(&mut forward, amount, out, lp_fee, protocol_fee, &Clock::default())
+                        .unwrap();
+                assert!(new_k >= old_k, "sol->rng {:?}", (sol, rng, fee, amount));
+            }
+
+            let mut reverse = pool(sol, rng, fee);
+            if let Some((out, _, protocol_fee)) = reverse.calculate_swap_output(amount, rng, sol) {
+                let old_k = reverse.k();
+                let new_k =
+                    record_rng_to_sol_swap(&mut reverse, amount, out, protocol_fee, &Clock::default()).unwrap();
+                assert!(new_k >= old_k, "rng->sol {:?}", (sol, rng, fee, amount));
+            }
+        }
+    }
+
+    #[test]
+    fn test_slot_notional_cap() {
+        let mut pool = pool(1_000_000, 1_000_000, 0);
+        pool.slot_notional_cap_bps = 1000;
+        let slot = |slot| Clock {
+            slot,
+            ..Clock::default()
+        };
+
+        // Front-run and victim fit within 10% of the SOL reserve...
+        let (out, lp_fee, protocol_fee) = pool.calculate_swap_output(60_000, 1_000_000, 1_000_000).unwrap();
+        record_sol_to_rng_swap(&mut pool, 60_000, out, lp_fee, protocol_fee, &slot(7)).unwrap();
+        let (input_reserve, output_reserve) = (pool.sol_reserve, pool.rng_reserve);
+        let (out, lp_fee, protocol_fee) = pool.calculate_swap_output(30_000, input_reserve, output_reserve).unwrap();
+        record_sol_to_rng_swap(&mut pool, 30_000, out, lp_fee, protocol_fee, &slot(7)).unwrap();
+
+        // ...but the back-run in the same slot is over the cap.
+        let rng_in = 60_000;
+        let (out, _, protocol_fee) = pool.calculate_swap_output(rng_in, pool.rng_reserve, pool.sol_reserve).unwrap();
+        assert_eq!(
+            record_rng_to_sol_swap(&mut pool, rng_in, out, protocol_fee, &slot(7)),
+            Err(ProgramError::InvalidArgument)
+        );
+
+        // The next slot starts fresh.
+        let (out, _, protocol_fee) = pool.calculate_swap_output(rng_in, pool.rng_reserve, pool.sol_reserve).unwrap();
+        record_rng_to_sol_swap(&mut pool, rng_in, out, protocol_fee, &slot(8)).unwrap();
+    }
+
+    #[test]
+    fn test_overpaying_swap_is_rejected() {
+        // Rounding the output up by a single unit is enough to shrink k on a fee-free pool.
+        let mut forward = pool(3, 7, 0);
+        let (out, lp_fee, protocol_fee) = forward.calculate_swap_output(2, 3, 7).unwrap();
+        assert_eq!(
+            record_sol_to_rng_swap(&mut forward, 2, out + 1, lp_fee, protocol_fee, &Clock::default()),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        let mut reverse = pool(1_000_000, 1_000_000, 0);
+        let (out, _, protocol_fee) = reverse.calculate_swap_output(1, 1_000_000, 1_000_000).unwrap();
+        assert_eq!(out, 0);
+        assert_eq!(
+            record_rng_to_sol_swap(&mut reverse, 1, out + 1, protocol_fee, &Clock::default()),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_protocol_fee_outflow_is_excluded_from_k() {
+        // A protocol fee of the entire input leaves reserves unchanged: k holds exactly.
+        let mut pool = pool(1_000, 1_000, 0);
+        let old_k = pool.k();
+        assert_eq!(record_sol_to_rng_swap(&mut pool, 5, 0, 0, 5, &Clock::default()), Ok(old_k));
+
+        // Paying out anything on top of that is a loss to LPs.
+        assert_eq!(
+            record_sol_to_rng_swap(&mut pool, 5, 1, 0, 5, &Clock::default()),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use ore_api::prelude::*;
+use solana_program::clock::Clock;
+use solana_program::log::sol_log;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::sysvar::Sysvar;
+use steel::*;
+
+use super::swap_sol_rng::{record_rng_to_sol_swap, record_sol_to_rng_swap};
+
+/// Adds liquidity from a single token ("zap").
+/// Swaps part of the deposit through the pool so both sides match the reserve
+/// ratio, then deposits both and mints LP tokens. The swapped tokens never leave
+/// the vaults. `min_lp_tokens` bounds slippage across both steps; rounding dust
+/// from the balanced deposit stays in the pool.
+///
+/// Account layout:
+/// 0: provider (signer, payer)
+/// 1: exchange_pool (PDA, writable)
+/// 2: lp_mint (PDA, writable)
+/// 3: sol_vault (PDA, writable)
+/// 4: rng_vault (PDA, writable)
+/// 5: provider_rng_ata (writable) - provider's RNG source (RNG zaps only)
+/// 6: provider_lp_ata (writable) - provider's LP destination
+/// 7: rng_mint - RNG token mint
+/// 8: sol_mint - wrapped SOL mint
+/// 9: system_program
+/// 10: token_program
+/// 11: associated_token_program
+pub fn process_zap_liquidity(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse instruction data.
+    let args = ZapLiquidity::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+    let min_lp_tokens = u64::from_le_bytes(args.min_lp_tokens);
+    let sol_in = args.sol_in != 0;
+
+    sol_log(&format!(
+        "ZapLiquidity: amount={}, min_lp={}, sol_in={}",
+        amount, min_lp_tokens, sol_in
+    ));
+
+    // Validate amounts.
+    if amount == 0 {
+        sol_log("Amount must be greater than 0");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [provider_info, exchange_pool_info, lp_mint_info, sol_vault_info, rng_vault_info, provider_rng_ata, provider_lp_ata, rng_mint, sol_mint, system_program, token_program, associated_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate accounts.
+    provider_info.is_signer()?;
+    exchange_pool_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_POOL], &ore_api::ID)?;
+    lp_mint_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_LP_MINT], &ore_api::ID)?;
+    sol_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_SOL_VAULT], &ore_api::ID)?;
+    rng_vault_info
+        .is_writable()?
+        .has_seeds(&[EXCHANGE_RNG_VAULT], &ore_api::ID)?;
+    provider_rng_ata.is_writable()?;
+    provider_lp_ata.is_writable()?;
+    rng_mint.has_address(&RNG_MINT_ADDRESS)?;
+    sol_mint.has_address(&SOL_MINT)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    // Pool must exist and be active.
+    if exchange_pool_info.data_is_empty() {
+        sol_log("Pool not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    // Get pool bump for signing.
+    let (_, pool_bump) = exchange_pool_pda();
+
+    // Load pool state.
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+
+    if !exchange_pool.is_active() {
+        sol_log("Pool is not active");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Zaps price against existing liquidity.
+    if exchange_pool.total_lp_supply == 0 {
+        sol_log("Pool has no liquidity");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let (input_reserve, output_reserve) = if sol_in {
+        (exchange_pool.sol_reserve, exchange_pool.rng_reserve)
+    } else {
+        (exchange_pool.rng_reserve, exchange_pool.sol_reserve)
+    };
+
+    // Calculate the internal swap.
+    let swap_amount = exchange_pool
+        .calculate_zap_swap_amount(amount, input_reserve, output_reserve)
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    // Check max swap size (prevent large impact swaps).
+    let max_swap = exchange_pool
+        .max_swap_amount(input_reserve)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if swap_amount > max_swap {
+        sol_log(&format!("Swap too large: {} > max {}", swap_amount, max_swap));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (swap_out, lp_fee, protocol_fee) = exchange_pool
+        .calculate_swap_output(swap_amount, input_reserve, output_reserve)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Apply the swap leg to the pool.
+    let clock = Clock::get()?;
+    if sol_in {
+        record_sol_to_rng_swap(
+            exchange_pool,
+            swap_amount,
+            swap_out,
+            lp_fee,
+            protocol_fee,
+            &clock,
+        )?;
+    } else {
+        record_rng_to_sol_swap(
+            exchange_pool,
+            swap_amount,
+            swap_out,
+            protocol_fee,
+            &clock,
+        )?;
+    }
+
+    // Deposit the remaining input and the swap output at the post-swap ratio.
+    let remaining = amount - swap_amount;
+    let (deposit_sol, deposit_rng) = if sol_in {
+        (remaining, swap_out)
+    } else {
+        (swap_out, remaining)
+    };
+
+    // Calculate LP tokens to mint, proportional to existing supply.
+    let sol_lp = (deposit_sol as u128)
+        .checked_mul(exchange_pool.total_lp_supply as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .checked_div(exchange_pool.sol_reserve as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)? as u64;
+    let rng_lp = (deposit_rng as u128)
+        .checked_mul(exchange_pool.total_lp_supply as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .checked_div(exchange_pool.rng_reserve as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)? as u64;
+    let lp_tokens = sol_lp.min(rng_lp);
+
+    sol_log(&format!(
+        "Zap: swapped {} for {}, depositing sol={}, rng={}, lp={}",
+        swap_amount, swap_out, deposit_sol, deposit_rng, lp_tokens
+    ));
+
+    if lp_tokens == 0 {
+        sol_log("LP tokens would be 0");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Check slippage.
+    if lp_tokens < min_lp_tokens {
+        sol_log(&format!(
+            "Slippage check failed: {} < {}",
+            lp_tokens, min_lp_tokens
+        ));
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Create provider's LP ATA if needed.
+    if provider_lp_ata.data_is_empty() {
+        create_associated_token_account(
+            provider_info,
+            provider_info,
+            provider_lp_ata,
+            lp_mint_info,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+        sol_log("Created provider LP ATA");
+    }
+
+    // Transfer the full deposit to its vault.
+    if sol_in {
+        invoke(
+            &solana_program::system_instruction::transfer(provider_info.key, sol_vault_info.key, amount),
+            &[provider_info.clone(), sol_vault_info.clone()],
+        )?;
+        // Sync native to update token balance.
+        invoke(
+            &spl_token::instruction::sync_native(&spl_token::ID, sol_vault_info.key)?,
+            &[sol_vault_info.clone()],
+        )?;
+    } else {
+        invoke(
+            &spl_token::instruction::transfer(
+                &spl_token::ID,
+                provider_rng_ata.key,
+                rng_vault_info.key,
+                provider_info.key,
+                &[],
+                amount,
+            )?,
+            &[
+                provider_rng_ata.clone(),
+                rng_vault_info.clone(),
+                provider_info.clone(),
+                token_program.clone(),
+            ],
+        )?;
+    }
+
+    // Mint LP tokens to provider.
+    let pool_seeds = &[EXCHANGE_POOL, &[pool_bump]];
+    invoke_signed(
+        &spl_token::instruction::mint_to(
+            &spl_token::ID,
+            lp_mint_info.key,
+            provider_lp_ata.key,
+            exchange_pool_info.key,
+            &[],
+            lp_tokens,
+        )?,
+        &[
+            lp_mint_info.clone(),
+            provider_lp_ata.clone(),
+            exchange_pool_info.clone(),
+        ],
+        &[pool_seeds],
+    )?;
+
+    // Update pool state.
+    let exchange_pool = exchange_pool_info.as_account_mut::<ExchangePool>(&ore_api::ID)?;
+    exchange_pool.sol_reserve = exchange_pool
+        .sol_reserve
+        .checked_add(deposit_sol)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    exchange_pool.rng_reserve = exchange_pool
+        .rng_reserve
+        .checked_add(deposit_rng)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    exchange_pool.total_lp_supply = exchange_pool
+        .total_lp_supply
+        .checked_add(lp_tokens)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Update k.
+    let new_k = (exchange_pool.sol_reserve as u128)
+        .checked_mul(exchange_pool.rng_reserve as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    exchange_pool.set_k(new_k);
+
+    sol_log(&format!(
+        "Zap complete: amount={}, lp_minted={}, new_k={}",
+        amount, lp_tokens, new_k
+    ));
+
+    Ok(())
+}
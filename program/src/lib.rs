@@ -1,15 +1,21 @@
 // Domain modules
 pub mod craps;
 pub mod mining;
+pub mod pool;
 pub mod staking;
 pub mod claiming;
 pub mod admin;
+pub mod session;
+pub mod exchange;
 
 use craps::*;
 use mining::*;
+use pool::*;
 use staking::*;
 use claiming::*;
 use admin::*;
+use session::*;
+use exchange::*;
 
 use ore_api::instruction::*;
 use steel::*;
@@ -26,45 +32,175 @@ pub fn process_instruction(
         OreInstruction::Initialize => process_initialize(accounts, data)?,
         OreInstruction::Automate => process_automate(accounts, data)?,
         OreInstruction::Checkpoint => process_checkpoint(accounts, data)?,
+        OreInstruction::CheckpointBatch => process_checkpoint_batch(accounts, data)?,
+        OreInstruction::CheckpointArchived => process_checkpoint_archived(accounts, data)?,
         OreInstruction::Deploy => process_deploy(accounts, data)?,
+        OreInstruction::DeployMany => process_deploy_many(accounts, data)?,
+        OreInstruction::DeploySpread => process_deploy_spread(accounts, data)?,
+        OreInstruction::DeployWithSol => process_deploy_with_sol(accounts, data)?,
+        OreInstruction::SetAutoRedeploy => process_set_auto_redeploy(accounts, data)?,
         OreInstruction::Log => process_log(accounts, data)?,
         OreInstruction::Close => process_close(accounts, data)?,
+        OreInstruction::ArchiveRound => process_archive_round(accounts, data)?,
         OreInstruction::Reset => process_reset(accounts, data)?,
         OreInstruction::RecycleSOL => process_recycle_sol(accounts, data)?,
 
         // Claiming
         OreInstruction::ClaimSOL => process_claim_sol(accounts, data)?,
         OreInstruction::ClaimORE => process_claim_ore(accounts, data)?,
+        OreInstruction::ClaimVested => process_claim_vested(accounts, data)?,
+        OreInstruction::SweepDust => process_sweep_dust(accounts, data)?,
+        OreInstruction::CrankClaims => process_crank_claims(accounts, data)?,
 
         // Staking
         OreInstruction::Deposit => process_deposit(accounts, data)?,
         OreInstruction::Withdraw => process_withdraw(accounts, data)?,
         OreInstruction::ClaimYield => process_claim_yield(accounts, data)?,
+        OreInstruction::ClaimYieldAndRestake => process_claim_yield_and_restake(accounts, data)?,
+        OreInstruction::ClaimYieldAsSol => process_claim_yield_as_sol(accounts, data)?,
+        OreInstruction::ClaimWithdrawal => process_claim_withdrawal(accounts, data)?,
+        OreInstruction::SetStakeOperator => process_set_stake_operator(accounts, data)?,
+        OreInstruction::MintStakeReceipt => process_mint_stake_receipt(accounts, data)?,
+        OreInstruction::RedeemStakeReceipt => process_redeem_stake_receipt(accounts, data)?,
+        OreInstruction::DepositRng => process_deposit_rng(accounts, data)?,
+        OreInstruction::WithdrawRng => process_withdraw_rng(accounts, data)?,
+        OreInstruction::CloseStake => process_close_stake(accounts, data)?,
+
+        // Mining pools
+        OreInstruction::OpenPool => process_open_pool(accounts, data)?,
+        OreInstruction::JoinPool => process_join_pool(accounts, data)?,
+        OreInstruction::LeavePool => process_leave_pool(accounts, data)?,
+        OreInstruction::ClaimPoolRewards => process_claim_pool_rewards(accounts, data)?,
+        OreInstruction::PoolDeploy => process_pool_deploy(accounts, data)?,
+        OreInstruction::HarvestPool => process_harvest_pool(accounts, data)?,
 
         // Admin
         OreInstruction::Bury => process_bury(accounts, data)?,
         OreInstruction::Wrap => process_wrap(accounts, data)?,
+        OreInstruction::DistributeRevenue => process_distribute_revenue(accounts, data)?,
+        OreInstruction::RouteSwap => process_route_swap(accounts, data)?,
         OreInstruction::SetAdmin => process_set_admin(accounts, data)?,
+        OreInstruction::SetAdminMultisig => process_set_admin_multisig(accounts, data)?,
+        OreInstruction::AcceptAdmin => process_accept_admin(accounts, data)?,
+        OreInstruction::Pause => process_pause(accounts, data)?,
+        OreInstruction::ScheduleUnpause => process_schedule_unpause(accounts, data)?,
+        OreInstruction::ApplyUnpause => process_apply_unpause(accounts, data)?,
+        OreInstruction::SetGuardian => process_set_guardian(accounts, data)?,
+        OreInstruction::ApplyGuardian => process_apply_guardian(accounts, data)?,
+        OreInstruction::SetParam => process_set_param(accounts, data)?,
+        OreInstruction::SetRole => process_set_role(accounts, data)?,
+        OreInstruction::SetRoundPermission => process_set_round_permission(accounts, data)?,
+        OreInstruction::SetRevenuePolicy => process_set_revenue_policy(accounts, data)?,
         OreInstruction::SetFeeCollector => process_set_fee_collector(accounts, data)?,
         OreInstruction::SetSwapProgram => process_set_swap_program(accounts, data)?,
         OreInstruction::SetVarAddress => process_set_var_address(accounts, data)?,
         OreInstruction::NewVar => process_new_var(accounts, data)?,
         OreInstruction::SetAdminFee => process_set_admin_fee(accounts, data)?,
+        OreInstruction::SetUnstakeCooldown => process_set_unstake_cooldown(accounts, data)?,
+        OreInstruction::SetMotherlodeParams => process_set_motherlode_params(accounts, data)?,
+        OreInstruction::SetRewardSplitPolicy => process_set_reward_split_policy(accounts, data)?,
+        OreInstruction::SetVestingPolicy => process_set_vesting_policy(accounts, data)?,
+        OreInstruction::SetClaimMinimums => process_set_claim_minimums(accounts, data)?,
         OreInstruction::StartRound => process_start_round(accounts, data)?,
 
         // Craps
         OreInstruction::PlaceCrapsBet => process_place_craps_bet(accounts, data)?,
+        OreInstruction::PlaceCrapsBundle => process_place_craps_bundle(accounts, data)?,
         OreInstruction::SettleCraps => process_settle_craps(accounts, data)?,
+        OreInstruction::SettleCrapsSingleRoll => process_settle_craps_single_roll(accounts, data)?,
+        OreInstruction::SimulateSettle => process_simulate_settle(accounts, data)?,
         OreInstruction::ClaimCrapsWinnings => process_claim_craps_winnings(accounts, data)?,
         OreInstruction::FundCrapsHouse => process_fund_craps_house(accounts, data)?,
+        OreInstruction::ScheduleHouseWithdrawal => process_schedule_house_withdrawal(accounts, data)?,
+        OreInstruction::WithdrawHouseBankroll => process_withdraw_house_bankroll(accounts, data)?,
+        OreInstruction::AssertSolvency => process_assert_solvency(accounts, data)?,
+        OreInstruction::SetHouseProfitShare => process_set_house_profit_share(accounts, data)?,
+        OreInstruction::DistributeHouseProfit => process_distribute_house_profit(accounts, data)?,
         // SECURITY FIX 2.1: Force settle for reserved payout DoS prevention
         OreInstruction::ForceSettleCraps => process_force_settle_craps(accounts, data)?,
         // SECURITY FIX 2.2: Claim unpaid debt from insolvency
         OreInstruction::ClaimCrapsDebt => process_claim_craps_debt(accounts, data)?,
 
+        // Test roll oracle (localnet/devnet builds only)
+        #[cfg(feature = "test-oracle")]
+        OreInstruction::SetTestRoll => process_set_test_roll(accounts, data)?,
+        #[cfg(not(feature = "test-oracle"))]
+        OreInstruction::SetTestRoll => return Err(ProgramError::InvalidInstructionData),
+
+        // One-shot table setup (localnet builds only)
+        #[cfg(feature = "localnet")]
+        OreInstruction::Bootstrap => process_bootstrap(accounts, data)?,
+        #[cfg(not(feature = "localnet"))]
+        OreInstruction::Bootstrap => return Err(ProgramError::InvalidInstructionData),
+
+        // Test token faucet (devnet builds only)
+        #[cfg(feature = "devnet")]
+        OreInstruction::FaucetRng => process_faucet_rng(accounts, data)?,
+        #[cfg(feature = "devnet")]
+        OreInstruction::FaucetCrap => process_faucet_crap(accounts, data)?,
+        #[cfg(not(feature = "devnet"))]
+        OreInstruction::FaucetRng | OreInstruction::FaucetCrap => {
+            return Err(ProgramError::InvalidInstructionData)
+        }
+
+        // Protocol KPI counters
+        OreInstruction::OpenMetrics => process_open_metrics(accounts, data)?,
+
+        // Session keys
+        OreInstruction::CreateSession => process_create_session(accounts, data)?,
+        OreInstruction::RevokeSession => process_revoke_session(accounts, data)?,
+
+        // Exchange
+        OreInstruction::InitializeExchangePool => process_initialize_exchange_pool(accounts, data)?,
+        OreInstruction::AddLiquidity => process_add_liquidity(accounts, data)?,
+        OreInstruction::RemoveLiquidity => process_remove_liquidity(accounts, data)?,
+        OreInstruction::SwapSolToRng => process_swap_sol_to_rng(accounts, data)?,
+        OreInstruction::SwapRngToSol => process_swap_rng_to_sol(accounts, data)?,
+        OreInstruction::SwapRngToGameToken => process_swap_rng_to_game_token(accounts, data)?,
+        OreInstruction::SwapGameTokenToRng => process_swap_game_token_to_rng(accounts, data)?,
+        OreInstruction::ClaimProtocolFees => process_claim_protocol_fees(accounts, data)?,
+        OreInstruction::QuoteSwap => process_quote_swap(accounts, data)?,
+        OreInstruction::SwapSolToGameToken => process_swap_sol_to_game_token(accounts, data)?,
+        OreInstruction::SwapSolToRngExactOut => process_swap_sol_to_rng_exact_out(accounts, data)?,
+        OreInstruction::SwapRngToSolExactOut => process_swap_rng_to_sol_exact_out(accounts, data)?,
+        OreInstruction::SetPoolStatus => process_set_pool_status(accounts, data)?,
+        OreInstruction::SetPoolGuardian => process_set_pool_guardian(accounts, data)?,
+        OreInstruction::SetPoolFees => process_set_pool_fees(accounts, data)?,
+        OreInstruction::ApplyPoolFees => process_apply_pool_fees(accounts, data)?,
+        OreInstruction::InitializeGameTokenPool => process_initialize_game_token_pool(accounts, data)?,
+        OreInstruction::SwapGameTokenPool => process_swap_game_token_pool(accounts, data)?,
+        OreInstruction::StakeLp => process_stake_lp(accounts, data)?,
+        OreInstruction::UnstakeLp => process_unstake_lp(accounts, data)?,
+        OreInstruction::ClaimLpRewards => process_claim_lp_rewards(accounts, data)?,
+        OreInstruction::ZapLiquidity => process_zap_liquidity(accounts, data)?,
+        OreInstruction::RemoveLiquiditySingle => process_remove_liquidity_single(accounts, data)?,
+        OreInstruction::SkimProtocolFees => process_skim_protocol_fees(accounts, data)?,
+        OreInstruction::RegisterReferrer => process_register_referrer(accounts, data)?,
+        OreInstruction::ClaimReferralFees => process_claim_referral_fees(accounts, data)?,
+        OreInstruction::SetReferralFee => process_set_referral_fee(accounts, data)?,
+        OreInstruction::SetCircuitBreaker => process_set_circuit_breaker(accounts, data)?,
+        OreInstruction::SetMaxSwap => process_set_max_swap(accounts, data)?,
+        OreInstruction::EmergencyRemoveLiquidity => process_emergency_remove_liquidity(accounts, data)?,
+        OreInstruction::PlaceLimitOrder => process_place_limit_order(accounts, data)?,
+        OreInstruction::FillLimitOrder => process_fill_limit_order(accounts, data)?,
+        OreInstruction::CancelLimitOrder => process_cancel_limit_order(accounts, data)?,
+        OreInstruction::OpenDcaOrder => process_open_dca_order(accounts, data)?,
+        OreInstruction::CrankDcaOrder => process_crank_dca_order(accounts, data)?,
+        OreInstruction::CancelDcaOrder => process_cancel_dca_order(accounts, data)?,
+        OreInstruction::LockLp => process_lock_lp(accounts, data)?,
+        OreInstruction::UnlockLp => process_unlock_lp(accounts, data)?,
+        OreInstruction::QuoteGameTokenBacking => process_quote_game_token_backing(accounts, data)?,
+        OreInstruction::SetBuybackConfig => process_set_buyback_config(accounts, data)?,
+        OreInstruction::BuybackCrap => process_buyback_crap(accounts, data)?,
+        OreInstruction::SetSlotNotionalCap => process_set_slot_notional_cap(accounts, data)?,
+
         // Migration
         OreInstruction::MigrateRound => process_migrate_round(accounts, data)?,
         OreInstruction::MigrateMiner => process_migrate_miner(accounts, data)?,
+        OreInstruction::MigrateCrapsPosition => process_migrate_craps_position(accounts, data)?,
+        OreInstruction::MigrateStake => process_migrate_stake(accounts, data)?,
+        OreInstruction::MigrateConfig => process_migrate_config(accounts, data)?,
+        OreInstruction::MigrateAccount => process_migrate_account(accounts, data)?,
     }
 
     Ok(())
@@ -0,0 +1,60 @@
+use ore_api::prelude::*;
+use solana_program::rent::Rent;
+use steel::*;
+
+/// Replaces an ended round with a compact archive account.
+///
+/// The archive stores the round's results and a Merkle root of its per-square
+/// totals, and holds the round's unclaimed SOL so late claimants can still
+/// checkpoint. The round's rent goes back to its rent payer; the signer pays the
+/// archive's rent.
+pub fn process_archive_round(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    ArchiveRound::try_from_bytes(data)?;
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, board_info, round_info, archive_info, rent_payer_info, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let board = board_info.as_account::<Board>(&ore_api::ID)?;
+    rent_payer_info.is_writable()?;
+    let round = round_info
+        .as_account_mut::<Round>(&ore_api::ID)?
+        .assert_mut(|r| r.id < board.round_id)?
+        .assert_mut(|r| r.slot_hash != [0; 32])? // Ensure round has been rolled.
+        .assert_mut(|r| r.expires_at >= clock.slot)? // Expired rounds are closed instead.
+        .assert_mut(|r| r.rent_payer == *rent_payer_info.key)?;
+    archive_info
+        .is_empty()?
+        .is_writable()?
+        .has_seeds(&[ROUND_ARCHIVE, &round.id.to_le_bytes()], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Create the archive.
+    let snapshot = RoundArchive::from_round(round, *signer_info.key);
+    create_program_account::<RoundArchive>(
+        archive_info,
+        system_program,
+        signer_info,
+        &ore_api::ID,
+        &[ROUND_ARCHIVE, &round.id.to_le_bytes()],
+    )?;
+    let archive = archive_info.as_account_mut::<RoundArchive>(&ore_api::ID)?;
+    *archive = snapshot;
+
+    // Move unclaimed SOL into the archive.
+    let min_rent = Rent::get()?.minimum_balance(Round::SIZE);
+    let unclaimed_sol = round_info.lamports() - min_rent;
+    if unclaimed_sol > 0 {
+        round_info.send(unclaimed_sol, archive_info);
+    }
+
+    // Close the round.
+    round_info.close(rent_payer_info)?;
+
+    Ok(())
+}
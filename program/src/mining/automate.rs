@@ -1,8 +1,12 @@
 use ore_api::consts::BOARD_SIZE;
 use ore_api::prelude::*;
+use solana_program::log::sol_log;
 use steel::*;
 
 /// Sets the executor.
+///
+/// Also sets the per-round spend limit and total budget enforced on automated
+/// deploys. Grows a legacy Automation account to the current size.
 pub fn process_automate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse data.
     let args = Automate::try_from_bytes(data)?;
@@ -10,7 +14,16 @@ pub fn process_automate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRes
     let deposit = u64::from_le_bytes(args.deposit);
     let fee = u64::from_le_bytes(args.fee);
     let mask = u64::from_le_bytes(args.mask);
-    let strategy = AutomationStrategy::from_u64(args.strategy as u64);
+    let Ok(strategy) = AutomationStrategy::try_from(args.strategy) else {
+        sol_log("Invalid automation strategy");
+        return Err(ProgramError::InvalidArgument);
+    };
+    if strategy == AutomationStrategy::FixedSquare && (mask & 0xFF) as usize >= BOARD_SIZE {
+        sol_log("Invalid fixed square");
+        return Err(ProgramError::InvalidArgument);
+    }
+    let round_limit = u64::from_le_bytes(args.round_limit);
+    let budget = u64::from_le_bytes(args.budget);
 
     // Load accounts.
     let [signer_info, automation_info, executor_info, miner_info, system_program] = accounts else {
@@ -74,8 +87,26 @@ pub fn process_automate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRes
         let automation = automation_info.as_account_mut::<Automation>(&ore_api::ID)?;
         automation.balance = 0;
         automation.authority = *signer_info.key;
+        automation.spent_round_id = 0;
+        automation.spent_this_round = 0;
         automation
     } else {
+        // Reallocate legacy automation accounts that predate the spend limits.
+        let current_size = automation_info.data_len();
+        if current_size < Automation::SIZE {
+            let rent = solana_program::rent::Rent::get()?;
+            let additional_rent = rent
+                .minimum_balance(Automation::SIZE)
+                .saturating_sub(rent.minimum_balance(current_size));
+            sol_log(&format!(
+                "Reallocation: {} -> {} bytes, additional rent: {} lamports",
+                current_size, Automation::SIZE, additional_rent
+            ));
+            if additional_rent > 0 {
+                automation_info.collect(additional_rent, signer_info)?;
+            }
+            automation_info.realloc(Automation::SIZE, false)?;
+        }
         automation_info
             .as_account_mut::<Automation>(&ore_api::ID)?
             .assert_mut_err(
@@ -91,6 +122,8 @@ pub fn process_automate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRes
     automation.fee = fee;
     automation.mask = mask;
     automation.strategy = strategy as u64;
+    automation.round_limit = round_limit;
+    automation.budget = budget;
 
     // Top up checkpoint fee.
     if miner.checkpoint_fee == 0 {
@@ -3,35 +3,78 @@ use solana_program::{log::sol_log, native_token::lamports_to_sol, rent::Rent};
 use spl_token::amount_to_ui_amount;
 use steel::*;
 
+use super::enter_round;
+use crate::admin::assert_not_paused;
+
 // TODO(#049): Integrate admin fee - tracked in todos/049
 
 /// Checkpoints a miner's rewards.
+///
+/// Any signer may checkpoint, including a delegated automation executor acting
+/// for the authority. Rewards always accrue to the miner account.
+///
+/// An optional trailing stake account for the miner's authority applies the
+/// staker boost to ORE rewards, paid from the motherlode pool. If the miner has
+/// auto-redeploy enabled, an optional round account after it for the current
+/// round receives the winnings as a deployment on the same squares.
 pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
     let clock = Clock::get()?;
-    let [signer_info, board_info, miner_info, round_info, treasury_info, system_program] = accounts
+    let [signer_info, board_info, miner_info, round_info, treasury_info, system_program, config_info, optional_accounts @ ..] =
+        accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_MINING)?;
     let board = board_info.as_account::<Board>(&ore_api::ID)?;
-    let miner = miner_info.as_account_mut::<Miner>(&ore_api::ID)?;
     let treasury = treasury_info.as_account_mut::<Treasury>(&ore_api::ID)?;
     system_program.is_program(&system_program::ID)?;
 
+    // Checkpoint the miner and pay the bot fee, if any.
+    let bot_fee = checkpoint_miner(
+        board,
+        miner_info,
+        round_info,
+        treasury,
+        optional_accounts.first(),
+        optional_accounts.get(1),
+        &clock,
+    )?;
+    if bot_fee > 0 {
+        miner_info.send(bot_fee, &signer_info);
+    }
+    assert_miner_funded(miner_info)?;
+
+    Ok(())
+}
+
+/// Settles a miner's rewards for the round it last played in and returns the bot
+/// fee the caller may collect from the miner account.
+pub(crate) fn checkpoint_miner(
+    board: &Board,
+    miner_info: &AccountInfo<'_>,
+    round_info: &AccountInfo<'_>,
+    treasury: &mut Treasury,
+    stake_info: Option<&AccountInfo<'_>>,
+    next_round_info: Option<&AccountInfo<'_>>,
+    clock: &Clock,
+) -> Result<u64, ProgramError> {
+    let miner = miner_info.as_account_mut::<Miner>(&ore_api::ID)?;
+
     // If miner has already checkpointed this round, return.
     if miner.checkpoint_id == miner.round_id {
-        return Ok(());
+        return Ok(0);
     }
 
     // If round account is empty, verify the correct account was provided.
-    // This can happen if the miner attempted to checkpoint after the round expired and the account was closed.
-    // In this case, the miner forfeits any potential rewards.
+    // The round was either closed after it expired or archived, so the miner must
+    // checkpoint against the round archive instead.
     if round_info.data_is_empty() {
-        sol_log(&format!("Round account is empty").as_str());
+        sol_log(&format!("Round account is empty, checkpoint against the archive").as_str());
         round_info.has_seeds(&[ROUND, &miner.round_id.to_le_bytes()], &ore_api::ID)?;
-        miner.checkpoint_id = miner.round_id;
-        return Ok(());
+        return Ok(0);
     }
 
     // If round is current round, or the miner round ID does not match the provided round, return.
@@ -39,7 +82,7 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
     sol_log(&format!("Round ID: {}", round.id).as_str());
     if round.id == board.round_id || round.id != miner.round_id || round.slot_hash == [0; 32] {
         sol_log(&format!("Round not valid").as_str());
-        return Ok(());
+        return Ok(0);
     }
 
     // Ensure round is not expired.
@@ -47,9 +90,36 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
     if clock.slot >= round.expires_at {
         sol_log(&format!("Round expired").as_str());
         miner.checkpoint_id = miner.round_id;
-        return Ok(());
+        return Ok(0);
     }
 
+    settle_miner(
+        board,
+        miner,
+        round,
+        miner_info,
+        round_info,
+        treasury,
+        stake_info,
+        next_round_info,
+        clock,
+    )
+}
+
+/// Pays a miner's rewards for an unexpired, ended round out of `source_info`, which
+/// holds the round's SOL, and returns the bot fee.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn settle_miner(
+    board: &Board,
+    miner: &mut Miner,
+    round: &mut Round,
+    miner_info: &AccountInfo<'_>,
+    source_info: &AccountInfo<'_>,
+    treasury: &mut Treasury,
+    stake_info: Option<&AccountInfo<'_>>,
+    next_round_info: Option<&AccountInfo<'_>>,
+    clock: &Clock,
+) -> Result<u64, ProgramError> {
     // Calculate bot fee.
     // If the round expires in less than 12h, anyone may checkpoint this account and collect the bot fee.
     let mut bot_fee = 0;
@@ -61,11 +131,14 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
     // Calculate miner rewards.
     let mut rewards_sol = 0;
     let mut rewards_ore = 0;
+    let mut hit_motherlode = false;
 
     // Get the RNG.
     if let Some(r) = round.rng() {
-        // Get the winning square.
-        let winning_square = round.winning_square(r) as usize;
+        // Get the winning square, preferring the result recorded at reset.
+        let winning_square = round
+            .dice_result_square()
+            .unwrap_or_else(|| round.winning_square(r));
 
         // If the miner deployed to the winning square, calculate rewards.
         if miner.deployed[winning_square] > 0 {
@@ -84,8 +157,9 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
             sol_log(&format!("Base rewards: {} SOL", lamports_to_sol(rewards_sol)).as_str());
 
             // Calculate ORE rewards using dice betting mechanic.
-            // The base reward is distributed based on dice prediction accuracy.
-            let base_ore_reward = round.top_miner_reward;
+            // The base reward, less any top miner share, is distributed based on dice
+            // prediction accuracy.
+            let (top_miner_ore, base_ore_reward) = round.top_miner_split();
             let dice_prediction = miner.dice_prediction;
             let dice_sum = round.dice_sum;
 
@@ -141,6 +215,25 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
                 );
             }
 
+            // Pay the top miner's share if this miner holds the sampled position.
+            if top_miner_ore > 0 {
+                let top_miner_sample = round.top_miner_sample(r, winning_square);
+                let start = miner.cumulative[winning_square];
+                if top_miner_sample >= start
+                    && top_miner_sample < start + miner.deployed[winning_square]
+                {
+                    round.top_miner = miner.authority;
+                    rewards_ore += top_miner_ore;
+                    sol_log(
+                        &format!(
+                            "Top miner rewards: {} ORE",
+                            amount_to_ui_amount(top_miner_ore, TOKEN_DECIMALS)
+                        )
+                        .as_str(),
+                    );
+                }
+            }
+
             // Calculate motherlode rewards.
             if round.motherlode > 0 {
                 let motherload_rewards =
@@ -154,6 +247,28 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
                     .as_str(),
                 );
                 rewards_ore += motherload_rewards;
+                hit_motherlode = true;
+            }
+
+            // Apply the staker boost, funded from the motherlode pool.
+            if rewards_ore > 0 {
+                if let Some(stake_info) = stake_info {
+                    let boost_bps = staker_boost_bps(stake_info, &miner.authority)?;
+                    let boost_ore = ((rewards_ore as u128 * boost_bps as u128) / 10000) as u64;
+                    let boost_ore = boost_ore.min(treasury.motherlode);
+                    if boost_ore > 0 {
+                        treasury.motherlode -= boost_ore;
+                        rewards_ore += boost_ore;
+                        sol_log(
+                            &format!(
+                                "Staker boost: {} ORE ({} bps)",
+                                amount_to_ui_amount(boost_ore, TOKEN_DECIMALS),
+                                boost_bps
+                            )
+                            .as_str(),
+                        );
+                    }
+                }
             }
         }
     } else {
@@ -170,6 +285,11 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
         rewards_sol = refund_amount;
     }
 
+    // Record lifetime statistics. Refunded rounds do not count as played.
+    if round.rng().is_some() {
+        miner.record_round(round.id, rewards_sol, hit_motherlode);
+    }
+
     // Checkpoint rewards.
     miner.update_rewards(treasury);
 
@@ -177,27 +297,92 @@ pub fn process_checkpoint(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
     miner.checkpoint_id = round.id;
     miner.rewards_ore += rewards_ore;
     miner.lifetime_rewards_ore += rewards_ore;
-    miner.rewards_sol += rewards_sol;
     miner.lifetime_rewards_sol += rewards_sol;
 
+    // Roll winnings into the current round, if enabled. Anything not redeployed
+    // stays claimable.
+    let mut redeployed = 0;
+    if miner.auto_redeploy == 1 && rewards_sol > 0 {
+        if let Some(next_round_info) = next_round_info {
+            redeployed = redeploy_winnings(next_round_info, board, miner, rewards_sol, clock)?;
+            if redeployed > 0 {
+                source_info.send(redeployed, next_round_info);
+            }
+        }
+    }
+    let rewards_sol = rewards_sol - redeployed;
+    miner.rewards_sol += rewards_sol;
+
     // Update treasury.
     treasury.total_unclaimed += rewards_ore;
 
     // Do SOL transfers.
     if rewards_sol > 0 {
-        round_info.send(rewards_sol, &miner_info);
-    }
-    if bot_fee > 0 {
-        miner_info.send(bot_fee, &signer_info);
+        source_info.send(rewards_sol, &miner_info);
     }
 
-    // Assert miner account has sufficient funds for rent and rewards.
-    let account_size = 8 + std::mem::size_of::<Miner>();
-    let required_rent = Rent::get()?.minimum_balance(account_size);
+    Ok(bot_fee)
+}
+
+/// Asserts the miner account has sufficient funds for rent and rewards.
+pub(crate) fn assert_miner_funded(miner_info: &AccountInfo<'_>) -> ProgramResult {
+    let miner = miner_info.as_account::<Miner>(&ore_api::ID)?;
+    let required_rent = Rent::get()?.minimum_balance(Miner::SIZE);
     assert!(
         miner_info.lamports() >= required_rent + miner.checkpoint_fee + miner.rewards_sol,
         "Miner does not have sufficient funds for rent and rewards"
     );
-
     Ok(())
 }
+
+/// Reads the mining boost from the authority's stake account. Missing stakes get no boost.
+fn staker_boost_bps(stake_info: &AccountInfo<'_>, authority: &Pubkey) -> Result<u64, ProgramError> {
+    stake_info.has_seeds(&[STAKE, &authority.to_bytes()], &ore_api::ID)?;
+    if stake_info.data_is_empty() {
+        return Ok(0);
+    }
+    let stake = stake_info.as_account::<Stake>(&ore_api::ID)?;
+    Ok(stake.mining_boost_bps())
+}
+
+/// Deploys `amount` to the current round on the squares the miner played last round.
+/// Returns the amount deployed, which is zero if the round is not open for deploys.
+fn redeploy_winnings(
+    next_round_info: &AccountInfo<'_>,
+    board: &Board,
+    miner: &mut Miner,
+    amount: u64,
+    clock: &Clock,
+) -> Result<u64, ProgramError> {
+    let next_round = next_round_info
+        .is_writable()?
+        .as_account_mut::<Round>(&ore_api::ID)?
+        .assert_mut(|r| r.id == board.round_id)?;
+
+    // Only redeploy into a round that has started and is still taking deploys.
+    if board.end_slot == u64::MAX || clock.slot < board.start_slot || clock.slot >= board.end_slot {
+        sol_log("Current round not open, winnings left claimable");
+        return Ok(0);
+    }
+
+    // Split across last round's squares before the miner is reset.
+    let amounts = miner.redeploy_amounts(amount);
+    enter_round(miner, next_round, miner.dice_prediction);
+
+    let mut total = 0;
+    for (square_id, &amount) in amounts.iter().enumerate() {
+        if amount == 0 {
+            continue;
+        }
+        miner.cumulative[square_id] = next_round.deployed[square_id];
+        miner.deployed[square_id] = amount;
+        next_round.deployed[square_id] += amount;
+        next_round.total_deployed += amount;
+        next_round.count[square_id] += 1;
+        total += amount;
+    }
+
+    sol_log(&format!("Redeployed {} SOL into round #{}", lamports_to_sol(total), next_round.id).as_str());
+
+    Ok(total)
+}
@@ -0,0 +1,89 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::{assert_miner_funded, settle_miner};
+
+/// Checkpoints a miner whose round account has been archived or closed.
+///
+/// The miner proves the winning square's totals against the archive's Merkle root,
+/// and rewards are paid from the SOL held by the archive. If the archive does not
+/// exist, the round was closed after it expired and the miner forfeits any rewards.
+pub fn process_checkpoint_archived(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = CheckpointArchived::try_from_bytes(data)?;
+    let deployed = u64::from_le_bytes(args.deployed);
+    let count = u64::from_le_bytes(args.count);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, board_info, miner_info, round_info, archive_info, treasury_info, system_program, optional_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let board = board_info.as_account::<Board>(&ore_api::ID)?;
+    let miner = miner_info.as_account_mut::<Miner>(&ore_api::ID)?;
+    round_info
+        .is_empty()?
+        .has_seeds(&[ROUND, &miner.round_id.to_le_bytes()], &ore_api::ID)?;
+    archive_info
+        .is_writable()?
+        .has_seeds(&[ROUND_ARCHIVE, &miner.round_id.to_le_bytes()], &ore_api::ID)?;
+    let treasury = treasury_info.as_account_mut::<Treasury>(&ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // If miner has already checkpointed this round, return.
+    if miner.checkpoint_id == miner.round_id {
+        return Ok(());
+    }
+
+    // If neither the round nor its archive exist, the round expired and was closed.
+    // In this case, the miner forfeits any potential rewards.
+    if archive_info.data_is_empty() {
+        sol_log("Round closed without archive");
+        miner.checkpoint_id = miner.round_id;
+        return Ok(());
+    }
+
+    // Ensure round is not expired.
+    // In this case, the miner forfeits any potential rewards.
+    let archive = archive_info.as_account_mut::<RoundArchive>(&ore_api::ID)?;
+    if clock.slot >= archive.expires_at {
+        sol_log("Round expired");
+        miner.checkpoint_id = miner.round_id;
+        return Ok(());
+    }
+
+    // Restore the round with the proven winning square, if any.
+    let mut round = match archive.winning_square() {
+        Some(square) => {
+            if !archive.verify_square(square, deployed, count, &args.proof) {
+                return Err(OreError::InvalidProof.into());
+            }
+            archive.to_round(square, deployed, count)
+        }
+        None => archive.to_round(0, 0, 0),
+    };
+
+    // Checkpoint the miner and pay the bot fee, if any.
+    let bot_fee = settle_miner(
+        board,
+        miner,
+        &mut round,
+        miner_info,
+        archive_info,
+        treasury,
+        optional_accounts.first(),
+        None,
+        &clock,
+    )?;
+    archive.top_miner = round.top_miner;
+    if bot_fee > 0 {
+        miner_info.send(bot_fee, signer_info);
+    }
+    assert_miner_funded(miner_info)?;
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use ore_api::prelude::*;
+use solana_program::{log::sol_log, native_token::lamports_to_sol};
+use steel::*;
+
+use super::{assert_miner_funded, checkpoint_miner};
+
+/// Checkpoints many miners from one round in a single instruction.
+///
+/// Miners are passed as trailing (miner, stake) account pairs, where the stake is the
+/// miner authority's stake PDA used for the staker boost. Miners from other rounds,
+/// or already checkpointed, are skipped. Winnings are never auto-redeployed here;
+/// they stay claimable. Bot fees from every miner are paid out to the signer together.
+pub fn process_checkpoint_batch(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    CheckpointBatch::try_from_bytes(data)?;
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, board_info, round_info, treasury_info, system_program, miner_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let board = board_info.as_account::<Board>(&ore_api::ID)?;
+    let round_id = round_info.as_account::<Round>(&ore_api::ID)?.id;
+    let treasury = treasury_info.as_account_mut::<Treasury>(&ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+    if miner_accounts.is_empty() || miner_accounts.len() % 2 != 0 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    // Checkpoint each miner, collecting bot fees.
+    let mut total_bot_fee = 0;
+    let mut num_checkpointed = 0;
+    for pair in miner_accounts.chunks_exact(2) {
+        let [miner_info, stake_info] = pair else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let miner = miner_info.as_account::<Miner>(&ore_api::ID)?;
+        if miner.round_id != round_id || miner.checkpoint_id == miner.round_id {
+            continue;
+        }
+
+        let bot_fee = checkpoint_miner(
+            board,
+            miner_info,
+            round_info,
+            treasury,
+            Some(stake_info),
+            None,
+            &clock,
+        )?;
+        if bot_fee > 0 {
+            miner_info.send(bot_fee, signer_info);
+            total_bot_fee += bot_fee;
+        }
+        assert_miner_funded(miner_info)?;
+        num_checkpointed += 1;
+    }
+
+    sol_log(
+        &format!(
+            "Checkpointed {} miners, bot fee: {} SOL",
+            num_checkpointed,
+            lamports_to_sol(total_bot_fee)
+        )
+        .as_str(),
+    );
+
+    Ok(())
+}
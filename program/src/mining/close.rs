@@ -2,16 +2,22 @@ use ore_api::prelude::*;
 use solana_program::rent::Rent;
 use steel::*;
 
-/// Closes a round accound, and returns the rent to the rent payer.
+use crate::admin::param_u64;
+
+/// Closes an expired round account, and returns the rent to the rent payer.
+///
+/// Anyone may close an expired round. The closer is paid a small bounty, taken
+/// from the round's unclaimed SOL first and its rent only if that runs short.
+/// The bounty is the `PARAM_ROUND_CLOSE_BOUNTY` param.
 pub fn process_close(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
     let clock = Clock::get()?;
-    let [signer_info, board_info, rent_payer_info, round_info, treasury_info, system_program] =
+    let [signer_info, board_info, rent_payer_info, round_info, treasury_info, system_program, param_info] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
-    signer_info.is_signer()?;
+    signer_info.is_signer()?.is_writable()?;
     let board = board_info.as_account_mut::<Board>(&ore_api::ID)?;
     rent_payer_info.is_writable()?;
     round_info
@@ -22,10 +28,15 @@ pub fn process_close(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
     let treasury = treasury_info.as_account_mut::<Treasury>(&ore_api::ID)?;
     system_program.is_program(&system_program::ID)?;
 
+    // Pay the closer's bounty.
+    let bounty = param_u64(param_info, PARAM_ROUND_CLOSE_BOUNTY)?.min(round_info.lamports());
+    if bounty > 0 {
+        round_info.send(bounty, signer_info);
+    }
+
     // Vault all unclaimed rewards.
-    let size = 8 + std::mem::size_of::<Round>();
-    let min_rent = Rent::get()?.minimum_balance(size);
-    let unclaimed_sol = round_info.lamports() - min_rent;
+    let min_rent = Rent::get()?.minimum_balance(Round::SIZE);
+    let unclaimed_sol = round_info.lamports().saturating_sub(min_rent);
     if unclaimed_sol > 0 {
         round_info.send(unclaimed_sol, treasury_info);
         treasury.balance += unclaimed_sol;
@@ -1,10 +1,18 @@
 use entropy_api::state::Var;
 use ore_api::consts::{BOARD_SIZE, RNG_MINT_ADDRESS, ONE_RNG};
 use ore_api::prelude::*;
-use solana_program::{keccak::hashv, log::sol_log, program::invoke, pubkey};
+use solana_program::{
+    keccak::hashv,
+    log::{sol_log, sol_log_data},
+    program::invoke,
+    pubkey,
+};
 use spl_associated_token_account::get_associated_token_address;
 use steel::*;
 
+use crate::admin::{assert_not_paused, migrate_account};
+use crate::session::validate_session_or_authority;
+
 pub const ORE_VAR_ADDRESS: Pubkey = pubkey!("BWCaDY96Xe4WkFq1M7UiCCRcChsJ3p51L5KrGzhxgm2E");
 
 /// Deploys RNG tokens to prospect on dice combinations.
@@ -23,10 +31,14 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
     }
 
     // Load accounts.
-    // Account layout: [ore accounts (7)] [token accounts (4)] [entropy accounts (2)]
+    // Account layout: [ore accounts (7)] [token accounts (4)] [entropy accounts (2)] [config] [previous round (optional)] [session (optional)]
+    if accounts.len() < 14 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
     let clock = Clock::get()?;
     let (ore_accounts, remaining) = accounts.split_at(7);
-    let (token_accounts, entropy_accounts) = remaining.split_at(4);
+    let (token_accounts, remaining) = remaining.split_at(4);
+    let (entropy_accounts, remaining) = remaining.split_at(2);
 
     sol_log(&format!("Ore accounts: {:?}", ore_accounts.len()).to_string());
     sol_log(&format!("Token accounts: {:?}", token_accounts.len()).to_string());
@@ -44,7 +56,12 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
+    let [config_info, optional_accounts @ ..] = remaining else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
     signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_MINING)?;
     authority_info.is_writable()?;
     automation_info
         .is_writable()?
@@ -71,29 +88,7 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
         .has_address(&get_associated_token_address(round_info.key, &RNG_MINT_ADDRESS))?;
 
     // Wait until first deploy to start round.
-    if board.end_slot == u64::MAX {
-        board.start_slot = clock.slot;
-        board.end_slot = board.start_slot + 150;
-        round.expires_at = board.end_slot + ONE_DAY_SLOTS;
-
-        // Bump var to the next value.
-        let [var_info, entropy_program] = entropy_accounts else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
-        var_info
-            .has_address(&ORE_VAR_ADDRESS)?
-            .as_account::<Var>(&entropy_api::ID)?
-            .assert(|v| v.authority == *board_info.key)?;
-        entropy_program.is_program(&entropy_api::ID)?;
-
-        // Bump var to the next value.
-        invoke_signed(
-            &entropy_api::sdk::next(*board_info.key, *var_info.key, board.end_slot),
-            &[board_info.clone(), var_info.clone()],
-            &ore_api::ID,
-            &[BOARD],
-        )?;
-    }
+    start_round_on_first_deploy(board_info, board, round, entropy_accounts, &clock)?;
 
     // Check if signer is the automation executor.
     let automation = if !automation_info.data_is_empty() {
@@ -108,17 +103,17 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
 
     // Update amount and mask for automation.
     let mut squares = [false; BOARD_SIZE];
+    let mut session = None;
     if let Some(automation) = &automation {
         // Set amount
         amount = automation.amount;
 
         // Set squares
+        let mask_squares = mask_to_squares(automation.mask);
         match AutomationStrategy::from_u64(automation.strategy as u64) {
             AutomationStrategy::Preferred => {
                 // Preferred automation strategy. Use the miner authority's provided mask.
-                for i in 0..BOARD_SIZE {
-                    squares[i] = (automation.mask & (1 << i)) != 0;
-                }
+                squares = mask_squares;
             }
             AutomationStrategy::Random => {
                 // Random automation strategy. Generate a random mask based on number of squares user wants to deploy to.
@@ -126,6 +121,49 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
                 let r = hashv(&[&automation.authority.to_bytes(), &round.id.to_le_bytes()]).0;
                 squares = generate_random_mask(num_squares, &r);
             }
+            AutomationStrategy::FixedSquare => {
+                let square = (automation.mask & 0xFF) as usize;
+                if square < BOARD_SIZE {
+                    squares[square] = true;
+                }
+            }
+            AutomationStrategy::Rotate => {
+                // Pick the next square of the mask, advancing once per round.
+                let selected: Vec<usize> = (0..BOARD_SIZE).filter(|&i| mask_squares[i]).collect();
+                if !selected.is_empty() {
+                    squares[selected[(round.id % selected.len() as u64) as usize]] = true;
+                }
+            }
+            AutomationStrategy::FollowLastWinner => {
+                // The previous round is an optional account after the config.
+                match last_winning_square(optional_accounts.first(), board.round_id)? {
+                    Some(square) => squares[square] = true,
+                    None => squares = mask_squares,
+                }
+            }
+            AutomationStrategy::Spread => {
+                let count = mask_squares.iter().filter(|&&s| s).count() as u64;
+                if count > 0 && automation.amount >= count {
+                    amount = automation.amount / count;
+                    squares = mask_squares;
+                }
+            }
+            AutomationStrategy::Delegated => {
+                // The executor chooses the deploy, within the authority's per-square cap,
+                // and spends from the deploy budget of its Mining session.
+                amount = u64::from_le_bytes(args.amount);
+                if amount == 0 {
+                    sol_log("Delegated deploy amount must be non-zero");
+                    return Err(ProgramError::InvalidArgument);
+                }
+                amount = amount.min(automation.amount);
+                session = delegated_session(
+                    signer_info,
+                    &automation.authority,
+                    optional_accounts.get(1),
+                )?;
+                squares = mask_to_squares(mask);
+            }
         }
     } else {
         // Convert provided 64-bit mask into array of 36 booleans, where each bit in the mask
@@ -137,27 +175,9 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
 
     // Open miner account.
     let miner = if miner_info.data_is_empty() {
-        create_program_account::<Miner>(
-            miner_info,
-            system_program,
-            signer_info,
-            &ore_api::ID,
-            &[MINER, &signer_info.key.to_bytes()],
-        )?;
-        let miner = miner_info.as_account_mut::<Miner>(&ore_api::ID)?;
-        miner.authority = *signer_info.key;
-        miner.deployed = [0; BOARD_SIZE];
-        miner.cumulative = [0; BOARD_SIZE];
-        miner.rewards_sol = 0;
-        miner.rewards_ore = 0;
-        miner.round_id = 0;
-        miner.checkpoint_id = 0;
-        miner.lifetime_rewards_sol = 0;
-        miner.lifetime_rewards_ore = 0;
-        miner.dice_prediction = dice_prediction;
-        miner._padding = [0; 7];
-        miner
+        open_miner(miner_info, system_program, signer_info, signer_info.key, dice_prediction)?
     } else {
+        migrate_account::<Miner>(signer_info, miner_info, system_program)?;
         miner_info
             .as_account_mut::<Miner>(&ore_api::ID)?
             .assert_mut(|m| {
@@ -170,23 +190,7 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
     };
 
     // Reset miner
-    if miner.round_id != round.id {
-        // Assert miner has checkpointed prior round.
-        assert!(
-            miner.checkpoint_id == miner.round_id,
-            "Miner has not checkpointed"
-        );
-
-        // Reset miner for new round.
-        miner.deployed = [0; BOARD_SIZE];
-        miner.cumulative = round.deployed;
-        miner.round_id = round.id;
-        miner.dice_prediction = dice_prediction;
-    } else {
-        // Update dice prediction if miner is already in this round
-        // (allows changing prediction before round ends)
-        miner.dice_prediction = dice_prediction;
-    }
+    enter_round(miner, round, dice_prediction);
 
     // Calculate all deployments.
     let mut total_amount = 0;
@@ -207,6 +211,18 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
             continue;
         }
 
+        // Stop if automation would exceed its budget or per-round limit.
+        if let Some(automation) = &automation {
+            if total_amount + amount + automation.fee > automation.spend_allowance(round.id) {
+                break;
+            }
+            if let Some(session) = &session {
+                if total_amount + amount + automation.fee > session.deploy_budget {
+                    break;
+                }
+            }
+        }
+
         // Record cumulative amount.
         miner.cumulative[square_id] = round.deployed[square_id];
 
@@ -241,13 +257,17 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
         if let Some(automation) = automation {
             // Automation mode: transfer from automation's RNG balance
             automation.balance -= total_amount + automation.fee;
+            automation.record_spend(round.id, total_amount + automation.fee);
+            if let Some(session) = session {
+                session.deploy_budget -= total_amount + automation.fee;
+            }
             // Note: For automation, we need separate RNG token handling
             // For now, automation still uses the old SOL flow
             automation_info.send(total_amount, &round_info);
             automation_info.send(automation.fee, &signer_info);
 
-            // Close automation if balance is less than what's required to deploy 1 square.
-            if automation.balance < automation.amount + automation.fee {
+            // Close automation if balance or budget is less than what's required to deploy 1 square.
+            if automation.balance.min(automation.budget) < automation.amount + automation.fee {
                 automation_info.close(authority_info)?;
             }
         } else {
@@ -283,9 +303,158 @@ pub fn process_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
         .as_str(),
     );
 
+    // Emit event.
+    sol_log_data(&[DeployEvent {
+        disc: OreEvent::Deploy as u64,
+        authority: miner.authority,
+        amount: total_amount,
+        mask: miner.deployed_mask(),
+        round_id: round.id,
+        ts: clock.unix_timestamp,
+    }
+    .to_bytes()]);
+
+    Ok(())
+}
+
+/// Starts the round timer on the first deploy and bumps the entropy var for it.
+pub(crate) fn start_round_on_first_deploy(
+    board_info: &AccountInfo<'_>,
+    board: &mut Board,
+    round: &mut Round,
+    entropy_accounts: &[AccountInfo<'_>],
+    clock: &Clock,
+) -> ProgramResult {
+    if board.end_slot != u64::MAX {
+        return Ok(());
+    }
+    board.start_slot = clock.slot;
+    board.end_slot = board.start_slot + 150;
+    round.expires_at = board.end_slot + ONE_DAY_SLOTS;
+
+    // Bump var to the next value.
+    let [var_info, entropy_program, ..] = entropy_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    var_info
+        .has_address(&ORE_VAR_ADDRESS)?
+        .as_account::<Var>(&entropy_api::ID)?
+        .assert(|v| v.authority == *board_info.key)?;
+    entropy_program.is_program(&entropy_api::ID)?;
+
+    // Bump var to the next value.
+    invoke_signed(
+        &entropy_api::sdk::next(*board_info.key, *var_info.key, board.end_slot),
+        &[board_info.clone(), var_info.clone()],
+        &ore_api::ID,
+        &[BOARD],
+    )?;
+
     Ok(())
 }
 
+/// Opens a new miner account for `authority`, paid for by the signer.
+pub(crate) fn open_miner<'a>(
+    miner_info: &'a AccountInfo<'_>,
+    system_program: &AccountInfo<'_>,
+    signer_info: &AccountInfo<'_>,
+    authority: &Pubkey,
+    dice_prediction: u8,
+) -> Result<&'a mut Miner, ProgramError> {
+    create_program_account::<Miner>(
+        miner_info,
+        system_program,
+        signer_info,
+        &ore_api::ID,
+        &[MINER, &authority.to_bytes()],
+    )?;
+    let miner = miner_info.as_account_mut::<Miner>(&ore_api::ID)?;
+    miner.authority = *authority;
+    miner.deployed = [0; BOARD_SIZE];
+    miner.cumulative = [0; BOARD_SIZE];
+    miner.rewards_sol = 0;
+    miner.rewards_ore = 0;
+    miner.round_id = 0;
+    miner.checkpoint_id = 0;
+    miner.lifetime_rewards_sol = 0;
+    miner.lifetime_rewards_ore = 0;
+    miner.dice_prediction = dice_prediction;
+    miner.auto_redeploy = 0;
+    miner._padding = [0; 6];
+    miner.rounds_played = 0;
+    miner.lifetime_deployed = 0;
+    miner.lifetime_won = 0;
+    miner.motherlodes_hit = 0;
+    miner.best_round_won = 0;
+    miner.best_round_id = 0;
+    Ok(miner)
+}
+
+/// Moves the miner into the current round, resetting its deployments if it was
+/// last active in a prior (checkpointed) round.
+pub(crate) fn enter_round(miner: &mut Miner, round: &Round, dice_prediction: u8) {
+    if miner.round_id != round.id {
+        // Assert miner has checkpointed prior round.
+        assert!(
+            miner.checkpoint_id == miner.round_id,
+            "Miner has not checkpointed"
+        );
+
+        // Reset miner for new round.
+        miner.deployed = [0; BOARD_SIZE];
+        miner.cumulative = round.deployed;
+        miner.round_id = round.id;
+        miner.dice_prediction = dice_prediction;
+    } else {
+        // Update dice prediction if miner is already in this round
+        // (allows changing prediction before round ends)
+        miner.dice_prediction = dice_prediction;
+    }
+}
+
+/// Loads the session a Delegated executor deploys under, or `None` when the
+/// authority signs for itself.
+fn delegated_session<'a>(
+    signer_info: &AccountInfo<'_>,
+    authority: &Pubkey,
+    session_info: Option<&'a AccountInfo<'_>>,
+) -> Result<Option<&'a mut Session>, ProgramError> {
+    let validation =
+        validate_session_or_authority(signer_info, authority, session_info, SessionOperation::Mining)?;
+    match session_info {
+        Some(session_info) if validation.is_delegate => {
+            Ok(Some(session_info.is_writable()?.as_account_mut::<Session>(&ore_api::ID)?))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Converts a 64-bit mask into 36 booleans, where each bit selects a square.
+fn mask_to_squares(mask: u64) -> [bool; BOARD_SIZE] {
+    let mut squares = [false; BOARD_SIZE];
+    for (i, square) in squares.iter_mut().enumerate() {
+        *square = (mask & (1 << i)) != 0;
+    }
+    squares
+}
+
+/// Reads the winning square of the round before `round_id`, if the account was
+/// provided and the round was rolled.
+fn last_winning_square(
+    last_round_info: Option<&AccountInfo<'_>>,
+    round_id: u64,
+) -> Result<Option<usize>, ProgramError> {
+    let Some(last_round_info) = last_round_info else {
+        return Ok(None);
+    };
+    if round_id == 0 || last_round_info.data_is_empty() {
+        return Ok(None);
+    }
+    last_round_info.has_seeds(&[ROUND, &(round_id - 1).to_le_bytes()], &ore_api::ID)?;
+    let last_round = last_round_info.as_account::<Round>(&ore_api::ID)?;
+    Ok(last_round.dice_result_square())
+}
+
 fn generate_random_mask(num_squares: u64, r: &[u8]) -> [bool; BOARD_SIZE] {
     let mut new_mask = [false; BOARD_SIZE];
     let mut selected = 0;
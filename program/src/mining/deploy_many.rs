@@ -0,0 +1,222 @@
+use ore_api::consts::{BOARD_SIZE, ONE_RNG, RNG_MINT_ADDRESS};
+use ore_api::prelude::*;
+use solana_program::{
+    log::{sol_log, sol_log_data},
+    program::invoke,
+};
+use spl_associated_token_account::get_associated_token_address;
+use steel::*;
+
+use super::{enter_round, open_miner, start_round_on_first_deploy};
+use crate::admin::assert_not_paused;
+
+/// Deploys RNG tokens to several squares at once, each with its own amount.
+///
+/// Instruction data is the `DeployMany` args followed by up to `BOARD_SIZE`
+/// `(square, amount)` entries. All deployments are paid with a single RNG
+/// transfer. Squares the miner has already deployed to this round are skipped,
+/// matching `Deploy`.
+pub fn process_deploy_many(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args_len = std::mem::size_of::<DeployMany>();
+    if data.len() < args_len {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (args_data, entries_data) = data.split_at(args_len);
+    let args = DeployMany::try_from_bytes(args_data)?;
+    let amounts = parse_deploy_entries(entries_data)?;
+
+    deploy_amounts(accounts, &amounts, args.dice_prediction, false)
+}
+
+/// Parses trailing `DeployManyEntry` values into an amount per square.
+pub(crate) fn parse_deploy_entries(entries_data: &[u8]) -> Result<[u64; BOARD_SIZE], ProgramError> {
+    let entry_len = std::mem::size_of::<DeployManyEntry>();
+    if entries_data.is_empty()
+        || entries_data.len() % entry_len != 0
+        || entries_data.len() / entry_len > BOARD_SIZE
+    {
+        sol_log("Expected 1 to 36 (square, amount) entries");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Collect amounts per square.
+    let mut amounts = [0u64; BOARD_SIZE];
+    for entry in entries_data.chunks_exact(entry_len) {
+        let square = entry[0] as usize;
+        let amount = u64::from_le_bytes(entry[1..9].try_into().unwrap());
+        if square >= BOARD_SIZE || amount == 0 {
+            sol_log("Invalid square or zero amount");
+            return Err(ProgramError::InvalidArgument);
+        }
+        if amounts[square] > 0 {
+            sol_log("Duplicate square");
+            return Err(ProgramError::InvalidArgument);
+        }
+        amounts[square] = amount;
+    }
+    Ok(amounts)
+}
+
+/// Deploys `amounts[i]` to each square `i` with a nonzero amount, paid with a single
+/// RNG transfer. Squares the miner already holds this round are skipped, or rejected
+/// when `exact` is set so the requested distribution is applied in full or not at all.
+pub(crate) fn deploy_amounts(
+    accounts: &[AccountInfo<'_>],
+    amounts: &[u64; BOARD_SIZE],
+    dice_prediction: u8,
+    exact: bool,
+) -> ProgramResult {
+    // Validate dice prediction (0 = safe mode, 2-12 = valid prediction).
+    if dice_prediction != 0 && (dice_prediction < 2 || dice_prediction > 12) {
+        sol_log("Invalid dice prediction. Must be 0 (safe) or 2-12.");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    // Account layout: [ore accounts (5)] [token accounts (4)] [entropy accounts (2)] [config]
+    if accounts.len() < 12 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let clock = Clock::get()?;
+    let (ore_accounts, remaining) = accounts.split_at(5);
+    let (token_accounts, remaining) = remaining.split_at(4);
+    let (entropy_accounts, remaining) = remaining.split_at(2);
+    let [signer_info, board_info, miner_info, round_info, system_program] = ore_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let [config_info, ..] = remaining else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let [signer_rng_ata_info, round_rng_ata_info, rng_mint_info, token_program_info] =
+        token_accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_MINING)?;
+    let board = board_info
+        .as_account_mut::<Board>(&ore_api::ID)?
+        .assert_mut(|b| clock.slot >= b.start_slot && clock.slot < b.end_slot)?;
+    let round = round_info
+        .as_account_mut::<Round>(&ore_api::ID)?
+        .assert_mut(|r| r.id == board.round_id)?;
+    miner_info
+        .is_writable()?
+        .has_seeds(&[MINER, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+    rng_mint_info.has_address(&RNG_MINT_ADDRESS)?;
+    token_program_info.is_program(&spl_token::ID)?;
+    signer_rng_ata_info
+        .is_writable()?
+        .has_address(&get_associated_token_address(signer_info.key, &RNG_MINT_ADDRESS))?;
+    round_rng_ata_info
+        .is_writable()?
+        .has_address(&get_associated_token_address(round_info.key, &RNG_MINT_ADDRESS))?;
+
+    // Wait until first deploy to start round.
+    start_round_on_first_deploy(board_info, board, round, entropy_accounts, &clock)?;
+
+    // Open miner account.
+    let miner = if miner_info.data_is_empty() {
+        open_miner(miner_info, system_program, signer_info, signer_info.key, dice_prediction)?
+    } else {
+        miner_info
+            .as_account_mut::<Miner>(&ore_api::ID)?
+            .assert_mut(|m| m.authority == *signer_info.key)?
+    };
+
+    // Reset miner
+    enter_round(miner, round, dice_prediction);
+
+    // Apply all deployments.
+    let (total_amount, total_squares) = record_deployments(miner, round, amounts, exact)?;
+
+    // Top up checkpoint fee (still requires small SOL for transaction fees).
+    if miner.checkpoint_fee == 0 {
+        miner.checkpoint_fee = CHECKPOINT_FEE;
+        miner_info.collect(CHECKPOINT_FEE, &signer_info)?;
+    }
+
+    // Transfer RNG tokens from signer to round pool in one transfer.
+    if total_amount > 0 {
+        invoke(
+            &spl_token::instruction::transfer(
+                &spl_token::ID,
+                signer_rng_ata_info.key,
+                round_rng_ata_info.key,
+                signer_info.key,
+                &[],
+                total_amount,
+            )?,
+            &[
+                signer_rng_ata_info.clone(),
+                round_rng_ata_info.clone(),
+                signer_info.clone(),
+                token_program_info.clone(),
+            ],
+        )?;
+    }
+
+    // Log deployment
+    sol_log(
+        &format!(
+            "Round #{}: deploying {:.2} RNG to {} squares",
+            round.id,
+            total_amount as f64 / ONE_RNG as f64,
+            total_squares,
+        )
+        .as_str(),
+    );
+
+    // Emit event.
+    sol_log_data(&[DeployEvent {
+        disc: OreEvent::Deploy as u64,
+        authority: miner.authority,
+        amount: total_amount,
+        mask: miner.deployed_mask(),
+        round_id: round.id,
+        ts: clock.unix_timestamp,
+    }
+    .to_bytes()]);
+
+    Ok(())
+}
+
+/// Records `amounts` as the miner's deployments on the round and returns the total
+/// amount and number of squares deployed. Squares the miner already holds are
+/// skipped, or rejected when `exact` is set.
+pub(crate) fn record_deployments(
+    miner: &mut Miner,
+    round: &mut Round,
+    amounts: &[u64; BOARD_SIZE],
+    exact: bool,
+) -> Result<(u64, u64), ProgramError> {
+    let mut total_amount: u64 = 0;
+    let mut total_squares = 0;
+    for (square_id, &amount) in amounts.iter().enumerate() {
+        if amount == 0 {
+            continue;
+        }
+        if miner.deployed[square_id] > 0 {
+            if exact {
+                sol_log(&format!("Already deployed to square {}", square_id));
+                return Err(ProgramError::InvalidArgument);
+            }
+            continue;
+        }
+
+        miner.cumulative[square_id] = round.deployed[square_id];
+        miner.deployed[square_id] = amount;
+        round.deployed[square_id] += amount;
+        round.total_deployed += amount;
+        round.count[square_id] += 1;
+
+        total_amount = total_amount
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        total_squares += 1;
+    }
+    Ok((total_amount, total_squares))
+}
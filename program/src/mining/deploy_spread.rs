@@ -0,0 +1,32 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+use super::deploy_amounts;
+
+/// Spreads a total RNG amount evenly across a preset set of squares.
+///
+/// The whole split is applied or the instruction fails, so the miner never ends
+/// up holding only part of the preset.
+pub fn process_deploy_spread(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = DeploySpread::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+    let Ok(preset) = DeployPreset::try_from(args.preset) else {
+        sol_log("Invalid deploy preset");
+        return Err(ProgramError::InvalidArgument);
+    };
+    if amount == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Every square in the preset must receive a nonzero amount.
+    let amounts = preset.split(amount);
+    let squares = preset.squares();
+    if squares.iter().zip(amounts.iter()).any(|(&s, &a)| s && a == 0) {
+        sol_log("Amount too small to cover every square in the preset");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    deploy_amounts(accounts, &amounts, args.dice_prediction, true)
+}
@@ -0,0 +1,145 @@
+use ore_api::consts::{BOARD_SIZE, RNG_MINT_ADDRESS};
+use ore_api::prelude::*;
+use solana_program::{log::sol_log, program::invoke};
+use spl_associated_token_account::get_associated_token_address;
+use steel::*;
+
+use super::deploy_amounts;
+use crate::admin::assert_not_paused;
+
+/// Swaps SOL for RNG through the configured swap program and deploys the RNG received,
+/// so new miners do not need to hold RNG first.
+///
+/// The swap is fenced in like `RouteSwap`: the program must match `config.swap_program`,
+/// the route may not call back into this program, and only the signer signs it. The
+/// swap may take at most `sol_amount` lamports from the signer and must leave at least
+/// `min_rng_out` more RNG in the signer's RNG account.
+///
+/// The RNG received is split evenly across the selected squares, with any remainder
+/// going to the lowest squares. Squares the miner already holds this round are skipped,
+/// and their share stays in the signer's RNG account.
+///
+/// Account layout:
+/// 0-11: deploy accounts, as for `DeployMany`, ending with the config
+/// 12: swap_program
+/// 13..: the swap program's accounts
+pub fn process_deploy_with_sol(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data. Anything after the args is forwarded to the swap program.
+    if data.len() < std::mem::size_of::<DeployWithSol>() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (args_data, swap_data) = data.split_at(std::mem::size_of::<DeployWithSol>());
+    let args = DeployWithSol::try_from_bytes(args_data)?;
+    let sol_amount = u64::from_le_bytes(args.sol_amount);
+    let min_rng_out = u64::from_le_bytes(args.min_rng_out);
+    let mask = u64::from_le_bytes(args.squares);
+
+    sol_log(&format!(
+        "DeployWithSol: sol_in={}, min_rng_out={}, squares={:#x}",
+        sol_amount, min_rng_out, mask
+    ));
+
+    // Validate amounts and squares.
+    if sol_amount == 0 {
+        sol_log("SOL amount must be greater than 0");
+        return Err(ProgramError::InvalidArgument);
+    }
+    let squares: Vec<usize> = (0..BOARD_SIZE).filter(|&i| mask & (1 << i) != 0).collect();
+    if squares.is_empty() {
+        sol_log("No squares selected");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    if accounts.len() < 13 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let (deploy_accounts, remaining) = accounts.split_at(12);
+    let (route_accounts, swap_accounts) = remaining.split_at(1);
+    let [swap_program_info] = route_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let signer_info = &deploy_accounts[0];
+    let config_info = &deploy_accounts[11];
+    let signer_rng_ata = &deploy_accounts[5];
+    signer_info.is_signer()?;
+    signer_rng_ata
+        .is_writable()?
+        .has_address(&get_associated_token_address(signer_info.key, &RNG_MINT_ADDRESS))?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_EXCHANGE | PAUSE_MINING)?;
+    if config.swap_program == Pubkey::default() || *swap_program_info.key != config.swap_program {
+        return Err(OreError::InvalidAccount.into());
+    }
+    swap_program_info.is_executable()?;
+
+    // The route may not call back into this program.
+    if swap_accounts.iter().any(|acc| *acc.key == ore_api::ID) {
+        return Err(OreError::InvalidAccount.into());
+    }
+
+    // Record pre-swap balances. The route may open the RNG account itself.
+    let pre_swap_rng_balance = rng_balance(signer_info, signer_rng_ata)?;
+    let pre_swap_lamports = signer_info.lamports();
+
+    // Build swap accounts. Only the signer signs.
+    let metas: Vec<AccountMeta> = swap_accounts
+        .iter()
+        .map(|acc| AccountMeta {
+            pubkey: *acc.key,
+            is_signer: acc.key == signer_info.key,
+            is_writable: acc.is_writable,
+        })
+        .collect();
+    let mut account_infos: Vec<AccountInfo> = swap_accounts.to_vec();
+    account_infos.push(swap_program_info.clone());
+
+    // Invoke swap program.
+    invoke(
+        &Instruction {
+            program_id: config.swap_program,
+            accounts: metas,
+            data: swap_data.to_vec(),
+        },
+        &account_infos,
+    )?;
+
+    // Check the swap stayed within the requested bounds.
+    let sol_spent = pre_swap_lamports.saturating_sub(signer_info.lamports());
+    let rng_out = rng_balance(signer_info, signer_rng_ata)?
+        .checked_sub(pre_swap_rng_balance)
+        .ok_or(OreError::ArithmeticOverflow)?;
+    if sol_spent > sol_amount {
+        sol_log(&format!("Swap spent {} > {}", sol_spent, sol_amount));
+        return Err(OreError::SlippageExceeded.into());
+    }
+    if rng_out < min_rng_out {
+        sol_log(&format!("Swap received {} < {}", rng_out, min_rng_out));
+        return Err(OreError::SlippageExceeded.into());
+    }
+    if rng_out < squares.len() as u64 {
+        sol_log("Swap output too small to cover every square");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Split the output across the selected squares.
+    let share = rng_out / squares.len() as u64;
+    let remainder = (rng_out % squares.len() as u64) as usize;
+    let mut amounts = [0u64; BOARD_SIZE];
+    for (i, &square) in squares.iter().enumerate() {
+        amounts[square] = share + u64::from(i < remainder);
+    }
+
+    // Deploy.
+    deploy_amounts(deploy_accounts, &amounts, args.dice_prediction, false)
+}
+
+/// The signer's RNG balance, or zero if its RNG account does not exist yet.
+fn rng_balance(signer_info: &AccountInfo<'_>, signer_rng_ata: &AccountInfo<'_>) -> Result<u64, ProgramError> {
+    if signer_rng_ata.data_is_empty() {
+        return Ok(0);
+    }
+    Ok(signer_rng_ata
+        .as_associated_token_account(signer_info.key, &RNG_MINT_ADDRESS)?
+        .amount())
+}
@@ -1,17 +1,31 @@
 //! Mining module - ORE mining functionality
 
 mod deploy;
+mod deploy_many;
+mod deploy_spread;
+mod deploy_with_sol;
 mod checkpoint;
+mod checkpoint_batch;
+mod checkpoint_archived;
 mod reset;
 mod automate;
 mod log;
 mod close;
+mod archive_round;
 mod recycle_sol;
+mod set_auto_redeploy;
 
 pub use deploy::*;
+pub use deploy_many::*;
+pub use deploy_spread::*;
+pub use deploy_with_sol::*;
 pub use checkpoint::*;
+pub use checkpoint_batch::*;
+pub use checkpoint_archived::*;
 pub use reset::*;
 pub use automate::*;
 pub use log::*;
 pub use close::*;
+pub use archive_round::*;
 pub use recycle_sol::*;
+pub use set_auto_redeploy::*;
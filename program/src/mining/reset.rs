@@ -1,19 +1,24 @@
 use entropy_api::state::Var;
 use ore_api::consts::BOARD_SIZE;
 use ore_api::prelude::*;
-use solana_program::{keccak, log::sol_log};
+use solana_program::{keccak, log::{sol_log, sol_log_data}};
 use steel::*;
 
+use crate::admin::{assert_not_paused, assert_role, migrate_account, param_u64};
+
 // TODO(#049): Integrate admin fee - tracked in todos/049
 
 /// Pays out the winners and block reward.
 pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
     let clock = Clock::get()?;
-    let (ore_accounts, entropy_accounts) = accounts.split_at(14);
+    if accounts.len() < 19 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let (ore_accounts, entropy_accounts) = accounts.split_at(19);
     sol_log(&format!("Ore accounts: {:?}", ore_accounts.len()).to_string());
     sol_log(&format!("Entropy accounts: {:?}", entropy_accounts.len()).to_string());
-    let [signer_info, board_info, config_info, fee_collector_info, mint_info, round_info, round_next_info, _top_miner_info, treasury_info, treasury_tokens_info, system_program, token_program, ore_program, slot_hashes_sysvar] =
+    let [signer_info, board_info, config_info, fee_collector_info, mint_info, round_info, round_next_info, _top_miner_info, treasury_info, treasury_tokens_info, system_program, token_program, ore_program, slot_hashes_sysvar, roll_proof_info, roles_info, param_info, rolls_param_info, craps_game_info] =
         ore_accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -23,9 +28,14 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
         .as_account_mut::<Board>(&ore_api::ID)?
         .assert_mut(|b| clock.slot >= b.end_slot + INTERMISSION_SLOTS)?;
     let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_MINING)?;
+    if config.round_permission == ROUND_PERMISSION_OPERATORS {
+        assert_role(config, roles_info, signer_info, ROLE_OPERATOR | ROLE_CRANKER, &[])?;
+    }
     fee_collector_info
         .is_writable()?
         .has_address(&config.fee_collector)?;
+    migrate_account::<Round>(signer_info, round_info, system_program)?;
     let round = round_info
         .as_account_mut::<Round>(&ore_api::ID)?
         .assert_mut(|r| r.id == board.round_id)?;
@@ -40,6 +50,20 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
     token_program.is_program(&spl_token::ID)?;
     ore_program.is_program(&ore_api::ID)?;
     slot_hashes_sysvar.is_sysvar(&sysvar::slot_hashes::ID)?;
+    let proof_slot = round.id % ROLL_PROOF_SLOTS;
+    roll_proof_info
+        .is_writable()?
+        .has_seeds(&[ROLL_PROOF, &proof_slot.to_le_bytes()], &ore_api::ID)?;
+    craps_game_info
+        .is_writable()?
+        .has_seeds(&[CRAPS_GAME], &ore_api::ID)?;
+
+    // Pay the resetter's bounty from undistributed revenue.
+    let bounty = param_u64(param_info, PARAM_ROUND_RESET_BOUNTY)?.min(treasury.balance);
+    if bounty > 0 {
+        treasury.balance -= bounty;
+        treasury_info.send(bounty, signer_info);
+    }
 
     // Open next round account.
     create_program_account::<Round>(
@@ -64,10 +88,15 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
     round_next.total_winnings = 0;
     round_next.dice_results = [0; 2];
     round_next.dice_sum = 0;
-    round_next._padding = [0; 5];
+    round_next.winning_square_index = 0;
+    round_next.roll_count = 0;
+    round_next.top_miner_share_pct = 0;
+    round_next._padding = [0; 2];
+    round_next.injected_sub_rolls = [0; MAX_ROLLS_PER_ROUND as usize];
+    round_next._sub_roll_padding = [0; 4];
 
     // Sample random variable
-    let [var_info, entropy_program] = entropy_accounts else {
+    let [var_info, entropy_program, _test_accounts @ ..] = entropy_accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     let var = var_info
@@ -90,8 +119,16 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
     sol_log(&format!("var value: {:?}", value).to_string());
     round.slot_hash = var.value;
 
-    // Exit early if no slot hash was found.
-    let Some(r) = round.rng() else {
+    // In test builds, an admin-injected roll for this round replaces the sampled square.
+    #[cfg(feature = "test-oracle")]
+    let injected = injected_test_roll(_test_accounts, round.id)?;
+    #[cfg(not(feature = "test-oracle"))]
+    let injected: Option<(usize, [u8; MAX_ROLLS_PER_ROUND as usize])> = None;
+    let injected_square = injected.map(|(square, _)| square);
+
+    // Exit early if no slot hash was found and no roll was injected.
+    // An injected roll without a slot hash uses a fixed rng that never hits the motherlode.
+    let Some(r) = round.rng().or(injected_square.map(|_| 1)) else {
         // Slot hash could not be found, refund all SOL.
         round.total_vaulted = 0;
         round.total_winnings = 0;
@@ -117,7 +154,9 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
                 dice_result_1: 0,
                 dice_result_2: 0,
                 dice_sum: 0,
-                _event_padding: [0; 5],
+                split_reward: 0,
+                top_miner_share_pct: 0,
+                _event_padding: [0; 3],
             }
             .to_bytes(),
         )?;
@@ -129,18 +168,46 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
         return Ok(());
     };
 
-    // Calculate dice roll for this round.
-    let (die1, die2, dice_sum) = round.roll_dice(r);
-    round.dice_results = [die1, die2];
-    round.dice_sum = dice_sum;
+    // Get the winning square and record the canonical dice result on the round.
+    // Craps settlement reads this result instead of recomputing it from the slot hash.
+    let winning_square = injected_square.unwrap_or_else(|| round.winning_square(r));
+    round.set_dice_result(winning_square);
+    round.roll_count = param_u64(rolls_param_info, PARAM_ROLLS_PER_ROUND)?
+        .clamp(1, MAX_ROLLS_PER_ROUND as u64) as u8;
+    round.injected_sub_rolls = injected.map_or([0; MAX_ROLLS_PER_ROUND as usize], |(_, s)| s);
+    let [die1, die2] = round.dice_results;
+    let dice_sum = round.dice_sum;
     sol_log(&format!("Dice roll: {} + {} = {}", die1, die2, dice_sum).to_string());
 
+    // Write the roll proof so the result can be audited after the round is closed.
+    // The slot is only created the first time around; later rounds overwrite it.
+    if roll_proof_info.data_is_empty() {
+        create_program_account::<RollProof>(
+            roll_proof_info,
+            system_program,
+            signer_info,
+            &ore_api::ID,
+            &[ROLL_PROOF, &proof_slot.to_le_bytes()],
+        )?;
+    }
+    let roll_proof = roll_proof_info.as_account_mut::<RollProof>(&ore_api::ID)?;
+    roll_proof.round_id = round.id;
+    roll_proof.slot_hash = round.slot_hash;
+    roll_proof.die1_hash = die_domain_hash(&round.slot_hash, DIE1_DOMAIN);
+    roll_proof.die2_hash = die_domain_hash(&round.slot_hash, DIE2_DOMAIN);
+    roll_proof.dice_results = round.dice_results;
+    roll_proof.dice_sum = round.dice_sum;
+    roll_proof.winning_square = round.winning_square_index;
+    roll_proof.is_injected = injected_square.is_some() as u8;
+    roll_proof.roll_count = round.roll_count;
+    roll_proof._padding = [0; 2];
+
+    // Move the craps table through the round's rolls, once for every position.
+    roll_craps_table(signer_info, craps_game_info, system_program, round, clock.unix_timestamp)?;
+
     // Caculate admin fees.
     let total_admin_fee = round.total_deployed / 100;
 
-    // Get the winning square.
-    let winning_square = round.winning_square(r);
-
     // If no one deployed on the winning square, vault all deployed.
     if round.deployed[winning_square] == 0 {
         // Vault all deployed.
@@ -167,7 +234,9 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
                 dice_result_1: die1,
                 dice_result_2: die2,
                 dice_sum,
-                _event_padding: [0; 5],
+                split_reward: 0,
+                top_miner_share_pct: 0,
+                _event_padding: [0; 3],
             }
             .to_bytes(),
         )?;
@@ -216,20 +285,26 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
         &[TREASURY],
     )?;
 
-    // With 1 in 2 odds, split the +1 ORE reward.
-    if round.is_split_reward(r) {
+    // With the configured odds (1 in 2 by default), split the +1 ORE reward. Otherwise
+    // the top miner takes the configured share and the rest is split pro-rata.
+    let split_reward = round.is_split_reward(r, config.split_reward_bps());
+    if split_reward {
         round.top_miner = SPLIT_ADDRESS;
+    } else {
+        round.top_miner_share_pct = config.top_miner_share_pct.min(100) as u8;
     }
 
     // Payout the motherlode if it was activated.
-    if round.did_hit_motherlode(r) {
+    if round.did_hit_motherlode(r, config.motherlode_odds()) {
         round.motherlode = treasury.motherlode;
         treasury.motherlode = 0;
     }
 
-    // Mint +0.2 ORE to the motherlode rewards pool.
+    // Mint the configured funding (+0.2 ORE by default) to the motherlode rewards pool.
     let mint = mint_info.as_mint()?;
-    let motherlode_mint_amount = MAX_SUPPLY.saturating_sub(mint.supply()).min(ONE_ORE / 5);
+    let motherlode_mint_amount = MAX_SUPPLY
+        .saturating_sub(mint.supply())
+        .min(config.motherlode_funding());
     if motherlode_mint_amount > 0 {
         mint_to_signed(
             mint_info,
@@ -274,7 +349,9 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
             dice_result_1: die1,
             dice_result_2: die2,
             dice_sum,
-            _event_padding: [0; 5],
+            split_reward: split_reward as u8,
+            top_miner_share_pct: round.top_miner_share_pct,
+            _event_padding: [0; 3],
         }
         .to_bytes(),
     )?;
@@ -290,3 +367,75 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
 
     Ok(())
 }
+
+/// Reads the admin-injected winning square and sub-rolls for a round from the optional
+/// TestRollOracle account. An oracle that predates sub-rolls injects nothing until the next
+/// `SetTestRoll` grows it.
+#[cfg(feature = "test-oracle")]
+fn injected_test_roll(
+    accounts: &[AccountInfo<'_>],
+    round_id: u64,
+) -> Result<Option<(usize, [u8; MAX_ROLLS_PER_ROUND as usize])>, ProgramError> {
+    let [test_roll_oracle_info, ..] = accounts else {
+        return Ok(None);
+    };
+    test_roll_oracle_info.has_seeds(&[TEST_ROLL_ORACLE], &ore_api::ID)?;
+    if test_roll_oracle_info.data_len() != 8 + std::mem::size_of::<TestRollOracle>() {
+        return Ok(None);
+    }
+    let oracle = test_roll_oracle_info.as_account::<TestRollOracle>(&ore_api::ID)?;
+    let (Some(square), Some(sub_rolls)) =
+        (oracle.roll_for(round_id), oracle.sub_rolls_for(round_id))
+    else {
+        return Ok(None);
+    };
+    sol_log(&format!("TEST MODE: Using injected square {} for round {}", square, round_id).as_str());
+    Ok(Some((square, sub_rolls)))
+}
+
+/// Applies a round's sub-rolls to the craps table in order, recording the phase each was
+/// thrown in for positions to settle against, and emits the table's phase changes.
+/// Nothing happens before the table is opened; a table that predates the phase history
+/// is grown first, at the signer's expense.
+fn roll_craps_table<'a>(
+    signer_info: &AccountInfo<'a>,
+    craps_game_info: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    round: &Round,
+    ts: i64,
+) -> ProgramResult {
+    if craps_game_info.data_is_empty() {
+        return Ok(());
+    }
+    migrate_account::<CrapsGame>(signer_info, craps_game_info, system_program)?;
+    let craps_game = craps_game_info.as_checked_account_mut::<CrapsGame>()?;
+    for index in 0..round.num_rolls() {
+        let Some(square) = round.sub_roll_square(index) else {
+            break;
+        };
+        let dice_sum = (square / 6 + square % 6 + 2) as u8;
+        let phase = craps_game.apply_roll(round.id, index, dice_sum);
+        if craps_game.epoch_id != phase.epoch_id {
+            sol_log_data(&[SevenOutEvent {
+                disc: OreEvent::SevenOut as u64,
+                round_id: round.id,
+                epoch_id: phase.epoch_id,
+                ts,
+                point: phase.point,
+                _event_padding: [0; 7],
+            }
+            .to_bytes()]);
+        } else if phase.point == 0 && craps_game.point != 0 {
+            sol_log_data(&[PointEstablishedEvent {
+                disc: OreEvent::PointEstablished as u64,
+                round_id: round.id,
+                epoch_id: craps_game.epoch_id,
+                ts,
+                point: craps_game.point,
+                _event_padding: [0; 7],
+            }
+            .to_bytes()]);
+        }
+    }
+    Ok(())
+}
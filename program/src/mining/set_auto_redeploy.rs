@@ -0,0 +1,27 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+/// Enables or disables rolling checkpointed winnings into the current round.
+pub fn process_set_auto_redeploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetAutoRedeploy::try_from_bytes(data)?;
+    if args.enabled > 1 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [signer_info, miner_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let miner = miner_info
+        .as_account_mut::<Miner>(&ore_api::ID)?
+        .assert_mut(|m| m.authority == *signer_info.key)?;
+
+    miner.auto_redeploy = args.enabled;
+
+    sol_log(&format!("Auto-redeploy: {}", args.enabled == 1).as_str());
+
+    Ok(())
+}
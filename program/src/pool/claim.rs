@@ -0,0 +1,78 @@
+use ore_api::prelude::*;
+use solana_program::{log::sol_log, native_token::lamports_to_sol};
+use spl_token::amount_to_ui_amount;
+use steel::*;
+
+/// Claims a member's share of harvested pool winnings.
+pub fn process_claim_pool_rewards(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    ClaimPoolRewards::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer_info, pool_info, member_info, mint_info, recipient_info, pool_tokens_info, system_program, token_program, associated_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let pool = pool_info.as_account::<Pool>(&ore_api::ID)?;
+    let member = member_info
+        .as_account_mut::<PoolMember>(&ore_api::ID)?
+        .assert_mut(|m| m.authority == *signer_info.key)?
+        .assert_mut(|m| m.pool == *pool_info.key)?;
+    mint_info.has_address(&MINT_ADDRESS)?.as_mint()?;
+    recipient_info.is_writable()?;
+    pool_tokens_info.is_writable()?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    // Settle rewards.
+    member.update_rewards(pool);
+    let amount_sol = member.rewards_sol;
+    let amount_ore = member.rewards_ore;
+    member.rewards_sol = 0;
+    member.rewards_ore = 0;
+
+    // Pay SOL from the pool account.
+    if amount_sol > 0 {
+        pool_info.send(amount_sol, signer_info);
+    }
+
+    // Pay ORE from the pool's token account.
+    if amount_ore > 0 {
+        pool_tokens_info.as_associated_token_account(pool_info.key, mint_info.key)?;
+        if recipient_info.data_is_empty() {
+            create_associated_token_account(
+                signer_info,
+                signer_info,
+                recipient_info,
+                mint_info,
+                system_program,
+                token_program,
+                associated_token_program,
+            )?;
+        } else {
+            recipient_info.as_associated_token_account(signer_info.key, mint_info.key)?;
+        }
+        transfer_signed(
+            pool_info,
+            pool_tokens_info,
+            recipient_info,
+            token_program,
+            amount_ore,
+            &[POOL, &pool.authority.to_bytes()],
+        )?;
+    }
+
+    sol_log(
+        &format!(
+            "Claimed {} SOL and {} ORE from pool",
+            lamports_to_sol(amount_sol),
+            amount_to_ui_amount(amount_ore, TOKEN_DECIMALS)
+        )
+        .as_str(),
+    );
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use ore_api::consts::{ONE_RNG, RNG_MINT_ADDRESS};
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use spl_associated_token_account::get_associated_token_address;
+use steel::*;
+
+use crate::admin::assert_not_paused;
+use crate::mining::{
+    enter_round, open_miner, parse_deploy_entries, record_deployments, start_round_on_first_deploy,
+};
+
+/// Deploys pooled RNG through the pool's miner account.
+///
+/// Only the pool authority may deploy, and only from the pool's undeployed balance.
+/// Instruction data is the `PoolDeploy` args followed by `(square, amount)` entries,
+/// as in `DeployMany`. The pool may deploy several times within a round, but not to
+/// a new round until the previous round's winnings are harvested.
+pub fn process_pool_deploy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args_len = std::mem::size_of::<PoolDeploy>();
+    if data.len() < args_len {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (args_data, entries_data) = data.split_at(args_len);
+    let args = PoolDeploy::try_from_bytes(args_data)?;
+    let dice_prediction = args.dice_prediction;
+    let amounts = parse_deploy_entries(entries_data)?;
+
+    // Validate dice prediction (0 = safe mode, 2-12 = valid prediction).
+    if dice_prediction != 0 && (dice_prediction < 2 || dice_prediction > 12) {
+        sol_log("Invalid dice prediction. Must be 0 (safe) or 2-12.");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    // Account layout: [ore accounts (6)] [token accounts (4)] [entropy accounts (2)] [config]
+    if accounts.len() < 13 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let clock = Clock::get()?;
+    let (ore_accounts, remaining) = accounts.split_at(6);
+    let (token_accounts, remaining) = remaining.split_at(4);
+    let (entropy_accounts, remaining) = remaining.split_at(2);
+    let [signer_info, pool_info, board_info, miner_info, round_info, system_program] =
+        ore_accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let [pool_rng_info, round_rng_info, rng_mint_info, token_program] = token_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let [config_info, ..] = remaining else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_MINING)?;
+    let pool = pool_info
+        .as_account_mut::<Pool>(&ore_api::ID)?
+        .assert_mut_err(
+            |p| p.authority == *signer_info.key,
+            OreError::InvalidAuthority.into(),
+        )?;
+    let board = board_info
+        .as_account_mut::<Board>(&ore_api::ID)?
+        .assert_mut(|b| clock.slot >= b.start_slot && clock.slot < b.end_slot)?;
+    let round = round_info
+        .as_account_mut::<Round>(&ore_api::ID)?
+        .assert_mut(|r| r.id == board.round_id)?;
+    if pool.pending_harvest == 1 && pool.deployed_round_id != round.id {
+        return Err(OreError::PoolHarvestPending.into());
+    }
+    miner_info
+        .is_writable()?
+        .has_seeds(&[MINER, &pool_info.key.to_bytes()], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+    pool_rng_info
+        .is_writable()?
+        .as_associated_token_account(pool_info.key, &RNG_MINT_ADDRESS)?;
+    round_rng_info
+        .is_writable()?
+        .has_address(&get_associated_token_address(round_info.key, &RNG_MINT_ADDRESS))?;
+    rng_mint_info.has_address(&RNG_MINT_ADDRESS)?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Wait until first deploy to start round.
+    start_round_on_first_deploy(board_info, board, round, entropy_accounts, &clock)?;
+
+    // Open the pool's miner account.
+    let miner = if miner_info.data_is_empty() {
+        open_miner(miner_info, system_program, signer_info, pool_info.key, dice_prediction)?
+    } else {
+        miner_info
+            .as_account_mut::<Miner>(&ore_api::ID)?
+            .assert_mut(|m| m.authority == *pool_info.key)?
+    };
+
+    // Reset miner
+    enter_round(miner, round, dice_prediction);
+
+    // Apply all deployments from the pool's balance.
+    let (total_amount, total_squares) = record_deployments(miner, round, &amounts, false)?;
+    if total_amount > pool.balance {
+        sol_log("Deployment exceeds pool balance");
+        return Err(ProgramError::InsufficientFunds);
+    }
+    pool.balance -= total_amount;
+    pool.lifetime_deployed += total_amount;
+    if total_amount > 0 {
+        pool.deployed_round_id = round.id;
+        pool.pending_harvest = 1;
+    }
+
+    // Top up checkpoint fee. The pool authority pays it.
+    if miner.checkpoint_fee == 0 {
+        miner.checkpoint_fee = CHECKPOINT_FEE;
+        miner_info.collect(CHECKPOINT_FEE, &signer_info)?;
+    }
+
+    // Transfer RNG tokens from the pool to the round.
+    if total_amount > 0 {
+        transfer_signed(
+            pool_info,
+            pool_rng_info,
+            round_rng_info,
+            token_program,
+            total_amount,
+            &[POOL, &pool.authority.to_bytes()],
+        )?;
+    }
+
+    // Log deployment
+    sol_log(
+        &format!(
+            "Round #{}: pool deploying {:.2} RNG to {} squares",
+            round.id,
+            total_amount as f64 / ONE_RNG as f64,
+            total_squares,
+        )
+        .as_str(),
+    );
+
+    Ok(())
+}
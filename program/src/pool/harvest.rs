@@ -0,0 +1,88 @@
+use ore_api::prelude::*;
+use solana_program::{log::sol_log, native_token::lamports_to_sol};
+use spl_token::amount_to_ui_amount;
+use steel::*;
+
+/// Collects the pool miner's checkpointed winnings into the pool and distributes
+/// them to members pro-rata by shares. Anyone may harvest.
+///
+/// The round pays the pool's winning principal back in SOL with the winnings, so it is
+/// distributed with them. The pool's RNG balance is not credited, since shares are
+/// priced on the undeployed balance only.
+pub fn process_harvest_pool(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    HarvestPool::try_from_bytes(data)?;
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, pool_info, miner_info, mint_info, pool_tokens_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let pool = pool_info
+        .as_account_mut::<Pool>(&ore_api::ID)?
+        .assert_mut(|p| p.pending_harvest == 1)?;
+    let miner = miner_info
+        .as_account_mut::<Miner>(&ore_api::ID)?
+        .assert_mut(|m| m.authority == *pool_info.key)?;
+    if miner.round_id != pool.deployed_round_id || miner.checkpoint_id != miner.round_id {
+        sol_log("Pool miner has not checkpointed its last round");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    mint_info.has_address(&MINT_ADDRESS)?.as_mint()?;
+    pool_tokens_info.is_writable()?;
+    let treasury = treasury_info.as_account_mut::<Treasury>(&ore_api::ID)?;
+    treasury_tokens_info.as_associated_token_account(treasury_info.key, mint_info.key)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    // Open pool ORE account.
+    if pool_tokens_info.data_is_empty() {
+        create_associated_token_account(
+            signer_info,
+            pool_info,
+            pool_tokens_info,
+            mint_info,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+    } else {
+        pool_tokens_info.as_associated_token_account(pool_info.key, mint_info.key)?;
+    }
+
+    // Claim the miner's rewards into the pool.
+    let amount_sol = miner.claim_sol(&clock);
+    let amount_ore = miner.claim_ore(&clock, treasury);
+    if amount_sol > 0 {
+        miner_info.send(amount_sol, pool_info);
+    }
+    if amount_ore > 0 {
+        transfer_signed(
+            treasury_info,
+            treasury_tokens_info,
+            pool_tokens_info,
+            token_program,
+            amount_ore,
+            &[TREASURY],
+        )?;
+    }
+
+    // Distribute to members.
+    pool.distribute(amount_sol, amount_ore);
+    pool.pending_harvest = 0;
+
+    sol_log(
+        &format!(
+            "Harvested {} SOL and {} ORE for pool",
+            lamports_to_sol(amount_sol),
+            amount_to_ui_amount(amount_ore, TOKEN_DECIMALS)
+        )
+        .as_str(),
+    );
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use ore_api::consts::RNG_MINT_ADDRESS;
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use spl_token::amount_to_ui_amount;
+use steel::*;
+
+/// Deposits RNG into a pool in exchange for shares.
+///
+/// Members can only join while the pool has no unharvested deployment, so a new
+/// member cannot buy into winnings from a round they did not fund.
+pub fn process_join_pool(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = JoinPool::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+    if amount == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Load accounts.
+    let [signer_info, pool_info, member_info, signer_rng_info, pool_rng_info, rng_mint_info, system_program, token_program, associated_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let pool = pool_info
+        .as_account_mut::<Pool>(&ore_api::ID)?
+        .assert_mut_err(
+            |p| p.pending_harvest == 0,
+            OreError::PoolHarvestPending.into(),
+        )?;
+    member_info.is_writable()?.has_seeds(
+        &[POOL_MEMBER, &pool_info.key.to_bytes(), &signer_info.key.to_bytes()],
+        &ore_api::ID,
+    )?;
+    signer_rng_info
+        .is_writable()?
+        .as_associated_token_account(signer_info.key, &RNG_MINT_ADDRESS)?;
+    pool_rng_info.is_writable()?;
+    rng_mint_info.has_address(&RNG_MINT_ADDRESS)?.as_mint()?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    // Open pool RNG account.
+    if pool_rng_info.data_is_empty() {
+        create_associated_token_account(
+            signer_info,
+            pool_info,
+            pool_rng_info,
+            rng_mint_info,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+    } else {
+        pool_rng_info.as_associated_token_account(pool_info.key, rng_mint_info.key)?;
+    }
+
+    // Open member account.
+    let member = if member_info.data_is_empty() {
+        create_program_account::<PoolMember>(
+            member_info,
+            system_program,
+            signer_info,
+            &ore_api::ID,
+            &[POOL_MEMBER, &pool_info.key.to_bytes(), &signer_info.key.to_bytes()],
+        )?;
+        let member = member_info.as_account_mut::<PoolMember>(&ore_api::ID)?;
+        member.authority = *signer_info.key;
+        member.pool = *pool_info.key;
+        member.shares = 0;
+        member.sol_rewards_factor = pool.sol_rewards_factor;
+        member.ore_rewards_factor = pool.ore_rewards_factor;
+        member.rewards_sol = 0;
+        member.rewards_ore = 0;
+        member
+    } else {
+        member_info
+            .as_account_mut::<PoolMember>(&ore_api::ID)?
+            .assert_mut(|m| m.authority == *signer_info.key)?
+    };
+
+    // Issue shares.
+    let Some(shares) = pool.deposit(member, amount) else {
+        sol_log("Pool balance is depleted");
+        return Err(ProgramError::InsufficientFunds);
+    };
+
+    // Transfer RNG to the pool.
+    transfer(
+        signer_info,
+        signer_rng_info,
+        pool_rng_info,
+        token_program,
+        amount,
+    )?;
+
+    sol_log(
+        &format!(
+            "Joined pool with {} RNG for {} shares",
+            amount_to_ui_amount(amount, TOKEN_DECIMALS),
+            shares
+        )
+        .as_str(),
+    );
+
+    Ok(())
+}
+
+/// Burns pool shares for their portion of the pool's undeployed RNG.
+///
+/// Winnings earned on the burned shares stay claimable on the member account.
+pub fn process_leave_pool(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = LeavePool::try_from_bytes(data)?;
+    let shares = u64::from_le_bytes(args.shares);
+
+    // Load accounts.
+    let [signer_info, pool_info, member_info, signer_rng_info, pool_rng_info, rng_mint_info, system_program, token_program, associated_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let pool = pool_info
+        .as_account_mut::<Pool>(&ore_api::ID)?
+        .assert_mut_err(
+            |p| p.pending_harvest == 0,
+            OreError::PoolHarvestPending.into(),
+        )?;
+    let member = member_info
+        .as_account_mut::<PoolMember>(&ore_api::ID)?
+        .assert_mut(|m| m.authority == *signer_info.key)?
+        .assert_mut(|m| m.pool == *pool_info.key)?;
+    signer_rng_info.is_writable()?;
+    pool_rng_info
+        .is_writable()?
+        .as_associated_token_account(pool_info.key, &RNG_MINT_ADDRESS)?;
+    rng_mint_info.has_address(&RNG_MINT_ADDRESS)?.as_mint()?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    // Open recipient.
+    if signer_rng_info.data_is_empty() {
+        create_associated_token_account(
+            signer_info,
+            signer_info,
+            signer_rng_info,
+            rng_mint_info,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+    } else {
+        signer_rng_info.as_associated_token_account(signer_info.key, rng_mint_info.key)?;
+    }
+
+    // Burn shares.
+    let amount = pool.withdraw(member, shares);
+
+    // Transfer RNG to the member.
+    if amount > 0 {
+        transfer_signed(
+            pool_info,
+            pool_rng_info,
+            signer_rng_info,
+            token_program,
+            amount,
+            &[POOL, &pool.authority.to_bytes()],
+        )?;
+    }
+
+    sol_log(
+        &format!(
+            "Left pool with {} RNG",
+            amount_to_ui_amount(amount, TOKEN_DECIMALS)
+        )
+        .as_str(),
+    );
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+//! Mining pool module - shared RNG deploys with pro-rata winnings
+
+mod open;
+mod membership;
+mod claim;
+mod deploy;
+mod harvest;
+
+pub use open::*;
+pub use membership::*;
+pub use claim::*;
+pub use deploy::*;
+pub use harvest::*;
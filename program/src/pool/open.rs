@@ -0,0 +1,44 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+/// Opens a mining pool with the signer as its authority.
+pub fn process_open_pool(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    OpenPool::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer_info, pool_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    pool_info
+        .is_empty()?
+        .is_writable()?
+        .has_seeds(&[POOL, &signer_info.key.to_bytes()], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Open pool account.
+    create_program_account::<Pool>(
+        pool_info,
+        system_program,
+        signer_info,
+        &ore_api::ID,
+        &[POOL, &signer_info.key.to_bytes()],
+    )?;
+    let pool = pool_info.as_account_mut::<Pool>(&ore_api::ID)?;
+    pool.authority = *signer_info.key;
+    pool.total_shares = 0;
+    pool.balance = 0;
+    pool.sol_rewards_factor = Numeric::ZERO;
+    pool.ore_rewards_factor = Numeric::ZERO;
+    pool.deployed_round_id = 0;
+    pool.pending_harvest = 0;
+    pool.lifetime_deployed = 0;
+    pool.lifetime_rewards_sol = 0;
+    pool.lifetime_rewards_ore = 0;
+
+    sol_log("Opened mining pool");
+
+    Ok(())
+}
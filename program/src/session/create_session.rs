@@ -6,7 +6,7 @@ use steel::*;
 pub const MAX_SESSION_DURATION: i64 = 24 * 60 * 60;
 
 /// Creates a session that allows a delegate key to sign on behalf of the user.
-/// The delegate can perform all operations EXCEPT withdrawals.
+/// The delegate can deploy, claim and manage staking yield as allowed, but never withdraw.
 ///
 /// Accounts:
 /// 0. `[signer]` Authority - The user creating the session
@@ -19,6 +19,7 @@ pub fn process_create_session(accounts: &[AccountInfo<'_>], data: &[u8]) -> Prog
     let delegate = Pubkey::new_from_array(args.delegate);
     let duration = i64::from_le_bytes(args.duration);
     let allowed_operations = u64::from_le_bytes(args.allowed_operations);
+    let deploy_budget = u64::from_le_bytes(args.deploy_budget);
 
     // Validate duration (max 24 hours).
     if duration <= 0 || duration > MAX_SESSION_DURATION {
@@ -26,7 +27,13 @@ pub fn process_create_session(accounts: &[AccountInfo<'_>], data: &[u8]) -> Prog
         return Err(ProgramError::InvalidArgument);
     }
 
-    // If allowed_operations is 0, enable all non-withdrawal ops.
+    // Reject operations a session can't grant.
+    if allowed_operations & !Session::all_operations() != 0 {
+        sol_log("Session operation not supported");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // If allowed_operations is 0, enable every operation a session can grant.
     let allowed_ops = if allowed_operations == 0 {
         Session::all_operations()
     } else {
@@ -67,11 +74,12 @@ pub fn process_create_session(accounts: &[AccountInfo<'_>], data: &[u8]) -> Prog
         session.created_at = clock.unix_timestamp;
         session.expires_at = clock.unix_timestamp + duration;
         session.allowed_operations = allowed_ops;
-        session._reserved = [0u8; 32];
+        session.deploy_budget = deploy_budget;
+        session._reserved = [0u8; 24];
 
         sol_log(&format!(
-            "Session created: delegate={}, expires_at={}, ops={}",
-            delegate, session.expires_at, allowed_ops
+            "Session created: delegate={}, expires_at={}, ops={}, deploy_budget={}",
+            delegate, session.expires_at, allowed_ops, deploy_budget
         ));
     } else {
         // Update existing session (must be the authority).
@@ -83,10 +91,11 @@ pub fn process_create_session(accounts: &[AccountInfo<'_>], data: &[u8]) -> Prog
         session.created_at = clock.unix_timestamp;
         session.expires_at = clock.unix_timestamp + duration;
         session.allowed_operations = allowed_ops;
+        session.deploy_budget = deploy_budget;
 
         sol_log(&format!(
-            "Session updated: delegate={}, expires_at={}, ops={}",
-            delegate, session.expires_at, allowed_ops
+            "Session updated: delegate={}, expires_at={}, ops={}, deploy_budget={}",
+            delegate, session.expires_at, allowed_ops, deploy_budget
         ));
     }
 
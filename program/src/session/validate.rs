@@ -92,6 +92,42 @@ pub fn validate_session_or_authority(
     })
 }
 
+/// Validates that a signer may claim or compound yield on a stake account.
+///
+/// Accepts the stake authority, the stake's designated operator, or a session
+/// delegate of the authority with the StakingYield operation. Yield always pays
+/// to the authority, and principal withdrawals never go through this path.
+pub fn validate_stake_yield_signer(
+    signer: &AccountInfo<'_>,
+    stake: &Stake,
+    session_info: Option<&AccountInfo<'_>>,
+) -> Result<SessionValidation, ProgramError> {
+    if signer.is_signer && stake.can_manage_yield(signer.key) {
+        return Ok(SessionValidation {
+            authority: stake.authority,
+            is_delegate: *signer.key != stake.authority,
+        });
+    }
+    validate_session_or_authority(
+        signer,
+        &stake.authority,
+        session_info,
+        SessionOperation::StakingYield,
+    )
+}
+
+/// Validates that a signer may run ClaimORE/ClaimSOL for a miner.
+///
+/// Accepts the miner authority or a session delegate with the Claims operation.
+/// Callers must pay proceeds to the authority's own accounts, never the signer's.
+pub fn validate_claim_signer(
+    signer: &AccountInfo<'_>,
+    miner: &Miner,
+    session_info: Option<&AccountInfo<'_>>,
+) -> Result<SessionValidation, ProgramError> {
+    validate_session_or_authority(signer, &miner.authority, session_info, SessionOperation::Claims)
+}
+
 /// Simpler validation that just checks if signer can act as authority.
 /// Does not require session info - will fail if signer != authority.
 pub fn require_authority(signer: &AccountInfo<'_>, authority: &Pubkey) -> ProgramResult {
@@ -132,7 +168,7 @@ pub fn is_potential_session(account: &AccountInfo<'_>, authority: &Pubkey) -> bo
 //        signer_info,
 //        authority_info.key,
 //        session_info,
-//        SessionOperation::Games, // or Swaps, StakingDeposit, Mining
+//        SessionOperation::Mining, // or StakingYield, Claims
 //    )?;
 //    ```
 //
@@ -1,9 +1,18 @@
 use ore_api::prelude::*;
-use solana_program::log::sol_log;
+use solana_program::{
+    log::{sol_log, sol_log_data},
+    native_token::lamports_to_sol,
+    program::invoke,
+};
 use spl_token::amount_to_ui_amount;
 use steel::*;
 
-/// Claims yield from the staking contract.
+use crate::admin::assert_not_paused;
+use crate::session::validate_stake_yield_signer;
+
+/// Claims yield from the staking contract to the authority's token account.
+/// May be signed by the stake authority, its operator, or a session delegate of the
+/// authority with the StakingYield permission, passing the session account last.
 pub fn process_claim_yield(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse data.
     let args = ClaimYield::try_from_bytes(data)?;
@@ -11,17 +20,18 @@ pub fn process_claim_yield(accounts: &[AccountInfo<'_>], data: &[u8]) -> Program
 
     // Load accounts.
     let clock = Clock::get()?;
-    let [signer_info, mint_info, recipient_info, stake_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program] =
+    let [signer_info, mint_info, recipient_info, stake_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program, config_info, optional_accounts @ ..] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_STAKING)?;
     mint_info.has_address(&MINT_ADDRESS)?.as_mint()?;
     recipient_info.is_writable()?;
-    let stake = stake_info
-        .as_account_mut::<Stake>(&ore_api::ID)?
-        .assert_mut(|s| s.authority == *signer_info.key)?;
+    let stake = stake_info.as_account_mut::<Stake>(&ore_api::ID)?;
+    validate_stake_yield_signer(signer_info, stake, optional_accounts.first())?;
     let treasury = treasury_info.as_account_mut::<Treasury>(&ore_api::ID)?;
     treasury_tokens_info
         .is_writable()?
@@ -30,8 +40,12 @@ pub fn process_claim_yield(accounts: &[AccountInfo<'_>], data: &[u8]) -> Program
     token_program.is_program(&spl_token::ID)?;
     associated_token_program.is_program(&spl_associated_token_account::ID)?;
 
-    // Open recipient token account.
+    // Open recipient token account. Operators and delegates cannot open it for the authority.
     if recipient_info.data_is_empty() {
+        if stake.authority != *signer_info.key {
+            sol_log("Authority token account must exist for operator and delegate claims");
+            return Err(ProgramError::UninitializedAccount);
+        }
         create_associated_token_account(
             signer_info,
             signer_info,
@@ -42,7 +56,7 @@ pub fn process_claim_yield(accounts: &[AccountInfo<'_>], data: &[u8]) -> Program
             associated_token_program,
         )?;
     } else {
-        recipient_info.as_associated_token_account(&signer_info.key, &mint_info.key)?;
+        recipient_info.as_associated_token_account(&stake.authority, &mint_info.key)?;
     }
 
     // Claim yield from stake account.
@@ -66,6 +80,212 @@ pub fn process_claim_yield(accounts: &[AccountInfo<'_>], data: &[u8]) -> Program
         )
         .as_str(),
     );
+    sol_log_data(&[YieldClaimedEvent {
+        disc: OreEvent::YieldClaimed as u64,
+        authority: stake.authority,
+        amount,
+        ts: clock.unix_timestamp,
+        restaked: 0,
+        _event_padding: [0; 7],
+    }
+    .to_bytes()]);
+
+    Ok(())
+}
+
+/// Claims yield and restakes it into the stake balance in a single instruction.
+/// May be signed by the stake authority, its operator, or a session delegate of the
+/// authority with the StakingYield permission, passing the session account last.
+pub fn process_claim_yield_and_restake(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = ClaimYieldAndRestake::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, mint_info, stake_info, stake_tokens_info, treasury_info, treasury_tokens_info, token_program, config_info, optional_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_STAKING)?;
+    mint_info.has_address(&MINT_ADDRESS)?.as_mint()?;
+    let stake = stake_info.as_account_mut::<Stake>(&ore_api::ID)?;
+    validate_stake_yield_signer(signer_info, stake, optional_accounts.first())?;
+    stake_tokens_info
+        .is_writable()?
+        .as_associated_token_account(stake_info.key, mint_info.key)?;
+    let treasury = treasury_info.as_account_mut::<Treasury>(&ore_api::ID)?;
+    treasury_tokens_info
+        .is_writable()?
+        .as_associated_token_account(&treasury_info.key, &mint_info.key)?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Fold yield into the stake balance.
+    let amount = stake.compound(amount, &clock, treasury)?;
+
+    // Move the claimed ORE from the treasury into the stake's token account.
+    transfer_signed(
+        treasury_info,
+        treasury_tokens_info,
+        stake_tokens_info,
+        token_program,
+        amount,
+        &[TREASURY],
+    )?;
+
+    // Log restake.
+    sol_log(
+        &format!(
+            "Restaking {} ORE",
+            amount_to_ui_amount(amount, TOKEN_DECIMALS)
+        )
+        .as_str(),
+    );
+    sol_log_data(&[YieldClaimedEvent {
+        disc: OreEvent::YieldClaimed as u64,
+        authority: stake.authority,
+        amount,
+        ts: clock.unix_timestamp,
+        restaked: 1,
+        _event_padding: [0; 7],
+    }
+    .to_bytes()]);
+
+    // Safety check.
+    let stake_tokens =
+        stake_tokens_info.as_associated_token_account(stake_info.key, mint_info.key)?;
+    assert!(stake_tokens.amount() >= stake.balance);
+
+    Ok(())
+}
+
+/// Claims yield and swaps it for SOL through the configured swap program, so yield can
+/// be taken as gas token directly. Only the stake authority may sign.
+///
+/// The ORE is claimed into the signer's token account, then the swap runs signed by the
+/// signer alone, as in `RouteSwap`. It may spend no more than was claimed and must leave
+/// the signer at least `min_sol_out` lamports better off.
+///
+/// Account layout:
+/// 0-5: signer, mint, recipient, stake, treasury, treasury_tokens
+/// 6: token_program
+/// 7: config
+/// 8: swap_program
+/// 9..: the swap program's accounts
+pub fn process_claim_yield_as_sol(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data. Anything after the args is forwarded to the swap program.
+    if data.len() < std::mem::size_of::<ClaimYieldAsSol>() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (args_data, swap_data) = data.split_at(std::mem::size_of::<ClaimYieldAsSol>());
+    let args = ClaimYieldAsSol::try_from_bytes(args_data)?;
+    let amount = u64::from_le_bytes(args.amount);
+    let min_sol_out = u64::from_le_bytes(args.min_sol_out);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    if accounts.len() < 9 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let (ore_accounts, swap_accounts) = accounts.split_at(9);
+    let [signer_info, mint_info, recipient_info, stake_info, treasury_info, treasury_tokens_info, token_program, config_info, swap_program_info] =
+        ore_accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_STAKING | PAUSE_EXCHANGE)?;
+    mint_info.has_address(&MINT_ADDRESS)?.as_mint()?;
+    let pre_claim_balance = recipient_info
+        .is_writable()?
+        .as_associated_token_account(signer_info.key, mint_info.key)?
+        .amount();
+    let stake = stake_info
+        .as_account_mut::<Stake>(&ore_api::ID)?
+        .assert_mut(|s| s.authority == *signer_info.key)?;
+    let treasury = treasury_info.as_account_mut::<Treasury>(&ore_api::ID)?;
+    treasury_tokens_info
+        .is_writable()?
+        .as_associated_token_account(&treasury_info.key, &mint_info.key)?;
+    token_program.is_program(&spl_token::ID)?;
+    if config.swap_program == Pubkey::default() || *swap_program_info.key != config.swap_program {
+        return Err(OreError::InvalidAccount.into());
+    }
+    swap_program_info.is_executable()?;
+
+    // The route may not call back into this program.
+    if swap_accounts.iter().any(|acc| *acc.key == ore_api::ID) {
+        return Err(OreError::InvalidAccount.into());
+    }
+
+    // Claim yield into the signer's token account.
+    let amount = stake.claim(amount, &clock, treasury);
+    transfer_signed(
+        treasury_info,
+        treasury_tokens_info,
+        recipient_info,
+        token_program,
+        amount,
+        &[TREASURY],
+    )?;
+
+    // Swap it. Only the signer signs.
+    let pre_swap_lamports = signer_info.lamports();
+    let metas: Vec<AccountMeta> = swap_accounts
+        .iter()
+        .map(|acc| AccountMeta {
+            pubkey: *acc.key,
+            is_signer: acc.key == signer_info.key,
+            is_writable: acc.is_writable,
+        })
+        .collect();
+    let mut account_infos: Vec<AccountInfo> = swap_accounts.to_vec();
+    account_infos.push(swap_program_info.clone());
+    invoke(
+        &Instruction {
+            program_id: config.swap_program,
+            accounts: metas,
+            data: swap_data.to_vec(),
+        },
+        &account_infos,
+    )?;
+
+    // Check the swap stayed within the claim and the slippage bound.
+    let post_swap_balance = recipient_info
+        .as_associated_token_account(signer_info.key, mint_info.key)?
+        .amount();
+    if post_swap_balance < pre_claim_balance {
+        sol_log("Swap spent more than was claimed");
+        return Err(OreError::SlippageExceeded.into());
+    }
+    let sol_out = signer_info.lamports().saturating_sub(pre_swap_lamports);
+    if sol_out < min_sol_out {
+        sol_log(&format!("Swap received {} < {}", sol_out, min_sol_out));
+        return Err(OreError::SlippageExceeded.into());
+    }
+
+    // Log claim.
+    sol_log(
+        &format!(
+            "Claiming {} ORE as {} SOL",
+            amount_to_ui_amount(amount, TOKEN_DECIMALS),
+            lamports_to_sol(sol_out)
+        )
+        .as_str(),
+    );
+    sol_log_data(&[YieldClaimedEvent {
+        disc: OreEvent::YieldClaimed as u64,
+        authority: stake.authority,
+        amount,
+        ts: clock.unix_timestamp,
+        restaked: 0,
+        _event_padding: [0; 7],
+    }
+    .to_bytes()]);
 
     Ok(())
 }
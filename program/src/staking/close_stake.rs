@@ -0,0 +1,68 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use solana_program::program::invoke_signed;
+use steel::*;
+
+/// Closes an empty stake account and its token account, returning rent to the authority.
+pub fn process_close_stake(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    CloseStake::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer_info, mint_info, stake_info, stake_tokens_info, treasury_info, system_program, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    mint_info.has_address(&MINT_ADDRESS)?.as_mint()?;
+    let stake = stake_info
+        .is_writable()?
+        .as_account_mut::<Stake>(&ore_api::ID)?
+        .assert_mut(|s| s.authority == *signer_info.key)?;
+    stake_tokens_info.is_writable()?;
+    let treasury = treasury_info.as_account::<Treasury>(&ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Settle rewards so unclaimed yield is not forfeited.
+    stake.update_rewards(treasury);
+    if !stake.is_empty() {
+        sol_log("Stake must have no balance, pending withdrawal, or unclaimed yield");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Close the stake token account, if it was opened.
+    if !stake_tokens_info.data_is_empty() {
+        let stake_tokens =
+            stake_tokens_info.as_associated_token_account(stake_info.key, mint_info.key)?;
+        if stake_tokens.amount() > 0 {
+            sol_log("Stake token account is not empty");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let (_, stake_bump) = stake_pda(*signer_info.key);
+        invoke_signed(
+            &spl_token::instruction::close_account(
+                &spl_token::ID,
+                stake_tokens_info.key,
+                signer_info.key,
+                stake_info.key,
+                &[],
+            )?,
+            &[
+                stake_tokens_info.clone(),
+                signer_info.clone(),
+                stake_info.clone(),
+                token_program.clone(),
+            ],
+            &[&[STAKE, &signer_info.key.to_bytes(), &[stake_bump]]],
+        )?;
+    }
+
+    // Close the stake account.
+    stake_info.close(signer_info)?;
+
+    sol_log("Closed stake account");
+
+    Ok(())
+}
@@ -3,6 +3,8 @@ use solana_program::log::sol_log;
 use spl_token::amount_to_ui_amount;
 use steel::*;
 
+use crate::admin::{assert_not_paused, migrate_account};
+
 /// Deposits ORE into the staking contract.
 pub fn process_deposit(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse data.
@@ -11,7 +13,7 @@ pub fn process_deposit(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
 
     // Load accounts.
     let clock = Clock::get()?;
-    let [signer_info, payer_info, mint_info, sender_info, stake_info, stake_tokens_info, treasury_info, system_program, token_program, associated_token_program] =
+    let [signer_info, payer_info, mint_info, sender_info, stake_info, stake_tokens_info, treasury_info, system_program, token_program, associated_token_program, config_info] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -27,6 +29,8 @@ pub fn process_deposit(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
     system_program.is_program(&system_program::ID)?;
     token_program.is_program(&spl_token::ID)?;
     associated_token_program.is_program(&spl_associated_token_account::ID)?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_STAKING)?;
 
     // Open stake account.
     let stake = if stake_info.data_is_empty() {
@@ -46,8 +50,15 @@ pub fn process_deposit(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
         stake.rewards_factor = treasury.stake_rewards_factor;
         stake.rewards = 0;
         stake.lifetime_rewards = 0;
+        stake.pending_withdrawal = 0;
+        stake.withdraw_unlock_slot = 0;
+        stake.operator = Pubkey::default();
+        stake.receipts_outstanding = 0;
+        stake.rng_balance = 0;
+        stake.boost_weight = 0;
         stake
     } else {
+        migrate_account::<Stake>(payer_info, stake_info, system_program)?;
         stake_info
             .as_account_mut::<Stake>(&ore_api::ID)?
             .assert_mut(|s| s.authority == *signer_info.key)?
@@ -72,7 +83,7 @@ pub fn process_deposit(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
     // assert!(stake.is_seeker == 1, "Only seekers can deposit stake");
 
     // Deposit into stake account.
-    let amount = stake.deposit(amount, &clock, treasury, &sender);
+    let amount = stake.deposit(amount, &clock, treasury, &sender)?;
 
     // Transfer ORE to treasury.
     transfer(
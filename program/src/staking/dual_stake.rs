@@ -0,0 +1,154 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use spl_token::amount_to_ui_amount;
+use steel::*;
+
+use crate::admin::assert_not_paused;
+
+/// Stakes RNG alongside an existing ORE stake for the dual staking boost.
+pub fn process_deposit_rng(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = DepositRng::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, rng_mint_info, sender_info, stake_info, stake_rng_tokens_info, treasury_info, system_program, token_program, associated_token_program, config_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_STAKING)?;
+    rng_mint_info.has_address(&RNG_MINT_ADDRESS)?.as_mint()?;
+    let sender = sender_info
+        .is_writable()?
+        .as_associated_token_account(&signer_info.key, &RNG_MINT_ADDRESS)?;
+    let stake = stake_info
+        .as_account_mut::<Stake>(&ore_api::ID)?
+        .assert_mut(|s| s.authority == *signer_info.key)?;
+    stake_rng_tokens_info.is_writable()?;
+    let treasury = treasury_info.as_account_mut::<Treasury>(&ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    // Create stake RNG tokens account.
+    if stake_rng_tokens_info.data_is_empty() {
+        create_associated_token_account(
+            signer_info,
+            stake_info,
+            stake_rng_tokens_info,
+            rng_mint_info,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+    } else {
+        stake_rng_tokens_info.as_associated_token_account(stake_info.key, rng_mint_info.key)?;
+    }
+
+    // Deposit RNG into stake account.
+    let amount = stake.deposit_rng(amount, &clock, treasury, &sender)?;
+
+    // Transfer RNG to the stake.
+    transfer(
+        signer_info,
+        sender_info,
+        stake_rng_tokens_info,
+        token_program,
+        amount,
+    )?;
+
+    // Log deposit.
+    sol_log(
+        &format!(
+            "Depositing {} RNG, boost weight {} ORE",
+            amount_to_ui_amount(amount, RNG_TOKEN_DECIMALS),
+            amount_to_ui_amount(stake.boost_weight, TOKEN_DECIMALS)
+        )
+        .as_str(),
+    );
+
+    // Safety check.
+    let stake_rng_tokens =
+        stake_rng_tokens_info.as_associated_token_account(stake_info.key, rng_mint_info.key)?;
+    assert!(stake_rng_tokens.amount() >= stake.rng_balance);
+
+    Ok(())
+}
+
+/// Unstakes RNG from a dual stake, reducing its boost.
+pub fn process_withdraw_rng(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = WithdrawRng::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, rng_mint_info, recipient_info, stake_info, stake_rng_tokens_info, treasury_info, system_program, token_program, associated_token_program, config_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_STAKING)?;
+    rng_mint_info.has_address(&RNG_MINT_ADDRESS)?.as_mint()?;
+    recipient_info.is_writable()?;
+    let stake = stake_info
+        .as_account_mut::<Stake>(&ore_api::ID)?
+        .assert_mut(|s| s.authority == *signer_info.key)?;
+    stake_rng_tokens_info
+        .is_writable()?
+        .as_associated_token_account(stake_info.key, rng_mint_info.key)?;
+    let treasury = treasury_info.as_account_mut::<Treasury>(&ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    // Open recipient token account.
+    if recipient_info.data_is_empty() {
+        create_associated_token_account(
+            signer_info,
+            signer_info,
+            recipient_info,
+            rng_mint_info,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+    } else {
+        recipient_info.as_associated_token_account(&signer_info.key, &rng_mint_info.key)?;
+    }
+
+    // Withdraw RNG from stake account.
+    let amount = stake.withdraw_rng(amount, &clock, treasury)?;
+
+    // Transfer RNG to recipient.
+    transfer_signed(
+        stake_info,
+        stake_rng_tokens_info,
+        recipient_info,
+        token_program,
+        amount,
+        &[STAKE, &stake.authority.to_bytes()],
+    )?;
+
+    // Log withdraw.
+    sol_log(
+        &format!(
+            "Withdrawing {} RNG",
+            amount_to_ui_amount(amount, RNG_TOKEN_DECIMALS)
+        )
+        .as_str(),
+    );
+
+    // Safety check.
+    let stake_rng_tokens =
+        stake_rng_tokens_info.as_associated_token_account(stake_info.key, rng_mint_info.key)?;
+    assert!(stake_rng_tokens.amount() >= stake.rng_balance);
+
+    Ok(())
+}
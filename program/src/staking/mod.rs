@@ -3,7 +3,15 @@
 mod deposit;
 mod withdraw;
 mod claim_yield;
+mod set_operator;
+mod receipt;
+mod dual_stake;
+mod close_stake;
 
 pub use deposit::*;
 pub use withdraw::*;
 pub use claim_yield::*;
+pub use set_operator::*;
+pub use receipt::*;
+pub use dual_stake::*;
+pub use close_stake::*;
@@ -0,0 +1,241 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use solana_program::program::{invoke, invoke_signed};
+use spl_token::amount_to_ui_amount;
+use steel::*;
+
+use crate::admin::assert_not_paused;
+
+/// Mints receipt tokens representing part of a stake balance.
+///
+/// Receipts are transferable SPL tokens, 1:1 with staked ORE. The tokenized
+/// portion of the balance keeps earning yield for this stake but cannot be
+/// withdrawn until receipts are burned through RedeemStakeReceipt.
+pub fn process_mint_stake_receipt(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = MintStakeReceipt::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let [signer_info, stake_info, receipt_mint_info, receipt_tokens_info, treasury_info, system_program, token_program, associated_token_program, config_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_STAKING)?;
+    let stake = stake_info
+        .as_account_mut::<Stake>(&ore_api::ID)?
+        .assert_mut(|s| s.authority == *signer_info.key)?;
+    receipt_mint_info
+        .is_writable()?
+        .has_seeds(&[STAKE_RECEIPT_MINT], &ore_api::ID)?;
+    receipt_tokens_info.is_writable()?;
+    treasury_info.has_seeds(&[TREASURY], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    // Create the receipt mint on first use. The treasury is the mint authority.
+    if receipt_mint_info.data_is_empty() {
+        let (_, receipt_mint_bump) = stake_receipt_mint_pda();
+        let receipt_mint_seeds = &[STAKE_RECEIPT_MINT, &[receipt_mint_bump]];
+        invoke_signed(
+            &solana_program::system_instruction::create_account(
+                signer_info.key,
+                receipt_mint_info.key,
+                solana_program::rent::Rent::get()?.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            &[
+                signer_info.clone(),
+                receipt_mint_info.clone(),
+                system_program.clone(),
+            ],
+            &[receipt_mint_seeds],
+        )?;
+        invoke(
+            &spl_token::instruction::initialize_mint2(
+                &spl_token::ID,
+                receipt_mint_info.key,
+                treasury_info.key,
+                None,
+                TOKEN_DECIMALS,
+            )?,
+            &[receipt_mint_info.clone()],
+        )?;
+        sol_log("Created stake receipt mint");
+    }
+    receipt_mint_info.as_mint()?;
+
+    // Open receipt token account.
+    if receipt_tokens_info.data_is_empty() {
+        create_associated_token_account(
+            signer_info,
+            signer_info,
+            receipt_tokens_info,
+            receipt_mint_info,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+    } else {
+        receipt_tokens_info.as_associated_token_account(signer_info.key, receipt_mint_info.key)?;
+    }
+
+    // Tokenize stake balance.
+    let amount = stake.tokenize(amount);
+    if amount == 0 {
+        sol_log("No withdrawable balance to tokenize");
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    // Mint receipts to the signer.
+    mint_to_signed(
+        receipt_mint_info,
+        receipt_tokens_info,
+        treasury_info,
+        token_program,
+        amount,
+        &[TREASURY],
+    )?;
+
+    // Log mint.
+    sol_log(
+        &format!(
+            "Minted {} stake receipts",
+            amount_to_ui_amount(amount, TOKEN_DECIMALS)
+        )
+        .as_str(),
+    );
+
+    Ok(())
+}
+
+/// Burns receipt tokens to claim the staked ORE they represent.
+///
+/// The principal moves from the source stake (the one that minted the receipts)
+/// into the signer's own stake account. Each side keeps the rewards it earned
+/// before the transfer. Redeeming against one's own stake simply untokenizes it.
+pub fn process_redeem_stake_receipt(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = RedeemStakeReceipt::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, mint_info, receipt_mint_info, receipt_tokens_info, source_stake_info, source_stake_tokens_info, stake_info, stake_tokens_info, treasury_info, token_program, config_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_STAKING)?;
+    mint_info.has_address(&MINT_ADDRESS)?.as_mint()?;
+    receipt_mint_info
+        .is_writable()?
+        .has_seeds(&[STAKE_RECEIPT_MINT], &ore_api::ID)?
+        .as_mint()?;
+    let receipt_tokens = receipt_tokens_info
+        .is_writable()?
+        .as_associated_token_account(signer_info.key, receipt_mint_info.key)?;
+    source_stake_info.is_writable()?;
+    source_stake_tokens_info
+        .is_writable()?
+        .as_associated_token_account(source_stake_info.key, mint_info.key)?;
+    stake_info.is_writable()?;
+    stake_tokens_info
+        .is_writable()?
+        .as_associated_token_account(stake_info.key, mint_info.key)?;
+    let treasury = treasury_info
+        .is_writable()?
+        .as_account_mut::<Treasury>(&ore_api::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+
+    if amount == 0 || receipt_tokens.amount() < amount {
+        sol_log("Insufficient stake receipts");
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    // Burn the receipts.
+    invoke(
+        &spl_token::instruction::burn(
+            &spl_token::ID,
+            receipt_tokens_info.key,
+            receipt_mint_info.key,
+            signer_info.key,
+            &[],
+            amount,
+        )?,
+        &[
+            receipt_tokens_info.clone(),
+            receipt_mint_info.clone(),
+            signer_info.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    // Redeeming against one's own stake just frees the principal.
+    if source_stake_info.key == stake_info.key {
+        let stake = stake_info
+            .as_account_mut::<Stake>(&ore_api::ID)?
+            .assert_mut(|s| s.authority == *signer_info.key)?;
+        stake.receipts_outstanding = stake
+            .receipts_outstanding
+            .checked_sub(amount)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        sol_log(
+            &format!(
+                "Untokenized {} ORE of stake",
+                amount_to_ui_amount(amount, TOKEN_DECIMALS)
+            )
+            .as_str(),
+        );
+        return Ok(());
+    }
+
+    // Release principal from the source stake.
+    let source_stake = source_stake_info.as_account_mut::<Stake>(&ore_api::ID)?;
+    let source_authority = source_stake.authority;
+    if source_stake.receipts_outstanding < amount {
+        sol_log("Source stake has too few receipts outstanding");
+        return Err(ProgramError::InsufficientFunds);
+    }
+    source_stake.release_receipts(amount, treasury)?;
+
+    // Credit principal to the signer's stake.
+    let stake = stake_info
+        .as_account_mut::<Stake>(&ore_api::ID)?
+        .assert_mut(|s| s.authority == *signer_info.key)?;
+    stake.credit_receipts(amount, &clock, treasury)?;
+
+    // Move the staked ORE between stake token accounts.
+    transfer_signed(
+        source_stake_info,
+        source_stake_tokens_info,
+        stake_tokens_info,
+        token_program,
+        amount,
+        &[STAKE, &source_authority.to_bytes()],
+    )?;
+
+    // Log redeem.
+    sol_log(
+        &format!(
+            "Redeemed {} ORE of stake from {}",
+            amount_to_ui_amount(amount, TOKEN_DECIMALS),
+            source_authority
+        )
+        .as_str(),
+    );
+
+    // Safety check.
+    let stake_tokens =
+        stake_tokens_info.as_associated_token_account(stake_info.key, mint_info.key)?;
+    assert!(stake_tokens.amount() >= stake.balance + stake.pending_withdrawal);
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use ore_api::prelude::*;
+use solana_program::log::sol_log;
+use steel::*;
+
+/// Designates an operator that can claim and compound yield, but never withdraw principal.
+/// Passing the default pubkey clears the operator. Only the stake authority may call this.
+pub fn process_set_stake_operator(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    let args = SetStakeOperator::try_from_bytes(data)?;
+    let operator = Pubkey::new_from_array(args.operator);
+
+    // Load accounts.
+    let [signer_info, stake_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let stake = stake_info
+        .as_account_mut::<Stake>(&ore_api::ID)?
+        .assert_mut(|s| s.authority == *signer_info.key)?;
+
+    // Set operator.
+    stake.operator = operator;
+
+    // Log operator.
+    sol_log(&format!("Stake operator set to {}", operator).as_str());
+
+    Ok(())
+}
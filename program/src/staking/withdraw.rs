@@ -3,7 +3,10 @@ use solana_program::log::sol_log;
 use spl_token::amount_to_ui_amount;
 use steel::*;
 
-/// Withdraws ORE from the staking contract.
+use crate::admin::assert_not_paused;
+
+/// Withdraws ORE from the staking contract. When an unstake cooldown is configured, the
+/// amount is queued and must be claimed with ClaimWithdrawal once the cooldown elapses.
 pub fn process_withdraw(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse data.
     let args = Withdraw::try_from_bytes(data)?;
@@ -11,7 +14,7 @@ pub fn process_withdraw(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRes
 
     // Load accounts.
     let clock = Clock::get()?;
-    let [signer_info, mint_info, recipient_info, stake_info, stake_tokens_info, treasury_info, system_program, token_program, associated_token_program] =
+    let [signer_info, mint_info, recipient_info, stake_info, stake_tokens_info, treasury_info, config_info, system_program, token_program, associated_token_program] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -26,6 +29,91 @@ pub fn process_withdraw(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRes
         .assert_mut(|s| s.authority == *signer_info.key)?;
     stake_tokens_info.as_associated_token_account(stake_info.key, mint_info.key)?;
     let treasury = treasury_info.as_account_mut::<Treasury>(&ore_api::ID)?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_STAKING)?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    // Open recipient token account.
+    if recipient_info.data_is_empty() {
+        create_associated_token_account(
+            signer_info,
+            signer_info,
+            recipient_info,
+            mint_info,
+            system_program,
+            token_program,
+            associated_token_program,
+        )?;
+    }
+
+    // Queue the withdrawal behind the cooldown, if one is configured.
+    if config.unstake_cooldown_slots > 0 {
+        let amount =
+            stake.queue_withdraw(amount, &clock, treasury, config.unstake_cooldown_slots)?;
+        sol_log(
+            &format!(
+                "Queued {} ORE for withdrawal, claimable at slot {}",
+                amount_to_ui_amount(amount, TOKEN_DECIMALS),
+                stake.withdraw_unlock_slot
+            )
+            .as_str(),
+        );
+        return Ok(());
+    }
+
+    // Withdraw from stake account.
+    let amount = stake.withdraw(amount, &clock, treasury)?;
+
+    // Transfer ORE to recipient.
+    transfer_signed(
+        stake_info,
+        stake_tokens_info,
+        recipient_info,
+        token_program,
+        amount,
+        &[STAKE, &stake.authority.to_bytes()],
+    )?;
+
+    // Log withdraw.
+    sol_log(
+        &format!(
+            "Withdrawing {} ORE",
+            amount_to_ui_amount(amount, TOKEN_DECIMALS)
+        )
+        .as_str(),
+    );
+
+    // Safety check.
+    let stake_tokens =
+        stake_tokens_info.as_associated_token_account(stake_info.key, mint_info.key)?;
+    assert!(stake_tokens.amount() >= stake.balance + stake.pending_withdrawal);
+
+    Ok(())
+}
+
+/// Claims a queued withdrawal once its unstake cooldown has elapsed.
+pub fn process_claim_withdrawal(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse data.
+    ClaimWithdrawal::try_from_bytes(data)?;
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, mint_info, recipient_info, stake_info, stake_tokens_info, system_program, token_program, associated_token_program, config_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    assert_not_paused(config, PAUSE_STAKING)?;
+    mint_info.has_address(&MINT_ADDRESS)?.as_mint()?;
+    recipient_info.is_writable()?;
+    let stake = stake_info
+        .as_account_mut::<Stake>(&ore_api::ID)?
+        .assert_mut(|s| s.authority == *signer_info.key)?;
+    stake_tokens_info.as_associated_token_account(stake_info.key, mint_info.key)?;
     system_program.is_program(&system_program::ID)?;
     token_program.is_program(&spl_token::ID)?;
     associated_token_program.is_program(&spl_associated_token_account::ID)?;
@@ -41,10 +129,19 @@ pub fn process_withdraw(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRes
             token_program,
             associated_token_program,
         )?;
+    } else {
+        recipient_info.as_associated_token_account(&signer_info.key, &mint_info.key)?;
     }
 
-    // Deposit into stake account.
-    let amount = stake.withdraw(amount, &clock, treasury);
+    // Release the pending withdrawal.
+    if stake.pending_withdrawal > 0 && clock.slot < stake.withdraw_unlock_slot {
+        sol_log(&format!(
+            "Withdrawal locked until slot {} (current slot {})",
+            stake.withdraw_unlock_slot, clock.slot
+        ));
+        return Err(OreError::WithdrawalLocked.into());
+    }
+    let amount = stake.claim_withdrawal(&clock);
 
     // Transfer ORE to recipient.
     transfer_signed(
@@ -68,7 +165,7 @@ pub fn process_withdraw(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRes
     // Safety check.
     let stake_tokens =
         stake_tokens_info.as_associated_token_account(stake_info.key, mint_info.key)?;
-    assert!(stake_tokens.amount() >= stake.balance);
+    assert!(stake_tokens.amount() >= stake.balance + stake.pending_withdrawal);
 
     Ok(())
 }
@@ -0,0 +1,294 @@
+//! In-process test harness. Runs the compiled program in LiteSVM with the mints, token
+//! accounts and entropy var a round needs, so tests can drive whole flows.
+//!
+//! The harness loads two binaries:
+//! - `target/deploy/ore.so`, built with `cargo build-sbf --features localnet` so rolls
+//!   can be injected through the test roll oracle.
+//! - `program/tests/fixtures/entropy.so`, a dump of the entropy program (see
+//!   `scripts/test-integration.sh`).
+
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+
+use entropy_api::state::Var;
+use litesvm::{types::TransactionResult, LiteSVM};
+use ore_api::prelude::*;
+use solana_program::{program_option::COption, program_pack::Pack};
+use solana_sdk::{
+    account::Account,
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{Instruction, InstructionError},
+    native_token::LAMPORTS_PER_SOL,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::get_associated_token_address;
+use steel::*;
+
+/// The most compute a transaction may request.
+pub const MAX_TRANSACTION_CU: u64 = 1_400_000;
+
+/// The compute an instruction gets when the transaction does not request a limit.
+pub const DEFAULT_INSTRUCTION_CU: u64 = 200_000;
+
+/// CRAP minted into the house bankroll at setup.
+pub const HOUSE_BANKROLL: u64 = 1_000_000 * ONE_CRAP;
+
+pub struct Harness {
+    pub svm: LiteSVM,
+    pub admin: Keypair,
+}
+
+impl Harness {
+    /// Loads the program and sets up an initialized, funded table with round 0 open.
+    pub fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(ore_api::ID, binary("../target/deploy/ore.so"))
+            .expect("failed to load ore.so");
+        svm.add_program_from_file(entropy_api::ID, binary("tests/fixtures/entropy.so"))
+            .expect("failed to load entropy.so");
+
+        let admin = Keypair::new();
+        svm.airdrop(&admin.pubkey(), 100 * LAMPORTS_PER_SOL).unwrap();
+        let mut harness = Self { svm, admin };
+
+        // Mints live at fixed addresses, so write them directly.
+        // The treasury mints ORE at reset; test tokens are minted by the harness.
+        harness.set_mint(MINT_ADDRESS, treasury_pda().0, TOKEN_DECIMALS);
+        harness.set_mint(RNG_MINT_ADDRESS, harness.admin.pubkey(), RNG_TOKEN_DECIMALS);
+        harness.set_mint(CRAP_MINT_ADDRESS, harness.admin.pubkey(), CRAP_TOKEN_DECIMALS);
+
+        let admin = harness.admin.insecure_clone();
+        let bootstrap = [
+            ComputeBudgetInstruction::set_compute_unit_limit(MAX_TRANSACTION_CU as u32),
+            ore_api::sdk::bootstrap(admin.pubkey(), HOUSE_BANKROLL),
+            ore_api::sdk::open_metrics(admin.pubkey()),
+        ];
+        harness.send(&bootstrap, &[&admin]).expect("bootstrap failed");
+
+        // The entropy var is written already finalized; injected rolls pick the square.
+        let board_address = board_pda().0;
+        let var_address = entropy_api::state::var_pda(board_address, 0).0;
+        let mut var = Var::zeroed();
+        var.authority = board_address;
+        var.samples = 1;
+        var.seed = [1; 32];
+        var.slot_hash = [2; 32];
+        var.value = [3; 32];
+        harness.set_program_account(var_address, entropy_api::ID, &var);
+        harness
+            .send(&[ore_api::sdk::set_var_address(admin.pubkey(), var_address)], &[&admin])
+            .expect("set_var_address failed");
+
+        harness
+    }
+
+    /// Creates a player holding SOL, `rng` RNG and `crap` CRAP.
+    pub fn player(&mut self, rng: u64, crap: u64) -> Keypair {
+        let player = Keypair::new();
+        self.svm.airdrop(&player.pubkey(), 10 * LAMPORTS_PER_SOL).unwrap();
+        self.set_token_account(player.pubkey(), RNG_MINT_ADDRESS, rng);
+        self.set_token_account(player.pubkey(), CRAP_MINT_ADDRESS, crap);
+        player
+    }
+
+    /// Sends the instructions in one transaction paid for by the first signer.
+    pub fn send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> TransactionResult {
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(tx);
+        // A fresh blockhash lets identical transactions be sent again.
+        self.svm.expire_blockhash();
+        result
+    }
+
+    /// Sends `ix` alone and asserts it fails because its module is paused.
+    pub fn assert_paused(&mut self, ix: Instruction, signers: &[&Keypair]) {
+        let failed = self.send(&[ix], signers).expect_err("instruction ran while paused");
+        assert_eq!(
+            failed.err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(OreError::ModulePaused as u32)
+            ),
+            "{:?}",
+            failed.meta.logs
+        );
+    }
+
+    /// Sends the instructions under the full transaction compute budget and returns the
+    /// units they consumed.
+    pub fn compute_units(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> u64 {
+        let mut budgeted =
+            vec![ComputeBudgetInstruction::set_compute_unit_limit(MAX_TRANSACTION_CU as u32)];
+        budgeted.extend_from_slice(ixs);
+        match self.send(&budgeted, signers) {
+            Ok(meta) => meta.compute_units_consumed,
+            Err(failed) => panic!("transaction failed: {:?}\n{:#?}", failed.err, failed.meta.logs),
+        }
+    }
+
+    /// Ends the open round on `square`: injects the roll, waits out the round and resets.
+    pub fn roll(&mut self, square: u64) {
+        self.roll_sequence(&[square]);
+    }
+
+    /// Ends the open round with its sub-rolls landing on `squares` in order, starting with
+    /// the winning square. Sub-rolls past the end of `squares` come from the slot hash.
+    pub fn roll_sequence(&mut self, squares: &[u64]) {
+        let admin = self.admin.insecure_clone();
+        let board = self.account::<Board>(board_pda().0);
+        self.send(
+            &[ore_api::sdk::set_test_rolls(admin.pubkey(), board.round_id, squares)],
+            &[&admin],
+        )
+        .expect("set_test_roll failed");
+        self.warp_to_slot(board.end_slot + INTERMISSION_SLOTS);
+        self.send(
+            &[ore_api::sdk::reset_with_test_roll(
+                admin.pubkey(),
+                admin.pubkey(),
+                board.round_id,
+                admin.pubkey(),
+            )],
+            &[&admin],
+        )
+        .expect("reset failed");
+    }
+
+    pub fn warp_to_slot(&mut self, slot: u64) {
+        self.svm.warp_to_slot(slot);
+    }
+
+    /// Sets the clock's unix timestamp, keeping the current slot.
+    pub fn set_unix_timestamp(&mut self, timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = timestamp;
+        self.svm.set_sysvar(&clock);
+    }
+
+    /// Opens the SOL/RNG exchange pool, seeded by the admin with `sol` lamports and `rng` RNG.
+    pub fn open_exchange_pool(&mut self, sol: u64, rng: u64) {
+        // The pool's SOL vault is a wSOL account, so the native mint must exist.
+        if self.svm.get_account(&SOL_MINT).is_none() {
+            self.set_mint(SOL_MINT, Pubkey::default(), 9);
+        }
+        let admin = self.admin.insecure_clone();
+        self.set_token_account(admin.pubkey(), RNG_MINT_ADDRESS, rng);
+        self.send(
+            &[ore_api::sdk::initialize_exchange_pool(admin.pubkey(), sol, rng)],
+            &[&admin],
+        )
+        .expect("initialize_exchange_pool failed");
+    }
+
+    /// Creates `owner`'s wSOL associated token account so it can receive SOL from the
+    /// exchange.
+    pub fn open_wsol_account(&mut self, owner: &Keypair) -> Pubkey {
+        let ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &owner.pubkey(),
+            &owner.pubkey(),
+            &SOL_MINT,
+            &spl_token::ID,
+        );
+        self.send(&[ix], &[owner]).expect("create wSOL account failed");
+        get_associated_token_address(&owner.pubkey(), &SOL_MINT)
+    }
+
+    /// Reads a program account through the checked reader.
+    pub fn account<T: AccountReader>(&self, address: Pubkey) -> T {
+        let account = self
+            .svm
+            .get_account(&address)
+            .unwrap_or_else(|| panic!("account {} not found", address));
+        T::read_unaligned(&account.owner, &account.data).unwrap()
+    }
+
+    pub fn token_balance(&self, owner: Pubkey, mint: Pubkey) -> u64 {
+        let address = get_associated_token_address(&owner, &mint);
+        self.svm
+            .get_account(&address)
+            .map(|account| spl_token::state::Account::unpack(&account.data).unwrap().amount)
+            .unwrap_or(0)
+    }
+
+    /// Writes an initialized mint at `address`.
+    pub fn set_mint(&mut self, address: Pubkey, authority: Pubkey, decimals: u8) {
+        let mint = spl_token::state::Mint {
+            mint_authority: COption::Some(authority),
+            supply: 0,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint::pack(mint, &mut data).unwrap();
+        self.set_raw_account(address, spl_token::ID, data);
+    }
+
+    /// Overwrites an existing mint's supply.
+    pub fn set_mint_supply(&mut self, address: Pubkey, supply: u64) {
+        let account = self.svm.get_account(&address).expect("mint not found");
+        let mut mint = spl_token::state::Mint::unpack(&account.data).unwrap();
+        mint.supply = supply;
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint::pack(mint, &mut data).unwrap();
+        self.set_raw_account(address, spl_token::ID, data);
+    }
+
+    /// Writes `owner`'s associated token account for `mint` holding `amount`.
+    pub fn set_token_account(&mut self, owner: Pubkey, mint: Pubkey, amount: u64) -> Pubkey {
+        let address = get_associated_token_address(&owner, &mint);
+        let token_account = spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account::pack(token_account, &mut data).unwrap();
+        self.set_raw_account(address, spl_token::ID, data);
+        address
+    }
+
+    /// Writes a steel account (discriminator, padding, then the struct) owned by `owner`.
+    pub fn set_program_account<T: Discriminator + Pod>(
+        &mut self,
+        address: Pubkey,
+        owner: Pubkey,
+        account: &T,
+    ) {
+        let mut data = vec![0; 8];
+        data[0] = T::discriminator();
+        data.extend_from_slice(bytemuck::bytes_of(account));
+        self.set_raw_account(address, owner, data);
+    }
+
+    fn set_raw_account(&mut self, address: Pubkey, owner: Pubkey, data: Vec<u8>) {
+        let account = Account {
+            lamports: self.svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.svm.set_account(address, account).unwrap();
+    }
+}
+
+/// Resolves a binary relative to the program crate, with a hint on how to produce it.
+fn binary(relative: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(relative);
+    assert!(
+        path.exists(),
+        "{} not found; run scripts/test-integration.sh to build the program and fetch fixtures",
+        path.display()
+    );
+    path
+}
@@ -0,0 +1,244 @@
+//! Compute-unit regression checks. Each test measures an instruction's worst case in
+//! LiteSVM and fails when it crosses its ceiling, so a change that pushes settlement or
+//! betting past the compute budget fails here instead of on-chain. Run with
+//! `scripts/test-integration.sh`.
+
+mod common;
+
+use common::{Harness, DEFAULT_INSTRUCTION_CU};
+use ore_api::{instruction::CrapsBetType, prelude::*};
+use solana_sdk::signature::{Keypair, Signer};
+
+/// Ceiling for a single PlaceCrapsBet, including creating the position accounts.
+const PLACE_BET_MAX_CU: u64 = 100_000;
+
+/// Ceiling for SettleCraps. Clients settle without a compute budget instruction, so the
+/// worst case has to fit the default instruction budget.
+const SETTLE_MAX_CU: u64 = DEFAULT_INSTRUCTION_CU;
+
+/// Ceiling for SettleCraps on a position holding only a Pass Line bet. The active-bet
+/// bitmap lets settlement skip every other section, so it needs half the default budget.
+const SETTLE_LINE_ONLY_MAX_CU: u64 = DEFAULT_INSTRUCTION_CU / 2;
+
+/// Ceiling for RouteSwap's own checks around a minimal swap. The router's cost comes on
+/// top and has to fit in what is left of the 1.4M transaction budget.
+const ROUTE_SWAP_MAX_CU: u64 = 60_000;
+
+const STAKE: u64 = ONE_CRAP;
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_place_bet_compute_units() {
+    let mut harness = Harness::new();
+    let player = harness.player(0, 1_000 * ONE_CRAP);
+
+    // The first bet creates the position; side bets create their own account.
+    let bets = [
+        (CrapsBetType::PassLine, 0),
+        (CrapsBetType::BonusSmall, 0),
+        (CrapsBetType::Place, 6),
+        (CrapsBetType::Field, 0),
+    ];
+    for (bet_type, point) in bets {
+        let ix = ore_api::sdk::place_craps_bet(player.pubkey(), bet_type, point, STAKE);
+        let units = harness.compute_units(&[ix], &[&player]);
+        println!("PlaceCrapsBet {:?}: {} CU", bet_type, units);
+        assert!(
+            units <= PLACE_BET_MAX_CU,
+            "PlaceCrapsBet {:?} used {} CU, ceiling is {}",
+            bet_type,
+            units,
+            PLACE_BET_MAX_CU
+        );
+    }
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_settle_worst_case_compute_units() {
+    let mut harness = Harness::new();
+    let player = harness.player(0, 1_000 * ONE_CRAP);
+    let opening = [
+        ore_api::sdk::place_craps_bet(player.pubkey(), CrapsBetType::PassLine, 0, STAKE),
+        ore_api::sdk::place_craps_bet(player.pubkey(), CrapsBetType::BonusSmall, 0, STAKE),
+    ];
+    harness.send(&opening, &[&player]).expect("opening bets failed");
+    harness.roll(0);
+
+    // Round 0 is rewritten for every case, and the position rewound to its first roll,
+    // so the same round can be settled again each time.
+    let round_address = round_pda(0).0;
+    let mut worst = (0, 0, 0);
+    for seed in 0..8u8 {
+        for square in 0..BOARD_SIZE {
+            let mut round = harness.account::<Round>(round_address);
+            round.slot_hash = [seed + 1; 32];
+            round.set_dice_result(square);
+            round.roll_count = MAX_ROLLS_PER_ROUND;
+            harness.set_program_account(round_address, ore_api::ID, &round);
+            load_table(&mut harness, &player);
+
+            let ix = ore_api::sdk::settle_craps(player.pubkey(), 0, square as u64);
+            let units = harness.compute_units(&[ix], &[&player]);
+            if units > worst.0 {
+                worst = (units, square, seed);
+            }
+        }
+    }
+
+    let (units, square, seed) = worst;
+    println!("SettleCraps worst case: {} CU (square {}, seed {})", units, square, seed);
+    assert!(
+        units <= SETTLE_MAX_CU,
+        "SettleCraps used {} CU on square {} with seed {}, ceiling is {}",
+        units,
+        square,
+        seed,
+        SETTLE_MAX_CU
+    );
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_settle_line_only_compute_units() {
+    let mut harness = Harness::new();
+    let player = harness.player(0, 1_000 * ONE_CRAP);
+    harness
+        .send(
+            &[ore_api::sdk::place_craps_bet(player.pubkey(), CrapsBetType::PassLine, 0, STAKE)],
+            &[&player],
+        )
+        .expect("place bet failed");
+
+    // 2 + 2 sets a point, so the Pass Line rides every sub-roll of the round.
+    harness.roll(7);
+    let ix = ore_api::sdk::settle_craps(player.pubkey(), 0, 7);
+    let units = harness.compute_units(&[ix], &[&player]);
+    println!("SettleCraps, Pass Line only: {} CU", units);
+    assert!(
+        units <= SETTLE_LINE_ONLY_MAX_CU,
+        "SettleCraps used {} CU on a Pass Line only position, ceiling is {}",
+        units,
+        SETTLE_LINE_ONLY_MAX_CU
+    );
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_route_swap_compute_units() {
+    let mut harness = Harness::new();
+    let admin = harness.admin.insecure_clone();
+    let treasury = treasury_pda().0;
+    let amount = 100 * ONE_RNG;
+
+    // The token program stands in for the router: the "swap" moves the treasury's RNG
+    // out and returns nothing, which is the cheapest route RouteSwap accepts.
+    harness
+        .send(&[ore_api::sdk::set_swap_program(admin.pubkey(), spl_token::ID)], &[&admin])
+        .expect("set_swap_program failed");
+    let treasury_rng = harness.set_token_account(treasury, RNG_MINT_ADDRESS, amount);
+    harness.set_token_account(treasury, CRAP_MINT_ADDRESS, 0);
+    let sink = harness.set_token_account(admin.pubkey(), RNG_MINT_ADDRESS, 0);
+    let transfer = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &treasury_rng,
+        &sink,
+        &treasury,
+        &[],
+        amount,
+    )
+    .unwrap();
+
+    let ix = ore_api::sdk::route_swap(
+        admin.pubkey(),
+        RNG_MINT_ADDRESS,
+        CRAP_MINT_ADDRESS,
+        spl_token::ID,
+        amount,
+        0,
+        &transfer.accounts,
+        &transfer.data,
+    );
+    let units = harness.compute_units(&[ix], &[&admin]);
+    println!("RouteSwap: {} CU", units);
+    assert!(
+        units <= ROUTE_SWAP_MAX_CU,
+        "RouteSwap used {} CU, ceiling is {}",
+        units,
+        ROUTE_SWAP_MAX_CU
+    );
+    assert_eq!(harness.token_balance(admin.pubkey(), RNG_MINT_ADDRESS), amount);
+}
+
+/// Writes the player's position and side bets with every bet type live and records every
+/// roll of round 0 as thrown with a point, so settlement walks every branch it can.
+fn load_table(harness: &mut Harness, player: &Keypair) {
+    let game_address = craps_game_pda().0;
+    let mut game = harness.account::<CrapsGame>(game_address);
+    game.set_point(6);
+    game.reserved_payouts = game.house_bankroll / 2;
+    let first_phase = game.phase_id.wrapping_sub(MAX_ROLLS_PER_ROUND as u32);
+    for index in 0..MAX_ROLLS_PER_ROUND {
+        let phase_id = first_phase.wrapping_add(index as u32);
+        game.phase_history[phase_id as usize % PHASE_HISTORY_LEN] = TablePhase {
+            round_id: 0,
+            epoch_id: game.epoch_id,
+            phase_id,
+            point: 6,
+            is_come_out: 0,
+            roll_index: index,
+            is_recorded: 1,
+        };
+    }
+    harness.set_program_account(game_address, ore_api::ID, &game);
+
+    let position_address = craps_position_pda(player.pubkey()).0;
+    let mut position = harness.account::<CrapsPosition>(position_address);
+    position.epoch_id = game.epoch_id;
+    position.phase_id = first_phase;
+    position.last_updated_round = 0;
+    position.pass_line = STAKE;
+    position.dont_pass = STAKE;
+    position.pass_odds = STAKE;
+    position.dont_pass_odds = STAKE;
+    position.come_bets = [STAKE; NUM_POINTS];
+    position.come_odds = [STAKE; NUM_POINTS];
+    position.dont_come_bets = [STAKE; NUM_POINTS];
+    position.dont_come_odds = [STAKE; NUM_POINTS];
+    position.place_bets = [STAKE; NUM_POINTS];
+    position.hardways = [STAKE; NUM_HARDWAYS];
+    position.field_bet = STAKE;
+    position.any_seven = STAKE;
+    position.any_craps = STAKE;
+    position.yo_eleven = STAKE;
+    position.aces = STAKE;
+    position.twelve = STAKE;
+    for i in 0..11 {
+        // Sum 7 can't carry Yes or No bets.
+        if i != 5 {
+            position.yes_bets[i] = STAKE;
+            position.no_bets[i] = STAKE;
+        }
+        position.next_bets[i] = STAKE;
+    }
+    position.pending_winnings = 0;
+    position.set_place_working(true);
+    position.refresh_active_bets();
+    position.set_side_bets_active(true);
+    harness.set_program_account(position_address, ore_api::ID, &position);
+
+    let side_bets_address = craps_side_bets_pda(player.pubkey()).0;
+    let mut side_bets = harness.account::<CrapsSideBets>(side_bets_address);
+    side_bets.epoch_id = game.epoch_id;
+    side_bets.bonus_small = STAKE;
+    side_bets.bonus_tall = STAKE;
+    side_bets.bonus_all = STAKE;
+    side_bets.fire_bet = STAKE;
+    side_bets.diff_doubles_bet = STAKE;
+    side_bets.ride_the_line_bet = STAKE;
+    side_bets.mugsy_bet = STAKE;
+    side_bets.hot_hand_bet = STAKE;
+    side_bets.replay_bet = STAKE;
+    side_bets.fielders_choice = [STAKE; 3];
+    harness.set_program_account(side_bets_address, ore_api::ID, &side_bets);
+}
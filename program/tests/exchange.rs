@@ -0,0 +1,799 @@
+//! Exchange flows against the compiled program. Run with `scripts/test-integration.sh`,
+//! which builds `ore.so` and fetches the fixtures first.
+
+mod common;
+
+use common::Harness;
+use ore_api::prelude::*;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Signer};
+
+/// SOL the admin seeds the pool with.
+const POOL_SOL: u64 = 10 * LAMPORTS_PER_SOL;
+
+/// RNG the admin seeds the pool with.
+const POOL_RNG: u64 = 10_000 * ONE_RNG;
+
+fn pool(harness: &Harness) -> ExchangePool {
+    harness.account::<ExchangePool>(exchange_pool_pda().0)
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_twap_weights_each_price_by_how_long_it_held() {
+    let mut harness = Harness::new();
+    harness.set_unix_timestamp(1_000);
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let start = pool(&harness).observe(1_000);
+    let (opening_price, _) = pool(&harness).spot_prices().unwrap();
+
+    // The opening price holds for 100 seconds, then a swap moves it.
+    harness.set_unix_timestamp(1_100);
+    let player = harness.player(0, 0);
+    harness
+        .send(
+            &[ore_api::sdk::swap_sol_to_rng(player.pubkey(), LAMPORTS_PER_SOL, 0, None)],
+            &[&player],
+        )
+        .expect("swap failed");
+    assert!(harness.token_balance(player.pubkey(), RNG_MINT_ADDRESS) > 0);
+    let swapped = pool(&harness);
+    assert_eq!(swapped.last_observation_at, 1_100);
+    let (moved_price, _) = swapped.spot_prices().unwrap();
+    assert!(moved_price < opening_price);
+
+    // The moved price holds for the next 300 seconds.
+    let end = swapped.observe(1_400);
+    let (twap, _) = end.twap_since(&start).unwrap();
+    assert_eq!(twap, (opening_price * 100 + moved_price * 300) / 400);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_quote_swap_matches_executed_swap() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let player = harness.player(0, 0);
+    let amount = LAMPORTS_PER_SOL / 2;
+
+    let before = pool(&harness);
+    let meta = harness
+        .send(&[ore_api::sdk::quote_swap(amount, true)], &[&player])
+        .expect("quote failed");
+    let quote = SwapQuote::from_bytes(&meta.return_data.data).unwrap();
+    assert_eq!(quote, before.quote(amount, true).unwrap());
+    assert_eq!(pool(&harness), before);
+
+    harness
+        .send(
+            &[ore_api::sdk::swap_sol_to_rng(player.pubkey(), amount, quote.amount_out, None)],
+            &[&player],
+        )
+        .expect("swap failed");
+    assert_eq!(harness.token_balance(player.pubkey(), RNG_MINT_ADDRESS), quote.amount_out);
+    assert_eq!(pool(&harness).protocol_fees_sol, quote.protocol_fee);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_exact_out_swap_pays_exactly_the_requested_amount() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let player = harness.player(0, 0);
+    let rng_out = 100 * ONE_RNG;
+
+    // A maximum input below the required SOL fails.
+    let sol_in = pool(&harness)
+        .calculate_swap_input(rng_out, POOL_SOL, POOL_RNG)
+        .unwrap()
+        .0;
+    assert!(harness
+        .send(
+            &[ore_api::sdk::swap_sol_to_rng_exact_out(player.pubkey(), rng_out, sol_in - 1, None)],
+            &[&player],
+        )
+        .is_err());
+
+    harness
+        .send(
+            &[ore_api::sdk::swap_sol_to_rng_exact_out(player.pubkey(), rng_out, sol_in, None)],
+            &[&player],
+        )
+        .expect("swap failed");
+    assert_eq!(harness.token_balance(player.pubkey(), RNG_MINT_ADDRESS), rng_out);
+    assert_eq!(pool(&harness).rng_reserve, POOL_RNG - rng_out);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_guardian_can_pause_but_only_admin_resumes() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let admin = harness.admin.insecure_clone();
+    let guardian = harness.player(0, 0);
+    let player = harness.player(0, 0);
+    let swap = ore_api::sdk::swap_sol_to_rng(player.pubkey(), LAMPORTS_PER_SOL / 10, 0, None);
+
+    harness
+        .send(&[ore_api::sdk::set_pool_guardian(admin.pubkey(), guardian.pubkey())], &[&admin])
+        .expect("set guardian failed");
+    harness
+        .send(
+            &[ore_api::sdk::set_pool_status(guardian.pubkey(), EXCHANGE_STATUS_PAUSED)],
+            &[&guardian],
+        )
+        .expect("guardian pause failed");
+    assert!(harness.send(&[swap.clone()], &[&player]).is_err());
+
+    // The guardian can't undo its own pause.
+    assert!(harness
+        .send(
+            &[ore_api::sdk::set_pool_status(guardian.pubkey(), EXCHANGE_STATUS_ACTIVE)],
+            &[&guardian],
+        )
+        .is_err());
+
+    harness
+        .send(&[ore_api::sdk::set_pool_status(admin.pubkey(), EXCHANGE_STATUS_ACTIVE)], &[&admin])
+        .expect("admin resume failed");
+    harness.send(&[swap], &[&player]).expect("swap failed");
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_fee_change_waits_out_the_timelock() {
+    let mut harness = Harness::new();
+    harness.set_unix_timestamp(1_000);
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let admin = harness.admin.insecure_clone();
+
+    harness
+        .send(&[ore_api::sdk::set_pool_fees(admin.pubkey(), 50, 2_500)], &[&admin])
+        .expect("set fees failed");
+    assert_eq!(pool(&harness).fee_numerator, EXCHANGE_DEFAULT_FEE_NUMERATOR);
+
+    harness.set_unix_timestamp(1_000 + EXCHANGE_FEE_TIMELOCK_SECONDS - 1);
+    assert!(harness.send(&[ore_api::sdk::apply_pool_fees(admin.pubkey())], &[&admin]).is_err());
+
+    harness.set_unix_timestamp(1_000 + EXCHANGE_FEE_TIMELOCK_SECONDS);
+    harness
+        .send(&[ore_api::sdk::apply_pool_fees(admin.pubkey())], &[&admin])
+        .expect("apply fees failed");
+    let pool = pool(&harness);
+    assert_eq!(pool.fee_numerator, 50);
+    assert_eq!(pool.protocol_fee_share_bps, 2_500);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_game_token_pool_prices_swaps_off_its_reserves() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let admin = harness.admin.insecure_clone();
+    harness.set_token_account(admin.pubkey(), RNG_MINT_ADDRESS, 1_000 * ONE_RNG);
+    harness.set_token_account(admin.pubkey(), CRAP_MINT_ADDRESS, 2_000 * ONE_CRAP);
+    harness
+        .send(
+            &[ore_api::sdk::initialize_game_token_pool(
+                admin.pubkey(),
+                CRAP_MINT_ADDRESS,
+                0,
+                1_000 * ONE_RNG,
+                2_000 * ONE_CRAP,
+            )],
+            &[&admin],
+        )
+        .expect("initialize game token pool failed");
+
+    let player = harness.player(10 * ONE_RNG, 0);
+    let game_pool_address = game_token_pool_pda(&CRAP_MINT_ADDRESS).0;
+    let (expected, _, _) = harness
+        .account::<GameTokenPool>(game_pool_address)
+        .calculate_swap_output(10 * ONE_RNG, true)
+        .unwrap();
+    // Twice as many CRAP as RNG in the pool, so RNG buys nearly two CRAP each.
+    assert!(expected > 19 * ONE_CRAP && expected < 20 * ONE_CRAP);
+    harness
+        .send(
+            &[ore_api::sdk::swap_game_token_pool(
+                player.pubkey(),
+                CRAP_MINT_ADDRESS,
+                10 * ONE_RNG,
+                expected,
+                true,
+            )],
+            &[&player],
+        )
+        .expect("swap failed");
+    assert_eq!(harness.token_balance(player.pubkey(), CRAP_MINT_ADDRESS), expected);
+    assert_eq!(harness.token_balance(player.pubkey(), RNG_MINT_ADDRESS), 0);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_lp_stakers_earn_their_share_of_protocol_fees() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let admin = harness.admin.insecure_clone();
+    let lp_mint = exchange_lp_mint_pda().0;
+    let lp_tokens = harness.token_balance(admin.pubkey(), lp_mint);
+    harness
+        .send(&[ore_api::sdk::stake_lp(admin.pubkey(), lp_tokens)], &[&admin])
+        .expect("stake failed");
+    assert_eq!(pool(&harness).total_lp_staked, lp_tokens);
+    assert_eq!(harness.token_balance(admin.pubkey(), lp_mint), 0);
+
+    let player = harness.player(0, 0);
+    harness
+        .send(
+            &[ore_api::sdk::swap_sol_to_rng(player.pubkey(), LAMPORTS_PER_SOL, 0, None)],
+            &[&player],
+        )
+        .expect("swap failed");
+    let owed = pool(&harness).lp_rewards_owed_sol;
+    assert!(owed > 0);
+
+    harness.open_wsol_account(&admin);
+    harness
+        .send(&[ore_api::sdk::claim_lp_rewards(admin.pubkey())], &[&admin])
+        .expect("claim failed");
+    // The sole staker gets everything owed, less at most a lamport of rounding.
+    let claimed = harness.token_balance(admin.pubkey(), SOL_MINT);
+    assert!(claimed <= owed && owed - claimed <= 1);
+    assert_eq!(pool(&harness).lp_rewards_owed_sol, owed - claimed);
+
+    harness
+        .send(&[ore_api::sdk::unstake_lp(admin.pubkey(), lp_tokens)], &[&admin])
+        .expect("unstake failed");
+    assert_eq!(harness.token_balance(admin.pubkey(), lp_mint), lp_tokens);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_zap_deposits_a_single_token_for_lp_tokens() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let player = harness.player(0, 0);
+    let before = pool(&harness);
+
+    harness
+        .send(
+            &[ore_api::sdk::zap_liquidity(player.pubkey(), LAMPORTS_PER_SOL, 1, true)],
+            &[&player],
+        )
+        .expect("zap failed");
+    let after = pool(&harness);
+    let lp_tokens = harness.token_balance(player.pubkey(), exchange_lp_mint_pda().0);
+    assert!(lp_tokens > 0);
+    assert_eq!(after.total_lp_supply, before.total_lp_supply + lp_tokens);
+    // The deposit lands in the SOL reserve, less the swap leg's protocol fee. The swapped
+    // RNG goes straight back in, so the RNG reserve is unchanged.
+    let protocol_fee = after.protocol_fees_sol - before.protocol_fees_sol;
+    assert_eq!(after.sol_reserve + protocol_fee, before.sol_reserve + LAMPORTS_PER_SOL);
+    assert_eq!(after.rng_reserve, before.rng_reserve);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_single_sided_removal_pays_out_one_token() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let admin = harness.admin.insecure_clone();
+    let lp_mint = exchange_lp_mint_pda().0;
+    let lp_tokens = harness.token_balance(admin.pubkey(), lp_mint);
+    let rng_before = harness.token_balance(admin.pubkey(), RNG_MINT_ADDRESS);
+    let before = pool(&harness);
+    let burn = lp_tokens / 20;
+    let sol_share = burn as u128 * before.sol_reserve as u128 / before.total_lp_supply as u128;
+
+    harness.open_wsol_account(&admin);
+    harness
+        .send(
+            &[ore_api::sdk::remove_liquidity_single(admin.pubkey(), burn, 1, true)],
+            &[&admin],
+        )
+        .expect("remove failed");
+    // The RNG leg was sold into the pool, so the payout beats the plain SOL share.
+    assert!(harness.token_balance(admin.pubkey(), SOL_MINT) as u128 > sol_share);
+    assert_eq!(harness.token_balance(admin.pubkey(), RNG_MINT_ADDRESS), rng_before);
+    assert_eq!(harness.token_balance(admin.pubkey(), lp_mint), lp_tokens - burn);
+    assert_eq!(pool(&harness).total_lp_supply, before.total_lp_supply - burn);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_remove_liquidity_pays_both_vault_legs() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let admin = harness.admin.insecure_clone();
+    let lp_mint = exchange_lp_mint_pda().0;
+    let burn = harness.token_balance(admin.pubkey(), lp_mint) / 2;
+    let rng_before = harness.token_balance(admin.pubkey(), RNG_MINT_ADDRESS);
+    let before = pool(&harness);
+    let sol_out = (burn as u128 * POOL_SOL as u128 / before.total_lp_supply as u128) as u64;
+    let rng_out = (burn as u128 * POOL_RNG as u128 / before.total_lp_supply as u128) as u64;
+
+    // Both vaults are owned by the pool PDA, so each leg must sign with the pool seeds.
+    harness.open_wsol_account(&admin);
+    harness
+        .send(
+            &[ore_api::sdk::remove_liquidity(admin.pubkey(), burn, sol_out, rng_out)],
+            &[&admin],
+        )
+        .expect("remove failed");
+    assert_eq!(harness.token_balance(admin.pubkey(), SOL_MINT), sol_out);
+    assert_eq!(harness.token_balance(admin.pubkey(), RNG_MINT_ADDRESS), rng_before + rng_out);
+    let after = pool(&harness);
+    assert_eq!(after.sol_reserve, POOL_SOL - sol_out);
+    assert_eq!(after.rng_reserve, POOL_RNG - rng_out);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_skim_leaves_fees_below_the_threshold() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let player = harness.player(0, 0);
+    harness
+        .send(
+            &[ore_api::sdk::swap_sol_to_rng(player.pubkey(), LAMPORTS_PER_SOL, 0, None)],
+            &[&player],
+        )
+        .expect("swap failed");
+    let fees = pool(&harness).protocol_fees_sol;
+    assert!(fees > 0 && fees < EXCHANGE_FEE_SKIM_THRESHOLD_SOL);
+
+    harness
+        .send(&[ore_api::sdk::skim_protocol_fees(player.pubkey())], &[&player])
+        .expect("skim failed");
+    assert_eq!(pool(&harness).protocol_fees_sol, fees);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_referrer_earns_its_share_of_the_protocol_fee() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let admin = harness.admin.insecure_clone();
+    harness
+        .send(&[ore_api::sdk::set_referral_fee(admin.pubkey(), 5_000)], &[&admin])
+        .expect("set referral fee failed");
+    let referrer = harness.player(0, 0);
+    harness
+        .send(&[ore_api::sdk::register_referrer(referrer.pubkey())], &[&referrer])
+        .expect("register failed");
+
+    // A referrer can't refer its own swaps.
+    let own_swap = ore_api::sdk::swap_sol_to_rng(
+        referrer.pubkey(),
+        LAMPORTS_PER_SOL,
+        0,
+        Some(referrer.pubkey()),
+    );
+    assert!(harness.send(&[own_swap], &[&referrer]).is_err());
+
+    let player = harness.player(0, 0);
+    let quote = pool(&harness).quote(LAMPORTS_PER_SOL, true).unwrap();
+    harness
+        .send(
+            &[ore_api::sdk::swap_sol_to_rng(
+                player.pubkey(),
+                LAMPORTS_PER_SOL,
+                0,
+                Some(referrer.pubkey()),
+            )],
+            &[&player],
+        )
+        .expect("swap failed");
+    let referral_fee = quote.protocol_fee / 2;
+    let swapped = pool(&harness);
+    assert_eq!(swapped.referral_fees_owed_sol, referral_fee);
+    assert_eq!(swapped.protocol_fees_sol, quote.protocol_fee - referral_fee);
+
+    harness.open_wsol_account(&referrer);
+    harness
+        .send(&[ore_api::sdk::claim_referral_fees(referrer.pubkey())], &[&referrer])
+        .expect("claim failed");
+    assert_eq!(harness.token_balance(referrer.pubkey(), SOL_MINT), referral_fee);
+    assert_eq!(pool(&harness).referral_fees_owed_sol, 0);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_circuit_breaker_rejects_large_price_moves() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let admin = harness.admin.insecure_clone();
+    harness
+        .send(&[ore_api::sdk::set_circuit_breaker(admin.pubkey(), 200, 60)], &[&admin])
+        .expect("set circuit breaker failed");
+    let player = harness.player(0, 0);
+
+    // About a 1% move fits under the 2% breaker; another 10% of the reserve does not.
+    harness
+        .send(
+            &[ore_api::sdk::swap_sol_to_rng(player.pubkey(), POOL_SOL / 200, 0, None)],
+            &[&player],
+        )
+        .expect("small swap failed");
+    assert!(harness
+        .send(
+            &[ore_api::sdk::swap_sol_to_rng(player.pubkey(), POOL_SOL / 10, 0, None)],
+            &[&player],
+        )
+        .is_err());
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_max_swap_bounds_a_single_swap() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let admin = harness.admin.insecure_clone();
+    let player = harness.player(0, 0);
+    let swap = ore_api::sdk::swap_sol_to_rng(player.pubkey(), POOL_SOL * 15 / 100, 0, None);
+
+    // 15% of the reserve is over the default cap.
+    assert!(harness.send(&[swap.clone()], &[&player]).is_err());
+
+    harness
+        .send(&[ore_api::sdk::set_max_swap(admin.pubkey(), 2_000)], &[&admin])
+        .expect("set max swap failed");
+    assert_eq!(pool(&harness).max_swap_bps, 2_000);
+    harness.send(&[swap], &[&player]).expect("swap failed");
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_reserve_product_never_decreases_across_swaps() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let player = harness.player(0, 0);
+    harness.open_wsol_account(&player);
+
+    // Odd-sized swaps in both directions, so every leg rounds.
+    let mut k = pool(&harness).k();
+    for (i, amount) in [7_777_777, 1_000_003, 333_333_333, 99_999].into_iter().enumerate() {
+        harness
+            .send(
+                &[ore_api::sdk::swap_sol_to_rng(player.pubkey(), amount + i as u64, 0, None)],
+                &[&player],
+            )
+            .expect("swap failed");
+        assert!(pool(&harness).k() >= k);
+        k = pool(&harness).k();
+
+        let rng = harness.token_balance(player.pubkey(), RNG_MINT_ADDRESS) / 3 + 1;
+        harness
+            .send(&[ore_api::sdk::swap_rng_to_sol(player.pubkey(), rng, 0, None)], &[&player])
+            .expect("swap failed");
+        assert!(pool(&harness).k() >= k);
+        k = pool(&harness).k();
+    }
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_last_emergency_withdrawer_empties_a_paused_pool() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let admin = harness.admin.insecure_clone();
+    harness.open_wsol_account(&admin);
+    let lp_tokens = harness.token_balance(admin.pubkey(), exchange_lp_mint_pda().0);
+    let rng_before = harness.token_balance(admin.pubkey(), RNG_MINT_ADDRESS);
+    let exit = ore_api::sdk::emergency_remove_liquidity(admin.pubkey(), lp_tokens, 0, 0);
+
+    // Emergency exits wait for the pool to halt.
+    assert!(harness.send(&[exit.clone()], &[&admin]).is_err());
+
+    harness
+        .send(&[ore_api::sdk::set_pool_status(admin.pubkey(), EXCHANGE_STATUS_PAUSED)], &[&admin])
+        .expect("pause failed");
+    harness.send(&[exit], &[&admin]).expect("emergency exit failed");
+    assert_eq!(harness.token_balance(admin.pubkey(), SOL_MINT), POOL_SOL);
+    assert_eq!(harness.token_balance(admin.pubkey(), RNG_MINT_ADDRESS), rng_before + POOL_RNG);
+    let pool = pool(&harness);
+    assert_eq!(pool.sol_reserve, 0);
+    assert_eq!(pool.rng_reserve, 0);
+    assert_eq!(pool.total_lp_supply, 0);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_limit_order_fills_only_at_its_limit() {
+    let mut harness = Harness::new();
+    harness.set_unix_timestamp(1_000);
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let owner = harness.player(200 * ONE_RNG, 0);
+    let filler = harness.player(0, 0);
+    let side = LIMIT_ORDER_SIDE_RNG_TO_SOL;
+    let (_, rng_price) = pool(&harness).spot_prices().unwrap();
+
+    // An order asking twice the spot price can't fill, and the owner can cancel it.
+    harness
+        .send(
+            &[ore_api::sdk::place_limit_order(
+                owner.pubkey(),
+                1,
+                side,
+                100 * ONE_RNG,
+                rng_price * 2,
+                2_000,
+            )],
+            &[&owner],
+        )
+        .expect("place failed");
+    assert!(harness
+        .send(
+            &[ore_api::sdk::fill_limit_order(filler.pubkey(), owner.pubkey(), 1, side)],
+            &[&filler],
+        )
+        .is_err());
+    harness
+        .send(
+            &[ore_api::sdk::cancel_limit_order(owner.pubkey(), owner.pubkey(), 1, side)],
+            &[&owner],
+        )
+        .expect("cancel failed");
+    assert_eq!(harness.token_balance(owner.pubkey(), RNG_MINT_ADDRESS), 200 * ONE_RNG);
+
+    // An order at half the spot price fills right away.
+    harness
+        .send(
+            &[ore_api::sdk::place_limit_order(
+                owner.pubkey(),
+                2,
+                side,
+                100 * ONE_RNG,
+                rng_price / 2,
+                2_000,
+            )],
+            &[&owner],
+        )
+        .expect("place failed");
+    let order_address = limit_order_pda(owner.pubkey(), 2).0;
+    let (_, bounty) = harness.account::<LimitOrder>(order_address).split_bounty();
+    harness
+        .send(
+            &[ore_api::sdk::fill_limit_order(filler.pubkey(), owner.pubkey(), 2, side)],
+            &[&filler],
+        )
+        .expect("fill failed");
+    assert!(harness.token_balance(owner.pubkey(), SOL_MINT) > 0);
+    assert_eq!(harness.token_balance(owner.pubkey(), RNG_MINT_ADDRESS), 100 * ONE_RNG);
+    assert_eq!(harness.token_balance(filler.pubkey(), RNG_MINT_ADDRESS), bounty);
+    assert!(harness
+        .svm
+        .get_account(&order_address)
+        .map_or(true, |account| account.lamports == 0));
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_dca_order_swaps_one_slice_per_interval() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let owner = harness.player(30 * ONE_RNG, 0);
+    let cranker = harness.player(0, 0);
+    let side = LIMIT_ORDER_SIDE_RNG_TO_SOL;
+    let order_address = dca_order_pda(owner.pubkey(), 1).0;
+    harness
+        .send(
+            &[ore_api::sdk::open_dca_order(
+                owner.pubkey(),
+                1,
+                side,
+                10 * ONE_RNG,
+                100,
+                30 * ONE_RNG,
+                1,
+            )],
+            &[&owner],
+        )
+        .expect("open failed");
+    let crank = ore_api::sdk::crank_dca_order(cranker.pubkey(), owner.pubkey(), 1, side);
+
+    // The first slice is due right away; the next waits out the interval.
+    harness.send(&[crank.clone()], &[&cranker]).expect("crank failed");
+    let order = harness.account::<DcaOrder>(order_address);
+    assert_eq!(order.executions, 1);
+    assert_eq!(order.remaining_budget, 20 * ONE_RNG);
+    assert!(harness.token_balance(owner.pubkey(), SOL_MINT) > 0);
+    assert!(harness.send(&[crank.clone()], &[&cranker]).is_err());
+
+    harness.warp_to_slot(order.next_execution_slot);
+    harness.send(&[crank], &[&cranker]).expect("crank failed");
+    assert_eq!(harness.account::<DcaOrder>(order_address).remaining_budget, 10 * ONE_RNG);
+    let bounty = 2 * (10 * ONE_RNG * EXCHANGE_DCA_CRANK_BOUNTY_BPS / 10_000);
+    assert_eq!(harness.token_balance(cranker.pubkey(), RNG_MINT_ADDRESS), bounty);
+
+    // Cancelling refunds the unspent slice.
+    harness
+        .send(&[ore_api::sdk::cancel_dca_order(owner.pubkey(), 1, side)], &[&owner])
+        .expect("cancel failed");
+    assert_eq!(harness.token_balance(owner.pubkey(), RNG_MINT_ADDRESS), 10 * ONE_RNG);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_lp_lock_boosts_weight_and_penalizes_early_exit() {
+    let mut harness = Harness::new();
+    harness.set_unix_timestamp(1_000);
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let admin = harness.admin.insecure_clone();
+    harness.open_wsol_account(&admin);
+    let lp_mint = exchange_lp_mint_pda().0;
+    let lp_tokens = harness.token_balance(admin.pubkey(), lp_mint);
+    let amount = lp_tokens / 2;
+
+    // A max-term lock counts double toward the LP staker fee share.
+    harness
+        .send(
+            &[ore_api::sdk::lock_lp(admin.pubkey(), 1, amount, EXCHANGE_LP_LOCK_MAX_DURATION)],
+            &[&admin],
+        )
+        .expect("lock failed");
+    assert_eq!(pool(&harness).total_lp_staked, amount * 2);
+
+    // Leaving a day in burns the early-exit penalty.
+    harness.set_unix_timestamp(1_000 + 24 * 60 * 60);
+    let supply = pool(&harness).total_lp_supply;
+    harness
+        .send(&[ore_api::sdk::unlock_lp(admin.pubkey(), 1)], &[&admin])
+        .expect("unlock failed");
+    let penalty = amount * EXCHANGE_LP_LOCK_EARLY_EXIT_PENALTY_BPS / 10_000;
+    assert_eq!(harness.token_balance(admin.pubkey(), lp_mint), lp_tokens - penalty);
+    let pool = pool(&harness);
+    assert_eq!(pool.total_lp_staked, 0);
+    assert_eq!(pool.total_lp_supply, supply - penalty);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_game_token_backing_tracks_minted_supply() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    // Game tokens mint against the RNG vault under the pool's authority.
+    harness.set_mint(CRAP_MINT_ADDRESS, exchange_pool_pda().0, CRAP_TOKEN_DECIMALS);
+    let player = harness.player(100 * ONE_RNG, 0);
+    harness
+        .send(
+            &[ore_api::sdk::swap_rng_to_game_token(
+                player.pubkey(),
+                CRAP_MINT_ADDRESS,
+                0,
+                100 * ONE_RNG,
+            )],
+            &[&player],
+        )
+        .expect("swap failed");
+    let minted = harness.token_balance(player.pubkey(), CRAP_MINT_ADDRESS);
+    assert!(minted > 0);
+
+    let meta = harness
+        .send(&[ore_api::sdk::quote_game_token_backing(0)], &[&player])
+        .expect("quote failed");
+    let backing = GameTokenBacking::from_bytes(&meta.return_data.data).unwrap();
+    assert_eq!(backing.outstanding, minted);
+    assert!(backing.backing >= minted);
+    assert!(backing.collateralization_bps >= 10_000);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_game_token_mint_stops_at_the_supply_cap() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    harness.set_mint(CRAP_MINT_ADDRESS, exchange_pool_pda().0, CRAP_TOKEN_DECIMALS);
+    harness.set_mint_supply(CRAP_MINT_ADDRESS, MAX_CRAP_SUPPLY - ONE_CRAP);
+    let player = harness.player(10 * ONE_RNG, 0);
+    let swap = |rng_amount| {
+        ore_api::sdk::swap_rng_to_game_token(player.pubkey(), CRAP_MINT_ADDRESS, 0, rng_amount)
+    };
+
+    // Ten RNG would mint past the cap; one RNG fits under it.
+    assert!(harness.send(&[swap(10 * ONE_RNG)], &[&player]).is_err());
+    harness.send(&[swap(ONE_RNG)], &[&player]).expect("swap failed");
+    assert!(harness.token_balance(player.pubkey(), CRAP_MINT_ADDRESS) > 0);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_buyback_burns_crap_with_protocol_fees() {
+    let mut harness = Harness::new();
+    harness.set_unix_timestamp(10_000);
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let admin = harness.admin.insecure_clone();
+    harness.set_token_account(admin.pubkey(), RNG_MINT_ADDRESS, 1_000 * ONE_RNG);
+    harness.set_token_account(admin.pubkey(), CRAP_MINT_ADDRESS, 2_000 * ONE_CRAP);
+    // The burn draws down the mint's supply, so it must cover the seeded CRAP.
+    harness.set_mint_supply(CRAP_MINT_ADDRESS, 2_000 * ONE_CRAP);
+    harness
+        .send(
+            &[
+                ore_api::sdk::initialize_game_token_pool(
+                    admin.pubkey(),
+                    CRAP_MINT_ADDRESS,
+                    0,
+                    1_000 * ONE_RNG,
+                    2_000 * ONE_CRAP,
+                ),
+                ore_api::sdk::set_buyback_config(
+                    admin.pubkey(),
+                    10_000,
+                    LAMPORTS_PER_SOL,
+                    EXCHANGE_MIN_BUYBACK_INTERVAL_SECONDS,
+                ),
+            ],
+            &[&admin],
+        )
+        .expect("setup failed");
+
+    let player = harness.player(0, 0);
+    harness
+        .send(
+            &[ore_api::sdk::swap_sol_to_rng(player.pubkey(), LAMPORTS_PER_SOL, 0, None)],
+            &[&player],
+        )
+        .expect("swap failed");
+    let fees = pool(&harness).protocol_fees_sol;
+    let game_pool_address = game_token_pool_pda(&CRAP_MINT_ADDRESS).0;
+    let crap_before = harness.account::<GameTokenPool>(game_pool_address).game_reserve;
+
+    harness
+        .send(&[ore_api::sdk::buyback_crap(player.pubkey())], &[&player])
+        .expect("buyback failed");
+    // Only the buyback swap's own protocol fee is left.
+    let pool = pool(&harness);
+    assert!(pool.protocol_fees_sol < fees / 100);
+    assert_eq!(pool.total_buyback_sol, fees);
+    assert!(pool.total_crap_burned > 0);
+    assert_eq!(
+        harness.account::<GameTokenPool>(game_pool_address).game_reserve,
+        crap_before - pool.total_crap_burned
+    );
+
+    // The next buyback waits out the interval.
+    assert!(harness.send(&[ore_api::sdk::buyback_crap(player.pubkey())], &[&player]).is_err());
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_rolling_stats_drop_swaps_older_than_a_day() {
+    let hour = EXCHANGE_STATS_BUCKET_SECONDS;
+    let mut harness = Harness::new();
+    harness.set_unix_timestamp(100 * hour);
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let player = harness.player(0, 0);
+    let swap = |sol_amount| ore_api::sdk::swap_sol_to_rng(player.pubkey(), sol_amount, 0, None);
+
+    harness.send(&[swap(LAMPORTS_PER_SOL / 2)], &[&player]).expect("swap failed");
+    harness.set_unix_timestamp(102 * hour);
+    harness.send(&[swap(LAMPORTS_PER_SOL / 4)], &[&player]).expect("swap failed");
+
+    let pool = pool(&harness);
+    let (volume, fees) = pool.rolling_24h_stats(102 * hour);
+    assert_eq!(volume, LAMPORTS_PER_SOL / 2 + LAMPORTS_PER_SOL / 4);
+    assert!(fees > 0);
+    // A day after the first swap, only the second is left in the window.
+    assert_eq!(pool.rolling_24h_stats(124 * hour).0, LAMPORTS_PER_SOL / 4);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_slot_notional_cap_spans_swaps_in_one_slot() {
+    let mut harness = Harness::new();
+    harness.open_exchange_pool(POOL_SOL, POOL_RNG);
+    let admin = harness.admin.insecure_clone();
+    harness
+        .send(&[ore_api::sdk::set_slot_notional_cap(admin.pubkey(), 1_500)], &[&admin])
+        .expect("set slot notional cap failed");
+    let player = harness.player(0, 0);
+    let swap = ore_api::sdk::swap_sol_to_rng(player.pubkey(), LAMPORTS_PER_SOL, 0, None);
+
+    // Each swap fits under the max swap size, but two in one slot pass 15% of the reserve.
+    harness.send(&[swap.clone()], &[&player]).expect("swap failed");
+    assert!(harness.send(&[swap.clone()], &[&player]).is_err());
+
+    harness.warp_to_slot(pool(&harness).notional_slot + 1);
+    harness.send(&[swap], &[&player]).expect("swap failed");
+}
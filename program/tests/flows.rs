@@ -0,0 +1,786 @@
+//! End-to-end flows against the compiled program. Run with
+//! `scripts/test-integration.sh`, which builds `ore.so` and fetches the fixtures first.
+
+mod common;
+
+use common::Harness;
+use ore_api::{instruction::CrapsBetType, prelude::*, simulator};
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Signer};
+use steel::Zeroable;
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_pass_line_natural_pays_even_money() {
+    let mut harness = Harness::new();
+    let player = harness.player(0, 1_000 * ONE_CRAP);
+    let amount = 100 * ONE_CRAP;
+
+    harness
+        .send(
+            &[ore_api::sdk::place_craps_bet(player.pubkey(), CrapsBetType::PassLine, 0, amount)],
+            &[&player],
+        )
+        .expect("place bet failed");
+    assert_eq!(harness.token_balance(player.pubkey(), CRAP_MINT_ADDRESS), 900 * ONE_CRAP);
+
+    // 3 + 4: a natural on the come-out roll.
+    harness.roll(15);
+    harness
+        .send(&[ore_api::sdk::settle_craps(player.pubkey(), 0, 15)], &[&player])
+        .expect("settle failed");
+    let position = harness.account::<CrapsPosition>(craps_position_pda(player.pubkey()).0);
+    assert_eq!(position.pass_line, 0);
+    assert_eq!(position.pending_winnings, 2 * amount);
+
+    let metrics = harness.account::<Metrics>(METRICS_ADDRESS);
+    assert_eq!(metrics.total_bets, 1);
+    assert_eq!(metrics.total_volume, amount);
+    assert_eq!(metrics.unique_positions, 1);
+    assert_eq!(metrics.total_settled, 1);
+    assert_eq!(metrics.house_pnl(), -(amount as i128));
+
+    harness
+        .send(&[ore_api::sdk::claim_craps_winnings(player.pubkey())], &[&player])
+        .expect("claim failed");
+    let position = harness.account::<CrapsPosition>(craps_position_pda(player.pubkey()).0);
+    assert_eq!(position.pending_winnings, 0);
+    assert_eq!(harness.token_balance(player.pubkey(), CRAP_MINT_ADDRESS), 1_100 * ONE_CRAP);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_settlement_matches_simulator() {
+    let mut harness = Harness::new();
+    let player = harness.player(0, 1_000 * ONE_CRAP);
+    let bets = [
+        (CrapsBetType::PassLine, 0, 50 * ONE_CRAP),
+        (CrapsBetType::Field, 0, 10 * ONE_CRAP),
+        (CrapsBetType::Place, 8, 30 * ONE_CRAP),
+        (CrapsBetType::Hardway, 6, 10 * ONE_CRAP),
+    ];
+    let ixs: Vec<_> = bets
+        .iter()
+        .map(|&(bet_type, point, amount)| {
+            ore_api::sdk::place_craps_bet(player.pubkey(), bet_type, point, amount)
+        })
+        .collect();
+    harness.send(&ixs, &[&player]).expect("place bets failed");
+    let admin = harness.admin.insecure_clone();
+    let set_rolls = ore_api::sdk::set_param(
+        admin.pubkey(),
+        PARAM_ROLLS_PER_ROUND,
+        MAX_ROLLS_PER_ROUND as u64,
+    );
+    harness.send(&[set_rolls], &[&admin]).expect("set param failed");
+
+    // 2 + 2 establishes a point of 4; the round's later sub-rolls come from the slot hash.
+    // Reset moves the table, so the simulator starts from the table as it stood before.
+    let position_address = craps_position_pda(player.pubkey()).0;
+    let mut position = harness.account::<CrapsPosition>(position_address);
+    let mut game = harness.account::<CrapsGame>(craps_game_pda().0);
+    harness.roll(7);
+    let round = harness.account::<Round>(round_pda(0).0);
+
+    let mut expected = 0;
+    for index in 0..round.num_rolls() {
+        let square = round.sub_roll_square(index).unwrap();
+        let roll = simulator::Roll::from_square(square).unwrap();
+        let outcome = simulator::settle(&position, &game, roll).unwrap();
+        expected += outcome.winnings;
+        position = outcome.position;
+        game = outcome.game;
+    }
+
+    harness
+        .send(&[ore_api::sdk::settle_craps(player.pubkey(), 0, 7)], &[&player])
+        .expect("settle failed");
+    let settled = harness.account::<CrapsPosition>(position_address);
+    let settled_game = harness.account::<CrapsGame>(craps_game_pda().0);
+    assert_eq!(settled.pending_winnings, expected);
+    assert_eq!(settled.pass_line, position.pass_line);
+    assert_eq!(settled.field_bet, position.field_bet);
+    assert_eq!(settled.place_bets, position.place_bets);
+    assert_eq!(settled.hardways, position.hardways);
+    assert_eq!(settled.epoch_id, position.epoch_id);
+    assert_eq!(settled_game.point, game.point);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_every_sub_roll_can_be_injected() {
+    let mut harness = Harness::new();
+    let player = harness.player(0, 1_000 * ONE_CRAP);
+    let amount = 100 * ONE_CRAP;
+    harness
+        .send(
+            &[ore_api::sdk::place_craps_bet(player.pubkey(), CrapsBetType::PassLine, 0, amount)],
+            &[&player],
+        )
+        .expect("place bet failed");
+
+    // A round has a single roll until the admin raises it. 2 + 2 establishes a point of 4.
+    harness.roll(7);
+    let round = harness.account::<Round>(round_pda(0).0);
+    assert_eq!(round.num_rolls(), 1);
+    harness
+        .send(&[ore_api::sdk::settle_craps(player.pubkey(), 0, 7)], &[&player])
+        .expect("settle failed");
+
+    let admin = harness.admin.insecure_clone();
+    let set_rolls = ore_api::sdk::set_param(
+        admin.pubkey(),
+        PARAM_ROLLS_PER_ROUND,
+        MAX_ROLLS_PER_ROUND as u64,
+    );
+    harness.send(&[set_rolls], &[&admin]).expect("set param failed");
+    let too_many = ore_api::sdk::set_param(
+        admin.pubkey(),
+        PARAM_ROLLS_PER_ROUND,
+        MAX_ROLLS_PER_ROUND as u64 + 1,
+    );
+    assert!(harness.send(&[too_many], &[&admin]).is_err());
+
+    // 1 + 1, 3 + 3 and 5 + 5 leave the point standing, then 1 + 3 makes it.
+    let squares = [0, 14, 28, 2];
+    harness.roll_sequence(&squares);
+    let round = harness.account::<Round>(round_pda(1).0);
+    assert_eq!(round.num_rolls(), MAX_ROLLS_PER_ROUND);
+    for (index, &square) in squares.iter().enumerate() {
+        assert_eq!(round.sub_roll_square(index as u8), Some(square as usize));
+    }
+
+    harness
+        .send(&[ore_api::sdk::settle_craps(player.pubkey(), 1, 0)], &[&player])
+        .expect("settle failed");
+    let position = harness.account::<CrapsPosition>(craps_position_pda(player.pubkey()).0);
+    assert_eq!(position.pass_line, 0);
+    assert_eq!(position.pending_winnings, 2 * amount);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_positions_settle_against_the_same_rolls() {
+    let mut harness = Harness::new();
+    let shooter = harness.player(0, 1_000 * ONE_CRAP);
+    let fader = harness.player(0, 1_000 * ONE_CRAP);
+    let amount = 100 * ONE_CRAP;
+    harness
+        .send(
+            &[ore_api::sdk::place_craps_bet(shooter.pubkey(), CrapsBetType::PassLine, 0, amount)],
+            &[&shooter],
+        )
+        .expect("place bet failed");
+    harness
+        .send(
+            &[ore_api::sdk::place_craps_bet(fader.pubkey(), CrapsBetType::DontPass, 0, amount)],
+            &[&fader],
+        )
+        .expect("place bet failed");
+
+    // 2 + 2 establishes a point of 4 at reset. Settling doesn't move the table again.
+    harness.roll(7);
+    let game = harness.account::<CrapsGame>(craps_game_pda().0);
+    for player in [&shooter, &fader] {
+        harness
+            .send(&[ore_api::sdk::settle_craps(player.pubkey(), 0, 7)], &[player])
+            .expect("settle failed");
+        let position = harness.account::<CrapsPosition>(craps_position_pda(player.pubkey()).0);
+        assert_eq!(position.phase_id, game.phase_id);
+        assert_eq!(position.epoch_id, game.epoch_id);
+    }
+    let settled_game = harness.account::<CrapsGame>(craps_game_pda().0);
+    assert_eq!(settled_game.point, game.point);
+    assert_eq!(settled_game.epoch_id, game.epoch_id);
+    assert_eq!(settled_game.phase_id, game.phase_id);
+
+    // Both saw the same rolls, so the line bets stand or resolve together.
+    let shooter_position = harness.account::<CrapsPosition>(craps_position_pda(shooter.pubkey()).0);
+    let fader_position = harness.account::<CrapsPosition>(craps_position_pda(fader.pubkey()).0);
+    assert_eq!(shooter_position.pass_line, fader_position.dont_pass);
+
+    // Each roll settles once.
+    assert!(harness
+        .send(&[ore_api::sdk::settle_craps(shooter.pubkey(), 0, 7)], &[&shooter])
+        .is_err());
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_hedged_bundle_reserves_worst_case() {
+    let mut harness = Harness::new();
+    let player = harness.player(0, 1_000 * ONE_CRAP);
+    let pass = 100 * ONE_CRAP;
+    let any_craps = 10 * ONE_CRAP;
+
+    harness
+        .send(
+            &[ore_api::sdk::place_craps_bundle(
+                player.pubkey(),
+                &[(CrapsBetType::PassLine, 0, pass), (CrapsBetType::AnyCraps, 0, any_craps)],
+            )],
+            &[&player],
+        )
+        .expect("place bundle failed");
+
+    // Pass and Any Craps can't both win: the worst case is Pass returning 2x, not
+    // that plus Any Craps returning 8x.
+    let position_address = craps_position_pda(player.pubkey()).0;
+    let position = harness.account::<CrapsPosition>(position_address);
+    let game = harness.account::<CrapsGame>(craps_game_pda().0);
+    assert_eq!(game.reserved_payouts, 2 * pass);
+    assert_eq!(position.reserved_payouts, 2 * pass);
+    assert_eq!(position.get_bundle_credit(), 8 * any_craps);
+
+    // 3 + 4: Pass wins and Any Craps loses, releasing everything.
+    harness.roll(15);
+    harness
+        .send(&[ore_api::sdk::settle_craps(player.pubkey(), 0, 15)], &[&player])
+        .expect("settle failed");
+    let position = harness.account::<CrapsPosition>(position_address);
+    let game = harness.account::<CrapsGame>(craps_game_pda().0);
+    assert_eq!(position.pending_winnings, 2 * pass);
+    assert_eq!(position.reserved_payouts, 0);
+    assert_eq!(position.get_bundle_credit(), 0);
+    assert_eq!(game.reserved_payouts, 0);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_hedged_bundle_covers_the_whole_round() {
+    let mut harness = Harness::new();
+    let player = harness.player(0, 1_000 * ONE_CRAP);
+    let dont_pass = 100 * ONE_CRAP;
+    let yo = 10 * ONE_CRAP;
+
+    harness
+        .send(
+            &[ore_api::sdk::place_craps_bundle(
+                player.pubkey(),
+                &[(CrapsBetType::DontPass, 0, dont_pass), (CrapsBetType::YoEleven, 0, yo)],
+            )],
+            &[&player],
+        )
+        .expect("place bundle failed");
+
+    // An 11 pays Yo 16x and takes the Don't Pass; anything else leaves Yo with nothing.
+    let position_address = craps_position_pda(player.pubkey()).0;
+    let position = harness.account::<CrapsPosition>(position_address);
+    let game = harness.account::<CrapsGame>(craps_game_pda().0);
+    assert_eq!(game.reserved_payouts, 2 * dont_pass);
+    assert_eq!(position.get_bundle_credit(), 16 * yo);
+
+    // 2 + 2 sets a point of 4 and Yo loses. The Don't Pass rides the round's later
+    // sub-rolls at its own reservation.
+    harness.roll(7);
+    harness
+        .send(&[ore_api::sdk::settle_craps(player.pubkey(), 0, 7)], &[&player])
+        .expect("settle failed");
+    let position = harness.account::<CrapsPosition>(position_address);
+    let game = harness.account::<CrapsGame>(craps_game_pda().0);
+    assert_eq!(position.yo_eleven, 0);
+    assert_eq!(position.get_bundle_credit(), 0);
+    assert_eq!(position.reserved_payouts, 2 * position.dont_pass);
+    assert_eq!(game.reserved_payouts, position.reserved_payouts);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_deploy_with_sol_deploys_swap_output() {
+    let mut harness = Harness::new();
+    let admin = harness.admin.insecure_clone();
+    harness
+        .send(&[ore_api::sdk::set_swap_program(admin.pubkey(), spl_token::ID)], &[&admin])
+        .expect("set_swap_program failed");
+    let signer_rng = harness.set_token_account(admin.pubkey(), RNG_MINT_ADDRESS, 0);
+    harness.set_token_account(round_pda(0).0, RNG_MINT_ADDRESS, 0);
+
+    // The token program stands in for the router: the admin holds the RNG mint
+    // authority, so the "swap" mints 3 RNG to the signer without spending any SOL.
+    let swap = spl_token::instruction::mint_to(
+        &spl_token::ID,
+        &RNG_MINT_ADDRESS,
+        &signer_rng,
+        &admin.pubkey(),
+        &[],
+        3 * ONE_RNG + 1,
+    )
+    .unwrap();
+    let mut squares = [false; BOARD_SIZE];
+    squares[..3].fill(true);
+    let ix = ore_api::sdk::deploy_with_sol(
+        admin.pubkey(),
+        0,
+        spl_token::ID,
+        LAMPORTS_PER_SOL,
+        3 * ONE_RNG,
+        squares,
+        0,
+        &swap.accounts,
+        &swap.data,
+    );
+    harness.send(&[ix], &[&admin]).expect("deploy with SOL failed");
+
+    // The remainder goes to the lowest square.
+    let miner = harness.account::<Miner>(miner_pda(admin.pubkey()).0);
+    assert_eq!(miner.deployed[..4], [ONE_RNG + 1, ONE_RNG, ONE_RNG, 0]);
+    assert_eq!(harness.token_balance(admin.pubkey(), RNG_MINT_ADDRESS), 0);
+    assert_eq!(harness.token_balance(round_pda(0).0, RNG_MINT_ADDRESS), 3 * ONE_RNG + 1);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_house_profit_distribution_once_per_epoch() {
+    let mut harness = Harness::new();
+    let admin = harness.admin.insecure_clone();
+    let treasury = treasury_pda().0;
+    harness
+        .send(&[ore_api::sdk::set_swap_program(admin.pubkey(), spl_token::ID)], &[&admin])
+        .expect("set_swap_program failed");
+
+    // A staker to distribute to.
+    let staker = harness.player(0, 0);
+    harness.set_token_account(staker.pubkey(), MINT_ADDRESS, 100 * ONE_ORE);
+    harness
+        .send(&[ore_api::sdk::deposit(staker.pubkey(), staker.pubkey(), 100 * ONE_ORE)], &[&staker])
+        .expect("deposit failed");
+
+    // Share 10% of wins from here on, then book 10,000 CRAP of net wins and end the epoch.
+    harness
+        .send(&[ore_api::sdk::set_house_profit_share(admin.pubkey(), 1000)], &[&admin])
+        .expect("set_house_profit_share failed");
+    let game_address = craps_game_pda().0;
+    let mut game = harness.account::<CrapsGame>(game_address);
+    game.total_collected += 10_000 * ONE_CRAP;
+    game.epoch_id += 1;
+    harness.set_program_account(game_address, ore_api::ID, &game);
+
+    // The token program stands in for the router: the "swap" moves the treasury's CRAP
+    // to the market and returns nothing.
+    let share = 1_000 * ONE_CRAP;
+    let treasury_crap = harness.set_token_account(treasury, CRAP_MINT_ADDRESS, 0);
+    let market = harness.set_token_account(admin.pubkey(), CRAP_MINT_ADDRESS, 0);
+    let swap = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &treasury_crap,
+        &market,
+        &treasury,
+        &[],
+        share,
+    )
+    .unwrap();
+    let ix = ore_api::sdk::distribute_house_profit(
+        admin.pubkey(),
+        spl_token::ID,
+        0,
+        &swap.accounts,
+        &swap.data,
+    );
+    harness.send(&[ix.clone()], &[&admin]).expect("distribution failed");
+
+    let game = harness.account::<CrapsGame>(game_address);
+    assert_eq!(game.house_bankroll, common::HOUSE_BANKROLL - share);
+    assert_eq!(game.total_distributed_to_stakers, share);
+    assert_eq!(game.distributed_net_wins, 10_000 * ONE_CRAP);
+    assert_eq!(
+        harness.token_balance(craps_vault_pda().0, CRAP_MINT_ADDRESS),
+        common::HOUSE_BANKROLL - share
+    );
+    assert_eq!(harness.token_balance(admin.pubkey(), CRAP_MINT_ADDRESS), share);
+    assert_eq!(harness.token_balance(treasury, CRAP_MINT_ADDRESS), 0);
+
+    // Nothing more is owed this epoch.
+    harness.send(&[ix], &[&admin]).expect("repeat distribution failed");
+    let game = harness.account::<CrapsGame>(game_address);
+    assert_eq!(game.house_bankroll, common::HOUSE_BANKROLL - share);
+    assert_eq!(harness.token_balance(admin.pubkey(), CRAP_MINT_ADDRESS), share);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_session_delegate_claims_sol_to_authority() {
+    let mut harness = Harness::new();
+    let authority = harness.player(0, 0);
+    let delegate = harness.player(0, 0);
+
+    // A miner holding 1 SOL of rewards.
+    let miner_address = miner_pda(authority.pubkey()).0;
+    let mut miner = Miner::zeroed();
+    miner.authority = authority.pubkey();
+    miner.rewards_sol = LAMPORTS_PER_SOL;
+    harness.set_program_account(miner_address, ore_api::ID, &miner);
+    let mut account = harness.svm.get_account(&miner_address).unwrap();
+    account.lamports += LAMPORTS_PER_SOL;
+    harness.svm.set_account(miner_address, account).unwrap();
+
+    // Sessions can't grant operations they don't enforce.
+    let retired = ore_api::sdk::create_session(
+        authority.pubkey(),
+        authority.pubkey(),
+        delegate.pubkey(),
+        3600,
+        1,
+        0,
+    );
+    assert!(harness.send(&[retired], &[&authority]).is_err());
+
+    // A session without the Claims permission can't claim.
+    let mining = 1 << SessionOperation::Mining as u8;
+    harness
+        .send(
+            &[ore_api::sdk::create_session(
+                authority.pubkey(),
+                authority.pubkey(),
+                delegate.pubkey(),
+                3600,
+                mining,
+                0,
+            )],
+            &[&authority],
+        )
+        .expect("create_session failed");
+    let claim = ore_api::sdk::claim_sol_with_session(delegate.pubkey(), authority.pubkey());
+    assert!(harness.send(&[claim.clone()], &[&delegate]).is_err());
+
+    // With it, the delegate claims and the SOL goes to the authority.
+    let claims = 1 << SessionOperation::Claims as u8;
+    harness
+        .send(
+            &[ore_api::sdk::create_session(
+                authority.pubkey(),
+                authority.pubkey(),
+                delegate.pubkey(),
+                3600,
+                claims,
+                0,
+            )],
+            &[&authority],
+        )
+        .expect("renew session failed");
+    let authority_before = harness.svm.get_balance(&authority.pubkey()).unwrap();
+    let delegate_before = harness.svm.get_balance(&delegate.pubkey()).unwrap();
+    harness.send(&[claim], &[&delegate]).expect("delegated claim failed");
+    assert_eq!(
+        harness.svm.get_balance(&authority.pubkey()).unwrap(),
+        authority_before + LAMPORTS_PER_SOL
+    );
+    assert!(harness.svm.get_balance(&delegate.pubkey()).unwrap() < delegate_before);
+    assert_eq!(harness.account::<Miner>(miner_address).rewards_sol, 0);
+
+    // Once revoked, the delegate is locked out again.
+    harness
+        .send(
+            &[ore_api::sdk::revoke_session(authority.pubkey(), authority.pubkey())],
+            &[&authority],
+        )
+        .expect("revoke_session failed");
+    let claim = ore_api::sdk::claim_sol_with_session(delegate.pubkey(), authority.pubkey());
+    assert!(harness.send(&[claim], &[&delegate]).is_err());
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_session_delegate_manages_stake_yield() {
+    let mut harness = Harness::new();
+    let authority = harness.player(0, 0);
+    let delegate = harness.player(0, 0);
+
+    // A stake holding 1 ORE of yield, backed by the treasury.
+    let stake_address = stake_pda(authority.pubkey()).0;
+    let mut stake = Stake::zeroed();
+    stake.authority = authority.pubkey();
+    stake.rewards = ONE_ORE;
+    harness.set_program_account(stake_address, ore_api::ID, &stake);
+    harness.set_token_account(stake_address, MINT_ADDRESS, 0);
+    harness.set_token_account(TREASURY_ADDRESS, MINT_ADDRESS, ONE_ORE);
+    harness.set_token_account(authority.pubkey(), MINT_ADDRESS, 0);
+
+    // A session without the StakingYield permission can't touch the yield.
+    let claims = 1 << SessionOperation::Claims as u8;
+    harness
+        .send(
+            &[ore_api::sdk::create_session(
+                authority.pubkey(),
+                authority.pubkey(),
+                delegate.pubkey(),
+                3600,
+                claims,
+                0,
+            )],
+            &[&authority],
+        )
+        .expect("create_session failed");
+    let claim =
+        ore_api::sdk::claim_yield_with_session(delegate.pubkey(), authority.pubkey(), ONE_ORE / 2);
+    assert!(harness.send(&[claim.clone()], &[&delegate]).is_err());
+
+    // With it, the delegate claims to the authority and restakes the rest.
+    let staking_yield = 1 << SessionOperation::StakingYield as u8;
+    harness
+        .send(
+            &[ore_api::sdk::create_session(
+                authority.pubkey(),
+                authority.pubkey(),
+                delegate.pubkey(),
+                3600,
+                staking_yield,
+                0,
+            )],
+            &[&authority],
+        )
+        .expect("renew session failed");
+    harness.send(&[claim], &[&delegate]).expect("delegated claim failed");
+    assert_eq!(harness.token_balance(authority.pubkey(), MINT_ADDRESS), ONE_ORE / 2);
+
+    let restake = ore_api::sdk::claim_yield_and_restake_with_session(
+        delegate.pubkey(),
+        authority.pubkey(),
+        ONE_ORE / 2,
+    );
+    harness.send(&[restake], &[&delegate]).expect("delegated restake failed");
+    let stake = harness.account::<Stake>(stake_address);
+    assert_eq!(stake.rewards, 0);
+    assert_eq!(stake.balance, ONE_ORE / 2);
+
+    // The delegate can't claim without passing the session.
+    let claim = ore_api::sdk::claim_yield(delegate.pubkey(), authority.pubkey(), 1);
+    assert!(harness.send(&[claim], &[&delegate]).is_err());
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_session_delegate_deploys_within_session_budget() {
+    let mut harness = Harness::new();
+    let authority = harness.player(0, 0);
+    let executor = harness.player(0, 0);
+    harness.set_token_account(round_pda(0).0, RNG_MINT_ADDRESS, 0);
+
+    // A Delegated automation capped at 1_000_000 per square, and a Mining session
+    // whose deploy budget covers two and a half squares.
+    let per_square = 1_000_000;
+    harness
+        .send(
+            &[ore_api::sdk::automate(
+                authority.pubkey(),
+                per_square,
+                10 * per_square,
+                executor.pubkey(),
+                0,
+                0,
+                AutomationStrategy::Delegated as u8,
+                0,
+                10 * per_square,
+            )],
+            &[&authority],
+        )
+        .expect("automate failed");
+    let mining = 1 << SessionOperation::Mining as u8;
+    harness
+        .send(
+            &[ore_api::sdk::create_session(
+                authority.pubkey(),
+                authority.pubkey(),
+                executor.pubkey(),
+                3600,
+                mining,
+                5 * per_square / 2,
+            )],
+            &[&authority],
+        )
+        .expect("create_session failed");
+
+    // The executor can't deploy without passing the session, or deploy nothing.
+    let mut squares = [false; BOARD_SIZE];
+    squares[..4].fill(true);
+    let deploy = ore_api::sdk::deploy(
+        executor.pubkey(),
+        authority.pubkey(),
+        per_square,
+        0,
+        squares,
+        0,
+    );
+    assert!(harness.send(&[deploy], &[&executor]).is_err());
+    let deploy =
+        ore_api::sdk::deploy_with_session(executor.pubkey(), authority.pubkey(), 0, 0, squares);
+    assert!(harness.send(&[deploy], &[&executor]).is_err());
+
+    // With it, the deploy stops where the session budget runs out.
+    let deploy = ore_api::sdk::deploy_with_session(
+        executor.pubkey(),
+        authority.pubkey(),
+        per_square,
+        0,
+        squares,
+    );
+    harness.send(&[deploy], &[&executor]).expect("delegated deploy failed");
+    let miner = harness.account::<Miner>(miner_pda(authority.pubkey()).0);
+    assert_eq!(miner.deployed[..4], [per_square, per_square, 0, 0]);
+    let session = harness.account::<Session>(session_pda(authority.pubkey()).0);
+    assert_eq!(session.deploy_budget, per_square / 2);
+
+    // What is left can't cover another square.
+    let mut squares = [false; BOARD_SIZE];
+    squares[4] = true;
+    let deploy = ore_api::sdk::deploy_with_session(
+        executor.pubkey(),
+        authority.pubkey(),
+        per_square,
+        0,
+        squares,
+    );
+    harness.send(&[deploy], &[&executor]).expect("delegated deploy failed");
+    let miner = harness.account::<Miner>(miner_pda(authority.pubkey()).0);
+    assert_eq!(miner.deployed[4], 0);
+    let session = harness.account::<Session>(session_pda(authority.pubkey()).0);
+    assert_eq!(session.deploy_budget, per_square / 2);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_pool_member_recovers_deployed_principal_as_rewards() {
+    let mut harness = Harness::new();
+    let operator = harness.player(0, 0);
+    let member = harness.player(1_000_000, 0);
+    let pool_address = pool_pda(operator.pubkey()).0;
+    harness
+        .send(&[ore_api::sdk::open_pool(operator.pubkey())], &[&operator])
+        .expect("open_pool failed");
+    harness
+        .send(&[ore_api::sdk::join_pool(member.pubkey(), pool_address, 1_000_000)], &[&member])
+        .expect("join_pool failed");
+
+    // The pool deploys 400k RNG alone on square 7.
+    let deploy = ore_api::sdk::pool_deploy(operator.pubkey(), 0, &[(7, 400_000)], 0);
+    harness.send(&[deploy], &[&operator]).expect("pool_deploy failed");
+    assert_eq!(harness.account::<Pool>(pool_address).balance, 600_000);
+
+    // Rounds settle deployments in lamports, so back the round with the deployment.
+    let round_address = round_pda(0).0;
+    let mut account = harness.svm.get_account(&round_address).unwrap();
+    account.lamports += 400_000;
+    harness.svm.set_account(round_address, account).unwrap();
+
+    // The pool wins and its principal comes back, less the 1% fee, with nothing else.
+    harness.roll(7);
+    harness
+        .send(&[ore_api::sdk::checkpoint(operator.pubkey(), pool_address, 0)], &[&operator])
+        .expect("checkpoint failed");
+    harness
+        .send(&[ore_api::sdk::harvest_pool(operator.pubkey(), pool_address)], &[&operator])
+        .expect("harvest_pool failed");
+    let pool = harness.account::<Pool>(pool_address);
+    assert_eq!(pool.pending_harvest, 0);
+    assert_eq!(pool.balance, 600_000);
+    assert_eq!(pool.lifetime_rewards_sol, 396_000);
+
+    // Leaving returns the undeployed RNG; the principal stays claimable as rewards.
+    harness
+        .send(&[ore_api::sdk::leave_pool(member.pubkey(), pool_address, u64::MAX)], &[&member])
+        .expect("leave_pool failed");
+    assert_eq!(harness.token_balance(member.pubkey(), RNG_MINT_ADDRESS), 600_000);
+    let member_address = pool_member_pda(pool_address, member.pubkey()).0;
+    let membership = harness.account::<PoolMember>(member_address);
+    assert_eq!(membership.shares, 0);
+    assert_eq!(membership.rewards_sol, 396_000);
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_mining_pause_blocks_deploys_and_checkpoints() {
+    let mut harness = Harness::new();
+    let admin = harness.admin.insecure_clone();
+    let player = harness.player(10 * ONE_RNG, 0);
+    harness
+        .send(&[ore_api::sdk::pause(admin.pubkey(), PAUSE_MINING)], &[&admin])
+        .expect("pause failed");
+
+    let key = player.pubkey();
+    let mut squares = [false; BOARD_SIZE];
+    squares[0] = true;
+    let gated = [
+        ore_api::sdk::deploy(key, key, ONE_RNG, 0, squares, 0),
+        ore_api::sdk::deploy_many(key, 0, &[(0, ONE_RNG)], 0),
+        ore_api::sdk::deploy_spread(key, 0, 6 * ONE_RNG, DeployPreset::AllSevens, 0),
+        ore_api::sdk::deploy_with_sol(key, 0, spl_token::ID, 1, 0, squares, 0, &[], &[]),
+        ore_api::sdk::pool_deploy(key, 0, &[(0, ONE_RNG)], 0),
+        ore_api::sdk::checkpoint(key, key, 0),
+    ];
+    for ix in gated {
+        harness.assert_paused(ix, &[&player]);
+    }
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_staking_pause_blocks_yield_receipts_and_rng_stakes() {
+    let mut harness = Harness::new();
+    let admin = harness.admin.insecure_clone();
+    let player = harness.player(10 * ONE_RNG, 0);
+    harness
+        .send(&[ore_api::sdk::pause(admin.pubkey(), PAUSE_STAKING)], &[&admin])
+        .expect("pause failed");
+
+    let key = player.pubkey();
+    let gated = [
+        ore_api::sdk::claim_yield(key, key, 1),
+        ore_api::sdk::claim_yield_and_restake(key, key, 1),
+        ore_api::sdk::claim_yield_as_sol(key, 1, 0, spl_token::ID, &[], &[]),
+        ore_api::sdk::deposit_rng(key, ONE_RNG),
+        ore_api::sdk::withdraw_rng(key, ONE_RNG),
+        ore_api::sdk::mint_stake_receipt(key, 1),
+        ore_api::sdk::redeem_stake_receipt(key, key, 1),
+        ore_api::sdk::claim_withdrawal(key),
+    ];
+    for ix in gated {
+        harness.assert_paused(ix, &[&player]);
+    }
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_exchange_pause_blocks_routed_swaps() {
+    let mut harness = Harness::new();
+    let admin = harness.admin.insecure_clone();
+    let player = harness.player(0, 0);
+    harness
+        .send(&[ore_api::sdk::pause(admin.pubkey(), PAUSE_EXCHANGE)], &[&admin])
+        .expect("pause failed");
+
+    // The admin is the bury authority, so only the pause stops its route.
+    let route = ore_api::sdk::route_swap(
+        admin.pubkey(),
+        MINT_ADDRESS,
+        RNG_MINT_ADDRESS,
+        spl_token::ID,
+        1,
+        0,
+        &[],
+        &[],
+    );
+    harness.assert_paused(route, &[&admin]);
+
+    let key = player.pubkey();
+    let mut squares = [false; BOARD_SIZE];
+    squares[0] = true;
+    let gated = [
+        ore_api::sdk::deploy_with_sol(key, 0, spl_token::ID, 1, 0, squares, 0, &[], &[]),
+        ore_api::sdk::claim_yield_as_sol(key, 1, 0, spl_token::ID, &[], &[]),
+    ];
+    for ix in gated {
+        harness.assert_paused(ix, &[&player]);
+    }
+}
+
+#[test]
+#[ignore = "requires target/deploy/ore.so; run scripts/test-integration.sh"]
+fn test_claiming_pause_blocks_dust_sweeps() {
+    let mut harness = Harness::new();
+    let admin = harness.admin.insecure_clone();
+    let player = harness.player(0, 0);
+    harness
+        .send(&[ore_api::sdk::pause(admin.pubkey(), PAUSE_CLAIMING)], &[&admin])
+        .expect("pause failed");
+
+    let sweep = ore_api::sdk::sweep_dust(player.pubkey(), &[player.pubkey()]);
+    harness.assert_paused(sweep, &[&player]);
+}
@@ -0,0 +1,13 @@
+#!/bin/bash
+# Checks that the API builds for wasm32 frontends without the instruction builders
+# and their SPL dependencies.
+
+set -e
+
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+PROJECT_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+
+cd "$PROJECT_ROOT"
+
+rustup target add wasm32-unknown-unknown
+cargo check -p ore-api --target wasm32-unknown-unknown --no-default-features --features wasm
@@ -0,0 +1,26 @@
+#!/bin/bash
+#
+# Generate the program IDL from the Shank annotations in the api crate.
+#
+# Instruction accounts and args are described in api/src/idl.rs, and account
+# layouts by the ShankAccount derives in api/src/state. Requires shank-cli:
+#   cargo install shank-cli
+
+set -e
+
+# Get script directory
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+PROJECT_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+
+cd "$PROJECT_ROOT"
+
+PROGRAM_ID="JDcrnBXPW4o1G7bQgPHZZGtUPMFDLrosvqhTTHRWxXzK"
+
+echo "Generating IDL from Shank annotations..."
+
+mkdir -p idl
+
+shank idl --crate-root api --out-dir idl --out-filename ore.json --program-id "$PROGRAM_ID"
+
+echo ""
+echo "IDL written to idl/ore.json"
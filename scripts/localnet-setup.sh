@@ -28,7 +28,7 @@ check_dependencies() {
 build_program() {
     log_info "Building program..."
     cd "$PROJECT_ROOT"
-    cargo build-sbf --manifest-path program/Cargo.toml
+    cargo build-sbf --manifest-path program/Cargo.toml --features localnet
     if [ ! -f "$PROGRAM_SO" ]; then
         log_error "Program build failed - $PROGRAM_SO not found"
         exit 1
@@ -89,9 +89,10 @@ initialize_program() {
         cargo build --release -p ore-cli
     fi
 
-    # Initialize the board/config (if CLI supports it)
-    COMMAND=initialize RPC="http://127.0.0.1:8899" KEYPAIR="$KEYPAIR" "$CLI_PATH" || {
-        log_warn "Initialize command may not exist or already initialized"
+    # Initialize the program and fund the craps table in one transaction.
+    # The keypair must be the CRAP mint authority.
+    COMMAND=bootstrap RPC="http://127.0.0.1:8899" KEYPAIR="$KEYPAIR" "$CLI_PATH" || {
+        log_warn "Bootstrap failed; is the keypair the CRAP mint authority?"
     }
 
     log_info "Program initialization complete"
@@ -0,0 +1,23 @@
+#!/bin/bash
+# Builds the program with the test roll oracle and runs the LiteSVM integration and
+# compute-unit tests.
+
+set -e
+
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+PROJECT_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+ENTROPY_PROGRAM_ID="3jSkUuYBoJzQPMEzTvkDFXCZUBksPamrVhrnHR9igu2X"
+ENTROPY_SO="$PROJECT_ROOT/program/tests/fixtures/entropy.so"
+
+cd "$PROJECT_ROOT"
+
+echo "Building program with localnet features..."
+cargo build-sbf --manifest-path program/Cargo.toml --features localnet
+
+if [ ! -f "$ENTROPY_SO" ]; then
+    echo "Fetching entropy program from mainnet..."
+    mkdir -p "$(dirname "$ENTROPY_SO")"
+    solana program dump -u m "$ENTROPY_PROGRAM_ID" "$ENTROPY_SO"
+fi
+
+cargo test -p ore-program --test flows --test exchange --test compute_units -- --ignored "$@"